@@ -0,0 +1,641 @@
+//! `boon bench file.bn` and `boon bench diff`.
+//!
+//! A report is the JSON written by `boon bench --json` (or another bench runner): the
+//! environment it ran in and one timing summary per scenario. Two reports from
+//! different commits are compared scenario by scenario on the median, which is less
+//! noisy than the mean on shared CI machines.
+
+use boon::engine_v2::event_loop::EventLoop;
+use boon::evaluator_v2::CompileContext;
+use boon::platform::cli::clock::TestClock;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::{append_path, diagnostics, settle};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BenchReport {
+    pub environment: BenchEnvironment,
+    pub scenarios: Vec<ScenarioResult>,
+}
+
+/// Where a report was measured; printed by `bench diff` so numbers from
+/// different machines are not compared by accident.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct BenchEnvironment {
+    pub cpu: String,
+    pub commit: String,
+    pub engine_features: Vec<String>,
+}
+
+/// Timing summary of one scenario (e.g. `todo_mvc/tick`), in nanoseconds.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScenarioResult {
+    pub name: String,
+    pub iterations: usize,
+    pub mean_ns: f64,
+    pub median_ns: f64,
+    pub p95_ns: f64,
+}
+
+impl BenchEnvironment {
+    /// The machine and checkout this process runs on. `BOON_COMMIT` overrides the
+    /// commit for CI checkouts without `.git`.
+    pub fn current() -> Self {
+        let cpu = fs::read_to_string("/proc/cpuinfo")
+            .ok()
+            .and_then(|cpuinfo| {
+                cpuinfo
+                    .lines()
+                    .find_map(|line| line.strip_prefix("model name"))
+                    .and_then(|line| line.split_once(':'))
+                    .map(|(_, name)| name.trim().to_string())
+            })
+            .unwrap_or_else(|| std::env::consts::ARCH.to_string());
+        let commit = std::env::var("BOON_COMMIT")
+            .ok()
+            .or_else(|| {
+                std::process::Command::new("git")
+                    .args(["rev-parse", "--short", "HEAD"])
+                    .output()
+                    .ok()
+                    .filter(|output| output.status.success())
+                    .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+            })
+            .unwrap_or_else(|| "unknown".to_string());
+        let build = if cfg!(debug_assertions) {
+            "debug"
+        } else {
+            "release"
+        };
+        Self {
+            cpu,
+            commit,
+            engine_features: vec!["engine_v2".to_string(), build.to_string()],
+        }
+    }
+}
+
+impl ScenarioResult {
+    /// Mean, median and p95 (nearest rank) of `samples`.
+    pub fn from_samples(name: impl Into<String>, samples: &[Duration]) -> Self {
+        let mut nanos = samples
+            .iter()
+            .map(|sample| sample.as_secs_f64() * 1e9)
+            .collect::<Vec<_>>();
+        nanos.sort_by(f64::total_cmp);
+        let count = nanos.len();
+        let median_ns = if count == 0 {
+            0.0
+        } else if count.is_multiple_of(2) {
+            (nanos[count / 2 - 1] + nanos[count / 2]) / 2.0
+        } else {
+            nanos[count / 2]
+        };
+        let p95_index = (count * 95).div_ceil(100).saturating_sub(1);
+        Self {
+            name: name.into(),
+            iterations: count,
+            mean_ns: if count == 0 {
+                0.0
+            } else {
+                nanos.iter().sum::<f64>() / f64::from(u32::try_from(count).unwrap_or(u32::MAX))
+            },
+            median_ns,
+            p95_ns: nanos.get(p95_index).copied().unwrap_or(0.0),
+        }
+    }
+}
+
+pub fn read_report(path: &Path) -> Result<BenchReport, String> {
+    let json =
+        fs::read_to_string(path).map_err(|e| format!("Error reading {}: {}", path.display(), e))?;
+    serde_json::from_str(&json)
+        .map_err(|e| format!("Invalid bench report {}: {}", path.display(), e))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum DiffFormat {
+    Text,
+    Markdown,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ScenarioChange {
+    /// Present in both reports; `percent` is positive when the candidate is slower.
+    Compared {
+        baseline_ns: f64,
+        candidate_ns: f64,
+        percent: f64,
+    },
+    Added,
+    Removed,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScenarioDiff {
+    pub name: String,
+    pub change: ScenarioChange,
+}
+
+impl ScenarioDiff {
+    /// Slowdown in percent, if the scenario ran in both reports.
+    pub fn percent(&self) -> Option<f64> {
+        match self.change {
+            ScenarioChange::Compared { percent, .. } => Some(percent),
+            ScenarioChange::Added | ScenarioChange::Removed => None,
+        }
+    }
+
+    pub fn regressed(&self, threshold: f64) -> bool {
+        self.percent().is_some_and(|percent| percent > threshold)
+    }
+}
+
+/// Pairs scenarios by name: baseline order first, then scenarios only the candidate has.
+pub fn diff_reports(baseline: &BenchReport, candidate: &BenchReport) -> Vec<ScenarioDiff> {
+    let mut diffs = baseline
+        .scenarios
+        .iter()
+        .map(|base| {
+            let change = match candidate
+                .scenarios
+                .iter()
+                .find(|scenario| scenario.name == base.name)
+            {
+                Some(new) => ScenarioChange::Compared {
+                    baseline_ns: base.median_ns,
+                    candidate_ns: new.median_ns,
+                    percent: percent_change(base.median_ns, new.median_ns),
+                },
+                None => ScenarioChange::Removed,
+            };
+            ScenarioDiff {
+                name: base.name.clone(),
+                change,
+            }
+        })
+        .collect::<Vec<_>>();
+    diffs.extend(
+        candidate
+            .scenarios
+            .iter()
+            .filter(|new| !baseline.scenarios.iter().any(|base| base.name == new.name))
+            .map(|new| ScenarioDiff {
+                name: new.name.clone(),
+                change: ScenarioChange::Added,
+            }),
+    );
+    diffs
+}
+
+fn percent_change(baseline_ns: f64, candidate_ns: f64) -> f64 {
+    if baseline_ns == 0.0 {
+        if candidate_ns == 0.0 {
+            0.0
+        } else {
+            f64::INFINITY
+        }
+    } else {
+        (candidate_ns - baseline_ns) / baseline_ns * 100.0
+    }
+}
+
+fn format_duration(ns: f64) -> String {
+    if ns >= 1_000_000.0 {
+        format!("{:.2} ms", ns / 1_000_000.0)
+    } else if ns >= 1_000.0 {
+        format!("{:.2} µs", ns / 1_000.0)
+    } else {
+        format!("{ns:.0} ns")
+    }
+}
+
+fn diff_row(diff: &ScenarioDiff, threshold: f64) -> [String; 5] {
+    match &diff.change {
+        ScenarioChange::Compared {
+            baseline_ns,
+            candidate_ns,
+            percent,
+        } => [
+            diff.name.clone(),
+            format_duration(*baseline_ns),
+            format_duration(*candidate_ns),
+            format!("{percent:+.1}%"),
+            if diff.regressed(threshold) {
+                "REGRESSION".to_string()
+            } else if *percent < -threshold {
+                "improved".to_string()
+            } else {
+                String::new()
+            },
+        ],
+        ScenarioChange::Added => [
+            diff.name.clone(),
+            "-".to_string(),
+            String::new(),
+            String::new(),
+            "added".to_string(),
+        ],
+        ScenarioChange::Removed => [
+            diff.name.clone(),
+            String::new(),
+            "-".to_string(),
+            String::new(),
+            "removed".to_string(),
+        ],
+    }
+}
+
+/// Renders the comparison table; regressions are rows slower than `threshold` percent.
+pub fn render_diff(
+    baseline: &BenchReport,
+    candidate: &BenchReport,
+    diffs: &[ScenarioDiff],
+    threshold: f64,
+    format: DiffFormat,
+) -> String {
+    let header = ["scenario", "baseline", "candidate", "change", ""].map(str::to_string);
+    let rows = diffs
+        .iter()
+        .map(|diff| diff_row(diff, threshold))
+        .collect::<Vec<_>>();
+    let mut out = String::new();
+    let environments = [
+        ("baseline", &baseline.environment),
+        ("candidate", &candidate.environment),
+    ];
+    match format {
+        DiffFormat::Text => {
+            for (label, environment) in environments {
+                let _ = writeln!(
+                    out,
+                    "{label}: {} on {} [{}]",
+                    environment.commit,
+                    environment.cpu,
+                    environment.engine_features.join(", ")
+                );
+            }
+            out.push('\n');
+            let widths = (0..header.len())
+                .map(|column| {
+                    std::iter::once(&header)
+                        .chain(&rows)
+                        .map(|row| row[column].chars().count())
+                        .max()
+                        .unwrap_or(0)
+                })
+                .collect::<Vec<_>>();
+            for row in std::iter::once(&header).chain(&rows) {
+                let line = row
+                    .iter()
+                    .zip(&widths)
+                    .map(|(cell, width)| format!("{cell:<width$}"))
+                    .collect::<Vec<_>>()
+                    .join("  ");
+                let _ = writeln!(out, "{}", line.trim_end());
+            }
+        }
+        DiffFormat::Markdown => {
+            for (label, environment) in environments {
+                let _ = writeln!(
+                    out,
+                    "- **{label}**: `{}` on {} ({})",
+                    environment.commit,
+                    environment.cpu,
+                    environment.engine_features.join(", ")
+                );
+            }
+            out.push('\n');
+            let _ = writeln!(out, "| {} |", header.join(" | "));
+            let _ = writeln!(out, "|---|---:|---:|---:|---|");
+            for row in &rows {
+                let mut row = row.clone();
+                if row[4] == "REGRESSION" {
+                    row[4] = "**REGRESSION**".to_string();
+                }
+                let _ = writeln!(out, "| {} |", row.join(" | "));
+            }
+        }
+    }
+    out
+}
+
+/// `boon bench diff`: prints the table and exits non-zero when a scenario
+/// regressed by more than `fail_threshold` percent.
+pub fn run_diff(
+    baseline_path: &Path,
+    candidate_path: &Path,
+    threshold: f64,
+    fail_threshold: Option<f64>,
+    format: DiffFormat,
+) {
+    let (baseline, candidate) = match (read_report(baseline_path), read_report(candidate_path)) {
+        (Ok(baseline), Ok(candidate)) => (baseline, candidate),
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    let diffs = diff_reports(&baseline, &candidate);
+    print!(
+        "{}",
+        render_diff(&baseline, &candidate, &diffs, threshold, format)
+    );
+
+    if let Some(fail_threshold) = fail_threshold {
+        let failing = diffs
+            .iter()
+            .filter(|diff| diff.regressed(fail_threshold))
+            .map(|diff| diff.name.as_str())
+            .collect::<Vec<_>>();
+        if !failing.is_empty() {
+            eprintln!(
+                "\n{} scenario(s) regressed by more than {}%: {}",
+                failing.len(),
+                fail_threshold,
+                failing.join(", ")
+            );
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Aligned table of the phases of one `boon bench` run.
+pub fn render_report(report: &BenchReport) -> String {
+    let header = ["scenario", "iterations", "mean", "median", "p95"].map(str::to_string);
+    let rows = report
+        .scenarios
+        .iter()
+        .map(|scenario| {
+            [
+                scenario.name.clone(),
+                scenario.iterations.to_string(),
+                format_duration(scenario.mean_ns),
+                format_duration(scenario.median_ns),
+                format_duration(scenario.p95_ns),
+            ]
+        })
+        .collect::<Vec<_>>();
+    let widths = (0..header.len())
+        .map(|column| {
+            std::iter::once(&header)
+                .chain(&rows)
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{} on {} [{}]\n",
+        report.environment.commit,
+        report.environment.cpu,
+        report.environment.engine_features.join(", ")
+    );
+    for row in std::iter::once(&header).chain(&rows) {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        let _ = writeln!(out, "{}", line.trim_end());
+    }
+    out
+}
+
+fn compile(expressions: &[boon::parser::Spanned<boon::parser::Expression<'_>>]) -> EventLoop {
+    let mut event_loop = EventLoop::new();
+    let mut ctx = CompileContext::new(&mut event_loop);
+    ctx.compile_program(expressions);
+    event_loop
+}
+
+/// Measures `code` over `iterations` runs of each phase:
+/// - `name/parse`: lexing and parsing
+/// - `name/compile`: `compile_program` into a fresh event loop
+/// - `name/tick`: one tick of the program after its initial evaluation
+///
+/// `injects` are `(path, payload)` pairs from `--inject`. Their paths are checked
+/// against the program, then the run is refused before anything is measured: the
+/// engine cannot send events by path yet, and a report without the `name/inject`
+/// scenario would pass for one that measured it.
+pub fn bench_program(
+    name: &str,
+    code: &str,
+    iterations: usize,
+    injects: &[(String, String)],
+    max_ticks: u64,
+) -> Result<BenchReport, String> {
+    let expressions = diagnostics::parse_with_diagnostics(code).map_err(|errors| {
+        errors
+            .iter()
+            .map(|error| format!("{}:{}: {}", error.line, error.column, error.message))
+            .collect::<Vec<_>>()
+            .join("\n")
+    })?;
+    for (path, payload) in injects {
+        append_path(code, path).map_err(|e| format!("--inject {} {}: {}", path, payload, e))?;
+    }
+    if !injects.is_empty() {
+        return Err(
+            "`--inject` is not supported yet: the engine cannot send events by path".to_string(),
+        );
+    }
+
+    let mut parse = Vec::with_capacity(iterations);
+    let mut compile_times = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let started = Instant::now();
+        std::hint::black_box(diagnostics::parse_with_diagnostics(code)).ok();
+        parse.push(started.elapsed());
+
+        let started = Instant::now();
+        let event_loop = compile(&expressions);
+        compile_times.push(started.elapsed());
+        drop(event_loop);
+    }
+
+    let mut event_loop = compile(&expressions);
+    let all_slots: Vec<_> = (0..event_loop.arena_len())
+        .filter_map(|idx| {
+            let slot = boon::engine_v2::arena::SlotId {
+                index: u32::try_from(idx).ok()?,
+                generation: 0,
+            };
+            event_loop.is_valid(slot).then_some(slot)
+        })
+        .collect();
+    for slot in all_slots {
+        event_loop.mark_dirty(slot, boon::engine_v2::address::Port::Output);
+    }
+    let mut clock = TestClock::new();
+    settle(&mut event_loop, &mut clock, max_ticks);
+
+    let mut ticks = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let started = Instant::now();
+        event_loop.run_tick();
+        ticks.push(started.elapsed());
+    }
+
+    Ok(BenchReport {
+        environment: BenchEnvironment::current(),
+        scenarios: vec![
+            ScenarioResult::from_samples(format!("{name}/parse"), &parse),
+            ScenarioResult::from_samples(format!("{name}/compile"), &compile_times),
+            ScenarioResult::from_samples(format!("{name}/tick"), &ticks),
+        ],
+    })
+}
+
+/// `boon bench file.bn`: prints the table, or the report as JSON for `bench diff`.
+pub fn run_bench(
+    name: &str,
+    code: &str,
+    iterations: usize,
+    injects: &[(String, String)],
+    json: bool,
+) {
+    let report = match bench_program(name, code, iterations, injects, 100) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    if json {
+        match serde_json::to_string_pretty(&report) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("Failed to serialize bench report: {}", e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        print!("{}", render_report(&report));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(commit: &str, scenarios: &[(&str, f64)]) -> BenchReport {
+        BenchReport {
+            environment: BenchEnvironment {
+                cpu: "test cpu".to_string(),
+                commit: commit.to_string(),
+                engine_features: vec!["engine_v2".to_string()],
+            },
+            scenarios: scenarios
+                .iter()
+                .map(|(name, median_ns)| ScenarioResult {
+                    name: name.to_string(),
+                    iterations: 100,
+                    mean_ns: *median_ns,
+                    median_ns: *median_ns,
+                    p95_ns: *median_ns,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn regressions_are_measured_against_the_baseline_median() {
+        let baseline = report(
+            "aaaa",
+            &[
+                ("counter/tick", 1_000.0),
+                ("todo_mvc/tick", 2_000.0),
+                ("old", 10.0),
+            ],
+        );
+        let candidate = report(
+            "bbbb",
+            &[
+                ("counter/tick", 1_250.0),
+                ("todo_mvc/tick", 1_500.0),
+                ("new", 10.0),
+            ],
+        );
+        let diffs = diff_reports(&baseline, &candidate);
+
+        let percents = diffs.iter().map(ScenarioDiff::percent).collect::<Vec<_>>();
+        assert_eq!(percents, vec![Some(25.0), Some(-25.0), None, None]);
+        assert_eq!(diffs[2].change, ScenarioChange::Removed);
+        assert_eq!(diffs[3].change, ScenarioChange::Added);
+
+        assert!(diffs[0].regressed(10.0));
+        assert!(!diffs[0].regressed(25.0));
+        assert!(!diffs[1].regressed(10.0));
+        assert!(!diffs[3].regressed(0.0));
+    }
+
+    #[test]
+    fn samples_summarize_to_mean_median_and_p95() {
+        let samples = (1..=20).map(Duration::from_micros).collect::<Vec<_>>();
+        let result = ScenarioResult::from_samples("counter/tick", &samples);
+        assert_eq!(result.iterations, 20);
+        assert!((result.mean_ns - 10_500.0).abs() < 1e-6);
+        assert!((result.median_ns - 10_500.0).abs() < 1e-6);
+        assert!((result.p95_ns - 19_000.0).abs() < 1e-6);
+
+        let single = ScenarioResult::from_samples("one", &[Duration::from_nanos(7)]);
+        assert_eq!((single.median_ns, single.p95_ns), (7.0, 7.0));
+        assert_eq!(ScenarioResult::from_samples("none", &[]).mean_ns, 0.0);
+    }
+
+    #[test]
+    fn markdown_table_flags_regressions() {
+        let baseline = report("aaaa", &[("counter/tick", 1_000.0)]);
+        let candidate = report("bbbb", &[("counter/tick", 1_250.0)]);
+        let diffs = diff_reports(&baseline, &candidate);
+        let table = render_diff(&baseline, &candidate, &diffs, 5.0, DiffFormat::Markdown);
+        assert!(table.contains("| counter/tick | 1.00 µs | 1.25 µs | +25.0% | **REGRESSION** |"));
+        assert!(table.contains("- **baseline**: `aaaa` on test cpu (engine_v2)"));
+    }
+
+    #[test]
+    fn injects_are_checked_then_refused_before_measuring() {
+        let code = "increment_button: LINK\ncount: 0";
+        let inject = |path: &str| vec![(path.to_string(), "Unit".to_string())];
+
+        let error = bench_program(
+            "counter",
+            code,
+            1,
+            &inject("decrement_button.event.press"),
+            100,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error,
+            "--inject decrement_button.event.press Unit: `decrement_button` is not a top-level variable"
+        );
+        let error = bench_program(
+            "counter",
+            code,
+            1,
+            &inject("increment_button.event.press"),
+            100,
+        )
+        .unwrap_err();
+        assert!(error.starts_with("`--inject` is not supported yet"));
+    }
+
+    #[test]
+    fn reports_round_trip_through_json() {
+        let original = report("aaaa", &[("counter/parse", 12_345.0)]);
+        let json = serde_json::to_string(&original).unwrap();
+        assert_eq!(
+            serde_json::from_str::<BenchReport>(&json).unwrap(),
+            original
+        );
+    }
+}
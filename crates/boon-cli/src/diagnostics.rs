@@ -0,0 +1,347 @@
+//! Diagnostics of `boon check`: JSON for editors (`--json`), or the offending line
+//! with a caret under the span for people.
+
+use boon::parser::builtins::unknown_builtin_calls;
+use boon::parser::unused_variables::unused_variables;
+use boon::parser::{
+    Expression, Parser, Spanned, lexer, parser, reset_expression_depth, resolve_references, span_at,
+};
+use serde::Serialize;
+use std::fmt::Write as _;
+use std::ops::Range;
+use std::path::Path;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+        }
+    }
+}
+
+/// One problem in a source file. `start`/`end` are byte offsets; `line` and `column`
+/// (1-based, column in characters) point at `start`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Diagnostic {
+    pub message: String,
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+    pub severity: Severity,
+}
+
+impl Diagnostic {
+    pub fn new(
+        severity: Severity,
+        message: impl Into<String>,
+        range: Range<usize>,
+        source: &str,
+    ) -> Self {
+        let start = floor_char_boundary(source, range.start);
+        let end = floor_char_boundary(source, range.end).max(start);
+        let (line, column) = line_column(source, start);
+        Self {
+            message: message.into(),
+            start,
+            end,
+            line,
+            column,
+            severity,
+        }
+    }
+
+    /// `error: message`, the location and the source line with `^` under the span
+    /// (up to the end of the line for spans over several lines).
+    pub fn render(&self, source: &str, file: &Path) -> String {
+        let line_start = line_start(source, self.start);
+        let line_end = source[self.start..]
+            .find('\n')
+            .map_or(source.len(), |offset| self.start + offset);
+        let line_text = source[line_start..line_end].trim_end_matches('\r');
+        let caret_count = source[self.start..self.end.min(line_end)]
+            .chars()
+            .count()
+            .max(1);
+        let gutter = " ".repeat(self.line.to_string().len());
+
+        let mut out = String::new();
+        let _ = writeln!(out, "{}: {}", self.severity.as_str(), self.message);
+        let _ = writeln!(
+            out,
+            "{gutter}--> {}:{}:{}",
+            file.display(),
+            self.line,
+            self.column
+        );
+        let _ = writeln!(out, "{gutter} |");
+        let _ = writeln!(out, "{} | {}", self.line, line_text);
+        let _ = writeln!(
+            out,
+            "{gutter} | {}{}",
+            " ".repeat(self.column - 1),
+            "^".repeat(caret_count)
+        );
+        out
+    }
+}
+
+fn floor_char_boundary(source: &str, index: usize) -> usize {
+    let mut index = index.min(source.len());
+    while !source.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+/// 1-based line and column (in characters) of the byte offset `index`.
+pub fn line_column(source: &str, index: usize) -> (usize, usize) {
+    let index = floor_char_boundary(source, index);
+    let line_start = line_start(source, index);
+    (
+        source[..index].matches('\n').count() + 1,
+        source[line_start..index].chars().count() + 1,
+    )
+}
+
+fn line_start(source: &str, index: usize) -> usize {
+    source[..index].rfind('\n').map_or(0, |newline| newline + 1)
+}
+
+/// Lexes and parses `code` like `boon check`; on failure returns one diagnostic per
+/// lexer error (the lexer recovers, so separate errors are all reported) or parser error.
+pub fn parse_with_diagnostics(code: &str) -> Result<Vec<Spanned<Expression<'_>>>, Vec<Diagnostic>> {
+    reset_expression_depth();
+
+    let (tokens, lex_errors) = lexer().parse(code).into_output_errors();
+    if !lex_errors.is_empty() {
+        return Err(lex_errors
+            .iter()
+            .map(|error| {
+                Diagnostic::new(
+                    Severity::Error,
+                    error.to_string(),
+                    error.span().start..error.span().end,
+                    code,
+                )
+            })
+            .collect());
+    }
+    let Some(mut tokens) = tokens else {
+        return Err(vec![Diagnostic::new(
+            Severity::Error,
+            "No tokens from lexer",
+            0..0,
+            code,
+        )]);
+    };
+    tokens.retain(|t| !matches!(t.node, boon::parser::Token::Comment(_)));
+
+    let input = tokens.map(
+        span_at(code.len()),
+        |Spanned {
+             node,
+             span,
+             persistence: _,
+         }| (node, span),
+    );
+    let (expressions, parse_errors) = parser().parse(input).into_output_errors();
+    if !parse_errors.is_empty() {
+        return Err(parse_errors
+            .iter()
+            .map(|error| {
+                Diagnostic::new(
+                    Severity::Error,
+                    error.to_string(),
+                    error.span().start..error.span().end,
+                    code,
+                )
+            })
+            .collect());
+    }
+    expressions.ok_or_else(|| {
+        vec![Diagnostic::new(
+            Severity::Error,
+            "No expressions from parser",
+            0..0,
+            code,
+        )]
+    })
+}
+
+/// One error per alias that resolves to no variable, argument or parameter in scope.
+pub fn reference_errors(expressions: Vec<Spanned<Expression<'_>>>, code: &str) -> Vec<Diagnostic> {
+    match resolve_references(expressions) {
+        Ok(_) => Vec::new(),
+        Err(errors) => errors
+            .iter()
+            .map(|error| {
+                Diagnostic::new(
+                    Severity::Error,
+                    error.to_string(),
+                    error.span().start..error.span().end,
+                    code,
+                )
+            })
+            .collect(),
+    }
+}
+
+/// One error per call of a function its builtin module does not have, with the
+/// nearest builtins as suggestions.
+pub fn unknown_function_errors(code: &str) -> Vec<Diagnostic> {
+    unknown_builtin_calls(code, |_| false)
+        .iter()
+        .map(|call| Diagnostic::new(Severity::Error, call.message(), call.range.clone(), code))
+        .collect()
+}
+
+/// Warnings for variables and parameters that are never read, pointing at their
+/// names; empty when `code` does not resolve.
+pub fn unused_variable_warnings(code: &str) -> Vec<Diagnostic> {
+    unused_variables(code)
+        .unwrap_or_default()
+        .iter()
+        .map(|variable| {
+            Diagnostic::new(
+                Severity::Warning,
+                variable.message(),
+                variable.range.clone(),
+                code,
+            )
+        })
+        .collect()
+}
+
+/// `--strict` for `boon eval` and `boon run`: the checks of `boon check` before
+/// evaluating. Syntax, unknown reference and unknown builtin function errors exit
+/// with 1, unused variables are printed as warnings.
+pub fn check_strict(code: &str, file: &Path) {
+    let errors = match parse_with_diagnostics(code) {
+        Ok(expressions) => {
+            let mut errors = reference_errors(expressions, code);
+            errors.extend(unknown_function_errors(code));
+            errors
+        }
+        Err(errors) => errors,
+    };
+    if !errors.is_empty() {
+        report(&errors, code, file, false);
+        std::process::exit(1);
+    }
+    report(&unused_variable_warnings(code), code, file, false);
+}
+
+/// Prints `diagnostics` as a JSON array on stdout, or rendered on stderr.
+pub fn report(diagnostics: &[Diagnostic], source: &str, file: &Path, json: bool) {
+    if json {
+        match serde_json::to_string_pretty(diagnostics) {
+            Ok(json) => println!("{}", json),
+            Err(e) => eprintln!("Failed to serialize diagnostics: {}", e),
+        }
+    } else {
+        for diagnostic in diagnostics {
+            eprintln!("{}", diagnostic.render(source, file));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn separate_syntax_errors_get_their_own_positions() {
+        let code = "first: 1 $\nsecond: 2 @\n";
+        let diagnostics = parse_with_diagnostics(code).unwrap_err();
+        let positions = diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.line, diagnostic.column, diagnostic.severity))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            positions,
+            vec![(1, 10, Severity::Error), (2, 11, Severity::Error)]
+        );
+    }
+
+    #[test]
+    fn typo_is_an_error_and_unused_variable_a_warning() {
+        let code = "counter: 0\nlabel: countr + 1\n";
+        let errors = reference_errors(parse_with_diagnostics(code).unwrap(), code);
+        assert_eq!(errors.len(), 1);
+        assert_eq!((errors[0].line, errors[0].column), (2, 8));
+        assert!(errors[0].message.contains("countr"));
+
+        let code = "step: 2\ncounter: 0\nresult: counter + 1\n";
+        let warnings = unused_variable_warnings(code);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].severity, Severity::Warning);
+        assert_eq!(&code[warnings[0].start..warnings[0].end], "step");
+    }
+
+    #[test]
+    fn misspelled_builtin_is_an_error_with_a_suggestion() {
+        let code = "items: LIST { 1 } |> List/appendd(item: 2)\n";
+        let errors = unknown_function_errors(code);
+        assert_eq!(errors.len(), 1);
+        assert_eq!((errors[0].line, errors[0].column), (1, 22));
+        assert_eq!(
+            errors[0].message,
+            "unknown builtin function `List/appendd`, did you mean `List/append`?"
+        );
+    }
+
+    #[test]
+    fn bundled_examples_call_only_known_builtins() {
+        let mut directories = vec![
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("../../playground/frontend/src/examples"),
+        ];
+        let mut checked = 0;
+        while let Some(directory) = directories.pop() {
+            for entry in std::fs::read_dir(&directory).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    directories.push(path);
+                } else if path.extension().is_some_and(|extension| extension == "bn") {
+                    let code = std::fs::read_to_string(&path).unwrap();
+                    let errors = unknown_function_errors(&code);
+                    assert!(
+                        errors.is_empty(),
+                        "{}: {}",
+                        path.display(),
+                        errors[0].message
+                    );
+                    checked += 1;
+                }
+            }
+        }
+        assert!(checked > 0);
+    }
+
+    #[test]
+    fn caret_underlines_the_span_on_its_line() {
+        let source = "counter: 0\nlabel: TEXT { café } |> Oops\n";
+        let start = source.find("Oops").unwrap();
+        let diagnostic = Diagnostic::new(Severity::Warning, "unknown", start..start + 4, source);
+        assert_eq!((diagnostic.line, diagnostic.column), (2, 25));
+        assert_eq!(
+            diagnostic.render(source, Path::new("main.bn")),
+            "warning: unknown\n \
+             --> main.bn:2:25\n  \
+             |\n\
+             2 | label: TEXT { café } |> Oops\n  \
+             |                         ^^^^\n"
+        );
+        let json = serde_json::to_value(&diagnostic).unwrap();
+        assert_eq!(json["severity"], "warning");
+        assert_eq!(json["start"], start);
+    }
+}
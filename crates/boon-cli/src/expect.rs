@@ -0,0 +1,130 @@
+//! `boon eval --expect` / `--expect-contains`: assert on the result for CI scripts.
+
+use serde_json::Value;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExpectMode {
+    Exact,
+    /// Objects may have more fields and lists more items than expected
+    Contains,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Expectation {
+    pub expected: Value,
+    pub mode: ExpectMode,
+}
+
+impl Expectation {
+    /// Reads `--expect` or `--expect-contains` (clap makes them exclusive).
+    pub fn from_args(
+        expect: Option<&str>,
+        expect_contains: Option<&str>,
+    ) -> Result<Option<Self>, String> {
+        let (json, mode) = match (expect, expect_contains) {
+            (Some(json), _) => (json, ExpectMode::Exact),
+            (None, Some(json)) => (json, ExpectMode::Contains),
+            (None, None) => return Ok(None),
+        };
+        let expected = serde_json::from_str(json)
+            .map_err(|e| format!("Invalid expected JSON `{}`: {}", json, e))?;
+        Ok(Some(Self { expected, mode }))
+    }
+
+    pub fn matches(&self, actual: &Value) -> bool {
+        json_matches(&self.expected, actual, self.mode)
+    }
+}
+
+/// Compares like `==`, except that numbers compare by value (`42` matches the
+/// engine's `42.0`) and, with [`ExpectMode::Contains`], expected objects match when
+/// their fields are a subset and expected lists when their items appear in order.
+pub fn json_matches(expected: &Value, actual: &Value, mode: ExpectMode) -> bool {
+    match (expected, actual) {
+        (Value::Number(expected), Value::Number(actual)) => expected.as_f64() == actual.as_f64(),
+        (Value::Object(expected), Value::Object(actual)) => {
+            (mode == ExpectMode::Contains || expected.len() == actual.len())
+                && expected.iter().all(|(key, expected)| {
+                    actual
+                        .get(key)
+                        .is_some_and(|actual| json_matches(expected, actual, mode))
+                })
+        }
+        (Value::Array(expected), Value::Array(actual)) => match mode {
+            ExpectMode::Exact => {
+                expected.len() == actual.len()
+                    && expected
+                        .iter()
+                        .zip(actual)
+                        .all(|(expected, actual)| json_matches(expected, actual, mode))
+            }
+            ExpectMode::Contains => {
+                let mut actual = actual.iter();
+                expected
+                    .iter()
+                    .all(|expected| actual.any(|actual| json_matches(expected, actual, mode)))
+            }
+        },
+        _ => expected == actual,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn exact_match_compares_numbers_by_value() {
+        assert!(json_matches(&json!(42), &json!(42.0), ExpectMode::Exact));
+        assert!(json_matches(
+            &json!({ "count": 1, "items": ["a"] }),
+            &json!({ "count": 1.0, "items": ["a"] }),
+            ExpectMode::Exact
+        ));
+        assert!(!json_matches(
+            &json!({ "count": 1 }),
+            &json!({ "count": 1.0, "label": "x" }),
+            ExpectMode::Exact
+        ));
+        assert!(!json_matches(&json!("42"), &json!(42.0), ExpectMode::Exact));
+    }
+
+    #[test]
+    fn contains_matches_nested_subsets_in_order() {
+        let element = json!({
+            "tag": "Column",
+            "items": [
+                { "tag": "Label", "text": "Todos" },
+                { "tag": "Button", "label": "Add", "style": {} },
+                { "tag": "Label", "text": "3 left" }
+            ]
+        });
+        assert!(json_matches(
+            &json!({ "items": [{ "text": "Todos" }, { "text": "3 left" }] }),
+            &element,
+            ExpectMode::Contains
+        ));
+        assert!(!json_matches(
+            &json!({ "items": [{ "text": "3 left" }, { "text": "Todos" }] }),
+            &element,
+            ExpectMode::Contains
+        ));
+        assert!(!json_matches(
+            &json!({ "items": [{ "label": "Remove" }] }),
+            &element,
+            ExpectMode::Contains
+        ));
+    }
+
+    #[test]
+    fn expected_json_is_validated_up_front() {
+        assert_eq!(Expectation::from_args(None, None), Ok(None));
+        assert_eq!(
+            Expectation::from_args(None, Some("[1]"))
+                .map(|expectation| expectation.map(|e| e.mode)),
+            Ok(Some(ExpectMode::Contains))
+        );
+        assert!(Expectation::from_args(Some("{oops"), None).is_err());
+    }
+}
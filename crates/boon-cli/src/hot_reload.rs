@@ -0,0 +1,264 @@
+//! Hot reload for `boon run --watch`: which HOLD states survive an edit.
+//!
+//! Every build resolves persistence against the previous build, the same span
+//! matching the playground does through local storage, so a HOLD that is still in
+//! the same place in the program keeps its `PersistenceId`. A HOLD keeps its stored
+//! value only if its definition, the initial value piped into it and its body, reads
+//! the same as before (whitespace aside). Edits elsewhere in the program, such as a
+//! label next to a counter, leave the count alone; HOLDs whose definition changed
+//! start over from their initial value and are reported.
+
+use boon::parser::{
+    ArithmeticOperator, Comparator, Expression, PersistenceId, Span, Spanned,
+    resolve_persistence_with_pairs,
+};
+use std::collections::HashMap;
+
+/// A HOLD of a build, with what is needed to match it in the next one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HoldSite {
+    pub id: PersistenceId,
+    /// Path of the variable holding it, e.g. `store.counter`
+    pub name: String,
+    /// Source of `initial |> HOLD state { body }` with whitespace collapsed
+    definition: String,
+}
+
+/// What a build remembers for the next one.
+pub struct Resolution {
+    pub span_id_pairs: HashMap<Span, PersistenceId>,
+    pub holds: Vec<HoldSite>,
+}
+
+/// HOLDs of the new build sorted by whether their state can be carried over.
+#[derive(Debug, Default, PartialEq)]
+pub struct HoldChanges {
+    pub preserved: Vec<PersistenceId>,
+    /// Variable paths of HOLDs whose definition changed
+    pub reset: Vec<String>,
+}
+
+/// Sets persistence on `expressions` of `code`, matching them against the previous
+/// build's `old_expressions` and its `previous` resolution.
+pub fn resolve<'code>(
+    code: &str,
+    expressions: Vec<Spanned<Expression<'code>>>,
+    old_expressions: Option<Vec<Spanned<Expression<'_>>>>,
+    previous: Option<&Resolution>,
+) -> Result<(Vec<Spanned<Expression<'code>>>, Resolution), String> {
+    let empty = HashMap::new();
+    let old_span_id_pairs = previous.map_or(&empty, |previous| &previous.span_id_pairs);
+    let (expressions, span_id_pairs, _) =
+        resolve_persistence_with_pairs(expressions, old_expressions, old_span_id_pairs)
+            .map_err(|errors| format!("Persistence errors: {:?}", errors))?;
+    let mut holds = Vec::new();
+    for expression in &expressions {
+        collect_holds(code, expression, "", &mut holds);
+    }
+    Ok((
+        expressions,
+        Resolution {
+            span_id_pairs,
+            holds,
+        },
+    ))
+}
+
+/// Compares the HOLDs of two builds. HOLDs new in `current` are neither preserved
+/// nor reset.
+pub fn hold_changes(previous: &Resolution, current: &Resolution) -> HoldChanges {
+    let mut changes = HoldChanges::default();
+    for hold in &current.holds {
+        match previous.holds.iter().find(|old| old.id == hold.id) {
+            Some(old) if old.definition == hold.definition => changes.preserved.push(hold.id),
+            Some(_) => changes.reset.push(hold.name.clone()),
+            None => {}
+        }
+    }
+    changes
+}
+
+fn collect_holds(
+    code: &str,
+    expression: &Spanned<Expression>,
+    name: &str,
+    holds: &mut Vec<HoldSite>,
+) {
+    let definition_span = match &expression.node {
+        Expression::Pipe { to, .. } if matches!(to.node, Expression::Hold { .. }) => {
+            Some((expression.span, to.persistence.as_ref()))
+        }
+        Expression::Hold { .. } => Some((expression.span, expression.persistence.as_ref())),
+        _ => None,
+    };
+    if let Some((span, Some(persistence))) = definition_span
+        && !holds.iter().any(|hold| hold.id == persistence.id)
+    {
+        holds.push(HoldSite {
+            id: persistence.id,
+            name: name.to_owned(),
+            definition: code[span.start..span.end]
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" "),
+        });
+    }
+    for (child_name, child) in children(&expression.node) {
+        let path = match child_name {
+            Some(child_name) if name.is_empty() => child_name.to_owned(),
+            Some(child_name) => format!("{}.{}", name, child_name),
+            None => name.to_owned(),
+        };
+        collect_holds(code, child, &path, holds);
+    }
+}
+
+type Child<'a, 'code> = (Option<&'code str>, &'a Spanned<Expression<'code>>);
+
+/// Sub-expressions, with the variable name they are bound to if any.
+fn children<'a, 'code>(expression: &'a Expression<'code>) -> Vec<Child<'a, 'code>> {
+    let unnamed = |expressions: Vec<&'a Spanned<Expression<'code>>>| -> Vec<Child<'a, 'code>> {
+        expressions
+            .into_iter()
+            .map(|expression| (None, expression))
+            .collect()
+    };
+    match expression {
+        Expression::Variable(variable) => vec![(Some(variable.name), &variable.value)],
+        Expression::Object(object) | Expression::TaggedObject { object, .. } => object
+            .variables
+            .iter()
+            .map(|variable| (Some(variable.node.name), &variable.node.value))
+            .collect(),
+        Expression::Block { variables, output } => variables
+            .iter()
+            .map(|variable| (Some(variable.node.name), &variable.node.value))
+            .chain([(None, output.as_ref())])
+            .collect(),
+        Expression::List { items } => unnamed(items.iter().collect()),
+        Expression::Map { entries } => unnamed(entries.iter().map(|entry| &entry.value).collect()),
+        Expression::Function { body, .. }
+        | Expression::Hold { body, .. }
+        | Expression::Then { body } => unnamed(vec![body.as_ref()]),
+        Expression::FunctionCall { arguments, .. } => unnamed(
+            arguments
+                .iter()
+                .filter_map(|argument| argument.node.value.as_ref())
+                .collect(),
+        ),
+        Expression::Latest { inputs } => unnamed(inputs.iter().collect()),
+        Expression::Flush { value } | Expression::Spread { value } => unnamed(vec![value.as_ref()]),
+        Expression::When { arms } | Expression::While { arms } => {
+            unnamed(arms.iter().map(|arm| &arm.body).collect())
+        }
+        Expression::Pipe { from, to } => unnamed(vec![from.as_ref(), to.as_ref()]),
+        Expression::Comparator(
+            Comparator::Equal {
+                operand_a,
+                operand_b,
+            }
+            | Comparator::NotEqual {
+                operand_a,
+                operand_b,
+            }
+            | Comparator::Greater {
+                operand_a,
+                operand_b,
+            }
+            | Comparator::GreaterOrEqual {
+                operand_a,
+                operand_b,
+            }
+            | Comparator::Less {
+                operand_a,
+                operand_b,
+            }
+            | Comparator::LessOrEqual {
+                operand_a,
+                operand_b,
+            },
+        )
+        | Expression::ArithmeticOperator(
+            ArithmeticOperator::Add {
+                operand_a,
+                operand_b,
+            }
+            | ArithmeticOperator::Subtract {
+                operand_a,
+                operand_b,
+            }
+            | ArithmeticOperator::Multiply {
+                operand_a,
+                operand_b,
+            }
+            | ArithmeticOperator::Divide {
+                operand_a,
+                operand_b,
+            },
+        ) => unnamed(vec![operand_a.as_ref(), operand_b.as_ref()]),
+        Expression::ArithmeticOperator(ArithmeticOperator::Negate { operand }) => {
+            unnamed(vec![operand.as_ref()])
+        }
+        Expression::Bits { size } => unnamed(vec![size.as_ref()]),
+        Expression::Memory { address } => unnamed(vec![address.as_ref()]),
+        Expression::Bytes { data } => unnamed(data.iter().collect()),
+        Expression::PostfixFieldAccess { expr, .. } => unnamed(vec![expr.as_ref()]),
+        Expression::Literal(_)
+        | Expression::Alias(_)
+        | Expression::LinkSetter { .. }
+        | Expression::Link
+        | Expression::Skip
+        | Expression::TextLiteral { .. }
+        | Expression::FieldAccess { .. } => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::parse_with_diagnostics;
+
+    const COUNTER: &str = "\
+counter: 0 |> HOLD state {
+    increment_button.event.press |> THEN { state + 1 }
+}
+label: TEXT { Clicks }
+increment_button: LINK
+";
+
+    fn first_build() -> Resolution {
+        let expressions = parse_with_diagnostics(COUNTER).expect("parses");
+        resolve(COUNTER, expressions, None, None)
+            .expect("resolves")
+            .1
+    }
+
+    fn rebuild(previous: &Resolution, code: &str) -> HoldChanges {
+        let old_expressions = parse_with_diagnostics(COUNTER).expect("parses");
+        let expressions = parse_with_diagnostics(code).expect("parses");
+        let (_, current) =
+            resolve(code, expressions, Some(old_expressions), Some(previous)).expect("resolves");
+        hold_changes(previous, &current)
+    }
+
+    #[test]
+    fn editing_a_label_keeps_the_count() {
+        let previous = first_build();
+        let changes = rebuild(&previous, &COUNTER.replace("Clicks", "Presses"));
+        assert_eq!(changes.preserved, vec![previous.holds[0].id]);
+        assert!(changes.reset.is_empty());
+    }
+
+    #[test]
+    fn editing_a_hold_resets_and_reports_it() {
+        let previous = first_build();
+        let changes = rebuild(&previous, &COUNTER.replace("state + 1", "state + 2"));
+        assert_eq!(
+            changes,
+            HoldChanges {
+                preserved: Vec::new(),
+                reset: vec!["counter".to_owned()],
+            }
+        );
+    }
+}
@@ -0,0 +1,324 @@
+//! Static HTML for `boon serve`, rendered from the JSON view of a `Document`.
+//!
+//! Elements arrive as tagged objects expanded by `expand_payload_to_json`, e.g.
+//! `{"tag": "ElementButton", "element": {..}, "settings": {"label": .., "style": ..}}`,
+//! the same structures the browser bridge consumes. Settings may also sit directly
+//! on the object. Only layout and text are rendered: sizes, padding, gaps and font
+//! sizes map to inline CSS, everything visual beyond that (colors, materials,
+//! transforms) is left to the playground. Unknown element tags become an empty
+//! `<div data-tag="..">` so the rest of the tree still renders.
+
+use serde_json::{Map, Value};
+use std::fmt::Write as _;
+
+/// Escapes text for HTML content and attribute values.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Text of a scalar value; numbers drop a zero fraction (`3.0` reads `3`).
+fn text_of(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Number(_) => number_text(value).unwrap_or_default(),
+        Value::Bool(flag) => flag.to_string(),
+        // A `TEXT` placeholder or label given as an object, e.g. `[text: TEXT { .. }]`
+        Value::Object(object) => object.get("text").map(text_of).unwrap_or_default(),
+        Value::Null | Value::Array(_) => String::new(),
+    }
+}
+
+/// A number without a zero fraction, as CSS and text want it (`15.0` is `15`).
+fn number_text(value: &Value) -> Option<String> {
+    value.as_f64().map(|number| number.to_string())
+}
+
+/// Inline CSS for the subset of `style` the static renderer understands.
+fn style_css(style: Option<&Value>) -> String {
+    let Some(Value::Object(style)) = style else {
+        return String::new();
+    };
+    let mut css = String::new();
+    for (property, name) in [("width", "width"), ("height", "height")] {
+        match style.get(property) {
+            Some(Value::String(size)) if size == "Fill" => {
+                let _ = write!(css, "{}:100%;", name);
+            }
+            Some(size) => {
+                if let Some(size) = number_text(size) {
+                    let _ = write!(css, "{}:{}px;", name, size);
+                }
+            }
+            None => {}
+        }
+    }
+    if let Some(padding) = style.get("padding").and_then(number_text) {
+        let _ = write!(css, "padding:{}px;", padding);
+    }
+    if let Some(size) = style
+        .get("font")
+        .and_then(|font| font.get("size"))
+        .and_then(number_text)
+    {
+        let _ = write!(css, "font-size:{}px;", size);
+    }
+    css
+}
+
+fn style_attribute(css: &str) -> String {
+    if css.is_empty() {
+        String::new()
+    } else {
+        format!(" style=\"{}\"", escape(css))
+    }
+}
+
+/// Renders one element (or a list of them, or plain text) into `out`.
+fn render(value: &Value, out: &mut String) {
+    let object = match value {
+        Value::Array(items) => {
+            items.iter().for_each(|item| render(item, out));
+            return;
+        }
+        Value::Object(object) => object,
+        scalar => {
+            out.push_str(&escape(&text_of(scalar)));
+            return;
+        }
+    };
+    let Some(tag) = object.get("tag").and_then(Value::as_str) else {
+        // `Document/new` result or another plain object wrapping the root
+        if let Some(root) = object.get("root_element").or(object.get("root")) {
+            render(root, out);
+        }
+        return;
+    };
+    let settings = match object.get("settings") {
+        Some(Value::Object(settings)) => settings,
+        _ => object,
+    };
+    if settings.get("visible") == Some(&Value::Bool(false)) {
+        return;
+    }
+    render_element(tag, settings, out);
+}
+
+fn render_element(tag: &str, settings: &Map<String, Value>, out: &mut String) {
+    let field = |name: &str| settings.get(name).unwrap_or(&Value::Null);
+    let text = |name: &str| escape(&text_of(field(name)));
+    let mut css = style_css(settings.get("style"));
+    let children = |out: &mut String, name: &str| render(field(name), out);
+
+    match tag {
+        "ElementContainer" | "ElementBlock" => {
+            let _ = write!(out, "<div{}>", style_attribute(&css));
+            children(out, "child");
+            out.push_str("</div>");
+        }
+        "ElementStripe" => {
+            let direction = match field("direction").as_str() {
+                Some("Row") => "row",
+                _ => "column",
+            };
+            css.insert_str(0, &format!("display:flex;flex-direction:{};", direction));
+            if let Some(gap) = number_text(field("gap")) {
+                let _ = write!(css, "gap:{}px;", gap);
+            }
+            let _ = write!(out, "<div{}>", style_attribute(&css));
+            children(out, "items");
+            out.push_str("</div>");
+        }
+        "ElementStack" => {
+            css.insert_str(0, "display:grid;");
+            let _ = write!(out, "<div{}>", style_attribute(&css));
+            if let Value::Array(layers) = field("layers") {
+                for layer in layers {
+                    out.push_str("<div style=\"grid-area:1/1\">");
+                    render(layer, out);
+                    out.push_str("</div>");
+                }
+            }
+            out.push_str("</div>");
+        }
+        "ElementButton" => {
+            let _ = write!(out, "<button{}>", style_attribute(&css));
+            children(out, "label");
+            out.push_str("</button>");
+        }
+        "ElementLabel" | "ElementText" => {
+            let _ = write!(out, "<span{}>", style_attribute(&css));
+            children(
+                out,
+                if tag == "ElementLabel" {
+                    "label"
+                } else {
+                    "text"
+                },
+            );
+            out.push_str("</span>");
+        }
+        "ElementParagraph" => {
+            let _ = write!(out, "<p{}>", style_attribute(&css));
+            children(out, "contents");
+            out.push_str("</p>");
+        }
+        "ElementLink" => {
+            let _ = write!(out, "<a href=\"{}\"{}>", text("to"), style_attribute(&css));
+            children(out, "label");
+            out.push_str("</a>");
+        }
+        "ElementTextInput" => {
+            let _ = write!(
+                out,
+                "<input type=\"text\" value=\"{}\" placeholder=\"{}\"{}>",
+                text("text"),
+                text("placeholder"),
+                style_attribute(&css)
+            );
+        }
+        "ElementCheckbox" => {
+            let checked = if field("checked") == &Value::Bool(true) {
+                " checked"
+            } else {
+                ""
+            };
+            let _ = write!(
+                out,
+                "<input type=\"checkbox\"{}{}>",
+                checked,
+                style_attribute(&css)
+            );
+        }
+        "ElementSlider" => {
+            let _ = write!(
+                out,
+                "<input type=\"range\" min=\"{}\" max=\"{}\" value=\"{}\"{}>",
+                text("min"),
+                text("max"),
+                text("value"),
+                style_attribute(&css)
+            );
+        }
+        "ElementSelect" => {
+            let selected = text_of(field("selected"));
+            let _ = write!(out, "<select{}>", style_attribute(&css));
+            if let Value::Array(options) = field("options") {
+                for option in options {
+                    let value = option.get("value").map_or_else(|| text_of(option), text_of);
+                    let label = option.get("label").map_or_else(|| value.clone(), text_of);
+                    let is_selected = if value == selected { " selected" } else { "" };
+                    let _ = write!(
+                        out,
+                        "<option value=\"{}\"{}>{}</option>",
+                        escape(&value),
+                        is_selected,
+                        escape(&label)
+                    );
+                }
+            }
+            out.push_str("</select>");
+        }
+        "ElementCode" => {
+            let _ = write!(
+                out,
+                "<pre{}><code class=\"language-{}\">{}</code></pre>",
+                style_attribute(&css),
+                text("language").to_lowercase(),
+                text("content")
+            );
+        }
+        unknown => {
+            let _ = write!(out, "<div data-tag=\"{}\"></div>", escape(unknown));
+        }
+    }
+}
+
+/// A complete page for the JSON view of a `document` value.
+pub fn render_page(title: &str, document: &Value) -> String {
+    let mut body = String::new();
+    render(document, &mut body);
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n{}\n</body>\n</html>\n",
+        escape(title),
+        body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn html(value: Value) -> String {
+        let mut out = String::new();
+        render(&value, &mut out);
+        out
+    }
+
+    #[test]
+    fn counter_document_renders_a_row_with_a_button() {
+        let document = json!({
+            "root_element": {
+                "tag": "ElementStripe",
+                "element": {},
+                "settings": {
+                    "direction": "Row",
+                    "gap": 15.0,
+                    "style": {},
+                    "items": [
+                        { "tag": "ElementLabel", "settings": { "style": {}, "label": 3.0 } },
+                        {
+                            "tag": "ElementButton",
+                            "element": { "event": { "press": null } },
+                            "settings": { "style": { "width": 45.0 }, "label": "+" }
+                        }
+                    ]
+                }
+            }
+        });
+        assert_eq!(
+            html(document),
+            "<div style=\"display:flex;flex-direction:row;gap:15px;\">\
+             <span>3</span>\
+             <button style=\"width:45px;\">+</button>\
+             </div>"
+        );
+    }
+
+    #[test]
+    fn text_is_escaped_and_hidden_elements_are_skipped() {
+        assert_eq!(
+            html(json!([
+                { "tag": "ElementText", "text": "<b>&</b>" },
+                { "tag": "ElementLabel", "visible": false, "label": "hidden" },
+                { "tag": "ElementLink", "to": "/a?b=\"c\"", "label": "go" }
+            ])),
+            "<span>&lt;b&gt;&amp;&lt;/b&gt;</span><a href=\"/a?b=&quot;c&quot;\">go</a>"
+        );
+    }
+
+    #[test]
+    fn inputs_show_their_current_values() {
+        assert_eq!(
+            html(json!([
+                { "tag": "ElementTextInput", "text": "Buy milk", "placeholder": { "text": "What needs to be done?" } },
+                { "tag": "ElementCheckbox", "checked": true },
+                { "tag": "ElementSvg", "children": [] }
+            ])),
+            "<input type=\"text\" value=\"Buy milk\" placeholder=\"What needs to be done?\">\
+             <input type=\"checkbox\" checked>\
+             <div data-tag=\"ElementSvg\"></div>"
+        );
+    }
+}
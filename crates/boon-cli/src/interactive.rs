@@ -0,0 +1,223 @@
+//! `boon run --interactive`: poke at a running program from stdin.
+//!
+//! ```text
+//! read
+//! read store.counter
+//! quit
+//! ```
+//!
+//! Timers keep firing between commands: the virtual clock follows wall-clock time
+//! since the session started. Every command prints one JSON line. `read <path>`
+//! compiles the program again with the path appended as its last expression, restores
+//! the running program's state into it and prints the new result, like `boon repl`
+//! does for its inputs. `inject` is recognised but answered with an error: the engine
+//! has no way to deliver an event payload by path.
+
+use boon::engine_v2::arena::SlotId;
+use boon::engine_v2::event_loop::EventLoop;
+use boon::evaluator_v2::CompileContext;
+use boon::platform::cli::clock::TestClock;
+use std::io::BufRead;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+use crate::{advance_time, append_path, diagnostics, mark_all_dirty, run_until_quiescent};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InteractiveCommand {
+    /// Print the current program result
+    Read,
+    /// Print the current value at a dotted path
+    ReadPath(String),
+    Quit,
+}
+
+/// Parses one input line; `Ok(None)` for blank lines and `--` comments.
+pub fn parse_command(line: &str) -> Result<Option<InteractiveCommand>, String> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with("--") {
+        return Ok(None);
+    }
+    let (command, arguments) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    let arguments = arguments.trim();
+
+    let command = match command {
+        "read" if arguments.is_empty() => InteractiveCommand::Read,
+        "read" => InteractiveCommand::ReadPath(arguments.to_string()),
+        "inject" => {
+            return Err(
+                "`inject` is not supported yet: the engine cannot send events by path".to_string(),
+            );
+        }
+        "quit" => InteractiveCommand::Quit,
+        other => {
+            return Err(format!(
+                "Unknown command `{}` (expected read or quit)",
+                other
+            ));
+        }
+    };
+    Ok(Some(command))
+}
+
+/// Current value at `path` in the program `code` running on `event_loop`.
+///
+/// Appending an expression leaves the spans of `code` as they are, so the state of
+/// `event_loop` restores into the new compilation. Values that are not held (a bare
+/// `Timer/interval`, say) start over there.
+fn read_path(
+    code: &str,
+    event_loop: &EventLoop,
+    path: &str,
+    max_ticks: u64,
+) -> Result<serde_json::Value, String> {
+    let code = append_path(code, path)?;
+    let expressions = diagnostics::parse_with_diagnostics(&code)
+        .map_err(|errors| format!("{} parse error(s)", errors.len()))?;
+    let mut reader = EventLoop::new();
+    let result_slot = CompileContext::new(&mut reader).compile_program(&expressions);
+    reader.restore_snapshot(&event_loop.create_snapshot());
+    mark_all_dirty(&mut reader);
+    run_until_quiescent(&mut reader, max_ticks);
+    Ok(result_slot
+        .and_then(|slot| reader.get_current_value(slot))
+        .map(|value| reader.expand_payload_to_json(value))
+        .unwrap_or(serde_json::Value::Null))
+}
+
+/// Runs commands from stdin against `event_loop`, compiled from `code`, until `quit`
+/// or end of input, firing the timers registered with `clock` as real time passes.
+pub fn run_interactive(
+    code: &str,
+    event_loop: &mut EventLoop,
+    clock: &mut TestClock,
+    result_slot: Option<SlotId>,
+    max_ticks: u64,
+) {
+    // Reading stdin blocks, so it gets its own thread; the loop below waits on the
+    // channel with a timeout of the next timer deadline.
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in std::io::stdin().lock().lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    let started = Instant::now();
+    eprintln!("Interactive: read, read <path>, quit");
+    loop {
+        let line = match clock.time_to_next_timer() {
+            Some(wait_ms) => match receiver.recv_timeout(Duration::from_millis(wait_ms)) {
+                Ok(line) => Some(line),
+                Err(RecvTimeoutError::Timeout) => None,
+                Err(RecvTimeoutError::Disconnected) => break,
+            },
+            None => match receiver.recv() {
+                Ok(line) => Some(line),
+                Err(_) => break,
+            },
+        };
+
+        // Catch up with the time spent waiting before running the command
+        let elapsed_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+        let behind_ms = elapsed_ms.saturating_sub(clock.now_ms());
+        if behind_ms > 0 {
+            advance_time(event_loop, clock, behind_ms, max_ticks);
+        }
+
+        let Some(line) = line else {
+            continue;
+        };
+        let output = match parse_command(&line) {
+            Ok(None) => continue,
+            Ok(Some(InteractiveCommand::Quit)) => break,
+            Ok(Some(InteractiveCommand::Read)) => serde_json::json!({
+                "status": "ok",
+                "ticks": event_loop.current_tick,
+                "result": result_slot
+                    .and_then(|slot| event_loop.get_current_value(slot))
+                    .map(|value| event_loop.expand_payload_to_json(value))
+                    .unwrap_or(serde_json::Value::Null)
+            }),
+            Ok(Some(InteractiveCommand::ReadPath(path))) => {
+                match read_path(code, event_loop, &path, max_ticks) {
+                    Ok(value) => serde_json::json!({
+                        "status": "ok",
+                        "ticks": event_loop.current_tick,
+                        "path": path,
+                        "result": value
+                    }),
+                    Err(e) => serde_json::json!({
+                        "status": "error",
+                        "error": format!("`read {}`: {}", path, e)
+                    }),
+                }
+            }
+            Err(e) => serde_json::json!({
+                "status": "error",
+                "error": e
+            }),
+        };
+        println!("{}", output);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_and_quit_are_the_commands() {
+        assert_eq!(parse_command("  read "), Ok(Some(InteractiveCommand::Read)));
+        assert_eq!(parse_command("quit"), Ok(Some(InteractiveCommand::Quit)));
+        assert_eq!(parse_command("-- wait for the timer"), Ok(None));
+        assert!(parse_command("press button").is_err());
+    }
+
+    #[test]
+    fn read_takes_an_optional_path() {
+        assert_eq!(
+            parse_command("read store.counter"),
+            Ok(Some(InteractiveCommand::ReadPath(
+                "store.counter".to_string()
+            )))
+        );
+        let error = parse_command("inject store.add_button.event.press Unit").unwrap_err();
+        assert!(error.starts_with("`inject` is not supported yet"));
+    }
+
+    #[test]
+    fn read_path_sees_the_held_state_of_the_running_program() {
+        let code = "\
+counter: 0 |> HOLD state {
+    Duration[milliseconds: 1000] |> Timer/interval() |> THEN { state + 1 }
+}
+store: [doubled: counter * 2]
+";
+        let expressions = diagnostics::parse_with_diagnostics(code).unwrap();
+        let mut event_loop = EventLoop::new();
+        CompileContext::new(&mut event_loop).compile_program(&expressions);
+        mark_all_dirty(&mut event_loop);
+        let mut clock = TestClock::new();
+        crate::settle(&mut event_loop, &mut clock, 100);
+        advance_time(&mut event_loop, &mut clock, 2000, 100);
+
+        assert_eq!(
+            read_path(code, &event_loop, "counter", 100),
+            Ok(serde_json::json!(2.0))
+        );
+        assert_eq!(
+            read_path(code, &event_loop, "store.doubled", 100),
+            Ok(serde_json::json!(4.0))
+        );
+        assert_eq!(
+            read_path(code, &event_loop, "total", 100),
+            Err("`total` is not a top-level variable".to_string())
+        );
+    }
+}
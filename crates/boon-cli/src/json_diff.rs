@@ -0,0 +1,219 @@
+//! Structural diff of an expected and an actual JSON value, for failing `boon test`
+//! expectations: only the paths that differ are printed, e.g.
+//! `result.todos[2].completed: expected true, actual false`.
+
+use serde_json::Value;
+use std::fmt::Write as _;
+
+/// Objects and arrays nested deeper than this are compared as a whole by default.
+pub const DEFAULT_DIFF_DEPTH: usize = 8;
+
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const RESET: &str = "\x1b[0m";
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Difference {
+    Changed {
+        path: String,
+        expected: Value,
+        actual: Value,
+    },
+    /// Object field or array item only in the expected value
+    Missing { path: String, expected: Value },
+    /// Object field or array item only in the actual value
+    Unexpected { path: String, actual: Value },
+}
+
+/// Differing paths below `root`: fields of the expected value first, then fields
+/// only the actual value has. Values nested deeper than `max_depth` levels are
+/// reported as one change.
+pub fn diff_json(
+    root: &str,
+    expected: &Value,
+    actual: &Value,
+    max_depth: usize,
+) -> Vec<Difference> {
+    let mut differences = Vec::new();
+    diff_into(
+        root.to_string(),
+        expected,
+        actual,
+        max_depth,
+        &mut differences,
+    );
+    differences
+}
+
+fn diff_into(
+    path: String,
+    expected: &Value,
+    actual: &Value,
+    depth_left: usize,
+    differences: &mut Vec<Difference>,
+) {
+    if expected == actual {
+        return;
+    }
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) if depth_left > 0 => {
+            for (key, expected) in expected {
+                let path = field_path(&path, key);
+                match actual.get(key) {
+                    Some(actual) => diff_into(path, expected, actual, depth_left - 1, differences),
+                    None => differences.push(Difference::Missing {
+                        path,
+                        expected: expected.clone(),
+                    }),
+                }
+            }
+            for (key, actual) in actual {
+                if !expected.contains_key(key) {
+                    differences.push(Difference::Unexpected {
+                        path: field_path(&path, key),
+                        actual: actual.clone(),
+                    });
+                }
+            }
+        }
+        (Value::Array(expected), Value::Array(actual)) if depth_left > 0 => {
+            for index in 0..expected.len().max(actual.len()) {
+                let path = format!("{}[{}]", path, index);
+                match (expected.get(index), actual.get(index)) {
+                    (Some(expected), Some(actual)) => {
+                        diff_into(path, expected, actual, depth_left - 1, differences)
+                    }
+                    (Some(expected), None) => differences.push(Difference::Missing {
+                        path,
+                        expected: expected.clone(),
+                    }),
+                    (None, Some(actual)) => differences.push(Difference::Unexpected {
+                        path,
+                        actual: actual.clone(),
+                    }),
+                    (None, None) => {}
+                }
+            }
+        }
+        _ => differences.push(Difference::Changed {
+            path,
+            expected: expected.clone(),
+            actual: actual.clone(),
+        }),
+    }
+}
+
+/// `path.key`, or `path["key"]` when the key is not an identifier.
+fn field_path(path: &str, key: &str) -> String {
+    let is_identifier = key
+        .chars()
+        .next()
+        .is_some_and(|first| first.is_alphabetic() || first == '_')
+        && key
+            .chars()
+            .all(|character| character.is_alphanumeric() || character == '_');
+    if is_identifier {
+        format!("{}.{}", path, key)
+    } else {
+        format!("{}[{}]", path, Value::from(key))
+    }
+}
+
+/// One line per difference, indented by `indent`; expected values in green and
+/// actual values in red when `color` is set.
+pub fn render_differences(differences: &[Difference], indent: &str, color: bool) -> String {
+    let paint = |value: &Value, code: &str| {
+        if color {
+            format!("{}{}{}", code, value, RESET)
+        } else {
+            value.to_string()
+        }
+    };
+    let mut out = String::new();
+    for difference in differences {
+        let _ = match difference {
+            Difference::Changed {
+                path,
+                expected,
+                actual,
+            } => writeln!(
+                out,
+                "{}{}: expected {}, actual {}",
+                indent,
+                path,
+                paint(expected, GREEN),
+                paint(actual, RED)
+            ),
+            Difference::Missing { path, expected } => writeln!(
+                out,
+                "{}{}: missing, expected {}",
+                indent,
+                path,
+                paint(expected, GREEN)
+            ),
+            Difference::Unexpected { path, actual } => {
+                writeln!(out, "{}{}: unexpected {}", indent, path, paint(actual, RED))
+            }
+        };
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn only_differing_paths_are_reported() {
+        // Keys in alphabetical order, so the output does not depend on whether
+        // serde_json preserves insertion order
+        let expected = json!({
+            "filter": "All",
+            "item count": 2.0,
+            "todos": [
+                { "completed": false, "title": "Buy milk" },
+                { "completed": true, "title": "Walk dog" }
+            ]
+        });
+        let actual = json!({
+            "editing": null,
+            "item count": 2.0,
+            "todos": [
+                { "completed": false, "title": "Buy milk" },
+                { "completed": false, "title": "Walk dog" },
+                { "completed": false, "title": "Read" }
+            ]
+        });
+        assert_eq!(
+            render_differences(
+                &diff_json("result", &expected, &actual, DEFAULT_DIFF_DEPTH),
+                "  ",
+                false
+            ),
+            "  result.filter: missing, expected \"All\"
+  result.todos[1].completed: expected true, actual false
+  result.todos[2]: unexpected {\"completed\":false,\"title\":\"Read\"}
+  result.editing: unexpected null
+"
+        );
+    }
+
+    #[test]
+    fn depth_cap_reports_deep_values_whole() {
+        let expected = json!({ "a": { "b": { "c": 1.0 } } });
+        let actual = json!({ "a": { "b": { "c": 2.0 } } });
+        assert_eq!(
+            diff_json("result", &expected, &actual, 1),
+            vec![Difference::Changed {
+                path: "result.a".to_string(),
+                expected: json!({ "b": { "c": 1.0 } }),
+                actual: json!({ "b": { "c": 2.0 } }),
+            }]
+        );
+        assert_eq!(
+            render_differences(&diff_json("result", &json!(1), &json!(2), 0), "", true),
+            "result: expected \x1b[32m1\x1b[0m, actual \x1b[31m2\x1b[0m\n"
+        );
+    }
+}
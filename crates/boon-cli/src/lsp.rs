@@ -0,0 +1,829 @@
+//! `boon lsp`: a minimal language server over stdio.
+//!
+//! Documents are synced in full. Every `didOpen`/`didChange` re-runs the parse
+//! pipeline of `boon check` and publishes its errors, plus a warning for each call
+//! of a renamed builtin. Hover describes stdlib functions (`List/append`,
+//! `Timer/interval`, ...) and the document outline lists top-level variables and
+//! functions. Positions use UTF-16 columns, the LSP default.
+
+use boon::parser::builtin_aliases::{builtin_alias, deprecated_builtin_calls};
+use boon::parser::{Expression, Spanned};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::io::{self, BufRead, Read, Write};
+use std::ops::Range;
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::diagnostics::{self, Diagnostic, Severity};
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+
+const TEXT_DOCUMENT_SYNC_FULL: u8 = 1;
+const SYMBOL_KIND_FUNCTION: u8 = 12;
+const SYMBOL_KIND_VARIABLE: u8 = 13;
+const DIAGNOSTIC_TAG_DEPRECATED: u8 = 2;
+
+/// Stdlib functions shown on hover: path, arguments and what the function does.
+/// Mirrors the builtin paths `compile_function_call` dispatches on.
+const STDLIB_FUNCTIONS: &[(&str, &str, &str)] = &[
+    (
+        "Bool/and",
+        "value |> Bool/and(that)",
+        "True when both Bools are True.",
+    ),
+    ("Bool/not", "value |> Bool/not()", "Negates a Bool."),
+    (
+        "Bool/or",
+        "value |> Bool/or(that)",
+        "True when either Bool is True.",
+    ),
+    (
+        "Bool/toggle",
+        "Bool/toggle(on, initial)",
+        "Flips the stored Bool each time `on` fires, starting at the first `initial` (default False). Persisted like a HOLD.",
+    ),
+    (
+        "Document/new",
+        "Document/new(root)",
+        "The document whose root element is rendered.",
+    ),
+    (
+        "List/append",
+        "list |> List/append(item)",
+        "Adds every value of `item` to the end of the list.",
+    ),
+    (
+        "List/clear",
+        "list |> List/clear(on)",
+        "Removes all items when `on` fires.",
+    ),
+    (
+        "List/count",
+        "list |> List/count()",
+        "Number of items, updated as the list changes.",
+    ),
+    (
+        "List/get",
+        "list |> List/get(index)",
+        "Item at the 1-based `index`.",
+    ),
+    (
+        "List/is_empty",
+        "list |> List/is_empty()",
+        "True when the list has no items.",
+    ),
+    (
+        "List/is_not_empty",
+        "list |> List/is_not_empty()",
+        "True when the list has items.",
+    ),
+    ("List/last", "list |> List/last()", "The last item."),
+    (
+        "List/latest",
+        "list |> List/latest()",
+        "The most recent value of any item.",
+    ),
+    (
+        "List/map",
+        "list |> List/map(item, new)",
+        "Transforms every item with the `new` expression.",
+    ),
+    (
+        "List/product",
+        "list |> List/product()",
+        "Product of the numbers in the list.",
+    ),
+    (
+        "List/range",
+        "List/range(from, to)",
+        "Numbers from `from` to `to`, inclusive.",
+    ),
+    (
+        "List/remove_last",
+        "list |> List/remove_last(on)",
+        "Removes the last item when `on` fires.",
+    ),
+    (
+        "List/sum",
+        "list |> List/sum()",
+        "Sum of the numbers in the list.",
+    ),
+    (
+        "Log/error",
+        "value |> Log/error()",
+        "Logs the value as an error and passes it on.",
+    ),
+    (
+        "Log/info",
+        "value |> Log/info()",
+        "Logs the value and passes it on.",
+    ),
+    (
+        "Math/abs",
+        "value |> Math/abs()",
+        "The number without its sign.",
+    ),
+    (
+        "Math/average",
+        "value |> Math/average(reset)",
+        "Running mean of the incoming numbers; `reset` starts over at 0.",
+    ),
+    (
+        "Math/ceil",
+        "value |> Math/ceil()",
+        "Rounds up to the next whole number.",
+    ),
+    (
+        "Math/clamp",
+        "value |> Math/clamp(min, max)",
+        "The number kept between `min` and `max`; `min` when the bounds cross.",
+    ),
+    (
+        "Math/count",
+        "value |> Math/count(reset)",
+        "How many values have arrived; `reset` starts over at 0.",
+    ),
+    (
+        "Math/divide_int",
+        "value |> Math/divide_int(by)",
+        "Divides by `by` and rounds down; `MathError` when `by` is 0.",
+    ),
+    (
+        "Math/floor",
+        "value |> Math/floor()",
+        "Rounds down to the previous whole number.",
+    ),
+    (
+        "Math/max",
+        "value |> Math/max(with)",
+        "The larger of two numbers.",
+    ),
+    (
+        "Math/min",
+        "value |> Math/min(with)",
+        "The smaller of two numbers.",
+    ),
+    (
+        "Math/modulo",
+        "value |> Math/modulo(by)",
+        "Remainder of dividing by `by`, with the sign of `by`; `MathError` when `by` is 0.",
+    ),
+    (
+        "Math/negate_if",
+        "value |> Math/negate_if(condition)",
+        "The number negated while `condition` is True.",
+    ),
+    (
+        "Math/pow",
+        "base |> Math/pow(exponent)",
+        "`base` raised to `exponent`; `MathError` when the result is not finite.",
+    ),
+    (
+        "Math/round",
+        "value |> Math/round()",
+        "Rounds to the nearest whole number.",
+    ),
+    (
+        "Math/sqrt",
+        "value |> Math/sqrt()",
+        "Square root; `MathError` for a negative number.",
+    ),
+    (
+        "Math/sum",
+        "value |> Math/sum()",
+        "Running sum of the incoming numbers.",
+    ),
+    (
+        "Number/parse",
+        "text |> Number/parse()",
+        "The trimmed Text as a Number, or `ParseError[text]` when it is not one.",
+    ),
+    (
+        "Number/to_text",
+        "number |> Number/to_text(decimals, thousands_separator)",
+        "The Number with fixed `decimals`, digits grouped by the optional `thousands_separator`.",
+    ),
+    (
+        "Router/go_to",
+        "route |> Router/go_to()",
+        "Navigates to the route.",
+    ),
+    (
+        "Router/route",
+        "Router/route()",
+        "The current route, updated on navigation.",
+    ),
+    (
+        "Stream/debounce",
+        "stream |> Stream/debounce(duration)",
+        "Passes a value on once no new one arrived for `duration`.",
+    ),
+    (
+        "Stream/distinct",
+        "stream |> Stream/distinct()",
+        "Drops values equal to the previous one.",
+    ),
+    (
+        "Stream/gate",
+        "stream |> Stream/gate(open)",
+        "Passes values on only while `open` is True.",
+    ),
+    (
+        "Stream/max",
+        "stream |> Stream/max()",
+        "The highest number so far; emits only when it changes.",
+    ),
+    (
+        "Stream/min",
+        "stream |> Stream/min()",
+        "The lowest number so far; emits only when it changes.",
+    ),
+    (
+        "Stream/sample",
+        "stream |> Stream/sample(on)",
+        "The latest value, each time `on` fires.",
+    ),
+    (
+        "Stream/sequence",
+        "count |> Stream/sequence()",
+        "Emits `count` values, one per tick.",
+    ),
+    (
+        "Stream/skip",
+        "stream |> Stream/skip(count)",
+        "Drops the first `count` values.",
+    ),
+    (
+        "Stream/take",
+        "stream |> Stream/take(count)",
+        "Passes on only the first `count` values.",
+    ),
+    (
+        "Text/contains",
+        "text |> Text/contains(needle)",
+        "True when `needle` occurs in the Text.",
+    ),
+    ("Text/empty", "Text/empty()", "The empty Text."),
+    (
+        "Text/ends_with",
+        "text |> Text/ends_with(suffix)",
+        "True when the Text ends with `suffix`.",
+    ),
+    (
+        "Text/is_empty",
+        "text |> Text/is_empty()",
+        "True for the empty Text.",
+    ),
+    (
+        "Text/is_not_empty",
+        "text |> Text/is_not_empty()",
+        "True for a non-empty Text.",
+    ),
+    (
+        "Text/length",
+        "text |> Text/length()",
+        "Number of characters.",
+    ),
+    (
+        "Text/pad_end",
+        "text |> Text/pad_end(length, with)",
+        "Repeats `with` after the Text until it is `length` characters long.",
+    ),
+    (
+        "Text/pad_start",
+        "text |> Text/pad_start(length, with)",
+        "Repeats `with` before the Text until it is `length` characters long.",
+    ),
+    (
+        "Text/replace",
+        "text |> Text/replace(from, to)",
+        "The Text with every `from` replaced by `to`.",
+    ),
+    (
+        "Text/slice",
+        "text |> Text/slice(start, end)",
+        "Characters from the 1-based `start` to `end`, inclusive.",
+    ),
+    ("Text/space", "Text/space()", "A single space."),
+    (
+        "Text/starts_with",
+        "text |> Text/starts_with(prefix)",
+        "True when the Text begins with `prefix`.",
+    ),
+    (
+        "Text/substring",
+        "text |> Text/substring(start, length)",
+        "`length` characters from the 1-based `start`.",
+    ),
+    (
+        "Text/to_lowercase",
+        "text |> Text/to_lowercase()",
+        "The Text in lower case.",
+    ),
+    (
+        "Text/to_number",
+        "text |> Text/to_number()",
+        "The number the Text spells, or NaN.",
+    ),
+    (
+        "Text/to_uppercase",
+        "text |> Text/to_uppercase()",
+        "The Text in upper case.",
+    ),
+    (
+        "Text/trim",
+        "text |> Text/trim()",
+        "The Text without leading and trailing whitespace.",
+    ),
+    (
+        "Timer/interval",
+        "duration |> Timer/interval()",
+        "Fires every `duration`, e.g. `Duration[seconds: 1]`.",
+    ),
+    (
+        "Ulid/generate",
+        "Ulid/generate()",
+        "A new unique, sortable id.",
+    ),
+];
+
+/// Reads one `Content-Length` framed message body; `Ok(None)` at end of input.
+pub fn read_message(reader: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut content_length = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.eq_ignore_ascii_case("Content-Length")
+        {
+            content_length = value.trim().parse::<usize>().ok();
+        }
+    }
+    let Some(content_length) = content_length else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "message without a Content-Length header",
+        ));
+    };
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    String::from_utf8(body)
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+pub fn write_message(writer: &mut impl Write, message: &Value) -> io::Result<()> {
+    let body = message.to_string();
+    write!(writer, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    writer.flush()
+}
+
+fn response(id: &Value, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+fn error_response(id: &Value, code: i64, message: impl Into<String>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message.into() }
+    })
+}
+
+fn notification(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+/// LSP position (0-based line, UTF-16 column) of the byte offset `index`.
+fn position(source: &str, index: usize) -> Value {
+    let mut index = index.min(source.len());
+    while !source.is_char_boundary(index) {
+        index -= 1;
+    }
+    let line_start = source[..index].rfind('\n').map_or(0, |newline| newline + 1);
+    json!({
+        "line": source[..index].matches('\n').count(),
+        "character": source[line_start..index].encode_utf16().count()
+    })
+}
+
+fn range(source: &str, range: Range<usize>) -> Value {
+    json!({ "start": position(source, range.start), "end": position(source, range.end) })
+}
+
+/// Byte offset of an LSP position, clamped to the end of its line and of `source`.
+fn offset(source: &str, position: &Value) -> usize {
+    let line = position["line"].as_u64().unwrap_or(0);
+    let character = position["character"].as_u64().unwrap_or(0);
+    let mut line_start = 0;
+    for _ in 0..line {
+        match source[line_start..].find('\n') {
+            Some(newline) => line_start += newline + 1,
+            None => return source.len(),
+        }
+    }
+    let mut units = 0;
+    for (index, character_here) in source[line_start..].char_indices() {
+        if units >= character || character_here == '\n' {
+            return line_start + index;
+        }
+        units += u64::try_from(character_here.len_utf16()).unwrap_or(1);
+    }
+    source.len()
+}
+
+fn lsp_diagnostic(source: &str, diagnostic: &Diagnostic) -> Value {
+    json!({
+        "range": range(source, diagnostic.start..diagnostic.end),
+        "severity": match diagnostic.severity {
+            Severity::Error => 1,
+            Severity::Warning => 2,
+        },
+        "source": "boon",
+        "message": diagnostic.message
+    })
+}
+
+/// Parser errors, or warnings for renamed builtins when the code parses. The parser
+/// sees every keystroke, so a panic on half-typed code is reported instead of
+/// taking the server down.
+fn document_diagnostics(source: &str) -> Vec<Value> {
+    let parsed = panic::catch_unwind(AssertUnwindSafe(|| {
+        diagnostics::parse_with_diagnostics(source).err()
+    }));
+    match parsed {
+        Ok(Some(errors)) => errors
+            .iter()
+            .map(|error| lsp_diagnostic(source, error))
+            .collect(),
+        Ok(None) => deprecated_builtin_calls(source)
+            .into_iter()
+            .map(|call| {
+                let mut diagnostic = lsp_diagnostic(
+                    source,
+                    &Diagnostic::new(Severity::Warning, call.alias.message(), call.range, source),
+                );
+                diagnostic["tags"] = json!([DIAGNOSTIC_TAG_DEPRECATED]);
+                diagnostic
+            })
+            .collect(),
+        Err(_) => vec![lsp_diagnostic(
+            source,
+            &Diagnostic::new(Severity::Error, "Internal parser error", 0..0, source),
+        )],
+    }
+}
+
+/// Top-level variables and functions, in source order; empty while the code does
+/// not parse.
+fn document_symbols(source: &str) -> Vec<Value> {
+    let symbol = |name: &str, kind: u8, expression: &Spanned<Expression>| {
+        let range = range(source, expression.span.start..expression.span.end);
+        json!({ "name": name, "kind": kind, "range": range, "selectionRange": range })
+    };
+    let parsed = panic::catch_unwind(AssertUnwindSafe(|| {
+        let Ok(expressions) = diagnostics::parse_with_diagnostics(source) else {
+            return Vec::new();
+        };
+        expressions
+            .iter()
+            .filter_map(|expression| match &expression.node {
+                Expression::Variable(variable) => {
+                    Some(symbol(variable.name, SYMBOL_KIND_VARIABLE, expression))
+                }
+                Expression::Function { name, .. } => {
+                    Some(symbol(name, SYMBOL_KIND_FUNCTION, expression))
+                }
+                _ => None,
+            })
+            .collect()
+    }));
+    parsed.unwrap_or_default()
+}
+
+/// Markdown for the `Module/function` path under byte offset `index`, if it names a
+/// stdlib function (or the old name of a renamed one).
+fn hover_text(source: &str, index: usize) -> Option<String> {
+    let is_path_character =
+        |character: char| character.is_alphanumeric() || "_/".contains(character);
+    let start = source[..index]
+        .char_indices()
+        .rev()
+        .take_while(|(_, character)| is_path_character(*character))
+        .last()
+        .map_or(index, |(start, _)| start);
+    let end = source[index..]
+        .char_indices()
+        .find(|(_, character)| !is_path_character(*character))
+        .map_or(source.len(), |(after, _)| index + after);
+    let path = &source[start..end];
+    let segments = path.split('/').collect::<Vec<_>>();
+    let (path, note) = match builtin_alias(&segments) {
+        Some(alias) => (alias.new_name(), format!("\n\n*{}*", alias.message())),
+        None => (path.to_string(), String::new()),
+    };
+    let (_, signature, summary) = STDLIB_FUNCTIONS
+        .iter()
+        .find(|(function, _, _)| *function == path)?;
+    Some(format!("```boon\n{}\n```\n{}{}", signature, summary, note))
+}
+
+#[derive(Default)]
+pub struct Server {
+    documents: HashMap<String, String>,
+    shutdown_requested: bool,
+}
+
+impl Server {
+    /// Handles one client message and returns the messages to send back.
+    pub fn handle(&mut self, message: &Value) -> Vec<Value> {
+        let method = message["method"].as_str().unwrap_or_default();
+        let params = &message["params"];
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+
+        let Some(id) = message.get("id") else {
+            return match method {
+                "textDocument/didOpen" => {
+                    let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+                    self.documents.insert(uri.to_string(), text.to_string());
+                    vec![self.publish_diagnostics(uri)]
+                }
+                "textDocument/didChange" => {
+                    // Full sync: the last change holds the whole document
+                    let changes = params["contentChanges"].as_array();
+                    let Some(text) = changes
+                        .and_then(|changes| changes.last())
+                        .and_then(|change| change["text"].as_str())
+                    else {
+                        return Vec::new();
+                    };
+                    self.documents.insert(uri.to_string(), text.to_string());
+                    vec![self.publish_diagnostics(uri)]
+                }
+                "textDocument/didClose" => {
+                    self.documents.remove(uri);
+                    vec![notification(
+                        "textDocument/publishDiagnostics",
+                        json!({ "uri": uri, "diagnostics": [] }),
+                    )]
+                }
+                _ => Vec::new(),
+            };
+        };
+
+        if self.shutdown_requested {
+            return vec![error_response(
+                id,
+                INVALID_REQUEST,
+                "Server is shutting down",
+            )];
+        }
+        let result = match method {
+            "initialize" => json!({
+                "capabilities": {
+                    "textDocumentSync": TEXT_DOCUMENT_SYNC_FULL,
+                    "hoverProvider": true,
+                    "documentSymbolProvider": true
+                },
+                "serverInfo": { "name": "boon", "version": env!("CARGO_PKG_VERSION") }
+            }),
+            "shutdown" => {
+                self.shutdown_requested = true;
+                Value::Null
+            }
+            "textDocument/hover" => self
+                .documents
+                .get(uri)
+                .and_then(|source| hover_text(source, offset(source, &params["position"])))
+                .map_or(
+                    Value::Null,
+                    |markdown| json!({ "contents": { "kind": "markdown", "value": markdown } }),
+                ),
+            "textDocument/documentSymbol" => self
+                .documents
+                .get(uri)
+                .map_or(json!([]), |source| json!(document_symbols(source))),
+            other => {
+                return vec![error_response(
+                    id,
+                    METHOD_NOT_FOUND,
+                    format!("Unsupported method `{}`", other),
+                )];
+            }
+        };
+        vec![response(id, result)]
+    }
+
+    fn publish_diagnostics(&self, uri: &str) -> Value {
+        let source = self.documents.get(uri).map_or("", String::as_str);
+        notification(
+            "textDocument/publishDiagnostics",
+            json!({ "uri": uri, "diagnostics": document_diagnostics(source) }),
+        )
+    }
+}
+
+/// `boon lsp`: serves one client on stdin/stdout until `exit` or end of input.
+/// Exits with 0 only when the client asked for `shutdown` first.
+pub fn run_lsp() {
+    let stdin = io::stdin();
+    let mut reader = stdin.lock();
+    let mut stdout = io::stdout().lock();
+    let mut server = Server::default();
+    loop {
+        let body = match read_message(&mut reader) {
+            Ok(Some(body)) => body,
+            Ok(None) => break,
+            Err(e) => {
+                eprintln!("boon lsp: {}", e);
+                break;
+            }
+        };
+        let outgoing = match serde_json::from_str::<Value>(&body) {
+            Ok(message) if message["method"] == "exit" => break,
+            Ok(message) => server.handle(&message),
+            Err(e) => vec![error_response(&Value::Null, PARSE_ERROR, e.to_string())],
+        };
+        for message in &outgoing {
+            if let Err(e) = write_message(&mut stdout, message) {
+                eprintln!("boon lsp: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+    std::process::exit(if server.shutdown_requested { 0 } else { 1 });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    const URI: &str = "file:///counter.bn";
+
+    fn open(server: &mut Server, text: &str) -> Vec<Value> {
+        server.handle(&notification(
+            "textDocument/didOpen",
+            json!({ "textDocument": { "uri": URI, "languageId": "boon", "version": 1, "text": text } }),
+        ))
+    }
+
+    fn request(server: &mut Server, method: &str, params: Value) -> Value {
+        let mut outgoing = server.handle(&json!({
+            "jsonrpc": "2.0", "id": 7, "method": method, "params": params
+        }));
+        assert_eq!(outgoing.len(), 1);
+        outgoing.remove(0)
+    }
+
+    #[test]
+    fn messages_are_framed_by_content_length() {
+        let mut written = Vec::new();
+        write_message(&mut written, &json!({ "id": 1 })).unwrap();
+        write_message(&mut written, &json!({ "text": "café" })).unwrap();
+        assert!(written.starts_with(b"Content-Length: 8\r\n\r\n{\"id\":1}"));
+
+        let mut reader = Cursor::new(written);
+        assert_eq!(
+            read_message(&mut reader).unwrap().as_deref(),
+            Some("{\"id\":1}")
+        );
+        assert_eq!(
+            read_message(&mut reader).unwrap().as_deref(),
+            Some("{\"text\":\"café\"}")
+        );
+        assert_eq!(read_message(&mut reader).unwrap(), None);
+        assert!(read_message(&mut Cursor::new("X-Other: 1\r\n\r\n{}")).is_err());
+    }
+
+    #[test]
+    fn positions_count_utf16_columns() {
+        let source = "label: TEXT { 🙂 } |> Oops\nnext: 1";
+        let index = source.find("Oops").unwrap();
+        assert_eq!(
+            position(source, index),
+            json!({ "line": 0, "character": 22 })
+        );
+        assert_eq!(
+            offset(source, &json!({ "line": 0, "character": 22 })),
+            index
+        );
+        assert_eq!(
+            offset(source, &json!({ "line": 0, "character": 99 })),
+            source.find('\n').unwrap()
+        );
+        assert_eq!(
+            offset(source, &json!({ "line": 5, "character": 0 })),
+            source.len()
+        );
+    }
+
+    #[test]
+    fn edits_publish_diagnostics_and_never_panic_on_partial_input() {
+        let mut server = Server::default();
+        let published = open(&mut server, "counter: 0 $\n");
+        assert_eq!(published[0]["method"], "textDocument/publishDiagnostics");
+        let diagnostics = published[0]["params"]["diagnostics"].as_array().unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0]["severity"], 1);
+        assert_eq!(
+            diagnostics[0]["range"]["start"],
+            json!({ "line": 0, "character": 11 })
+        );
+
+        // Every prefix of a program, as typed one character at a time
+        let program = "FUNCTION double(value) { value * 2 }\ncounter: LIST { 1, 2 } |> List/count() |> double()\n";
+        for (end, _) in program.char_indices().chain([(program.len(), ' ')]) {
+            let published = server.handle(&notification(
+                "textDocument/didChange",
+                json!({
+                    "textDocument": { "uri": URI, "version": 2 },
+                    "contentChanges": [{ "text": &program[..end] }]
+                }),
+            ));
+            assert_eq!(published.len(), 1);
+        }
+        assert_eq!(
+            server.handle(&notification(
+                "textDocument/didChange",
+                json!({ "textDocument": { "uri": URI }, "contentChanges": [{ "text": program }] }),
+            ))[0]["params"]["diagnostics"],
+            json!([])
+        );
+    }
+
+    #[test]
+    fn hover_describes_stdlib_functions() {
+        let mut server = Server::default();
+        let source = "items: LIST {} |> List/append(item: 1)\nticks: 3 |> Stream/pulses()\n";
+        open(&mut server, source);
+        let hover = request(
+            &mut server,
+            "textDocument/hover",
+            json!({ "textDocument": { "uri": URI }, "position": { "line": 0, "character": 22 } }),
+        );
+        let markdown = hover["result"]["contents"]["value"].as_str().unwrap();
+        assert!(markdown.contains("list |> List/append(item)"));
+
+        let hover = request(
+            &mut server,
+            "textDocument/hover",
+            json!({ "textDocument": { "uri": URI }, "position": { "line": 1, "character": 15 } }),
+        );
+        let markdown = hover["result"]["contents"]["value"].as_str().unwrap();
+        assert!(markdown.contains("count |> Stream/sequence()"));
+        assert!(markdown.contains("deprecated"));
+
+        let hover = request(
+            &mut server,
+            "textDocument/hover",
+            json!({ "textDocument": { "uri": URI }, "position": { "line": 0, "character": 2 } }),
+        );
+        assert_eq!(hover["result"], Value::Null);
+    }
+
+    #[test]
+    fn outline_lists_top_level_variables_and_functions() {
+        let mut server = Server::default();
+        open(
+            &mut server,
+            "FUNCTION double(value) { value * 2 }\ncounter: 1 |> double()\n",
+        );
+        let symbols = request(
+            &mut server,
+            "textDocument/documentSymbol",
+            json!({ "textDocument": { "uri": URI } }),
+        );
+        let outline = symbols["result"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|symbol| (symbol["name"].clone(), symbol["kind"].clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            outline,
+            vec![(json!("double"), json!(12)), (json!("counter"), json!(13))]
+        );
+        assert_eq!(
+            symbols["result"][1]["range"]["start"],
+            json!({ "line": 1, "character": 0 })
+        );
+
+        assert_eq!(
+            request(&mut server, "shutdown", Value::Null)["result"],
+            Value::Null
+        );
+        assert_eq!(
+            request(&mut server, "textDocument/hover", json!({}))["error"]["code"],
+            INVALID_REQUEST
+        );
+    }
+}
@@ -1,10 +1,42 @@
 use boon::engine_v2::event_loop::EventLoop;
 use boon::evaluator_v2::CompileContext;
-use boon::parser::{Input, Parser, Spanned, lexer, parser, reset_expression_depth, span_at};
+use boon::parser::builtin_aliases::deprecated_builtin_calls;
+use boon::parser::exhaustiveness::check_source_exhaustiveness;
+use boon::parser::formatter;
+use boon::parser::{
+    Argument, Expression, Input, Parser, Spanned, lexer, parser, reset_expression_depth, span_at,
+};
+use boon::platform::browser::common::{
+    DEFAULT_MAX_TEXT_BYTES, LogLevel, emit_warning, max_text_bytes, set_max_text_bytes,
+    set_warning_hook, truncate_oversized_text,
+};
 use boon::platform::cli::clock::TestClock;
 use clap::{Parser as ClapParser, Subcommand};
+use diagnostics::{Diagnostic, Severity};
+use expect::Expectation;
+use serde::Serialize;
+use std::fmt::Write as _;
 use std::fs;
-use std::path::PathBuf;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use test_report::{CaseReport, CaseResult, FileReport, TestFormat};
+
+mod bench;
+mod diagnostics;
+mod expect;
+mod hot_reload;
+mod html;
+mod interactive;
+mod json_diff;
+mod lsp;
+mod repl;
+mod serve;
+mod state;
+mod test_file;
+mod test_report;
+mod watch;
 
 #[derive(ClapParser)]
 #[command(name = "boon")]
@@ -20,33 +52,205 @@ enum Commands {
     Eval {
         /// The code to evaluate
         code: String,
-        /// Number of ticks to run
+        /// Safety cap on the number of ticks to run
         #[arg(long)]
         ticks: Option<u64>,
+        /// Advance virtual time at most this far; timers due later do not fire
+        #[arg(long)]
+        max_time_ms: Option<u64>,
+        /// Stop after this many timer fires
+        #[arg(long)]
+        max_timer_fires: Option<u64>,
+        /// Add phase timings and graph sizes to the JSON output: `parse_ms`,
+        /// `compile_ms`, `eval_ms`, `arena_slots` and `timers_fired`
+        #[arg(long)]
+        stats: bool,
+        /// Expected result as JSON; prints PASS or FAIL and exits with 1 on a mismatch
+        #[arg(long)]
+        expect: Option<String>,
+        /// Like --expect, but objects may have more fields and lists more items
+        #[arg(long, conflicts_with = "expect")]
+        expect_contains: Option<String>,
+        /// Check references first like `boon check`: unknown variables and builtin
+        /// functions stop evaluation, unused variables and parameters are reported
+        /// as warnings
+        #[arg(long)]
+        strict: bool,
     },
     /// Run a Boon file
     Run {
-        /// Path to .bn file
+        /// Path to .bn file, or `-` to read the program from stdin
         file: PathBuf,
-        /// Number of ticks to run
+        /// Safety cap on the number of ticks to run
         #[arg(long)]
         ticks: Option<u64>,
-        /// State file for persistence (load on start, save on exit)
+        /// Advance virtual time at most this far; timers due later do not fire
+        #[arg(long)]
+        max_time_ms: Option<u64>,
+        /// Stop after this many timer fires
+        #[arg(long)]
+        max_timer_fires: Option<u64>,
+        /// State file for persistence (load on start, save on exit and on SIGINT/SIGTERM)
         #[arg(long)]
         state: Option<PathBuf>,
+        /// Also save the state file this often while running, e.g. `30s`, `500ms`, `5m`
+        #[arg(
+            long,
+            value_parser = state::parse_interval,
+            requires = "state",
+            conflicts_with = "interactive"
+        )]
+        state_save_interval: Option<Duration>,
+        /// Append non-fatal engine warnings to this file (one JSON object per line)
+        #[arg(long)]
+        warnings_log: Option<PathBuf>,
+        /// Soft limit for one Text payload; longer Text in the result is truncated and reported
+        #[arg(long, default_value_t = DEFAULT_MAX_TEXT_BYTES)]
+        max_text_bytes: usize,
+        /// Re-evaluate whenever the file or a module next to it changes, keeping HOLD state
+        #[arg(long, conflicts_with = "state")]
+        watch: bool,
+        /// After the initial evaluation, read `read`, `read <path>` and `quit` commands
+        /// from stdin while timers keep running in real time
+        #[arg(long, conflicts_with = "watch")]
+        interactive: bool,
+        /// Check references first like `boon check`: unknown variables and builtin
+        /// functions stop evaluation, unused variables and parameters are reported
+        /// as warnings
+        #[arg(long, conflicts_with = "watch")]
+        strict: bool,
     },
     /// Check if code parses correctly
     Check {
-        /// Path to .bn file
+        /// Path to .bn file, or `-` to read the program from stdin
         file: PathBuf,
+        /// Fail when the code calls a renamed builtin by its old name (for CI)
+        #[arg(long)]
+        deny_deprecated: bool,
+        /// Print the parsed AST as JSON, with the byte span of every node
+        #[arg(long)]
+        emit_ast: bool,
+        /// Print errors and warnings as a JSON array of diagnostics (for editors)
+        #[arg(long, conflicts_with = "emit_ast")]
+        json: bool,
+    },
+    /// Print files in canonical style (comments are kept)
+    Fmt {
+        /// Path(s) to .bn files
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+        /// Exit with an error when a file is not formatted (for CI)
+        #[arg(long, conflicts_with = "write")]
+        check: bool,
+        /// Rewrite the files in place instead of printing them
+        #[arg(long)]
+        write: bool,
     },
     /// Run test files with expected output verification
     Test {
-        /// Path to test file(s) - can be a glob pattern
+        /// Test files, directories or glob patterns (`tests/**/*.bn.test`)
+        #[arg(required = true)]
         files: Vec<PathBuf>,
         /// Update expected outputs instead of verifying
         #[arg(long)]
         update: bool,
+        /// Only run tests whose name contains this text (case-insensitive); repeat
+        /// to run tests matching any of them. With --update, only their
+        /// expectations are rewritten
+        #[arg(long, value_name = "PATTERN")]
+        filter: Vec<String>,
+        /// Print mismatches without ANSI colors (the default when stderr is not a terminal)
+        #[arg(long)]
+        no_color: bool,
+        /// Nesting depth below which mismatching objects and lists are printed whole
+        #[arg(long, default_value_t = json_diff::DEFAULT_DIFF_DEPTH)]
+        diff_depth: usize,
+        /// Report format; progress and mismatches are always printed to stderr
+        #[arg(long, value_enum, default_value_t)]
+        format: TestFormat,
+        /// Write the junit or tap report to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Serve the program's document as HTML over HTTP, re-rendered on every request
+    Serve {
+        /// Path to .bn file, or `-` to read the program from stdin
+        file: PathBuf,
+        /// Port on 127.0.0.1
+        #[arg(long, default_value_t = 3000)]
+        port: u16,
+        /// Dotted path to the value to render, e.g. `store.document`
+        #[arg(long, default_value = "document")]
+        path: String,
+        /// Safety cap on the number of ticks per evaluation step
+        #[arg(long)]
+        ticks: Option<u64>,
+    },
+    /// Start an interactive session; definitions persist between inputs
+    Repl {
+        /// Maximum number of ticks to run after each input
+        #[arg(long)]
+        ticks: Option<u64>,
+    },
+    /// Benchmark a program, or compare saved benchmark reports
+    #[command(args_conflicts_with_subcommands = true)]
+    Bench {
+        #[command(subcommand)]
+        command: Option<BenchCommand>,
+        /// Program to benchmark: parse, compile and tick
+        file: Option<PathBuf>,
+        /// Runs of each phase
+        #[arg(long, default_value_t = 100)]
+        iterations: usize,
+        /// Event to inject on every iteration, e.g. `--inject button.event.press Unit`;
+        /// repeat it to inject several events in order. The path is checked, but the
+        /// engine cannot send events by path yet, so the run is refused.
+        #[arg(long, num_args = 2, value_names = ["PATH", "PAYLOAD"])]
+        inject: Vec<String>,
+        /// Print the report as JSON, the input of `boon bench diff`
+        #[arg(long)]
+        json: bool,
+    },
+    /// Inspect state files written by `boon run --state`
+    State {
+        #[command(subcommand)]
+        command: StateCommand,
+    },
+    /// Language server over stdio: diagnostics, hover for stdlib functions and an outline
+    Lsp,
+}
+
+#[derive(Subcommand)]
+enum StateCommand {
+    /// List the slots whose stored values changed, appeared or disappeared
+    Diff {
+        /// Earlier state file
+        old: PathBuf,
+        /// Later state file
+        new: PathBuf,
+        /// Print the differences as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum BenchCommand {
+    /// Compare two saved bench reports scenario by scenario
+    Diff {
+        /// Report of the reference run (e.g. main)
+        baseline: PathBuf,
+        /// Report of the run to check
+        candidate: PathBuf,
+        /// Slowdown in percent above which a scenario is flagged as a regression
+        #[arg(long, default_value_t = 5.0)]
+        threshold: f64,
+        /// Exit with an error when any scenario slows down by more than this percent
+        #[arg(long)]
+        fail_threshold: Option<f64>,
+        /// Table format
+        #[arg(long, value_enum, default_value_t = bench::DiffFormat::Text)]
+        format: bench::DiffFormat,
     },
 }
 
@@ -54,222 +258,642 @@ fn main() {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Eval { code, ticks } => {
-            eval_code(&code, ticks.unwrap_or(100));
+        Commands::Eval {
+            code,
+            ticks,
+            max_time_ms,
+            max_timer_fires,
+            stats,
+            expect,
+            expect_contains,
+            strict,
+        } => {
+            let expectation =
+                match Expectation::from_args(expect.as_deref(), expect_contains.as_deref()) {
+                    Ok(expectation) => expectation,
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        std::process::exit(1);
+                    }
+                };
+            if strict {
+                diagnostics::check_strict(&code, Path::new("<eval>"));
+            }
+            let result = eval_code(
+                &code,
+                RunBudget {
+                    max_ticks: ticks.unwrap_or(100),
+                    max_time_ms,
+                    max_timer_fires,
+                },
+                stats,
+            );
+            if let Some(expectation) = expectation {
+                check_expectation(&expectation, result.as_ref());
+            }
         }
-        Commands::Run { file, ticks, state } => match fs::read_to_string(&file) {
-            Ok(code) => {
-                eprintln!("Running: {}", file.display());
-                eval_code_with_persistence(&code, ticks.unwrap_or(100), state);
+        Commands::Run {
+            file,
+            ticks,
+            max_time_ms,
+            max_timer_fires,
+            state,
+            state_save_interval,
+            warnings_log,
+            max_text_bytes,
+            watch,
+            interactive,
+            strict,
+        } => {
+            if let Some(warnings_log) = warnings_log {
+                install_warnings_log(warnings_log);
             }
-            Err(e) => {
-                eprintln!("Error reading file: {}", e);
+            set_max_text_bytes(max_text_bytes);
+            let budget = RunBudget {
+                max_ticks: ticks.unwrap_or(100),
+                max_time_ms,
+                max_timer_fires,
+            };
+            if interactive && is_stdin(&file) {
+                eprintln!("--interactive reads commands from stdin, so the program needs a file");
                 std::process::exit(1);
             }
-        },
-        Commands::Check { file } => match fs::read_to_string(&file) {
+            if watch {
+                if is_stdin(&file) {
+                    eprintln!("--watch needs a file; stdin cannot be watched");
+                    std::process::exit(1);
+                }
+                watch::run_watch(&file, budget);
+                return;
+            }
+            match read_source(&file) {
+                Ok(code) => {
+                    if strict {
+                        diagnostics::check_strict(&code, &source_name(&file));
+                    }
+                    eprintln!("Running: {}", source_name(&file).display());
+                    eval_code_with_persistence(
+                        &code,
+                        budget,
+                        state,
+                        state_save_interval,
+                        interactive,
+                    );
+                }
+                Err(e) => {
+                    eprintln!("Error reading {}: {}", source_name(&file).display(), e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::Check {
+            file,
+            deny_deprecated,
+            emit_ast,
+            json,
+        } => match read_source(&file) {
             Ok(code) => {
-                check_code(&code, &file);
+                check_code(&code, &source_name(&file), deny_deprecated, emit_ast, json);
             }
             Err(e) => {
-                eprintln!("Error reading file: {}", e);
+                eprintln!("Error reading {}: {}", source_name(&file).display(), e);
                 std::process::exit(1);
             }
         },
-        Commands::Test { files, update } => {
-            run_tests(&files, update);
+        Commands::Fmt {
+            files,
+            check,
+            write,
+        } => {
+            format_files(&files, check, write);
+        }
+        Commands::Test {
+            files,
+            update,
+            filter,
+            no_color,
+            diff_depth,
+            format,
+            output,
+        } => {
+            let diff = DiffOptions {
+                color: !no_color && std::io::stderr().is_terminal(),
+                max_depth: diff_depth,
+            };
+            let report = TestReport { format, output };
+            run_tests(&files, update, &filter, diff, report);
+        }
+        Commands::Serve {
+            file,
+            port,
+            path,
+            ticks,
+        } => {
+            serve::run_serve(&file, port, &path, ticks.unwrap_or(100));
+        }
+        Commands::Repl { ticks } => {
+            repl::run_repl(ticks.unwrap_or(100));
         }
+        Commands::Bench {
+            command:
+                Some(BenchCommand::Diff {
+                    baseline,
+                    candidate,
+                    threshold,
+                    fail_threshold,
+                    format,
+                }),
+            ..
+        } => {
+            bench::run_diff(&baseline, &candidate, threshold, fail_threshold, format);
+        }
+        Commands::Bench {
+            command: None,
+            file,
+            iterations,
+            inject,
+            json,
+        } => {
+            let Some(file) = file else {
+                eprintln!("boon bench needs a .bn file or a subcommand (see boon bench --help)");
+                std::process::exit(1);
+            };
+            let injects: Vec<(String, String)> = inject
+                .chunks(2)
+                .filter_map(|pair| match pair {
+                    [path, payload] => Some((path.clone(), payload.clone())),
+                    _ => None,
+                })
+                .collect();
+            match read_source(&file) {
+                Ok(code) => {
+                    let name = if is_stdin(&file) {
+                        "stdin".into()
+                    } else {
+                        file.file_stem()
+                            .map_or("program".into(), |stem| stem.to_string_lossy())
+                    };
+                    bench::run_bench(&name, &code, iterations, &injects, json);
+                }
+                Err(e) => {
+                    eprintln!("Error reading {}: {}", source_name(&file).display(), e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::State {
+            command: StateCommand::Diff { old, new, json },
+        } => {
+            state::run_diff(&old, &new, json);
+        }
+        Commands::Lsp => lsp::run_lsp(),
     }
 }
 
-/// Run test files with expected output verification.
-/// Test file format:
-/// ```
-/// -- test: test_name
-/// code here
-/// -- expect: expected_json_value
-/// ```
-fn run_tests(files: &[PathBuf], update: bool) {
-    let mut total = 0;
-    let mut passed = 0;
-    let mut failed = 0;
+fn is_stdin(file: &Path) -> bool {
+    file.as_os_str() == "-"
+}
 
-    for file in files {
+/// Reads the program from `file`, or all of stdin when `file` is `-`.
+fn read_source(file: &Path) -> std::io::Result<String> {
+    if is_stdin(file) {
+        std::io::read_to_string(std::io::stdin())
+    } else {
+        fs::read_to_string(file)
+    }
+}
+
+/// Name of the program in messages: the path, or `<stdin>`.
+fn source_name(file: &Path) -> PathBuf {
+    if is_stdin(file) {
+        PathBuf::from("<stdin>")
+    } else {
+        file.to_path_buf()
+    }
+}
+
+/// `code` ended with `path` (dotted, e.g. `store.document`), which makes the value at
+/// `path` the program result. Fails when `path` is not a dotted list of snake_case
+/// names or its first part is not a top-level variable of `code`.
+fn append_path(code: &str, path: &str) -> Result<String, String> {
+    let is_name = |part: &str| {
+        part.starts_with(|first: char| first.is_ascii_lowercase())
+            && part
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+    };
+    if !path.split('.').all(is_name) {
+        return Err(format!(
+            "`{}` is not a dotted path such as `store.counter`",
+            path
+        ));
+    }
+    let root = path.split('.').next().unwrap_or(path);
+    let expressions = diagnostics::parse_with_diagnostics(code)
+        .map_err(|errors| format!("{} parse error(s)", errors.len()))?;
+    let defined = expressions.iter().any(|expression| {
+        matches!(&expression.node, Expression::Variable(variable) if variable.name == root)
+    });
+    if !defined {
+        return Err(format!("`{}` is not a top-level variable", root));
+    }
+    Ok(format!("{}\n{}\n", code, path))
+}
+
+/// Prints PASS or FAIL for `boon eval --expect`; a mismatch or a failed evaluation
+/// exits with 1.
+fn check_expectation(expectation: &Expectation, result: Option<&serde_json::Value>) {
+    match result {
+        Some(result) if expectation.matches(result) => eprintln!("PASS"),
+        Some(result) => {
+            eprintln!("FAIL");
+            eprintln!("  expected: {}", expectation.expected);
+            eprintln!("  actual:   {}", result);
+            std::process::exit(1);
+        }
+        None => {
+            eprintln!("FAIL: no result to compare with {}", expectation.expected);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Run number written with each warning; `--watch` starts a new run on every rebuild.
+static WARNINGS_RUN: AtomicU64 = AtomicU64::new(1);
+
+fn start_next_warnings_run() {
+    WARNINGS_RUN.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Append every engine warning to `path` as a JSON line with its timestamp and run number.
+fn install_warnings_log(path: PathBuf) {
+    set_warning_hook(move |warning| {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .ok()
+            .and_then(|elapsed| u64::try_from(elapsed.as_millis()).ok())
+            .unwrap_or(0);
+        let line = serde_json::json!({
+            "timestamp": timestamp,
+            "run": WARNINGS_RUN.load(Ordering::Relaxed),
+            "level": warning.level.as_str(),
+            "category": warning.category,
+            "message": warning.message,
+        });
+        let written = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| writeln!(file, "{}", line));
+        if let Err(e) = written {
+            eprintln!("Error writing warnings log {}: {}", path.display(), e);
+        }
+    });
+}
+
+/// How `boon test` prints mismatching expectations.
+#[derive(Clone, Copy, Debug)]
+struct DiffOptions {
+    color: bool,
+    max_depth: usize,
+}
+
+/// Where `boon test` writes the junit or tap report, besides the stderr progress.
+struct TestReport {
+    format: TestFormat,
+    output: Option<PathBuf>,
+}
+
+/// Whether `--filter` selects the test `name`: no filters select every test,
+/// otherwise the name must contain one of them, ignoring case.
+fn matches_filters(name: &str, filters: &[String]) -> bool {
+    let name = name.to_lowercase();
+    filters.is_empty()
+        || filters
+            .iter()
+            .any(|filter| name.contains(&filter.to_lowercase()))
+}
+
+/// Run test files with expected output verification (format in [`test_file`]).
+/// With `update`, missing or wrong expectations are rewritten instead of failing.
+/// Tests not selected by `filters` are reported as filtered and not run.
+fn run_tests(
+    arguments: &[PathBuf],
+    update: bool,
+    filters: &[String],
+    diff: DiffOptions,
+    report: TestReport,
+) {
+    let files = match test_file::expand_test_paths(arguments) {
+        Ok(files) => files,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let mut reports = Vec::new();
+    for file in &files {
         match fs::read_to_string(file) {
-            Ok(content) => {
-                let results = run_test_file(file, &content, update);
-                total += results.0;
-                passed += results.1;
-                failed += results.2;
-            }
+            Ok(content) => reports.push(run_test_file(file, &content, update, filters, diff)),
             Err(e) => {
                 eprintln!("Error reading {}: {}", file.display(), e);
-                failed += 1;
+                reports.push(FileReport {
+                    path: file.display().to_string(),
+                    cases: vec![CaseReport {
+                        name: file.display().to_string(),
+                        duration: Duration::ZERO,
+                        result: CaseResult::Error(format!("Error reading file: {}", e)),
+                    }],
+                });
             }
         }
     }
 
-    eprintln!("\n{} tests: {} passed, {} failed", total, passed, failed);
+    let cases = reports.iter().flat_map(|report| &report.cases);
+    let total = cases
+        .clone()
+        .filter(|case| !matches!(case.result, CaseResult::Error(_)))
+        .count();
+    let filtered = cases
+        .clone()
+        .filter(|case| case.result == CaseResult::Filtered)
+        .count();
+    let passed = cases
+        .clone()
+        .filter(|case| case.result == CaseResult::Passed)
+        .count();
+    let failed = cases.count() - passed - filtered;
+    if filters.is_empty() {
+        eprintln!("\n{} tests: {} passed, {} failed", total, passed, failed);
+    } else {
+        eprintln!(
+            "\n{} tests: {} run, {} filtered, {} passed, {} failed",
+            total,
+            total - filtered,
+            filtered,
+            passed,
+            failed
+        );
+    }
+
+    let rendered = match report.format {
+        TestFormat::Human => None,
+        TestFormat::Junit => Some(test_report::render_junit(&reports)),
+        TestFormat::Tap => Some(test_report::render_tap(&reports)),
+    };
+    match (rendered, &report.output) {
+        (Some(rendered), Some(path)) => {
+            if let Err(e) = fs::write(path, rendered) {
+                eprintln!("Error writing {}: {}", path.display(), e);
+                std::process::exit(1);
+            }
+        }
+        (Some(rendered), None) => print!("{}", rendered),
+        (None, _) => {}
+    }
+
     if failed > 0 {
         std::process::exit(1);
     }
 }
 
 /// Parse and run tests from a single test file.
-/// Returns (total, passed, failed) counts.
-fn run_test_file(file: &PathBuf, content: &str, update: bool) -> (usize, usize, usize) {
-    let mut total = 0;
-    let mut passed = 0;
-    let mut failed = 0;
-
-    // Simple parsing: split by "-- test:" and "-- expect:"
-    let mut current_test: Option<(&str, String)> = None;
-
-    for line in content.lines() {
-        if line.starts_with("-- test:") {
-            // Save previous test if any
-            if let Some((name, code)) = current_test.take() {
-                // No expect found, treat as parse-only test
-                if run_single_test(file, name, &code, None, update) {
-                    passed += 1;
-                } else {
-                    failed += 1;
-                }
-                total += 1;
-            }
-            let name = line.strip_prefix("-- test:").unwrap().trim();
-            current_test = Some((name, String::new()));
-        } else if line.starts_with("-- expect:") {
-            if let Some((name, code)) = current_test.take() {
-                let expected = line.strip_prefix("-- expect:").unwrap().trim();
-                if run_single_test(file, name, &code, Some(expected), update) {
-                    passed += 1;
-                } else {
-                    failed += 1;
-                }
-                total += 1;
-            }
-        } else if let Some((name, ref mut code)) = current_test {
-            if !code.is_empty() {
-                code.push('\n');
-            }
-            code.push_str(line);
+fn run_test_file(
+    file: &PathBuf,
+    content: &str,
+    update: bool,
+    filters: &[String],
+    diff: DiffOptions,
+) -> FileReport {
+    let mut cases = Vec::new();
+    let mut updates = Vec::new();
+
+    for (index, case) in test_file::parse_test_file(content).iter().enumerate() {
+        if !matches_filters(case.name, filters) {
+            cases.push(CaseReport {
+                name: case.name.to_string(),
+                duration: Duration::ZERO,
+                result: CaseResult::Filtered,
+            });
+            continue;
         }
+        let started = Instant::now();
+        let outcome = run_single_test(case, update, diff);
+        let result = match outcome {
+            TestOutcome::Passed => CaseResult::Passed,
+            TestOutcome::Failed(details) => CaseResult::Failed(details),
+            TestOutcome::Updated(values) => {
+                updates.extend(values.into_iter().map(|(expectation, value)| {
+                    test_file::ExpectationUpdate {
+                        case: index,
+                        expectation,
+                        value,
+                    }
+                }));
+                CaseResult::Passed
+            }
+        };
+        cases.push(CaseReport {
+            name: case.name.to_string(),
+            duration: started.elapsed(),
+            result,
+        });
     }
 
-    // Handle last test without expect
-    if let Some((name, code)) = current_test {
-        if run_single_test(file, name, &code, None, update) {
-            passed += 1;
-        } else {
-            failed += 1;
-        }
-        total += 1;
+    if let Err(e) = test_file::update_test_file(file, content, &updates) {
+        eprintln!("{}", e);
+        std::process::exit(1);
     }
 
-    (total, passed, failed)
+    FileReport {
+        path: file.display().to_string(),
+        cases,
+    }
 }
 
-/// Run a single test case.
-fn run_single_test(
-    file: &PathBuf,
-    name: &str,
-    code: &str,
-    expected: Option<&str>,
-    _update: bool,
-) -> bool {
-    eprint!("  {} ... ", name);
+enum TestOutcome {
+    Passed,
+    /// Mismatching expectations or the evaluation error, without colors
+    Failed(String),
+    /// `--update`: these expectations (by index) are missing or wrong and will be
+    /// rewritten to the actual values
+    Updated(Vec<(usize, serde_json::Value)>),
+}
+
+/// Run a single test case, checking every expectation at its point in the
+/// directive sequence.
+fn run_single_test(case: &test_file::TestCase, update: bool, diff: DiffOptions) -> TestOutcome {
+    eprint!("  {} ... ", case.name);
 
     // Evaluate the code
-    let result = eval_code_to_json(code, 100);
-
-    match (&result, expected) {
-        (Ok(actual), Some(expected)) => {
-            // Parse expected JSON
-            match serde_json::from_str::<serde_json::Value>(expected) {
-                Ok(expected_val) => {
-                    if actual == &expected_val {
-                        eprintln!("ok");
-                        true
-                    } else {
-                        eprintln!("FAILED");
-                        eprintln!("    expected: {}", expected);
-                        eprintln!("    actual:   {}", actual);
-                        false
-                    }
+    let checkpoints = match eval_test_checkpoints(&case.code, 100) {
+        Ok(checkpoints) => checkpoints,
+        Err(e) => {
+            eprintln!("FAILED: {}", e);
+            return TestOutcome::Failed(e);
+        }
+    };
+    // The checkpoint of the last directive before `code_offset`
+    let checkpoint_at = |code_offset: usize| {
+        checkpoints
+            .iter()
+            .rfind(|checkpoint| checkpoint.code_offset <= code_offset)
+            .expect("the initial evaluation is always a checkpoint")
+    };
+
+    if case.expectations.is_empty() {
+        let actual = &checkpoint_at(case.code.len()).value;
+        if update {
+            eprintln!("updated ({})", actual);
+            return TestOutcome::Updated(vec![(0, actual.clone())]);
+        }
+        // No expected value - just check it runs
+        eprintln!("ok ({})", actual);
+        return TestOutcome::Passed;
+    }
+
+    let mut mismatches = Vec::new();
+    for (index, expectation) in case.expectations.iter().enumerate() {
+        let checkpoint = checkpoint_at(expectation.code_offset);
+        let expected = expectation.parse();
+        if expected.as_ref() != Ok(&checkpoint.value) {
+            mismatches.push((index, expectation, checkpoint, expected));
+        }
+    }
+
+    if mismatches.is_empty() {
+        eprintln!("ok");
+        return TestOutcome::Passed;
+    }
+
+    let count = case.expectations.len();
+    let describe = |color: bool| {
+        let mut out = String::new();
+        for (index, expectation, checkpoint, expected) in &mismatches {
+            let _ = writeln!(
+                out,
+                "expectation {} of {} at {} ms:",
+                index + 1,
+                count,
+                checkpoint.elapsed_ms
+            );
+            match expected {
+                Ok(expected) => out.push_str(&json_diff::render_differences(
+                    &json_diff::diff_json("result", expected, &checkpoint.value, diff.max_depth),
+                    "  ",
+                    color,
+                )),
+                Err(e) if !update => {
+                    let _ = writeln!(out, "  invalid expected JSON: {}", e);
                 }
-                Err(e) => {
-                    eprintln!("FAILED (invalid expected JSON: {})", e);
-                    false
+                Err(_) => {
+                    let _ = writeln!(out, "  expected: {}", expectation.expected.trim());
+                    let _ = writeln!(out, "  actual:   {}", checkpoint.value);
                 }
             }
         }
-        (Ok(actual), None) => {
-            // No expected value - just check it runs
-            eprintln!("ok ({})", actual);
-            true
-        }
-        (Err(e), _) => {
-            eprintln!("FAILED: {}", e);
-            false
-        }
+        out
+    };
+    eprintln!("{}", if update { "updated" } else { "FAILED" });
+    for line in describe(diff.color).lines() {
+        eprintln!("    {}", line);
+    }
+
+    if update {
+        TestOutcome::Updated(
+            mismatches
+                .into_iter()
+                .map(|(index, _, checkpoint, _)| (index, checkpoint.value.clone()))
+                .collect(),
+        )
+    } else {
+        TestOutcome::Failed(describe(false))
     }
 }
 
 /// Command to advance virtual time in tests.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 enum TestCommand {
     /// Advance virtual time by milliseconds
     AdvanceMs(u64),
 }
 
-/// Pre-process test code to extract Test/advance directives.
-/// Returns the cleaned code (without Test/advance lines) and a list of commands.
-fn extract_test_commands(code: &str) -> (String, Vec<TestCommand>) {
-    let mut cleaned_lines = Vec::new();
-    let mut commands = Vec::new();
-
-    for line in code.lines() {
-        let trimmed = line.trim();
+/// Program of a test without its directives, and the directives with the offsets
+/// where they end.
+type TestProgram<'code> = (Vec<Spanned<Expression<'code>>>, Vec<(TestCommand, usize)>);
 
-        // Check for Test/advance(milliseconds: N)
-        if let Some(ms) = parse_test_advance(trimmed) {
-            commands.push(TestCommand::AdvanceMs(ms));
-        } else {
-            cleaned_lines.push(line);
-        }
+/// Splits the top-level `Test/...` calls off a parsed test program. Returns the
+/// program to compile and the directives in source order, each with the byte
+/// offset in `code` where its call ends.
+fn extract_test_commands<'code>(
+    code: &str,
+    expressions: Vec<Spanned<Expression<'code>>>,
+) -> Result<TestProgram<'code>, String> {
+    let mut program = Vec::new();
+    let mut commands = Vec::new();
+    for expression in expressions {
+        let Expression::FunctionCall { path, arguments } = &expression.node else {
+            program.push(expression);
+            continue;
+        };
+        let ["Test", function] = path.as_slice() else {
+            program.push(expression);
+            continue;
+        };
+        let command = test_command(code, function, arguments).map_err(|e| {
+            let (line, _) = diagnostics::line_column(code, expression.span.start);
+            format!("{} (line {} of the test)", e, line)
+        })?;
+        commands.push((command, expression.span.end));
     }
-
-    (cleaned_lines.join("\n"), commands)
+    Ok((program, commands))
 }
 
-/// Parse a Test/advance(milliseconds: N) call and extract the milliseconds value.
-fn parse_test_advance(line: &str) -> Option<u64> {
-    // Match patterns like:
-    // - Test/advance(milliseconds: 1000)
-    // - Test/advance(milliseconds: 1000)  # comment
-
-    let line = line.split('#').next()?.trim(); // Remove comments
-
-    if !line.starts_with("Test/advance(") || !line.ends_with(')') {
-        return None;
-    }
-
-    // Extract the part between parentheses
-    let inner = line.strip_prefix("Test/advance(")?.strip_suffix(')')?;
-
-    // Look for "milliseconds:" pattern
-    let parts: Vec<&str> = inner.split(':').collect();
-    if parts.len() != 2 {
-        return None;
-    }
-
-    if parts[0].trim() != "milliseconds" {
-        return None;
+/// `Test/advance(milliseconds: N)`. `Test/inject` is rejected: the engine cannot send
+/// events by path yet.
+fn test_command(
+    code: &str,
+    function: &str,
+    arguments: &[Spanned<Argument>],
+) -> Result<TestCommand, String> {
+    let expected: &[&str] = match function {
+        "advance" => &["milliseconds"],
+        "inject" => {
+            return Err(
+                "`Test/inject` is not supported yet: the engine cannot send events by path"
+                    .to_string(),
+            );
+        }
+        _ => return Err(format!("Unknown test directive `Test/{}`", function)),
+    };
+    if let Some(argument) = arguments
+        .iter()
+        .find(|argument| !expected.contains(&argument.node.name))
+    {
+        return Err(format!(
+            "`Test/{}` has no argument `{}`",
+            function, argument.node.name
+        ));
     }
-
-    // Parse the number
-    parts[1].trim().parse().ok()
+    let argument = |name: &str| {
+        arguments
+            .iter()
+            .find(|argument| argument.node.name == name)
+            .and_then(|argument| argument.node.value.as_ref())
+    };
+    let source = |value: &Spanned<Expression>| code[value.span.start..value.span.end].trim();
+
+    let milliseconds = argument("milliseconds").ok_or("`Test/advance` needs `milliseconds`")?;
+    source(milliseconds)
+        .parse()
+        .map(TestCommand::AdvanceMs)
+        .map_err(|_| {
+            format!(
+                "`milliseconds` must be a whole number, not `{}`",
+                source(milliseconds)
+            )
+        })
 }
 
 /// Run the event loop until quiescent, with TestClock for timers.
@@ -320,15 +944,237 @@ fn advance_time(event_loop: &mut EventLoop, clock: &mut TestClock, ms: u64, max_
     }
 }
 
-/// Evaluate code and return the result as JSON value.
-fn eval_code_to_json(code: &str, max_ticks: u64) -> Result<serde_json::Value, String> {
-    // Extract Test/advance commands from the code
-    let (cleaned_code, commands) = extract_test_commands(code);
+/// How far `eval` and `run` advance a program. Timers fire in virtual time, so a
+/// program with an interval never becomes quiescent on its own; the time budget
+/// decides how much of it runs, while `max_ticks` only guards against runaway loops.
+#[derive(Clone, Copy, Debug)]
+struct RunBudget {
+    max_ticks: u64,
+    /// Virtual time after which no timer fires
+    max_time_ms: Option<u64>,
+    max_timer_fires: Option<u64>,
+}
+
+/// Which limit ended a run, reported as `"stopped"` in the JSON output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum StopReason {
+    /// Nothing dirty and no pending timers
+    Quiescent,
+    /// `max_time_ms` or `max_timer_fires` reached with timers still pending
+    TimeBudget,
+    /// `max_ticks` reached with nodes still dirty
+    TickBudget,
+    /// SIGINT or SIGTERM arrived while `boon run --state` was running
+    Interrupted,
+}
+
+impl StopReason {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Quiescent => "quiescent",
+            Self::TimeBudget => "time_budget",
+            Self::TickBudget => "tick_budget",
+            Self::Interrupted => "interrupted",
+        }
+    }
+}
+
+/// Marks every node dirty so the first ticks evaluate the whole program, also after
+/// a snapshot was restored into it.
+fn mark_all_dirty(event_loop: &mut EventLoop) {
+    let all_slots: Vec<_> = (0..event_loop.arena_len())
+        .filter_map(|idx| {
+            let slot = boon::engine_v2::arena::SlotId {
+                index: u32::try_from(idx).ok()?,
+                generation: 0,
+            };
+            event_loop.is_valid(slot).then_some(slot)
+        })
+        .collect();
+
+    for slot in all_slots {
+        event_loop.mark_dirty(slot, boon::engine_v2::address::Port::Output);
+    }
+}
+
+/// Runs the initial evaluation, then jumps virtual time from one timer deadline to
+/// the next, firing timers one at a time (see [`advance_time`]) until nothing is
+/// left to do or a budget runs out.
+fn run_with_budget(
+    event_loop: &mut EventLoop,
+    clock: &mut TestClock,
+    budget: RunBudget,
+) -> StopReason {
+    run_with_budget_until(event_loop, clock, budget, |_| true)
+}
+
+/// [`run_with_budget`] that calls `after_tick` after every tick and stops as
+/// [`StopReason::Interrupted`] once it returns `false`.
+fn run_with_budget_until(
+    event_loop: &mut EventLoop,
+    clock: &mut TestClock,
+    budget: RunBudget,
+    after_tick: impl FnMut(&EventLoop) -> bool,
+) -> StopReason {
+    run_counting_fires(event_loop, clock, budget, after_tick).0
+}
+
+/// [`run_with_budget_until`] that also returns how many timers fired.
+fn run_counting_fires(
+    event_loop: &mut EventLoop,
+    clock: &mut TestClock,
+    budget: RunBudget,
+    mut after_tick: impl FnMut(&EventLoop) -> bool,
+) -> (StopReason, u64) {
+    let mut ticks = 0;
+    loop {
+        if ticks == budget.max_ticks {
+            warn_tick_budget_exhausted(budget.max_ticks);
+            return (StopReason::TickBudget, 0);
+        }
+        event_loop.run_tick();
+        ticks += 1;
+        if !after_tick(event_loop) {
+            return (StopReason::Interrupted, 0);
+        }
+        for (node_id, interval_ms) in event_loop.take_pending_timers() {
+            clock.register_timer(node_id, interval_ms);
+        }
+        if event_loop.dirty_nodes.is_empty() {
+            break;
+        }
+    }
+
+    let mut fires = 0;
+    while let Some(wait_ms) = clock.time_to_next_timer() {
+        if budget
+            .max_time_ms
+            .is_some_and(|max_time_ms| clock.now_ms() + wait_ms > max_time_ms)
+        {
+            return (StopReason::TimeBudget, fires);
+        }
+        let due = clock.advance_by(wait_ms);
+        for (index, node_id) in due.iter().copied().enumerate() {
+            if budget
+                .max_timer_fires
+                .is_some_and(|max_timer_fires| fires == max_timer_fires)
+            {
+                warn_missed_timer_ticks(due.len() - index, clock.now_ms());
+                return (StopReason::TimeBudget, fires);
+            }
+            fires += 1;
+            event_loop.fire_timer(node_id);
+            loop {
+                if ticks == budget.max_ticks {
+                    warn_tick_budget_exhausted(budget.max_ticks);
+                    warn_missed_timer_ticks(due.len() - index - 1, clock.now_ms());
+                    return (StopReason::TickBudget, fires);
+                }
+                event_loop.run_tick();
+                ticks += 1;
+                if !after_tick(event_loop) {
+                    return (StopReason::Interrupted, fires);
+                }
+                // TestClock already rescheduled the timer that fired
+                let _ = event_loop.take_pending_timers();
+                if event_loop.dirty_nodes.is_empty() {
+                    break;
+                }
+            }
+        }
+    }
+    (StopReason::Quiescent, fires)
+}
+
+/// Cuts every Text in `result` to the soft limit (`--max-text-bytes`) before it is
+/// printed, and reports the first cut as a `text-size` warning.
+fn cap_result_texts(result: &mut serde_json::Value) {
+    fn cap(value: &mut serde_json::Value, truncated: &mut bool) {
+        match value {
+            serde_json::Value::String(text) => *truncated |= truncate_oversized_text(text),
+            serde_json::Value::Array(items) => {
+                items.iter_mut().for_each(|item| cap(item, truncated));
+            }
+            serde_json::Value::Object(fields) => {
+                fields.values_mut().for_each(|field| cap(field, truncated));
+            }
+            _ => {}
+        }
+    }
+    let mut truncated = false;
+    cap(result, &mut truncated);
+    if truncated {
+        emit_warning(
+            LogLevel::Warn,
+            "text-size",
+            format!(
+                "The result holds Text longer than {} bytes; it is truncated",
+                max_text_bytes()
+            ),
+        );
+    }
+}
+
+/// Reports a run cut off by `--ticks`. The tick budget is the CLI's growth guard: a
+/// loop or a list that keeps growing stops there, and its remaining updates are dropped.
+fn warn_tick_budget_exhausted(max_ticks: u64) {
+    emit_warning(
+        LogLevel::Warn,
+        "growth",
+        format!(
+            "Stopped after {max_ticks} ticks with updates still pending; \
+             the remaining updates were dropped (raise --ticks to run further)"
+        ),
+    );
+}
+
+/// Reports timer fires that were due at `now_ms` but never delivered because a budget
+/// stopped the run in the middle of them.
+fn warn_missed_timer_ticks(missed: usize, now_ms: u64) {
+    if missed > 0 {
+        emit_warning(
+            LogLevel::Warn,
+            "timer",
+            format!(
+                "Timer ticks due at {now_ms} ms did not fire before the run stopped: {missed} skipped"
+            ),
+        );
+    }
+}
+
+/// Runs until no node is dirty, registering new timers with `clock` but not
+/// advancing virtual time.
+fn settle(event_loop: &mut EventLoop, clock: &mut TestClock, max_ticks: u64) -> StopReason {
+    let now_ms = clock.now_ms();
+    run_with_budget(
+        event_loop,
+        clock,
+        RunBudget {
+            max_ticks,
+            max_time_ms: Some(now_ms),
+            max_timer_fires: None,
+        },
+    )
+}
+
+/// Result value of a test at one point of its directive sequence.
+struct Checkpoint {
+    value: serde_json::Value,
+    /// Virtual time advanced so far
+    elapsed_ms: u64,
+    /// Byte offset in the test code where the directive that led here ends; 0 for
+    /// the initial evaluation
+    code_offset: usize,
+}
 
+/// Evaluate test code and return the result after the initial evaluation and after
+/// each `Test/...` directive, in source order.
+fn eval_test_checkpoints(code: &str, max_ticks: u64) -> Result<Vec<Checkpoint>, String> {
     reset_expression_depth();
 
     // Lex the code
-    let (tokens, lex_errors) = lexer().parse(&cleaned_code).into_output_errors();
+    let (tokens, lex_errors) = lexer().parse(code).into_output_errors();
 
     if !lex_errors.is_empty() {
         return Err(format!("Lexer errors: {:?}", lex_errors));
@@ -338,7 +1184,7 @@ fn eval_code_to_json(code: &str, max_ticks: u64) -> Result<serde_json::Value, St
     tokens.retain(|t| !matches!(t.node, boon::parser::Token::Comment(_)));
 
     let input = tokens.map(
-        span_at(cleaned_code.len()),
+        span_at(code.len()),
         |Spanned {
              node,
              span,
@@ -353,6 +1199,7 @@ fn eval_code_to_json(code: &str, max_ticks: u64) -> Result<serde_json::Value, St
     }
 
     let expressions = expressions.ok_or("No expressions from parser")?;
+    let (expressions, commands) = extract_test_commands(code, expressions)?;
 
     let mut event_loop = EventLoop::new();
     let mut ctx = CompileContext::new(&mut event_loop);
@@ -361,103 +1208,167 @@ fn eval_code_to_json(code: &str, max_ticks: u64) -> Result<serde_json::Value, St
     // Create TestClock for virtual time
     let mut clock = TestClock::new();
 
-    // Mark all nodes dirty
-    let all_slots: Vec<_> = (0..event_loop.arena_len() as u32)
-        .filter_map(|idx| {
-            let slot = boon::engine_v2::arena::SlotId {
-                index: idx,
-                generation: 0,
-            };
-            if event_loop.is_valid(slot) {
-                Some(slot)
-            } else {
-                None
-            }
-        })
-        .collect();
+    mark_all_dirty(&mut event_loop);
 
-    for slot in all_slots {
-        event_loop.mark_dirty(slot, boon::engine_v2::address::Port::Output);
-    }
+    // Get result - use expand_payload_to_json to resolve ListHandle/ObjectHandle
+    let current_value = |event_loop: &EventLoop| {
+        result_slot
+            .and_then(|slot| event_loop.get_current_value(slot))
+            .map(|value| event_loop.expand_payload_to_json(value))
+            .unwrap_or(serde_json::Value::Null)
+    };
 
     // Run initial evaluation
     run_with_clock(&mut event_loop, &mut clock, max_ticks);
+    let mut elapsed_ms = 0;
+    let mut checkpoints = vec![Checkpoint {
+        value: current_value(&event_loop),
+        elapsed_ms,
+        code_offset: 0,
+    }];
 
     // Execute Test/advance commands
-    for cmd in commands {
+    for (cmd, code_offset) in commands {
         match cmd {
             TestCommand::AdvanceMs(ms) => {
                 advance_time(&mut event_loop, &mut clock, ms, max_ticks);
+                elapsed_ms += ms;
             }
         }
+        checkpoints.push(Checkpoint {
+            value: current_value(&event_loop),
+            elapsed_ms,
+            code_offset,
+        });
     }
 
-    // Get result - use expand_payload_to_json to resolve ListHandle/ObjectHandle
-    if let Some(slot) = result_slot {
-        if let Some(value) = event_loop.get_current_value(slot) {
-            Ok(event_loop.expand_payload_to_json(value))
-        } else {
-            Ok(serde_json::Value::Null)
-        }
-    } else {
-        Ok(serde_json::Value::Null)
-    }
+    Ok(checkpoints)
 }
 
-fn check_code(code: &str, file: &PathBuf) {
-    eprintln!("Checking: {}", file.display());
-
-    reset_expression_depth();
-
-    // Lex the code
-    let (tokens, lex_errors) = lexer().parse(code).into_output_errors();
-
-    if !lex_errors.is_empty() {
-        eprintln!("Lexer errors: {:?}", lex_errors);
-        std::process::exit(1);
+fn check_code(code: &str, file: &PathBuf, deny_deprecated: bool, emit_ast: bool, json: bool) {
+    if !json {
+        eprintln!("Checking: {}", file.display());
     }
 
-    let mut tokens = match tokens {
-        Some(t) => t,
-        None => {
-            eprintln!("No tokens from lexer");
+    let exprs = match diagnostics::parse_with_diagnostics(code) {
+        Ok(exprs) => exprs,
+        Err(errors) => {
+            diagnostics::report(&errors, code, file, json);
             std::process::exit(1);
         }
     };
+    if !json {
+        eprintln!("Parse OK: {} top-level expressions", exprs.len());
+    }
+    if emit_ast {
+        match serde_json::to_string_pretty(&exprs) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("Failed to serialize AST: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
 
-    // Filter comments
-    tokens.retain(|t| !matches!(t.node, boon::parser::Token::Comment(_)));
-
-    // Create input with span mapping
-    let input = tokens.map(
-        span_at(code.len()),
-        |Spanned {
-             node,
-             span,
-             persistence: _,
-         }| (node, span),
-    );
-
-    // Parse
-    let (expressions, parse_errors) = parser().parse(input).into_output_errors();
-
-    if !parse_errors.is_empty() {
-        eprintln!("Parser errors: {:?}", parse_errors);
+    let mut errors = diagnostics::reference_errors(exprs, code);
+    errors.extend(diagnostics::unknown_function_errors(code));
+    if !errors.is_empty() {
+        diagnostics::report(&errors, code, file, json);
         std::process::exit(1);
     }
 
-    match expressions {
-        Some(exprs) => {
-            eprintln!("Parse OK: {} top-level expressions", exprs.len());
+    let mut warnings = Vec::new();
+    match check_source_exhaustiveness(code) {
+        Ok(exhaustiveness_warnings) => {
+            warnings.extend(exhaustiveness_warnings.iter().map(|warning| {
+                Diagnostic::new(
+                    Severity::Warning,
+                    warning.message(code),
+                    warning.span.start..warning.span.end,
+                    code,
+                )
+            }));
         }
-        None => {
-            eprintln!("No expressions from parser");
+        Err(error) => {
+            eprintln!("Reference errors: {error}");
             std::process::exit(1);
         }
     }
+    warnings.extend(diagnostics::unused_variable_warnings(code));
+
+    let deprecated_calls = deprecated_builtin_calls(code);
+    warnings.extend(deprecated_calls.iter().map(|call| {
+        Diagnostic::new(
+            Severity::Warning,
+            format!("deprecated: {}", call.alias.message()),
+            call.range.clone(),
+            code,
+        )
+    }));
+    diagnostics::report(&warnings, code, file, json);
+
+    if deny_deprecated && !deprecated_calls.is_empty() {
+        eprintln!(
+            "{} deprecated builtin call(s) denied by --deny-deprecated",
+            deprecated_calls.len()
+        );
+        std::process::exit(1);
+    }
 }
 
-fn eval_code_with_persistence(code: &str, max_ticks: u64, state_file: Option<PathBuf>) {
+/// `boon fmt`: prints each formatted file, or with `--write` rewrites the ones that
+/// changed. With `--check` nothing is written and the exit code is non-zero if any
+/// file is not formatted or does not parse.
+fn format_files(files: &[PathBuf], check: bool, write: bool) {
+    let mut failed = false;
+
+    for file in files {
+        let code = match fs::read_to_string(file) {
+            Ok(code) => code,
+            Err(e) => {
+                eprintln!("Error reading {}: {}", file.display(), e);
+                failed = true;
+                continue;
+            }
+        };
+        let Some(formatted) = formatter::format(&code) else {
+            eprintln!(
+                "{}: cannot format, the file has lex or parse errors (see `boon check`)",
+                file.display()
+            );
+            failed = true;
+            continue;
+        };
+
+        if check {
+            if formatted != code {
+                eprintln!("{}: not formatted", file.display());
+                failed = true;
+            }
+        } else if !write {
+            print!("{}", formatted);
+        } else if formatted != code {
+            if let Err(e) = fs::write(file, &formatted) {
+                eprintln!("Error writing {}: {}", file.display(), e);
+                failed = true;
+            } else {
+                eprintln!("Formatted: {}", file.display());
+            }
+        }
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+fn eval_code_with_persistence(
+    code: &str,
+    budget: RunBudget,
+    state_file: Option<PathBuf>,
+    state_save_interval: Option<Duration>,
+    interactive: bool,
+) {
     use boon::engine_v2::snapshot::GraphSnapshot;
 
     reset_expression_depth();
@@ -552,59 +1463,72 @@ fn eval_code_with_persistence(code: &str, max_ticks: u64, state_file: Option<Pat
                         event_loop.restore_snapshot(&snapshot);
                         eprintln!("Loaded state from: {}", state_path.display());
                     }
+                    // Running on would overwrite the file with fresh state on exit
                     Err(e) => {
-                        eprintln!("Warning: Failed to parse state file: {}", e);
+                        eprintln!("State file {} cannot be read: {}", state_path.display(), e);
+                        eprintln!(
+                            "Run it with the boon that wrote it, or delete it to start from scratch."
+                        );
+                        std::process::exit(1);
                     }
                 },
                 Err(e) => {
                     eprintln!("Warning: Failed to read state file: {}", e);
+                    emit_warning(
+                        LogLevel::Warn,
+                        "restore",
+                        format!(
+                            "State file {} was not restored, the run starts from scratch: {e}",
+                            state_path.display()
+                        ),
+                    );
                 }
             }
         }
     }
 
-    // Mark all nodes as dirty to trigger initial evaluation
-    let all_slots: Vec<_> = (0..event_loop.arena_len() as u32)
-        .filter_map(|idx| {
-            let slot = boon::engine_v2::arena::SlotId {
-                index: idx,
-                generation: 0,
-            };
-            if event_loop.is_valid(slot) {
-                Some(slot)
-            } else {
-                None
+    mark_all_dirty(&mut event_loop);
+
+    // Run until quiescent or a budget runs out; timers fire in virtual time
+    let mut clock = TestClock::new();
+    let stopped = if interactive {
+        let stopped = settle(&mut event_loop, &mut clock, budget.max_ticks);
+        interactive::run_interactive(
+            code,
+            &mut event_loop,
+            &mut clock,
+            result_slot,
+            budget.max_ticks,
+        );
+        stopped
+    } else if let Some(state_path) = &state_file {
+        // Interactive runs block on stdin, so only batch runs stop on a signal
+        state::install_interrupt_handler();
+        let mut periodic_save = state_save_interval
+            .map(|interval| state::PeriodicSave::new(state_path.clone(), interval));
+        run_with_budget_until(&mut event_loop, &mut clock, budget, |event_loop| {
+            if let Some(periodic_save) = &mut periodic_save {
+                periodic_save.save_if_due(event_loop);
             }
+            !state::interrupted()
         })
-        .collect();
-
-    for slot in all_slots {
-        event_loop.mark_dirty(slot, boon::engine_v2::address::Port::Output);
-    }
-
-    // Run until quiescent or max ticks
-    for tick in 0..max_ticks {
-        event_loop.run_tick();
-        if event_loop.dirty_nodes.is_empty() && event_loop.timer_queue.is_empty() {
-            eprintln!("Quiescent after {} ticks", tick + 1);
-            break;
-        }
-    }
+    } else {
+        run_with_budget(&mut event_loop, &mut clock, budget)
+    };
+    eprintln!(
+        "Stopped ({}) after {} ticks at {} ms",
+        stopped.as_str(),
+        event_loop.current_tick,
+        clock.now_ms()
+    );
 
     // Save state to file if provided
     if let Some(ref state_path) = state_file {
-        let snapshot = event_loop.create_snapshot();
-        match snapshot.to_json() {
-            Ok(json_str) => match fs::write(state_path, json_str) {
-                Ok(_) => {
-                    eprintln!("Saved state to: {}", state_path.display());
-                }
-                Err(e) => {
-                    eprintln!("Warning: Failed to write state file: {}", e);
-                }
-            },
+        match state::save_state(&event_loop, state_path) {
+            Ok(()) => eprintln!("Saved state to: {}", state_path.display()),
             Err(e) => {
-                eprintln!("Warning: Failed to serialize state: {}", e);
+                eprintln!("Warning: {}", e);
+                emit_warning(LogLevel::Warn, "persistence", e);
             }
         }
     }
@@ -612,12 +1536,15 @@ fn eval_code_with_persistence(code: &str, max_ticks: u64, state_file: Option<Pat
     // Output result as JSON
     if let Some(slot) = result_slot {
         if let Some(value) = event_loop.get_current_value(slot) {
+            let mut result = event_loop.expand_payload_to_json(value);
+            cap_result_texts(&mut result);
             println!(
                 "{}",
                 serde_json::json!({
                     "status": "ok",
                     "ticks": event_loop.current_tick,
-                    "result": event_loop.expand_payload_to_json(value)
+                    "stopped": stopped,
+                    "result": result
                 })
             );
         } else {
@@ -626,6 +1553,7 @@ fn eval_code_with_persistence(code: &str, max_ticks: u64, state_file: Option<Pat
                 serde_json::json!({
                     "status": "ok",
                     "ticks": event_loop.current_tick,
+                    "stopped": stopped,
                 })
             );
         }
@@ -635,14 +1563,39 @@ fn eval_code_with_persistence(code: &str, max_ticks: u64, state_file: Option<Pat
             serde_json::json!({
                 "status": "ok",
                 "ticks": event_loop.current_tick,
+                "stopped": stopped,
                 "note": "No expressions to evaluate"
             })
         );
     }
 }
 
-fn eval_code(code: &str, max_ticks: u64) {
+/// `boon eval --stats`: where the time went and how big the graph grew.
+#[derive(Clone, Debug, PartialEq)]
+struct EvalStats {
+    parse: Duration,
+    compile: Duration,
+    eval: Duration,
+    arena_slots: usize,
+    timers_fired: u64,
+}
+
+impl EvalStats {
+    /// Adds the statistics to the JSON output; durations in milliseconds, to the
+    /// microsecond.
+    fn insert_into(&self, output: &mut serde_json::Value) {
+        let milliseconds = |duration: Duration| (duration.as_secs_f64() * 1e6).round() / 1e3;
+        output["parse_ms"] = milliseconds(self.parse).into();
+        output["compile_ms"] = milliseconds(self.compile).into();
+        output["eval_ms"] = milliseconds(self.eval).into();
+        output["arena_slots"] = self.arena_slots.into();
+        output["timers_fired"] = self.timers_fired.into();
+    }
+}
+
+fn eval_code(code: &str, budget: RunBudget, stats: bool) -> Option<serde_json::Value> {
     reset_expression_depth();
+    let parse_started = Instant::now();
 
     // Lex the code
     let (tokens, lex_errors) = lexer().parse(code).into_output_errors();
@@ -656,7 +1609,7 @@ fn eval_code(code: &str, max_ticks: u64) {
                 "error": format!("Lexer errors: {:?}", lex_errors)
             })
         );
-        return;
+        return None;
     }
 
     let mut tokens = match tokens {
@@ -670,7 +1623,7 @@ fn eval_code(code: &str, max_ticks: u64) {
                     "error": "No tokens from lexer"
                 })
             );
-            return;
+            return None;
         }
     };
 
@@ -699,7 +1652,7 @@ fn eval_code(code: &str, max_ticks: u64) {
                 "error": format!("Parser errors: {:?}", parse_errors)
             })
         );
-        return;
+        return None;
     }
 
     let expressions = match expressions {
@@ -714,16 +1667,21 @@ fn eval_code(code: &str, max_ticks: u64) {
                     "note": "No expressions to evaluate"
                 })
             );
-            return;
+            return None;
         }
     };
 
+    let parse_duration = parse_started.elapsed();
+
     // Create event loop and compile context
+    let compile_started = Instant::now();
     let mut event_loop = EventLoop::new();
     let mut ctx = CompileContext::new(&mut event_loop);
 
     // Compile the program
     let result_slot = ctx.compile_program(&expressions);
+    let compile_duration = compile_started.elapsed();
+    let eval_started = Instant::now();
 
     // Mark all nodes as dirty to trigger initial evaluation
     // This ensures all Producers emit their initial values
@@ -745,44 +1703,397 @@ fn eval_code(code: &str, max_ticks: u64) {
         event_loop.mark_dirty(slot, boon::engine_v2::address::Port::Output);
     }
 
-    // Run until quiescent or max ticks
-    // Note: Timers can re-fire, so we need to check both dirty_nodes AND timer_queue
-    for tick in 0..max_ticks {
-        event_loop.run_tick();
-        if event_loop.dirty_nodes.is_empty() && event_loop.timer_queue.is_empty() {
-            eprintln!("Quiescent after {} ticks", tick + 1);
-            break;
+    // Run until quiescent or a budget runs out; timers fire in virtual time
+    let mut clock = TestClock::new();
+    let (stopped, timers_fired) = run_counting_fires(&mut event_loop, &mut clock, budget, |_| true);
+    let eval_duration = eval_started.elapsed();
+    eprintln!(
+        "Stopped ({}) after {} ticks at {} ms",
+        stopped.as_str(),
+        event_loop.current_tick,
+        clock.now_ms()
+    );
+
+    // Output result as JSON - use expand_payload_to_json for lists/objects
+    let mut output = serde_json::json!({
+        "status": "ok",
+        "ticks": event_loop.current_tick,
+        "stopped": stopped,
+    });
+    let result = result_slot.and_then(|slot| {
+        event_loop
+            .get_current_value(slot)
+            .map(|value| event_loop.expand_payload_to_json(value))
+    });
+    match &result {
+        Some(result) => output["result"] = result.clone(),
+        None if result_slot.is_none() => output["note"] = "No expressions to evaluate".into(),
+        None => {}
+    }
+    if stats {
+        EvalStats {
+            parse: parse_duration,
+            compile: compile_duration,
+            eval: eval_duration,
+            arena_slots: event_loop.arena_len(),
+            timers_fired,
         }
+        .insert_into(&mut output);
     }
+    println!("{}", output);
+    result
+}
 
-    // Output result as JSON - use expand_payload_to_json for lists/objects
-    if let Some(slot) = result_slot {
-        if let Some(value) = event_loop.get_current_value(slot) {
-            println!(
-                "{}",
-                serde_json::json!({
-                    "status": "ok",
-                    "ticks": event_loop.current_tick,
-                    "result": event_loop.expand_payload_to_json(value)
-                })
-            );
-        } else {
-            println!(
-                "{}",
-                serde_json::json!({
-                    "status": "ok",
-                    "ticks": event_loop.current_tick,
-                })
-            );
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_commands(code: &str) -> Result<(usize, Vec<TestCommand>), String> {
+        let expressions = diagnostics::parse_with_diagnostics(code)
+            .unwrap_or_else(|_| panic!("`{code}` should parse"));
+        let (program, commands) = extract_test_commands(code, expressions)?;
+        let commands = commands.into_iter().map(|(command, _)| command).collect();
+        Ok((program.len(), commands))
+    }
+
+    #[test]
+    fn stats_are_added_next_to_the_result() {
+        let mut output = serde_json::json!({"status": "ok", "ticks": 2, "result": 3});
+        EvalStats {
+            parse: Duration::from_micros(1500),
+            compile: Duration::from_nanos(2_000_400),
+            eval: Duration::ZERO,
+            arena_slots: 12,
+            timers_fired: 0,
         }
-    } else {
-        println!(
-            "{}",
+        .insert_into(&mut output);
+        assert_eq!(
+            output,
             serde_json::json!({
                 "status": "ok",
-                "ticks": event_loop.current_tick,
-                "note": "No expressions to evaluate"
+                "ticks": 2,
+                "result": 3,
+                "parse_ms": 1.5,
+                "compile_ms": 2.0,
+                "eval_ms": 0.0,
+                "arena_slots": 12,
+                "timers_fired": 0
             })
         );
     }
+
+    #[test]
+    fn filters_select_tests_by_any_case_insensitive_substring() {
+        let filters = vec!["COUNTER".to_string(), "timer".to_string()];
+        assert!(matches_filters("Counter increments", &filters));
+        assert!(matches_filters("interval timer fires", &filters));
+        assert!(!matches_filters("text concatenation", &filters));
+        assert!(matches_filters("text concatenation", &[]));
+    }
+
+    #[test]
+    fn test_directives_are_calls_in_the_parsed_program() {
+        let code = "\
+counter: 0
+Test/advance(milliseconds: 1000)
+    Test/advance(milliseconds: 500)  -- indented
+counter
+";
+        assert_eq!(
+            test_commands(code),
+            Ok((
+                2,
+                vec![TestCommand::AdvanceMs(1000), TestCommand::AdvanceMs(500)]
+            ))
+        );
+
+        let error = test_commands("counter\nTest/inject(path: button.event.press)").unwrap_err();
+        assert!(error.starts_with("`Test/inject` is not supported yet"));
+        assert!(test_commands("Test/advance(milliseconds: 1.5)").is_err());
+        assert!(test_commands("Test/advance(seconds: 1)").is_err());
+        let error = test_commands("counter: 0\nTest/wait()").unwrap_err();
+        assert_eq!(
+            error,
+            "Unknown test directive `Test/wait` (line 2 of the test)"
+        );
+    }
+
+    #[test]
+    fn stop_reasons_serialize_in_snake_case() {
+        for reason in [
+            StopReason::Quiescent,
+            StopReason::TimeBudget,
+            StopReason::TickBudget,
+            StopReason::Interrupted,
+        ] {
+            assert_eq!(serde_json::json!(reason), reason.as_str());
+        }
+    }
+
+    #[test]
+    fn appended_path_becomes_the_program_result() {
+        let code = "store: [counter: 5, label: TEXT { five }]\nother: 1";
+        let code = append_path(code, "store.counter").unwrap();
+        let expressions = diagnostics::parse_with_diagnostics(&code).unwrap();
+        let mut event_loop = EventLoop::new();
+        let slot = CompileContext::new(&mut event_loop)
+            .compile_program(&expressions)
+            .unwrap();
+        mark_all_dirty(&mut event_loop);
+        run_until_quiescent(&mut event_loop, 100);
+        let result = event_loop
+            .get_current_value(slot)
+            .map(|value| event_loop.expand_payload_to_json(value));
+        assert_eq!(result, Some(serde_json::json!(5.0)));
+
+        assert_eq!(
+            append_path("a: 1", "document"),
+            Err("`document` is not a top-level variable".to_string())
+        );
+        assert!(append_path("a: 1", "a.").is_err());
+        assert!(append_path("a: 1", "A").is_err());
+    }
+
+    #[test]
+    fn timer_fires_are_counted_within_the_time_budget() {
+        let fires = |code: &str| {
+            let expressions = diagnostics::parse_with_diagnostics(code).unwrap();
+            let mut event_loop = EventLoop::new();
+            CompileContext::new(&mut event_loop).compile_program(&expressions);
+            mark_all_dirty(&mut event_loop);
+            let budget = RunBudget {
+                max_ticks: 1000,
+                max_time_ms: Some(3000),
+                max_timer_fires: None,
+            };
+            run_counting_fires(&mut event_loop, &mut TestClock::new(), budget, |_| true)
+        };
+        assert_eq!(fires("a: 1"), (StopReason::Quiescent, 0));
+        assert_eq!(
+            fires("Duration[seconds: 1] |> Timer/interval()"),
+            (StopReason::TimeBudget, 3)
+        );
+    }
+
+    #[test]
+    fn result_texts_are_capped_at_the_soft_limit() {
+        let warnings = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let sink = warnings.clone();
+        set_warning_hook(move |warning| sink.borrow_mut().push(warning.category));
+        set_max_text_bytes(4);
+        let mut result = serde_json::json!({
+            "short": "abcd",
+            "items": ["abcčd", 12345678.0],
+        });
+        cap_result_texts(&mut result);
+        set_max_text_bytes(DEFAULT_MAX_TEXT_BYTES);
+
+        assert_eq!(
+            result,
+            serde_json::json!({ "short": "abcd", "items": ["abc…", 12345678.0] })
+        );
+        assert_eq!(*warnings.borrow(), vec!["text-size".to_string()]);
+    }
+
+    #[test]
+    fn budget_stops_report_growth_and_missed_timer_warnings() {
+        let warnings = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let sink = warnings.clone();
+        set_warning_hook(move |warning| sink.borrow_mut().push(warning.category));
+        let run = |code: &str, budget: RunBudget| {
+            let expressions = diagnostics::parse_with_diagnostics(code).unwrap();
+            let mut event_loop = EventLoop::new();
+            CompileContext::new(&mut event_loop).compile_program(&expressions);
+            mark_all_dirty(&mut event_loop);
+            run_with_budget(&mut event_loop, &mut TestClock::new(), budget)
+        };
+
+        let settled = RunBudget {
+            max_ticks: 1000,
+            max_time_ms: Some(3000),
+            max_timer_fires: None,
+        };
+        run("Duration[seconds: 1] |> Timer/interval()", settled);
+        assert!(warnings.borrow().is_empty());
+
+        // Both intervals are due at 1000 ms, only one of them may fire
+        let one_fire = RunBudget {
+            max_timer_fires: Some(1),
+            ..settled
+        };
+        let two_timers = "\
+first: Duration[seconds: 1] |> Timer/interval()
+second: Duration[seconds: 1] |> Timer/interval()
+[first: first, second: second]
+";
+        assert_eq!(run(two_timers, one_fire), StopReason::TimeBudget);
+        assert_eq!(*warnings.borrow(), vec!["timer".to_string()]);
+
+        warnings.borrow_mut().clear();
+        let one_tick = RunBudget {
+            max_ticks: 1,
+            ..settled
+        };
+        assert_eq!(run(two_timers, one_tick), StopReason::TickBudget);
+        assert_eq!(*warnings.borrow(), vec!["growth".to_string()]);
+    }
+
+    #[test]
+    fn interrupted_run_restores_its_counter_from_the_state_file() {
+        use boon::engine_v2::snapshot::GraphSnapshot;
+
+        let code = "\
+counter: 0 |> HOLD state {
+    Duration[milliseconds: 1000] |> Timer/interval() |> THEN { state + 1 }
+}
+";
+        let expressions = diagnostics::parse_with_diagnostics(code).unwrap();
+        let state_path =
+            std::env::temp_dir().join(format!("boon-interrupted-{}.json", std::process::id()));
+        let budget = RunBudget {
+            max_ticks: 1000,
+            max_time_ms: None,
+            max_timer_fires: None,
+        };
+        let counter = |event_loop: &EventLoop, slot| {
+            event_loop
+                .get_current_value(slot)
+                .map(|value| event_loop.expand_payload_to_json(value))
+        };
+
+        // The signal arrives after the third timer fire
+        let mut event_loop = EventLoop::new();
+        let slot = CompileContext::new(&mut event_loop)
+            .compile_program(&expressions)
+            .unwrap();
+        mark_all_dirty(&mut event_loop);
+        let stopped = run_with_budget_until(
+            &mut event_loop,
+            &mut TestClock::new(),
+            budget,
+            |event_loop| counter(event_loop, slot) != Some(serde_json::json!(3.0)),
+        );
+        assert_eq!(stopped, StopReason::Interrupted);
+        state::save_state(&event_loop, &state_path).unwrap();
+
+        let mut event_loop = EventLoop::new();
+        let slot = CompileContext::new(&mut event_loop)
+            .compile_program(&expressions)
+            .unwrap();
+        let json = fs::read_to_string(&state_path).unwrap();
+        fs::remove_file(&state_path).unwrap();
+        event_loop.restore_snapshot(&GraphSnapshot::from_json(&json).unwrap());
+        mark_all_dirty(&mut event_loop);
+        let no_fires = RunBudget {
+            max_timer_fires: Some(0),
+            ..budget
+        };
+        run_with_budget(&mut event_loop, &mut TestClock::new(), no_fires);
+        assert_eq!(counter(&event_loop, slot), Some(serde_json::json!(3.0)));
+    }
+
+    #[test]
+    fn stream_extremes_survive_a_state_file_round_trip() {
+        use boon::engine_v2::snapshot::GraphSnapshot;
+
+        let code = "\
+level: 5 |> HOLD state {
+    Duration[milliseconds: 1000] |> Timer/interval() |> THEN { state - 1 }
+}
+lowest: level |> Stream/min()
+highest: level |> Stream/max()
+[lowest: lowest, highest: highest]
+";
+        let expressions = diagnostics::parse_with_diagnostics(code).unwrap();
+        let state_path =
+            std::env::temp_dir().join(format!("boon-extremes-{}.json", std::process::id()));
+        let fires = |max_timer_fires| RunBudget {
+            max_ticks: 1000,
+            max_time_ms: None,
+            max_timer_fires: Some(max_timer_fires),
+        };
+        let extremes = |event_loop: &EventLoop, slot| {
+            event_loop
+                .get_current_value(slot)
+                .map(|value| event_loop.expand_payload_to_json(value))
+        };
+
+        let mut event_loop = EventLoop::new();
+        let slot = CompileContext::new(&mut event_loop)
+            .compile_program(&expressions)
+            .unwrap();
+        mark_all_dirty(&mut event_loop);
+        run_with_budget(&mut event_loop, &mut TestClock::new(), fires(3));
+        assert_eq!(
+            extremes(&event_loop, slot),
+            Some(serde_json::json!({ "highest": 5.0, "lowest": 2.0 }))
+        );
+        state::save_state(&event_loop, &state_path).unwrap();
+
+        // The restored `level` is 2, so a `highest` that was not restored would read 2
+        let mut event_loop = EventLoop::new();
+        let slot = CompileContext::new(&mut event_loop)
+            .compile_program(&expressions)
+            .unwrap();
+        let json = fs::read_to_string(&state_path).unwrap();
+        fs::remove_file(&state_path).unwrap();
+        event_loop.restore_snapshot(&GraphSnapshot::from_json(&json).unwrap());
+        mark_all_dirty(&mut event_loop);
+        run_with_budget(&mut event_loop, &mut TestClock::new(), fires(0));
+        assert_eq!(
+            extremes(&event_loop, slot),
+            Some(serde_json::json!({ "highest": 5.0, "lowest": 2.0 }))
+        );
+        run_with_budget(&mut event_loop, &mut TestClock::new(), fires(1));
+        assert_eq!(
+            extremes(&event_loop, slot),
+            Some(serde_json::json!({ "highest": 5.0, "lowest": 1.0 }))
+        );
+    }
+
+    #[test]
+    fn bool_toggle_survives_a_state_file_round_trip() {
+        use boon::engine_v2::snapshot::GraphSnapshot;
+
+        let code = "\
+tick: Duration[milliseconds: 1000] |> Timer/interval()
+Bool/toggle(on: tick, initial: False)
+";
+        let expressions = diagnostics::parse_with_diagnostics(code).unwrap();
+        let state_path =
+            std::env::temp_dir().join(format!("boon-toggle-{}.json", std::process::id()));
+        let fires = |max_timer_fires| RunBudget {
+            max_ticks: 1000,
+            max_time_ms: None,
+            max_timer_fires: Some(max_timer_fires),
+        };
+        let toggled = |event_loop: &EventLoop, slot| {
+            event_loop
+                .get_current_value(slot)
+                .map(|value| event_loop.expand_payload_to_json(value))
+        };
+
+        let mut event_loop = EventLoop::new();
+        let slot = CompileContext::new(&mut event_loop)
+            .compile_program(&expressions)
+            .unwrap();
+        mark_all_dirty(&mut event_loop);
+        run_with_budget(&mut event_loop, &mut TestClock::new(), fires(3));
+        assert_eq!(toggled(&event_loop, slot), Some(serde_json::json!(true)));
+        state::save_state(&event_loop, &state_path).unwrap();
+
+        // A toggle that was not restored would read its `initial` False
+        let mut event_loop = EventLoop::new();
+        let slot = CompileContext::new(&mut event_loop)
+            .compile_program(&expressions)
+            .unwrap();
+        let json = fs::read_to_string(&state_path).unwrap();
+        fs::remove_file(&state_path).unwrap();
+        event_loop.restore_snapshot(&GraphSnapshot::from_json(&json).unwrap());
+        mark_all_dirty(&mut event_loop);
+        run_with_budget(&mut event_loop, &mut TestClock::new(), fires(0));
+        assert_eq!(toggled(&event_loop, slot), Some(serde_json::json!(true)));
+        run_with_budget(&mut event_loop, &mut TestClock::new(), fires(1));
+        assert_eq!(toggled(&event_loop, slot), Some(serde_json::json!(false)));
+    }
 }
@@ -0,0 +1,257 @@
+//! `boon repl`: an interactive session that remembers earlier inputs.
+//!
+//! Every input that compiles is kept, and the next input is compiled together with
+//! all of them, so a variable defined on one line can be used on the next one. HOLD
+//! state of the earlier inputs is carried over with a `GraphSnapshot`, the same way
+//! `boon run --state` carries it from one run to the next.
+//! Inputs with unclosed brackets continue on the next line.
+
+use boon::engine_v2::event_loop::EventLoop;
+use boon::engine_v2::snapshot::GraphSnapshot;
+use boon::evaluator_v2::CompileContext;
+use boon::parser::{Expression, Parser, Spanned, lexer, parser, reset_expression_depth, span_at};
+use boon::platform::cli::clock::TestClock;
+use std::io::{BufRead, Write};
+
+use crate::{mark_all_dirty, run_with_clock};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MetaCommand {
+    /// Forget every earlier input and start over
+    Reset,
+    Quit,
+    /// Print the current value of every top-level variable
+    Dump,
+}
+
+/// Parses a `:command` line; `None` when the line is Boon code.
+pub fn parse_meta_command(line: &str) -> Option<Result<MetaCommand, String>> {
+    let command = line.trim().strip_prefix(':')?;
+    Some(match command.trim() {
+        "reset" => Ok(MetaCommand::Reset),
+        "quit" | "q" => Ok(MetaCommand::Quit),
+        "dump" => Ok(MetaCommand::Dump),
+        other => Err(format!(
+            "Unknown command :{} (expected :reset, :quit or :dump)",
+            other
+        )),
+    })
+}
+
+/// Whether `source` still has unclosed `(`, `[` or `{`, ignoring `--` comments.
+pub fn is_incomplete(source: &str) -> bool {
+    let depth = source
+        .lines()
+        .map(|line| line.split("--").next().unwrap_or_default())
+        .flat_map(str::chars)
+        .fold(0_i64, |depth, character| match character {
+            '(' | '[' | '{' => depth + 1,
+            ')' | ']' | '}' => depth - 1,
+            _ => depth,
+        });
+    depth > 0
+}
+
+struct ReplSession {
+    /// Every input that compiled so far, in order
+    source: String,
+    /// State after the last input
+    snapshot: Option<GraphSnapshot>,
+    max_ticks: u64,
+}
+
+/// One build of the session program.
+struct Evaluation {
+    /// `{"status": "ok", "ticks": .., "result": ..}`
+    output: serde_json::Value,
+    snapshot: GraphSnapshot,
+}
+
+impl ReplSession {
+    fn new(max_ticks: u64) -> Self {
+        Self {
+            source: String::new(),
+            snapshot: None,
+            max_ticks,
+        }
+    }
+
+    /// Compiles `input` after the earlier inputs and runs until quiescent. The input
+    /// is kept only when it compiles.
+    fn eval(&mut self, input: &str) -> Result<serde_json::Value, String> {
+        let code = format!("{}{}", self.source, input);
+        let evaluation = self.build(&code)?;
+        self.source = code;
+        self.snapshot = Some(evaluation.snapshot);
+        Ok(evaluation.output)
+    }
+
+    /// Current value of every top-level variable, by name.
+    fn dump(&self) -> Result<serde_json::Value, String> {
+        let names: Vec<&str> = parse(&self.source)?
+            .iter()
+            .filter_map(|expression| match &expression.node {
+                Expression::Variable(variable) => Some(variable.name),
+                _ => None,
+            })
+            .collect();
+        if names.is_empty() {
+            return Ok(serde_json::json!({}));
+        }
+        let fields: Vec<String> = names
+            .iter()
+            .map(|name| format!("{}: {}", name, name))
+            .collect();
+        let code = format!("{}[{}]\n", self.source, fields.join(", "));
+        Ok(self.build(&code)?.output["result"].take())
+    }
+
+    /// Compiles `code` on a fresh event loop, restores the session state into it and
+    /// runs until quiescent.
+    fn build(&self, code: &str) -> Result<Evaluation, String> {
+        let expressions = parse(code)?;
+
+        let mut event_loop = EventLoop::new();
+        let mut ctx = CompileContext::new(&mut event_loop);
+        let result_slot = ctx.compile_program(&expressions);
+        if let Some(snapshot) = &self.snapshot {
+            event_loop.restore_snapshot(snapshot);
+        }
+        mark_all_dirty(&mut event_loop);
+
+        run_with_clock(&mut event_loop, &mut TestClock::new(), self.max_ticks);
+
+        let result = result_slot
+            .and_then(|slot| event_loop.get_current_value(slot))
+            .map(|value| event_loop.expand_payload_to_json(value))
+            .unwrap_or(serde_json::Value::Null);
+        Ok(Evaluation {
+            output: serde_json::json!({
+                "status": "ok",
+                "ticks": event_loop.current_tick,
+                "result": result
+            }),
+            snapshot: event_loop.create_snapshot(),
+        })
+    }
+}
+
+fn parse(code: &str) -> Result<Vec<Spanned<Expression<'_>>>, String> {
+    reset_expression_depth();
+
+    let (tokens, lex_errors) = lexer().parse(code).into_output_errors();
+    if !lex_errors.is_empty() {
+        return Err(format!("Lexer errors: {:?}", lex_errors));
+    }
+
+    let mut tokens = tokens.ok_or("No tokens from lexer")?;
+    tokens.retain(|t| !matches!(t.node, boon::parser::Token::Comment(_)));
+
+    let input = tokens.map(
+        span_at(code.len()),
+        |Spanned {
+             node,
+             span,
+             persistence: _,
+         }| (node, span),
+    );
+
+    let (expressions, parse_errors) = parser().parse(input).into_output_errors();
+    if !parse_errors.is_empty() {
+        return Err(format!("Parser errors: {:?}", parse_errors));
+    }
+
+    Ok(expressions.ok_or("No expressions from parser")?)
+}
+
+/// Reads inputs from stdin until `:quit` or end of input and prints one JSON
+/// result per input.
+pub fn run_repl(max_ticks: u64) {
+    let stdin = std::io::stdin();
+    let mut lines = stdin.lock().lines();
+    let mut session = ReplSession::new(max_ticks);
+    let mut buffer = String::new();
+
+    loop {
+        let prompt = if buffer.is_empty() {
+            "boon> "
+        } else {
+            "  ... "
+        };
+        eprint!("{}", prompt);
+        let _ = std::io::stderr().flush();
+
+        let line = match lines.next() {
+            Some(Ok(line)) => line,
+            Some(Err(e)) => {
+                eprintln!("Error reading input: {}", e);
+                break;
+            }
+            None => break,
+        };
+
+        if buffer.is_empty() {
+            match parse_meta_command(&line) {
+                Some(Ok(MetaCommand::Quit)) => break,
+                Some(Ok(MetaCommand::Reset)) => {
+                    session = ReplSession::new(max_ticks);
+                    eprintln!("Session reset");
+                    continue;
+                }
+                Some(Ok(MetaCommand::Dump)) => {
+                    match session.dump() {
+                        Ok(values) => println!("{}", values),
+                        Err(e) => eprintln!("{}", e),
+                    }
+                    continue;
+                }
+                Some(Err(e)) => {
+                    eprintln!("{}", e);
+                    continue;
+                }
+                None => {}
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+        }
+
+        buffer.push_str(&line);
+        buffer.push('\n');
+        if is_incomplete(&buffer) {
+            continue;
+        }
+
+        let output = match session.eval(&buffer) {
+            Ok(output) => output,
+            Err(e) => serde_json::json!({
+                "status": "error",
+                "error": e
+            }),
+        };
+        println!("{}", output);
+        buffer.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn meta_commands_start_with_a_colon() {
+        assert_eq!(parse_meta_command(":reset"), Some(Ok(MetaCommand::Reset)));
+        assert_eq!(parse_meta_command("  :quit "), Some(Ok(MetaCommand::Quit)));
+        assert_eq!(parse_meta_command(":dump"), Some(Ok(MetaCommand::Dump)));
+        assert!(matches!(parse_meta_command(":save"), Some(Err(_))));
+        assert_eq!(parse_meta_command("counter: 0"), None);
+    }
+
+    #[test]
+    fn unclosed_brackets_continue_the_input() {
+        assert!(!is_incomplete("counter: 1 + 2\n"));
+        assert!(is_incomplete("list: LIST {\n"));
+        assert!(is_incomplete("x: [a: 1 -- closing ] later\n"));
+        assert!(!is_incomplete("list: LIST {\n    1\n}\n"));
+    }
+}
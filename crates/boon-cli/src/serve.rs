@@ -0,0 +1,211 @@
+//! `boon serve`: the program's `document` as static HTML over HTTP, for demos
+//! without the wasm playground.
+//!
+//! The program is compiled once and kept running. Every request first catches the
+//! virtual clock up with wall-clock time since the server started, so timer-driven
+//! values move on between refreshes, then renders the value at `--path` (`document`
+//! by default, or a nested one such as `store.document`) with [`crate::html`]. The
+//! path is resolved by ending the program with it, which makes it the program result.
+//! `/?json=1` returns the `expand_payload_to_json` view instead. Requests are served
+//! one at a time.
+
+use boon::engine_v2::event_loop::EventLoop;
+use boon::evaluator_v2::CompileContext;
+use boon::platform::cli::clock::TestClock;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::Path;
+use std::time::Instant;
+
+use crate::{
+    advance_time, append_path, diagnostics, html, mark_all_dirty, read_source, settle, source_name,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+enum Route {
+    Page,
+    Json,
+    NotFound,
+    MethodNotAllowed,
+    BadRequest,
+}
+
+/// Routes a request line such as `GET /?json=1 HTTP/1.1`.
+fn route(request_line: &str) -> Route {
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target), Some(_version)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Route::BadRequest;
+    };
+    if method != "GET" {
+        return Route::MethodNotAllowed;
+    }
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    if path != "/" {
+        return Route::NotFound;
+    }
+    if query.split('&').any(|pair| pair == "json=1") {
+        Route::Json
+    } else {
+        Route::Page
+    }
+}
+
+fn response(status: &str, content_type: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )
+}
+
+struct Server {
+    title: String,
+    event_loop: EventLoop,
+    clock: TestClock,
+    /// The value at the served path
+    result_slot: Option<boon::engine_v2::arena::SlotId>,
+    started: Instant,
+    max_ticks: u64,
+}
+
+impl Server {
+    /// JSON view of the value at the served path.
+    fn document(&mut self) -> serde_json::Value {
+        let elapsed_ms = u64::try_from(self.started.elapsed().as_millis()).unwrap_or(u64::MAX);
+        let behind_ms = elapsed_ms.saturating_sub(self.clock.now_ms());
+        if behind_ms > 0 {
+            advance_time(
+                &mut self.event_loop,
+                &mut self.clock,
+                behind_ms,
+                self.max_ticks,
+            );
+        }
+        self.result_slot
+            .and_then(|slot| self.event_loop.get_current_value(slot))
+            .map(|value| self.event_loop.expand_payload_to_json(value))
+            .unwrap_or(serde_json::Value::Null)
+    }
+
+    fn respond(&mut self, stream: TcpStream) -> std::io::Result<()> {
+        let mut reader = BufReader::new(stream);
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line)?;
+        // Headers are not needed; read them so the client sees a complete exchange
+        let mut header = String::new();
+        while reader.read_line(&mut header)? > 0 && !header.trim_end().is_empty() {
+            header.clear();
+        }
+
+        let reply = match route(&request_line) {
+            Route::Page => response(
+                "200 OK",
+                "text/html; charset=utf-8",
+                &html::render_page(&self.title, &self.document()),
+            ),
+            Route::Json => response("200 OK", "application/json", &self.document().to_string()),
+            Route::NotFound => response("404 Not Found", "text/plain", "Not found\n"),
+            Route::MethodNotAllowed => response(
+                "405 Method Not Allowed",
+                "text/plain",
+                "Only GET is served\n",
+            ),
+            Route::BadRequest => response("400 Bad Request", "text/plain", "Bad request\n"),
+        };
+        let mut stream = reader.into_inner();
+        stream.write_all(reply.as_bytes())?;
+        stream.flush()
+    }
+}
+
+/// Compiles `file`, runs it to quiescence and serves the value at `path` on
+/// `127.0.0.1:port` until the process is stopped.
+pub fn run_serve(file: &Path, port: u16, path: &str, max_ticks: u64) {
+    let name = source_name(file);
+    let code = match read_source(file) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("Error reading {}: {}", name.display(), e);
+            std::process::exit(1);
+        }
+    };
+    if let Err(errors) = diagnostics::parse_with_diagnostics(&code) {
+        diagnostics::report(&errors, &code, &name, false);
+        std::process::exit(1);
+    }
+    let code = match append_path(&code, path) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("Error resolving `{}` in {}: {}", path, name.display(), e);
+            std::process::exit(1);
+        }
+    };
+    let expressions = match diagnostics::parse_with_diagnostics(&code) {
+        Ok(expressions) => expressions,
+        Err(errors) => {
+            diagnostics::report(&errors, &code, &name, false);
+            std::process::exit(1);
+        }
+    };
+
+    let mut event_loop = EventLoop::new();
+    let result_slot = CompileContext::new(&mut event_loop).compile_program(&expressions);
+    mark_all_dirty(&mut event_loop);
+    let mut clock = TestClock::new();
+    settle(&mut event_loop, &mut clock, max_ticks);
+
+    let listener = match TcpListener::bind(("127.0.0.1", port)) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("Error listening on port {}: {}", port, e);
+            std::process::exit(1);
+        }
+    };
+    eprintln!(
+        "Serving {} at http://127.0.0.1:{}/ (JSON at /?json=1)",
+        name.display(),
+        port
+    );
+
+    let mut server = Server {
+        title: name.display().to_string(),
+        event_loop,
+        clock,
+        result_slot,
+        started: Instant::now(),
+        max_ticks,
+    };
+    for stream in listener.incoming() {
+        let result = stream.and_then(|stream| server.respond(stream));
+        if let Err(e) = result {
+            eprintln!("Warning: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests_route_to_the_page_or_its_json() {
+        assert_eq!(route("GET / HTTP/1.1\r\n"), Route::Page);
+        assert_eq!(route("GET /?json=1 HTTP/1.1\r\n"), Route::Json);
+        assert_eq!(route("GET /?theme=dark&json=1 HTTP/1.1\r\n"), Route::Json);
+        assert_eq!(route("GET /?json=0 HTTP/1.1\r\n"), Route::Page);
+        assert_eq!(route("GET /favicon.ico HTTP/1.1\r\n"), Route::NotFound);
+        assert_eq!(route("POST / HTTP/1.1\r\n"), Route::MethodNotAllowed);
+        assert_eq!(route("\r\n"), Route::BadRequest);
+    }
+
+    #[test]
+    fn responses_carry_their_length() {
+        assert_eq!(
+            response("200 OK", "text/plain", "ok\n"),
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nContent-Length: 3\r\nConnection: close\r\n\r\nok\n"
+        );
+    }
+}
@@ -0,0 +1,343 @@
+//! State files of `boon run --state`: saving them and `boon state diff`.
+//!
+//! A state file is a `GraphSnapshot` as JSON. It is replaced atomically (written
+//! next to the target, then renamed over it), so a run killed mid-save leaves the
+//! previous snapshot intact. Long runs can also save every `--state-save-interval`,
+//! and SIGINT/SIGTERM stop the run so it saves once more before exiting.
+//!
+//! `boon state diff` loads two files and compares them entry by entry: nested
+//! objects are walked down to their leaves, which are keyed by their dotted path.
+
+use boon::engine_v2::event_loop::EventLoop;
+use boon::engine_v2::snapshot::GraphSnapshot;
+use boon::platform::browser::common::{LogLevel, emit_warning};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{self, Write as _};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// Set by the SIGINT/SIGTERM handler; runs poll it between ticks.
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// Parses `--state-save-interval`: a whole number with `ms`, `s` or `m`, e.g. `30s`.
+pub fn parse_interval(text: &str) -> Result<Duration, String> {
+    let split = text
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(|| format!("Interval `{}` needs a unit: ms, s or m", text))?;
+    let (amount, unit) = text.split_at(split);
+    let amount = amount
+        .parse::<u64>()
+        .map_err(|_| format!("Interval `{}` must start with a whole number", text))?;
+    let interval = match unit {
+        "ms" => Duration::from_millis(amount),
+        "s" => Duration::from_secs(amount),
+        "m" => Duration::from_secs(amount.saturating_mul(60)),
+        _ => {
+            return Err(format!(
+                "Unknown unit `{}` in `{}`; use ms, s or m",
+                unit, text
+            ));
+        }
+    };
+    if interval.is_zero() {
+        return Err("The interval must be longer than zero".to_string());
+    }
+    Ok(interval)
+}
+
+/// Replaces `path` with `contents` through a temporary file in the same directory,
+/// so readers see either the old or the new file, never a partial one.
+pub fn write_atomically(path: &Path, contents: &str) -> io::Result<()> {
+    let mut file_name = path.file_name().unwrap_or(path.as_os_str()).to_os_string();
+    file_name.push(".tmp");
+    let temporary = path.with_file_name(file_name);
+    let mut file = fs::File::create(&temporary)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()?;
+    drop(file);
+    fs::rename(&temporary, path).inspect_err(|_| {
+        let _ = fs::remove_file(&temporary);
+    })
+}
+
+/// Serializes the current state of `event_loop` into the state file at `path`.
+pub fn save_state(event_loop: &EventLoop, path: &Path) -> Result<(), String> {
+    let json = event_loop
+        .create_snapshot()
+        .to_json()
+        .map_err(|e| format!("Failed to serialize state: {}", e))?;
+    write_atomically(path, &json)
+        .map_err(|e| format!("Failed to write state file {}: {}", path.display(), e))
+}
+
+/// Makes SIGINT and SIGTERM set the interrupt flag instead of killing the process.
+pub fn install_interrupt_handler() {
+    if let Err(e) = ctrlc::set_handler(|| INTERRUPTED.store(true, Ordering::SeqCst)) {
+        eprintln!("Warning: Failed to install the interrupt handler: {}", e);
+    }
+}
+
+/// Whether SIGINT or SIGTERM arrived since [`install_interrupt_handler`].
+pub fn interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+/// Saves the state file whenever `interval` of wall-clock time has passed since
+/// the last save.
+pub struct PeriodicSave {
+    path: PathBuf,
+    interval: Duration,
+    last_save: Instant,
+}
+
+impl PeriodicSave {
+    pub fn new(path: PathBuf, interval: Duration) -> Self {
+        Self {
+            path,
+            interval,
+            last_save: Instant::now(),
+        }
+    }
+
+    pub fn save_if_due(&mut self, event_loop: &EventLoop) {
+        if self.last_save.elapsed() < self.interval {
+            return;
+        }
+        if let Err(e) = save_state(event_loop, &self.path) {
+            eprintln!("Warning: {}", e);
+            emit_warning(LogLevel::Warn, "persistence", e);
+        }
+        self.last_save = Instant::now();
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct StateDiff {
+    pub changed: Vec<ChangedSlot>,
+    /// Slots only in the new state
+    pub added: Vec<SlotValue>,
+    /// Slots only in the old state
+    pub removed: Vec<SlotValue>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ChangedSlot {
+    pub slot: String,
+    pub before: Value,
+    pub after: Value,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SlotValue {
+    pub slot: String,
+    pub value: Value,
+}
+
+/// Compares stored values slot by slot; slots are listed in key order.
+pub fn diff_values(old: &BTreeMap<String, Value>, new: &BTreeMap<String, Value>) -> StateDiff {
+    let mut diff = StateDiff::default();
+    for (slot, before) in old {
+        match new.get(slot) {
+            Some(after) if after != before => diff.changed.push(ChangedSlot {
+                slot: slot.clone(),
+                before: before.clone(),
+                after: after.clone(),
+            }),
+            Some(_) => {}
+            None => diff.removed.push(SlotValue {
+                slot: slot.clone(),
+                value: before.clone(),
+            }),
+        }
+    }
+    for (slot, value) in new {
+        if !old.contains_key(slot) {
+            diff.added.push(SlotValue {
+                slot: slot.clone(),
+                value: value.clone(),
+            });
+        }
+    }
+    diff
+}
+
+/// One line per slot (`~` changed, `+` added, `-` removed) and a summary line.
+pub fn render_diff(diff: &StateDiff) -> String {
+    let mut out = String::new();
+    for changed in &diff.changed {
+        let _ = writeln!(
+            out,
+            "~ {}: {} -> {}",
+            changed.slot, changed.before, changed.after
+        );
+    }
+    for added in &diff.added {
+        let _ = writeln!(out, "+ {}: {}", added.slot, added.value);
+    }
+    for removed in &diff.removed {
+        let _ = writeln!(out, "- {}: {}", removed.slot, removed.value);
+    }
+    let _ = writeln!(
+        out,
+        "{} changed, {} added, {} removed",
+        diff.changed.len(),
+        diff.added.len(),
+        diff.removed.len()
+    );
+    out
+}
+
+/// Reads a state file that `GraphSnapshot::from_json` accepts, as plain JSON.
+fn read_snapshot(path: &Path) -> Result<Value, String> {
+    let json =
+        fs::read_to_string(path).map_err(|e| format!("Error reading {}: {}", path.display(), e))?;
+    GraphSnapshot::from_json(&json)
+        .map_err(|e| format!("Invalid state file {}: {}", path.display(), e))?;
+    serde_json::from_str(&json).map_err(|e| format!("Invalid state file {}: {}", path.display(), e))
+}
+
+/// The leaves of a state file keyed by their dotted path, e.g. `holds.3`.
+/// Arrays are leaves, so a list that changed is one entry.
+pub fn snapshot_entries(snapshot: &Value) -> BTreeMap<String, Value> {
+    fn collect(prefix: &str, value: &Value, entries: &mut BTreeMap<String, Value>) {
+        match value {
+            Value::Object(fields) if !fields.is_empty() => {
+                for (key, field) in fields {
+                    let path = if prefix.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{prefix}.{key}")
+                    };
+                    collect(&path, field, entries);
+                }
+            }
+            _ => {
+                entries.insert(prefix.to_string(), value.clone());
+            }
+        }
+    }
+    let mut entries = BTreeMap::new();
+    collect("", snapshot, &mut entries);
+    entries
+}
+
+/// `boon state diff`: prints the differences as text, or as one JSON object with
+/// `changed`, `added` and `removed` arrays.
+pub fn run_diff(old_path: &Path, new_path: &Path, json: bool) {
+    let (old, new) = match (read_snapshot(old_path), read_snapshot(new_path)) {
+        (Ok(old), Ok(new)) => (old, new),
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    let diff = diff_values(&snapshot_entries(&old), &snapshot_entries(&new));
+    if json {
+        match serde_json::to_string_pretty(&diff) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("Failed to serialize state diff: {}", e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        print!("{}", render_diff(&diff));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn values(entries: &[(&str, Value)]) -> BTreeMap<String, Value> {
+        entries
+            .iter()
+            .map(|(slot, value)| (slot.to_string(), value.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn diff_lists_changed_added_and_removed_slots() {
+        let old = values(&[
+            ("3", json!(1.0)),
+            ("7", json!(["Buy milk"])),
+            ("9", json!("kept")),
+        ]);
+        let new = values(&[
+            ("3", json!(2.0)),
+            ("9", json!("kept")),
+            ("12", json!({ "done": true })),
+        ]);
+        let diff = diff_values(&old, &new);
+        assert_eq!(
+            render_diff(&diff),
+            "\
+~ 3: 1.0 -> 2.0
++ 12: {\"done\":true}
+- 7: [\"Buy milk\"]
+1 changed, 1 added, 1 removed
+"
+        );
+        assert_eq!(
+            serde_json::to_value(&diff).unwrap()["changed"],
+            json!([{ "slot": "3", "before": 1.0, "after": 2.0 }])
+        );
+    }
+
+    #[test]
+    fn snapshot_entries_are_keyed_by_their_dotted_path() {
+        let entries = snapshot_entries(&json!({
+            "tick": 4,
+            "holds": { "3": 1.0, "7": { "items": ["Buy milk"] } },
+            "empty": {}
+        }));
+        assert_eq!(
+            entries,
+            values(&[
+                ("empty", json!({})),
+                ("holds.3", json!(1.0)),
+                ("holds.7.items", json!(["Buy milk"])),
+                ("tick", json!(4)),
+            ])
+        );
+    }
+
+    #[test]
+    fn intervals_take_a_unit() {
+        assert_eq!(parse_interval("30s"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_interval("250ms"), Ok(Duration::from_millis(250)));
+        assert_eq!(parse_interval("5m"), Ok(Duration::from_secs(300)));
+        assert!(parse_interval("30").is_err());
+        assert!(parse_interval("0s").is_err());
+        assert!(parse_interval("1.5s").is_err());
+        assert!(parse_interval("s").is_err());
+    }
+
+    #[test]
+    fn atomic_writes_replace_the_file_and_leave_no_temporary() {
+        let directory = std::env::temp_dir().join(format!("boon-state-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("state.json");
+        fs::write(&path, "old").unwrap();
+        write_atomically(&path, "new").unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        let files = fs::read_dir(&directory).unwrap().count();
+        fs::remove_dir_all(&directory).unwrap();
+        assert_eq!(contents, "new");
+        assert_eq!(files, 1);
+    }
+
+    #[test]
+    fn identical_states_have_an_empty_diff() {
+        let state = values(&[("3", json!(1.0))]);
+        let diff = diff_values(&state, &state);
+        assert_eq!(diff, StateDiff::default());
+        assert_eq!(render_diff(&diff), "0 changed, 0 added, 0 removed\n");
+    }
+}
@@ -0,0 +1,570 @@
+//! `boon test` files: finding them, parsing test cases and rewriting their expectations.
+//!
+//! ```text
+//! -- test: test_name
+//! code here
+//! -- expect: expected_json_value
+//! Test/advance(milliseconds: 1000)
+//! -- expect: expected_json_value
+//! ```
+//!
+//! A test runs until the next `-- test:` line. Each `-- expect:` line checks the
+//! result after the `Test/...` directives above it; a test without `-- expect:`
+//! only has to evaluate. Larger values go in a block:
+//!
+//! ```text
+//! -- expect-begin
+//! {
+//!     "title": "Buy milk",
+//!     "completed": false
+//! }
+//! -- expect-end
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const TEST_PREFIX: &str = "-- test:";
+const EXPECT_PREFIX: &str = "-- expect:";
+const EXPECT_BEGIN: &str = "-- expect-begin";
+const EXPECT_END: &str = "-- expect-end";
+
+#[derive(Debug, PartialEq)]
+pub struct TestCase<'a> {
+    pub name: &'a str,
+    /// Boon code and directives, without the `-- expect:` lines
+    pub code: String,
+    pub expectations: Vec<Expectation<'a>>,
+    /// Line index after which a missing `-- expect:` line is inserted:
+    /// the last non-blank line of the test
+    last_line: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Expectation<'a> {
+    /// JSON text; for a block, its lines with their line breaks
+    pub expected: &'a str,
+    /// Byte offset in the test's `code` where the expectation stands; it is checked
+    /// once the directives before this offset have run
+    pub code_offset: usize,
+    /// Line index of the `-- expect:` or `-- expect-begin` line
+    line: usize,
+    /// Line index of the `-- expect-end` line (or the last line of an unterminated
+    /// block); `None` for the single-line form
+    block_end: Option<usize>,
+}
+
+impl Expectation<'_> {
+    /// Parses the expected JSON. Errors point at the line in the test file.
+    pub fn parse(&self) -> Result<serde_json::Value, String> {
+        serde_json::from_str(self.expected).map_err(|e| {
+            // serde_json counts lines from the start of `expected`
+            let first_line = self.line + usize::from(self.block_end.is_some()) + 1;
+            let message = e.to_string();
+            let message = message
+                .rsplit_once(" at line ")
+                .map_or(message.as_str(), |(message, _)| message);
+            format!(
+                "line {}, column {}: {}",
+                first_line + e.line().max(1) - 1,
+                e.column(),
+                message
+            )
+        })
+    }
+}
+
+/// New expected value for the expectation `expectation` of test `case`; an
+/// expectation is added when the test has none.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpectationUpdate {
+    pub case: usize,
+    pub expectation: usize,
+    pub value: serde_json::Value,
+}
+
+pub fn parse_test_file(content: &str) -> Vec<TestCase<'_>> {
+    let mut cases = Vec::new();
+    let mut current: Option<TestCase> = None;
+    // Byte offset where the open `-- expect-begin` block's JSON starts
+    let mut block_start: Option<usize> = None;
+    let mut offset = 0;
+
+    for (index, raw_line) in content.split_inclusive('\n').enumerate() {
+        let line_start = offset;
+        offset += raw_line.len();
+        let line = raw_line.trim_end_matches(['\n', '\r']);
+
+        if let Some(start) = block_start {
+            if line.trim_end() == EXPECT_END {
+                close_block(&mut current, content, start..line_start, index);
+                block_start = None;
+                continue;
+            }
+            if !line.starts_with(TEST_PREFIX) {
+                continue;
+            }
+            // A new test ends a block that was never closed
+            close_block(&mut current, content, start..line_start, index - 1);
+            block_start = None;
+        }
+
+        if let Some(name) = line.strip_prefix(TEST_PREFIX) {
+            cases.extend(current.take());
+            current = Some(TestCase {
+                name: name.trim(),
+                code: String::new(),
+                expectations: Vec::new(),
+                last_line: index,
+            });
+            continue;
+        }
+        let Some(case) = current.as_mut() else {
+            continue;
+        };
+        if let Some(expected) = line.strip_prefix(EXPECT_PREFIX) {
+            case.expectations.push(Expectation {
+                expected: expected.trim(),
+                code_offset: case.code.len(),
+                line: index,
+                block_end: None,
+            });
+        } else if line.trim_end() == EXPECT_BEGIN {
+            case.expectations.push(Expectation {
+                expected: "",
+                code_offset: case.code.len(),
+                line: index,
+                block_end: Some(index),
+            });
+            block_start = Some(offset);
+        } else {
+            if !case.code.is_empty() {
+                case.code.push('\n');
+            }
+            case.code.push_str(line);
+        }
+        if !line.trim().is_empty() {
+            case.last_line = index;
+        }
+    }
+    if let Some(start) = block_start {
+        let last_line = content.split_inclusive('\n').count().saturating_sub(1);
+        close_block(&mut current, content, start..content.len(), last_line);
+    }
+    cases.extend(current);
+    cases
+}
+
+/// Sets the JSON of the block opened last in `current` to `content[range]`;
+/// `end_line` is the line index of its `-- expect-end` line.
+fn close_block<'a>(
+    current: &mut Option<TestCase<'a>>,
+    content: &'a str,
+    range: std::ops::Range<usize>,
+    end_line: usize,
+) {
+    let Some(case) = current.as_mut() else {
+        return;
+    };
+    if let Some(expectation) = case.expectations.last_mut() {
+        expectation.expected = &content[range];
+        expectation.block_end = Some(end_line);
+    }
+    case.last_line = case.last_line.max(end_line);
+}
+
+/// Applies `updates` to the `-- expect:` lines and blocks; a block stays a block,
+/// pretty-printed. Everything else is kept byte for byte.
+pub fn rewrite_expectations(content: &str, updates: &[ExpectationUpdate]) -> String {
+    let cases = parse_test_file(content);
+    let newline = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    // (first line index, last line index, new text without the final line break)
+    let mut replaced = Vec::new();
+    let mut inserted = Vec::new();
+    for update in updates {
+        let Some(case) = cases.get(update.case) else {
+            continue;
+        };
+        match case.expectations.get(update.expectation) {
+            Some(expectation) => match expectation.block_end {
+                Some(block_end) => {
+                    let pretty = serde_json::to_string_pretty(&update.value)
+                        .unwrap_or_else(|_| update.value.to_string());
+                    let block = std::iter::once(EXPECT_BEGIN)
+                        .chain(pretty.lines())
+                        .chain(std::iter::once(EXPECT_END))
+                        .collect::<Vec<_>>()
+                        .join(newline);
+                    replaced.push((expectation.line, block_end, block));
+                }
+                None => replaced.push((
+                    expectation.line,
+                    expectation.line,
+                    format!("{} {}", EXPECT_PREFIX, update.value),
+                )),
+            },
+            None if case.expectations.is_empty() => inserted.push((
+                case.last_line,
+                format!("{} {}", EXPECT_PREFIX, update.value),
+            )),
+            None => {}
+        }
+    }
+
+    let mut out = String::with_capacity(content.len());
+    let mut replacing_until = None;
+    for (index, line) in content.split_inclusive('\n').enumerate() {
+        if let Some((_, last, new_text)) = replaced.iter().find(|(first, ..)| *first == index) {
+            out.push_str(new_text);
+            replacing_until = Some(*last);
+        }
+        match replacing_until {
+            Some(last) if index == last => {
+                out.push_str(line_ending(line));
+                replacing_until = None;
+            }
+            Some(_) => {}
+            None => out.push_str(line),
+        }
+        for (_, new_line) in inserted.iter().filter(|(after, _)| *after == index) {
+            if !out.ends_with('\n') {
+                out.push('\n');
+            }
+            out.push_str(new_line);
+            out.push_str(if line.ends_with("\r\n") { "\r\n" } else { "\n" });
+        }
+    }
+    out
+}
+
+fn line_ending(line: &str) -> &str {
+    if line.ends_with("\r\n") {
+        "\r\n"
+    } else if line.ends_with('\n') {
+        "\n"
+    } else {
+        ""
+    }
+}
+
+/// Rewrites `file` with [`rewrite_expectations`] and reports how many expectations changed.
+pub fn update_test_file(
+    file: &Path,
+    content: &str,
+    updates: &[ExpectationUpdate],
+) -> Result<(), String> {
+    if updates.is_empty() {
+        return Ok(());
+    }
+    fs::write(file, rewrite_expectations(content, updates))
+        .map_err(|e| format!("Error writing {}: {}", file.display(), e))?;
+    eprintln!(
+        "{} expectation{} updated in {}",
+        updates.len(),
+        if updates.len() == 1 { "" } else { "s" },
+        file.display()
+    );
+    Ok(())
+}
+
+/// Expands `boon test` arguments into the files to run, without relying on the shell:
+/// a glob pattern (`**` recurses), a directory (every `.bn` and `.bn.test` file under
+/// it) or a file. Matches of each argument are sorted; a file named twice runs once.
+/// An argument that matches nothing is an error.
+pub fn expand_test_paths(arguments: &[PathBuf]) -> Result<Vec<PathBuf>, String> {
+    let mut files = Vec::new();
+    for argument in arguments {
+        let patterns = if argument.is_dir() {
+            let directory = glob::Pattern::escape(&argument.to_string_lossy());
+            vec![
+                format!("{}/**/*.bn", directory),
+                format!("{}/**/*.bn.test", directory),
+            ]
+        } else {
+            vec![argument.to_string_lossy().into_owned()]
+        };
+
+        let mut matched = Vec::new();
+        for pattern in &patterns {
+            let paths = glob::glob(pattern)
+                .map_err(|e| format!("Invalid test pattern `{}`: {}", pattern, e))?;
+            matched.extend(paths.filter_map(Result::ok).filter(|path| path.is_file()));
+        }
+        if matched.is_empty() {
+            return Err(format!("No test files match `{}`", argument.display()));
+        }
+        matched.sort();
+        for path in matched {
+            if !files.contains(&path) {
+                files.push(path);
+            }
+        }
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const FILE: &str = "\
+-- test: add
+-- adds two numbers
+1 + 2
+-- expect: 4.0
+
+-- test: object
+[x: 1]
+
+-- test: unchanged
+42
+-- expect: 42.0
+";
+
+    #[test]
+    fn cases_keep_names_code_and_expectations() {
+        let cases = parse_test_file(FILE);
+        let names = cases.iter().map(|case| case.name).collect::<Vec<_>>();
+        assert_eq!(names, vec!["add", "object", "unchanged"]);
+        assert_eq!(cases[0].code, "-- adds two numbers\n1 + 2\n");
+        assert_eq!(cases[0].expectations[0].expected, "4.0");
+        assert_eq!(cases[1].code, "[x: 1]\n");
+        assert!(cases[1].expectations.is_empty());
+    }
+
+    #[test]
+    fn expectations_are_checked_after_the_directives_above_them() {
+        let content = "\
+-- test: timer
+counter: 0 |> HOLD state { Duration[milliseconds: 1000] |> Timer/interval() |> THEN { state + 1 } }
+counter
+-- expect: 0.0
+Test/advance(milliseconds: 1000)
+-- expect: 1.0
+Test/advance(milliseconds: 1000)
+-- expect: 2.0
+";
+        let case = &parse_test_file(content)[0];
+        let checkpoints = case
+            .expectations
+            .iter()
+            .map(|expectation| {
+                let before = &case.code[..expectation.code_offset];
+                (
+                    expectation.expected,
+                    before.lines().last().unwrap_or_default(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            checkpoints,
+            vec![
+                ("0.0", "counter"),
+                ("1.0", "Test/advance(milliseconds: 1000)"),
+                ("2.0", "Test/advance(milliseconds: 1000)"),
+            ]
+        );
+        assert!(case.expectations[1].code_offset < case.expectations[2].code_offset);
+        assert_eq!(case.code.lines().count(), 4);
+    }
+
+    #[test]
+    fn updating_a_temp_file_rewrites_only_the_failing_expectations() {
+        let path = std::env::temp_dir().join(format!(
+            "boon_update_expectations_{}.bn.test",
+            std::process::id()
+        ));
+        fs::write(&path, FILE).unwrap();
+
+        let updates = [
+            ExpectationUpdate {
+                case: 0,
+                expectation: 0,
+                value: json!(3.0),
+            },
+            ExpectationUpdate {
+                case: 1,
+                expectation: 0,
+                value: json!({ "x": 1.0 }),
+            },
+        ];
+        update_test_file(&path, FILE, &updates).unwrap();
+        let updated = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            updated,
+            "\
+-- test: add
+-- adds two numbers
+1 + 2
+-- expect: 3.0
+
+-- test: object
+[x: 1]
+-- expect: {\"x\":1.0}
+
+-- test: unchanged
+42
+-- expect: 42.0
+"
+        );
+        let cases = parse_test_file(&updated);
+        assert_eq!(cases[1].expectations[0].expected, "{\"x\":1.0}");
+        assert_eq!(cases[1].code, "[x: 1]\n");
+    }
+
+    #[test]
+    fn patterns_and_directories_expand_to_sorted_test_files() {
+        let root = std::env::temp_dir().join(format!("boon_test_paths_{}", std::process::id()));
+        fs::create_dir_all(root.join("nested")).unwrap();
+        for file in ["b.bn.test", "a.bn", "notes.md", "nested/c.bn.test"] {
+            fs::write(root.join(file), "").unwrap();
+        }
+
+        let recursive = root.join("**").join("*.bn.test");
+        let by_pattern = expand_test_paths(&[recursive.clone()]);
+        let by_directory = expand_test_paths(&[root.clone(), root.join("a.bn")]);
+        let nothing = expand_test_paths(&[root.join("*.missing")]);
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(
+            by_pattern.unwrap(),
+            vec![root.join("b.bn.test"), root.join("nested/c.bn.test")]
+        );
+        assert_eq!(
+            by_directory.unwrap(),
+            vec![
+                root.join("a.bn"),
+                root.join("b.bn.test"),
+                root.join("nested/c.bn.test")
+            ]
+        );
+        assert!(nothing.unwrap_err().contains("No test files match"));
+    }
+
+    const TODO_STORE_BLOCK: &str = "\
+-- test: store
+store
+-- expect: 1.0
+-- expect-begin
+{
+    \"heading\": \"todos\",
+    \"filter\": \"All\",
+    \"todos\": [
+        {
+            \"completed\": false,
+            \"title\": \"Buy groceries\"
+        },
+        {
+            \"completed\": true,
+            \"title\": \"Walk the dog\"
+        },
+        {
+            \"completed\": false,
+            \"title\": \"Read a book\"
+        },
+        {
+            \"completed\": true,
+            \"title\": \"Pay rent\"
+        },
+        {
+            \"completed\": false,
+            \"title\": \"Call mom\"
+        }
+    ],
+    \"active_count\": 3.0,
+    \"completed_count\": 2.0
+}
+-- expect-end";
+
+    #[test]
+    fn block_expectation_is_one_value_up_to_the_end_of_the_file() {
+        let cases = parse_test_file(TODO_STORE_BLOCK);
+        let expectations = &cases[0].expectations;
+        assert_eq!(cases[0].code, "store");
+        assert_eq!(expectations.len(), 2);
+        assert_eq!(expectations[0].parse(), Ok(json!(1.0)));
+        assert_eq!(expectations[1].expected.lines().count(), 28);
+        let store = expectations[1].parse().unwrap();
+        assert_eq!(store["todos"][4]["title"], "Call mom");
+        assert_eq!(store["active_count"], 3.0);
+    }
+
+    #[test]
+    fn invalid_block_json_reports_the_line_in_the_file() {
+        let content = "\
+-- test: broken
+[a: 1]
+-- expect-begin
+{
+    \"a\": 1.0,
+}
+-- expect-end
+
+-- test: next
+2
+-- expect: 2.0
+";
+        let cases = parse_test_file(content);
+        let error = cases[0].expectations[0].parse().unwrap_err();
+        assert!(error.starts_with("line 6, column 1: "), "{}", error);
+        assert_eq!(cases[1].expectations[0].parse(), Ok(json!(2.0)));
+    }
+
+    #[test]
+    fn updating_a_block_keeps_it_a_block() {
+        let content = "\
+-- test: pair
+[a: 1, b: 2]
+-- expect-begin
+{ \"a\": 1.0 }
+-- expect-end
+-- test: after
+3
+";
+        let updated = rewrite_expectations(
+            content,
+            &[ExpectationUpdate {
+                case: 0,
+                expectation: 0,
+                value: json!({ "a": 1.0 }),
+            }],
+        );
+        assert_eq!(
+            updated,
+            "\
+-- test: pair
+[a: 1, b: 2]
+-- expect-begin
+{
+  \"a\": 1.0
+}
+-- expect-end
+-- test: after
+3
+"
+        );
+    }
+
+    #[test]
+    fn expectation_is_added_to_a_last_test_without_trailing_newline() {
+        let content = "-- test: last\n1 + 1";
+        assert_eq!(
+            rewrite_expectations(
+                content,
+                &[ExpectationUpdate {
+                    case: 0,
+                    expectation: 0,
+                    value: json!(2.0),
+                }]
+            ),
+            "-- test: last\n1 + 1\n-- expect: 2.0\n"
+        );
+    }
+}
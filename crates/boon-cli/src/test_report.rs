@@ -0,0 +1,297 @@
+//! Machine-readable results of `boon test` for CI: JUnit XML and TAP.
+//!
+//! Every test file becomes a JUnit `<testsuite>` and every test a `<testcase>`;
+//! mismatching expectations are the text of its `<failure>`. TAP lists the tests
+//! of all files in one plan, with a `# file` comment before each file's tests and
+//! the mismatches as comments after a `not ok` line. Tests left out by `--filter`
+//! are JUnit `<skipped>` and TAP `# SKIP`.
+
+use std::fmt::Write as _;
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum TestFormat {
+    /// Progress and colored diffs on stderr
+    #[default]
+    Human,
+    /// JUnit XML document
+    Junit,
+    /// Test Anything Protocol, version 13
+    Tap,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum CaseResult {
+    Passed,
+    /// Mismatching expectations or an evaluation error, as plain text
+    Failed(String),
+    /// The test file could not be read
+    Error(String),
+    /// Not selected by `--filter`, so not run
+    Filtered,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CaseReport {
+    pub name: String,
+    pub duration: Duration,
+    pub result: CaseResult,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FileReport {
+    pub path: String,
+    pub cases: Vec<CaseReport>,
+}
+
+impl FileReport {
+    fn count(&self, matches: impl Fn(&CaseResult) -> bool) -> usize {
+        self.cases
+            .iter()
+            .filter(|case| matches(&case.result))
+            .count()
+    }
+
+    fn duration(&self) -> Duration {
+        self.cases.iter().map(|case| case.duration).sum()
+    }
+}
+
+/// Escapes text for XML attributes and content. Control characters XML 1.0 cannot
+/// represent (ANSI escapes, for example) become U+FFFD.
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c.is_control() => escaped.push('\u{FFFD}'),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn seconds(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64())
+}
+
+/// First line of a failure, used as the `message` attribute.
+fn summary(text: &str) -> &str {
+    text.lines()
+        .next()
+        .unwrap_or_default()
+        .trim_end_matches(':')
+}
+
+pub fn render_junit(files: &[FileReport]) -> String {
+    let failures = files
+        .iter()
+        .map(|file| file.count(|result| matches!(result, CaseResult::Failed(_))))
+        .sum::<usize>();
+    let errors = files
+        .iter()
+        .map(|file| file.count(|result| matches!(result, CaseResult::Error(_))))
+        .sum::<usize>();
+    let skipped = files
+        .iter()
+        .map(|file| file.count(|result| *result == CaseResult::Filtered))
+        .sum::<usize>();
+    let tests = files.iter().map(|file| file.cases.len()).sum::<usize>();
+    let time = files.iter().map(FileReport::duration).sum::<Duration>();
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        out,
+        "<testsuites name=\"boon test\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{}\">",
+        tests,
+        failures,
+        errors,
+        skipped,
+        seconds(time)
+    );
+    for file in files {
+        let path = escape_xml(&file.path);
+        let _ = writeln!(
+            out,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{}\">",
+            path,
+            file.cases.len(),
+            file.count(|result| matches!(result, CaseResult::Failed(_))),
+            file.count(|result| matches!(result, CaseResult::Error(_))),
+            file.count(|result| *result == CaseResult::Filtered),
+            seconds(file.duration())
+        );
+        for case in &file.cases {
+            let _ = write!(
+                out,
+                "    <testcase name=\"{}\" classname=\"{}\" time=\"{}\"",
+                escape_xml(&case.name),
+                path,
+                seconds(case.duration)
+            );
+            let (element, text) = match &case.result {
+                CaseResult::Passed => {
+                    out.push_str("/>\n");
+                    continue;
+                }
+                CaseResult::Filtered => {
+                    out.push_str(">\n      <skipped message=\"filtered\"/>\n    </testcase>\n");
+                    continue;
+                }
+                CaseResult::Failed(text) => ("failure", text),
+                CaseResult::Error(text) => ("error", text),
+            };
+            let _ = writeln!(
+                out,
+                ">\n      <{} message=\"{}\">{}</{}>\n    </testcase>",
+                element,
+                escape_xml(summary(text)),
+                escape_xml(text),
+                element
+            );
+        }
+        out.push_str("  </testsuite>\n");
+    }
+    out.push_str("</testsuites>\n");
+    out
+}
+
+pub fn render_tap(files: &[FileReport]) -> String {
+    let tests = files.iter().map(|file| file.cases.len()).sum::<usize>();
+    let mut out = format!("TAP version 13\n1..{}\n", tests);
+    let mut number = 0;
+    for file in files {
+        let _ = writeln!(out, "# {}", file.path);
+        for case in &file.cases {
+            number += 1;
+            // `#` would start a TAP directive in the description
+            let name = case.name.replace('#', "\\#");
+            let details = match &case.result {
+                CaseResult::Passed => {
+                    let _ = writeln!(out, "ok {} - {}", number, name);
+                    continue;
+                }
+                CaseResult::Filtered => {
+                    let _ = writeln!(out, "ok {} - {} # SKIP filtered", number, name);
+                    continue;
+                }
+                CaseResult::Failed(text) | CaseResult::Error(text) => text,
+            };
+            let _ = writeln!(out, "not ok {} - {}", number, name);
+            for line in details.lines() {
+                let _ = writeln!(out, "#   {}", line);
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reports() -> Vec<FileReport> {
+        vec![
+            FileReport {
+                path: "tests/basic.bn.test".to_string(),
+                cases: vec![
+                    CaseReport {
+                        name: "addition".to_string(),
+                        duration: Duration::from_millis(12),
+                        result: CaseResult::Passed,
+                    },
+                    CaseReport {
+                        name: "text <concat>".to_string(),
+                        duration: Duration::from_millis(3),
+                        result: CaseResult::Failed(
+                            "expectation 1 of 1 at 0 ms:\n  result: expected \"a&b\", got \"ab\"\n"
+                                .to_string(),
+                        ),
+                    },
+                ],
+            },
+            FileReport {
+                path: "tests/missing.bn.test".to_string(),
+                cases: vec![CaseReport {
+                    name: "tests/missing.bn.test".to_string(),
+                    duration: Duration::ZERO,
+                    result: CaseResult::Error("No such file or directory".to_string()),
+                }],
+            },
+        ]
+    }
+
+    #[test]
+    fn junit_has_a_suite_per_file_and_a_failure_per_mismatch() {
+        assert_eq!(
+            render_junit(&reports()),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="boon test" tests="3" failures="1" errors="1" skipped="0" time="0.015">
+  <testsuite name="tests/basic.bn.test" tests="2" failures="1" errors="0" skipped="0" time="0.015">
+    <testcase name="addition" classname="tests/basic.bn.test" time="0.012"/>
+    <testcase name="text &lt;concat&gt;" classname="tests/basic.bn.test" time="0.003">
+      <failure message="expectation 1 of 1 at 0 ms">expectation 1 of 1 at 0 ms:
+  result: expected &quot;a&amp;b&quot;, got &quot;ab&quot;
+</failure>
+    </testcase>
+  </testsuite>
+  <testsuite name="tests/missing.bn.test" tests="1" failures="0" errors="1" skipped="0" time="0.000">
+    <testcase name="tests/missing.bn.test" classname="tests/missing.bn.test" time="0.000">
+      <error message="No such file or directory">No such file or directory</error>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#
+        );
+    }
+
+    #[test]
+    fn tap_numbers_tests_across_files() {
+        assert_eq!(
+            render_tap(&reports()),
+            r#"TAP version 13
+1..3
+# tests/basic.bn.test
+ok 1 - addition
+not ok 2 - text <concat>
+#   expectation 1 of 1 at 0 ms:
+#     result: expected "a&b", got "ab"
+# tests/missing.bn.test
+not ok 3 - tests/missing.bn.test
+#   No such file or directory
+"#
+        );
+    }
+
+    #[test]
+    fn filtered_tests_are_skipped() {
+        let reports = vec![FileReport {
+            path: "tests/basic.bn.test".to_string(),
+            cases: vec![CaseReport {
+                name: "addition".to_string(),
+                duration: Duration::ZERO,
+                result: CaseResult::Filtered,
+            }],
+        }];
+        assert!(render_junit(&reports).contains(
+            "skipped=\"1\" time=\"0.000\">\n    <testcase name=\"addition\" classname=\"tests/basic.bn.test\" time=\"0.000\">\n      <skipped message=\"filtered\"/>\n    </testcase>\n"
+        ));
+        assert_eq!(
+            render_tap(&reports),
+            "TAP version 13\n1..1\n# tests/basic.bn.test\nok 1 - addition # SKIP filtered\n"
+        );
+    }
+
+    #[test]
+    fn control_characters_are_not_written_to_xml() {
+        assert_eq!(
+            escape_xml("\u{1b}[31mred\u{1b}[0m"),
+            "\u{FFFD}[31mred\u{FFFD}[0m"
+        );
+    }
+}
@@ -0,0 +1,233 @@
+//! `boon run --watch`: re-evaluate a file whenever it or a module next to it changes.
+//!
+//! Modules are resolved relative to the file's directory (`Module.bn`,
+//! `Module/Module.bn`, `Generated/Module.bn`), so the whole directory is watched and
+//! any `.bn` change triggers a rebuild. HOLD state of the previous build is carried
+//! over with a `GraphSnapshot` when [`hot_reload`] matched every HOLD as unchanged,
+//! so editing unrelated code does not reset counters. Editing a HOLD starts every
+//! HOLD over, and the edited ones are listed on stderr.
+
+use boon::engine_v2::event_loop::EventLoop;
+use boon::engine_v2::snapshot::GraphSnapshot;
+use boon::evaluator_v2::CompileContext;
+use boon::parser::{Expression, Parser, Spanned, lexer, parser, reset_expression_depth, span_at};
+use boon::platform::browser::common::{LogLevel, emit_warning};
+use boon::platform::cli::clock::TestClock;
+use notify::{EventKind, RecursiveMode, Watcher};
+use std::fs;
+use std::path::Path;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use crate::hot_reload::{self, Resolution};
+use crate::{
+    RunBudget, cap_result_texts, mark_all_dirty, run_with_budget, start_next_warnings_run,
+};
+
+/// Editors save in several steps (truncate, write, rename); wait for them to settle.
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Whether a file system event should trigger a rebuild.
+pub fn is_source_change(event: &notify::Event) -> bool {
+    matches!(
+        event.kind,
+        EventKind::Modify(_) | EventKind::Create(_) | EventKind::Remove(_)
+    ) && event
+        .paths
+        .iter()
+        .any(|path| path.extension().is_some_and(|extension| extension == "bn"))
+}
+
+/// The last successful build.
+struct Previous {
+    code: String,
+    resolution: Resolution,
+    snapshot: GraphSnapshot,
+}
+
+fn parse(code: &str) -> Result<Vec<Spanned<Expression<'_>>>, String> {
+    reset_expression_depth();
+
+    let (tokens, lex_errors) = lexer().parse(code).into_output_errors();
+    if !lex_errors.is_empty() {
+        return Err(format!("Lexer errors: {:?}", lex_errors));
+    }
+
+    let mut tokens = tokens.ok_or("No tokens from lexer")?;
+    tokens.retain(|t| !matches!(t.node, boon::parser::Token::Comment(_)));
+
+    let input = tokens.map(
+        span_at(code.len()),
+        |Spanned {
+             node,
+             span,
+             persistence: _,
+         }| (node, span),
+    );
+
+    let (expressions, parse_errors) = parser().parse(input).into_output_errors();
+    if !parse_errors.is_empty() {
+        return Err(format!("Parser errors: {:?}", parse_errors));
+    }
+
+    expressions.ok_or_else(|| "No expressions from parser".to_owned())
+}
+
+/// Builds a fresh event loop for `code`, restores the unchanged HOLDs of `previous`
+/// into it and runs it within `budget`. Returns the result JSON and the state for the
+/// next build.
+fn build(
+    code: &str,
+    budget: RunBudget,
+    previous: Option<&Previous>,
+) -> Result<(serde_json::Value, Previous), String> {
+    let old_expressions = previous.map(|previous| parse(&previous.code)).transpose()?;
+    let expressions = parse(code)?;
+    let (expressions, resolution) = hot_reload::resolve(
+        code,
+        expressions,
+        old_expressions,
+        previous.map(|previous| &previous.resolution),
+    )?;
+
+    let mut event_loop = EventLoop::new();
+    let mut ctx = CompileContext::new(&mut event_loop);
+    let result_slot = ctx.compile_program(&expressions);
+
+    if let Some(previous) = previous {
+        let changes = hot_reload::hold_changes(&previous.resolution, &resolution);
+        // The snapshot restores every HOLD or none, so one changed HOLD resets them all
+        if !changes.reset.is_empty() {
+            eprintln!(
+                "Reset HOLD state: {} changed, so every HOLD starts over",
+                changes.reset.join(", ")
+            );
+            emit_warning(
+                LogLevel::Warn,
+                "restore",
+                format!(
+                    "HOLD state was dropped: {} changed, so every HOLD starts over",
+                    changes.reset.join(", ")
+                ),
+            );
+        } else if !changes.preserved.is_empty() {
+            event_loop.restore_snapshot(&previous.snapshot);
+        }
+    }
+
+    mark_all_dirty(&mut event_loop);
+
+    let stopped = run_with_budget(&mut event_loop, &mut TestClock::new(), budget);
+
+    let mut result = result_slot
+        .and_then(|slot| event_loop.get_current_value(slot))
+        .map(|value| event_loop.expand_payload_to_json(value))
+        .unwrap_or(serde_json::Value::Null);
+    cap_result_texts(&mut result);
+    let output = serde_json::json!({
+        "status": "ok",
+        "ticks": event_loop.current_tick,
+        "stopped": stopped,
+        "result": result
+    });
+    let snapshot = event_loop.create_snapshot();
+    Ok((
+        output,
+        Previous {
+            code: code.to_owned(),
+            resolution,
+            snapshot,
+        },
+    ))
+}
+
+/// Evaluates `file`, then rebuilds it on every change until the process is stopped.
+/// Read and parse errors are printed and the previous state is kept for the next build.
+pub fn run_watch(file: &Path, budget: RunBudget) {
+    let directory = file
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher =
+        match notify::recommended_watcher(move |res: Result<notify::Event, notify::Error>| {
+            if let Ok(event) = res
+                && is_source_change(&event)
+            {
+                let _ = sender.send(());
+            }
+        }) {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                eprintln!("Error starting file watcher: {}", e);
+                std::process::exit(1);
+            }
+        };
+    if let Err(e) = watcher.watch(directory, RecursiveMode::Recursive) {
+        eprintln!("Error watching {}: {}", directory.display(), e);
+        std::process::exit(1);
+    }
+
+    let mut previous = None;
+    loop {
+        eprintln!("Running: {}", file.display());
+        let built = fs::read_to_string(file)
+            .map_err(|e| format!("Error reading file: {}", e))
+            .and_then(|code| build(&code, budget, previous.as_ref()));
+        match built {
+            Ok((output, next)) => {
+                println!("{}", output);
+                previous = Some(next);
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                println!(
+                    "{}",
+                    serde_json::json!({
+                        "status": "error",
+                        "error": e
+                    })
+                );
+            }
+        }
+
+        eprintln!("Watching {} for changes...", directory.display());
+        if receiver.recv().is_err() {
+            return;
+        }
+        while receiver.recv_timeout(DEBOUNCE).is_ok() {}
+        start_next_warnings_run();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{CreateKind, ModifyKind};
+    use std::path::PathBuf;
+
+    fn event(kind: EventKind, path: &str) -> notify::Event {
+        notify::Event::new(kind).add_path(PathBuf::from(path))
+    }
+
+    #[test]
+    fn only_boon_source_changes_trigger_a_rebuild() {
+        assert!(is_source_change(&event(
+            EventKind::Modify(ModifyKind::Any),
+            "examples/counter.bn"
+        )));
+        assert!(is_source_change(&event(
+            EventKind::Create(CreateKind::File),
+            "examples/Generated/Theme.bn"
+        )));
+        assert!(!is_source_change(&event(
+            EventKind::Modify(ModifyKind::Any),
+            "examples/counter.bn.swp"
+        )));
+        assert!(!is_source_change(&event(
+            EventKind::Access(notify::event::AccessKind::Any),
+            "examples/counter.bn"
+        )));
+    }
+}
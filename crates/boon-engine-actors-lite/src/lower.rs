@@ -4532,10 +4532,11 @@ const GENERIC_HOST_SURFACE_GROUP: SurfaceProgramGroup<
                         subset: "switched_hold_items_document",
                         top_level_bindings: &[],
                         required_paths: &[
+                            ["store", "show_item_a"].as_slice(),
                             ["store", "item_a"].as_slice(),
                             ["store", "item_b"].as_slice(),
                         ],
-                        hold_paths: &[["store", "show_item_a"].as_slice()],
+                        hold_paths: &[],
                         required_functions: &["create_item"],
                         alias_paths: &[
                             ["view_toggle", "event", "press"].as_slice(),
@@ -4544,7 +4545,7 @@ const GENERIC_HOST_SURFACE_GROUP: SurfaceProgramGroup<
                         function_call_paths: &[
                             ["Document", "new"].as_slice(),
                             ["Element", "button"].as_slice(),
-                            ["Bool", "not"].as_slice(),
+                            ["Bool", "toggle"].as_slice(),
                         ],
                         text_fragments: &[
                             "Showing: Item A",
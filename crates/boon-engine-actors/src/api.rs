@@ -2,19 +2,25 @@ use std::future;
 use std::sync::Arc;
 
 use zoon::Timer;
-use zoon::futures_channel::mpsc;
+use zoon::futures_channel::{mpsc, oneshot};
 use zoon::futures_util::{
-    FutureExt, SinkExt,
-    future::join_all,
-    pin_mut, select,
+    FutureExt, SinkExt, pin_mut, select, select_biased,
     stream::{self, LocalBoxStream, Stream, StreamExt},
 };
-use zoon::{Closure, JsCast, JsValue, SendWrapper, UnwrapThrowExt, history, window};
-use zoon::{Deserialize, Serialize, serde};
+use zoon::{
+    Closure, JsCast, JsValue, SendWrapper, UnwrapThrowExt, history, js_sys, web_sys, window,
+};
+use zoon::{Deserialize, Serialize, serde, serde_json};
 
 use crate::engine::*;
 
 use boon::parser::PersistenceId;
+use boon::parser::source_map::{SourceLocation, SourceMap, source_map_path};
+use boon::platform::browser::common::{LogLevel, emit_log, emit_warning, max_text_bytes};
+use boon::platform::browser::stdlib::{
+    clamp_between, floored_division, floored_modulo, format_number, format_query_params,
+    parse_query_params, set_query_param,
+};
 
 // @TODO make sure Values are deduplicated everywhere it makes sense
 
@@ -1962,1089 +1968,3606 @@ pub fn function_math_sum(
     })
 }
 
-/// Math/round(number) -> Number
-pub fn function_math_round(
+/// Math/average(value) -> Number
+/// Math/average(value, reset) -> Number
+/// Mean of every Number received so far, emitted after each one. `reset` starts over
+/// and emits 0.
+pub fn function_math_average(
     arguments: Arc<Vec<ActorHandle>>,
     function_call_id: ConstructId,
-    _function_call_persistence_id: PersistenceId,
+    function_call_persistence_id: PersistenceId,
     construct_context: ConstructContext,
     _actor_context: ActorContext,
 ) -> impl Stream<Item = Value> {
-    let [argument_number] = arguments.as_slice() else {
-        panic!("Math/round expects 1 argument")
-    };
-    argument_number.clone().stream().map(move |value| {
-        let number = match &value {
-            Value::Number(n, _) => n.number(),
-            _ => panic!("Math/round expects a Number value"),
-        };
-        Number::new_value(
-            ConstructInfo::new(function_call_id.with_child_id(0), None, "Math/round result"),
-            construct_context.clone(),
-            ValueIdempotencyKey::new(),
-            number.round(),
-        )
-    })
+    math_running_total(
+        arguments,
+        function_call_id,
+        function_call_persistence_id,
+        construct_context,
+        "Math/average",
+        |value| match value {
+            Value::Number(n, _) => Some(n.number()),
+            _ => None,
+        },
+        |sum, count| if count == 0.0 { 0.0 } else { sum / count },
+    )
 }
 
-/// Math/min(a, b) -> Number
-pub fn function_math_min(
+/// Math/count(value) -> Number
+/// Math/count(value, reset) -> Number
+/// How many values have been received so far, of any type. `reset` starts over and
+/// emits 0.
+pub fn function_math_count(
     arguments: Arc<Vec<ActorHandle>>,
     function_call_id: ConstructId,
-    _function_call_persistence_id: PersistenceId,
+    function_call_persistence_id: PersistenceId,
     construct_context: ConstructContext,
     _actor_context: ActorContext,
 ) -> impl Stream<Item = Value> {
-    let [argument_a, argument_b] = arguments.as_slice() else {
-        panic!("Math/min expects 2 arguments")
-    };
-    enum Input {
-        A(f64),
-        B(f64),
-    }
-    let a_stream = argument_a.clone().stream().map(|v| match &v {
-        Value::Number(n, _) => Input::A(n.number()),
-        _ => panic!("Math/min expects Number arguments"),
-    });
-    let b_stream = argument_b.clone().stream().map(|v| match &v {
-        Value::Number(n, _) => Input::B(n.number()),
-        _ => panic!("Math/min expects Number arguments"),
-    });
-    stream::select(a_stream, b_stream)
-        .scan(
-            (None::<f64>, None::<f64>),
-            move |(last_a, last_b), input| {
-                match input {
-                    Input::A(val) => *last_a = Some(val),
-                    Input::B(val) => *last_b = Some(val),
-                }
-                if let (Some(a), Some(b)) = (*last_a, *last_b) {
-                    future::ready(Some(Some(Number::new_value(
-                        ConstructInfo::new(
-                            function_call_id.with_child_id(0),
-                            None,
-                            "Math/min result",
-                        ),
-                        construct_context.clone(),
-                        ValueIdempotencyKey::new(),
-                        a.min(b),
-                    ))))
-                } else {
-                    future::ready(Some(None))
-                }
-            },
-        )
-        .filter_map(future::ready)
+    math_running_total(
+        arguments,
+        function_call_id,
+        function_call_persistence_id,
+        construct_context,
+        "Math/count",
+        // Only the count matters, so every value adds nothing to the sum
+        |_| Some(0.0),
+        |_, count| count,
+    )
 }
 
-/// Math/max(a, b) -> Number
-pub fn function_math_max(
+/// Shared body of Math/average and Math/count: a persisted running sum and count of
+/// the input, zeroed by the optional `reset` argument. A value that `number_of`
+/// rejects is skipped and reported once per call.
+fn math_running_total(
     arguments: Arc<Vec<ActorHandle>>,
     function_call_id: ConstructId,
-    _function_call_persistence_id: PersistenceId,
+    function_call_persistence_id: PersistenceId,
     construct_context: ConstructContext,
-    _actor_context: ActorContext,
+    function_name: &'static str,
+    number_of: fn(&Value) -> Option<f64>,
+    result: fn(f64, f64) -> f64,
 ) -> impl Stream<Item = Value> {
-    let [argument_a, argument_b] = arguments.as_slice() else {
-        panic!("Math/max expects 2 arguments")
-    };
+    #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+    #[serde(crate = "serde")]
+    struct State {
+        input_value_idempotency_key: Option<ValueIdempotencyKey>,
+        reset_idempotency_key: Option<ValueIdempotencyKey>,
+        sum: f64,
+        count: f64,
+        output_value_idempotency_key: Option<ValueIdempotencyKey>,
+    }
     enum Input {
-        A(f64),
-        B(f64),
+        Restored(State),
+        Value(ValueIdempotencyKey, Option<f64>),
+        Reset(ValueIdempotencyKey),
     }
-    let a_stream = argument_a.clone().stream().map(|v| match &v {
-        Value::Number(n, _) => Input::A(n.number()),
-        _ => panic!("Math/max expects Number arguments"),
-    });
-    let b_stream = argument_b.clone().stream().map(|v| match &v {
-        Value::Number(n, _) => Input::B(n.number()),
-        _ => panic!("Math/max expects Number arguments"),
-    });
-    stream::select(a_stream, b_stream)
-        .scan(
-            (None::<f64>, None::<f64>),
-            move |(last_a, last_b), input| {
-                match input {
-                    Input::A(val) => *last_a = Some(val),
-                    Input::B(val) => *last_b = Some(val),
-                }
-                if let (Some(a), Some(b)) = (*last_a, *last_b) {
-                    future::ready(Some(Some(Number::new_value(
-                        ConstructInfo::new(
-                            function_call_id.with_child_id(0),
-                            None,
-                            "Math/max result",
-                        ),
-                        construct_context.clone(),
-                        ValueIdempotencyKey::new(),
-                        a.max(b),
-                    ))))
-                } else {
-                    future::ready(Some(None))
-                }
-            },
-        )
-        .filter_map(future::ready)
-}
 
-// @TODO remember configuration?
-/// ```text
-/// Timer/interval(duration<Duration[seconds<Number> | milliseconds<Number>]>) -> []
-/// ```
-pub fn function_timer_interval(
-    arguments: Arc<Vec<ActorHandle>>,
-    function_call_id: ConstructId,
-    _function_call_persistence_id: PersistenceId,
-    construct_context: ConstructContext,
-    _actor_context: ActorContext,
-) -> impl Stream<Item = Value> {
-    let [argument_duration] = arguments.as_slice() else {
-        panic!("Unexpected argument count")
+    let (argument_value, argument_reset) = match arguments.as_slice() {
+        [argument_value] => (argument_value.clone(), None),
+        [argument_value, argument_reset] => (argument_value.clone(), Some(argument_reset.clone())),
+        _ => panic!("{function_name} expects 1 or 2 arguments"),
     };
-    let argument_duration_for_stream = argument_duration.clone();
-    let duration_stream = stream::once(async move {
-        argument_duration_for_stream
-            .stream()
-            .then(|value| async move {
-                let duration_object = value.expect_tagged_object("Duration");
-                if let Some(seconds) = duration_object.variable("seconds") {
-                    seconds
-                        .stream()
-                        .map(|value| value.expect_number().number() * 1000.0)
-                        .left_stream()
-                } else if let Some(milliseconds) = duration_object.variable("milliseconds") {
-                    milliseconds
-                        .stream()
-                        .map(|value| value.expect_number().number())
-                        .right_stream()
-                } else {
-                    panic!(
-                        "Failed to get property 'seconds' or 'milliseconds' from tagged object 'Duration'"
-                    );
-                }
-            })
-            .flatten()
-    })
-    .flatten()
-    .scan(None::<u32>, |last_ms, milliseconds| {
-        let normalized_ms = milliseconds.round().max(0.0).min(u32::MAX as f64) as u32;
-        if *last_ms == Some(normalized_ms) {
-            future::ready(Some(None))
-        } else {
-            *last_ms = Some(normalized_ms);
-            future::ready(Some(Some(normalized_ms)))
+    let storage = construct_context.construct_storage.clone();
+
+    stream::once({
+        let storage = storage.clone();
+        async move {
+            let loaded: Option<State> = storage.load_state(function_call_persistence_id).await;
+            loaded
         }
     })
-    .filter_map(future::ready);
-
-    let (tick_sender, tick_receiver) = mpsc::channel::<Value>(16);
-    let driver_loop_holder: Arc<std::sync::OnceLock<ActorLoop>> =
-        Arc::new(std::sync::OnceLock::new());
-    let driver_loop_holder_for_stream = driver_loop_holder.clone();
-
-    let driver = ActorLoop::new(async move {
-        let mut tick_sender = tick_sender;
-        let mut duration_stream = Box::pin(duration_stream.fuse());
-        let mut current_milliseconds = None::<u32>;
-        let mut result_version = 0u64;
-
-        loop {
-            let Some(milliseconds) = current_milliseconds else {
-                current_milliseconds = duration_stream.as_mut().next().await;
-                if current_milliseconds.is_none() {
-                    break;
-                }
-                continue;
+    .filter_map(future::ready)
+    .map(Input::Restored)
+    .chain(
+        stream::once(async move {
+            let value_stream = argument_value
+                .stream()
+                .map(move |value| Input::Value(value.idempotency_key(), number_of(&value)));
+            let reset_stream = match argument_reset {
+                Some(argument_reset) => argument_reset
+                    .stream()
+                    .map(|value| Input::Reset(value.idempotency_key()))
+                    .boxed_local(),
+                None => stream::empty().boxed_local(),
             };
-
-            let mut duration_stream_ref = duration_stream.as_mut();
-            let next_duration = duration_stream_ref.next().fuse();
-            let interval_sleep = Timer::sleep(milliseconds).fuse();
-            pin_mut!(next_duration);
-            pin_mut!(interval_sleep);
-
-            select! {
-                next_milliseconds = next_duration => {
-                    match next_milliseconds {
-                        Some(next_milliseconds) => current_milliseconds = Some(next_milliseconds),
-                        None => break,
+            stream::select(value_stream, reset_stream)
+        })
+        .flatten(),
+    )
+    .scan(
+        (State::default(), false),
+        move |(state, reported_non_number), input| {
+            match input {
+                Input::Restored(restored) => {
+                    *state = restored;
+                    if state.output_value_idempotency_key.is_none() {
+                        return future::ready(Some(None));
                     }
                 }
-                _ = interval_sleep => {
-                    let output_value = Object::new_value(
-                        ConstructInfo::new(
-                            function_call_id.with_child_id(format!("Timer/interval result v.{result_version}")),
-                            None,
-                            "Timer/interval(.. ) -> [..]",
-                        ),
-                        construct_context.clone(),
-                        ValueIdempotencyKey::new(),
-                        [],
-                    );
-                    if tick_sender.send(output_value).await.is_err() {
-                        break;
+                Input::Value(key, number) => {
+                    if state.input_value_idempotency_key == Some(key) {
+                        return future::ready(Some(None));
                     }
-                    result_version += 1;
+                    state.input_value_idempotency_key = Some(key);
+                    let Some(number) = number else {
+                        report_non_number_once(reported_non_number, function_name);
+                        return future::ready(Some(None));
+                    };
+                    state.sum += number;
+                    state.count += 1.0;
+                    state.output_value_idempotency_key = Some(ValueIdempotencyKey::new());
+                    storage.save_state(function_call_persistence_id, &*state);
+                }
+                Input::Reset(key) => {
+                    if state.reset_idempotency_key == Some(key) {
+                        return future::ready(Some(None));
+                    }
+                    state.reset_idempotency_key = Some(key);
+                    state.sum = 0.0;
+                    state.count = 0.0;
+                    state.output_value_idempotency_key = Some(ValueIdempotencyKey::new());
+                    storage.save_state(function_call_persistence_id, &*state);
                 }
             }
+            future::ready(Some(
+                state
+                    .output_value_idempotency_key
+                    .map(|key| (result(state.sum, state.count), key)),
+            ))
+        },
+    )
+    .filter_map(future::ready)
+    .map({
+        let mut result_version = 0u64;
+        move |(number, idempotency_key)| {
+            let value = Number::new_value(
+                ConstructInfo::new(
+                    function_call_id
+                        .with_child_id(format!("{function_name} result v.{result_version}")),
+                    None,
+                    format!("{function_name}(..) -> Number"),
+                ),
+                construct_context.clone(),
+                idempotency_key,
+                number,
+            );
+            result_version += 1;
+            value
         }
-    });
-
-    let _ = driver_loop_holder.set(driver);
-    tick_receiver.map(move |value| {
-        let _keepalive = &driver_loop_holder_for_stream;
-        value
     })
 }
 
-// --- Text functions ---
+/// Math/round(number) -> Number
+pub fn function_math_round(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    math_unary(
+        arguments,
+        function_call_id,
+        construct_context,
+        "Math/round",
+        f64::round,
+    )
+}
 
-/// Text/empty constant
-pub fn function_text_empty(
-    _arguments: Arc<Vec<ActorHandle>>,
+/// Math/floor(number) -> Number
+/// The largest whole number not above `number`, so -2.5 becomes -3
+pub fn function_math_floor(
+    arguments: Arc<Vec<ActorHandle>>,
     function_call_id: ConstructId,
     _function_call_persistence_id: PersistenceId,
     construct_context: ConstructContext,
     _actor_context: ActorContext,
 ) -> impl Stream<Item = Value> {
-    stream::once(future::ready(Text::new_value(
-        ConstructInfo::new(function_call_id.with_child_id(0), None, "Text/empty"),
+    math_unary(
+        arguments,
+        function_call_id,
         construct_context,
-        ValueIdempotencyKey::new(),
-        String::new(),
-    )))
+        "Math/floor",
+        f64::floor,
+    )
 }
 
-/// Text/space constant - returns a single space character " "
-pub fn function_text_space(
-    _arguments: Arc<Vec<ActorHandle>>,
+/// Math/ceil(number) -> Number
+/// The smallest whole number not below `number`, so -2.5 becomes -2
+pub fn function_math_ceil(
+    arguments: Arc<Vec<ActorHandle>>,
     function_call_id: ConstructId,
     _function_call_persistence_id: PersistenceId,
     construct_context: ConstructContext,
     _actor_context: ActorContext,
 ) -> impl Stream<Item = Value> {
-    stream::once(future::ready(Text::new_value(
-        ConstructInfo::new(function_call_id.with_child_id(0), None, "Text/space"),
+    math_unary(
+        arguments,
+        function_call_id,
         construct_context,
-        ValueIdempotencyKey::new(),
-        " ".to_string(),
-    )))
+        "Math/ceil",
+        f64::ceil,
+    )
 }
 
-/// Text/trim(text) -> Text
-pub fn function_text_trim(
+/// Math/abs(number) -> Number
+pub fn function_math_abs(
     arguments: Arc<Vec<ActorHandle>>,
     function_call_id: ConstructId,
     _function_call_persistence_id: PersistenceId,
     construct_context: ConstructContext,
     _actor_context: ActorContext,
 ) -> impl Stream<Item = Value> {
-    let [argument_text] = arguments.as_slice() else {
-        panic!("Text/trim expects 1 argument")
-    };
-    argument_text.clone().stream().map(move |value| {
-        let text = match &value {
-            Value::Text(t, _) => t.text(),
-            _ => panic!("Text/trim expects a Text value"),
-        };
-        Text::new_value(
-            ConstructInfo::new(function_call_id.with_child_id(0), None, "Text/trim result"),
-            construct_context.clone(),
-            ValueIdempotencyKey::new(),
-            text.trim().to_string(),
-        )
-    })
-}
-
-/// Text/is_empty(text) -> Tag (True/False)
-/// Deduplicated: only emits when the result actually changes
-pub fn function_text_is_empty(
-    arguments: Arc<Vec<ActorHandle>>,
-    function_call_id: ConstructId,
-    _function_call_persistence_id: PersistenceId,
-    construct_context: ConstructContext,
-    _actor_context: ActorContext,
-) -> impl Stream<Item = Value> {
-    let [argument_text] = arguments.as_slice() else {
-        panic!("Text/is_empty expects 1 argument")
-    };
-    argument_text
-        .clone()
-        .stream()
-        .scan(None::<bool>, move |last_result, value| {
-            let text = match &value {
-                Value::Text(t, _) => t.text(),
-                _ => panic!("Text/is_empty expects a Text value"),
-            };
-            let current_result = text.is_empty();
-
-            // Only emit when result actually changes
-            if *last_result != Some(current_result) {
-                *last_result = Some(current_result);
-                let tag = if current_result { "True" } else { "False" };
-                future::ready(Some(Some(Tag::new_value(
-                    ConstructInfo::new(
-                        function_call_id.with_child_id(0),
-                        None,
-                        "Text/is_empty result",
-                    ),
-                    construct_context.clone(),
-                    ValueIdempotencyKey::new(),
-                    tag.to_string(),
-                ))))
-            } else {
-                future::ready(Some(None))
-            }
-        })
-        .filter_map(future::ready)
+    math_unary(
+        arguments,
+        function_call_id,
+        construct_context,
+        "Math/abs",
+        f64::abs,
+    )
 }
 
-/// Text/is_not_empty(text) -> Tag (True/False)
-/// Deduplicated: only emits when the result actually changes
-pub fn function_text_is_not_empty(
+/// Math/negate_if(number, condition) -> Number
+/// `-number` while `condition` is True, `number` while it is False
+pub fn function_math_negate_if(
     arguments: Arc<Vec<ActorHandle>>,
     function_call_id: ConstructId,
     _function_call_persistence_id: PersistenceId,
     construct_context: ConstructContext,
     _actor_context: ActorContext,
 ) -> impl Stream<Item = Value> {
-    let [argument_text] = arguments.as_slice() else {
-        panic!("Text/is_not_empty expects 1 argument")
+    let [argument_number, argument_condition] = arguments.as_slice() else {
+        panic!("Math/negate_if expects 2 arguments")
     };
-    argument_text
-        .clone()
-        .stream()
-        .scan(None::<bool>, move |last_result, value| {
-            let text = match &value {
-                Value::Text(t, _) => t.text(),
-                _ => panic!("Text/is_not_empty expects a Text value"),
-            };
-            let current_result = !text.is_empty();
-
-            // Only emit when result actually changes
-            if *last_result != Some(current_result) {
-                *last_result = Some(current_result);
-                let tag = if current_result { "True" } else { "False" };
-                future::ready(Some(Some(Tag::new_value(
+    enum Input {
+        Number(f64),
+        Condition(bool),
+    }
+    let number_stream = argument_number.clone().stream().map(|v| match &v {
+        Value::Number(n, _) => Input::Number(n.number()),
+        _ => panic!("Math/negate_if expects Number for first argument"),
+    });
+    let condition_stream = argument_condition.clone().stream().map(|v| match &v {
+        Value::Tag(tag, _) if tag.tag() == "True" => Input::Condition(true),
+        Value::Tag(tag, _) if tag.tag() == "False" => Input::Condition(false),
+        _ => panic!("Math/negate_if expects True or False for condition argument"),
+    });
+    stream::select(number_stream, condition_stream)
+        .scan(
+            (None::<f64>, None::<bool>),
+            move |(last_number, last_condition), input| {
+                match input {
+                    Input::Number(n) => *last_number = Some(n),
+                    Input::Condition(c) => *last_condition = Some(c),
+                }
+                let (Some(number), Some(condition)) = (*last_number, *last_condition) else {
+                    return future::ready(Some(None));
+                };
+                // `0.0 - number` rather than `-number`, so a negated 0 does not print as -0
+                let result = if condition { 0.0 - number } else { number };
+                future::ready(Some(Some(Number::new_value(
                     ConstructInfo::new(
                         function_call_id.with_child_id(0),
                         None,
-                        "Text/is_not_empty result",
+                        "Math/negate_if result",
                     ),
                     construct_context.clone(),
                     ValueIdempotencyKey::new(),
-                    tag.to_string(),
+                    result,
                 ))))
-            } else {
-                future::ready(Some(None))
-            }
-        })
+            },
+        )
         .filter_map(future::ready)
 }
 
-/// Text/to_number(text) -> Number | NaN tag
-/// Parses text to f64. Returns Number on success, NaN tag on failure.
-pub fn function_text_to_number(
+/// Math/sqrt(number) -> Number | MathError
+/// A negative number has no square root and becomes the `MathError` tag
+pub fn function_math_sqrt(
     arguments: Arc<Vec<ActorHandle>>,
     function_call_id: ConstructId,
     _function_call_persistence_id: PersistenceId,
     construct_context: ConstructContext,
     _actor_context: ActorContext,
 ) -> impl Stream<Item = Value> {
-    let [argument_text] = arguments.as_slice() else {
-        panic!("Text/to_number expects 1 argument")
+    let [argument_number] = arguments.as_slice() else {
+        panic!("Math/sqrt expects 1 argument")
     };
-    argument_text.clone().stream().map(move |value| {
-        let text = match &value {
-            Value::Text(t, _) => t.text(),
-            _ => panic!("Text/to_number expects a Text value"),
+    argument_number.clone().stream().map(move |value| {
+        let number = match &value {
+            Value::Number(n, _) => n.number(),
+            _ => panic!("Math/sqrt expects a Number value"),
         };
-        match text.trim().parse::<f64>() {
-            Ok(number) => Number::new_value(
-                ConstructInfo::new(
-                    function_call_id.with_child_id(0),
-                    None,
-                    "Text/to_number result",
-                ),
-                construct_context.clone(),
-                ValueIdempotencyKey::new(),
-                number,
-            ),
-            Err(_) => Tag::new_value(
-                ConstructInfo::new(
-                    function_call_id.with_child_id(1),
-                    None,
-                    "Text/to_number NaN",
-                ),
-                construct_context.clone(),
-                ValueIdempotencyKey::new(),
-                "NaN".to_string(),
-            ),
-        }
+        math_finite_result(
+            &function_call_id,
+            construct_context.clone(),
+            "Math/sqrt",
+            number.sqrt(),
+        )
     })
 }
 
-/// Text/starts_with(text, prefix) -> Tag (True/False)
-/// Deduplicated: only emits when the result actually changes
-pub fn function_text_starts_with(
+/// Math/pow(base, exponent) -> Number | MathError
+/// A result that is not finite (`0 |> Math/pow(exponent: -1)`, a fractional power of
+/// a negative base) becomes the `MathError` tag
+pub fn function_math_pow(
     arguments: Arc<Vec<ActorHandle>>,
     function_call_id: ConstructId,
     _function_call_persistence_id: PersistenceId,
     construct_context: ConstructContext,
     _actor_context: ActorContext,
 ) -> impl Stream<Item = Value> {
-    let [argument_text, argument_prefix] = arguments.as_slice() else {
-        panic!("Text/starts_with expects 2 arguments")
+    let [argument_base, argument_exponent] = arguments.as_slice() else {
+        panic!("Math/pow expects 2 arguments")
     };
     enum Input {
-        Text(String),
-        Prefix(String),
+        Base(f64),
+        Exponent(f64),
     }
-    let text_stream = argument_text.clone().stream().map(|v| match &v {
-        Value::Text(t, _) => Input::Text(t.text().to_string()),
-        _ => panic!("Text/starts_with expects Text for first argument"),
+    let base_stream = argument_base.clone().stream().map(|v| match &v {
+        Value::Number(n, _) => Input::Base(n.number()),
+        _ => panic!("Math/pow expects Number for first argument"),
     });
-    let prefix_stream = argument_prefix.clone().stream().map(|v| match &v {
-        Value::Text(t, _) => Input::Prefix(t.text().to_string()),
-        _ => panic!("Text/starts_with expects Text for second argument"),
+    let exponent_stream = argument_exponent.clone().stream().map(|v| match &v {
+        Value::Number(n, _) => Input::Exponent(n.number()),
+        _ => panic!("Math/pow expects Number for exponent argument"),
     });
-    stream::select(text_stream, prefix_stream)
+    stream::select(base_stream, exponent_stream)
         .scan(
-            (None::<String>, None::<String>, None::<bool>),
-            move |(last_text, last_prefix, last_result), input| {
+            (None::<f64>, None::<f64>),
+            move |(last_base, last_exponent), input| {
                 match input {
-                    Input::Text(t) => *last_text = Some(t),
-                    Input::Prefix(p) => *last_prefix = Some(p),
-                }
-                // Only compute when both inputs have arrived
-                if let (Some(text), Some(prefix)) = (last_text.as_ref(), last_prefix.as_ref()) {
-                    let current_result = text.starts_with(prefix.as_str());
-                    if *last_result != Some(current_result) {
-                        *last_result = Some(current_result);
-                        let tag = if current_result { "True" } else { "False" };
-                        future::ready(Some(Some(Tag::new_value(
-                            ConstructInfo::new(
-                                function_call_id.with_child_id(0),
-                                None,
-                                "Text/starts_with result",
-                            ),
-                            construct_context.clone(),
-                            ValueIdempotencyKey::new(),
-                            tag.to_string(),
-                        ))))
-                    } else {
-                        future::ready(Some(None))
-                    }
-                } else {
-                    // Don't emit until both inputs are available
-                    future::ready(Some(None))
+                    Input::Base(b) => *last_base = Some(b),
+                    Input::Exponent(e) => *last_exponent = Some(e),
                 }
+                let (Some(base), Some(exponent)) = (*last_base, *last_exponent) else {
+                    return future::ready(Some(None));
+                };
+                future::ready(Some(Some(math_finite_result(
+                    &function_call_id,
+                    construct_context.clone(),
+                    "Math/pow",
+                    base.powf(exponent),
+                ))))
             },
         )
         .filter_map(future::ready)
 }
 
-// --- Bool functions ---
-
-/// Bool/not(value) -> Tag (True/False)
-pub fn function_bool_not(
-    arguments: Arc<Vec<ActorHandle>>,
-    function_call_id: ConstructId,
-    _function_call_persistence_id: PersistenceId,
+/// `result` as a Number, or the `MathError` tag when it is NaN or infinite, so the
+/// failure can be matched in a WHEN instead of printing as "NaN" in TEXT.
+fn math_finite_result(
+    function_call_id: &ConstructId,
     construct_context: ConstructContext,
-    _actor_context: ActorContext,
-) -> impl Stream<Item = Value> {
-    let [argument_value] = arguments.as_slice() else {
-        panic!("Bool/not expects 1 argument")
-    };
-    argument_value.clone().stream().map(move |value| {
-        let is_true = match &value {
-            Value::Tag(tag, _) => tag.tag() == "True",
-            _ => panic!("Bool/not expects a Tag (True/False)"),
-        };
-        let result_tag = if is_true { "False" } else { "True" };
+    function_name: &'static str,
+    result: f64,
+) -> Value {
+    if result.is_finite() {
+        Number::new_value(
+            ConstructInfo::new(
+                function_call_id.with_child_id(0),
+                None,
+                format!("{function_name} result"),
+            ),
+            construct_context,
+            ValueIdempotencyKey::new(),
+            result,
+        )
+    } else {
         Tag::new_value(
-            ConstructInfo::new(function_call_id.with_child_id(0), None, "Bool/not result"),
-            construct_context.clone(),
+            ConstructInfo::new(
+                function_call_id.with_child_id(1),
+                None,
+                format!("{function_name} MathError"),
+            ),
+            construct_context,
             ValueIdempotencyKey::new(),
-            result_tag.to_string(),
+            "MathError".to_string(),
         )
-    })
+    }
 }
 
-/// Bool/toggle(value, when) -> Tag (True/False)
-/// Toggles the boolean value each time 'when' stream produces a value
-pub fn function_bool_toggle(
+/// Shared body of the one-operand Math functions, one result per incoming Number.
+fn math_unary(
     arguments: Arc<Vec<ActorHandle>>,
     function_call_id: ConstructId,
-    _function_call_persistence_id: PersistenceId,
     construct_context: ConstructContext,
-    _actor_context: ActorContext,
+    function_name: &'static str,
+    round: fn(f64) -> f64,
 ) -> impl Stream<Item = Value> {
-    let argument_value = arguments[0].clone();
-    let argument_when = arguments[1].clone();
-
-    // Two independent streams merged via select:
-    // - value_stream: pipe input (initial bool + updates from toggle-all)
-    // - when_stream: toggle trigger (individual checkbox click)
-    enum Msg {
-        SetValue(bool),
-        Toggle,
-    }
-
-    let value_stream = argument_value.stream().filter_map(|value| {
-        future::ready(match &value {
-            Value::Tag(tag, _) => Some(Msg::SetValue(tag.tag() == "True")),
-            _ => None,
-        })
-    });
-
-    let when_stream = argument_when.stream().map(|_| Msg::Toggle);
-
-    stream::select(value_stream, when_stream).scan(None::<bool>, move |state, msg| {
-        match msg {
-            Msg::SetValue(v) => *state = Some(v),
-            Msg::Toggle => {
-                let current = state.unwrap_or(false);
-                *state = Some(!current);
-            }
-        }
-        let is_true = state.unwrap_or(false);
-        let result_tag = if is_true { "True" } else { "False" };
-        future::ready(Some(Tag::new_value(
+    let [argument_number] = arguments.as_slice() else {
+        panic!("{function_name} expects 1 argument")
+    };
+    argument_number.clone().stream().map(move |value| {
+        let number = match &value {
+            Value::Number(n, _) => n.number(),
+            _ => panic!("{function_name} expects a Number value"),
+        };
+        Number::new_value(
             ConstructInfo::new(
                 function_call_id.with_child_id(0),
                 None,
-                "Bool/toggle result",
+                format!("{function_name} result"),
             ),
             construct_context.clone(),
             ValueIdempotencyKey::new(),
-            result_tag.to_string(),
-        )))
+            round(number),
+        )
     })
 }
 
-/// Bool/or(this, that) -> Tag (True/False)
-/// Returns True if either this or that is True
-pub fn function_bool_or(
+/// Math/min(a, with) -> Number
+/// Recomputes when either operand changes; `b:` is still accepted in place of `with:`
+pub fn function_math_min(
     arguments: Arc<Vec<ActorHandle>>,
     function_call_id: ConstructId,
     _function_call_persistence_id: PersistenceId,
     construct_context: ConstructContext,
     _actor_context: ActorContext,
 ) -> impl Stream<Item = Value> {
-    let this_actor = arguments[0].clone();
-    let that_actor = arguments[1].clone();
+    math_two_operands(
+        arguments,
+        function_call_id,
+        construct_context,
+        "Math/min",
+        f64::min,
+    )
+}
 
-    // Combine both boolean streams using select
-    let this_stream = this_actor.stream().map(|v| (true, v));
-    let that_stream = that_actor.stream().map(|v| (false, v));
+/// Math/max(a, with) -> Number
+/// Recomputes when either operand changes; `b:` is still accepted in place of `with:`
+pub fn function_math_max(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    math_two_operands(
+        arguments,
+        function_call_id,
+        construct_context,
+        "Math/max",
+        f64::max,
+    )
+}
 
-    stream::select(this_stream, that_stream)
+/// Shared body of Math/min and Math/max. A value that is not a Number is skipped,
+/// reported once per call, and the last Number of that operand stays in use.
+fn math_two_operands(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    construct_context: ConstructContext,
+    function_name: &'static str,
+    combine: fn(f64, f64) -> f64,
+) -> impl Stream<Item = Value> {
+    let [argument_a, argument_with] = arguments.as_slice() else {
+        panic!("{function_name} expects 2 arguments")
+    };
+    enum Input {
+        A(Option<f64>),
+        With(Option<f64>),
+    }
+    let number = |value: &Value| match value {
+        Value::Number(n, _) => Some(n.number()),
+        _ => None,
+    };
+    let a_stream = argument_a
+        .clone()
+        .stream()
+        .map(move |v| Input::A(number(&v)));
+    let with_stream = argument_with
+        .clone()
+        .stream()
+        .map(move |v| Input::With(number(&v)));
+    stream::select(a_stream, with_stream)
         .scan(
-            (None::<bool>, None::<bool>),
-            move |state, (is_this, value)| {
-                let is_true = match &value {
-                    Value::Tag(tag, _) => tag.tag() == "True",
-                    _ => false,
-                };
-                if is_this {
-                    state.0 = Some(is_true);
-                } else {
-                    state.1 = Some(is_true);
+            (None::<f64>, None::<f64>, false),
+            move |(last_a, last_with, reported_non_number), input| {
+                match input {
+                    Input::A(Some(a)) => *last_a = Some(a),
+                    Input::With(Some(with)) => *last_with = Some(with),
+                    Input::A(None) | Input::With(None) => {
+                        report_non_number_once(reported_non_number, function_name);
+                        return future::ready(Some(None));
+                    }
                 }
-                future::ready(Some(*state))
+                let (Some(a), Some(with)) = (*last_a, *last_with) else {
+                    return future::ready(Some(None));
+                };
+                future::ready(Some(Some(Number::new_value(
+                    ConstructInfo::new(
+                        function_call_id.with_child_id(0),
+                        None,
+                        format!("{function_name} result"),
+                    ),
+                    construct_context.clone(),
+                    ValueIdempotencyKey::new(),
+                    combine(a, with),
+                ))))
             },
         )
-        .filter_map(move |(this_bool, that_bool)| {
-            let construct_context = construct_context.clone();
-            let function_call_id = function_call_id.clone();
-            future::ready(match (this_bool, that_bool) {
-                (Some(a), Some(b)) => {
-                    let result = a || b;
-                    let tag = if result { "True" } else { "False" };
-                    Some(Tag::new_value(
-                        ConstructInfo::new(
-                            function_call_id.with_child_id(0),
-                            None,
-                            "Bool/or result",
-                        ),
-                        construct_context,
-                        ValueIdempotencyKey::new(),
-                        tag.to_string(),
-                    ))
-                }
-                _ => None, // Wait for both values
-            })
-        })
+        .filter_map(future::ready)
 }
 
-// --- List functions ---
-
-/// List/is_empty() -> Tag (True/False)
-/// Checks if the piped list is empty
-/// Deduplicated: only emits when the result actually changes
-pub fn function_list_is_empty(
+/// Math/clamp(value, min, max) -> Number
+/// Recomputes when any of the three changes. While `min` is above `max` the result is
+/// `min`, with a warning each time the bounds cross.
+pub fn function_math_clamp(
     arguments: Arc<Vec<ActorHandle>>,
     function_call_id: ConstructId,
     _function_call_persistence_id: PersistenceId,
     construct_context: ConstructContext,
     _actor_context: ActorContext,
 ) -> impl Stream<Item = Value> {
-    let list_actor = arguments[0].clone();
-    let list_stream = list_actor.stream().filter_map(move |value| {
-        let result = match &value {
-            Value::List(list, _) => Some(list.clone()),
-            _ => None,
-        };
-        future::ready(result)
-    });
-    switch_map(list_stream, move |list| {
-        let construct_context = construct_context.clone();
-        let function_call_id = function_call_id.clone();
-        list.stream()
-            .scan(
-                (Vec::<ActorHandle>::new(), None::<bool>),
-                move |(items, last_result), change| {
-                    change.apply_to_vec(items);
-                    let current_result = items.is_empty();
-
-                    // Only emit when result actually changes
-                    if *last_result != Some(current_result) {
-                        *last_result = Some(current_result);
-                        let tag = if current_result { "True" } else { "False" };
-                        future::ready(Some(Some(Tag::new_value(
-                            ConstructInfo::new(
-                                function_call_id.with_child_id(0),
-                                None,
-                                "List/is_empty result",
-                            ),
-                            construct_context.clone(),
-                            ValueIdempotencyKey::new(),
-                            tag.to_string(),
-                        ))))
-                    } else {
-                        future::ready(Some(None))
+    let [argument_value, argument_min, argument_max] = arguments.as_slice() else {
+        panic!("Math/clamp expects 3 arguments")
+    };
+    enum Input {
+        Value(Option<f64>),
+        Min(Option<f64>),
+        Max(Option<f64>),
+    }
+    let number = |value: &Value| match value {
+        Value::Number(n, _) => Some(n.number()),
+        _ => None,
+    };
+    let value_stream = argument_value
+        .clone()
+        .stream()
+        .map(move |v| Input::Value(number(&v)));
+    let min_stream = argument_min
+        .clone()
+        .stream()
+        .map(move |v| Input::Min(number(&v)));
+    let max_stream = argument_max
+        .clone()
+        .stream()
+        .map(move |v| Input::Max(number(&v)));
+    stream::select(value_stream, stream::select(min_stream, max_stream))
+        .scan(
+            ([None::<f64>; 3], false, false),
+            move |([last_value, last_min, last_max], reported_non_number, inverted), input| {
+                match input {
+                    Input::Value(Some(value)) => *last_value = Some(value),
+                    Input::Min(Some(min)) => *last_min = Some(min),
+                    Input::Max(Some(max)) => *last_max = Some(max),
+                    Input::Value(None) | Input::Min(None) | Input::Max(None) => {
+                        report_non_number_once(reported_non_number, "Math/clamp");
+                        return future::ready(Some(None));
                     }
-                },
-            )
-            .filter_map(future::ready)
-    })
+                }
+                let (Some(value), Some(min), Some(max)) = (*last_value, *last_min, *last_max)
+                else {
+                    return future::ready(Some(None));
+                };
+                if min > max && !*inverted {
+                    emit_warning(
+                        LogLevel::Warn,
+                        "math",
+                        format!("Math/clamp min {min} is above max {max}; using min"),
+                    );
+                }
+                *inverted = min > max;
+                future::ready(Some(Some(Number::new_value(
+                    ConstructInfo::new(
+                        function_call_id.with_child_id(0),
+                        None,
+                        "Math/clamp result",
+                    ),
+                    construct_context.clone(),
+                    ValueIdempotencyKey::new(),
+                    clamp_between(value, min, max),
+                ))))
+            },
+        )
+        .filter_map(future::ready)
 }
 
-/// List/count -> Number
-/// Returns the count of items in the list
-/// Deduplicated: only emits when the count actually changes
-pub fn function_list_count(
+/// Warns about the first value of a call that is not a Number; the Math and Stream
+/// functions skip it and any later ones silently.
+fn report_non_number_once(reported_non_number: &mut bool, function_name: &str) {
+    if !*reported_non_number {
+        *reported_non_number = true;
+        emit_warning(
+            LogLevel::Warn,
+            "type",
+            format!("{function_name} expects Numbers; skipping a value that is not one"),
+        );
+    }
+}
+
+/// Number/to_text(number, decimals) -> Text
+/// Number/to_text(number, decimals, thousands_separator) -> Text
+/// Fixed `decimals` places, with `thousands_separator` between digit groups when given.
+/// TEXT interpolation keeps printing numbers as they are; this is the explicit format.
+pub fn function_number_to_text(
     arguments: Arc<Vec<ActorHandle>>,
     function_call_id: ConstructId,
     _function_call_persistence_id: PersistenceId,
     construct_context: ConstructContext,
     _actor_context: ActorContext,
 ) -> impl Stream<Item = Value> {
-    let list_actor = arguments[0].clone();
-    let list_stream = list_actor.stream().filter_map(move |value| {
-        let result = match &value {
-            Value::List(list, _) => Some(list.clone()),
-            _ => None,
-        };
-        future::ready(result)
+    let (argument_number, argument_decimals, argument_separator) = match arguments.as_slice() {
+        [number, decimals] => (number, decimals, None),
+        [number, decimals, separator] => (number, decimals, Some(separator)),
+        _ => panic!("Number/to_text expects 2 or 3 arguments"),
+    };
+    enum Input {
+        Number(f64),
+        Decimals(f64),
+        Separator(String),
+    }
+    let number_stream = argument_number.clone().stream().map(|v| match &v {
+        Value::Number(n, _) => Input::Number(n.number()),
+        _ => panic!("Number/to_text expects Number for first argument"),
     });
-    switch_map(list_stream, move |list| {
-        let construct_context = construct_context.clone();
-        let function_call_id = function_call_id.clone();
-        list.stream()
-            .scan(
-                (Vec::<ActorHandle>::new(), None::<usize>),
-                move |(items, last_count), change| {
-                    change.apply_to_vec(items);
-                    let current_count = items.len();
-
-                    // Only emit when count actually changes
-                    if *last_count != Some(current_count) {
-                        *last_count = Some(current_count);
-                        future::ready(Some(Some(Number::new_value(
-                            ConstructInfo::new(
-                                function_call_id.with_child_id(0),
-                                None,
-                                "List/count result",
-                            ),
-                            construct_context.clone(),
-                            ValueIdempotencyKey::new(),
-                            current_count as f64,
-                        ))))
-                    } else {
-                        future::ready(Some(None))
-                    }
-                },
-            )
-            .filter_map(future::ready)
-    })
+    let decimals_stream = argument_decimals.clone().stream().map(|v| match &v {
+        Value::Number(n, _) => Input::Decimals(n.number()),
+        _ => panic!("Number/to_text expects Number for decimals argument"),
+    });
+    let separator_stream = match argument_separator {
+        Some(argument_separator) => argument_separator
+            .clone()
+            .stream()
+            .map(|v| match &v {
+                Value::Text(t, _) => Input::Separator(t.text().to_string()),
+                _ => panic!("Number/to_text expects Text for thousands_separator argument"),
+            })
+            .boxed_local(),
+        None => stream::empty().boxed_local(),
+    };
+    let options_stream = stream::select(decimals_stream, separator_stream);
+    // Without a thousands_separator argument the digits are not grouped at all.
+    let initial_separator = argument_separator.is_none().then(String::new);
+    stream::select(number_stream, options_stream)
+        .scan(
+            (None::<f64>, None::<f64>, initial_separator),
+            move |(last_number, last_decimals, last_separator), input| {
+                match input {
+                    Input::Number(n) => *last_number = Some(n),
+                    Input::Decimals(d) => *last_decimals = Some(d),
+                    Input::Separator(s) => *last_separator = Some(s),
+                }
+                let (Some(number), Some(decimals), Some(separator)) =
+                    (*last_number, *last_decimals, last_separator.as_ref())
+                else {
+                    return future::ready(Some(None));
+                };
+                future::ready(Some(Some(Text::new_value(
+                    ConstructInfo::new(
+                        function_call_id.with_child_id(0),
+                        None,
+                        "Number/to_text result",
+                    ),
+                    construct_context.clone(),
+                    ValueIdempotencyKey::new(),
+                    format_number(number, decimals, separator),
+                ))))
+            },
+        )
+        .filter_map(future::ready)
 }
 
-/// List/is_not_empty() -> Tag (True/False)
-/// Checks if the piped list is not empty (inverse of List/is_empty)
-/// Deduplicated: only emits when the result actually changes
-pub fn function_list_is_not_empty(
+/// Number/parse(text) -> Number | ParseError[text: Text]
+/// The trimmed Text read as a whole or decimal number. Anything else becomes
+/// `ParseError[text]` with the original Text, for a WHEN arm to branch on.
+pub fn function_number_parse(
     arguments: Arc<Vec<ActorHandle>>,
     function_call_id: ConstructId,
-    _function_call_persistence_id: PersistenceId,
+    function_call_persistence_id: PersistenceId,
     construct_context: ConstructContext,
-    _actor_context: ActorContext,
+    actor_context: ActorContext,
 ) -> impl Stream<Item = Value> {
-    let list_actor = arguments[0].clone();
-    let list_stream = list_actor.stream().filter_map(move |value| {
-        let result = match &value {
-            Value::List(list, _) => Some(list.clone()),
-            _ => None,
+    let [argument_text] = arguments.as_slice() else {
+        panic!("Number/parse expects 1 argument")
+    };
+    let mut result_version = 0u64;
+    argument_text.clone().stream().map(move |value| {
+        let text = match &value {
+            Value::Text(t, _) => t.text().to_string(),
+            _ => panic!("Number/parse expects a Text value"),
         };
-        future::ready(result)
-    });
-    switch_map(list_stream, move |list| {
-        let construct_context = construct_context.clone();
-        let function_call_id = function_call_id.clone();
-        list.stream()
-            .scan(
-                (Vec::<ActorHandle>::new(), None::<bool>),
-                move |(items, last_result), change| {
-                    change.apply_to_vec(items);
-                    let current_result = !items.is_empty();
-
-                    // Only emit when result actually changes
-                    if *last_result != Some(current_result) {
-                        *last_result = Some(current_result);
-                        let tag = if current_result { "True" } else { "False" };
-                        future::ready(Some(Some(Tag::new_value(
-                            ConstructInfo::new(
-                                function_call_id.with_child_id(0),
-                                None,
-                                "List/is_not_empty result",
-                            ),
-                            construct_context.clone(),
-                            ValueIdempotencyKey::new(),
-                            tag.to_string(),
-                        ))))
-                    } else {
-                        future::ready(Some(None))
-                    }
-                },
-            )
-            .filter_map(future::ready)
+        result_version += 1;
+        if let Some(number) = parse_number(&text) {
+            return Number::new_value(
+                ConstructInfo::new(
+                    function_call_id.with_child_id(0),
+                    None,
+                    "Number/parse result",
+                ),
+                construct_context.clone(),
+                ValueIdempotencyKey::new(),
+                number,
+            );
+        }
+        let text_actor = create_constant_actor(
+            ConstructInfo::new(
+                function_call_id.with_child_id(format!("Number/parse text v.{result_version}")),
+                None,
+                "Number/parse -> ParseError[text]",
+            ),
+            function_call_persistence_id.with_child_index(0),
+            Text::new_value(
+                ConstructInfo::new(
+                    function_call_id
+                        .with_child_id(format!("Number/parse text value v.{result_version}")),
+                    None,
+                    "Number/parse -> ParseError[text: ..]",
+                ),
+                construct_context.clone(),
+                ValueIdempotencyKey::new(),
+                text,
+            ),
+            actor_context.scope_id(),
+        );
+        TaggedObject::new_value(
+            ConstructInfo::new(
+                function_call_id.with_child_id(format!("Number/parse error v.{result_version}")),
+                None,
+                "Number/parse -> ParseError[..]",
+            ),
+            construct_context.clone(),
+            ValueIdempotencyKey::new(),
+            "ParseError",
+            [Variable::new_arc(
+                ConstructInfo::new(
+                    function_call_id
+                        .with_child_id(format!("Number/parse text variable v.{result_version}")),
+                    None,
+                    "Number/parse -> ParseError[text] variable",
+                ),
+                construct_context.clone(),
+                "text",
+                text_actor,
+                function_call_persistence_id.with_child_index(1),
+                actor_context.scope.clone(),
+            )],
+        )
+    })
+}
+
+/// Reads an optional sign, digits and an optional decimal point with more digits,
+/// after trimming whitespace: "42", " -3.5 ", ".5" and "5." parse. Exponents,
+/// `inf`, `NaN` and digit grouping do not, unlike `str::parse::<f64>`.
+fn parse_number(text: &str) -> Option<f64> {
+    let text = text.trim();
+    let unsigned = text.strip_prefix(['-', '+']).unwrap_or(text);
+    let (integer, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+    let all_digits = |part: &str| part.chars().all(|character| character.is_ascii_digit());
+    let has_digits = !integer.is_empty() || !fraction.is_empty();
+    if !(has_digits && all_digits(integer) && all_digits(fraction)) {
+        return None;
+    }
+    text.parse().ok()
+}
+
+// @TODO remember configuration?
+/// ```text
+/// Timer/interval(duration<Duration[seconds<Number> | milliseconds<Number>]>) -> []
+/// ```
+pub fn function_timer_interval(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    let [argument_duration] = arguments.as_slice() else {
+        panic!("Unexpected argument count")
+    };
+    let argument_duration_for_stream = argument_duration.clone();
+    let duration_stream = stream::once(async move {
+        argument_duration_for_stream
+            .stream()
+            .then(|value| async move {
+                let duration_object = value.expect_tagged_object("Duration");
+                if let Some(seconds) = duration_object.variable("seconds") {
+                    seconds
+                        .stream()
+                        .map(|value| value.expect_number().number() * 1000.0)
+                        .left_stream()
+                } else if let Some(milliseconds) = duration_object.variable("milliseconds") {
+                    milliseconds
+                        .stream()
+                        .map(|value| value.expect_number().number())
+                        .right_stream()
+                } else {
+                    panic!(
+                        "Failed to get property 'seconds' or 'milliseconds' from tagged object 'Duration'"
+                    );
+                }
+            })
+            .flatten()
+    })
+    .flatten()
+    .scan(None::<u32>, |last_ms, milliseconds| {
+        let normalized_ms = milliseconds.round().max(0.0).min(u32::MAX as f64) as u32;
+        if *last_ms == Some(normalized_ms) {
+            future::ready(Some(None))
+        } else {
+            *last_ms = Some(normalized_ms);
+            future::ready(Some(Some(normalized_ms)))
+        }
+    })
+    .filter_map(future::ready);
+
+    let (tick_sender, tick_receiver) = mpsc::channel::<Value>(16);
+    let driver_loop_holder: Arc<std::sync::OnceLock<ActorLoop>> =
+        Arc::new(std::sync::OnceLock::new());
+    let driver_loop_holder_for_stream = driver_loop_holder.clone();
+
+    let driver = ActorLoop::new(async move {
+        let mut tick_sender = tick_sender;
+        let mut duration_stream = Box::pin(duration_stream.fuse());
+        let mut current_milliseconds = None::<u32>;
+        let mut result_version = 0u64;
+
+        loop {
+            let Some(milliseconds) = current_milliseconds else {
+                current_milliseconds = duration_stream.as_mut().next().await;
+                if current_milliseconds.is_none() {
+                    break;
+                }
+                continue;
+            };
+
+            let mut duration_stream_ref = duration_stream.as_mut();
+            let next_duration = duration_stream_ref.next().fuse();
+            let interval_sleep = Timer::sleep(milliseconds).fuse();
+            pin_mut!(next_duration);
+            pin_mut!(interval_sleep);
+
+            select! {
+                next_milliseconds = next_duration => {
+                    match next_milliseconds {
+                        Some(next_milliseconds) => current_milliseconds = Some(next_milliseconds),
+                        None => break,
+                    }
+                }
+                _ = interval_sleep => {
+                    let output_value = Object::new_value(
+                        ConstructInfo::new(
+                            function_call_id.with_child_id(format!("Timer/interval result v.{result_version}")),
+                            None,
+                            "Timer/interval(.. ) -> [..]",
+                        ),
+                        construct_context.clone(),
+                        ValueIdempotencyKey::new(),
+                        [],
+                    );
+                    if tick_sender.send(output_value).await.is_err() {
+                        break;
+                    }
+                    result_version += 1;
+                }
+            }
+        }
+    });
+
+    let _ = driver_loop_holder.set(driver);
+    tick_receiver.map(move |value| {
+        let _keepalive = &driver_loop_holder_for_stream;
+        value
+    })
+}
+
+// --- Text functions ---
+
+/// Text/empty constant
+pub fn function_text_empty(
+    _arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    stream::once(future::ready(Text::new_value(
+        ConstructInfo::new(function_call_id.with_child_id(0), None, "Text/empty"),
+        construct_context,
+        ValueIdempotencyKey::new(),
+        String::new(),
+    )))
+}
+
+/// Text/space constant - returns a single space character " "
+pub fn function_text_space(
+    _arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    stream::once(future::ready(Text::new_value(
+        ConstructInfo::new(function_call_id.with_child_id(0), None, "Text/space"),
+        construct_context,
+        ValueIdempotencyKey::new(),
+        " ".to_string(),
+    )))
+}
+
+/// Text/trim(text) -> Text
+pub fn function_text_trim(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    let [argument_text] = arguments.as_slice() else {
+        panic!("Text/trim expects 1 argument")
+    };
+    argument_text.clone().stream().map(move |value| {
+        let text = match &value {
+            Value::Text(t, _) => t.text(),
+            _ => panic!("Text/trim expects a Text value"),
+        };
+        Text::new_value(
+            ConstructInfo::new(function_call_id.with_child_id(0), None, "Text/trim result"),
+            construct_context.clone(),
+            ValueIdempotencyKey::new(),
+            text.trim().to_string(),
+        )
+    })
+}
+
+/// Text/is_empty(text) -> Tag (True/False)
+/// Deduplicated: only emits when the result actually changes
+pub fn function_text_is_empty(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    let [argument_text] = arguments.as_slice() else {
+        panic!("Text/is_empty expects 1 argument")
+    };
+    argument_text
+        .clone()
+        .stream()
+        .scan(None::<bool>, move |last_result, value| {
+            let text = match &value {
+                Value::Text(t, _) => t.text(),
+                _ => panic!("Text/is_empty expects a Text value"),
+            };
+            let current_result = text.is_empty();
+
+            // Only emit when result actually changes
+            if *last_result != Some(current_result) {
+                *last_result = Some(current_result);
+                let tag = if current_result { "True" } else { "False" };
+                future::ready(Some(Some(Tag::new_value(
+                    ConstructInfo::new(
+                        function_call_id.with_child_id(0),
+                        None,
+                        "Text/is_empty result",
+                    ),
+                    construct_context.clone(),
+                    ValueIdempotencyKey::new(),
+                    tag.to_string(),
+                ))))
+            } else {
+                future::ready(Some(None))
+            }
+        })
+        .filter_map(future::ready)
+}
+
+/// Text/is_not_empty(text) -> Tag (True/False)
+/// Deduplicated: only emits when the result actually changes
+pub fn function_text_is_not_empty(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    let [argument_text] = arguments.as_slice() else {
+        panic!("Text/is_not_empty expects 1 argument")
+    };
+    argument_text
+        .clone()
+        .stream()
+        .scan(None::<bool>, move |last_result, value| {
+            let text = match &value {
+                Value::Text(t, _) => t.text(),
+                _ => panic!("Text/is_not_empty expects a Text value"),
+            };
+            let current_result = !text.is_empty();
+
+            // Only emit when result actually changes
+            if *last_result != Some(current_result) {
+                *last_result = Some(current_result);
+                let tag = if current_result { "True" } else { "False" };
+                future::ready(Some(Some(Tag::new_value(
+                    ConstructInfo::new(
+                        function_call_id.with_child_id(0),
+                        None,
+                        "Text/is_not_empty result",
+                    ),
+                    construct_context.clone(),
+                    ValueIdempotencyKey::new(),
+                    tag.to_string(),
+                ))))
+            } else {
+                future::ready(Some(None))
+            }
+        })
+        .filter_map(future::ready)
+}
+
+/// Text/to_number(text) -> Number | NaN tag
+/// Parses text to f64. Returns Number on success, NaN tag on failure.
+pub fn function_text_to_number(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    let [argument_text] = arguments.as_slice() else {
+        panic!("Text/to_number expects 1 argument")
+    };
+    argument_text.clone().stream().map(move |value| {
+        let text = match &value {
+            Value::Text(t, _) => t.text(),
+            _ => panic!("Text/to_number expects a Text value"),
+        };
+        match text.trim().parse::<f64>() {
+            Ok(number) => Number::new_value(
+                ConstructInfo::new(
+                    function_call_id.with_child_id(0),
+                    None,
+                    "Text/to_number result",
+                ),
+                construct_context.clone(),
+                ValueIdempotencyKey::new(),
+                number,
+            ),
+            Err(_) => Tag::new_value(
+                ConstructInfo::new(
+                    function_call_id.with_child_id(1),
+                    None,
+                    "Text/to_number NaN",
+                ),
+                construct_context.clone(),
+                ValueIdempotencyKey::new(),
+                "NaN".to_string(),
+            ),
+        }
+    })
+}
+
+/// Text/starts_with(text, prefix) -> Tag (True/False)
+pub fn function_text_starts_with(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    text_affix_predicate(
+        arguments,
+        function_call_id,
+        construct_context,
+        "Text/starts_with",
+        |text, prefix| text.starts_with(prefix),
+    )
+}
+
+/// Text/ends_with(text, suffix) -> Tag (True/False)
+pub fn function_text_ends_with(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    text_affix_predicate(
+        arguments,
+        function_call_id,
+        construct_context,
+        "Text/ends_with",
+        |text, suffix| text.ends_with(suffix),
+    )
+}
+
+/// Shared body of the prefix and suffix checks. Emits once both sides have arrived
+/// and then whenever the result changes; a side that is not Text never matches.
+fn text_affix_predicate(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    construct_context: ConstructContext,
+    function_name: &'static str,
+    matches: fn(&str, &str) -> bool,
+) -> impl Stream<Item = Value> {
+    let [argument_text, argument_affix] = arguments.as_slice() else {
+        panic!("{function_name} expects 2 arguments")
+    };
+    enum Input {
+        Text(Option<String>),
+        Affix(Option<String>),
+    }
+    let text_of = |value: &Value| match value {
+        Value::Text(text, _) => Some(text.text().to_string()),
+        _ => None,
+    };
+    let text_stream = argument_text
+        .clone()
+        .stream()
+        .map(move |value| Input::Text(text_of(&value)));
+    let affix_stream = argument_affix
+        .clone()
+        .stream()
+        .map(move |value| Input::Affix(text_of(&value)));
+    stream::select(text_stream, affix_stream)
+        .scan(
+            (None::<Option<String>>, None::<Option<String>>, None::<bool>),
+            move |(last_text, last_affix, last_result), input| {
+                match input {
+                    Input::Text(text) => *last_text = Some(text),
+                    Input::Affix(affix) => *last_affix = Some(affix),
+                }
+                // Don't emit until both inputs are available
+                let (Some(text), Some(affix)) = (last_text.as_ref(), last_affix.as_ref()) else {
+                    return future::ready(Some(None));
+                };
+                let current_result = match (text, affix) {
+                    (Some(text), Some(affix)) => matches(text, affix),
+                    _ => false,
+                };
+                if *last_result == Some(current_result) {
+                    return future::ready(Some(None));
+                }
+                *last_result = Some(current_result);
+                let tag = if current_result { "True" } else { "False" };
+                future::ready(Some(Some(Tag::new_value(
+                    ConstructInfo::new(
+                        function_call_id.with_child_id(0),
+                        None,
+                        format!("{function_name} result"),
+                    ),
+                    construct_context.clone(),
+                    ValueIdempotencyKey::new(),
+                    tag.to_string(),
+                ))))
+            },
+        )
+        .filter_map(future::ready)
+}
+
+/// Text/contains(text, needle) -> Tag (True/False)
+/// False until both sides are Text, so a search box reads False before it has a value
+pub fn function_text_contains(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    let [argument_text, argument_needle] = arguments.as_slice() else {
+        panic!("Text/contains expects 2 arguments")
+    };
+    enum Input {
+        Text(Option<String>),
+        Needle(Option<String>),
+    }
+    let text_of = |value: &Value| match value {
+        Value::Text(text, _) => Some(text.text().to_string()),
+        _ => None,
+    };
+    let text_stream = argument_text
+        .clone()
+        .stream()
+        .map(move |value| Input::Text(text_of(&value)));
+    let needle_stream = argument_needle
+        .clone()
+        .stream()
+        .map(move |value| Input::Needle(text_of(&value)));
+    stream::select(text_stream, needle_stream)
+        .scan(
+            (None::<String>, None::<String>, None::<bool>),
+            move |(last_text, last_needle, last_result), input| {
+                match input {
+                    Input::Text(text) => *last_text = text,
+                    Input::Needle(needle) => *last_needle = needle,
+                }
+                let current_result = match (last_text.as_ref(), last_needle.as_ref()) {
+                    (Some(text), Some(needle)) => text.contains(needle.as_str()),
+                    _ => false,
+                };
+                if *last_result == Some(current_result) {
+                    return future::ready(Some(None));
+                }
+                *last_result = Some(current_result);
+                let tag = if current_result { "True" } else { "False" };
+                future::ready(Some(Some(Tag::new_value(
+                    ConstructInfo::new(
+                        function_call_id.with_child_id(0),
+                        None,
+                        "Text/contains result",
+                    ),
+                    construct_context.clone(),
+                    ValueIdempotencyKey::new(),
+                    tag.to_string(),
+                ))))
+            },
+        )
+        .filter_map(future::ready)
+}
+
+/// Text/replace(text, from, to) -> Text
+/// Replaces every non-overlapping occurrence of `from`, left to right. An empty `from`
+/// leaves the text as it is.
+pub fn function_text_replace(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    let [argument_text, argument_from, argument_to] = arguments.as_slice() else {
+        panic!("Text/replace expects 3 arguments")
+    };
+    enum Input {
+        Text(String),
+        From(String),
+        To(String),
+    }
+    let text_stream = argument_text.clone().stream().map(|v| match &v {
+        Value::Text(t, _) => Input::Text(t.text().to_string()),
+        _ => panic!("Text/replace expects Text for first argument"),
+    });
+    let from_stream = argument_from.clone().stream().map(|v| match &v {
+        Value::Text(t, _) => Input::From(t.text().to_string()),
+        _ => panic!("Text/replace expects Text for `from`"),
+    });
+    let to_stream = argument_to.clone().stream().map(|v| match &v {
+        Value::Text(t, _) => Input::To(t.text().to_string()),
+        _ => panic!("Text/replace expects Text for `to`"),
+    });
+    stream::select(text_stream, stream::select(from_stream, to_stream))
+        .scan(
+            (None::<String>, None::<String>, None::<String>),
+            move |(last_text, last_from, last_to), input| {
+                match input {
+                    Input::Text(t) => *last_text = Some(t),
+                    Input::From(f) => *last_from = Some(f),
+                    Input::To(t) => *last_to = Some(t),
+                }
+                // Only compute when all three inputs have arrived
+                let (Some(text), Some(from), Some(to)) =
+                    (last_text.as_ref(), last_from.as_ref(), last_to.as_ref())
+                else {
+                    return future::ready(Some(None));
+                };
+                let result = if from.is_empty() {
+                    text.clone()
+                } else {
+                    text.replace(from.as_str(), to)
+                };
+                future::ready(Some(Some(Text::new_value(
+                    ConstructInfo::new(
+                        function_call_id.with_child_id(0),
+                        None,
+                        "Text/replace result",
+                    ),
+                    construct_context.clone(),
+                    ValueIdempotencyKey::new(),
+                    result,
+                ))))
+            },
+        )
+        .filter_map(future::ready)
+}
+
+/// Appends `value` the way `TEXT { {value} }` interpolates it. Values without a
+/// text form, such as objects and lists, add nothing.
+pub fn push_interpolated_value(text: &mut String, value: &Value) {
+    match value {
+        Value::Text(value_text, _) => text.push_str(value_text.text()),
+        Value::Number(number, _) => text.push_str(&number.number().to_string()),
+        Value::Tag(tag, _) => text.push_str(tag.tag()),
+        _ => {}
+    }
+}
+
+/// Text/join(separator) -> Text
+/// The list's items stringified as in `TEXT { {item} }` and joined with `separator`.
+/// Re-joins when membership, an item value or the separator changes; emits only on
+/// change.
+pub fn function_text_join(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    enum Input {
+        Items(Vec<Value>),
+        Separator(String),
+    }
+
+    let inputs = stream::select(
+        list_item_values(arguments[0].clone()).map(Input::Items),
+        arguments[1].clone().stream().filter_map(|value| {
+            future::ready(match value {
+                Value::Text(text, _) => Some(Input::Separator(text.text().to_string())),
+                _ => None,
+            })
+        }),
+    );
+    inputs
+        .scan(
+            (None::<Vec<Value>>, None::<String>, None::<String>),
+            move |(items, separator, last_joined), input| {
+                match input {
+                    Input::Items(values) => *items = Some(values),
+                    Input::Separator(text) => *separator = Some(text),
+                }
+                let (Some(items), Some(separator)) = (items.as_ref(), separator.as_ref()) else {
+                    return future::ready(Some(None));
+                };
+                let mut joined = String::new();
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        joined.push_str(separator);
+                    }
+                    push_interpolated_value(&mut joined, item);
+                }
+                if last_joined.as_ref() == Some(&joined) {
+                    return future::ready(Some(None));
+                }
+                *last_joined = Some(joined.clone());
+                future::ready(Some(Some(Text::new_value(
+                    ConstructInfo::new(function_call_id.with_child_id(0), None, "Text/join result"),
+                    construct_context.clone(),
+                    ValueIdempotencyKey::new(),
+                    joined,
+                ))))
+            },
+        )
+        .filter_map(future::ready)
+}
+
+/// Text/split(separator) -> List
+/// The parts of the text between separators. Each separator ends a part, so empty
+/// parts are kept, and empty text gives an empty list. A new text updates the parts
+/// by position: changed parts emit `UpdateAt` and the tail is pushed or popped.
+pub fn function_text_split(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    let [argument_text, argument_separator] = arguments.as_slice() else {
+        panic!("Text/split expects 2 arguments")
+    };
+    enum Input {
+        Text(String),
+        Separator(String),
+    }
+
+    let text_of = |value: Value| {
+        future::ready(match value {
+            Value::Text(text, _) => Some(text.text().to_string()),
+            _ => None,
+        })
+    };
+    let inputs = stream::select(
+        argument_text
+            .clone()
+            .stream()
+            .filter_map(text_of)
+            .map(Input::Text),
+        argument_separator
+            .clone()
+            .stream()
+            .filter_map(text_of)
+            .map(Input::Separator),
+    );
+
+    let scope_id = actor_context.scope_id();
+    let part_function_call_id = function_call_id.clone();
+    let mut part_version = 0u64;
+    let mut new_part = move |index: usize, part: &str| {
+        part_version += 1;
+        create_constant_actor(
+            ConstructInfo::new(
+                part_function_call_id.with_child_id(format!("Text/split part v.{part_version}")),
+                None,
+                "Text/split part",
+            ),
+            function_call_persistence_id.with_child_index(u32::try_from(index).unwrap_or(u32::MAX)),
+            Text::new_value(
+                ConstructInfo::new(
+                    part_function_call_id
+                        .with_child_id(format!("Text/split part text v.{part_version}")),
+                    None,
+                    "Text/split part text",
+                ),
+                construct_context.clone(),
+                ValueIdempotencyKey::new(),
+                part.to_string(),
+            ),
+            scope_id,
+        )
+    };
+
+    let change_stream = inputs
+        .scan(
+            (
+                None::<String>,
+                None::<String>,
+                None::<Vec<(String, ActorHandle)>>,
+                false,
+            ),
+            move |(text, separator, last_parts, reported_empty_separator), input| {
+                match input {
+                    Input::Text(new_text) => *text = Some(new_text),
+                    Input::Separator(new_separator) => *separator = Some(new_separator),
+                }
+                let (Some(text), Some(separator)) = (text.as_ref(), separator.as_ref()) else {
+                    return future::ready(Some(Vec::new()));
+                };
+                let parts: Vec<&str> = if text.is_empty() {
+                    Vec::new()
+                } else if separator.is_empty() {
+                    if !*reported_empty_separator {
+                        *reported_empty_separator = true;
+                        emit_warning(
+                            LogLevel::Warn,
+                            "text",
+                            "Text/split separator is empty; keeping the whole text as one part",
+                        );
+                    }
+                    vec![text.as_str()]
+                } else {
+                    text.split(separator.as_str()).collect()
+                };
+
+                let first_split = last_parts.is_none();
+                let old_parts = last_parts.take().unwrap_or_default();
+                let mut changes = Vec::new();
+                let mut new_parts = Vec::with_capacity(parts.len());
+                for (index, part) in parts.into_iter().enumerate() {
+                    match old_parts.get(index) {
+                        Some((old_part, actor)) if old_part == part => {
+                            new_parts.push((old_part.clone(), actor.clone()));
+                        }
+                        old => {
+                            let actor = new_part(index, part);
+                            changes.push(match old {
+                                Some(_) => ListChange::UpdateAt {
+                                    index,
+                                    item: actor.clone(),
+                                },
+                                None => ListChange::Push {
+                                    item: actor.clone(),
+                                },
+                            });
+                            new_parts.push((part.to_string(), actor));
+                        }
+                    }
+                }
+                changes.extend(std::iter::repeat_n(
+                    ListChange::Pop,
+                    old_parts.len().saturating_sub(new_parts.len()),
+                ));
+                if first_split {
+                    changes = vec![ListChange::Replace {
+                        items: new_parts.iter().map(|(_, actor)| actor.clone()).collect(),
+                    }];
+                }
+                *last_parts = Some(new_parts);
+                future::ready(Some(changes))
+            },
+        )
+        .flat_map(stream::iter);
+
+    let list = List::new_with_change_stream(
+        ConstructInfo::new(function_call_id.with_child_id(0), None, "Text/split result"),
+        actor_context,
+        change_stream,
+        arguments,
+    );
+
+    constant(Value::List(
+        Arc::new(list),
+        ValueMetadata::new(ValueIdempotencyKey::new()),
+    ))
+}
+
+// --- Bool functions ---
+
+/// Bool/not(value) -> Tag (True/False)
+pub fn function_bool_not(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    let [argument_value] = arguments.as_slice() else {
+        panic!("Bool/not expects 1 argument")
+    };
+    argument_value.clone().stream().map(move |value| {
+        let is_true = match &value {
+            Value::Tag(tag, _) => tag.tag() == "True",
+            _ => panic!("Bool/not expects a Tag (True/False)"),
+        };
+        let result_tag = if is_true { "False" } else { "True" };
+        Tag::new_value(
+            ConstructInfo::new(function_call_id.with_child_id(0), None, "Bool/not result"),
+            construct_context.clone(),
+            ValueIdempotencyKey::new(),
+            result_tag.to_string(),
+        )
+    })
+}
+
+/// Bool/toggle(value, when) -> Tag (True/False)
+/// Toggles the boolean value each time 'when' stream produces a value
+pub fn function_bool_toggle(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    let argument_value = arguments[0].clone();
+    let argument_when = arguments[1].clone();
+
+    // Two independent streams merged via select:
+    // - value_stream: pipe input (initial bool + updates from toggle-all)
+    // - when_stream: toggle trigger (individual checkbox click)
+    enum Msg {
+        SetValue(bool),
+        Toggle,
+    }
+
+    let value_stream = argument_value.stream().filter_map(|value| {
+        future::ready(match &value {
+            Value::Tag(tag, _) => Some(Msg::SetValue(tag.tag() == "True")),
+            _ => None,
+        })
+    });
+
+    let when_stream = argument_when.stream().map(|_| Msg::Toggle);
+
+    stream::select(value_stream, when_stream).scan(None::<bool>, move |state, msg| {
+        match msg {
+            Msg::SetValue(v) => *state = Some(v),
+            Msg::Toggle => {
+                let current = state.unwrap_or(false);
+                *state = Some(!current);
+            }
+        }
+        let is_true = state.unwrap_or(false);
+        let result_tag = if is_true { "True" } else { "False" };
+        future::ready(Some(Tag::new_value(
+            ConstructInfo::new(
+                function_call_id.with_child_id(0),
+                None,
+                "Bool/toggle result",
+            ),
+            construct_context.clone(),
+            ValueIdempotencyKey::new(),
+            result_tag.to_string(),
+        )))
+    })
+}
+
+/// Persisted state of `Bool/toggle(on:, initial:)`.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[serde(crate = "serde")]
+struct BoolToggleState {
+    trigger_idempotency_key: Option<ValueIdempotencyKey>,
+    value: bool,
+}
+
+enum BoolToggleInput {
+    Restored(BoolToggleState),
+    Initial(bool),
+    Toggle(ValueIdempotencyKey),
+}
+
+/// One step of `Bool/toggle(on:, initial:)`: the new Bool, or None when `input`
+/// changes nothing. `started` turns true with the first input; after that `initial`
+/// is ignored, so only its first Bool counts and only before `on` fires or a state is
+/// restored.
+fn bool_toggle_step(
+    state: &mut BoolToggleState,
+    started: &mut bool,
+    input: BoolToggleInput,
+) -> Option<bool> {
+    match input {
+        BoolToggleInput::Restored(restored) => *state = restored,
+        BoolToggleInput::Initial(_) if *started => return None,
+        BoolToggleInput::Initial(value) => state.value = value,
+        BoolToggleInput::Toggle(key) => {
+            if state.trigger_idempotency_key == Some(key) {
+                return None;
+            }
+            state.trigger_idempotency_key = Some(key);
+            state.value = !state.value;
+        }
+    }
+    *started = true;
+    Some(state.value)
+}
+
+/// Bool/toggle(on: trigger, initial: False) -> Tag (True/False)
+/// Starts at the first Bool of `initial` (False without `initial`) and flips each time
+/// `on` fires; `initial` is not read again, and a first Bool arriving after `on` has
+/// fired is ignored. The evaluator passes `initial` and `on` positionally. Unlike the
+/// piped `Bool/toggle(when:)`, the state is persisted like the HOLD this spelling
+/// replaces: a restored toggle keeps its Bool and ignores `initial`.
+pub fn function_bool_toggle_on(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    // Two independent streams merged via select:
+    // - value_stream: `initial` (the starting bool)
+    // - when_stream: `on` trigger (individual checkbox click)
+    // A restored state is read first, before either stream is subscribed.
+    let (argument_initial, argument_on) = match arguments.as_slice() {
+        [argument_on] => (None, argument_on.clone()),
+        [argument_initial, argument_on] => (Some(argument_initial.clone()), argument_on.clone()),
+        _ => panic!("Bool/toggle expects 1 or 2 arguments"),
+    };
+    let storage = construct_context.construct_storage.clone();
+
+    stream::once({
+        let storage = storage.clone();
+        async move {
+            let loaded: Option<BoolToggleState> =
+                storage.load_state(function_call_persistence_id).await;
+            loaded
+        }
+    })
+    .filter_map(future::ready)
+    .map(BoolToggleInput::Restored)
+    .chain(
+        stream::once(async move {
+            let value_stream = match argument_initial {
+                Some(argument_initial) => argument_initial
+                    .stream()
+                    .filter_map(|value| {
+                        future::ready(match &value {
+                            Value::Tag(tag, _) => {
+                                Some(BoolToggleInput::Initial(tag.tag() == "True"))
+                            }
+                            _ => None,
+                        })
+                    })
+                    .take(1)
+                    .boxed_local(),
+                None => stream::once(future::ready(BoolToggleInput::Initial(false))).boxed_local(),
+            };
+            let when_stream = argument_on
+                .stream()
+                .map(|value| BoolToggleInput::Toggle(value.idempotency_key()));
+            stream::select(value_stream, when_stream)
+        })
+        .flatten(),
+    )
+    .scan(
+        (BoolToggleState::default(), false),
+        move |(state, started), input| {
+            let restored = matches!(input, BoolToggleInput::Restored(_));
+            let value = bool_toggle_step(state, started, input);
+            if value.is_some() && !restored {
+                storage.save_state(function_call_persistence_id, &*state);
+            }
+            future::ready(Some(value))
+        },
+    )
+    .filter_map(future::ready)
+    .map(move |is_true| {
+        let result_tag = if is_true { "True" } else { "False" };
+        Tag::new_value(
+            ConstructInfo::new(
+                function_call_id.with_child_id(0),
+                None,
+                "Bool/toggle result",
+            ),
+            construct_context.clone(),
+            ValueIdempotencyKey::new(),
+            result_tag.to_string(),
+        )
+    })
+}
+
+/// Bool/and(this, that) -> Tag (True/False)
+/// True while both this and that are True. Emits nothing until both are True or
+/// False; a value that is neither is skipped and the last Bool of that side kept.
+pub fn function_bool_and(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    let [argument_this, argument_that] = arguments.as_slice() else {
+        panic!("Bool/and expects 2 arguments")
+    };
+    enum Input {
+        This(Option<bool>),
+        That(Option<bool>),
+    }
+    let bool_of = |value: &Value| match value {
+        Value::Tag(tag, _) if tag.tag() == "True" => Some(true),
+        Value::Tag(tag, _) if tag.tag() == "False" => Some(false),
+        _ => None,
+    };
+    let this_stream = argument_this
+        .clone()
+        .stream()
+        .map(move |v| Input::This(bool_of(&v)));
+    let that_stream = argument_that
+        .clone()
+        .stream()
+        .map(move |v| Input::That(bool_of(&v)));
+    stream::select(this_stream, that_stream)
+        .scan(
+            (None::<bool>, None::<bool>),
+            move |(last_this, last_that), input| {
+                match input {
+                    Input::This(Some(this)) => *last_this = Some(this),
+                    Input::That(Some(that)) => *last_that = Some(that),
+                    Input::This(None) | Input::That(None) => return future::ready(Some(None)),
+                }
+                let (Some(this), Some(that)) = (*last_this, *last_that) else {
+                    return future::ready(Some(None));
+                };
+                let tag = if this && that { "True" } else { "False" };
+                future::ready(Some(Some(Tag::new_value(
+                    ConstructInfo::new(function_call_id.with_child_id(0), None, "Bool/and result"),
+                    construct_context.clone(),
+                    ValueIdempotencyKey::new(),
+                    tag.to_string(),
+                ))))
+            },
+        )
+        .filter_map(future::ready)
+}
+
+/// Bool/or(this, that) -> Tag (True/False)
+/// Returns True if either this or that is True
+pub fn function_bool_or(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    let this_actor = arguments[0].clone();
+    let that_actor = arguments[1].clone();
+
+    // Combine both boolean streams using select
+    let this_stream = this_actor.stream().map(|v| (true, v));
+    let that_stream = that_actor.stream().map(|v| (false, v));
+
+    stream::select(this_stream, that_stream)
+        .scan(
+            (None::<bool>, None::<bool>),
+            move |state, (is_this, value)| {
+                let is_true = match &value {
+                    Value::Tag(tag, _) => tag.tag() == "True",
+                    _ => false,
+                };
+                if is_this {
+                    state.0 = Some(is_true);
+                } else {
+                    state.1 = Some(is_true);
+                }
+                future::ready(Some(*state))
+            },
+        )
+        .filter_map(move |(this_bool, that_bool)| {
+            let construct_context = construct_context.clone();
+            let function_call_id = function_call_id.clone();
+            future::ready(match (this_bool, that_bool) {
+                (Some(a), Some(b)) => {
+                    let result = a || b;
+                    let tag = if result { "True" } else { "False" };
+                    Some(Tag::new_value(
+                        ConstructInfo::new(
+                            function_call_id.with_child_id(0),
+                            None,
+                            "Bool/or result",
+                        ),
+                        construct_context,
+                        ValueIdempotencyKey::new(),
+                        tag.to_string(),
+                    ))
+                }
+                _ => None, // Wait for both values
+            })
+        })
+}
+
+// --- List functions ---
+
+/// List/is_empty() -> Tag (True/False)
+/// Checks if the piped list is empty
+/// Deduplicated: only emits when the result actually changes
+pub fn function_list_is_empty(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    let list_actor = arguments[0].clone();
+    let list_stream = list_actor.stream().filter_map(move |value| {
+        let result = match &value {
+            Value::List(list, _) => Some(list.clone()),
+            _ => None,
+        };
+        future::ready(result)
+    });
+    switch_map(list_stream, move |list| {
+        let construct_context = construct_context.clone();
+        let function_call_id = function_call_id.clone();
+        list.stream()
+            .scan(
+                (Vec::<ActorHandle>::new(), None::<bool>),
+                move |(items, last_result), change| {
+                    change.apply_to_vec(items);
+                    let current_result = items.is_empty();
+
+                    // Only emit when result actually changes
+                    if *last_result != Some(current_result) {
+                        *last_result = Some(current_result);
+                        let tag = if current_result { "True" } else { "False" };
+                        future::ready(Some(Some(Tag::new_value(
+                            ConstructInfo::new(
+                                function_call_id.with_child_id(0),
+                                None,
+                                "List/is_empty result",
+                            ),
+                            construct_context.clone(),
+                            ValueIdempotencyKey::new(),
+                            tag.to_string(),
+                        ))))
+                    } else {
+                        future::ready(Some(None))
+                    }
+                },
+            )
+            .filter_map(future::ready)
+    })
+}
+
+/// List/count -> Number
+/// Returns the count of items in the list
+/// Deduplicated: only emits when the count actually changes
+pub fn function_list_count(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    let list_actor = arguments[0].clone();
+    let list_stream = list_actor.stream().filter_map(move |value| {
+        let result = match &value {
+            Value::List(list, _) => Some(list.clone()),
+            _ => None,
+        };
+        future::ready(result)
+    });
+    switch_map(list_stream, move |list| {
+        let construct_context = construct_context.clone();
+        let function_call_id = function_call_id.clone();
+        list.stream()
+            .scan(
+                (Vec::<ActorHandle>::new(), None::<usize>),
+                move |(items, last_count), change| {
+                    change.apply_to_vec(items);
+                    let current_count = items.len();
+
+                    // Only emit when count actually changes
+                    if *last_count != Some(current_count) {
+                        *last_count = Some(current_count);
+                        future::ready(Some(Some(Number::new_value(
+                            ConstructInfo::new(
+                                function_call_id.with_child_id(0),
+                                None,
+                                "List/count result",
+                            ),
+                            construct_context.clone(),
+                            ValueIdempotencyKey::new(),
+                            current_count as f64,
+                        ))))
+                    } else {
+                        future::ready(Some(None))
+                    }
+                },
+            )
+            .filter_map(future::ready)
+    })
+}
+
+/// List/is_not_empty() -> Tag (True/False)
+/// Checks if the piped list is not empty (inverse of List/is_empty)
+/// Deduplicated: only emits when the result actually changes
+pub fn function_list_is_not_empty(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    let list_actor = arguments[0].clone();
+    let list_stream = list_actor.stream().filter_map(move |value| {
+        let result = match &value {
+            Value::List(list, _) => Some(list.clone()),
+            _ => None,
+        };
+        future::ready(result)
+    });
+    switch_map(list_stream, move |list| {
+        let construct_context = construct_context.clone();
+        let function_call_id = function_call_id.clone();
+        list.stream()
+            .scan(
+                (Vec::<ActorHandle>::new(), None::<bool>),
+                move |(items, last_result), change| {
+                    change.apply_to_vec(items);
+                    let current_result = !items.is_empty();
+
+                    // Only emit when result actually changes
+                    if *last_result != Some(current_result) {
+                        *last_result = Some(current_result);
+                        let tag = if current_result { "True" } else { "False" };
+                        future::ready(Some(Some(Tag::new_value(
+                            ConstructInfo::new(
+                                function_call_id.with_child_id(0),
+                                None,
+                                "List/is_not_empty result",
+                            ),
+                            construct_context.clone(),
+                            ValueIdempotencyKey::new(),
+                            tag.to_string(),
+                        ))))
+                    } else {
+                        future::ready(Some(None))
+                    }
+                },
+            )
+            .filter_map(future::ready)
+    })
+}
+
+/// List/append(item: value) -> List
+/// Appends an item to the list when the item stream produces a value
+pub fn function_list_append(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    _construct_context: ConstructContext,
+    actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    // arguments[0] = the list (piped)
+    // arguments[1] = the item to append
+    let list_actor = arguments[0].clone();
+
+    // If item argument is SKIP (not provided), just forward the list unchanged
+    if arguments.len() < 2 {
+        return list_actor.stream().left_stream();
+    }
+
+    let item_actor = arguments[1].clone();
+
+    // Create a change stream that:
+    // 1. Forwards all changes from the original list
+    // 2. Adds Push changes when the item stream produces values
+    //
+    // IMPORTANT: The first change MUST be a Replace from the original list.
+    // We use scan to ensure proper ordering: buffer append changes until
+    // after the first list change arrives.
+    let change_stream = {
+        let function_call_id_for_append = function_call_id.clone();
+        let actor_context_for_append = actor_context.clone();
+
+        // Tag changes with their source so we can ensure proper ordering
+        enum TaggedChange {
+            FromList(ListChange),
+            FromAppend(ListChange),
+        }
+
+        let list_changes = list_actor
+            .clone()
+            .stream()
+            .filter_map(|value| {
+                future::ready(match value {
+                    Value::List(list, _) => Some(list),
+                    _ => None,
+                })
+            })
+            .flat_map(|list| list.stream())
+            .map(TaggedChange::FromList);
+
+        let append_changes = item_actor.clone().stream().map(move |value| {
+            // When item stream produces a value, create a new constant ValueActor
+            // containing that specific value and push it.
+            // Note: SKIP is not a Value - it's a stream behavior where streams end without
+            // producing values. If item is SKIP, this map closure is never called.
+            let new_item_actor = create_actor(
+                ConstructInfo::new(
+                    function_call_id_for_append.with_child_id("appended_item"),
+                    None,
+                    "List/append appended item",
+                ),
+                actor_context_for_append.clone(),
+                constant(value),
+                PersistenceId::new(),
+                actor_context_for_append.scope_id(),
+            );
+            TaggedChange::FromAppend(ListChange::Push {
+                item: new_item_actor,
+            })
+        });
+
+        // Merge both change streams, then use scan to ensure proper ordering
+        stream::select(list_changes, append_changes)
+            .scan(
+                (false, Vec::<ListChange>::new()), // (has_received_first_list_change, buffered_appends)
+                |state, tagged_change| {
+                    let (has_received_first, buffered) = state;
+
+                    let changes_to_emit = match tagged_change {
+                        TaggedChange::FromList(change) => {
+                            if !*has_received_first {
+                                // First list change - emit it plus any buffered appends
+                                *has_received_first = true;
+                                let mut all = vec![change];
+                                all.append(buffered);
+                                all
+                            } else {
+                                // Subsequent list change - emit directly
+                                vec![change]
+                            }
+                        }
+                        TaggedChange::FromAppend(change) => {
+                            if *has_received_first {
+                                // Already received first list change - emit directly
+                                vec![change]
+                            } else {
+                                // Buffer until first list change arrives
+                                buffered.push(change);
+                                vec![]
+                            }
+                        }
+                    };
+
+                    future::ready(Some(changes_to_emit))
+                },
+            )
+            .flat_map(|changes| stream::iter(changes))
+    };
+
+    let list = List::new_with_change_stream(
+        ConstructInfo::new(
+            function_call_id.with_child_id(ulid::Ulid::new().to_string()),
+            None,
+            "List/append result",
+        ),
+        actor_context,
+        change_stream,
+        (list_actor, item_actor),
+    );
+
+    constant(Value::List(
+        Arc::new(list),
+        ValueMetadata::new(ValueIdempotencyKey::new()),
+    ))
+    .right_stream()
+}
+
+/// List/clear(on: stream) -> List
+/// Clears all items from the list when the trigger stream emits any value
+pub fn function_list_clear(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    // arguments[0] = the list (piped)
+    // arguments[1] = the trigger stream (on: xxx)
+    let list_actor = arguments[0].clone();
+
+    // If trigger argument is not provided, just forward the list unchanged
+    if arguments.len() < 2 {
+        return list_actor.stream().left_stream();
+    }
+
+    let trigger_actor = arguments[1].clone();
+
+    // Similar pattern to List/append:
+    // 1. Forward all changes from the original list
+    // 2. When trigger fires, emit Clear AND clear recorded calls storage
+    let change_stream = {
+        enum TaggedChange {
+            FromList(ListChange),
+            Clear,
+        }
+
+        let list_changes = list_actor
+            .clone()
+            .stream()
+            .filter_map(|value| {
+                future::ready(match value {
+                    Value::List(list, _) => Some(list),
+                    _ => None,
+                })
+            })
+            .flat_map(|list| list.stream())
+            .map(TaggedChange::FromList);
+
+        // When trigger stream emits any value, emit Clear
+        let clear_changes = trigger_actor
+            .clone()
+            .stream()
+            .map(|_value| TaggedChange::Clear);
+
+        // Merge both streams, use scan for proper ordering
+        stream::select(list_changes, clear_changes)
+            .scan(
+                (false, false, Vec::<ActorHandle>::new()),
+                |state, tagged_change| {
+                    let (has_received_first, has_pending_clear, tracked_items) = state;
+
+                    let changes_to_emit = match tagged_change {
+                        TaggedChange::FromList(change) => {
+                            // Update tracked items based on the change
+                            match &change {
+                                ListChange::Replace { items } => {
+                                    *tracked_items = items.to_vec();
+                                }
+                                ListChange::Push { item } => {
+                                    tracked_items.push(item.clone());
+                                }
+                                ListChange::Pop => {
+                                    tracked_items.pop();
+                                }
+                                ListChange::Clear => {
+                                    tracked_items.clear();
+                                }
+                                ListChange::Remove { id } => {
+                                    if let Some(pos) =
+                                        tracked_items.iter().position(|i| i.persistence_id() == *id)
+                                    {
+                                        tracked_items.remove(pos);
+                                    }
+                                }
+                                _ => {}
+                            }
+
+                            if !*has_received_first {
+                                *has_received_first = true;
+                                // First list change - emit it plus pending clear if any
+                                if *has_pending_clear {
+                                    *has_pending_clear = false;
+                                    // Clear storage for tracked items
+                                    clear_source_storage_for_items(tracked_items);
+                                    tracked_items.clear();
+                                    vec![change, ListChange::Clear]
+                                } else {
+                                    vec![change]
+                                }
+                            } else {
+                                vec![change]
+                            }
+                        }
+                        TaggedChange::Clear => {
+                            if *has_received_first {
+                                // Clear storage for tracked items
+                                clear_source_storage_for_items(tracked_items);
+                                tracked_items.clear();
+                                vec![ListChange::Clear]
+                            } else {
+                                // Buffer the clear until first list change arrives
+                                *has_pending_clear = true;
+                                vec![]
+                            }
+                        }
+                    };
+
+                    future::ready(Some(changes_to_emit))
+                },
+            )
+            .flat_map(|changes| stream::iter(changes))
+    };
+
+    let list = List::new_with_change_stream_and_persistence(
+        ConstructInfo::new(function_call_id.with_child_id(0), None, "List/clear result"),
+        construct_context,
+        actor_context,
+        change_stream,
+        (list_actor, trigger_actor),
+        function_call_persistence_id,
+    );
+
+    constant(Value::List(
+        Arc::new(list),
+        ValueMetadata::new(ValueIdempotencyKey::new()),
+    ))
+    .right_stream()
+}
+
+/// Clear recorded calls storage for all items that have source origins.
+/// Called when List/clear triggers to ensure items don't restore on next Run.
+fn clear_source_storage_for_items(items: &[ActorHandle]) {
+    use std::collections::HashSet;
+    use zoon::{WebStorage, local_storage};
+
+    // Collect unique source storage keys
+    let mut source_keys: HashSet<String> = HashSet::new();
+    for item in items {
+        if let Some(origin) = item.list_item_origin() {
+            source_keys.insert(origin.source_storage_key.clone());
+        }
+    }
+
+    // Clear each source storage key
+    for key in source_keys {
+        if LOG_DEBUG {
+            zoon::println!("[DEBUG] List/clear: Clearing source storage key: {}", key);
+        }
+        local_storage().remove(&key);
+    }
+}
+
+/// List/insert(index, item, on) -> List
+/// Inserts the current value of `item` at the 1-based `index` each time `on` fires.
+/// The index clamps to the list, so 0 inserts first and a past-the-end index
+/// appends; a non-number index inserts nothing. Source items keep their place
+/// around inserted ones. The order is persisted and inserted items are restored on
+/// the next run.
+pub fn function_list_insert(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    reordered_list(
+        arguments,
+        function_call_id,
+        function_call_persistence_id,
+        construct_context,
+        actor_context,
+        ListReordering::Insert,
+    )
+}
+
+/// List/move(from, to, on) -> List
+/// Moves the item at the 1-based position `from` to position `to` each time `on`
+/// fires. Both positions clamp to the list, so `to: 100` always means the bottom;
+/// non-number positions move nothing. The item actors are shared with the source
+/// list, so moved items keep their state. The order is persisted.
+pub fn function_list_move(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    reordered_list(
+        arguments,
+        function_call_id,
+        function_call_persistence_id,
+        construct_context,
+        actor_context,
+        ListReordering::Move,
+    )
+}
+
+/// A reordering step whose positions are read when it runs.
+struct ReorderStep {
+    reordering: ListReordering,
+    first: ActorHandle,
+    second: ActorHandle,
+}
+
+enum ReorderInput {
+    Source(ListChange),
+    Fired(ReorderStep),
+}
+
+/// The shared order of a chain of `List/insert` and `List/move` calls.
+struct ReorderStage {
+    inputs: LocalBoxStream<'static, ReorderInput>,
+    source: Vec<ActorHandle>,
+    items: Vec<ActorHandle>,
+    /// Items the chain created; they stay when the source changes.
+    inserted: Vec<ActorHandle>,
+    /// Steps fired before the source list arrived.
+    pending_steps: Vec<ReorderStep>,
+    restored: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "serde")]
+struct SavedReorderItem {
+    value: serde_json::Value,
+    inserted: bool,
+}
+
+/// The list `arguments[0]` reordered each time `arguments[3]` fires, with
+/// `arguments[1]` and `arguments[2]` read at that moment. When the list comes from
+/// another `List/insert` or `List/move`, this call joins its order instead of
+/// starting one, so a chain of calls acts on one list.
+fn reordered_list(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    actor_context: ActorContext,
+    reordering: ListReordering,
+) -> impl Stream<Item = Value> {
+    let [argument_list, first_argument, second_argument, argument_on] = arguments.as_slice() else {
+        panic!(
+            "List/insert and List/move expect 4 arguments, got {}",
+            arguments.len()
+        )
+    };
+    let first_argument = first_argument.clone();
+    let second_argument = second_argument.clone();
+    let argument_on = argument_on.clone();
+    let description = match reordering {
+        ListReordering::Insert => "List/insert result",
+        ListReordering::Move => "List/move result",
+    };
+
+    let source_lists = argument_list
+        .clone()
+        .stream()
+        .filter_map(|value| {
+            future::ready(match value {
+                Value::List(list, _) => Some(list),
+                _ => None,
+            })
+        })
+        .scan(None::<Arc<List>>, |previous, list| {
+            let is_new = !previous
+                .as_ref()
+                .is_some_and(|previous| Arc::ptr_eq(previous, &list));
+            *previous = Some(list.clone());
+            future::ready(Some(is_new.then_some(list)))
+        })
+        .filter_map(future::ready);
+
+    switch_map(source_lists, move |source_list| {
+        let (drop_guard, dropped) = oneshot::channel::<()>();
+        let trigger = ListReorderTrigger {
+            reordering,
+            first: first_argument.clone(),
+            second: second_argument.clone(),
+            on: argument_on.clone(),
+            dropped,
+        };
+        let construct_info =
+            ConstructInfo::new(function_call_id.with_child_id(0), None, description);
+        let list = match source_list.reorder_sender() {
+            Some(reorder_sender) => {
+                reorder_sender.send_or_drop(trigger);
+                let mut list = List::new_with_change_stream(
+                    construct_info,
+                    actor_context.clone(),
+                    source_list.clone().stream(),
+                    (arguments.clone(), drop_guard),
+                );
+                list.set_reorder_sender(reorder_sender);
+                list
+            }
+            None => {
+                let (reorder_sender, reorder_receiver) =
+                    NamedChannel::new("list.reorder_triggers", 16);
+                let triggers = stream::once(future::ready(trigger))
+                    .chain(reorder_receiver)
+                    .map(reorder_steps)
+                    .flatten_unordered(None);
+                let stage = ReorderStage {
+                    inputs: stream::select(
+                        source_list.clone().stream().map(ReorderInput::Source),
+                        triggers,
+                    )
+                    .boxed_local(),
+                    source: Vec::new(),
+                    items: Vec::new(),
+                    inserted: Vec::new(),
+                    pending_steps: Vec::new(),
+                    restored: false,
+                };
+                let mut list = List::new_with_change_stream(
+                    construct_info,
+                    actor_context.clone(),
+                    kept_item_changes(ordered_items(
+                        stage,
+                        function_call_id.clone(),
+                        persistence_id,
+                        construct_context.clone(),
+                        actor_context.clone(),
+                    )),
+                    (arguments.clone(), drop_guard),
+                );
+                list.set_reorder_sender(reorder_sender);
+                list
+            }
+        };
+        constant(Value::List(
+            Arc::new(list),
+            ValueMetadata::new(ValueIdempotencyKey::new()),
+        ))
+    })
+}
+
+/// The steps `trigger` fires until the call that sent it is dropped.
+fn reorder_steps(trigger: ListReorderTrigger) -> LocalBoxStream<'static, ReorderInput> {
+    let ListReorderTrigger {
+        reordering,
+        first,
+        second,
+        on,
+        dropped,
+    } = trigger;
+    on.stream()
+        .map(move |_| {
+            ReorderInput::Fired(ReorderStep {
+                reordering,
+                first: first.clone(),
+                second: second.clone(),
+            })
+        })
+        .take_until(dropped)
+        .boxed_local()
+}
+
+/// The items of the shared order after each source change or fired step. The order
+/// is saved after every step and restored when the source list first arrives.
+fn ordered_items(
+    stage: ReorderStage,
+    function_call_id: ConstructId,
+    persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    actor_context: ActorContext,
+) -> impl Stream<Item = Vec<ActorHandle>> {
+    stream::unfold(stage, move |mut stage| {
+        let function_call_id = function_call_id.clone();
+        let construct_context = construct_context.clone();
+        let actor_context = actor_context.clone();
+        async move {
+            let mut steps = Vec::new();
+            loop {
+                match stage.inputs.next().await? {
+                    ReorderInput::Source(change) => {
+                        change.apply_to_vec(&mut stage.source);
+                        if !stage.restored {
+                            stage.restored = true;
+                            let saved = construct_context
+                                .construct_storage
+                                .clone()
+                                .load_state::<Vec<SavedReorderItem>>(persistence_id)
+                                .await;
+                            if let Some(saved) = saved {
+                                restore_saved_order(
+                                    &mut stage,
+                                    &saved,
+                                    &function_call_id,
+                                    &construct_context,
+                                    &actor_context,
+                                )
+                                .await;
+                            }
+                            steps = std::mem::take(&mut stage.pending_steps);
+                        }
+                        stage.items =
+                            follow_source_order(&stage.items, &stage.source, &stage.inserted);
+                    }
+                    ReorderInput::Fired(step) if !stage.restored => {
+                        stage.pending_steps.push(step);
+                        continue;
+                    }
+                    ReorderInput::Fired(step) => steps.push(step),
+                }
+                for step in steps {
+                    let first = step.first.current_value().await.ok();
+                    let second = step.second.current_value().await.ok();
+                    apply_reordering(
+                        &mut stage,
+                        step.reordering,
+                        first,
+                        second,
+                        &function_call_id,
+                        &actor_context,
+                    );
+                }
+                let mut saved = Vec::new();
+                for item in &stage.items {
+                    if let Ok(value) = item.current_value().await {
+                        saved.push(SavedReorderItem {
+                            value: value.to_json().await,
+                            inserted: stage
+                                .inserted
+                                .iter()
+                                .any(|inserted| inserted.actor_id() == item.actor_id()),
+                        });
+                    }
+                }
+                construct_context
+                    .construct_storage
+                    .save_state(persistence_id, &saved);
+                return Some((stage.items.clone(), stage));
+            }
+        }
+    })
+}
+
+/// Applies one `reordering` to `stage.items` with the positions `first` and
+/// `second` read when it fired.
+fn apply_reordering(
+    stage: &mut ReorderStage,
+    reordering: ListReordering,
+    first: Option<Value>,
+    second: Option<Value>,
+    function_call_id: &ConstructId,
+    actor_context: &ActorContext,
+) {
+    let position = |value: &Option<Value>| match value {
+        Some(Value::Number(number, _)) => Some(number.number()),
+        _ => None,
+    };
+    match reordering {
+        ListReordering::Insert => {
+            let (Some(index), Some(item)) = (position(&first), second) else {
+                return;
+            };
+            let index = clamped_slot(index, stage.items.len() + 1);
+            let item = create_actor(
+                ConstructInfo::new(
+                    function_call_id.with_child_id("inserted_item"),
+                    None,
+                    "List/insert inserted item",
+                ),
+                actor_context.clone(),
+                constant(item),
+                PersistenceId::new(),
+                actor_context.scope_id(),
+            );
+            stage.inserted.push(item.clone());
+            stage.items.insert(index, item);
+        }
+        ListReordering::Move => {
+            let (Some(from), Some(to)) = (position(&first), position(&second)) else {
+                return;
+            };
+            if stage.items.is_empty() {
+                return;
+            }
+            let from = clamped_slot(from, stage.items.len());
+            let to = clamped_slot(to, stage.items.len());
+            let item = stage.items.remove(from);
+            stage.items.insert(to, item);
+        }
+    }
+}
+
+/// Puts the source items in the saved order, matching them by value. Saved inserted
+/// items are recreated; saved source items that are gone from the source are dropped.
+async fn restore_saved_order(
+    stage: &mut ReorderStage,
+    saved: &[SavedReorderItem],
+    function_call_id: &ConstructId,
+    construct_context: &ConstructContext,
+    actor_context: &ActorContext,
+) {
+    let mut unmatched = Vec::new();
+    for item in &stage.source {
+        let json = match item.current_value().await {
+            Ok(value) => Some(value.to_json().await),
+            Err(_) => None,
+        };
+        unmatched.push((item.clone(), json));
+    }
+    let mut items = Vec::new();
+    for (index, saved_item) in saved.iter().enumerate() {
+        if saved_item.inserted {
+            let item = value_actor_from_json(
+                &saved_item.value,
+                function_call_id.with_child_id(format!("restored_item_{index}")),
+                construct_context.clone(),
+                ValueIdempotencyKey::new(),
+                actor_context.clone(),
+            );
+            stage.inserted.push(item.clone());
+            items.push(item);
+        } else if let Some(position) = unmatched
+            .iter()
+            .position(|(_, json)| json.as_ref() == Some(&saved_item.value))
+        {
+            items.push(unmatched.remove(position).0);
+        }
+    }
+    stage.items = items;
+}
+
+/// `previous` updated to the current `source`: items that left the source are
+/// dropped (unless they are `inserted`), and each new source item goes before the
+/// next source item already placed, or last.
+fn follow_source_order(
+    previous: &[ActorHandle],
+    source: &[ActorHandle],
+    inserted: &[ActorHandle],
+) -> Vec<ActorHandle> {
+    let contains = |items: &[ActorHandle], item: &ActorHandle| {
+        items
+            .iter()
+            .any(|other| other.actor_id() == item.actor_id())
+    };
+    let mut items: Vec<ActorHandle> = previous
+        .iter()
+        .filter(|item| contains(source, item) || contains(inserted, item))
+        .cloned()
+        .collect();
+    for (index, item) in source.iter().enumerate() {
+        if contains(&items, item) {
+            continue;
+        }
+        let position = source[index + 1..]
+            .iter()
+            .find_map(|next| {
+                items
+                    .iter()
+                    .position(|placed| placed.actor_id() == next.actor_id())
+            })
+            .unwrap_or(items.len());
+        items.insert(position, item.clone());
+    }
+    items
+}
+
+/// The 0-based slot a 1-based `position` clamps to among `slots` slots.
+fn clamped_slot(position: f64, slots: usize) -> usize {
+    (1_u32..)
+        .take(slots)
+        .filter(|slot| f64::from(*slot) < position)
+        .count()
+        .min(slots.saturating_sub(1))
+}
+
+/// List/first() -> Value
+/// Returns the current value of the first item in the list.
+/// Re-emits whenever the first item changes (list grows/shrinks or item value updates).
+/// Emits the tag `None` while the list is empty.
+pub fn function_list_first(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    list_end_item(
+        arguments[0].clone(),
+        function_call_id,
+        construct_context,
+        "List/first None",
+        |items| items.first().cloned(),
+    )
+}
+
+/// List/last() -> Value
+/// Returns the current value of the last item in the list.
+/// Re-emits whenever the last item changes (list grows/shrinks or item value updates).
+/// Emits the tag `None` while the list is empty.
+pub fn function_list_last(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    list_end_item(
+        arguments[0].clone(),
+        function_call_id,
+        construct_context,
+        "List/last None",
+        |items| items.last().cloned(),
+    )
+}
+
+/// Follows the item `pick` chooses from the list: that item's values while there is
+/// one, the tag `None` while there is not.
+fn list_end_item(
+    list_actor: ActorHandle,
+    function_call_id: ConstructId,
+    construct_context: ConstructContext,
+    none_description: &'static str,
+    pick: fn(&[ActorHandle]) -> Option<ActorHandle>,
+) -> impl Stream<Item = Value> {
+    // Get the picked item's ActorHandle, re-emitting whenever the list changes
+    let end_item_stream = switch_map(
+        list_actor.stream().filter_map(|value| {
+            future::ready(match value {
+                Value::List(list, _) => Some(list),
+                _ => None,
+            })
+        }),
+        move |list| {
+            list.stream()
+                .scan(Vec::<ActorHandle>::new(), move |items, change| {
+                    change.apply_to_vec(items);
+                    future::ready(Some(pick(items.as_slice())))
+                })
+        },
+    );
+    follow_selected_item(
+        end_item_stream,
+        function_call_id,
+        construct_context,
+        none_description,
+    )
+}
+
+/// List/reverse() -> List
+/// The list's items in reverse order. Changes to the source list are mirrored
+/// incrementally, so the item actors are shared rather than re-created.
+pub fn function_list_reverse(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    _construct_context: ConstructContext,
+    actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    let list_actor = arguments[0].clone();
+    let change_stream =
+        list_changes(list_actor.clone()).scan(Vec::<ActorHandle>::new(), |items, change| {
+            let len = items.len();
+            let mirrored = match &change {
+                ListChange::Replace { items: new_items } => ListChange::Replace {
+                    items: new_items.iter().rev().cloned().collect(),
+                },
+                // Out-of-range indices stay out of range, so both sides ignore them.
+                ListChange::InsertAt { index, item } => ListChange::InsertAt {
+                    index: if *index <= len { len - index } else { *index },
+                    item: item.clone(),
+                },
+                ListChange::UpdateAt { index, item } => ListChange::UpdateAt {
+                    index: if *index < len {
+                        len - 1 - index
+                    } else {
+                        *index
+                    },
+                    item: item.clone(),
+                },
+                ListChange::Move {
+                    old_index,
+                    new_index,
+                } if *old_index < len => ListChange::Move {
+                    old_index: len - 1 - old_index,
+                    new_index: len - 1 - (*new_index).min(len - 1),
+                },
+                ListChange::Move { .. } => change.clone(),
+                ListChange::Push { item } => ListChange::InsertAt {
+                    index: 0,
+                    item: item.clone(),
+                },
+                ListChange::Pop => match items.last() {
+                    Some(last) => ListChange::Remove {
+                        id: last.persistence_id(),
+                    },
+                    None => ListChange::Pop,
+                },
+                ListChange::Remove { id } => {
+                    // `Remove` drops the first item with `id`; mirrored, that is the
+                    // last one, so shared ids fall back to a full replace.
+                    let shared = items
+                        .iter()
+                        .filter(|item| item.persistence_id() == *id)
+                        .count()
+                        > 1;
+                    if shared {
+                        let mut after = items.clone();
+                        change.apply_to_vec(&mut after);
+                        ListChange::Replace {
+                            items: after.into_iter().rev().collect(),
+                        }
+                    } else {
+                        ListChange::Remove { id: *id }
+                    }
+                }
+                ListChange::Clear => ListChange::Clear,
+            };
+            change.apply_to_vec(items);
+            future::ready(Some(mirrored))
+        });
+
+    let list = List::new_with_change_stream(
+        ConstructInfo::new(
+            function_call_id.with_child_id(0),
+            None,
+            "List/reverse result",
+        ),
+        actor_context,
+        change_stream,
+        list_actor,
+    );
+
+    constant(Value::List(
+        Arc::new(list),
+        ValueMetadata::new(ValueIdempotencyKey::new()),
+    ))
+}
+
+/// List/unique() -> List
+/// The first item of each group of structurally equal items, in order. The kept
+/// item actors are shared with the source list and re-picked whenever membership
+/// or any item value changes.
+pub fn function_list_unique(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    _construct_context: ConstructContext,
+    actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    let list_actor = arguments[0].clone();
+    let kept_items =
+        list_items_with_values(list_actor.clone()).then(|(items, values)| async move {
+            first_distinct_indices(&values)
+                .await
+                .into_iter()
+                .map(|index| items[index].clone())
+                .collect::<Vec<_>>()
+        });
+
+    let list = List::new_with_change_stream(
+        ConstructInfo::new(
+            function_call_id.with_child_id(0),
+            None,
+            "List/unique result",
+        ),
+        actor_context,
+        kept_item_changes(kept_items),
+        list_actor,
+    );
+
+    constant(Value::List(
+        Arc::new(list),
+        ValueMetadata::new(ValueIdempotencyKey::new()),
+    ))
+}
+
+/// List/take(count) -> List
+/// The first `count` items of the list. Follows membership changes and `count`;
+/// a negative or non-number count keeps nothing.
+pub fn function_list_take(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    _construct_context: ConstructContext,
+    actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    list_count_slice(
+        arguments,
+        function_call_id,
+        actor_context,
+        "List/take result",
+        |position, count| position <= count,
+    )
+}
+
+/// List/drop(count) -> List
+/// The list without its first `count` items. Follows membership changes and
+/// `count`; a negative or non-number count drops nothing.
+pub fn function_list_drop(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    _construct_context: ConstructContext,
+    actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    list_count_slice(
+        arguments,
+        function_call_id,
+        actor_context,
+        "List/drop result",
+        |position, count| position > count,
+    )
+}
+
+/// The items of the list `arguments[0]` whose 1-based position `keep`s against the
+/// count `arguments[1]`. The kept item actors are shared with the source list.
+fn list_count_slice(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    actor_context: ActorContext,
+    description: &'static str,
+    keep: fn(f64, f64) -> bool,
+) -> impl Stream<Item = Value> {
+    let [argument_list, argument_count] = arguments.as_slice() else {
+        panic!("{description} expects 2 arguments, got {}", arguments.len())
+    };
+
+    enum Input {
+        Items(Vec<ActorHandle>),
+        Count(f64),
+    }
+    let memberships =
+        list_changes(argument_list.clone()).scan(Vec::<ActorHandle>::new(), |items, change| {
+            change.apply_to_vec(items);
+            future::ready(Some(Input::Items(items.clone())))
+        });
+    let counts = argument_count.clone().stream().map(|value| {
+        Input::Count(match value {
+            Value::Number(number, _) if !number.number().is_nan() => number.number().max(0.0),
+            _ => 0.0,
+        })
+    });
+    let kept_items = stream::select(memberships, counts)
+        .scan((None, None), |(items, count), input| {
+            match input {
+                Input::Items(new_items) => *items = Some(new_items),
+                Input::Count(new_count) => *count = Some(new_count),
+            }
+            future::ready(Some(items.clone().zip(*count)))
+        })
+        .filter_map(future::ready)
+        .map(move |(items, count)| {
+            items
+                .into_iter()
+                .zip(1_u32..)
+                .filter(|(_, position)| keep(f64::from(*position), count))
+                .map(|(item, _)| item)
+                .collect::<Vec<_>>()
+        });
+
+    let list = List::new_with_change_stream(
+        ConstructInfo::new(function_call_id.with_child_id(0), None, description),
+        actor_context,
+        kept_item_changes(kept_items),
+        arguments.clone(),
+    );
+
+    constant(Value::List(
+        Arc::new(list),
+        ValueMetadata::new(ValueIdempotencyKey::new()),
+    ))
+}
+
+/// List/chunk(size) -> List
+/// Groups consecutive items into lists of `size`; the last one may be shorter. A size
+/// below 1 or one that is not a number counts as 1. Each chunk is a list of its own
+/// that is kept while the number of chunks stays, so a membership change only
+/// reaches the chunks whose items changed.
+pub fn function_list_chunk(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    _construct_context: ConstructContext,
+    actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    let [argument_list, argument_size] = arguments.as_slice() else {
+        panic!("List/chunk expects 2 arguments, got {}", arguments.len())
+    };
+    let argument_list = argument_list.clone();
+    let argument_size = argument_size.clone();
+    let chunk_actor_context = actor_context.clone();
+    let chunk_function_call_id = function_call_id.clone();
+
+    let chunks = chunked_memberships(argument_list.clone(), argument_size.clone()).scan(
+        Vec::<ActorHandle>::new(),
+        move |chunks, (items, chunk_length)| {
+            let chunk_count = items.len().div_ceil(chunk_length);
+            while chunks.len() < chunk_count {
+                let skipped = chunks.len();
+                let chunk_items = chunked_memberships(argument_list.clone(), argument_size.clone())
+                    .map(move |(items, chunk_length)| {
+                        items
+                            .into_iter()
+                            .skip(skipped * chunk_length)
+                            .take(chunk_length)
+                            .collect::<Vec<_>>()
+                    });
+                let chunk_id = chunk_function_call_id.with_child_id(format!("chunk {skipped}"));
+                let list = List::new_with_change_stream(
+                    ConstructInfo::new(chunk_id.with_child_id(0), None, "List/chunk chunk list"),
+                    chunk_actor_context.clone(),
+                    kept_item_changes(chunk_items),
+                    (),
+                );
+                chunks.push(create_actor(
+                    ConstructInfo::new(chunk_id, None, "List/chunk chunk"),
+                    chunk_actor_context.clone(),
+                    constant(Value::List(
+                        Arc::new(list),
+                        ValueMetadata::new(ValueIdempotencyKey::new()),
+                    )),
+                    PersistenceId::new(),
+                    chunk_actor_context.scope_id(),
+                ));
+            }
+            chunks.truncate(chunk_count);
+            future::ready(Some(chunks.clone()))
+        },
+    );
+
+    let list = List::new_with_change_stream(
+        ConstructInfo::new(function_call_id.with_child_id(0), None, "List/chunk result"),
+        actor_context,
+        kept_item_changes(chunks),
+        arguments.clone(),
+    );
+
+    constant(Value::List(
+        Arc::new(list),
+        ValueMetadata::new(ValueIdempotencyKey::new()),
+    ))
+}
+
+/// The items of `list_actor` with the chunk length `size_actor` asks for, after every
+/// membership or size change.
+fn chunked_memberships(
+    list_actor: ActorHandle,
+    size_actor: ActorHandle,
+) -> impl Stream<Item = (Vec<ActorHandle>, usize)> {
+    enum Input {
+        Items(Vec<ActorHandle>),
+        Size(f64),
+    }
+    let memberships = list_changes(list_actor).scan(Vec::<ActorHandle>::new(), |items, change| {
+        change.apply_to_vec(items);
+        future::ready(Some(Input::Items(items.clone())))
+    });
+    let sizes = size_actor.stream().map(|value| {
+        Input::Size(match value {
+            Value::Number(number, _) => number.number(),
+            _ => 1.0,
+        })
+    });
+    stream::select(memberships, sizes)
+        .scan((None, None), |(items, size), input| {
+            match input {
+                Input::Items(new_items) => *items = Some(new_items),
+                Input::Size(new_size) => *size = Some(new_size),
+            }
+            future::ready(Some(items.clone().zip(*size)))
+        })
+        .filter_map(future::ready)
+        .map(|(items, size)| {
+            // Lengths past the list length chunk like the list length does.
+            let chunk_length = (1_u32..)
+                .take(items.len())
+                .take_while(|length| f64::from(*length) <= size)
+                .count()
+                .max(1);
+            (items, chunk_length)
+        })
+}
+
+/// List/zip(with) -> List
+/// Pairs items by position as `[first: .., second: ..]` objects, up to the shorter
+/// list. The pair fields are the paired item actors, so item value changes flow
+/// through without touching the list; membership changes re-pair from the first
+/// shifted position.
+pub fn function_list_zip(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    enum Side {
+        First(ListChange),
+        Second(ListChange),
+    }
+
+    #[derive(Default)]
+    struct ZipState {
+        firsts: Option<Vec<ActorHandle>>,
+        seconds: Option<Vec<ActorHandle>>,
+        // (first item, second item, pair) per output position
+        pairs: Option<Vec<(ActorId, ActorId, ActorHandle)>>,
+    }
+
+    let list_actor = arguments[0].clone();
+    let with_actor = arguments[1].clone();
+    let pair_actor_context = actor_context.clone();
+    let pair_function_call_id = function_call_id.clone();
+    let new_pair = move |first: &ActorHandle, second: &ActorHandle| {
+        let pair_id = pair_function_call_id.with_child_id(ulid::Ulid::new().to_string());
+        let field = |name: &'static str, item: &ActorHandle| {
+            Variable::new_arc(
+                ConstructInfo::new(pair_id.with_child_id(name), None, "List/zip pair field"),
+                construct_context.clone(),
+                name,
+                item.clone(),
+                PersistenceId::new(),
+                pair_actor_context.scope.clone(),
+            )
+        };
+        Object::new_arc_value_actor(
+            ConstructInfo::new(pair_id.clone(), None, "List/zip pair"),
+            construct_context.clone(),
+            ValueIdempotencyKey::new(),
+            pair_actor_context.clone(),
+            [field("first", first), field("second", second)],
+        )
+    };
+
+    let change_stream = stream::select(
+        list_changes(list_actor.clone()).map(Side::First),
+        list_changes(with_actor.clone()).map(Side::Second),
+    )
+    .scan(ZipState::default(), move |state, side| {
+        match side {
+            Side::First(change) => change.apply_to_vec(state.firsts.get_or_insert_default()),
+            Side::Second(change) => change.apply_to_vec(state.seconds.get_or_insert_default()),
+        }
+        // Nothing to pair until both lists have reported their items
+        let (Some(firsts), Some(seconds)) = (&state.firsts, &state.seconds) else {
+            return future::ready(Some(Vec::new()));
+        };
+        let old_pairs = state.pairs.take();
+        let pairs: Vec<_> = firsts
+            .iter()
+            .zip(seconds)
+            .enumerate()
+            .map(|(index, (first, second))| {
+                let ids = (first.actor_id(), second.actor_id());
+                match old_pairs.as_ref().and_then(|pairs| pairs.get(index)) {
+                    Some((first_id, second_id, pair)) if (*first_id, *second_id) == ids => {
+                        (ids.0, ids.1, pair.clone())
+                    }
+                    _ => (ids.0, ids.1, new_pair(first, second)),
+                }
+            })
+            .collect();
+
+        let changes = match &old_pairs {
+            None => vec![ListChange::Replace {
+                items: pairs.iter().map(|(_, _, pair)| pair.clone()).collect(),
+            }],
+            Some(old_pairs) => {
+                let mut changes: Vec<ListChange> = pairs
+                    .iter()
+                    .zip(old_pairs)
+                    .enumerate()
+                    .filter(|(_, ((_, _, pair), (_, _, old_pair)))| {
+                        pair.actor_id() != old_pair.actor_id()
+                    })
+                    .map(|(index, ((_, _, pair), _))| ListChange::UpdateAt {
+                        index,
+                        item: pair.clone(),
+                    })
+                    .collect();
+                changes.extend(
+                    pairs
+                        .iter()
+                        .skip(old_pairs.len())
+                        .map(|(_, _, pair)| ListChange::Push { item: pair.clone() }),
+                );
+                changes.extend(std::iter::repeat_n(
+                    ListChange::Pop,
+                    old_pairs.len().saturating_sub(pairs.len()),
+                ));
+                changes
+            }
+        };
+        state.pairs = Some(pairs);
+        future::ready(Some(changes))
     })
+    .flat_map(stream::iter);
+
+    let list = List::new_with_change_stream(
+        ConstructInfo::new(function_call_id.with_child_id(0), None, "List/zip result"),
+        actor_context,
+        change_stream,
+        (list_actor, with_actor),
+    );
+
+    constant(Value::List(
+        Arc::new(list),
+        ValueMetadata::new(ValueIdempotencyKey::new()),
+    ))
 }
 
-/// List/append(item: value) -> List
-/// Appends an item to the list when the item stream produces a value
-pub fn function_list_append(
+/// List/concat(with) -> List
+/// The items of the list followed by the items of `with`. Item actors are shared with
+/// both sources and changes on either side are forwarded with shifted positions, so
+/// items downstream keep their state when the other side changes.
+pub fn function_list_concat(
     arguments: Arc<Vec<ActorHandle>>,
     function_call_id: ConstructId,
     _function_call_persistence_id: PersistenceId,
     _construct_context: ConstructContext,
     actor_context: ActorContext,
 ) -> impl Stream<Item = Value> {
-    // arguments[0] = the list (piped)
-    // arguments[1] = the item to append
-    let list_actor = arguments[0].clone();
-
-    // If item argument is SKIP (not provided), just forward the list unchanged
-    if arguments.len() < 2 {
-        return list_actor.stream().left_stream();
+    enum Side {
+        First(ListChange),
+        Second(ListChange),
     }
 
-    let item_actor = arguments[1].clone();
-
-    // Create a change stream that:
-    // 1. Forwards all changes from the original list
-    // 2. Adds Push changes when the item stream produces values
-    //
-    // IMPORTANT: The first change MUST be a Replace from the original list.
-    // We use scan to ensure proper ordering: buffer append changes until
-    // after the first list change arrives.
-    let change_stream = {
-        let function_call_id_for_append = function_call_id.clone();
-        let actor_context_for_append = actor_context.clone();
-
-        // Tag changes with their source so we can ensure proper ordering
-        enum TaggedChange {
-            FromList(ListChange),
-            FromAppend(ListChange),
-        }
-
-        let list_changes = list_actor
-            .clone()
-            .stream()
-            .filter_map(|value| {
-                future::ready(match value {
-                    Value::List(list, _) => Some(list),
-                    _ => None,
-                })
-            })
-            .flat_map(|list| list.stream())
-            .map(TaggedChange::FromList);
-
-        let append_changes = item_actor.clone().stream().map(move |value| {
-            // When item stream produces a value, create a new constant ValueActor
-            // containing that specific value and push it.
-            // Note: SKIP is not a Value - it's a stream behavior where streams end without
-            // producing values. If item is SKIP, this map closure is never called.
-            let new_item_actor = create_actor(
-                ConstructInfo::new(
-                    function_call_id_for_append.with_child_id("appended_item"),
-                    None,
-                    "List/append appended item",
-                ),
-                actor_context_for_append.clone(),
-                constant(value),
-                PersistenceId::new(),
-                actor_context_for_append.scope_id(),
-            );
-            TaggedChange::FromAppend(ListChange::Push {
-                item: new_item_actor,
-            })
-        });
-
-        // Merge both change streams, then use scan to ensure proper ordering
-        stream::select(list_changes, append_changes)
-            .scan(
-                (false, Vec::<ListChange>::new()), // (has_received_first_list_change, buffered_appends)
-                |state, tagged_change| {
-                    let (has_received_first, buffered) = state;
-
-                    let changes_to_emit = match tagged_change {
-                        TaggedChange::FromList(change) => {
-                            if !*has_received_first {
-                                // First list change - emit it plus any buffered appends
-                                *has_received_first = true;
-                                let mut all = vec![change];
-                                all.append(buffered);
-                                all
-                            } else {
-                                // Subsequent list change - emit directly
-                                vec![change]
-                            }
-                        }
-                        TaggedChange::FromAppend(change) => {
-                            if *has_received_first {
-                                // Already received first list change - emit directly
-                                vec![change]
-                            } else {
-                                // Buffer until first list change arrives
-                                buffered.push(change);
-                                vec![]
-                            }
-                        }
-                    };
-
-                    future::ready(Some(changes_to_emit))
-                },
-            )
-            .flat_map(|changes| stream::iter(changes))
-    };
+    let list_actor = arguments[0].clone();
+    let with_actor = arguments[1].clone();
+    let change_stream = stream::select(
+        list_changes(list_actor.clone()).map(Side::First),
+        list_changes(with_actor.clone()).map(Side::Second),
+    )
+    .scan(
+        (None::<Vec<ActorHandle>>, None::<Vec<ActorHandle>>),
+        |(firsts, seconds), side| {
+            // `None` means the change has no positional equivalent; replace everything.
+            let forwarded = match (&side, firsts.as_ref(), seconds.as_ref()) {
+                (_, None, _) | (_, _, None) => None,
+                (Side::First(change), Some(firsts), Some(_)) => concat_first_change(change, firsts),
+                (Side::Second(change), Some(firsts), Some(seconds)) => {
+                    concat_second_change(change, firsts, seconds)
+                }
+            };
+            match side {
+                Side::First(change) => change.apply_to_vec(firsts.get_or_insert_default()),
+                Side::Second(change) => change.apply_to_vec(seconds.get_or_insert_default()),
+            }
+            // Nothing to emit until both lists have reported their items
+            let (Some(firsts), Some(seconds)) = (firsts.as_ref(), seconds.as_ref()) else {
+                return future::ready(Some(Vec::new()));
+            };
+            future::ready(Some(forwarded.unwrap_or_else(|| {
+                vec![ListChange::Replace {
+                    items: firsts.iter().chain(seconds).cloned().collect(),
+                }]
+            })))
+        },
+    )
+    .flat_map(stream::iter);
 
     let list = List::new_with_change_stream(
         ConstructInfo::new(
-            function_call_id.with_child_id(ulid::Ulid::new().to_string()),
+            function_call_id.with_child_id(0),
             None,
-            "List/append result",
+            "List/concat result",
         ),
         actor_context,
         change_stream,
-        (list_actor, item_actor),
+        (list_actor, with_actor),
     );
 
     constant(Value::List(
         Arc::new(list),
         ValueMetadata::new(ValueIdempotencyKey::new()),
     ))
-    .right_stream()
 }
 
-/// List/clear(on: stream) -> List
-/// Clears all items from the list when the trigger stream emits any value
-pub fn function_list_clear(
+/// A change of the first `List/concat` source, given its items before the change, as
+/// changes of the concatenated list. Out-of-range changes are dropped, as the source
+/// ignores them too.
+fn concat_first_change(change: &ListChange, firsts: &[ActorHandle]) -> Option<Vec<ListChange>> {
+    let len = firsts.len();
+    let has_id = |id: &PersistenceId| firsts.iter().any(|item| item.persistence_id() == *id);
+    Some(match change {
+        ListChange::Replace { .. } => return None,
+        ListChange::InsertAt { index, .. } if *index <= len => vec![change.clone()],
+        ListChange::UpdateAt { index, .. } if *index < len => vec![change.clone()],
+        // The source clamps `new_index` to its own length; here the second list follows.
+        ListChange::Move {
+            old_index,
+            new_index,
+        } if *old_index < len => vec![ListChange::Move {
+            old_index: *old_index,
+            new_index: (*new_index).min(len - 1),
+        }],
+        ListChange::InsertAt { .. } | ListChange::UpdateAt { .. } | ListChange::Move { .. } => {
+            Vec::new()
+        }
+        ListChange::Push { item } => vec![ListChange::InsertAt {
+            index: len,
+            item: item.clone(),
+        }],
+        // `Remove { id }` drops the first item with `id`, which is the last one only
+        // when no earlier item shares its id.
+        ListChange::Pop => match firsts.split_last() {
+            None => Vec::new(),
+            Some((last, rest)) => {
+                let id = last.persistence_id();
+                if rest.iter().any(|item| item.persistence_id() == id) {
+                    return None;
+                }
+                vec![ListChange::Remove { id }]
+            }
+        },
+        ListChange::Remove { id } if has_id(id) => vec![ListChange::Remove { id: *id }],
+        ListChange::Remove { .. } => Vec::new(),
+        // Each removal drops the first remaining item with that id, always one of `firsts`
+        ListChange::Clear => firsts
+            .iter()
+            .map(|item| ListChange::Remove {
+                id: item.persistence_id(),
+            })
+            .collect(),
+    })
+}
+
+/// A change of the second `List/concat` source, given both sources' items before the
+/// change, as changes of the concatenated list.
+fn concat_second_change(
+    change: &ListChange,
+    firsts: &[ActorHandle],
+    seconds: &[ActorHandle],
+) -> Option<Vec<ListChange>> {
+    let offset = firsts.len();
+    let len = seconds.len();
+    let has_id = |items: &[ActorHandle], id: &PersistenceId| {
+        items.iter().any(|item| item.persistence_id() == *id)
+    };
+    Some(match change {
+        ListChange::Replace { .. } => return None,
+        ListChange::InsertAt { index, item } if *index <= len => vec![ListChange::InsertAt {
+            index: offset + index,
+            item: item.clone(),
+        }],
+        ListChange::UpdateAt { index, item } if *index < len => vec![ListChange::UpdateAt {
+            index: offset + index,
+            item: item.clone(),
+        }],
+        ListChange::Move {
+            old_index,
+            new_index,
+        } if *old_index < len => vec![ListChange::Move {
+            old_index: offset + old_index,
+            new_index: offset + (*new_index).min(len - 1),
+        }],
+        ListChange::InsertAt { .. } | ListChange::UpdateAt { .. } | ListChange::Move { .. } => {
+            Vec::new()
+        }
+        ListChange::Push { item } => vec![ListChange::Push { item: item.clone() }],
+        ListChange::Pop if len > 0 => vec![ListChange::Pop],
+        ListChange::Pop => Vec::new(),
+        // The first item with `id` must be on this side, or the removal hits `firsts`
+        ListChange::Remove { id } if has_id(firsts, id) && has_id(seconds, id) => return None,
+        ListChange::Remove { id } if has_id(seconds, id) => vec![ListChange::Remove { id: *id }],
+        ListChange::Remove { .. } => Vec::new(),
+        ListChange::Clear => std::iter::repeat_n(ListChange::Pop, len).collect(),
+    })
+}
+
+/// List/flatten() -> List
+/// The items of each inner list in order, one level deep; items that are not lists
+/// are kept as they are. Follows the outer list, each inner list and items that
+/// switch between lists. The item actors are shared with the inner lists.
+pub fn function_list_flatten(
     arguments: Arc<Vec<ActorHandle>>,
     function_call_id: ConstructId,
-    function_call_persistence_id: PersistenceId,
-    construct_context: ConstructContext,
+    _function_call_persistence_id: PersistenceId,
+    _construct_context: ConstructContext,
     actor_context: ActorContext,
 ) -> impl Stream<Item = Value> {
-    // arguments[0] = the list (piped)
-    // arguments[1] = the trigger stream (on: xxx)
     let list_actor = arguments[0].clone();
-
-    // If trigger argument is not provided, just forward the list unchanged
-    if arguments.len() < 2 {
-        return list_actor.stream().left_stream();
-    }
-
-    let trigger_actor = arguments[1].clone();
-
-    // Similar pattern to List/append:
-    // 1. Forward all changes from the original list
-    // 2. When trigger fires, emit Clear AND clear recorded calls storage
-    let change_stream = {
-        enum TaggedChange {
-            FromList(ListChange),
-            Clear,
-        }
-
-        let list_changes = list_actor
-            .clone()
-            .stream()
-            .filter_map(|value| {
-                future::ready(match value {
-                    Value::List(list, _) => Some(list),
-                    _ => None,
-                })
+    let memberships =
+        list_changes(list_actor.clone()).scan(Vec::<ActorHandle>::new(), |items, change| {
+            change.apply_to_vec(items);
+            future::ready(Some(items.clone()))
+        });
+    let flattened_items = switch_map(
+        memberships,
+        |sections| -> LocalBoxStream<'static, Vec<ActorHandle>> {
+            if sections.is_empty() {
+                return stream::once(future::ready(Vec::new())).boxed_local();
+            }
+            let section_count = sections.len();
+            stream::select_all(sections.into_iter().enumerate().map(|(index, section)| {
+                flattened_section(section)
+                    .map(move |items| (index, items))
+                    .boxed_local()
+            }))
+            .scan(vec![None; section_count], |latest, (index, items)| {
+                latest[index] = Some(items);
+                let flattened = latest
+                    .iter()
+                    .cloned()
+                    .collect::<Option<Vec<_>>>()
+                    .map(|sections| sections.concat());
+                future::ready(Some(flattened))
             })
-            .flat_map(|list| list.stream())
-            .map(TaggedChange::FromList);
-
-        // When trigger stream emits any value, emit Clear
-        let clear_changes = trigger_actor
-            .clone()
-            .stream()
-            .map(|_value| TaggedChange::Clear);
-
-        // Merge both streams, use scan for proper ordering
-        stream::select(list_changes, clear_changes)
-            .scan(
-                (false, false, Vec::<ActorHandle>::new()),
-                |state, tagged_change| {
-                    let (has_received_first, has_pending_clear, tracked_items) = state;
-
-                    let changes_to_emit = match tagged_change {
-                        TaggedChange::FromList(change) => {
-                            // Update tracked items based on the change
-                            match &change {
-                                ListChange::Replace { items } => {
-                                    *tracked_items = items.to_vec();
-                                }
-                                ListChange::Push { item } => {
-                                    tracked_items.push(item.clone());
-                                }
-                                ListChange::Pop => {
-                                    tracked_items.pop();
-                                }
-                                ListChange::Clear => {
-                                    tracked_items.clear();
-                                }
-                                ListChange::Remove { id } => {
-                                    if let Some(pos) =
-                                        tracked_items.iter().position(|i| i.persistence_id() == *id)
-                                    {
-                                        tracked_items.remove(pos);
-                                    }
-                                }
-                                _ => {}
-                            }
-
-                            if !*has_received_first {
-                                *has_received_first = true;
-                                // First list change - emit it plus pending clear if any
-                                if *has_pending_clear {
-                                    *has_pending_clear = false;
-                                    // Clear storage for tracked items
-                                    clear_source_storage_for_items(tracked_items);
-                                    tracked_items.clear();
-                                    vec![change, ListChange::Clear]
-                                } else {
-                                    vec![change]
-                                }
-                            } else {
-                                vec![change]
-                            }
-                        }
-                        TaggedChange::Clear => {
-                            if *has_received_first {
-                                // Clear storage for tracked items
-                                clear_source_storage_for_items(tracked_items);
-                                tracked_items.clear();
-                                vec![ListChange::Clear]
-                            } else {
-                                // Buffer the clear until first list change arrives
-                                *has_pending_clear = true;
-                                vec![]
-                            }
-                        }
-                    };
-
-                    future::ready(Some(changes_to_emit))
-                },
-            )
-            .flat_map(|changes| stream::iter(changes))
-    };
+            .filter_map(future::ready)
+            .boxed_local()
+        },
+    );
 
-    let list = List::new_with_change_stream_and_persistence(
-        ConstructInfo::new(function_call_id.with_child_id(0), None, "List/clear result"),
-        construct_context,
+    let list = List::new_with_change_stream(
+        ConstructInfo::new(
+            function_call_id.with_child_id(0),
+            None,
+            "List/flatten result",
+        ),
         actor_context,
-        change_stream,
-        (list_actor, trigger_actor),
-        function_call_persistence_id,
+        kept_item_changes(flattened_items),
+        list_actor,
     );
 
     constant(Value::List(
         Arc::new(list),
         ValueMetadata::new(ValueIdempotencyKey::new()),
     ))
-    .right_stream()
 }
 
-/// Clear recorded calls storage for all items that have source origins.
-/// Called when List/clear triggers to ensure items don't restore on next Run.
-fn clear_source_storage_for_items(items: &[ActorHandle]) {
-    use std::collections::HashSet;
-    use zoon::{WebStorage, local_storage};
-
-    // Collect unique source storage keys
-    let mut source_keys: HashSet<String> = HashSet::new();
-    for item in items {
-        if let Some(origin) = item.list_item_origin() {
-            source_keys.insert(origin.source_storage_key.clone());
-        }
-    }
+/// The items one outer item contributes to `List/flatten`: the inner list's item
+/// actors while it holds a list, the outer item itself otherwise.
+fn flattened_section(section: ActorHandle) -> impl Stream<Item = Vec<ActorHandle>> {
+    switch_map(
+        section.clone().stream(),
+        move |value| -> LocalBoxStream<'static, Vec<ActorHandle>> {
+            match value {
+                Value::List(list, _) => list
+                    .stream()
+                    .scan(Vec::<ActorHandle>::new(), |items, change| {
+                        change.apply_to_vec(items);
+                        future::ready(Some(items.clone()))
+                    })
+                    .boxed_local(),
+                _ => stream::once(future::ready(vec![section.clone()])).boxed_local(),
+            }
+        },
+    )
+}
 
-    // Clear each source storage key
-    for key in source_keys {
-        if LOG_DEBUG {
-            zoon::println!("[DEBUG] List/clear: Clearing source storage key: {}", key);
-        }
-        local_storage().remove(&key);
-    }
+/// List/contains(value) -> Tag (True/False)
+/// Whether any item equals `value`, compared structurally.
+/// Re-runs when membership, an item value or `value` changes; emits only on change.
+pub fn function_list_contains(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    list_search(arguments, move |position| {
+        Tag::new_value(
+            ConstructInfo::new(
+                function_call_id.with_child_id(0),
+                None,
+                "List/contains result",
+            ),
+            construct_context.clone(),
+            ValueIdempotencyKey::new(),
+            if position.is_some() { "True" } else { "False" },
+        )
+    })
 }
 
-/// List/last() -> Value
-/// Returns the current value of the last item in the list.
-/// Re-emits whenever the last item changes (list grows/shrinks or item value updates).
-/// Emits nothing (stream pending) when the list is empty.
-pub fn function_list_last(
+/// List/index_of(value) -> Number | None
+/// The 1-based position of the first item equal to `value`, or the tag `None`.
+/// Re-runs when membership, an item value or `value` changes; emits only on change.
+pub fn function_list_index_of(
     arguments: Arc<Vec<ActorHandle>>,
-    _function_call_id: ConstructId,
+    function_call_id: ConstructId,
     _function_call_persistence_id: PersistenceId,
-    _construct_context: ConstructContext,
+    construct_context: ConstructContext,
     _actor_context: ActorContext,
 ) -> impl Stream<Item = Value> {
-    let list_actor = arguments[0].clone();
-    // Get the last item's ActorHandle, re-emitting whenever the list changes
-    let last_item_stream = switch_map(
+    list_search(arguments, move |position| match position {
+        Some(index) => Number::new_value(
+            ConstructInfo::new(
+                function_call_id.with_child_id(0),
+                None,
+                "List/index_of result",
+            ),
+            construct_context.clone(),
+            ValueIdempotencyKey::new(),
+            f64::from(u32::try_from(index + 1).unwrap_or(u32::MAX)),
+        ),
+        None => Tag::new_value(
+            ConstructInfo::new(
+                function_call_id.with_child_id(1),
+                None,
+                "List/index_of None",
+            ),
+            construct_context.clone(),
+            ValueIdempotencyKey::new(),
+            "None".to_string(),
+        ),
+    })
+}
+
+/// Finds the 0-based position of the first item equal to `arguments[1]` in the list
+/// `arguments[0]` and emits `result(position)` whenever the position changes.
+fn list_search(
+    arguments: Arc<Vec<ActorHandle>>,
+    result: impl Fn(Option<usize>) -> Value + 'static,
+) -> impl Stream<Item = Value> {
+    enum Input {
+        Items(Vec<Value>),
+        Searched(Value),
+    }
+
+    let inputs = stream::select(
+        list_item_values(arguments[0].clone()).map(Input::Items),
+        arguments[1].clone().stream().map(Input::Searched),
+    );
+    inputs
+        .scan((None, None), |(items, searched), input| {
+            match input {
+                Input::Items(values) => *items = Some(values),
+                Input::Searched(value) => *searched = Some(value),
+            }
+            future::ready(Some(items.clone().zip(searched.clone())))
+        })
+        .filter_map(future::ready)
+        .then(|(items, searched)| async move {
+            for (index, item) in items.iter().enumerate() {
+                if values_equal_async(item, &searched).await {
+                    return Some(index);
+                }
+            }
+            None
+        })
+        .scan(None, |last_position, position| {
+            let changed = *last_position != Some(position);
+            *last_position = Some(position);
+            future::ready(Some(changed.then_some(position)))
+        })
+        .filter_map(future::ready)
+        .map(result)
+}
+
+/// The current values of the list's items, in order. Re-emits when membership or
+/// any item value changes, once every item has a value.
+fn list_item_values(list_actor: ActorHandle) -> impl Stream<Item = Vec<Value>> {
+    list_items_with_values(list_actor).map(|(_, values)| values)
+}
+
+/// The list's item actors together with their current values; see `list_item_values`.
+fn list_items_with_values(
+    list_actor: ActorHandle,
+) -> LocalBoxStream<'static, (Vec<ActorHandle>, Vec<Value>)> {
+    let memberships = list_changes(list_actor).scan(Vec::<ActorHandle>::new(), |items, change| {
+        change.apply_to_vec(items);
+        future::ready(Some(items.clone()))
+    });
+    switch_map(
+        memberships,
+        |items| -> LocalBoxStream<'static, (Vec<ActorHandle>, Vec<Value>)> {
+            if items.is_empty() {
+                return stream::once(future::ready((Vec::new(), Vec::new()))).boxed_local();
+            }
+            let item_count = items.len();
+            stream::select_all(
+                items.iter().cloned().enumerate().map(|(index, item)| {
+                    item.stream().map(move |value| (index, value)).boxed_local()
+                }),
+            )
+            .scan(vec![None; item_count], move |latest, (index, value)| {
+                latest[index] = Some(value);
+                let values = latest.iter().cloned().collect::<Option<Vec<Value>>>();
+                future::ready(Some(values.map(|values| (items.clone(), values))))
+            })
+            .filter_map(future::ready)
+            .boxed_local()
+        },
+    )
+}
+
+/// The changes of whichever list the actor currently holds; a new list value
+/// starts over with its own initial `Replace`.
+fn list_changes(list_actor: ActorHandle) -> impl Stream<Item = ListChange> {
+    switch_map(
         list_actor.stream().filter_map(|value| {
             future::ready(match value {
                 Value::List(list, _) => Some(list),
                 _ => None,
             })
         }),
-        |list| {
-            list.stream()
-                .scan(Vec::<ActorHandle>::new(), |items, change| {
-                    change.apply_to_vec(items);
-                    future::ready(Some(items.last().cloned()))
-                })
-                .filter_map(future::ready)
-        },
-    );
-    // switch_map: when the last item changes identity, cancel old subscription and start new
-    switch_map(last_item_stream, |actor| actor.stream())
+        |list| list.stream(),
+    )
 }
 
 /// List/remove_last() -> List
@@ -3335,6 +5858,377 @@ pub fn function_router_go_to(
     })
 }
 
+// --- Document query parameter functions ---
+
+// Thread-local storage for the senders of every Document/query_params() call
+// (allows set_query_param to notify all listeners)
+thread_local! {
+    static QUERY_PARAMS_SENDERS: std::cell::RefCell<Vec<mpsc::Sender<Vec<(String, String)>>>> = std::cell::RefCell::new(Vec::new());
+}
+
+/// Sends `params` to every Document/query_params() stream, forgetting the dropped ones.
+fn broadcast_query_params(params: &[(String, String)]) {
+    QUERY_PARAMS_SENDERS.with(|cell| {
+        cell.borrow_mut().retain_mut(|sender| {
+            if let Err(e) = sender.try_send(params.to_vec()) {
+                if LOG_DEBUG {
+                    zoon::println!("[QUERY_PARAMS] Failed to send query params: {e}");
+                }
+                return !e.is_disconnected();
+            }
+            true
+        })
+    });
+}
+
+/// Get the current URL query string (including the leading `?`, if any) from the browser
+fn get_current_search() -> String {
+    window().location().search().unwrap_or_default()
+}
+
+/// Document/query_params() -> [key: Text, ..]
+/// Returns the current URL query parameters as a reactive object.
+/// Updates on browser back/forward navigation and after Document/set_query_param.
+pub fn function_document_query_params(
+    _arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    let (mut params_sender, params_receiver) = mpsc::channel::<Vec<(String, String)>>(8);
+
+    // Send initial query params
+    if let Err(e) = params_sender.try_send(parse_query_params(&get_current_search())) {
+        if LOG_DEBUG {
+            zoon::println!("[QUERY_PARAMS] Failed to send initial query params: {e}");
+        }
+    }
+
+    // Back/forward navigation may change the query string as well as the path
+    let popstate_closure: Closure<dyn Fn()> = Closure::new({
+        let params_sender = params_sender.clone();
+        move || {
+            let params = parse_query_params(&get_current_search());
+            if let Err(e) = params_sender.clone().try_send(params) {
+                if LOG_DEBUG {
+                    zoon::println!("[QUERY_PARAMS] Failed to send popstate query params: {e}");
+                }
+            }
+        }
+    });
+
+    window()
+        .add_event_listener_with_callback("popstate", popstate_closure.as_ref().unchecked_ref())
+        .unwrap_throw();
+
+    let popstate_closure = SendWrapper::new(popstate_closure);
+
+    QUERY_PARAMS_SENDERS.with(|cell| {
+        cell.borrow_mut().push(params_sender);
+    });
+
+    let mut result_version = 0u64;
+    params_receiver.map(move |params| {
+        // Prevent drop: captured by `move` closure, lives as long as stream combinator
+        let _popstate_closure = &popstate_closure;
+        let variables: Vec<Arc<Variable>> = params
+            .into_iter()
+            .enumerate()
+            .map(|(index, (key, value))| {
+                let index = u32::try_from(index).unwrap_or(u32::MAX);
+                let value_actor = create_constant_actor(
+                    ConstructInfo::new(
+                        function_call_id.with_child_id(format!(
+                            "Document/query_params {key} v.{result_version}"
+                        )),
+                        None,
+                        "Document/query_params() -> [..] value",
+                    ),
+                    function_call_persistence_id.with_child_index(index),
+                    Text::new_value(
+                        ConstructInfo::new(
+                            function_call_id.with_child_id(format!(
+                                "Document/query_params {key} text v.{result_version}"
+                            )),
+                            None,
+                            "Document/query_params() -> [..] text",
+                        ),
+                        construct_context.clone(),
+                        ValueIdempotencyKey::new(),
+                        value,
+                    ),
+                    actor_context.scope_id(),
+                );
+                Variable::new_arc(
+                    ConstructInfo::new(
+                        function_call_id
+                            .with_child_id(format!("Document/query_params {key} variable")),
+                        None,
+                        "Document/query_params() -> [..] variable",
+                    ),
+                    construct_context.clone(),
+                    key,
+                    value_actor,
+                    function_call_persistence_id.with_child_index(index),
+                    actor_context.scope.clone(),
+                )
+            })
+            .collect();
+        let value = Object::new_value(
+            ConstructInfo::new(
+                function_call_id.with_child_id(format!("Document/query_params v.{result_version}")),
+                None,
+                "Document/query_params() -> [..]",
+            ),
+            construct_context.clone(),
+            ValueIdempotencyKey::new(),
+            variables,
+        );
+        result_version += 1;
+        value
+    })
+}
+
+/// Document/set_query_param(key: Text, value: Text) -> []
+/// Writes the query parameter into the URL whenever the key or value changes.
+/// Uses replaceState so filter changes don't spam the browser history.
+/// An empty value removes the parameter.
+pub fn function_document_set_query_param(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    let [argument_key, argument_value] = arguments.as_slice() else {
+        panic!("Document/set_query_param expects 2 arguments")
+    };
+    enum Input {
+        Key(String),
+        Value(String),
+    }
+    let key_stream = argument_key.clone().stream().map(|value| match &value {
+        Value::Text(text, _) => Input::Key(text.text().to_string()),
+        _ => panic!("Document/set_query_param expects a Text key"),
+    });
+    let value_stream = argument_value.clone().stream().map(|value| match &value {
+        Value::Text(text, _) => Input::Value(text.text().to_string()),
+        Value::Number(number, _) => Input::Value(number.number().to_string()),
+        Value::Tag(tag, _) => Input::Value(tag.tag().to_string()),
+        _ => Input::Value(String::new()),
+    });
+    stream::select(key_stream, value_stream)
+        .scan(
+            (None::<String>, None::<String>),
+            move |(last_key, last_value), input| {
+                match input {
+                    Input::Key(key) => *last_key = Some(key),
+                    Input::Value(value) => *last_value = Some(value),
+                }
+                let (Some(key), Some(value)) = (last_key.clone(), last_value.clone()) else {
+                    return future::ready(Some(None));
+                };
+
+                let mut params = parse_query_params(&get_current_search());
+                set_query_param(&mut params, &key, value);
+                let pathname = get_current_pathname();
+                let url = format!("{pathname}{}", format_query_params(&params));
+                if LOG_DEBUG {
+                    zoon::println!(
+                        "[QUERY_PARAMS] set_query_param replacing URL with: '{}'",
+                        url
+                    );
+                }
+                history()
+                    .replace_state_with_url(&JsValue::NULL, "", Some(&url))
+                    .unwrap_throw();
+
+                broadcast_query_params(&params);
+
+                future::ready(Some(Some(Object::new_value(
+                    ConstructInfo::new(
+                        function_call_id.with_child_id(0),
+                        None,
+                        "Document/set_query_param result",
+                    ),
+                    construct_context.clone(),
+                    ValueIdempotencyKey::new(),
+                    [],
+                ))))
+            },
+        )
+        .filter_map(future::ready)
+}
+
+/// Document/visible() -> Tag (True/False)
+/// True while the page is visible, follows `visibilitychange` (tab switches, minimizing).
+pub fn function_document_visible(
+    _arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    let document = window().document().unwrap_throw();
+    browser_flag_stream(
+        vec![(document.into(), "visibilitychange")],
+        || {
+            !window()
+                .document()
+                .is_some_and(|document| document.hidden())
+        },
+        function_call_id,
+        construct_context,
+        "Document/visible",
+    )
+}
+
+/// Document/online() -> Tag (True/False)
+/// True while the browser reports a network connection, follows `online`/`offline`.
+pub fn function_document_online(
+    _arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    browser_flag_stream(
+        vec![(window().into(), "online"), (window().into(), "offline")],
+        || window().navigator().on_line(),
+        function_call_id,
+        construct_context,
+        "Document/online",
+    )
+}
+
+/// Emits `read()` as True/False now and again each time one of `events` fires on its target.
+fn browser_flag_stream(
+    events: Vec<(web_sys::EventTarget, &'static str)>,
+    read: fn() -> bool,
+    function_call_id: ConstructId,
+    construct_context: ConstructContext,
+    description: &'static str,
+) -> impl Stream<Item = Value> {
+    let (mut flag_sender, flag_receiver) = mpsc::channel::<bool>(8);
+
+    if let Err(e) = flag_sender.try_send(read()) {
+        if LOG_DEBUG {
+            zoon::println!("[{description}] Failed to send initial value: {e}");
+        }
+    }
+
+    let listener: Closure<dyn Fn()> = Closure::new(move || {
+        if let Err(e) = flag_sender.clone().try_send(read()) {
+            if LOG_DEBUG {
+                zoon::println!("[{description}] Failed to send value: {e}");
+            }
+        }
+    });
+    for (target, event) in &events {
+        target
+            .add_event_listener_with_callback(event, listener.as_ref().unchecked_ref())
+            .unwrap_throw();
+    }
+    let listener = SendWrapper::new(listener);
+
+    flag_receiver.map(move |flag| {
+        // Prevent drop: captured by `move` closure, lives as long as stream combinator
+        let _listener = &listener;
+        Tag::new_value(
+            ConstructInfo::new(function_call_id.with_child_id(0), None, description),
+            construct_context.clone(),
+            ValueIdempotencyKey::new(),
+            if flag { "True" } else { "False" }.to_string(),
+        )
+    })
+}
+
+/// `beforeunload` listener of Document/confirm_unload, unregistered when dropped
+/// (`when` turned False or the program stopped).
+struct UnloadConfirmation {
+    listener: Closure<dyn Fn(web_sys::Event)>,
+}
+
+impl UnloadConfirmation {
+    fn register(message: String) -> Self {
+        let listener: Closure<dyn Fn(web_sys::Event)> =
+            Closure::new(move |event: web_sys::Event| {
+                event.prevent_default();
+                // Browsers show their own wording; older ones display `returnValue`
+                let _ = js_sys::Reflect::set(
+                    &event,
+                    &"returnValue".into(),
+                    &JsValue::from_str(&message),
+                );
+            });
+        window()
+            .add_event_listener_with_callback("beforeunload", listener.as_ref().unchecked_ref())
+            .unwrap_throw();
+        Self { listener }
+    }
+}
+
+impl Drop for UnloadConfirmation {
+    fn drop(&mut self) {
+        let _ = window().remove_event_listener_with_callback(
+            "beforeunload",
+            self.listener.as_ref().unchecked_ref(),
+        );
+    }
+}
+
+/// Document/confirm_unload(when: Bool, message: Text) -> []
+/// While `when` is True, closing or leaving the page asks the user to confirm first
+/// (e.g. unsaved edits). Emits [] after every change of `when` or `message`.
+pub fn function_document_confirm_unload(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    let [argument_when, argument_message] = arguments.as_slice() else {
+        panic!("Document/confirm_unload expects 2 arguments")
+    };
+    enum Input {
+        When(bool),
+        Message(String),
+    }
+    let when_stream = argument_when.clone().stream().map(|value| match &value {
+        Value::Tag(tag, _) => Input::When(tag.tag() == "True"),
+        _ => panic!("Document/confirm_unload expects `when` to be a Tag (True/False)"),
+    });
+    let message_stream = argument_message.clone().stream().map(|value| match &value {
+        Value::Text(text, _) => Input::Message(text.text().to_string()),
+        _ => panic!("Document/confirm_unload expects a Text message"),
+    });
+    stream::select(when_stream, message_stream).scan(
+        (false, String::new(), None::<UnloadConfirmation>),
+        move |(when, message, confirmation), input| {
+            match input {
+                Input::When(value) => *when = value,
+                Input::Message(text) => *message = text,
+            }
+            // Re-registered on every change, so the listener owns the current message
+            *confirmation = None;
+            if *when {
+                *confirmation = Some(UnloadConfirmation::register(message.clone()));
+            }
+            future::ready(Some(Object::new_value(
+                ConstructInfo::new(
+                    function_call_id.with_child_id(0),
+                    None,
+                    "Document/confirm_unload result",
+                ),
+                construct_context.clone(),
+                ValueIdempotencyKey::new(),
+                [],
+            )))
+        },
+    )
+}
+
 // --- Ulid functions ---
 
 /// Ulid/generate() -> Text
@@ -3546,12 +6440,12 @@ pub fn function_log_info(
             // Resolve value with the configured timeout
             let value_str = resolve_value_for_log_with_timeout(value, options.timeout_ms).await;
             // Log with or without label
-            match options.label {
-                Some(label) if !label.is_empty() => {
-                    zoon::println!("[INFO] {}: {}", label, value_str)
-                }
-                _ => zoon::println!("[INFO] {}", value_str),
-            }
+            let message = match options.label {
+                Some(label) if !label.is_empty() => format!("{}: {}", label, value_str),
+                _ => value_str,
+            };
+            zoon::println!("[INFO] {}", message);
+            emit_log(LogLevel::Info, &message);
         }
     });
 
@@ -3621,12 +6515,12 @@ pub fn function_log_error(
             // Resolve value with the configured timeout
             let value_str = resolve_value_for_log_with_timeout(value, options.timeout_ms).await;
             // Log with or without label
-            match options.label {
-                Some(label) if !label.is_empty() => {
-                    zoon::eprintln!("[ERROR] {}: {}", label, value_str)
-                }
-                _ => zoon::eprintln!("[ERROR] {}", value_str),
-            }
+            let message = match options.label {
+                Some(label) if !label.is_empty() => format!("{}: {}", label, value_str),
+                _ => value_str,
+            };
+            zoon::eprintln!("[ERROR] {}", message);
+            emit_log(LogLevel::Error, &message);
         }
     });
 
@@ -3805,6 +6699,113 @@ pub fn function_element_text(
     )
 }
 
+/// Element/code(element?, style, language, content) -> ElementCode[element?, settings[style, language, content]]
+/// Monospace preformatted text; `language: TEXT { boon }` enables syntax highlighting.
+pub fn function_element_code(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    let (argument_element, argument_style, argument_language, argument_content) =
+        match arguments.as_slice() {
+            [element, style, language, content] => (Some(element), style, language, content),
+            [style, language, content] => (None, style, language, content),
+            _ => panic!("Element/code expects 3 or 4 arguments"),
+        };
+    let scoped_id = function_call_persistence_id;
+
+    let mut vars: Vec<Arc<Variable>> = Vec::new();
+
+    if let Some(argument_element) = argument_element {
+        vars.push(Variable::new_arc(
+            ConstructInfo::new(
+                function_call_id.with_child_id(1),
+                None,
+                "ElementCode[element]",
+            ),
+            construct_context.clone(),
+            "element",
+            argument_element.clone(),
+            scoped_id.with_child_index(1),
+            actor_context.scope.clone(),
+        ));
+    }
+
+    vars.push(Variable::new_arc(
+        ConstructInfo::new(
+            function_call_id.with_child_id(2),
+            None,
+            "ElementCode[settings]",
+        ),
+        construct_context.clone(),
+        "settings",
+        Object::new_arc_value_actor(
+            ConstructInfo::new(
+                function_call_id.with_child_id(3),
+                None,
+                "ElementCode[settings: [..]]",
+            ),
+            construct_context.clone(),
+            ValueIdempotencyKey::new(),
+            actor_context.clone(),
+            [
+                Variable::new_arc(
+                    ConstructInfo::new(
+                        function_call_id.with_child_id(4),
+                        None,
+                        "ElementCode[settings: [style]]",
+                    ),
+                    construct_context.clone(),
+                    "style",
+                    argument_style.clone(),
+                    scoped_id.with_child_index(4),
+                    actor_context.scope.clone(),
+                ),
+                Variable::new_arc(
+                    ConstructInfo::new(
+                        function_call_id.with_child_id(5),
+                        None,
+                        "ElementCode[settings: [language]]",
+                    ),
+                    construct_context.clone(),
+                    "language",
+                    argument_language.clone(),
+                    scoped_id.with_child_index(5),
+                    actor_context.scope.clone(),
+                ),
+                Variable::new_arc(
+                    ConstructInfo::new(
+                        function_call_id.with_child_id(6),
+                        None,
+                        "ElementCode[settings: [content]]",
+                    ),
+                    construct_context.clone(),
+                    "content",
+                    argument_content.clone(),
+                    scoped_id.with_child_index(6),
+                    actor_context.scope.clone(),
+                ),
+            ],
+        ),
+        scoped_id.with_child_index(2),
+        actor_context.scope,
+    ));
+
+    TaggedObject::new_constant(
+        ConstructInfo::new(
+            function_call_id.with_child_id(0),
+            None,
+            "Element/code(..) -> ElementCode[..]",
+        ),
+        construct_context,
+        ValueIdempotencyKey::new(),
+        "ElementCode",
+        vars,
+    )
+}
+
 /// Element/block(element?, style, child) -> ElementBlock[settings[element, style, child]]
 /// Generic block element with a single child.
 pub fn function_element_block(
@@ -4258,6 +7259,119 @@ pub fn function_file_write_text(
     })
 }
 
+/// File/write_with_map(path, content, map) -> Tag (Success/Failure)
+/// Writes like File/write_text and stores a line map next to the file, so diagnostics
+/// in the generated module point back at the template (see `boon::parser::source_map`).
+/// `map` is a LIST of `[line: Number, file: Text, source_line: Number]`. A malformed
+/// entry writes nothing and returns Failure.
+pub fn function_file_write_with_map(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    let [argument_path, argument_content, argument_map] = arguments.as_slice() else {
+        panic!("File/write_with_map expects 3 arguments")
+    };
+    enum Input {
+        Path(String),
+        Content(String),
+        Map(Arc<List>),
+    }
+    let path_stream = argument_path.clone().stream().map(|value| match &value {
+        Value::Text(text, _) => Input::Path(text.text().to_string()),
+        _ => panic!("File/write_with_map expects a Text path"),
+    });
+    let content_stream = argument_content.clone().stream().map(|value| match &value {
+        Value::Text(text, _) => Input::Content(text.text().to_string()),
+        _ => panic!("File/write_with_map expects Text content"),
+    });
+    let map_stream = argument_map.clone().stream().map(|value| match value {
+        Value::List(list, _) => Input::Map(list),
+        _ => panic!("File/write_with_map expects `map` to be a LIST"),
+    });
+    stream::select(stream::select(path_stream, content_stream), map_stream)
+        .scan((None, None, None), |(path, content, map), input| {
+            match input {
+                Input::Path(text) => *path = Some(text),
+                Input::Content(text) => *content = Some(text),
+                Input::Map(list) => *map = Some(list),
+            }
+            future::ready(Some(match (path, content, map) {
+                (Some(path), Some(content), Some(map)) => {
+                    Some((path.clone(), content.clone(), map.clone()))
+                }
+                _ => None,
+            }))
+        })
+        .filter_map(future::ready)
+        .then(move |(path, content, map)| {
+            let construct_context = construct_context.clone();
+            let function_call_id = function_call_id.clone();
+            async move {
+                let written = match source_map_from_list(&map).await {
+                    Some(source_map) => {
+                        let virtual_fs = &construct_context.virtual_fs;
+                        virtual_fs.write_text(&source_map_path(&path), source_map.to_text());
+                        virtual_fs.write_text(&path, content);
+                        true
+                    }
+                    None => false,
+                };
+                Tag::new_value(
+                    ConstructInfo::new(
+                        function_call_id.with_child_id(0),
+                        None,
+                        "File/write_with_map",
+                    ),
+                    construct_context.clone(),
+                    ValueIdempotencyKey::new(),
+                    if written { "Success" } else { "Failure" }.to_string(),
+                )
+            }
+        })
+}
+
+/// Reads `[line, file, source_line]` entries; `None` if any entry is malformed.
+async fn source_map_from_list(list: &List) -> Option<SourceMap> {
+    async fn field(object: &Object, name: &str) -> Option<Value> {
+        object
+            .variable(name)?
+            .value_actor()
+            .current_value()
+            .await
+            .ok()
+    }
+    async fn line_field(object: &Object, name: &str) -> Option<usize> {
+        match field(object, name).await? {
+            // Whole numbers print without a fraction, anything else fails to parse
+            Value::Number(number, _) => number
+                .number()
+                .to_string()
+                .parse()
+                .ok()
+                .filter(|line| *line >= 1),
+            _ => None,
+        }
+    }
+    let mut source_map = SourceMap::default();
+    for (_item_id, item_actor) in list.snapshot().await {
+        let Value::Object(object, _) = item_actor.current_value().await.ok()? else {
+            return None;
+        };
+        let Value::Text(file, _) = field(&object, "file").await? else {
+            return None;
+        };
+        let location = SourceLocation {
+            file: file.text().to_string(),
+            line: line_field(&object, "source_line").await?,
+        };
+        source_map.insert(line_field(&object, "line").await?, location);
+    }
+    Some(source_map)
+}
+
 // --- Stream functions ---
 
 /// Stream/skip(count) -> Stream<Value>
@@ -4547,40 +7661,177 @@ pub fn function_stream_take(
     })
 }
 
-/// Stream/distinct() -> Stream<Value>
-/// Suppresses consecutive duplicate values.
-pub fn function_stream_distinct(
-    arguments: Arc<Vec<ActorHandle>>,
-    _function_call_id: ConstructId,
-    _function_call_persistence_id: PersistenceId,
-    _construct_context: ConstructContext,
-    _actor_context: ActorContext,
-) -> impl Stream<Item = Value> {
-    let stream_actor = arguments[0].clone();
-
-    stream_actor
-        .stream()
-        .scan(None::<ValueIdempotencyKey>, move |last_key, value| {
-            let current_key = value.idempotency_key();
-            let should_emit = last_key.map_or(true, |k| k != current_key);
-            *last_key = Some(current_key);
-            if should_emit {
-                future::ready(Some(Some(value)))
-            } else {
-                future::ready(Some(None))
-            }
-        })
-        .filter_map(future::ready)
-}
-
-/// Stream/pulses() -> Stream<Number>
+/// Stream/distinct() -> Stream<Value>
+/// Suppresses consecutive duplicate values.
+pub fn function_stream_distinct(
+    arguments: Arc<Vec<ActorHandle>>,
+    _function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    _construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    let stream_actor = arguments[0].clone();
+
+    stream_actor
+        .stream()
+        .scan(None::<ValueIdempotencyKey>, move |last_key, value| {
+            let current_key = value.idempotency_key();
+            let should_emit = last_key.map_or(true, |k| k != current_key);
+            *last_key = Some(current_key);
+            if should_emit {
+                future::ready(Some(Some(value)))
+            } else {
+                future::ready(Some(None))
+            }
+        })
+        .filter_map(future::ready)
+}
+
+/// Stream/min() -> Stream<Number>
+/// The lowest Number seen so far, emitted only when a new low arrives.
+pub fn function_stream_min(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    stream_extreme(
+        arguments,
+        function_call_id,
+        function_call_persistence_id,
+        construct_context,
+        "Stream/min",
+        |number, extreme| number < extreme,
+    )
+}
+
+/// Stream/max() -> Stream<Number>
+/// The highest Number seen so far, emitted only when a new high arrives.
+pub fn function_stream_max(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    stream_extreme(
+        arguments,
+        function_call_id,
+        function_call_persistence_id,
+        construct_context,
+        "Stream/max",
+        |number, extreme| number > extreme,
+    )
+}
+
+/// Shared body of Stream/min and Stream/max. The extreme is persisted like the sum of
+/// Math/sum, so a restored run keeps its record. A value that is not a Number is
+/// skipped and reported once per call.
+fn stream_extreme(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    function_name: &'static str,
+    replaces: fn(f64, f64) -> bool,
+) -> impl Stream<Item = Value> {
+    #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+    #[serde(crate = "serde")]
+    struct State {
+        input_value_idempotency_key: Option<ValueIdempotencyKey>,
+        extreme: Option<f64>,
+        output_value_idempotency_key: Option<ValueIdempotencyKey>,
+    }
+    enum Input {
+        Restored(State),
+        Value(ValueIdempotencyKey, Option<f64>),
+    }
+
+    let [argument_value] = arguments.as_slice() else {
+        panic!("{function_name} expects 1 argument")
+    };
+    let argument_value = argument_value.clone();
+    let storage = construct_context.construct_storage.clone();
+
+    stream::once({
+        let storage = storage.clone();
+        async move {
+            let loaded: Option<State> = storage.load_state(function_call_persistence_id).await;
+            loaded
+        }
+    })
+    .filter_map(future::ready)
+    .map(Input::Restored)
+    .chain(
+        stream::once(async move {
+            argument_value.stream().map(|value| {
+                let number = match &value {
+                    Value::Number(n, _) => Some(n.number()),
+                    _ => None,
+                };
+                Input::Value(value.idempotency_key(), number)
+            })
+        })
+        .flatten(),
+    )
+    .scan(
+        (State::default(), false),
+        move |(state, reported_non_number), input| {
+            match input {
+                Input::Restored(restored) => *state = restored,
+                Input::Value(key, number) => {
+                    if state.input_value_idempotency_key == Some(key) {
+                        return future::ready(Some(None));
+                    }
+                    state.input_value_idempotency_key = Some(key);
+                    let Some(number) = number else {
+                        report_non_number_once(reported_non_number, function_name);
+                        return future::ready(Some(None));
+                    };
+                    if state
+                        .extreme
+                        .is_some_and(|extreme| !replaces(number, extreme))
+                    {
+                        return future::ready(Some(None));
+                    }
+                    state.extreme = Some(number);
+                    state.output_value_idempotency_key = Some(ValueIdempotencyKey::new());
+                    storage.save_state(function_call_persistence_id, &*state);
+                }
+            }
+            future::ready(Some(state.extreme.zip(state.output_value_idempotency_key)))
+        },
+    )
+    .filter_map(future::ready)
+    .map({
+        let mut result_version = 0u64;
+        move |(extreme, idempotency_key)| {
+            let value = Number::new_value(
+                ConstructInfo::new(
+                    function_call_id
+                        .with_child_id(format!("{function_name} result v.{result_version}")),
+                    None,
+                    format!("{function_name}() -> Number"),
+                ),
+                construct_context.clone(),
+                idempotency_key,
+                extreme,
+            );
+            result_version += 1;
+            value
+        }
+    })
+}
+
+/// Stream/sequence() -> Stream<Number>
 /// Generates N pulses (1, 2, 3, ..., N) from the piped count.
 /// When the count changes, restarts pulse generation from 1.
 ///
 /// Uses pure stream combinators (no Task, no Rc<RefCell>) per Engine Architecture Rules.
-/// Initial pulses are emitted synchronously via stream::iter() to ensure HOLD + Stream/pulses
+/// Initial pulses are emitted synchronously via stream::iter() to ensure HOLD + Stream/sequence
 /// patterns work correctly without race conditions.
-pub fn function_stream_pulses(
+pub fn function_stream_sequence(
     arguments: Arc<Vec<ActorHandle>>,
     function_call_id: ConstructId,
     _function_call_persistence_id: PersistenceId,
@@ -4604,7 +7855,7 @@ pub fn function_stream_pulses(
                         ConstructInfo::new(
                             function_call_id.with_child_id(format!("pulse_{}", n)),
                             None,
-                            "Stream/pulses result",
+                            "Stream/sequence result",
                         ),
                         construct_context.clone(),
                         ValueIdempotencyKey::new(),
@@ -4755,6 +8006,209 @@ pub fn function_stream_debounce(
     })
 }
 
+/// Stream/sample(on) -> Stream<Value>
+/// Emits the latest piped value each time `on` fires.
+/// A trigger that fires before the piped stream has any value emits nothing.
+/// When the piped stream ends, its last value stays available to later triggers.
+/// Each emission carries the trigger's identity, so sampling the same value twice
+/// still counts as two emissions downstream.
+///
+/// # Implementation
+/// Uses `stream::unfold()` for a pure demand-driven stream (no Task spawn).
+/// `select_biased!` takes ready piped values before a ready trigger, so a trigger
+/// never samples a value older than one enqueued ahead of it.
+pub fn function_stream_sample(
+    arguments: Arc<Vec<ActorHandle>>,
+    _function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    _construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    let stream_actor = arguments[0].clone();
+    let trigger_actor = arguments[1].clone();
+
+    // State type for unfold
+    type FusedSub = stream::Fuse<LocalBoxStream<'static, Value>>;
+    type SampleState = (
+        Option<FusedSub>,
+        Option<FusedSub>,
+        ActorHandle,
+        ActorHandle,
+        Option<Value>,
+    );
+
+    let initial_state: SampleState = (
+        None, // stream_sub - deferred
+        None, // trigger_sub - deferred
+        stream_actor,
+        trigger_actor,
+        None, // latest piped value
+    );
+
+    stream::unfold(initial_state, |state| async move {
+        let (stream_sub_opt, trigger_sub_opt, stream_actor, trigger_actor, mut latest) = state;
+
+        // Subscribe on first iteration
+        let mut stream_sub = match stream_sub_opt {
+            Some(s) => s,
+            None => stream_actor.clone().stream().boxed_local().fuse(),
+        };
+        let mut trigger_sub = match trigger_sub_opt {
+            Some(s) => s,
+            None => trigger_actor.clone().stream().boxed_local().fuse(),
+        };
+
+        loop {
+            select_biased! {
+                stream_value = stream_sub.next() => {
+                    // An ended piped stream is skipped from now on, `latest` stays
+                    if let Some(value) = stream_value {
+                        latest = Some(value);
+                    }
+                }
+                trigger_value = trigger_sub.next() => {
+                    let Some(trigger) = trigger_value else {
+                        return None; // Trigger ended
+                    };
+                    if let Some(mut value) = latest.clone() {
+                        value.set_idempotency_key(trigger.idempotency_key());
+                        value.metadata_mut().lamport_time = trigger.lamport_time();
+                        return Some((value, (Some(stream_sub), Some(trigger_sub), stream_actor, trigger_actor, latest)));
+                    }
+                    // No piped value yet - nothing to sample
+                }
+            }
+        }
+    })
+}
+
+/// Stream/gate(open) -> Stream<Value>
+/// Lets piped values through while `open` is True and drops them while it is False.
+/// Piped values that arrive before the first `open` value wait for it.
+/// Closing the gate does not drop values created before the close (by Lamport time):
+/// events already enqueued when `open` turns False in the same tick still deliver.
+/// When `open` ends, the gate keeps its last state.
+///
+/// # Implementation
+/// Uses `stream::unfold()` for a pure demand-driven stream (no Task spawn).
+pub fn function_stream_gate(
+    arguments: Arc<Vec<ActorHandle>>,
+    _function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    _construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    let stream_actor = arguments[0].clone();
+    let open_actor = arguments[1].clone();
+
+    fn is_open(value: &Value) -> bool {
+        match value {
+            Value::Tag(tag, _) => tag.tag() == "True",
+            _ => panic!("Stream/gate expects `open` to be a Tag (True/False)"),
+        }
+    }
+
+    /// Whether `value` gets through a gate in state `open` that last closed at `closed_at`.
+    fn passes(value: &Value, open: bool, closed_at: Option<u64>) -> bool {
+        open || closed_at.is_some_and(|closed_at| value.lamport_time() < closed_at)
+    }
+
+    // State type for unfold
+    type FusedSub = stream::Fuse<LocalBoxStream<'static, Value>>;
+    type GateState = (
+        Option<FusedSub>,
+        Option<FusedSub>,
+        ActorHandle,
+        ActorHandle,
+        Option<bool>,
+        Option<u64>,
+        Vec<Value>,
+    );
+
+    let initial_state: GateState = (
+        None, // stream_sub - deferred
+        None, // open_sub - deferred
+        stream_actor,
+        open_actor,
+        None,       // open - unknown until the first `open` value
+        None,       // closed_at - Lamport time of the last open -> closed change
+        Vec::new(), // buffered_values
+    );
+
+    stream::unfold(initial_state, |state| async move {
+        let (
+            stream_sub_opt,
+            open_sub_opt,
+            stream_actor,
+            open_actor,
+            mut open,
+            mut closed_at,
+            mut buffer,
+        ) = state;
+
+        // Subscribe on first iteration
+        let mut stream_sub = match stream_sub_opt {
+            Some(s) => s,
+            None => stream_actor.clone().stream().boxed_local().fuse(),
+        };
+        let mut open_sub = match open_sub_opt {
+            Some(s) => s,
+            None => open_actor.clone().stream().boxed_local().fuse(),
+        };
+
+        loop {
+            // Once `open` is known, judge values that were waiting for it
+            if open.is_some() && !buffer.is_empty() {
+                let buffered = buffer.remove(0);
+                if passes(&buffered, open == Some(true), closed_at) {
+                    return Some((
+                        buffered,
+                        (
+                            Some(stream_sub),
+                            Some(open_sub),
+                            stream_actor,
+                            open_actor,
+                            open,
+                            closed_at,
+                            buffer,
+                        ),
+                    ));
+                }
+                continue;
+            }
+
+            // Piped values first, so a value enqueued before a gate change is judged by it
+            select_biased! {
+                stream_value = stream_sub.next() => {
+                    let Some(value) = stream_value else {
+                        return None; // Stream ended
+                    };
+                    match open {
+                        None => buffer.push(value),
+                        Some(is_open_now) => {
+                            if passes(&value, is_open_now, closed_at) {
+                                return Some((value, (Some(stream_sub), Some(open_sub), stream_actor, open_actor, open, closed_at, buffer)));
+                            }
+                        }
+                    }
+                }
+                open_value = open_sub.next() => {
+                    // An ended `open` is skipped from now on, the gate keeps its state
+                    if let Some(value) = open_value {
+                        let opens = is_open(&value);
+                        if open == Some(true) && !opens {
+                            closed_at = Some(value.lamport_time());
+                        } else if opens {
+                            closed_at = None;
+                        }
+                        open = Some(opens);
+                    }
+                }
+            }
+        }
+    })
+}
+
 // --- Directory functions ---
 
 /// Directory/entries(path) -> List<Text>
@@ -5040,71 +8494,281 @@ pub fn function_text_find_closing(
             }
         },
     )
-    .filter_map(future::ready)
+    .filter_map(future::ready)
+}
+
+/// Text/substring(text, start, length) -> Text
+/// Returns a substring starting at char index `start` with `length` characters
+pub fn function_text_substring(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    let [argument_text, argument_start, argument_length] = arguments.as_slice() else {
+        panic!("Text/substring expects 3 arguments")
+    };
+    enum Input {
+        Text(String),
+        Start(f64),
+        Length(f64),
+    }
+    let text_stream = argument_text.clone().stream().map(|v| match &v {
+        Value::Text(t, _) => Input::Text(t.text().to_string()),
+        _ => panic!("Text/substring expects Text for first argument"),
+    });
+    let start_stream = argument_start.clone().stream().map(|v| match &v {
+        Value::Number(n, _) => Input::Start(n.number()),
+        _ => panic!("Text/substring expects Number for start argument"),
+    });
+    let length_stream = argument_length.clone().stream().map(|v| match &v {
+        Value::Number(n, _) => Input::Length(n.number()),
+        _ => panic!("Text/substring expects Number for length argument"),
+    });
+    stream::select(text_stream, stream::select(start_stream, length_stream))
+        .scan(
+            (None::<String>, None::<f64>, None::<f64>),
+            move |(last_text, last_start, last_length), input| {
+                match input {
+                    Input::Text(t) => *last_text = Some(t),
+                    Input::Start(s) => *last_start = Some(s),
+                    Input::Length(l) => *last_length = Some(l),
+                }
+                if let (Some(text), Some(start), Some(length)) =
+                    (last_text.as_ref(), *last_start, *last_length)
+                {
+                    let start_idx = (start as isize).max(0) as usize;
+                    let len = (length as isize).max(0) as usize;
+                    let result: String = text.chars().skip(start_idx).take(len).collect();
+                    future::ready(Some(Some(Text::new_value(
+                        ConstructInfo::new(
+                            function_call_id.with_child_id(0),
+                            None,
+                            "Text/substring result",
+                        ),
+                        construct_context.clone(),
+                        ValueIdempotencyKey::new(),
+                        result,
+                    ))))
+                } else {
+                    future::ready(Some(None))
+                }
+            },
+        )
+        .filter_map(future::ready)
+}
+
+/// Text/slice(text, start, end) -> Text
+/// Characters from position `start` to `end`, both 1-based and inclusive
+pub fn function_text_slice(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    let [argument_text, argument_start, argument_end] = arguments.as_slice() else {
+        panic!("Text/slice expects 3 arguments")
+    };
+    enum Input {
+        Text(String),
+        Start(f64),
+        End(f64),
+    }
+    let text_stream = argument_text.clone().stream().map(|v| match &v {
+        Value::Text(t, _) => Input::Text(t.text().to_string()),
+        _ => panic!("Text/slice expects Text for first argument"),
+    });
+    let start_stream = argument_start.clone().stream().map(|v| match &v {
+        Value::Number(n, _) => Input::Start(n.number()),
+        _ => panic!("Text/slice expects Number for start argument"),
+    });
+    let end_stream = argument_end.clone().stream().map(|v| match &v {
+        Value::Number(n, _) => Input::End(n.number()),
+        _ => panic!("Text/slice expects Number for end argument"),
+    });
+    stream::select(text_stream, stream::select(start_stream, end_stream))
+        .scan(
+            (None::<String>, None::<f64>, None::<f64>),
+            move |(last_text, last_start, last_end), input| {
+                match input {
+                    Input::Text(t) => *last_text = Some(t),
+                    Input::Start(s) => *last_start = Some(s),
+                    Input::End(e) => *last_end = Some(e),
+                }
+                let (Some(text), Some(start), Some(end)) =
+                    (last_text.as_ref(), *last_start, *last_end)
+                else {
+                    return future::ready(Some(None));
+                };
+                future::ready(Some(Some(Text::new_value(
+                    ConstructInfo::new(
+                        function_call_id.with_child_id(0),
+                        None,
+                        "Text/slice result",
+                    ),
+                    construct_context.clone(),
+                    ValueIdempotencyKey::new(),
+                    text_slice(text, start, end),
+                ))))
+            },
+        )
+        .filter_map(future::ready)
+}
+
+/// Characters of `text` whose 1-based position lies in `start..=end`. Works on chars,
+/// so it never splits a multi-byte character; bounds past either end clamp, and
+/// `end < start` (or NaN) gives the empty Text.
+fn text_slice(text: &str, start: f64, end: f64) -> String {
+    text.chars()
+        .zip(1_u32..)
+        .filter(|(_, position)| {
+            let position = f64::from(*position);
+            start <= position && position <= end
+        })
+        .map(|(character, _)| character)
+        .collect()
+}
+
+/// Text/pad_start(text, length, with) -> Text
+/// Repeats `with` in front of `text` until it is `length` characters long
+pub fn function_text_pad_start(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    text_padding(
+        arguments,
+        function_call_id,
+        construct_context,
+        "Text/pad_start",
+        |text, padding| format!("{padding}{text}"),
+    )
 }
 
-/// Text/substring(text, start, length) -> Text
-/// Returns a substring starting at char index `start` with `length` characters
-pub fn function_text_substring(
+/// Text/pad_end(text, length, with) -> Text
+/// Repeats `with` after `text` until it is `length` characters long
+pub fn function_text_pad_end(
     arguments: Arc<Vec<ActorHandle>>,
     function_call_id: ConstructId,
     _function_call_persistence_id: PersistenceId,
     construct_context: ConstructContext,
     _actor_context: ActorContext,
 ) -> impl Stream<Item = Value> {
-    let [argument_text, argument_start, argument_length] = arguments.as_slice() else {
-        panic!("Text/substring expects 3 arguments")
+    text_padding(
+        arguments,
+        function_call_id,
+        construct_context,
+        "Text/pad_end",
+        |text, padding| format!("{text}{padding}"),
+    )
+}
+
+/// Shared body of the paddings. A Number piped in is padded the way
+/// `Number/to_text` prints it with all its decimals, so `minutes |> Text/pad_start(..)`
+/// works directly. The padding stops at the soft Text size limit.
+fn text_padding(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    construct_context: ConstructContext,
+    function_name: &'static str,
+    join: fn(&str, &str) -> String,
+) -> impl Stream<Item = Value> {
+    let [argument_text, argument_length, argument_with] = arguments.as_slice() else {
+        panic!("{function_name} expects 3 arguments")
     };
     enum Input {
         Text(String),
-        Start(f64),
         Length(f64),
+        With(String),
     }
-    let text_stream = argument_text.clone().stream().map(|v| match &v {
+    let text_stream = argument_text.clone().stream().map(move |v| match &v {
         Value::Text(t, _) => Input::Text(t.text().to_string()),
-        _ => panic!("Text/substring expects Text for first argument"),
-    });
-    let start_stream = argument_start.clone().stream().map(|v| match &v {
-        Value::Number(n, _) => Input::Start(n.number()),
-        _ => panic!("Text/substring expects Number for start argument"),
+        Value::Number(n, _) => {
+            let number = n.number();
+            let decimals = number
+                .to_string()
+                .split_once('.')
+                .map_or(0, |(_, fraction)| fraction.len());
+            let decimals = f64::from(u32::try_from(decimals).unwrap_or(u32::MAX));
+            Input::Text(format_number(number, decimals, ""))
+        }
+        _ => panic!("{function_name} expects Text or Number for first argument"),
     });
-    let length_stream = argument_length.clone().stream().map(|v| match &v {
+    let length_stream = argument_length.clone().stream().map(move |v| match &v {
         Value::Number(n, _) => Input::Length(n.number()),
-        _ => panic!("Text/substring expects Number for length argument"),
+        _ => panic!("{function_name} expects Number for length argument"),
     });
-    stream::select(text_stream, stream::select(start_stream, length_stream))
+    let with_stream = argument_with.clone().stream().map(move |v| match &v {
+        Value::Text(t, _) => Input::With(t.text().to_string()),
+        _ => panic!("{function_name} expects Text for with argument"),
+    });
+    stream::select(text_stream, stream::select(length_stream, with_stream))
         .scan(
-            (None::<String>, None::<f64>, None::<f64>),
-            move |(last_text, last_start, last_length), input| {
+            (None::<String>, None::<f64>, None::<String>, false),
+            move |(last_text, last_length, last_with, reported_oversized), input| {
                 match input {
                     Input::Text(t) => *last_text = Some(t),
-                    Input::Start(s) => *last_start = Some(s),
                     Input::Length(l) => *last_length = Some(l),
+                    Input::With(w) => *last_with = Some(w),
                 }
-                if let (Some(text), Some(start), Some(length)) =
-                    (last_text.as_ref(), *last_start, *last_length)
-                {
-                    let start_idx = (start as isize).max(0) as usize;
-                    let len = (length as isize).max(0) as usize;
-                    let result: String = text.chars().skip(start_idx).take(len).collect();
-                    future::ready(Some(Some(Text::new_value(
-                        ConstructInfo::new(
-                            function_call_id.with_child_id(0),
-                            None,
-                            "Text/substring result",
+                let (Some(text), Some(length), Some(with)) =
+                    (last_text.as_ref(), *last_length, last_with.as_ref())
+                else {
+                    return future::ready(Some(None));
+                };
+                let padded = match text_padding_fill(text, length, with) {
+                    Some(padding) => join(text, &padding),
+                    None => text.clone(),
+                };
+                let padded_length = u32::try_from(padded.chars().count()).unwrap_or(u32::MAX);
+                if !with.is_empty() && f64::from(padded_length) < length && !*reported_oversized {
+                    *reported_oversized = true;
+                    emit_warning(
+                        LogLevel::Warn,
+                        "text-size",
+                        format!(
+                            "{function_name} length {length} is above the {} byte Text limit; \
+                             the padding is cut there",
+                            max_text_bytes()
                         ),
-                        construct_context.clone(),
-                        ValueIdempotencyKey::new(),
-                        result,
-                    ))))
-                } else {
-                    future::ready(Some(None))
+                    );
                 }
+                future::ready(Some(Some(Text::new_value(
+                    ConstructInfo::new(
+                        function_call_id.with_child_id(0),
+                        None,
+                        format!("{function_name} result"),
+                    ),
+                    construct_context.clone(),
+                    ValueIdempotencyKey::new(),
+                    padded,
+                ))))
             },
         )
         .filter_map(future::ready)
 }
 
+/// The characters `text` is missing to reach `length`, taken from `with` repeated
+/// (and cut off mid-`with` when it does not fit evenly). None when `with` is empty
+/// or `text` is already long enough, so the text passes through unchanged. The
+/// padding never grows past the soft Text size limit, however large `length` is.
+fn text_padding_fill(text: &str, length: f64, with: &str) -> Option<String> {
+    let max_bytes = max_text_bytes();
+    let text_length = u32::try_from(text.chars().count()).unwrap_or(u32::MAX);
+    let mut padding = String::new();
+    for (character, position) in with.chars().cycle().zip(text_length..) {
+        if f64::from(position) >= length || padding.len() + character.len_utf8() > max_bytes {
+            break;
+        }
+        padding.push(character);
+    }
+    (!padding.is_empty()).then_some(padding)
+}
+
 /// Text/to_uppercase(text) -> Text
 pub fn function_text_to_uppercase(
     arguments: Arc<Vec<ActorHandle>>,
@@ -5112,24 +8776,67 @@ pub fn function_text_to_uppercase(
     _function_call_persistence_id: PersistenceId,
     construct_context: ConstructContext,
     _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    text_case_conversion(
+        arguments,
+        function_call_id,
+        construct_context,
+        "Text/to_uppercase",
+        str::to_uppercase,
+    )
+}
+
+/// Text/to_lowercase(text) -> Text
+pub fn function_text_to_lowercase(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    text_case_conversion(
+        arguments,
+        function_call_id,
+        construct_context,
+        "Text/to_lowercase",
+        str::to_lowercase,
+    )
+}
+
+/// Shared body of the case conversions. Values that are not Text pass through
+/// unchanged, reported once per call instead of turning into Unit.
+fn text_case_conversion(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    construct_context: ConstructContext,
+    function_name: &'static str,
+    convert: fn(&str) -> String,
 ) -> impl Stream<Item = Value> {
     let [argument_text] = arguments.as_slice() else {
-        panic!("Text/to_uppercase expects 1 argument")
+        panic!("{function_name} expects 1 argument")
     };
+    let mut reported_non_text = false;
     argument_text.clone().stream().map(move |value| {
-        let text = match &value {
-            Value::Text(t, _) => t.text(),
-            _ => panic!("Text/to_uppercase expects a Text value"),
+        let Value::Text(text, _) = &value else {
+            if !reported_non_text {
+                reported_non_text = true;
+                emit_warning(
+                    LogLevel::Warn,
+                    "type",
+                    format!("{function_name} expects Text; passing the value through unchanged"),
+                );
+            }
+            return value;
         };
         Text::new_value(
             ConstructInfo::new(
                 function_call_id.with_child_id(0),
                 None,
-                "Text/to_uppercase result",
+                format!("{function_name} result"),
             ),
             construct_context.clone(),
             ValueIdempotencyKey::new(),
-            text.to_uppercase(),
+            convert(text.text()),
         )
     })
 }
@@ -5201,12 +8908,13 @@ pub fn function_text_from_char_code(
 /// List/get(list, index) -> Value
 /// Returns the item at the given 1-based index in the list.
 /// Index 1 = first element, index 2 = second, etc.
-/// Re-emits whenever the item at that index changes.
+/// Re-emits whenever the item at that index changes. An index that selects no item
+/// gives the tag `None`.
 pub fn function_list_get(
     arguments: Arc<Vec<ActorHandle>>,
     function_call_id: ConstructId,
     _function_call_persistence_id: PersistenceId,
-    _construct_context: ConstructContext,
+    construct_context: ConstructContext,
     _actor_context: ActorContext,
 ) -> impl Stream<Item = Value> {
     let [argument_list, argument_index] = arguments.as_slice() else {
@@ -5233,7 +8941,7 @@ pub fn function_list_get(
     });
 
     // When list or index changes, get item at index and subscribe to its values
-    let item_stream = stream::select(list_stream, index_stream)
+    let list_and_index = stream::select(list_stream, index_stream)
         .scan(
             (None::<Arc<List>>, None::<f64>),
             move |(last_list, last_index), input| {
@@ -5251,46 +8959,23 @@ pub fn function_list_get(
             })
         });
 
-    let construct_context_for_oob = _construct_context.clone();
-    switch_map(item_stream, move |(list, index)| {
-        // Convert 1-based Boon index to 0-based Rust index
-        let idx = if index >= 1.0 {
-            (index as usize) - 1
-        } else {
-            usize::MAX
-        };
-        let function_call_id = function_call_id.clone();
-        let construct_context = construct_context_for_oob.clone();
+    let selected_item = switch_map(list_and_index, |(list, index)| {
         list.stream()
             .scan(Vec::<ActorHandle>::new(), move |items, change| {
                 change.apply_to_vec(items);
-                future::ready(Some(items.get(idx).cloned()))
-            })
-            .flat_map(move |item_opt| -> LocalBoxStream<'static, Value> {
-                match item_opt {
-                    Some(actor) => {
-                        let actor_for_initial = actor.clone();
-                        Box::pin(
-                            stream::once(
-                                async move { actor_for_initial.current_value().await.ok() },
-                            )
-                            .filter_map(future::ready)
-                            .chain(actor.stream_from_now()),
-                        )
-                    }
-                    None => Box::pin(stream::once(future::ready(Tag::new_value(
-                        ConstructInfo::new(
-                            function_call_id.with_child_id(0),
-                            None,
-                            "List/get OutOfBounds",
-                        ),
-                        construct_context.clone(),
-                        ValueIdempotencyKey::new(),
-                        "OutOfBounds".to_string(),
-                    )))),
-                }
+                // 1-based; zero, negative, fractional and past-end indices select nothing
+                let item = items.iter().zip(1u32..).find_map(|(item, position)| {
+                    (f64::from(position) == index).then(|| item.clone())
+                });
+                future::ready(Some(item))
             })
-    })
+    });
+    follow_selected_item(
+        selected_item,
+        function_call_id,
+        construct_context,
+        "List/get None",
+    )
 }
 
 /// List/range(from, to) -> List {from, from+1, ..., to}
@@ -5377,7 +9062,8 @@ pub fn function_list_range(
 
 /// List/sum(list) -> Number
 /// Returns the sum of all Number items in the list. Empty list → 0.
-/// Re-emits whenever any item value changes.
+/// Re-emits whenever membership or any item value changes; items that are not
+/// Numbers are skipped with a warning.
 pub fn function_list_sum(
     arguments: Arc<Vec<ActorHandle>>,
     function_call_id: ConstructId,
@@ -5385,100 +9071,93 @@ pub fn function_list_sum(
     construct_context: ConstructContext,
     _actor_context: ActorContext,
 ) -> impl Stream<Item = Value> {
-    let list_actor = arguments[0].clone();
-    list_actor
-        .stream()
-        .filter_map(|value| {
-            future::ready(match value {
-                Value::List(list, _) => Some(list),
-                _ => None,
-            })
-        })
-        .flat_map(move |list| {
-            let construct_context = construct_context.clone();
-            let function_call_id = function_call_id.clone();
-            list.stream()
-                .scan(Vec::<ActorHandle>::new(), move |items, change| {
-                    change.apply_to_vec(items);
-                    future::ready(Some(items.clone()))
-                })
-                .flat_map(move |items| {
-                    let construct_context = construct_context.clone();
-                    let function_call_id = function_call_id.clone();
-                    if items.is_empty() {
-                        return stream::once(future::ready(Number::new_value(
-                            ConstructInfo::new(
-                                function_call_id.with_child_id(0),
-                                None,
-                                "List/sum empty",
-                            ),
-                            construct_context,
-                            ValueIdempotencyKey::new(),
-                            0.0,
-                        )))
-                        .left_stream();
+    list_aggregate(
+        arguments[0].clone(),
+        function_call_id,
+        construct_context,
+        "List/sum",
+        |numbers| Some(numbers.iter().sum()),
+    )
+}
+
+/// List/min(list) -> Number | None
+/// The smallest Number item, or the tag `None` when there is none.
+/// Re-emits whenever membership or any item value changes.
+pub fn function_list_min(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    list_aggregate(
+        arguments[0].clone(),
+        function_call_id,
+        construct_context,
+        "List/min",
+        |numbers| numbers.iter().copied().reduce(f64::min),
+    )
+}
+
+/// List/max(list) -> Number | None
+/// The largest Number item, or the tag `None` when there is none.
+/// Re-emits whenever membership or any item value changes.
+pub fn function_list_max(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    list_aggregate(
+        arguments[0].clone(),
+        function_call_id,
+        construct_context,
+        "List/max",
+        |numbers| numbers.iter().copied().reduce(f64::max),
+    )
+}
+
+/// Shared body of List/sum, List/min and List/max: `aggregate` sees the Number items
+/// in order; `None` from it emits the tag `None`. Emits only when the result changes.
+fn list_aggregate(
+    list_actor: ActorHandle,
+    function_call_id: ConstructId,
+    construct_context: ConstructContext,
+    function_name: &'static str,
+    aggregate: fn(&[f64]) -> Option<f64>,
+) -> impl Stream<Item = Value> {
+    list_item_values(list_actor)
+        .scan(
+            (false, None),
+            move |(reported_non_number, last_result), values| {
+                let mut numbers = Vec::with_capacity(values.len());
+                for value in &values {
+                    match value {
+                        Value::Number(number, _) => numbers.push(number.number()),
+                        _ => report_non_number_once(reported_non_number, function_name),
                     }
-                    let items_for_initial = items.clone();
-                    let items_for_updates = items.clone();
-                    stream::once(async move {
-                        join_all(
-                            items_for_initial
-                                .into_iter()
-                                .map(|item| async move { item.value().await.ok() }),
-                        )
-                        .await
-                    })
-                    .flat_map(move |initial_values| {
-                        let construct_context = construct_context.clone();
-                        let function_call_id = function_call_id.clone();
-                        let streams: Vec<_> = items_for_updates
-                            .iter()
-                            .enumerate()
-                            .map(|(i, item)| {
-                                Box::pin(item.clone().stream_from_now().map(move |v| (i, v)))
-                                    as Pin<Box<dyn Stream<Item = (usize, Value)>>>
-                            })
-                            .collect();
-                        let values: Vec<f64> = initial_values
-                            .into_iter()
-                            .map(|value| match value {
-                                Some(Value::Number(number, _)) => number.number(),
-                                _ => 0.0,
-                            })
-                            .collect();
-                        let initial_sum: f64 = values.iter().sum();
-                        stream::once(future::ready(Number::new_value(
-                            ConstructInfo::new(
-                                function_call_id.with_child_id(0),
-                                None,
-                                "List/sum result",
-                            ),
-                            construct_context.clone(),
-                            ValueIdempotencyKey::new(),
-                            initial_sum,
-                        )))
-                        .chain(stream::select_all(streams).scan(
-                            values,
-                            move |values, (i, value)| {
-                                if let Value::Number(n, _) = &value {
-                                    values[i] = n.number();
-                                }
-                                let sum: f64 = values.iter().sum();
-                                future::ready(Some(Number::new_value(
-                                    ConstructInfo::new(
-                                        function_call_id.with_child_id(0),
-                                        None,
-                                        "List/sum result",
-                                    ),
-                                    construct_context.clone(),
-                                    ValueIdempotencyKey::new(),
-                                    sum,
-                                )))
-                            },
-                        ))
-                    })
-                    .right_stream()
-                })
+                }
+                let result = aggregate(&numbers);
+                let changed = *last_result != Some(result);
+                *last_result = Some(result);
+                future::ready(Some(changed.then_some(result)))
+            },
+        )
+        .filter_map(future::ready)
+        .map(move |result| match result {
+            Some(number) => Number::new_value(
+                ConstructInfo::new(function_call_id.with_child_id(0), None, function_name),
+                construct_context.clone(),
+                ValueIdempotencyKey::new(),
+                number,
+            ),
+            None => Tag::new_value(
+                ConstructInfo::new(function_call_id.with_child_id(1), None, function_name),
+                construct_context.clone(),
+                ValueIdempotencyKey::new(),
+                "None".to_string(),
+            ),
         })
 }
 
@@ -5555,8 +9234,9 @@ pub fn function_list_product(
 
 // --- Math functions (Cells spreadsheet) ---
 
-/// Math/modulo(a, divisor) -> Number
-/// Returns a % divisor (remainder)
+/// Math/modulo(a, by) -> Number | MathError
+/// Floored remainder: it takes the sign of `by`, so `-1 |> Math/modulo(by: 2)` is 1.
+/// `divisor:` is still accepted in place of `by:`
 pub fn function_math_modulo(
     arguments: Arc<Vec<ActorHandle>>,
     function_call_id: ConstructId,
@@ -5564,44 +9244,200 @@ pub fn function_math_modulo(
     construct_context: ConstructContext,
     _actor_context: ActorContext,
 ) -> impl Stream<Item = Value> {
-    let [argument_a, argument_divisor] = arguments.as_slice() else {
-        panic!("Math/modulo expects 2 arguments")
+    math_division(
+        arguments,
+        function_call_id,
+        construct_context,
+        "Math/modulo",
+        floored_modulo,
+    )
+}
+
+/// Math/divide_int(a, by) -> Number | MathError
+/// Floored division, so `-7 |> Math/divide_int(by: 2)` is -4
+pub fn function_math_divide_int(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    _function_call_persistence_id: PersistenceId,
+    construct_context: ConstructContext,
+    _actor_context: ActorContext,
+) -> impl Stream<Item = Value> {
+    math_division(
+        arguments,
+        function_call_id,
+        construct_context,
+        "Math/divide_int",
+        floored_division,
+    )
+}
+
+/// Shared body of Math/modulo and Math/divide_int. Dividing by zero gives the
+/// `MathError` tag instead of NaN.
+fn math_division(
+    arguments: Arc<Vec<ActorHandle>>,
+    function_call_id: ConstructId,
+    construct_context: ConstructContext,
+    function_name: &'static str,
+    divide: fn(f64, f64) -> f64,
+) -> impl Stream<Item = Value> {
+    let [argument_a, argument_by] = arguments.as_slice() else {
+        panic!("{function_name} expects 2 arguments")
     };
     enum Input {
         A(f64),
-        Divisor(f64),
+        By(f64),
     }
-    let a_stream = argument_a.clone().stream().map(|v| match &v {
+    let a_stream = argument_a.clone().stream().map(move |v| match &v {
         Value::Number(n, _) => Input::A(n.number()),
-        _ => panic!("Math/modulo expects Number arguments"),
+        _ => panic!("{function_name} expects Number arguments"),
     });
-    let divisor_stream = argument_divisor.clone().stream().map(|v| match &v {
-        Value::Number(n, _) => Input::Divisor(n.number()),
-        _ => panic!("Math/modulo expects Number arguments"),
+    let by_stream = argument_by.clone().stream().map(move |v| match &v {
+        Value::Number(n, _) => Input::By(n.number()),
+        _ => panic!("{function_name} expects Number arguments"),
     });
-    stream::select(a_stream, divisor_stream)
+    stream::select(a_stream, by_stream)
         .scan(
             (None::<f64>, None::<f64>),
-            move |(last_a, last_divisor), input| {
+            move |(last_a, last_by), input| {
                 match input {
                     Input::A(val) => *last_a = Some(val),
-                    Input::Divisor(val) => *last_divisor = Some(val),
-                }
-                if let (Some(a), Some(divisor)) = (*last_a, *last_divisor) {
-                    future::ready(Some(Some(Number::new_value(
-                        ConstructInfo::new(
-                            function_call_id.with_child_id(0),
-                            None,
-                            "Math/modulo result",
-                        ),
-                        construct_context.clone(),
-                        ValueIdempotencyKey::new(),
-                        a % divisor,
-                    ))))
-                } else {
-                    future::ready(Some(None))
+                    Input::By(val) => *last_by = Some(val),
                 }
+                let (Some(a), Some(by)) = (*last_a, *last_by) else {
+                    return future::ready(Some(None));
+                };
+                future::ready(Some(Some(math_finite_result(
+                    &function_call_id,
+                    construct_context.clone(),
+                    function_name,
+                    divide(a, by),
+                ))))
             },
         )
         .filter_map(future::ready)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BoolToggleInput, BoolToggleState, ValueIdempotencyKey, bool_toggle_step, parse_number,
+        text_padding_fill, text_slice,
+    };
+    use boon::platform::browser::common::set_max_text_bytes;
+
+    #[test]
+    fn text_slice_is_one_based_inclusive_and_clamped() {
+        assert_eq!(text_slice("Hello", 1.0, 3.0), "Hel");
+        assert_eq!(text_slice("Hello", 2.0, 4.0), "ell");
+        assert_eq!(text_slice("", 1.0, 3.0), "");
+        assert_eq!(text_slice("Hello", 4.0, 99.0), "lo");
+        assert_eq!(text_slice("Hello", -3.0, 2.0), "He");
+        assert_eq!(text_slice("Hello", 4.0, 2.0), "");
+        assert_eq!(text_slice("Hello", f64::NAN, 2.0), "");
+        assert_eq!(text_slice("čšž👋ab", 2.0, 4.0), "šž👋");
+    }
+
+    #[test]
+    fn text_slice_matches_a_char_vector_model_over_random_inputs() {
+        let alphabet: Vec<char> = "ab čž€👋🇭🇺\n".chars().collect();
+        for seed in 1..=500_u64 {
+            // xorshift64, so every failing seed can be replayed
+            let mut random = seed;
+            let mut next = |bound: usize| {
+                random ^= random << 13;
+                random ^= random >> 7;
+                random ^= random << 17;
+                usize::try_from(random % u64::try_from(bound).unwrap()).unwrap()
+            };
+            let chars: Vec<char> = (0..next(12))
+                .map(|_| alphabet[next(alphabet.len())])
+                .collect();
+            let text: String = chars.iter().collect();
+            let length = i32::try_from(chars.len()).unwrap();
+            let start = i32::try_from(next(20)).unwrap() - 5;
+            let end = i32::try_from(next(20)).unwrap() - 5;
+
+            let first = usize::try_from(start.max(1) - 1).unwrap();
+            let last = usize::try_from(end.min(length).max(0)).unwrap();
+            let expected: String = if first < last {
+                chars[first..last].iter().collect()
+            } else {
+                String::new()
+            };
+            assert_eq!(
+                text_slice(&text, f64::from(start), f64::from(end)),
+                expected,
+                "text_slice({text:?}, {start}, {end}) at seed {seed}"
+            );
+        }
+    }
+
+    #[test]
+    fn text_padding_fill_repeats_with_up_to_length() {
+        assert_eq!(text_padding_fill("7", 2.0, "0").as_deref(), Some("0"));
+        assert_eq!(text_padding_fill("7", 6.0, "ab").as_deref(), Some("ababa"));
+        assert_eq!(text_padding_fill("👋", 3.0, "č").as_deref(), Some("čč"));
+        assert_eq!(text_padding_fill("07", 2.0, "0"), None);
+        assert_eq!(text_padding_fill("123", 2.0, "0"), None);
+        assert_eq!(text_padding_fill("7", 5.0, ""), None);
+        assert_eq!(text_padding_fill("7", f64::NAN, "0"), None);
+    }
+
+    #[test]
+    fn bool_toggle_ignores_initial_once_on_has_fired() {
+        let mut state = BoolToggleState::default();
+        let mut started = false;
+        let toggle = BoolToggleInput::Toggle(ValueIdempotencyKey::new());
+        assert_eq!(
+            bool_toggle_step(&mut state, &mut started, toggle),
+            Some(true)
+        );
+        assert_eq!(
+            bool_toggle_step(&mut state, &mut started, BoolToggleInput::Initial(false)),
+            None
+        );
+        assert!(state.value);
+    }
+
+    #[test]
+    fn bool_toggle_takes_only_the_first_initial() {
+        let mut state = BoolToggleState::default();
+        let mut started = false;
+        let mut step = |input| bool_toggle_step(&mut state, &mut started, input);
+        assert_eq!(step(BoolToggleInput::Initial(true)), Some(true));
+        assert_eq!(step(BoolToggleInput::Initial(false)), None);
+        let key = ValueIdempotencyKey::new();
+        assert_eq!(step(BoolToggleInput::Toggle(key)), Some(false));
+        assert_eq!(step(BoolToggleInput::Toggle(key)), None);
+        assert_eq!(step(BoolToggleInput::Initial(true)), None);
+    }
+
+    #[test]
+    fn text_padding_fill_stops_at_the_text_size_limit() {
+        set_max_text_bytes(8);
+        assert_eq!(
+            text_padding_fill("7", 1e15, "0").as_deref(),
+            Some("00000000")
+        );
+        assert_eq!(
+            text_padding_fill("7", f64::INFINITY, "čš").as_deref(),
+            Some("čščš")
+        );
+        assert_eq!(text_padding_fill("7", 4.0, "0").as_deref(), Some("000"));
+    }
+
+    #[test]
+    fn parse_number_accepts_whole_and_decimal_forms_only() {
+        assert_eq!(parse_number("42"), Some(42.0));
+        assert_eq!(parse_number("  -3.5\n"), Some(-3.5));
+        assert_eq!(parse_number("+7"), Some(7.0));
+        assert_eq!(parse_number(".5"), Some(0.5));
+        assert_eq!(parse_number("5."), Some(5.0));
+        assert_eq!(parse_number("   12   "), Some(12.0));
+        for text in [
+            "", " ", ".", "-", "1e3", "inf", "NaN", "1,5", "1.2.3", "12abc", "- 3", "twelve",
+        ] {
+            assert_eq!(parse_number(text), None, "{text:?}");
+        }
+    }
+}
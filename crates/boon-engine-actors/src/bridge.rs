@@ -1,4 +1,4 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 use std::sync::Arc;
 
@@ -43,6 +43,10 @@ thread_local! {
         ConstructInfo::new("text_input::key_down_event::text_value", None, "key_down text value").complete(ConstructType::Text);
 }
 use boon::parser;
+use boon::platform::browser::code_block::code_block;
+use boon::platform::browser::saved_state::{
+    record_focused_input, record_scroll_position, take_focus_restore, take_scroll_restore,
+};
 
 /// Extract SceneContext from a ConstructContext's type-erased scene_ctx field.
 fn get_scene_ctx(construct_context: &ConstructContext) -> Option<&PhysicalSceneParams> {
@@ -925,6 +929,7 @@ fn value_to_element(value: Value, construct_context: ConstructContext) -> RawElO
             "ElementParagraph" => element_paragraph(tagged_object, construct_context).unify(),
             "ElementLink" => element_link(tagged_object, construct_context).unify(),
             "ElementText" => element_text(tagged_object, construct_context).unify(),
+            "ElementCode" => element_code(tagged_object, construct_context).unify(),
             "ElementBlock" => element_block(tagged_object, construct_context).unify(),
             other => panic!("Element cannot be created from the tagged object with tag '{other}'"),
         },
@@ -1589,6 +1594,20 @@ fn element_stripe(
     .map(|variable| variable.expect_link_value_sender())
     .chain(stream::pending());
 
+    let ui_id = ui_state_id(&tagged_object);
+
+    // Element comes from settings here too (see hovered above)
+    let sv_element_for_context_menu = tagged_object.expect_variable("settings");
+    let (context_menu_sender, context_menu_connected, context_menu_loop) = context_menu_link(
+        switch_map(sv_element_for_context_menu.stream(), |value| {
+            match value.expect_object().variable("element") {
+                Some(var) => var.stream().left_stream(),
+                None => stream::empty().right_stream(),
+            }
+        }),
+        construct_context.clone(),
+    );
+
     let hovered_handler_loop = ActorLoop::new({
         let _construct_context = construct_context.clone();
         async move {
@@ -2579,6 +2598,23 @@ fn element_stripe(
         // Raw CSS properties without Zoon typed equivalents
         .update_raw_el(move |raw_el| {
             raw_el
+                .after_insert({
+                    let ui_id = ui_id.clone();
+                    move |element| {
+                        if let Some((left, top)) = take_scroll_restore(&ui_id) {
+                            restore_after_render(move || {
+                                element.set_scroll_left(left);
+                                element.set_scroll_top(top);
+                            });
+                        }
+                    }
+                })
+                .event_handler(move |event: events::Scroll| {
+                    let Some(element) = event.dyn_target::<web_sys::Element>() else {
+                        return;
+                    };
+                    record_scroll_position(&ui_id, element.scroll_left(), element.scroll_top());
+                })
                 .style_signal("overflow", scrollbars_css_signal)
                 .style_signal("line-height", line_height_css_signal)
                 .style_signal("text-shadow", text_shadow_css_signal)
@@ -2601,11 +2637,15 @@ fn element_stripe(
                     }))
                 })
         })
+        .update_raw_el(move |raw_el| {
+            on_context_menu(raw_el, context_menu_sender, context_menu_connected)
+        })
         .update_raw_el(move |raw_el| apply_physical_css(raw_el, &sv_physical, &ctx_physical))
         // Keep tagged_object alive for the lifetime of this element
         .after_remove(move |_| {
             drop(tagged_object);
             drop(hovered_handler_loop);
+            drop(context_menu_loop);
         })
 }
 
@@ -3860,6 +3900,7 @@ fn element_text_input(
     let suppress_next_blur: Rc<RefCell<bool>> = Default::default();
 
     let element_variable = tagged_object.expect_variable("element");
+    let ui_id = ui_state_id(&tagged_object);
 
     // Set up event handlers - create separate subscriptions for each event type
     // Chain with pending() to prevent stream termination causing busy-polling in select!
@@ -4550,9 +4591,18 @@ fn element_text_input(
         .update_raw_el({
             let sender = dom_event_sender.clone();
             let dom_input_el_ref = dom_input_el.clone();
+            let ui_id = ui_id.clone();
             move |raw_el| {
                 raw_el
                     .after_insert(move |input_el: web_sys::HtmlInputElement| {
+                        if take_focus_restore(&ui_id) {
+                            restore_after_render({
+                                let input_el = input_el.clone();
+                                move || {
+                                    let _ = input_el.focus();
+                                }
+                            });
+                        }
                         *dom_input_el_ref.borrow_mut() = Some(input_el);
                     })
                     .event_handler(move |event: events::Input| {
@@ -4676,6 +4726,7 @@ fn element_text_input(
             let sender = focus_event_sender.clone();
             move |raw_el| {
                 raw_el.event_handler(move |_: events::Focus| {
+                    record_focused_input(&ui_id);
                     let event = TimestampedEvent::now(());
                     if LOG_DEBUG {
                         zoon::println!(
@@ -5728,6 +5779,142 @@ fn element_svg_circle(
         .after_remove(move |_| drop(event_handler_loop))
 }
 
+/// Routes `element.event.context_menu` for an element whose `element` object is
+/// emitted by `element_stream`.
+///
+/// The returned flag turns true once a LINK is connected; the DOM handler only
+/// suppresses the native menu then, so unhandled right-clicks keep browser behavior.
+fn context_menu_link(
+    element_stream: impl Stream<Item = Value> + 'static,
+    construct_context: ConstructContext,
+) -> (
+    NamedChannel<TimestampedEvent<(f64, f64)>>,
+    Rc<Cell<bool>>,
+    ActorLoop,
+) {
+    let (context_menu_sender, mut context_menu_receiver) =
+        NamedChannel::<TimestampedEvent<(f64, f64)>>::new(
+            "element.context_menu",
+            BRIDGE_PRESS_EVENT_CAPACITY,
+        );
+    let connected = Rc::new(Cell::new(false));
+
+    // Use switch_map (not flat_map) because variable streams are infinite.
+    let mut context_menu_stream = switch_map(
+        element_stream.filter_map(|value| future::ready(value.expect_object().variable("event"))),
+        |variable| variable_current_or_future_stream(variable),
+    )
+    .filter_map(|value| future::ready(value.expect_object().variable("context_menu")))
+    .map(|variable| variable.expect_link_value_sender())
+    .chain(stream::pending())
+    .fuse();
+
+    let event_handler_loop = ActorLoop::new({
+        let connected = connected.clone();
+        async move {
+            let scope_id = construct_context
+                .bridge_scope_id
+                .expect("Bug: bridge_scope_id not set for context_menu event handler");
+            let mut context_menu_link_sender: Option<NamedChannel<Value>> = None;
+            loop {
+                select! {
+                    link = context_menu_stream.next() => {
+                        if let Some(link) = link {
+                            connected.set(true);
+                            context_menu_link_sender = Some(link);
+                        }
+                    }
+                    event = context_menu_receiver.select_next_some() => {
+                        let Some(sender) = context_menu_link_sender.as_ref() else {
+                            continue;
+                        };
+                        let (x, y) = event.data;
+                        let coordinate = |name: &'static str, number: f64| {
+                            let number_value = Value::Number(
+                                EngineNumber::new_arc(
+                                    ConstructInfo::new("element::context_menu_coordinate_num", None, "context_menu coordinate number"),
+                                    construct_context.clone(),
+                                    number,
+                                ),
+                                ValueMetadata::with_lamport_time(ValueIdempotencyKey::new(), event.lamport_time),
+                            );
+                            Variable::new_arc(
+                                ConstructInfo::new("element::context_menu_coordinate", None, "context_menu coordinate"),
+                                construct_context.clone(),
+                                name,
+                                create_actor(
+                                    ConstructInfo::new("element::context_menu_coordinate_actor", None, "context_menu coordinate actor"),
+                                    ActorContext::default(),
+                                    TypedStream::infinite(
+                                        stream::once(future::ready(number_value)).chain(stream::pending()),
+                                    ),
+                                    parser::PersistenceId::new(),
+                                    scope_id,
+                                ),
+                                parser::PersistenceId::default(),
+                                parser::Scope::Root,
+                            )
+                        };
+                        let event_value = Object::new_value_with_lamport_time(
+                            ConstructInfo::new("element::context_menu_event", None, "context_menu event [x, y]"),
+                            construct_context.clone(),
+                            ValueIdempotencyKey::new(),
+                            event.lamport_time,
+                            [coordinate("x", x), coordinate("y", y)],
+                        );
+                        sender.send_or_drop(event_value);
+                    }
+                }
+            }
+        }
+    });
+
+    (context_menu_sender, connected, event_handler_loop)
+}
+
+/// Key of the element in the saved UI state. The settings variable keeps its persistence id
+/// across re-runs, so focus and scroll follow the element like its HOLDs do.
+fn ui_state_id(tagged_object: &TaggedObject) -> String {
+    tagged_object
+        .expect_variable("settings")
+        .persistence_id()
+        .to_string()
+}
+
+/// Runs `restore` once the re-run had a chance to render children and apply `focus` signals,
+/// which would otherwise clamp the scroll offset or take the focus away again.
+///
+/// A browser timeout rather than a task, the bridge never spawns tasks (see ACTOR_MODEL.md).
+fn restore_after_render(restore: impl FnOnce() + 'static) {
+    const RESTORE_DELAY_MS: i32 = 50;
+    let callback = Closure::once_into_js(restore);
+    let _ = window().set_timeout_with_callback_and_timeout_and_arguments_0(
+        callback.unchecked_ref(),
+        RESTORE_DELAY_MS,
+    );
+}
+
+/// Sends `contextmenu` viewport coordinates to [`context_menu_link`]; a no-op (keeping the
+/// native menu) until that link is connected.
+fn on_context_menu<T: RawEl>(
+    raw_el: T,
+    sender: NamedChannel<TimestampedEvent<(f64, f64)>>,
+    connected: Rc<Cell<bool>>,
+) -> T {
+    raw_el.event_handler_with_options(
+        EventOptions::new().preventable(),
+        move |event: events::ContextMenu| {
+            if !connected.get() {
+                return;
+            }
+            event.prevent_default();
+            let x = f64::from(event.x());
+            let y = f64::from(event.y());
+            sender.send_or_drop(TimestampedEvent::now((x, y)));
+        },
+    )
+}
+
 fn element_label(
     tagged_object: Arc<TaggedObject>,
     construct_context: ConstructContext,
@@ -5746,6 +5933,11 @@ fn element_label(
 
     let element_variable = tagged_object.expect_variable("element");
 
+    let (context_menu_sender, context_menu_connected, context_menu_loop) = context_menu_link(
+        variable_current_or_future_stream(element_variable.clone()),
+        construct_context.clone(),
+    );
+
     // Set up hovered link
     // Chain with pending() to prevent stream termination causing busy-polling in select!
     let hovered_stream = element_variable.clone();
@@ -6039,8 +6231,14 @@ fn element_label(
             }
         })
         .s(Visible::with_signal(visible_sig))
+        .update_raw_el(move |raw_el| {
+            on_context_menu(raw_el, context_menu_sender, context_menu_connected)
+        })
         .update_raw_el(move |raw_el| apply_physical_css(raw_el, &sv_physical, &ctx_physical))
-        .after_remove(move |_| drop(event_handler_loop))
+        .after_remove(move |_| {
+            drop(event_handler_loop);
+            drop(context_menu_loop);
+        })
 }
 
 fn element_paragraph(
@@ -6318,6 +6516,53 @@ fn element_text(
         })
 }
 
+/// Element/code - renders preformatted source, highlighted when the language is Boon.
+/// Structure: ElementCode[element?, settings[style, language, content]]
+fn element_code(
+    tagged_object: Arc<TaggedObject>,
+    construct_context: ConstructContext,
+) -> impl Element {
+    let sv_physical = tagged_object.expect_variable("settings");
+    let ctx_physical = construct_context.clone();
+    let settings_variable = tagged_object.expect_variable("settings");
+    let visible_sig = visible_signal_from_settings(tagged_object.expect_variable("settings"));
+
+    // CRITICAL: Use switch_map (not flat_map) because settings variable streams are infinite.
+    let language_stream = switch_map(settings_variable.clone().stream(), |value| {
+        value.expect_object().expect_variable("language").stream()
+    })
+    .filter_map(|value| {
+        future::ready(match value {
+            Value::Text(text, _) => Some(text.text().to_string()),
+            Value::Tag(tag, _) => Some(tag.tag().to_string()),
+            _ => None,
+        })
+    })
+    .boxed_local();
+    let content_stream = switch_map(settings_variable.stream(), |value| {
+        value.expect_object().expect_variable("content").stream()
+    })
+    .filter_map(|value| {
+        future::ready(match value {
+            Value::Text(text, _) => Some(text.text().to_string()),
+            _ => None,
+        })
+    })
+    .boxed_local();
+
+    El::new()
+        .s(Width::fill())
+        .child(code_block(
+            signal::from_stream(language_stream).map(Option::unwrap_or_default),
+            signal::from_stream(content_stream).map(Option::unwrap_or_default),
+        ))
+        .s(Visible::with_signal(visible_sig))
+        .update_raw_el(move |raw_el| apply_physical_css(raw_el, &sv_physical, &ctx_physical))
+        .after_remove(move |_| {
+            drop(tagged_object);
+        })
+}
+
 /// Element/block - renders a styled block element with a single child.
 /// Structure: ElementBlock[element?, settings[style, child]]
 fn element_block(
@@ -12,6 +12,9 @@ use super::evaluator::{FunctionRegistry, evaluate_static_expression_with_registr
 use boon::parser;
 use boon::parser::SourceCode;
 use boon::parser::static_expression;
+use boon::platform::browser::common::{
+    LogLevel, emit_warning, max_text_bytes, truncate_oversized_text,
+};
 
 use ulid::Ulid;
 
@@ -323,6 +326,11 @@ pub fn add_to_removed_set(removed_set_key: &str, call_id: &str) {
         removed.push(call_id.to_string());
         if let Err(e) = local_storage().insert(removed_set_key, &removed) {
             zoon::eprintln!("[DEBUG] Failed to save removed set: {:#}", e);
+            emit_warning(
+                LogLevel::Warn,
+                "persistence",
+                format!("Removal of {call_id} was not persisted: {e:#}"),
+            );
         } else if LOG_DEBUG {
             zoon::println!(
                 "[DEBUG] Added {} to removed set {}, now {} items",
@@ -1085,7 +1093,7 @@ struct LazyValueRequest {
 /// This is essential for sequential state updates in HOLD bodies:
 /// ```boon
 /// [count: 0] |> HOLD state {
-///     3 |> Stream/pulses() |> THEN { [count: state.count + 1] }
+///     3 |> Stream/sequence() |> THEN { [count: state.count + 1] }
 /// }
 /// ```
 /// With lazy actors, HOLD pulls one value at a time, updates state, then pulls the next.
@@ -2072,7 +2080,7 @@ pub struct ActorContext {
     /// When true, expression evaluation creates LazyValueActors instead of eager ValueActors.
     /// Lazy actors only poll their source stream when a subscriber requests values (demand-driven).
     /// This is set by HOLD for body evaluation to ensure sequential state updates:
-    /// each pulse from Stream/pulses is pulled one at a time, state is updated between each.
+    /// each pulse from Stream/sequence is pulled one at a time, state is updated between each.
     /// Default: false (normal eager evaluation).
     pub use_lazy_actors: bool,
     /// When true, code should use `.value()` instead of `.stream()` for subscriptions.
@@ -3465,13 +3473,18 @@ impl FunctionCall {
             zoon::futures_util::stream::select_all(flushed_streams).boxed_local()
         };
 
+        // Builtins that build Text (Text/replace, Text/join, padding, ...) are held to the
+        // same soft size limit as TEXT templates, reported once per call site
+        let call_site = construct_info.description.clone();
+        let mut reported_oversized = false;
+
         // Select between normal function output and FLUSHED bypass
         // FLUSHED values from arguments take priority
         let combined_stream = zoon::futures_util::stream::select(
             flushed_bypass_stream,
-            value_stream.map(|v| {
-                // If the function itself produces FLUSHED, pass it through
-                v
+            value_stream.map(move |v| {
+                // FLUSHED and other values that are not Text pass through unchanged
+                cap_oversized_text(v, &call_site, &mut reported_oversized)
             }),
         );
 
@@ -3499,6 +3512,35 @@ impl FunctionCall {
     }
 }
 
+/// `value` with its Text cut to the soft limit (`--max-text-bytes`, a playground setting).
+/// The first cut at a call site is reported as a `text-size` warning naming `call_site`.
+fn cap_oversized_text(value: Value, call_site: &str, reported: &mut bool) -> Value {
+    let Value::Text(text, metadata) = &value else {
+        return value;
+    };
+    let max_bytes = max_text_bytes();
+    if text.text.len() <= max_bytes {
+        return value;
+    }
+    let mut capped = text.text.to_string();
+    truncate_oversized_text(&mut capped);
+    if !*reported {
+        *reported = true;
+        emit_warning(
+            LogLevel::Warn,
+            "text-size",
+            format!("{call_site} produced more than {max_bytes} bytes of Text; it is truncated"),
+        );
+    }
+    Value::Text(
+        Arc::new(Text {
+            construct_info: text.construct_info.clone(),
+            text: Cow::Owned(capped),
+        }),
+        *metadata,
+    )
+}
+
 // --- LatestCombinator ---
 
 pub struct LatestCombinator {}
@@ -6955,6 +6997,24 @@ enum DiffHistoryQuery {
     },
 }
 
+#[derive(Clone, Copy, PartialEq)]
+pub enum ListReordering {
+    Insert,
+    Move,
+}
+
+/// A `List/insert` or `List/move` call. Calls piped into each other reorder one
+/// shared order, like mutations of one Bus: the first call owns the order and the
+/// later ones send their trigger to it through `List::reorder_sender`.
+pub struct ListReorderTrigger {
+    pub reordering: ListReordering,
+    pub first: ActorHandle,
+    pub second: ActorHandle,
+    pub on: ActorHandle,
+    /// Resolves when the call that sent the trigger is dropped.
+    pub dropped: oneshot::Receiver<()>,
+}
+
 pub struct List {
     construct_info: Arc<ConstructInfoComplete>,
     actor_loop: ActorLoop,
@@ -6968,6 +7028,8 @@ pub struct List {
     diff_query_sender: NamedChannel<DiffHistoryQuery>,
     /// Optional persistence actor loop - keeps alive for list's lifetime
     persistence_loop: Option<ActorLoop>,
+    /// Set on `List/insert` and `List/move` results; reaches the call owning the order.
+    reorder_sender: Option<NamedChannel<ListReorderTrigger>>,
 }
 
 impl List {
@@ -7190,6 +7252,7 @@ impl List {
             notify_sender_sender,
             diff_query_sender,
             persistence_loop: None,
+            reorder_sender: None,
         }
     }
 
@@ -7198,6 +7261,14 @@ impl List {
         self.persistence_loop = Some(loop_);
     }
 
+    pub fn set_reorder_sender(&mut self, sender: NamedChannel<ListReorderTrigger>) {
+        self.reorder_sender = Some(sender);
+    }
+
+    pub fn reorder_sender(&self) -> Option<NamedChannel<ListReorderTrigger>> {
+        self.reorder_sender.clone()
+    }
+
     /// Get current version.
     pub fn version(&self) -> u64 {
         self.current_version.load(Ordering::SeqCst)
@@ -8284,7 +8355,160 @@ pub enum ListBindingOperation {
     Remove,
     Every,
     Any,
+    CountWhere,
     SortBy,
+    UniqueBy,
+    Find,
+}
+
+/// Configuration for `List/update`.
+#[derive(Clone)]
+pub struct ListUpdateConfig {
+    /// Which items are replaced when `on` fires
+    pub target: ListUpdateTarget,
+    /// The replacement, evaluated afresh for each replaced item
+    pub new_expr: StaticSpanned<StaticExpression>,
+    /// The trigger
+    pub on: ActorHandle,
+    /// Reference connector for looking up scope-resolved references
+    pub reference_connector: Arc<ReferenceConnector>,
+    /// Link connector for connecting LINK variables with their setters
+    pub link_connector: Arc<LinkConnector>,
+    /// Pass-through connector for stable LINK pass-throughs
+    pub pass_through_connector: Arc<PassThroughConnector>,
+    /// Source code for creating borrowed expressions
+    pub source_code: SourceCode,
+    /// Function registry snapshot for resolving user-defined functions
+    pub function_registry_snapshot: Option<Arc<FunctionRegistry>>,
+}
+
+#[derive(Clone)]
+pub enum ListUpdateTarget {
+    /// The item at a 1-based position, read when `on` fires.
+    Index(ActorHandle),
+    /// Every item whose predicate is True when `on` fires. The item is bound to
+    /// `binding_name` in the predicate and in `new_expr`.
+    Matching {
+        binding_name: StrSlice,
+        predicate: StaticSpanned<StaticExpression>,
+    },
+}
+
+/// Item bookkeeping behind `List/count_where`.
+///
+/// Predicate results are keyed by PersistenceId the same way `List/retain` keys them:
+/// items sharing an id share one result and `Remove { id }` drops all of them, so the
+/// count always equals `List/retain(..) |> List/count()`. `T` is whatever the caller
+/// keeps per item (the predicate subscription in the actor).
+struct CountWhereState<T> {
+    items: Vec<(parser::PersistenceId, T)>,
+    /// Copies of each id in `items` and their shared predicate result.
+    results: HashMap<parser::PersistenceId, (usize, bool)>,
+    true_count: usize,
+}
+
+impl<T> CountWhereState<T> {
+    fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            results: HashMap::new(),
+            true_count: 0,
+        }
+    }
+
+    fn count(&self) -> usize {
+        self.true_count
+    }
+
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    fn result(&self, pid: parser::PersistenceId) -> Option<bool> {
+        self.results.get(&pid).map(|(_, is_true)| *is_true)
+    }
+
+    /// Adds an item at `index`; a repeated id overwrites the shared result like a
+    /// retain rebuild does.
+    fn insert(&mut self, index: usize, pid: parser::PersistenceId, is_true: bool, payload: T) {
+        self.items.insert(index, (pid, payload));
+        let (copies, result) = self.results.entry(pid).or_insert((0, false));
+        if *result {
+            self.true_count -= *copies;
+        }
+        *copies += 1;
+        *result = is_true;
+        if is_true {
+            self.true_count += *copies;
+        }
+    }
+
+    /// Records a predicate flip; updates for ids no longer in the list are ignored.
+    fn set(&mut self, pid: parser::PersistenceId, is_true: bool) {
+        let Some((copies, result)) = self.results.get_mut(&pid) else {
+            return;
+        };
+        if *result == is_true {
+            return;
+        }
+        *result = is_true;
+        if is_true {
+            self.true_count += *copies;
+        } else {
+            self.true_count -= *copies;
+        }
+    }
+
+    fn remove(&mut self, pid: parser::PersistenceId) -> Vec<T> {
+        if let Some((copies, true)) = self.results.remove(&pid) {
+            self.true_count -= copies;
+        }
+        let (removed, kept) = std::mem::take(&mut self.items)
+            .into_iter()
+            .partition::<Vec<_>, _>(|(item_pid, _)| *item_pid == pid);
+        self.items = kept;
+        removed.into_iter().map(|(_, payload)| payload).collect()
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.remove_at(self.items.len().checked_sub(1)?)
+    }
+
+    /// Removes one item by position; its id's other copies keep their shared result.
+    fn remove_at(&mut self, index: usize) -> Option<T> {
+        if index >= self.items.len() {
+            return None;
+        }
+        let (pid, payload) = self.items.remove(index);
+        if let Some((copies, is_true)) = self.results.get_mut(&pid) {
+            *copies -= 1;
+            if *is_true {
+                self.true_count -= 1;
+            }
+            if *copies == 0 {
+                self.results.remove(&pid);
+            }
+        }
+        Some(payload)
+    }
+
+    /// Moves an item; positions only matter for `pop` and `remove_at`, not the count.
+    fn move_item(&mut self, old_index: usize, new_index: usize) {
+        if old_index < self.items.len() {
+            let item = self.items.remove(old_index);
+            let new_index = new_index.min(self.items.len());
+            self.items.insert(new_index, item);
+        }
+    }
+
+    fn clear(&mut self) -> Vec<T> {
+        self.results.clear();
+        self.true_count = 0;
+        std::mem::take(&mut self.items)
+            .into_iter()
+            .map(|(_, payload)| payload)
+            .collect()
+    }
 }
 
 /// A sortable key extracted from a Value for use in List/sort_by.
@@ -8428,6 +8652,133 @@ fn compute_sort_diff(
     changes
 }
 
+/// Indices of the first value of each group of structurally equal values, in order.
+pub(crate) async fn first_distinct_indices(values: &[Value]) -> Vec<usize> {
+    let mut kept: Vec<usize> = Vec::new();
+    'values: for (index, value) in values.iter().enumerate() {
+        for &kept_index in &kept {
+            if values_equal_async(&values[kept_index], value).await {
+                continue 'values;
+            }
+        }
+        kept.push(index);
+    }
+    kept
+}
+
+/// The values of the currently selected item, or the tag `None` while nothing is
+/// selected. Re-subscribes only when the selection changes identity, so list changes
+/// that keep the same item selected do not re-emit.
+pub(crate) fn follow_selected_item(
+    selected: impl Stream<Item = Option<ActorHandle>> + 'static,
+    function_call_id: ConstructId,
+    construct_context: ConstructContext,
+    none_description: &'static str,
+) -> impl Stream<Item = Value> {
+    let distinct_selections = selected
+        .scan(None, |last_selected, item: Option<ActorHandle>| {
+            let selected_id = item.as_ref().map(ActorHandle::actor_id);
+            let changed = *last_selected != Some(selected_id);
+            *last_selected = Some(selected_id);
+            future::ready(Some(changed.then_some(item)))
+        })
+        .filter_map(future::ready);
+    // switch_map: when the selected item changes identity, cancel old subscription and start new
+    switch_map(
+        distinct_selections,
+        move |item| -> LocalBoxStream<'static, Value> {
+            match item {
+                Some(actor) => actor.stream().boxed_local(),
+                None => stream::once(future::ready(Tag::new_value(
+                    ConstructInfo::new(function_call_id.with_child_id(0), None, none_description),
+                    construct_context.clone(),
+                    ValueIdempotencyKey::new(),
+                    "None".to_string(),
+                )))
+                .boxed_local(),
+            }
+        },
+    )
+}
+
+/// Turns successive selections of a source list's items into changes of the list
+/// holding them. The first selection is a `Replace`; later ones are diffed.
+pub(crate) fn kept_item_changes(
+    selections: impl Stream<Item = Vec<ActorHandle>>,
+) -> impl Stream<Item = ListChange> {
+    selections
+        .scan(None, |previous: &mut Option<Vec<ActorHandle>>, kept| {
+            let changes = match previous.as_deref() {
+                None => vec![ListChange::Replace {
+                    items: Arc::from(kept.clone()),
+                }],
+                Some(old) => compute_kept_item_diff(old, &kept),
+            };
+            *previous = Some(kept);
+            future::ready(Some(stream::iter(changes)))
+        })
+        .flatten()
+}
+
+/// Remove+InsertAt operations turning the selection `old` into `new`, both taken
+/// from the same source list. Items shared by both keep their actors. Falls back to
+/// `Replace` when distinct items share a PersistenceId, since `Remove { id }` could
+/// then drop the wrong one.
+fn compute_kept_item_diff(old: &[ActorHandle], new: &[ActorHandle]) -> Vec<ListChange> {
+    let mut owners = HashMap::new();
+    for item in old.iter().chain(new) {
+        if *owners
+            .entry(item.persistence_id())
+            .or_insert_with(|| item.actor_id())
+            != item.actor_id()
+        {
+            return vec![ListChange::Replace {
+                items: Arc::from(new.to_vec()),
+            }];
+        }
+    }
+
+    let is_in = |items: &[ActorHandle], item: &ActorHandle| {
+        items
+            .iter()
+            .any(|other| other.actor_id() == item.actor_id())
+    };
+    let mut changes = Vec::new();
+    let mut current = Vec::new();
+    for item in old {
+        if is_in(new, item) {
+            current.push(item.clone());
+        } else {
+            changes.push(ListChange::Remove {
+                id: item.persistence_id(),
+            });
+        }
+    }
+    for (index, item) in new.iter().enumerate() {
+        if current
+            .get(index)
+            .is_some_and(|placed: &ActorHandle| placed.actor_id() == item.actor_id())
+        {
+            continue;
+        }
+        if let Some(position) = current
+            .iter()
+            .position(|placed| placed.actor_id() == item.actor_id())
+        {
+            current.remove(position);
+            changes.push(ListChange::Remove {
+                id: item.persistence_id(),
+            });
+        }
+        current.insert(index, item.clone());
+        changes.push(ListChange::InsertAt {
+            index,
+            item: item.clone(),
+        });
+    }
+    changes
+}
+
 impl ListBindingFunction {
     /// Creates a new ValueActor for a List binding function.
     ///
@@ -8492,6 +8843,13 @@ impl ListBindingFunction {
                     false, // is_every (false = any)
                 )
             }
+            ListBindingOperation::CountWhere => Self::create_count_where_actor(
+                construct_info,
+                construct_context,
+                actor_context,
+                source_list_actor,
+                config,
+            ),
             ListBindingOperation::SortBy => Self::create_sort_by_actor(
                 construct_info,
                 construct_context,
@@ -8499,6 +8857,20 @@ impl ListBindingFunction {
                 source_list_actor,
                 config,
             ),
+            ListBindingOperation::UniqueBy => Self::create_unique_by_actor(
+                construct_info,
+                construct_context,
+                actor_context,
+                source_list_actor,
+                config,
+            ),
+            ListBindingOperation::Find => Self::create_find_actor(
+                construct_info,
+                construct_context,
+                actor_context,
+                source_list_actor,
+                config,
+            ),
         }
     }
 
@@ -8789,110 +9161,109 @@ impl ListBindingFunction {
                 let list_stream: Pin<Box<dyn Stream<Item = ListChange>>> = Box::pin(list.stream());
 
                 stream::unfold(
-                (
-                    Vec::<ActorHandle>::new(),
-                    HashMap::<parser::PersistenceId, ActorHandle>::new(),
-                    HashMap::<parser::PersistenceId, bool>::new(),
-                    list_stream,
-                    // A3: Coalesced predicate stream yields batches instead of single items
-                    None::<Pin<Box<dyn Stream<Item = Vec<(parser::PersistenceId, bool)>>>>>,
-                    // A2: Track last emitted PersistenceIds for output deduplication
-                    Vec::<parser::PersistenceId>::new(),
-                    config,
-                    construct_context,
-                    actor_context,
-                ),
-                move |(mut items, mut predicates, mut predicate_results, mut list_stream, mut merged_predicates, mut last_emitted_pids, config, construct_context, actor_context)| {
-                    async move {
-                        loop {
-                            // If we have predicate streams, race between list changes and predicate updates
-                            if let Some(ref mut pred_stream) = merged_predicates {
-                                // Use select to race between list changes and predicate updates
-                                use zoon::futures_util::future::Either;
-
-                                let list_next = list_stream.next();
-                                let pred_next = pred_stream.next();
-
-                                match future::select(pin!(list_next), pin!(pred_next)).await {
-                                    Either::Left((Some(change), _)) => {
-                                        // List structure changed
-                                        match change {
-                                            ListChange::Replace { items: new_items } => {
-                                                items = new_items.to_vec();
-                                                if items.is_empty() {
-                                                    predicates.clear();
-                                                    predicate_results.clear();
-                                                    merged_predicates = None;
-                                                    last_emitted_pids.clear(); // A2: Clear for empty list
-                                                    return Some((
-                                                        Some(ListChange::Replace { items: Arc::from(Vec::<ActorHandle>::new()) }),
-                                                        (items, predicates, predicate_results, list_stream, merged_predicates, last_emitted_pids, config, construct_context, actor_context)
-                                                    ));
-                                                } else {
-                                                    let (
-                                                        new_predicates,
-                                                        new_predicate_results,
-                                                        new_merged_predicates,
-                                                        filtered,
-                                                        new_last_emitted_pids,
-                                                    ) = Self::rebuild_retain_state(
-                                                        &items,
-                                                        &predicate_results,
-                                                        &config,
-                                                        construct_context.clone(),
-                                                        actor_context.clone(),
-                                                    )
-                                                    .await;
-                                                    predicates = new_predicates;
-                                                    predicate_results = new_predicate_results;
-                                                    merged_predicates = new_merged_predicates;
-                                                    last_emitted_pids = new_last_emitted_pids;
-
-                                                    return Some((
-                                                        Some(ListChange::Replace { items: Arc::from(filtered) }),
-                                                        (items, predicates, predicate_results, list_stream, merged_predicates, last_emitted_pids, config, construct_context, actor_context)
-                                                    ));
-                                                }
-                                            }
-                                            ListChange::Push { item } => {
-                                                items.push(item);
-                                                let (
-                                                    new_predicates,
-                                                    new_predicate_results,
-                                                    new_merged_predicates,
-                                                    filtered,
-                                                    current_pids,
-                                                ) = Self::rebuild_retain_state(
-                                                    &items,
-                                                    &predicate_results,
-                                                    &config,
-                                                    construct_context.clone(),
-                                                    actor_context.clone(),
-                                                )
-                                                .await;
-                                                predicates = new_predicates;
-                                                predicate_results = new_predicate_results;
-                                                merged_predicates = new_merged_predicates;
-
-                                                // A2: Output deduplication - skip if same as last emitted
-                                                if current_pids == last_emitted_pids {
-                                                    continue; // Skip redundant emission (pushed item was filtered out)
+                    (
+                        Vec::<ActorHandle>::new(),
+                        HashMap::<parser::PersistenceId, ActorHandle>::new(),
+                        HashMap::<parser::PersistenceId, bool>::new(),
+                        list_stream,
+                        // A3: Coalesced predicate stream yields batches instead of single items
+                        None::<Pin<Box<dyn Stream<Item = Vec<(parser::PersistenceId, bool)>>>>>,
+                        // A2: Track last emitted PersistenceIds for output deduplication
+                        Vec::<parser::PersistenceId>::new(),
+                        config,
+                        construct_context,
+                        actor_context,
+                    ),
+                    move |(
+                        mut items,
+                        mut predicates,
+                        mut predicate_results,
+                        mut list_stream,
+                        mut merged_predicates,
+                        mut last_emitted_pids,
+                        config,
+                        construct_context,
+                        actor_context,
+                    )| {
+                        async move {
+                            loop {
+                                // If we have predicate streams, race between list changes and predicate updates
+                                if let Some(ref mut pred_stream) = merged_predicates {
+                                    // Use select to race between list changes and predicate updates
+                                    use zoon::futures_util::future::Either;
+
+                                    let list_next = list_stream.next();
+                                    let pred_next = pred_stream.next();
+
+                                    match future::select(pin!(list_next), pin!(pred_next)).await {
+                                        Either::Left((Some(change), _)) => {
+                                            // List structure changed
+                                            match change {
+                                                ListChange::Replace { items: new_items } => {
+                                                    items = new_items.to_vec();
+                                                    if items.is_empty() {
+                                                        predicates.clear();
+                                                        predicate_results.clear();
+                                                        merged_predicates = None;
+                                                        last_emitted_pids.clear(); // A2: Clear for empty list
+                                                        return Some((
+                                                            Some(ListChange::Replace {
+                                                                items: Arc::from(
+                                                                    Vec::<ActorHandle>::new(),
+                                                                ),
+                                                            }),
+                                                            (
+                                                                items,
+                                                                predicates,
+                                                                predicate_results,
+                                                                list_stream,
+                                                                merged_predicates,
+                                                                last_emitted_pids,
+                                                                config,
+                                                                construct_context,
+                                                                actor_context,
+                                                            ),
+                                                        ));
+                                                    } else {
+                                                        let (
+                                                            new_predicates,
+                                                            new_predicate_results,
+                                                            new_merged_predicates,
+                                                            filtered,
+                                                            new_last_emitted_pids,
+                                                        ) = Self::rebuild_retain_state(
+                                                            &items,
+                                                            &predicate_results,
+                                                            &config,
+                                                            construct_context.clone(),
+                                                            actor_context.clone(),
+                                                        )
+                                                        .await;
+                                                        predicates = new_predicates;
+                                                        predicate_results = new_predicate_results;
+                                                        merged_predicates = new_merged_predicates;
+                                                        last_emitted_pids = new_last_emitted_pids;
+
+                                                        return Some((
+                                                            Some(ListChange::Replace {
+                                                                items: Arc::from(filtered),
+                                                            }),
+                                                            (
+                                                                items,
+                                                                predicates,
+                                                                predicate_results,
+                                                                list_stream,
+                                                                merged_predicates,
+                                                                last_emitted_pids,
+                                                                config,
+                                                                construct_context,
+                                                                actor_context,
+                                                            ),
+                                                        ));
+                                                    }
                                                 }
-                                                last_emitted_pids = current_pids;
-
-                                                return Some((
-                                                    Some(ListChange::Replace { items: Arc::from(filtered) }),
-                                                    (items, predicates, predicate_results, list_stream, merged_predicates, last_emitted_pids, config, construct_context, actor_context)
-                                                ));
-                                            }
-                                            ListChange::Remove { id } => {
-                                                items.retain(|item| item.persistence_id() != id);
-                                                let (filtered, current_pids) = if items.is_empty() {
-                                                    predicates.clear();
-                                                    predicate_results.clear();
-                                                    merged_predicates = None;
-                                                    (Vec::new(), Vec::new())
-                                                } else {
+                                                ListChange::Push { item } => {
+                                                    items.push(item);
                                                     let (
                                                         new_predicates,
                                                         new_predicate_results,
@@ -8910,22 +9281,45 @@ impl ListBindingFunction {
                                                     predicates = new_predicates;
                                                     predicate_results = new_predicate_results;
                                                     merged_predicates = new_merged_predicates;
-                                                    (filtered, current_pids)
-                                                };
 
-                                                if current_pids == last_emitted_pids {
-                                                    continue; // Skip redundant emission (removed item was filtered out)
-                                                }
-                                                last_emitted_pids = current_pids;
+                                                    // A2: Output deduplication - skip if same as last emitted
+                                                    if current_pids == last_emitted_pids {
+                                                        continue; // Skip redundant emission (pushed item was filtered out)
+                                                    }
+                                                    last_emitted_pids = current_pids;
 
-                                                return Some((
-                                                    Some(ListChange::Replace { items: Arc::from(filtered) }),
-                                                    (items, predicates, predicate_results, list_stream, merged_predicates, last_emitted_pids, config, construct_context, actor_context)
-                                                ));
-                                            }
-                                            ListChange::Pop => {
-                                                if items.pop().is_some() {
-                                                    let (filtered, current_pids) = if items.is_empty() {
+                                                    return Some((
+                                                        Some(ListChange::Replace {
+                                                            items: Arc::from(filtered),
+                                                        }),
+                                                        (
+                                                            items,
+                                                            predicates,
+                                                            predicate_results,
+                                                            list_stream,
+                                                            merged_predicates,
+                                                            last_emitted_pids,
+                                                            config,
+                                                            construct_context,
+                                                            actor_context,
+                                                        ),
+                                                    ));
+                                                }
+                                                change @ (ListChange::Remove { .. }
+                                                | ListChange::InsertAt { .. }
+                                                | ListChange::UpdateAt { .. }
+                                                | ListChange::Move { .. }) => {
+                                                    match change {
+                                                        ListChange::Remove { id } => {
+                                                            items.retain(|item| {
+                                                                item.persistence_id() != id
+                                                            });
+                                                        }
+                                                        change => change.apply_to_vec(&mut items),
+                                                    }
+                                                    let (filtered, current_pids) = if items
+                                                        .is_empty()
+                                                    {
                                                         predicates.clear();
                                                         predicate_results.clear();
                                                         merged_predicates = None;
@@ -8952,139 +9346,269 @@ impl ListBindingFunction {
                                                     };
 
                                                     if current_pids == last_emitted_pids {
-                                                        continue; // Skip redundant emission (popped item was filtered out)
+                                                        continue; // Skip redundant emission (only filtered-out items changed)
                                                     }
                                                     last_emitted_pids = current_pids;
 
                                                     return Some((
-                                                        Some(ListChange::Replace { items: Arc::from(filtered) }),
-                                                        (items, predicates, predicate_results, list_stream, merged_predicates, last_emitted_pids, config, construct_context, actor_context)
+                                                        Some(ListChange::Replace {
+                                                            items: Arc::from(filtered),
+                                                        }),
+                                                        (
+                                                            items,
+                                                            predicates,
+                                                            predicate_results,
+                                                            list_stream,
+                                                            merged_predicates,
+                                                            last_emitted_pids,
+                                                            config,
+                                                            construct_context,
+                                                            actor_context,
+                                                        ),
                                                     ));
                                                 }
-                                                continue;
-                                            }
-                                            ListChange::Clear => {
-                                                items.clear();
-                                                predicates.clear();
-                                                predicate_results.clear();
-                                                merged_predicates = None;
-                                                last_emitted_pids.clear(); // A2: Clear for empty list
-                                                return Some((
-                                                    Some(ListChange::Replace { items: Arc::from(Vec::<ActorHandle>::new()) }),
-                                                    (items, predicates, predicate_results, list_stream, merged_predicates, last_emitted_pids, config, construct_context, actor_context)
-                                                ));
-                                            }
-                                            // Explicitly handle all ListChange variants to catch future additions.
-                                            // These variants are not currently generated by any List API.
-                                            // If you're adding a new List operation that uses these,
-                                            // you must implement proper handling here.
-                                            ListChange::InsertAt { .. } => {
-                                                panic!(
-                                                    "List/retain received InsertAt event which is not yet implemented. \
-                                                    If you added a List/insert_at API, implement handling in create_retain_actor."
-                                                );
-                                            }
-                                            ListChange::UpdateAt { .. } => {
-                                                panic!(
-                                                    "List/retain received UpdateAt event which is not yet implemented. \
-                                                    If you added a List/update_at API, implement handling in create_retain_actor."
-                                                );
-                                            }
-                                            ListChange::Move { .. } => {
-                                                panic!(
-                                                    "List/retain received Move event which is not yet implemented. \
-                                                    If you added a List/move API, implement handling in create_retain_actor."
-                                                );
-                                            }
-                                        }
-                                    }
-                                    Either::Left((None, _)) => {
-                                        // List stream ended
-                                        return None;
-                                    }
-                                    Either::Right((Some(batch), _)) => {
-                                        let mut visibility_changed = false;
-                                        for (pid, is_true) in batch {
-                                            if predicates.contains_key(&pid) {
-                                                if predicate_results.get(&pid) != Some(&is_true) {
-                                                    visibility_changed = true;
+                                                ListChange::Pop => {
+                                                    if items.pop().is_some() {
+                                                        let (filtered, current_pids) =
+                                                            if items.is_empty() {
+                                                                predicates.clear();
+                                                                predicate_results.clear();
+                                                                merged_predicates = None;
+                                                                (Vec::new(), Vec::new())
+                                                            } else {
+                                                                let (
+                                                                    new_predicates,
+                                                                    new_predicate_results,
+                                                                    new_merged_predicates,
+                                                                    filtered,
+                                                                    current_pids,
+                                                                ) = Self::rebuild_retain_state(
+                                                                    &items,
+                                                                    &predicate_results,
+                                                                    &config,
+                                                                    construct_context.clone(),
+                                                                    actor_context.clone(),
+                                                                )
+                                                                .await;
+                                                                predicates = new_predicates;
+                                                                predicate_results =
+                                                                    new_predicate_results;
+                                                                merged_predicates =
+                                                                    new_merged_predicates;
+                                                                (filtered, current_pids)
+                                                            };
+
+                                                        if current_pids == last_emitted_pids {
+                                                            continue; // Skip redundant emission (popped item was filtered out)
+                                                        }
+                                                        last_emitted_pids = current_pids;
+
+                                                        return Some((
+                                                            Some(ListChange::Replace {
+                                                                items: Arc::from(filtered),
+                                                            }),
+                                                            (
+                                                                items,
+                                                                predicates,
+                                                                predicate_results,
+                                                                list_stream,
+                                                                merged_predicates,
+                                                                last_emitted_pids,
+                                                                config,
+                                                                construct_context,
+                                                                actor_context,
+                                                            ),
+                                                        ));
+                                                    }
+                                                    continue;
                                                 }
-                                                predicate_results.insert(pid, is_true);
-                                            }
-                                        }
-
-                                        if !visibility_changed {
-                                            continue; // No visibility changes
-                                        }
+                                                ListChange::Clear => {
+                                                    items.clear();
+                                                    predicates.clear();
+                                                    predicate_results.clear();
+                                                    merged_predicates = None;
+                                                    last_emitted_pids.clear(); // A2: Clear for empty list
+                                                    return Some((
+                                                        Some(ListChange::Replace {
+                                                            items: Arc::from(
+                                                                Vec::<ActorHandle>::new(),
+                                                            ),
+                                                        }),
+                                                        (
+                                                            items,
+                                                            predicates,
+                                                            predicate_results,
+                                                            list_stream,
+                                                            merged_predicates,
+                                                            last_emitted_pids,
+                                                            config,
+                                                            construct_context,
+                                                            actor_context,
+                                                        ),
+                                                    ));
+                                                }
+                                            }
+                                        }
+                                        Either::Left((None, _)) => {
+                                            // List stream ended
+                                            return None;
+                                        }
+                                        Either::Right((Some(batch), _)) => {
+                                            let mut visibility_changed = false;
+                                            for (pid, is_true) in batch {
+                                                if predicates.contains_key(&pid) {
+                                                    if predicate_results.get(&pid) != Some(&is_true)
+                                                    {
+                                                        visibility_changed = true;
+                                                    }
+                                                    predicate_results.insert(pid, is_true);
+                                                }
+                                            }
 
-                                        // Simpler and more robust than trying to synthesize a
-                                        // per-batch insert/remove diff from out-of-order predicate
-                                        // updates. Milestone 1 parity values correctness here.
-                                        let filtered: Vec<_> = items.iter()
-                                            .filter(|item| predicate_results.get(&item.persistence_id()) == Some(&true))
-                                            .cloned()
-                                            .collect();
+                                            if !visibility_changed {
+                                                continue; // No visibility changes
+                                            }
 
-                                        // A2: Output deduplication - skip if same as last emitted (order-aware)
-                                        let current_pids: Vec<_> = filtered.iter()
-                                            .map(|item| item.persistence_id())
-                                            .collect();
-                                        if current_pids == last_emitted_pids {
-                                            continue; // Skip redundant emission
-                                        }
-                                        last_emitted_pids = current_pids;
+                                            // Simpler and more robust than trying to synthesize a
+                                            // per-batch insert/remove diff from out-of-order predicate
+                                            // updates. Milestone 1 parity values correctness here.
+                                            let filtered: Vec<_> = items
+                                                .iter()
+                                                .filter(|item| {
+                                                    predicate_results.get(&item.persistence_id())
+                                                        == Some(&true)
+                                                })
+                                                .cloned()
+                                                .collect();
+
+                                            // A2: Output deduplication - skip if same as last emitted (order-aware)
+                                            let current_pids: Vec<_> = filtered
+                                                .iter()
+                                                .map(|item| item.persistence_id())
+                                                .collect();
+                                            if current_pids == last_emitted_pids {
+                                                continue; // Skip redundant emission
+                                            }
+                                            last_emitted_pids = current_pids;
 
-                                        return Some((
-                                            Some(ListChange::Replace { items: Arc::from(filtered) }),
-                                            (items, predicates, predicate_results, list_stream, merged_predicates, last_emitted_pids, config, construct_context, actor_context)
-                                        ));
-                                    }
-                                    Either::Right((None, _)) => {
-                                        if predicates.is_empty() {
-                                            merged_predicates = None;
-                                        } else {
-                                            let (
-                                                new_predicates,
-                                                new_predicate_results,
-                                                new_merged_predicates,
-                                                _filtered,
-                                                _current_pids,
-                                            ) = Self::rebuild_retain_state(
-                                                &items,
-                                                &predicate_results,
-                                                &config,
-                                                construct_context.clone(),
-                                                actor_context.clone(),
-                                            )
-                                            .await;
-                                            predicates = new_predicates;
-                                            predicate_results = new_predicate_results;
-                                            merged_predicates = new_merged_predicates;
-                                        }
-                                        continue;
-                                    }
-                                }
-                            } else {
-                                // No predicate streams yet, wait for list change
-                                match list_stream.next().await {
-                                    Some(ListChange::Replace { items: new_items }) => {
-                                        items = new_items.to_vec();
-
-                                        if items.is_empty() {
-                                            predicates.clear();
-                                            predicate_results.clear();
-                                            last_emitted_pids.clear(); // A2: Clear for empty list
                                             return Some((
-                                                Some(ListChange::Replace { items: Arc::from(Vec::<ActorHandle>::new()) }),
-                                                (items, predicates, predicate_results, list_stream, merged_predicates, last_emitted_pids, config, construct_context, actor_context)
+                                                Some(ListChange::Replace {
+                                                    items: Arc::from(filtered),
+                                                }),
+                                                (
+                                                    items,
+                                                    predicates,
+                                                    predicate_results,
+                                                    list_stream,
+                                                    merged_predicates,
+                                                    last_emitted_pids,
+                                                    config,
+                                                    construct_context,
+                                                    actor_context,
+                                                ),
                                             ));
-                                        } else {
+                                        }
+                                        Either::Right((None, _)) => {
+                                            if predicates.is_empty() {
+                                                merged_predicates = None;
+                                            } else {
+                                                let (
+                                                    new_predicates,
+                                                    new_predicate_results,
+                                                    new_merged_predicates,
+                                                    _filtered,
+                                                    _current_pids,
+                                                ) = Self::rebuild_retain_state(
+                                                    &items,
+                                                    &predicate_results,
+                                                    &config,
+                                                    construct_context.clone(),
+                                                    actor_context.clone(),
+                                                )
+                                                .await;
+                                                predicates = new_predicates;
+                                                predicate_results = new_predicate_results;
+                                                merged_predicates = new_merged_predicates;
+                                            }
+                                            continue;
+                                        }
+                                    }
+                                } else {
+                                    // No predicate streams yet, wait for list change
+                                    match list_stream.next().await {
+                                        Some(ListChange::Replace { items: new_items }) => {
+                                            items = new_items.to_vec();
+
+                                            if items.is_empty() {
+                                                predicates.clear();
+                                                predicate_results.clear();
+                                                last_emitted_pids.clear(); // A2: Clear for empty list
+                                                return Some((
+                                                    Some(ListChange::Replace {
+                                                        items: Arc::from(Vec::<ActorHandle>::new()),
+                                                    }),
+                                                    (
+                                                        items,
+                                                        predicates,
+                                                        predicate_results,
+                                                        list_stream,
+                                                        merged_predicates,
+                                                        last_emitted_pids,
+                                                        config,
+                                                        construct_context,
+                                                        actor_context,
+                                                    ),
+                                                ));
+                                            } else {
+                                                let (
+                                                    new_predicates,
+                                                    new_predicate_results,
+                                                    new_merged_predicates,
+                                                    filtered,
+                                                    new_last_emitted_pids,
+                                                ) = Self::rebuild_retain_state(
+                                                    &items,
+                                                    &predicate_results,
+                                                    &config,
+                                                    construct_context.clone(),
+                                                    actor_context.clone(),
+                                                )
+                                                .await;
+                                                predicates = new_predicates;
+                                                predicate_results = new_predicate_results;
+                                                merged_predicates = new_merged_predicates;
+                                                last_emitted_pids = new_last_emitted_pids;
+
+                                                return Some((
+                                                    Some(ListChange::Replace {
+                                                        items: Arc::from(filtered),
+                                                    }),
+                                                    (
+                                                        items,
+                                                        predicates,
+                                                        predicate_results,
+                                                        list_stream,
+                                                        merged_predicates,
+                                                        last_emitted_pids,
+                                                        config,
+                                                        construct_context,
+                                                        actor_context,
+                                                    ),
+                                                ));
+                                            }
+                                        }
+                                        Some(
+                                            change @ (ListChange::Push { .. }
+                                            | ListChange::InsertAt { .. }),
+                                        ) => {
+                                            // Only index 0 fits an empty list; `apply_to_vec` ignores any other
+                                            change.apply_to_vec(&mut items);
                                             let (
                                                 new_predicates,
                                                 new_predicate_results,
                                                 new_merged_predicates,
                                                 filtered,
-                                                new_last_emitted_pids,
+                                                current_pids,
                                             ) = Self::rebuild_retain_state(
                                                 &items,
                                                 &predicate_results,
@@ -9096,78 +9620,45 @@ impl ListBindingFunction {
                                             predicates = new_predicates;
                                             predicate_results = new_predicate_results;
                                             merged_predicates = new_merged_predicates;
-                                            last_emitted_pids = new_last_emitted_pids;
+
+                                            if current_pids == last_emitted_pids {
+                                                continue; // Skip redundant emission
+                                            }
+                                            last_emitted_pids = current_pids;
 
                                             return Some((
-                                                Some(ListChange::Replace { items: Arc::from(filtered) }),
-                                                (items, predicates, predicate_results, list_stream, merged_predicates, last_emitted_pids, config, construct_context, actor_context)
+                                                Some(ListChange::Replace {
+                                                    items: Arc::from(filtered),
+                                                }),
+                                                (
+                                                    items,
+                                                    predicates,
+                                                    predicate_results,
+                                                    list_stream,
+                                                    merged_predicates,
+                                                    last_emitted_pids,
+                                                    config,
+                                                    construct_context,
+                                                    actor_context,
+                                                ),
                                             ));
                                         }
+                                        // When merged_predicates is None, the list is empty.
+                                        // These operations on an empty list are no-ops:
+                                        Some(ListChange::Remove { .. }) => continue, // Can't remove from empty
+                                        Some(ListChange::Pop) => continue, // Can't pop from empty
+                                        Some(ListChange::Clear) => continue, // Already empty
+                                        Some(
+                                            ListChange::UpdateAt { .. } | ListChange::Move { .. },
+                                        ) => continue, // Nothing to update or move
+                                        None => return None,
                                     }
-                                    Some(ListChange::Push { item }) => {
-                                        items.push(item);
-                                        let (
-                                            new_predicates,
-                                            new_predicate_results,
-                                            new_merged_predicates,
-                                            filtered,
-                                            current_pids,
-                                        ) = Self::rebuild_retain_state(
-                                            &items,
-                                            &predicate_results,
-                                            &config,
-                                            construct_context.clone(),
-                                            actor_context.clone(),
-                                        )
-                                        .await;
-                                        predicates = new_predicates;
-                                        predicate_results = new_predicate_results;
-                                        merged_predicates = new_merged_predicates;
-
-                                        if current_pids == last_emitted_pids {
-                                            continue; // Skip redundant emission
-                                        }
-                                        last_emitted_pids = current_pids;
-
-                                        return Some((
-                                            Some(ListChange::Replace { items: Arc::from(filtered) }),
-                                            (items, predicates, predicate_results, list_stream, merged_predicates, last_emitted_pids, config, construct_context, actor_context)
-                                        ));
-                                    }
-                                    // When merged_predicates is None, the list is empty.
-                                    // These operations on an empty list are no-ops:
-                                    Some(ListChange::Remove { .. }) => continue, // Can't remove from empty
-                                    Some(ListChange::Pop) => continue,           // Can't pop from empty
-                                    Some(ListChange::Clear) => continue,         // Already empty
-                                    // These variants are not currently generated by any List API.
-                                    // If you're adding a new List operation that uses these,
-                                    // you must implement proper handling here.
-                                    Some(ListChange::InsertAt { .. }) => {
-                                        panic!(
-                                            "List/retain received InsertAt event which is not yet implemented. \
-                                            If you added a List/insert_at API, implement handling in create_retain_actor."
-                                        );
-                                    }
-                                    Some(ListChange::UpdateAt { .. }) => {
-                                        panic!(
-                                            "List/retain received UpdateAt event which is not yet implemented. \
-                                            If you added a List/update_at API, implement handling in create_retain_actor."
-                                        );
-                                    }
-                                    Some(ListChange::Move { .. }) => {
-                                        panic!(
-                                            "List/retain received Move event which is not yet implemented. \
-                                            If you added a List/move API, implement handling in create_retain_actor."
-                                        );
-                                    }
-                                    None => return None,
                                 }
                             }
                         }
-                    }
-                }
-            )
-            .filter_map(future::ready)
+                    },
+                )
+                .filter_map(future::ready)
             },
         );
 
@@ -9616,173 +10107,218 @@ impl ListBindingFunction {
                 let construct_info_id_inner = construct_info_id.clone();
                 let construct_context_inner = construct_context.clone();
 
-                list.stream().scan(
-                Vec::<(ActorHandle, ActorHandle)>::new(),
-                move |item_predicates, change| {
-                    let config = config.clone();
-                    let construct_context = construct_context.clone();
-                    let actor_context = actor_context.clone();
-
-                    match &change {
-                        ListChange::Replace { items } => {
-                            *item_predicates = items.iter().enumerate().map(|(idx, item)| {
-                                let predicate = Self::transform_item(
-                                    item.clone(),
-                                    idx,
-                                    &config,
-                                    construct_context.clone(),
-                                    actor_context.clone(),
-                                );
-                                (item.clone(), predicate)
-                            }).collect();
-                        }
-                        ListChange::Push { item } => {
-                            let idx = item_predicates.len();
-                            let predicate = Self::transform_item(
-                                item.clone(),
-                                idx,
-                                &config,
-                                construct_context.clone(),
-                                actor_context.clone(),
-                            );
-                            item_predicates.push((item.clone(), predicate));
-                        }
-                        ListChange::Clear => {
-                            item_predicates.clear();
-                        }
-                        ListChange::Pop => {
-                            item_predicates.pop();
-                        }
-                        ListChange::Remove { id } => {
-                            // Find item by PersistenceId
-                            if let Some(index) = item_predicates.iter().position(|(item, _)| item.persistence_id() == *id) {
-                                item_predicates.remove(index);
+                list.stream()
+                    .scan(
+                        Vec::<(ActorHandle, ActorHandle)>::new(),
+                        move |item_predicates, change| {
+                            let config = config.clone();
+                            let construct_context = construct_context.clone();
+                            let actor_context = actor_context.clone();
+
+                            match &change {
+                                ListChange::Replace { items } => {
+                                    *item_predicates = items
+                                        .iter()
+                                        .enumerate()
+                                        .map(|(idx, item)| {
+                                            let predicate = Self::transform_item(
+                                                item.clone(),
+                                                idx,
+                                                &config,
+                                                construct_context.clone(),
+                                                actor_context.clone(),
+                                            );
+                                            (item.clone(), predicate)
+                                        })
+                                        .collect();
+                                }
+                                ListChange::Push { item } => {
+                                    let idx = item_predicates.len();
+                                    let predicate = Self::transform_item(
+                                        item.clone(),
+                                        idx,
+                                        &config,
+                                        construct_context.clone(),
+                                        actor_context.clone(),
+                                    );
+                                    item_predicates.push((item.clone(), predicate));
+                                }
+                                ListChange::Clear => {
+                                    item_predicates.clear();
+                                }
+                                ListChange::Pop => {
+                                    item_predicates.pop();
+                                }
+                                ListChange::Remove { id } => {
+                                    // Find item by PersistenceId
+                                    if let Some(index) = item_predicates
+                                        .iter()
+                                        .position(|(item, _)| item.persistence_id() == *id)
+                                    {
+                                        item_predicates.remove(index);
+                                    }
+                                }
+                                ListChange::InsertAt { index, item }
+                                    if *index <= item_predicates.len() =>
+                                {
+                                    let predicate = Self::transform_item(
+                                        item.clone(),
+                                        *index,
+                                        &config,
+                                        construct_context.clone(),
+                                        actor_context.clone(),
+                                    );
+                                    item_predicates.insert(*index, (item.clone(), predicate));
+                                }
+                                ListChange::UpdateAt { index, item }
+                                    if *index < item_predicates.len() =>
+                                {
+                                    let predicate = Self::transform_item(
+                                        item.clone(),
+                                        *index,
+                                        &config,
+                                        construct_context.clone(),
+                                        actor_context.clone(),
+                                    );
+                                    item_predicates[*index] = (item.clone(), predicate);
+                                }
+                                ListChange::Move {
+                                    old_index,
+                                    new_index,
+                                } if *old_index < item_predicates.len() => {
+                                    let entry = item_predicates.remove(*old_index);
+                                    let insert_index = (*new_index).min(item_predicates.len());
+                                    item_predicates.insert(insert_index, entry);
+                                }
+                                // Out-of-range changes are ignored, as in `ListChange::apply_to_vec`.
+                                ListChange::InsertAt { .. }
+                                | ListChange::UpdateAt { .. }
+                                | ListChange::Move { .. } => {}
                             }
-                        }
-                        // Explicitly handle all ListChange variants to catch future additions.
-                        // These variants are not currently generated by any List API.
-                        ListChange::InsertAt { .. } => {
-                            panic!(
-                                "List/every or List/any received InsertAt event which is not yet implemented. \
-                                If you added a List/insert_at API, implement handling in create_every_any_actor."
-                            );
-                        }
-                        ListChange::UpdateAt { .. } => {
-                            panic!(
-                                "List/every or List/any received UpdateAt event which is not yet implemented. \
-                                If you added a List/update_at API, implement handling in create_every_any_actor."
-                            );
-                        }
-                        ListChange::Move { .. } => {
-                            panic!(
-                                "List/every or List/any received Move event which is not yet implemented. \
-                                If you added a List/move API, implement handling in create_every_any_actor."
-                            );
-                        }
-                    }
 
-                    future::ready(Some(item_predicates.clone()))
-                }
-            ).flat_map(move |item_predicates| {
-                let _construct_info_id = construct_info_id_inner.clone();
-                let construct_context = construct_context_inner.clone();
-
-                if item_predicates.is_empty() {
-                    // Empty list: every([]) = True, any([]) = False
-                    let result = if is_every { "True" } else { "False" };
-                    return stream::once(future::ready(Tag::new_value(
-                        ConstructInfo::new(
-                            construct_info_id.clone().with_child_id(0),
-                            None,
-                            if is_every { "List/every result" } else { "List/any result" },
-                        ),
-                        construct_context,
-                        ValueIdempotencyKey::new(),
-                        result.to_string(),
-                    ))).boxed_local();
-                }
+                            future::ready(Some(item_predicates.clone()))
+                        },
+                    )
+                    .flat_map(move |item_predicates| {
+                        let _construct_info_id = construct_info_id_inner.clone();
+                        let construct_context = construct_context_inner.clone();
+
+                        if item_predicates.is_empty() {
+                            // Empty list: every([]) = True, any([]) = False
+                            let result = if is_every { "True" } else { "False" };
+                            return stream::once(future::ready(Tag::new_value(
+                                ConstructInfo::new(
+                                    construct_info_id.clone().with_child_id(0),
+                                    None,
+                                    if is_every {
+                                        "List/every result"
+                                    } else {
+                                        "List/any result"
+                                    },
+                                ),
+                                construct_context,
+                                ValueIdempotencyKey::new(),
+                                result.to_string(),
+                            )))
+                            .boxed_local();
+                        }
 
-                // Clone for the map closure
-                let construct_info_id_map = construct_info_id.clone();
-                let construct_context_map = construct_context.clone();
-
-                // B2: Add boolean deduplication to each predicate stream
-                let predicate_streams: Vec<_> = item_predicates.iter().enumerate().map(|(idx, (_, pred))| {
-                    pred.clone().stream()
-                        .map(move |value| {
-                            let is_true = match &value {
-                                Value::Tag(tag, _) => tag.tag() == "True",
-                                _ => false,
-                            };
-                            (idx, is_true)
-                        })
-                        // B2: Deduplicate booleans - skip emission if same as previous
-                        .scan(None::<bool>, |last_bool, (idx, is_true)| {
-                            if Some(is_true) == *last_bool {
-                                future::ready(Some(None)) // Skip duplicate
-                            } else {
-                                *last_bool = Some(is_true);
-                                future::ready(Some(Some((idx, is_true))))
-                            }
-                        })
-                        .filter_map(future::ready)
-                }).collect();
+                        // Clone for the map closure
+                        let construct_info_id_map = construct_info_id.clone();
+                        let construct_context_map = construct_context.clone();
 
-                // A3: Coalesce to batch all synchronously-available predicate updates
-                // B4: Track true_count/evaluated_count for O(1) every/any evaluation
-                let total = item_predicates.len();
-                coalesce(stream::select_all(predicate_streams))
-                    .scan(
-                        (vec![None::<bool>; total], 0usize, 0usize), // (states, true_count, evaluated_count)
-                        move |(states, true_count, evaluated_count), batch| {
-                            // Process entire batch of predicate updates at once
-                            for (idx, is_true) in batch {
-                                if idx < states.len() {
-                                    match states[idx] {
-                                        None => {
-                                            // First evaluation of this predicate
-                                            *evaluated_count += 1;
-                                            if is_true { *true_count += 1; }
+                        // B2: Add boolean deduplication to each predicate stream
+                        let predicate_streams: Vec<_> = item_predicates
+                            .iter()
+                            .enumerate()
+                            .map(|(idx, (_, pred))| {
+                                pred.clone()
+                                    .stream()
+                                    .map(move |value| {
+                                        let is_true = match &value {
+                                            Value::Tag(tag, _) => tag.tag() == "True",
+                                            _ => false,
+                                        };
+                                        (idx, is_true)
+                                    })
+                                    // B2: Deduplicate booleans - skip emission if same as previous
+                                    .scan(None::<bool>, |last_bool, (idx, is_true)| {
+                                        if Some(is_true) == *last_bool {
+                                            future::ready(Some(None)) // Skip duplicate
+                                        } else {
+                                            *last_bool = Some(is_true);
+                                            future::ready(Some(Some((idx, is_true))))
                                         }
-                                        Some(was_true) => {
-                                            // Update existing predicate
-                                            if was_true && !is_true { *true_count -= 1; }
-                                            if !was_true && is_true { *true_count += 1; }
+                                    })
+                                    .filter_map(future::ready)
+                            })
+                            .collect();
+
+                        // A3: Coalesce to batch all synchronously-available predicate updates
+                        // B4: Track true_count/evaluated_count for O(1) every/any evaluation
+                        let total = item_predicates.len();
+                        coalesce(stream::select_all(predicate_streams))
+                            .scan(
+                                (vec![None::<bool>; total], 0usize, 0usize), // (states, true_count, evaluated_count)
+                                move |(states, true_count, evaluated_count), batch| {
+                                    // Process entire batch of predicate updates at once
+                                    for (idx, is_true) in batch {
+                                        if idx < states.len() {
+                                            match states[idx] {
+                                                None => {
+                                                    // First evaluation of this predicate
+                                                    *evaluated_count += 1;
+                                                    if is_true {
+                                                        *true_count += 1;
+                                                    }
+                                                }
+                                                Some(was_true) => {
+                                                    // Update existing predicate
+                                                    if was_true && !is_true {
+                                                        *true_count -= 1;
+                                                    }
+                                                    if !was_true && is_true {
+                                                        *true_count += 1;
+                                                    }
+                                                }
+                                            }
+                                            states[idx] = Some(is_true);
                                         }
                                     }
-                                    states[idx] = Some(is_true);
-                                }
-                            }
 
-                            if *evaluated_count == total {
-                                // O(1) check instead of O(N) scan
-                                let result = if is_every {
-                                    *true_count == total
-                                } else {
-                                    *true_count > 0
-                                };
-                                future::ready(Some(Some(result)))
-                            } else {
-                                future::ready(Some(None))
-                            }
-                        }
-                    )
-                    .filter_map(future::ready)
-                    .map(move |result| {
-                        let tag = if result { "True" } else { "False" };
-                        Tag::new_value(
-                            ConstructInfo::new(
-                                construct_info_id_map.clone().with_child_id(0),
-                                None,
-                                if is_every { "List/every result" } else { "List/any result" },
-                            ),
-                            construct_context_map.clone(),
-                            ValueIdempotencyKey::new(),
-                            tag.to_string(),
-                        )
+                                    if *evaluated_count == total {
+                                        // O(1) check instead of O(N) scan
+                                        let result = if is_every {
+                                            *true_count == total
+                                        } else {
+                                            *true_count > 0
+                                        };
+                                        future::ready(Some(Some(result)))
+                                    } else {
+                                        future::ready(Some(None))
+                                    }
+                                },
+                            )
+                            .filter_map(future::ready)
+                            .map(move |result| {
+                                let tag = if result { "True" } else { "False" };
+                                Tag::new_value(
+                                    ConstructInfo::new(
+                                        construct_info_id_map.clone().with_child_id(0),
+                                        None,
+                                        if is_every {
+                                            "List/every result"
+                                        } else {
+                                            "List/any result"
+                                        },
+                                    ),
+                                    construct_context_map.clone(),
+                                    ValueIdempotencyKey::new(),
+                                    tag.to_string(),
+                                )
+                            })
+                            .boxed_local()
                     })
-                    .boxed_local()
-            })
             },
         );
 
@@ -9806,238 +10342,799 @@ impl ListBindingFunction {
         create_actor_complete(
             construct_info,
             actor_context_for_result,
-            TypedStream::infinite(deduplicated_stream),
+            TypedStream::infinite(deduplicated_stream),
+            parser::PersistenceId::new(),
+            scope_id,
+        )
+    }
+
+    /// Creates a count_where actor that produces the number of items whose predicate is True.
+    ///
+    /// Unlike `List/retain |> List/count`, no filtered list is built. Each item gets its
+    /// predicate once, when it joins the list, and the count moves by one per copy on
+    /// membership changes and predicate flips (see `CountWhereState`).
+    fn create_count_where_actor(
+        construct_info: ConstructInfoComplete,
+        construct_context: ConstructContext,
+        actor_context: ActorContext,
+        source_list_actor: ActorHandle,
+        config: Arc<ListBindingConfig>,
+    ) -> ActorHandle {
+        use zoon::futures_util::future::Either;
+
+        type PredicateUpdates = stream::SelectAll<
+            stream::Abortable<LocalBoxStream<'static, (parser::PersistenceId, bool)>>,
+        >;
+
+        let construct_info_id = construct_info.id.clone();
+        let construct_context_for_result = construct_context.clone();
+        let actor_context_for_result = actor_context.clone();
+
+        // Clone subscription scope for scope cancellation check
+        let subscription_scope = actor_context.subscription_scope.clone();
+
+        let count_stream = switch_map(
+            source_list_actor
+                .clone()
+                .stream()
+                // Check if subscription scope is cancelled (e.g., WHILE arm switched)
+                .take_while(move |_| {
+                    let is_active = subscription_scope
+                        .as_ref()
+                        .map_or(true, |s| !s.is_cancelled());
+                    future::ready(is_active)
+                })
+                .filter_map(|value| {
+                    future::ready(match value {
+                        Value::List(list, _) => Some(list),
+                        _ => None,
+                    })
+                })
+                // Deduplicate only identical runtime list instances.
+                .scan(None, |prev_key: &mut Option<usize>, list| {
+                    let list_key = list_instance_key(&list);
+                    if prev_key.as_ref() == Some(&list_key) {
+                        future::ready(Some(None))
+                    } else {
+                        *prev_key = Some(list_key);
+                        future::ready(Some(Some(list)))
+                    }
+                })
+                .filter_map(future::ready),
+            move |list| {
+                let config = config.clone();
+                let construct_context = construct_context.clone();
+                let actor_context = actor_context.clone();
+                let list_stream: Pin<Box<dyn Stream<Item = ListChange>>> = Box::pin(list.stream());
+
+                stream::unfold(
+                    (
+                        CountWhereState::<(stream::AbortHandle, ActorHandle)>::new(),
+                        list_stream,
+                        PredicateUpdates::new(),
+                        None::<usize>,
+                    ),
+                    move |(mut state, mut list_stream, mut predicate_updates, mut last_count)| {
+                        let config = config.clone();
+                        let construct_context = construct_context.clone();
+                        let actor_context = actor_context.clone();
+                        async move {
+                            loop {
+                                let update = if predicate_updates.is_empty() {
+                                    Either::Left(list_stream.next().await)
+                                } else {
+                                    match future::select(list_stream.next(), predicate_updates.next()).await {
+                                        Either::Left((change, _)) => Either::Left(change),
+                                        Either::Right((predicate_update, _)) => Either::Right(predicate_update),
+                                    }
+                                };
+
+                                // (index, item) pairs, inserted in order
+                                let mut added = Vec::new();
+                                match update {
+                                    Either::Left(None) => return None,
+                                    Either::Left(Some(change)) => match change {
+                                        ListChange::Replace { items } => {
+                                            for (abort_handle, _) in state.clear() {
+                                                abort_handle.abort();
+                                            }
+                                            added.extend(items.iter().cloned().enumerate());
+                                        }
+                                        ListChange::Push { item } => added.push((state.len(), item)),
+                                        ListChange::InsertAt { index, item } if index <= state.len() => {
+                                            added.push((index, item));
+                                        }
+                                        ListChange::UpdateAt { index, item } if index < state.len() => {
+                                            if let Some((abort_handle, _)) = state.remove_at(index) {
+                                                abort_handle.abort();
+                                            }
+                                            added.push((index, item));
+                                        }
+                                        ListChange::Move { old_index, new_index } => {
+                                            state.move_item(old_index, new_index);
+                                        }
+                                        ListChange::Remove { id } => {
+                                            for (abort_handle, _) in state.remove(id) {
+                                                abort_handle.abort();
+                                            }
+                                        }
+                                        ListChange::Pop => {
+                                            if let Some((abort_handle, _)) = state.pop() {
+                                                abort_handle.abort();
+                                            }
+                                        }
+                                        ListChange::Clear => {
+                                            for (abort_handle, _) in state.clear() {
+                                                abort_handle.abort();
+                                            }
+                                        }
+                                        // Out-of-range changes are ignored, as in `ListChange::apply_to_vec`.
+                                        ListChange::InsertAt { .. } | ListChange::UpdateAt { .. } => {}
+                                    },
+                                    Either::Right(Some((pid, is_true))) => state.set(pid, is_true),
+                                    // Only aborted predicates end; the next loop sees the shrunk set.
+                                    Either::Right(None) => continue,
+                                }
+
+                                for (index, item) in added {
+                                    let pid = item.persistence_id();
+                                    let predicate = Self::transform_item(
+                                        item,
+                                        index,
+                                        &config,
+                                        construct_context.clone(),
+                                        actor_context.clone(),
+                                    );
+                                    // Same initial read and fallback as rebuild_retain_state.
+                                    let fallback = state.result(pid).unwrap_or(true);
+                                    let is_true = match predicate.current_value().await {
+                                        Ok(value) => matches!(&value, Value::Tag(tag, _) if tag.tag() == "True"),
+                                        Err(_) => fallback,
+                                    };
+                                    let (updates, abort_handle) = stream::abortable(
+                                        predicate
+                                            .clone()
+                                            .stream_from_now()
+                                            .map(move |value| {
+                                                (pid, matches!(&value, Value::Tag(tag, _) if tag.tag() == "True"))
+                                            })
+                                            .boxed_local(),
+                                    );
+                                    predicate_updates.push(updates);
+                                    state.insert(index, pid, is_true, (abort_handle, predicate));
+                                }
+
+                                // Only emit when the count actually changes
+                                let count = state.count();
+                                if last_count != Some(count) {
+                                    last_count = Some(count);
+                                    return Some((count, (state, list_stream, predicate_updates, last_count)));
+                                }
+                            }
+                        }
+                    },
+                )
+            },
+        )
+        .map(move |count| {
+            Number::new_value(
+                ConstructInfo::new(
+                    construct_info_id.clone().with_child_id(0),
+                    None,
+                    "List/count_where result",
+                ),
+                construct_context_for_result.clone(),
+                ValueIdempotencyKey::new(),
+                f64::from(u32::try_from(count).unwrap_or(u32::MAX)),
+            )
+        });
+
+        let scope_id = actor_context_for_result.scope_id();
+        create_actor_complete(
+            construct_info,
+            actor_context_for_result,
+            TypedStream::infinite(count_stream),
+            parser::PersistenceId::new(),
+            scope_id,
+        )
+    }
+
+    /// Creates a sort_by actor that sorts list items based on a key expression.
+    /// When any item's key changes, emits an updated sorted list.
+    fn create_sort_by_actor(
+        construct_info: ConstructInfoComplete,
+        construct_context: ConstructContext,
+        actor_context: ActorContext,
+        source_list_actor: ActorHandle,
+        config: Arc<ListBindingConfig>,
+    ) -> ActorHandle {
+        let construct_info_id = construct_info.id.clone();
+
+        // Clone for use after the flat_map chain
+        let actor_context_for_list = actor_context.clone();
+        let actor_context_for_result = actor_context.clone();
+
+        // Clone subscription scope for scope cancellation check
+        let subscription_scope = actor_context.subscription_scope.clone();
+
+        // Create a stream that:
+        // 1. Subscribes to source list changes (stream() yields automatically)
+        // 2. For each item, evaluates key expression and subscribes to its changes
+        // 3. When list or any key changes, emits sorted Replace
+        // Use switch_map for proper list replacement handling:
+        // When source emits a new list, cancel old subscription and start fresh.
+        // But filter out re-emissions of the same list by ID to avoid cancelling
+        // subscriptions unnecessarily.
+        let value_stream = switch_map(
+            source_list_actor
+                .clone()
+                .stream()
+                // Check if subscription scope is cancelled (e.g., WHILE arm switched)
+                .take_while(move |_| {
+                    let is_active = subscription_scope
+                        .as_ref()
+                        .map_or(true, |s| !s.is_cancelled());
+                    future::ready(is_active)
+                })
+                .filter_map(|value| {
+                    future::ready(match value {
+                        Value::List(list, _) => Some(list),
+                        _ => None,
+                    })
+                })
+                // Deduplicate only identical runtime list instances.
+                .scan(None, |prev_key: &mut Option<usize>, list| {
+                    let list_key = list_instance_key(&list);
+                    if prev_key.as_ref() == Some(&list_key) {
+                        future::ready(Some(None))
+                    } else {
+                        *prev_key = Some(list_key);
+                        future::ready(Some(Some(list)))
+                    }
+                })
+                .filter_map(future::ready),
+            move |list| {
+                let config = config.clone();
+                let construct_context = construct_context.clone();
+                let actor_context = actor_context.clone();
+                let construct_info_id = construct_info_id.clone();
+
+                // Clone for the second flat_map
+                let construct_info_id_inner = construct_info_id.clone();
+
+                // Track items and their keys
+                list.stream()
+                    .scan(
+                        Vec::<(ActorHandle, ActorHandle)>::new(), // (item, key_actor)
+                        move |item_keys, change| {
+                            let config = config.clone();
+                            let construct_context = construct_context.clone();
+                            let actor_context = actor_context.clone();
+
+                            // Apply change and update key actors
+                            match &change {
+                                ListChange::Replace { items } => {
+                                    *item_keys = items
+                                        .iter()
+                                        .enumerate()
+                                        .map(|(idx, item)| {
+                                            let key_actor = Self::transform_item(
+                                                item.clone(),
+                                                idx,
+                                                &config,
+                                                construct_context.clone(),
+                                                actor_context.clone(),
+                                            );
+                                            (item.clone(), key_actor)
+                                        })
+                                        .collect();
+                                }
+                                ListChange::Push { item } => {
+                                    let idx = item_keys.len();
+                                    let key_actor = Self::transform_item(
+                                        item.clone(),
+                                        idx,
+                                        &config,
+                                        construct_context.clone(),
+                                        actor_context.clone(),
+                                    );
+                                    item_keys.push((item.clone(), key_actor));
+                                }
+                                ListChange::InsertAt { index, item } => {
+                                    let key_actor = Self::transform_item(
+                                        item.clone(),
+                                        *index,
+                                        &config,
+                                        construct_context.clone(),
+                                        actor_context.clone(),
+                                    );
+                                    if *index <= item_keys.len() {
+                                        item_keys.insert(*index, (item.clone(), key_actor));
+                                    }
+                                }
+                                ListChange::Remove { id } => {
+                                    // Find item by PersistenceId
+                                    if let Some(index) = item_keys
+                                        .iter()
+                                        .position(|(item, _)| item.persistence_id() == *id)
+                                    {
+                                        item_keys.remove(index);
+                                    }
+                                }
+                                ListChange::Clear => {
+                                    item_keys.clear();
+                                }
+                                ListChange::Pop => {
+                                    item_keys.pop();
+                                }
+                                ListChange::UpdateAt { index, item }
+                                    if *index < item_keys.len() =>
+                                {
+                                    let key_actor = Self::transform_item(
+                                        item.clone(),
+                                        *index,
+                                        &config,
+                                        construct_context.clone(),
+                                        actor_context.clone(),
+                                    );
+                                    item_keys[*index] = (item.clone(), key_actor);
+                                }
+                                ListChange::Move {
+                                    old_index,
+                                    new_index,
+                                } if *old_index < item_keys.len() => {
+                                    let entry = item_keys.remove(*old_index);
+                                    let insert_index = (*new_index).min(item_keys.len());
+                                    item_keys.insert(insert_index, entry);
+                                }
+                                // Out-of-range changes are ignored, as in `ListChange::apply_to_vec`.
+                                ListChange::UpdateAt { .. } | ListChange::Move { .. } => {}
+                            }
+
+                            future::ready(Some(item_keys.clone()))
+                        },
+                    )
+                    .flat_map(move |item_keys| {
+                        let _construct_info_id = construct_info_id_inner.clone();
+
+                        if item_keys.is_empty() {
+                            // Empty list - emit empty Replace
+                            return stream::once(future::ready(ListChange::Replace {
+                                items: Arc::from(Vec::<ActorHandle>::new()),
+                            }))
+                            .boxed_local();
+                        }
+
+                        // Subscribe to all keys and emit sorted list when any changes
+                        // A3: Use coalesce to batch simultaneous key updates
+                        let key_streams: Vec<_> = item_keys
+                            .iter()
+                            .enumerate()
+                            .map(|(idx, (item, key_actor))| {
+                                let item = item.clone();
+                                key_actor
+                                    .clone()
+                                    .stream()
+                                    .map(move |value| (idx, item.clone(), value))
+                            })
+                            .collect();
+
+                        // B5: Incremental sort using BTreeMap for O(log n) key updates.
+                        // State: (items, key_values, sorted_tree, prev_sorted_indices, unevaluated_count)
+                        // BTreeMap key: (SortKey, usize) where usize is original index for stable tie-breaking.
+                        // BTreeMap value: usize (index into items vec).
+                        let item_count = item_keys.len();
+                        coalesce(stream::select_all(key_streams))
+                            .scan(
+                                (
+                                    item_keys
+                                        .iter()
+                                        .map(|(item, _)| item.clone())
+                                        .collect::<Vec<_>>(),
+                                    vec![None::<SortKey>; item_count],
+                                    std::collections::BTreeMap::<(SortKey, usize), usize>::new(),
+                                    Vec::<usize>::new(), // prev_sorted_indices
+                                    item_count,          // unevaluated_count
+                                ),
+                                move |(
+                                    items,
+                                    key_values,
+                                    sorted_tree,
+                                    prev_sorted,
+                                    unevaluated,
+                                ),
+                                      batch| {
+                                    // Update keys in BTreeMap
+                                    for (idx, _item, value) in batch {
+                                        let new_key = SortKey::from_value(&value);
+                                        if idx < key_values.len() {
+                                            // Remove old key from tree if it existed
+                                            if let Some(old_key) = key_values[idx].take() {
+                                                sorted_tree.remove(&(old_key, idx));
+                                            } else {
+                                                // First evaluation of this key
+                                                *unevaluated = unevaluated.saturating_sub(1);
+                                            }
+                                            // Insert new key
+                                            sorted_tree.insert((new_key.clone(), idx), idx);
+                                            key_values[idx] = Some(new_key);
+                                        }
+                                    }
+
+                                    // Wait until all items have evaluated keys
+                                    if *unevaluated > 0 {
+                                        return future::ready(Some(None));
+                                    }
+
+                                    // Read sorted order from BTreeMap (already in order)
+                                    let new_sorted: Vec<usize> =
+                                        sorted_tree.values().copied().collect();
+
+                                    if prev_sorted.is_empty() {
+                                        // First evaluation - emit Replace
+                                        let sorted_items: Vec<ActorHandle> = new_sorted
+                                            .iter()
+                                            .map(|&idx| items[idx].clone())
+                                            .collect();
+                                        *prev_sorted = new_sorted;
+                                        future::ready(Some(Some(vec![ListChange::Replace {
+                                            items: Arc::from(sorted_items),
+                                        }])))
+                                    } else if *prev_sorted == new_sorted {
+                                        // No change in sorted order
+                                        future::ready(Some(None))
+                                    } else {
+                                        // B5: Compute incremental Remove+InsertAt operations
+                                        let changes =
+                                            compute_sort_diff(items, prev_sorted, &new_sorted);
+                                        *prev_sorted = new_sorted;
+                                        future::ready(Some(Some(changes)))
+                                    }
+                                },
+                            )
+                            .filter_map(future::ready)
+                            .flat_map(|changes| stream::iter(changes))
+                            .boxed_local()
+                    })
+            },
+        );
+
+        let list = List::new_with_change_stream(
+            ConstructInfo::new(
+                construct_info.id.clone().with_child_id(0),
+                None,
+                "List/sort_by result",
+            ),
+            actor_context_for_list,
+            value_stream,
+            source_list_actor.clone(),
+        );
+
+        let scope_id = actor_context_for_result.scope_id();
+        create_actor_complete(
+            construct_info,
+            actor_context_for_result,
+            constant(Value::List(
+                Arc::new(list),
+                ValueMetadata::new(ValueIdempotencyKey::new()),
+            )),
+            parser::PersistenceId::new(),
+            scope_id,
+        )
+    }
+
+    /// Creates a unique_by actor that keeps the first item of each key.
+    /// Keys are compared structurally; the kept items are re-picked whenever the
+    /// list or any key changes.
+    fn create_unique_by_actor(
+        construct_info: ConstructInfoComplete,
+        construct_context: ConstructContext,
+        actor_context: ActorContext,
+        source_list_actor: ActorHandle,
+        config: Arc<ListBindingConfig>,
+    ) -> ActorHandle {
+        let actor_context_for_list = actor_context.clone();
+        let actor_context_for_result = actor_context.clone();
+
+        let change_stream = switch_map(
+            Self::distinct_source_lists(source_list_actor.clone(), &actor_context),
+            move |list| {
+                let item_keys = Self::item_transform_snapshots(
+                    list,
+                    config.clone(),
+                    construct_context.clone(),
+                    actor_context.clone(),
+                );
+                let kept_items = switch_map(item_keys, |item_keys| {
+                    Self::latest_transform_values(item_keys).then(|(items, keys)| async move {
+                        first_distinct_indices(&keys)
+                            .await
+                            .into_iter()
+                            .map(|index| items[index].clone())
+                            .collect::<Vec<_>>()
+                    })
+                });
+                kept_item_changes(kept_items)
+            },
+        );
+
+        let list = List::new_with_change_stream(
+            ConstructInfo::new(
+                construct_info.id.clone().with_child_id(0),
+                None,
+                "List/unique_by result",
+            ),
+            actor_context_for_list,
+            change_stream,
+            source_list_actor.clone(),
+        );
+
+        let scope_id = actor_context_for_result.scope_id();
+        create_actor_complete(
+            construct_info,
+            actor_context_for_result,
+            constant(Value::List(
+                Arc::new(list),
+                ValueMetadata::new(ValueIdempotencyKey::new()),
+            )),
+            parser::PersistenceId::new(),
+            scope_id,
+        )
+    }
+
+    /// Creates a find actor that follows the first item whose predicate is True.
+    /// Emits that item's values, or the tag `None` while no item matches; the match
+    /// is re-picked whenever the list or any predicate result changes.
+    fn create_find_actor(
+        construct_info: ConstructInfoComplete,
+        construct_context: ConstructContext,
+        actor_context: ActorContext,
+        source_list_actor: ActorHandle,
+        config: Arc<ListBindingConfig>,
+    ) -> ActorHandle {
+        let actor_context_for_result = actor_context.clone();
+        let construct_context_for_none = construct_context.clone();
+        let find_id = construct_info.id.clone();
+
+        let found_item = switch_map(
+            Self::distinct_source_lists(source_list_actor, &actor_context),
+            move |list| {
+                let item_predicates = Self::item_transform_snapshots(
+                    list,
+                    config.clone(),
+                    construct_context.clone(),
+                    actor_context.clone(),
+                );
+                switch_map(item_predicates, |item_predicates| {
+                    Self::latest_transform_values(item_predicates).map(|(items, results)| {
+                        results
+                            .iter()
+                            .position(|result| {
+                                matches!(result, Value::Tag(tag, _) if tag.tag() == "True")
+                            })
+                            .map(|index| items[index].clone())
+                    })
+                })
+            },
+        );
+
+        let scope_id = actor_context_for_result.scope_id();
+        create_actor_complete(
+            construct_info,
+            actor_context_for_result,
+            follow_selected_item(
+                found_item,
+                find_id,
+                construct_context_for_none,
+                "List/find None",
+            ),
             parser::PersistenceId::new(),
             scope_id,
         )
     }
 
-    /// Creates a sort_by actor that sorts list items based on a key expression.
-    /// When any item's key changes, emits an updated sorted list.
-    fn create_sort_by_actor(
-        construct_info: ConstructInfoComplete,
+    /// Creates a `List/update` actor: the source list with items replaced by a fresh
+    /// evaluation of `new:` each time `on` fires. A replacement keeps the position
+    /// and PersistenceId of the item it replaces and is emitted as one `UpdateAt`.
+    /// Replacing or removing a replacement destroys its scope with its HOLDs and links.
+    pub fn new_update_value_actor(
+        construct_info: ConstructInfo,
         construct_context: ConstructContext,
         actor_context: ActorContext,
         source_list_actor: ActorHandle,
-        config: Arc<ListBindingConfig>,
+        config: ListUpdateConfig,
     ) -> ActorHandle {
-        let construct_info_id = construct_info.id.clone();
+        enum Input {
+            Source(ListChange),
+            Fired,
+        }
+        struct State {
+            inputs: LocalBoxStream<'static, Input>,
+            source: Vec<ActorHandle>,
+            items: Vec<ActorHandle>,
+            /// Predicates of the `Matching` form by item, kept alive so their latest
+            /// result is ready when `on` fires.
+            predicates: Vec<(ActorId, ActorHandle, Option<ScopeDestroyGuard>)>,
+            replacement_scopes: Vec<(ActorId, ScopeDestroyGuard)>,
+            evaluations: usize,
+        }
 
-        // Clone for use after the flat_map chain
+        let construct_info = construct_info.complete(ConstructType::FunctionCall);
+        let config = Arc::new(config);
+        let update_id = construct_info.id.clone();
         let actor_context_for_list = actor_context.clone();
         let actor_context_for_result = actor_context.clone();
 
-        // Clone subscription scope for scope cancellation check
-        let subscription_scope = actor_context.subscription_scope.clone();
+        let state = State {
+            inputs: stream::select(
+                switch_map(
+                    Self::distinct_source_lists(source_list_actor.clone(), &actor_context),
+                    |list| list.stream(),
+                )
+                .map(Input::Source),
+                config.on.clone().stream().map(|_| Input::Fired),
+            )
+            .boxed_local(),
+            source: Vec::new(),
+            items: Vec::new(),
+            predicates: Vec::new(),
+            replacement_scopes: Vec::new(),
+            evaluations: 0,
+        };
 
-        // Create a stream that:
-        // 1. Subscribes to source list changes (stream() yields automatically)
-        // 2. For each item, evaluates key expression and subscribes to its changes
-        // 3. When list or any key changes, emits sorted Replace
-        // Use switch_map for proper list replacement handling:
-        // When source emits a new list, cancel old subscription and start fresh.
-        // But filter out re-emissions of the same list by ID to avoid cancelling
-        // subscriptions unnecessarily.
-        let value_stream = switch_map(
-            source_list_actor
-                .clone()
-                .stream()
-                // Check if subscription scope is cancelled (e.g., WHILE arm switched)
-                .take_while(move |_| {
-                    let is_active = subscription_scope
-                        .as_ref()
-                        .map_or(true, |s| !s.is_cancelled());
-                    future::ready(is_active)
-                })
-                .filter_map(|value| {
-                    future::ready(match value {
-                        Value::List(list, _) => Some(list),
-                        _ => None,
-                    })
-                })
-                // Deduplicate only identical runtime list instances.
-                .scan(None, |prev_key: &mut Option<usize>, list| {
-                    let list_key = list_instance_key(&list);
-                    if prev_key.as_ref() == Some(&list_key) {
-                        future::ready(Some(None))
-                    } else {
-                        *prev_key = Some(list_key);
-                        future::ready(Some(Some(list)))
+        let change_stream = stream::unfold(state, move |mut state| {
+            let config = config.clone();
+            let construct_context = construct_context.clone();
+            let actor_context = actor_context.clone();
+            async move {
+                let mut changes = Vec::new();
+                match state.inputs.next().await? {
+                    Input::Source(ListChange::Replace { items }) => {
+                        let previous_source = std::mem::replace(&mut state.source, items.to_vec());
+                        let previous_items = std::mem::take(&mut state.items);
+                        state.items = items
+                            .iter()
+                            .map(|item| {
+                                previous_source
+                                    .iter()
+                                    .position(|previous| previous.actor_id() == item.actor_id())
+                                    .map_or_else(
+                                        || item.clone(),
+                                        |index| previous_items[index].clone(),
+                                    )
+                            })
+                            .collect();
+                        changes.push(ListChange::Replace {
+                            items: Arc::from(state.items.clone()),
+                        });
                     }
-                })
-                .filter_map(future::ready),
-            move |list| {
-                let config = config.clone();
-                let construct_context = construct_context.clone();
-                let actor_context = actor_context.clone();
-                let construct_info_id = construct_info_id.clone();
-
-                // Clone for the second flat_map
-                let construct_info_id_inner = construct_info_id.clone();
-
-                // Track items and their keys
-                list.stream().scan(
-                Vec::<(ActorHandle, ActorHandle)>::new(), // (item, key_actor)
-                move |item_keys, change| {
-                    let config = config.clone();
-                    let construct_context = construct_context.clone();
-                    let actor_context = actor_context.clone();
-
-                    // Apply change and update key actors
-                    match &change {
-                        ListChange::Replace { items } => {
-                            *item_keys = items.iter().enumerate().map(|(idx, item)| {
-                                let key_actor = Self::transform_item(
-                                    item.clone(),
-                                    idx,
-                                    &config,
-                                    construct_context.clone(),
-                                    actor_context.clone(),
-                                );
-                                (item.clone(), key_actor)
-                            }).collect();
+                    // Replacements keep the PersistenceId of the item they replace, so
+                    // every other change applies to both lists alike.
+                    Input::Source(change) => {
+                        change.clone().apply_to_vec(&mut state.source);
+                        change.clone().apply_to_vec(&mut state.items);
+                        changes.push(change);
+                    }
+                    Input::Fired => {
+                        let mut targets = Vec::new();
+                        match &config.target {
+                            ListUpdateTarget::Index(index) => {
+                                if let Ok(Value::Number(number, _)) = index.current_value().await {
+                                    targets.extend(
+                                        (1_u32..)
+                                            .zip(0..state.items.len())
+                                            .find(|(position, _)| {
+                                                f64::from(*position) == number.number()
+                                            })
+                                            .map(|(_, index)| index),
+                                    );
+                                }
+                            }
+                            ListUpdateTarget::Matching { .. } => {
+                                for (index, item) in state.items.iter().enumerate() {
+                                    let Some((_, predicate, _)) = state
+                                        .predicates
+                                        .iter()
+                                        .find(|(actor_id, _, _)| *actor_id == item.actor_id())
+                                    else {
+                                        continue;
+                                    };
+                                    if matches!(
+                                        predicate.current_value().await,
+                                        Ok(Value::Tag(tag, _)) if tag.tag() == "True"
+                                    ) {
+                                        targets.push(index);
+                                    }
+                                }
+                            }
                         }
-                        ListChange::Push { item } => {
-                            let idx = item_keys.len();
-                            let key_actor = Self::transform_item(
-                                item.clone(),
-                                idx,
-                                &config,
-                                construct_context.clone(),
-                                actor_context.clone(),
+                        for index in targets {
+                            state.evaluations += 1;
+                            let binding = match &config.target {
+                                ListUpdateTarget::Index(_) => None,
+                                ListUpdateTarget::Matching { binding_name, .. } => {
+                                    Some((binding_name, &state.items[index]))
+                                }
+                            };
+                            let (evaluation_context, scope_guard) = Self::update_evaluation_context(
+                                &actor_context,
+                                &format!("list_update_new_{}", state.evaluations),
+                                binding,
                             );
-                            item_keys.push((item.clone(), key_actor));
-                        }
-                        ListChange::InsertAt { index, item } => {
-                            let key_actor = Self::transform_item(
-                                item.clone(),
-                                *index,
+                            let Some(new_actor) = Self::evaluate_update_expression(
+                                &config.new_expr,
                                 &config,
                                 construct_context.clone(),
-                                actor_context.clone(),
+                                evaluation_context.clone(),
+                            ) else {
+                                continue;
+                            };
+                            let item = Self::keep_persistence_id(
+                                new_actor,
+                                state.items[index].persistence_id(),
+                                evaluation_context,
+                                "List/update new item",
                             );
-                            if *index <= item_keys.len() {
-                                item_keys.insert(*index, (item.clone(), key_actor));
-                            }
-                        }
-                        ListChange::Remove { id } => {
-                            // Find item by PersistenceId
-                            if let Some(index) = item_keys.iter().position(|(item, _)| item.persistence_id() == *id) {
-                                item_keys.remove(index);
+                            if let Some(scope_guard) = scope_guard {
+                                state
+                                    .replacement_scopes
+                                    .push((item.actor_id(), scope_guard));
                             }
-                        }
-                        ListChange::Clear => {
-                            item_keys.clear();
-                        }
-                        ListChange::Pop => {
-                            item_keys.pop();
-                        }
-                        // Explicitly handle all ListChange variants to catch future additions.
-                        // These variants are not currently generated by any List API.
-                        ListChange::UpdateAt { .. } => {
-                            panic!(
-                                "List/sort_by_key received UpdateAt event which is not yet implemented. \
-                                If you added a List/update_at API, implement handling in create_sort_by_actor."
-                            );
-                        }
-                        ListChange::Move { .. } => {
-                            panic!(
-                                "List/sort_by_key received Move event which is not yet implemented. \
-                                If you added a List/move API, implement handling in create_sort_by_actor."
-                            );
+                            state.items[index] = item.clone();
+                            changes.push(ListChange::UpdateAt { index, item });
                         }
                     }
-
-                    future::ready(Some(item_keys.clone()))
-                }
-            ).flat_map(move |item_keys| {
-                let _construct_info_id = construct_info_id_inner.clone();
-
-                if item_keys.is_empty() {
-                    // Empty list - emit empty Replace
-                    return stream::once(future::ready(ListChange::Replace { items: Arc::from(Vec::<ActorHandle>::new()) })).boxed_local();
                 }
 
-                // Subscribe to all keys and emit sorted list when any changes
-                // A3: Use coalesce to batch simultaneous key updates
-                let key_streams: Vec<_> = item_keys.iter().enumerate().map(|(idx, (item, key_actor))| {
-                    let item = item.clone();
-                    key_actor.clone().stream().map(move |value| (idx, item.clone(), value))
-                }).collect();
-
-                // B5: Incremental sort using BTreeMap for O(log n) key updates.
-                // State: (items, key_values, sorted_tree, prev_sorted_indices, unevaluated_count)
-                // BTreeMap key: (SortKey, usize) where usize is original index for stable tie-breaking.
-                // BTreeMap value: usize (index into items vec).
-                let item_count = item_keys.len();
-                coalesce(stream::select_all(key_streams))
-                    .scan(
-                        (
-                            item_keys.iter().map(|(item, _)| item.clone()).collect::<Vec<_>>(),
-                            vec![None::<SortKey>; item_count],
-                            std::collections::BTreeMap::<(SortKey, usize), usize>::new(),
-                            Vec::<usize>::new(), // prev_sorted_indices
-                            item_count,          // unevaluated_count
-                        ),
-                        move |(items, key_values, sorted_tree, prev_sorted, unevaluated), batch| {
-                            // Update keys in BTreeMap
-                            for (idx, _item, value) in batch {
-                                let new_key = SortKey::from_value(&value);
-                                if idx < key_values.len() {
-                                    // Remove old key from tree if it existed
-                                    if let Some(old_key) = key_values[idx].take() {
-                                        sorted_tree.remove(&(old_key, idx));
-                                    } else {
-                                        // First evaluation of this key
-                                        *unevaluated = unevaluated.saturating_sub(1);
-                                    }
-                                    // Insert new key
-                                    sorted_tree.insert((new_key.clone(), idx), idx);
-                                    key_values[idx] = Some(new_key);
-                                }
-                            }
-
-                            // Wait until all items have evaluated keys
-                            if *unevaluated > 0 {
-                                return future::ready(Some(None));
-                            }
-
-                            // Read sorted order from BTreeMap (already in order)
-                            let new_sorted: Vec<usize> = sorted_tree.values().copied().collect();
-
-                            if prev_sorted.is_empty() {
-                                // First evaluation - emit Replace
-                                let sorted_items: Vec<ActorHandle> = new_sorted.iter()
-                                    .map(|&idx| items[idx].clone())
-                                    .collect();
-                                *prev_sorted = new_sorted;
-                                future::ready(Some(Some(vec![ListChange::Replace { items: Arc::from(sorted_items) }])))
-                            } else if *prev_sorted == new_sorted {
-                                // No change in sorted order
-                                future::ready(Some(None))
-                            } else {
-                                // B5: Compute incremental Remove+InsertAt operations
-                                let changes = compute_sort_diff(items, prev_sorted, &new_sorted);
-                                *prev_sorted = new_sorted;
-                                future::ready(Some(Some(changes)))
-                            }
+                let items = &state.items;
+                let is_listed =
+                    |actor_id: &ActorId| items.iter().any(|item| item.actor_id() == *actor_id);
+                state
+                    .replacement_scopes
+                    .retain(|(actor_id, _)| is_listed(actor_id));
+                state
+                    .predicates
+                    .retain(|(actor_id, _, _)| is_listed(actor_id));
+                if let ListUpdateTarget::Matching {
+                    binding_name,
+                    predicate,
+                } = &config.target
+                {
+                    for item in &state.items {
+                        if state
+                            .predicates
+                            .iter()
+                            .any(|(actor_id, _, _)| *actor_id == item.actor_id())
+                        {
+                            continue;
                         }
-                    )
-                    .filter_map(future::ready)
-                    .flat_map(|changes| stream::iter(changes))
-                    .boxed_local()
-            })
-            },
-        );
+                        state.evaluations += 1;
+                        let (evaluation_context, scope_guard) = Self::update_evaluation_context(
+                            &actor_context,
+                            &format!("list_update_if_{}", state.evaluations),
+                            Some((binding_name, item)),
+                        );
+                        if let Some(result) = Self::evaluate_update_expression(
+                            predicate,
+                            &config,
+                            construct_context.clone(),
+                            evaluation_context,
+                        ) {
+                            state
+                                .predicates
+                                .push((item.actor_id(), result, scope_guard));
+                        }
+                    }
+                }
+                Some((stream::iter(changes), state))
+            }
+        })
+        .flatten();
 
         let list = List::new_with_change_stream(
-            ConstructInfo::new(
-                construct_info.id.clone().with_child_id(0),
-                None,
-                "List/sort_by result",
-            ),
+            ConstructInfo::new(update_id.with_child_id(0), None, "List/update result"),
             actor_context_for_list,
-            value_stream,
+            change_stream,
             source_list_actor.clone(),
         );
 
@@ -10054,6 +11151,192 @@ impl ListBindingFunction {
         )
     }
 
+    /// The actor context of one `List/update` evaluation: the child scope `scope_name`
+    /// with `binding` in scope, owning a fresh registry scope that the returned guard
+    /// destroys.
+    fn update_evaluation_context(
+        actor_context: &ActorContext,
+        scope_name: &str,
+        binding: Option<(&StrSlice, &ActorHandle)>,
+    ) -> (ActorContext, Option<ScopeDestroyGuard>) {
+        let mut parameters = (*actor_context.parameters).clone();
+        if let Some((binding_name, item)) = binding {
+            parameters.insert(binding_name.to_string(), item.clone());
+        }
+        let child_scope = actor_context.with_child_scope(scope_name);
+        let registry_scope = child_scope
+            .registry_scope_id
+            .map(|parent_scope| create_registry_scope(Some(parent_scope)));
+        let evaluation_context = ActorContext {
+            parameters: Arc::new(parameters),
+            registry_scope_id: registry_scope.or(child_scope.registry_scope_id),
+            ..child_scope
+        };
+        (
+            evaluation_context,
+            registry_scope.map(ScopeDestroyGuard::new),
+        )
+    }
+
+    fn evaluate_update_expression(
+        expression: &StaticSpanned<StaticExpression>,
+        config: &ListUpdateConfig,
+        construct_context: ConstructContext,
+        evaluation_context: ActorContext,
+    ) -> Option<ActorHandle> {
+        match evaluate_static_expression_with_registry(
+            expression,
+            construct_context,
+            evaluation_context,
+            config.reference_connector.clone(),
+            config.link_connector.clone(),
+            config.pass_through_connector.clone(),
+            config.source_code.clone(),
+            config.function_registry_snapshot.clone(),
+        ) {
+            Ok(result_actor) => Some(result_actor),
+            Err(e) => {
+                zoon::eprintln!("Error evaluating List/update expression: {e}");
+                None
+            }
+        }
+    }
+
+    /// The lists the source actor holds, each once, while the subscription scope
+    /// is active.
+    fn distinct_source_lists(
+        source_list_actor: ActorHandle,
+        actor_context: &ActorContext,
+    ) -> impl Stream<Item = Arc<List>> {
+        let subscription_scope = actor_context.subscription_scope.clone();
+        source_list_actor
+            .stream()
+            .take_while(move |_| {
+                let is_active = subscription_scope
+                    .as_ref()
+                    .map_or(true, |s| !s.is_cancelled());
+                future::ready(is_active)
+            })
+            .filter_map(|value| {
+                future::ready(match value {
+                    Value::List(list, _) => Some(list),
+                    _ => None,
+                })
+            })
+            .scan(None, |prev_key: &mut Option<usize>, list| {
+                let list_key = list_instance_key(&list);
+                if prev_key.as_ref() == Some(&list_key) {
+                    future::ready(Some(None))
+                } else {
+                    *prev_key = Some(list_key);
+                    future::ready(Some(Some(list)))
+                }
+            })
+            .filter_map(future::ready)
+    }
+
+    /// `(item, transform_actor)` pairs for the items of `list`, re-emitted after every
+    /// change. A transform actor evaluates the binding expression for its item and
+    /// lives as long as the item.
+    fn item_transform_snapshots(
+        list: Arc<List>,
+        config: Arc<ListBindingConfig>,
+        construct_context: ConstructContext,
+        actor_context: ActorContext,
+    ) -> impl Stream<Item = Vec<(ActorHandle, ActorHandle)>> {
+        list.stream().scan(
+            Vec::<(ActorHandle, ActorHandle)>::new(),
+            move |item_transforms, change| {
+                let transform_of = |item: &ActorHandle, index: usize| {
+                    Self::transform_item(
+                        item.clone(),
+                        index,
+                        &config,
+                        construct_context.clone(),
+                        actor_context.clone(),
+                    )
+                };
+                match change {
+                    ListChange::Replace { items } => {
+                        *item_transforms = items
+                            .iter()
+                            .enumerate()
+                            .map(|(index, item)| (item.clone(), transform_of(item, index)))
+                            .collect();
+                    }
+                    ListChange::InsertAt { index, item } if index <= item_transforms.len() => {
+                        let transform_actor = transform_of(&item, index);
+                        item_transforms.insert(index, (item, transform_actor));
+                    }
+                    ListChange::UpdateAt { index, item } if index < item_transforms.len() => {
+                        let transform_actor = transform_of(&item, index);
+                        item_transforms[index] = (item, transform_actor);
+                    }
+                    ListChange::Push { item } => {
+                        let transform_actor = transform_of(&item, item_transforms.len());
+                        item_transforms.push((item, transform_actor));
+                    }
+                    ListChange::Remove { id } => {
+                        if let Some(index) = item_transforms
+                            .iter()
+                            .position(|(item, _)| item.persistence_id() == id)
+                        {
+                            item_transforms.remove(index);
+                        }
+                    }
+                    ListChange::Move {
+                        old_index,
+                        new_index,
+                    } if old_index < item_transforms.len() => {
+                        let entry = item_transforms.remove(old_index);
+                        let insert_index = new_index.min(item_transforms.len());
+                        item_transforms.insert(insert_index, entry);
+                    }
+                    ListChange::Pop => {
+                        item_transforms.pop();
+                    }
+                    ListChange::Clear => item_transforms.clear(),
+                    // Out-of-range changes are ignored, as in `ListChange::apply_to_vec`.
+                    ListChange::InsertAt { .. }
+                    | ListChange::UpdateAt { .. }
+                    | ListChange::Move { .. } => {}
+                }
+                future::ready(Some(item_transforms.clone()))
+            },
+        )
+    }
+
+    /// The items of one `item_transform_snapshots` snapshot with the latest values of
+    /// their transform actors. Re-emits when any transform value changes, once every
+    /// transform has a value.
+    fn latest_transform_values(
+        item_transforms: Vec<(ActorHandle, ActorHandle)>,
+    ) -> LocalBoxStream<'static, (Vec<ActorHandle>, Vec<Value>)> {
+        if item_transforms.is_empty() {
+            return stream::once(future::ready((Vec::new(), Vec::new()))).boxed_local();
+        }
+        let (items, transform_actors): (Vec<_>, Vec<_>) = item_transforms.into_iter().unzip();
+        let transform_count = transform_actors.len();
+        stream::select_all(transform_actors.into_iter().enumerate().map(
+            |(index, transform_actor)| {
+                transform_actor
+                    .stream()
+                    .map(move |value| (index, value))
+                    .boxed_local()
+            },
+        ))
+        .scan(
+            vec![None; transform_count],
+            move |latest, (index, value)| {
+                latest[index] = Some(value);
+                let values = latest.iter().cloned().collect::<Option<Vec<Value>>>();
+                future::ready(Some(values.map(|values| (items.clone(), values))))
+            },
+        )
+        .filter_map(future::ready)
+        .boxed_local()
+    }
+
     /// Transform a single list item using the config's transform expression.
     fn transform_item(
         item_actor: ActorHandle,
@@ -10113,25 +11396,11 @@ impl ListBindingFunction {
                 // Wrap the result under the ORIGINAL item's PersistenceId while preserving:
                 // - the current value (some mapped expressions have one before any stream item)
                 // - future updates (especially when the mapped body depends on external state)
-                let original_pid = item_actor.persistence_id();
-                let scope_id = new_actor_context.scope_id();
-                let result_actor_for_initial = result_actor.clone();
-                let seeded_result_stream =
-                    stream::once(async move { result_actor_for_initial.value().await.ok() })
-                        .filter_map(|value| async move { value });
-                create_actor(
-                    ConstructInfo::new(
-                        ConstructId::new("List/map mapped item"),
-                        None,
-                        "List/map mapped item",
-                    ),
+                Self::keep_persistence_id(
+                    result_actor,
+                    item_actor.persistence_id(), // Preserve original PersistenceId!
                     new_actor_context,
-                    TypedStream::infinite(stream::select(
-                        seeded_result_stream,
-                        result_actor.stream_from_now(),
-                    )),
-                    original_pid, // Preserve original PersistenceId!
-                    scope_id,
+                    "List/map mapped item",
                 )
             }
             Err(e) => {
@@ -10142,6 +11411,31 @@ impl ListBindingFunction {
         }
     }
 
+    /// `result_actor` re-created under `persistence_id`, keeping its current value and
+    /// its later updates.
+    fn keep_persistence_id(
+        result_actor: ActorHandle,
+        persistence_id: parser::PersistenceId,
+        actor_context: ActorContext,
+        description: &'static str,
+    ) -> ActorHandle {
+        let scope_id = actor_context.scope_id();
+        let result_actor_for_initial = result_actor.clone();
+        let seeded_result_stream =
+            stream::once(async move { result_actor_for_initial.value().await.ok() })
+                .filter_map(|value| async move { value });
+        create_actor(
+            ConstructInfo::new(ConstructId::new(description), None, description),
+            actor_context,
+            TypedStream::infinite(stream::select(
+                seeded_result_stream,
+                result_actor.stream_from_now(),
+            )),
+            persistence_id,
+            scope_id,
+        )
+    }
+
     /// Transform a ListChange by applying the transform expression to affected items.
     /// Only used for map operation.
     ///
@@ -10570,10 +11864,10 @@ impl ListBindingFunction {
 #[cfg(test)]
 mod tests {
     use super::{
-        ActorContext, ConstructContext, ConstructInfo, ConstructStorage, LatestCombinator, List,
-        ListChange, ScopeDestroyGuard, TaggedObject, Text, Value, ValueIdempotencyKey, Variable,
-        VirtualFilesystem, create_actor_forwarding, create_constant_actor, create_registry_scope,
-        list_item_scope_id, values_equal_async,
+        ActorContext, ConstructContext, ConstructInfo, ConstructStorage, CountWhereState,
+        LatestCombinator, List, ListChange, ScopeDestroyGuard, TaggedObject, Text, Value,
+        ValueIdempotencyKey, Variable, VirtualFilesystem, create_actor_forwarding,
+        create_constant_actor, create_registry_scope, list_item_scope_id, values_equal_async,
     };
     use boon::parser::PersistenceId;
     use std::future::Future;
@@ -10631,6 +11925,92 @@ mod tests {
         );
     }
 
+    /// `List/retain(..) |> List/count()` as the retain actor computes it: one result per
+    /// PersistenceId, counted once for every item carrying that id.
+    #[derive(Default)]
+    struct RetainCountModel {
+        items: Vec<PersistenceId>,
+        results: std::collections::HashMap<PersistenceId, bool>,
+    }
+
+    impl RetainCountModel {
+        fn count(&self) -> usize {
+            self.items
+                .iter()
+                .filter(|pid| self.results.get(pid) == Some(&true))
+                .count()
+        }
+    }
+
+    #[test]
+    fn count_where_agrees_with_retain_count_over_random_changes() {
+        let pids: Vec<_> = (0..6).map(|_| PersistenceId::new()).collect();
+        for seed in 1..=200_u64 {
+            // xorshift64, so every failing seed can be replayed
+            let mut random = seed;
+            let mut next = |bound: usize| {
+                random ^= random << 13;
+                random ^= random >> 7;
+                random ^= random << 17;
+                usize::try_from(random % u64::try_from(bound).unwrap()).unwrap()
+            };
+            let mut state = CountWhereState::new();
+            let mut model = RetainCountModel::default();
+
+            for step in 0..300 {
+                let pid = pids[next(pids.len())];
+                let is_true = next(2) == 0;
+                match next(6) {
+                    0 | 1 => {
+                        let index = next(model.items.len() + 1);
+                        state.insert(index, pid, is_true, ());
+                        model.items.insert(index, pid);
+                        model.results.insert(pid, is_true);
+                    }
+                    2 => {
+                        state.set(pid, is_true);
+                        if model.items.contains(&pid) {
+                            model.results.insert(pid, is_true);
+                        }
+                    }
+                    3 => {
+                        state.remove(pid);
+                        model.items.retain(|item| *item != pid);
+                        model.results.remove(&pid);
+                    }
+                    4 => {
+                        let removed = if next(2) == 0 {
+                            state.pop();
+                            model.items.pop()
+                        } else {
+                            let index = next(model.items.len() + 1);
+                            state.remove_at(index);
+                            (index < model.items.len()).then(|| model.items.remove(index))
+                        };
+                        if let Some(removed) =
+                            removed.filter(|removed| !model.items.contains(removed))
+                        {
+                            model.results.remove(&removed);
+                        }
+                    }
+                    _ => {
+                        if next(10) == 0 {
+                            state.clear();
+                            model.items.clear();
+                            model.results.clear();
+                        }
+                    }
+                }
+                assert_eq!(
+                    state.count(),
+                    model.count(),
+                    "count_where and retain |> count differ at seed {seed}, step {step}"
+                );
+                assert_eq!(state.len(), model.items.len());
+            }
+        }
+    }
+
     #[test]
     fn tagged_objects_compare_structurally_when_fields_match() {
         let scope_id = create_registry_scope(None);
@@ -14,9 +14,16 @@ use zoon::{SinkExt, Stream, StreamExt, Task, TaskHandle, mpsc};
 
 use super::engine::*;
 use crate::api;
+use crate::interpreter::render_error_reports;
 use boon::parser::{
-    Persistence, PersistenceId, PersistenceStatus, Scope, SourceCode, Span, Spanned, Token, lexer,
-    parser, resolve_persistence, resolve_references, span_at, static_expression,
+    Persistence, PersistenceId, PersistenceStatus, Scope, SourceCode, Span, Spanned, Token,
+    builtin_aliases::canonical_builtin_path,
+    lexer, parser, resolve_persistence, resolve_references,
+    source_map::{SourceMap, source_map_path},
+    span_at, static_expression,
+};
+use boon::platform::browser::common::{
+    LogLevel, emit_warning, max_text_bytes, truncate_oversized_text,
 };
 
 /// Creates a persistence-wrapped stream for a variable.
@@ -1371,15 +1378,20 @@ fn schedule_expression(
             let path_strs: Vec<String> = path.iter().map(|s| s.to_string()).collect();
             let path_strs_ref: Vec<&str> = path_strs.iter().map(|s| s.as_str()).collect();
 
-            // Special handling for List binding functions (map, retain, remove, every, any, sort_by)
+            // Special handling for List binding functions (map, retain, filter, remove, every, all, any, count_where, sort_by, unique_by, find)
             // These need the unevaluated expression to evaluate per-item with bindings
             match path_strs_ref.as_slice() {
                 ["List", "map"]
                 | ["List", "retain"]
+                | ["List", "filter"]
                 | ["List", "remove"]
                 | ["List", "every"]
+                | ["List", "all"]
                 | ["List", "any"]
-                | ["List", "sort_by"] => {
+                | ["List", "count_where"]
+                | ["List", "sort_by"]
+                | ["List", "unique_by"]
+                | ["List", "find"] => {
                     // Handle List binding functions specially - don't pre-evaluate transform expression
                     if let Some(actor) = build_list_binding_function(
                         &path_strs,
@@ -1392,6 +1404,14 @@ fn schedule_expression(
                         state.store(result_slot, actor);
                     }
                 }
+                ["List", "update"] => {
+                    // List/update evaluates `new:` afresh for each replaced item
+                    if let Some(actor) =
+                        build_list_update(arguments, span, persistence, persistence_id, ctx)?
+                    {
+                        state.store(result_slot, actor);
+                    }
+                }
                 ["List", "append"] => {
                     // Special handling for List/append - enable call recording for the item expression
                     // This captures function calls that produce list items (e.g., new_todo())
@@ -2736,14 +2756,21 @@ fn process_work_item(state: &mut EvaluationState, item: WorkItem) -> Result<(),
                 match path_strs_ref.as_slice() {
                     ["List", "map"]
                     | ["List", "retain"]
+                    | ["List", "filter"]
                     | ["List", "remove"]
                     | ["List", "every"]
+                    | ["List", "all"]
                     | ["List", "any"]
+                    | ["List", "count_where"]
                     | ["List", "sort_by"]
-                    | ["List", "append"] => {
+                    | ["List", "unique_by"]
+                    | ["List", "find"]
+                    | ["List", "append"]
+                    | ["List", "update"] => {
                         // Handle List binding functions specially - they have their own handling
                         // These use the piped value from the context
                         // List/append also has special handling for call recording (persistence)
+                        // List/update keeps `new:` unevaluated for fresh replacements
                         schedule_expression(state, expr, new_ctx, result_slot)?;
                     }
                     _ => {
@@ -4787,7 +4814,7 @@ fn build_hold_actor(
         parameters: Arc::new(body_parameters),
         // Force sequential processing in HOLD body to ensure state consistency.
         // Without this, THEN/WHEN would spawn parallel body evaluations that all
-        // read stale state (e.g., Stream/pulses(3) |> THEN { counter + 1 } would read counter=0 three times).
+        // read stale state (e.g., Stream/sequence(3) |> THEN { counter + 1 } would read counter=0 three times).
         sequential_processing: true,
         // Pass permit to body - THEN will acquire before each evaluation
         backpressure_permit: Some(backpressure_permit),
@@ -5461,24 +5488,33 @@ fn build_text_literal_actor(
         // Move forwarding_loops into scan state to keep them alive
         let combined_stream = merged
             .scan(
-                (vec![None; part_count], forwarding_loops),
-                move |(latest_values, _forwarding_loops), (idx, value)| {
+                (vec![None; part_count], forwarding_loops, false),
+                move |(latest_values, _forwarding_loops, reported_oversized), (idx, value)| {
                     latest_values[idx] = Some(value);
 
                     // Check if all parts have values
                     if latest_values.iter().all(|v| v.is_some()) {
-                        // Combine all text parts
-                        let combined: String = latest_values
-                            .iter()
-                            .filter_map(|v| {
-                                v.as_ref().and_then(|val| match val {
-                                    Value::Text(text, _) => Some(text.text().to_string()),
-                                    Value::Number(num, _) => Some(num.number().to_string()),
-                                    Value::Tag(tag, _) => Some(tag.tag().to_string()),
-                                    _ => None,
-                                })
-                            })
-                            .collect();
+                        // Combine all text parts, stopping once the soft size limit is
+                        // exceeded so a runaway payload is never materialized in full
+                        let max_bytes = max_text_bytes();
+                        let mut combined = String::new();
+                        for val in latest_values.iter().flatten() {
+                            api::push_interpolated_value(&mut combined, val);
+                            if combined.len() > max_bytes {
+                                break;
+                            }
+                        }
+                        if truncate_oversized_text(&mut combined) && !*reported_oversized {
+                            // Once per template instance, not on every re-render
+                            *reported_oversized = true;
+                            emit_warning(
+                                LogLevel::Warn,
+                                "text-size",
+                                format!(
+                                    "TEXT {{..}} at {span_for_combine} produced more than {max_bytes} bytes; rendering is truncated"
+                                ),
+                            );
+                        }
 
                         std::future::ready(Some(Some(combined)))
                     } else {
@@ -5710,7 +5746,7 @@ async fn get_link_sender_from_alias(
     }
 }
 
-/// Build a List binding function (map, retain, every, any, sort_by).
+/// Build a List binding function (map, retain, filter, every, any, count_where, sort_by, unique_by).
 /// These functions take an unevaluated expression that gets evaluated per-item.
 fn build_list_binding_function(
     path_strs: &[String],
@@ -5722,11 +5758,15 @@ fn build_list_binding_function(
 ) -> Result<Option<ActorHandle>, String> {
     let operation = match path_strs[1].as_str() {
         "map" => ListBindingOperation::Map,
-        "retain" => ListBindingOperation::Retain,
+        // A retain over another list's actor is already a view that leaves the source alone
+        "retain" | "filter" => ListBindingOperation::Retain,
         "remove" => ListBindingOperation::Remove,
-        "every" => ListBindingOperation::Every,
+        "every" | "all" => ListBindingOperation::Every,
         "any" => ListBindingOperation::Any,
+        "count_where" => ListBindingOperation::CountWhere,
         "sort_by" => ListBindingOperation::SortBy,
+        "unique_by" => ListBindingOperation::UniqueBy,
+        "find" => ListBindingOperation::Find,
         _ => return Err(format!("Unknown List binding function: {}", path_strs[1])),
     };
 
@@ -5805,6 +5845,95 @@ fn build_list_binding_function(
     Ok(Some(result))
 }
 
+/// Build List/update. `new:` (and `if:` in the `item, if:` form) stay unevaluated,
+/// since each replacement evaluates them afresh with the replaced item in scope.
+fn build_list_update(
+    arguments: Vec<static_expression::Spanned<static_expression::Argument>>,
+    span: Span,
+    persistence: Option<Persistence>,
+    persistence_id: PersistenceId,
+    ctx: EvaluationContext,
+) -> Result<Option<ActorHandle>, String> {
+    // Two forms:
+    // - List/update(index: expr, new: expr, on: expr)
+    // - List/update(item, if: expr, new: expr, on: expr), binding `item` in `if:` and `new:`
+    let argument_value = |name: &str| {
+        arguments
+            .iter()
+            .find(|argument| argument.node.name.as_str() == name)
+            .and_then(|argument| argument.node.value.clone())
+    };
+    let new_expr =
+        argument_value("new").ok_or_else(|| "List/update requires a new expression".to_string())?;
+    let on_expr =
+        argument_value("on").ok_or_else(|| "List/update requires an on trigger".to_string())?;
+
+    let list_actor = if let Some(ref piped) = ctx.actor_context.piped {
+        piped.clone()
+    } else {
+        return Err("List/update requires a list (piped)".to_string());
+    };
+
+    let target = match argument_value("if") {
+        Some(predicate) => {
+            let Some(binding) = arguments.first() else {
+                return Err("List/update requires an item binding".to_string());
+            };
+            ListUpdateTarget::Matching {
+                binding_name: binding.node.name.clone(),
+                predicate,
+            }
+        }
+        None => {
+            let index_expr = argument_value("index")
+                .ok_or_else(|| "List/update requires an index or an if".to_string())?;
+            match evaluate_expression(index_expr, ctx.clone())? {
+                Some(index) => ListUpdateTarget::Index(index),
+                // Index is SKIP - nothing is ever updated
+                None => return Ok(Some(list_actor)),
+            }
+        }
+    };
+    let on = match evaluate_expression(on_expr, ctx.clone())? {
+        Some(on) => on,
+        // Trigger is SKIP - nothing is ever updated
+        None => return Ok(Some(list_actor)),
+    };
+
+    let reference_connector = ctx
+        .try_reference_connector()
+        .ok_or_else(|| "ReferenceConnector dropped - program shutting down".to_string())?;
+    let link_connector = ctx
+        .try_link_connector()
+        .ok_or_else(|| "LinkConnector dropped - program shutting down".to_string())?;
+    let pass_through_connector = ctx
+        .try_pass_through_connector()
+        .ok_or_else(|| "PassThroughConnector dropped - program shutting down".to_string())?;
+    let config = ListUpdateConfig {
+        target,
+        new_expr,
+        on,
+        reference_connector,
+        link_connector,
+        pass_through_connector,
+        source_code: ctx.source_code.clone(),
+        function_registry_snapshot: ctx.function_registry_snapshot.clone(),
+    };
+
+    let result = ListBindingFunction::new_update_value_actor(
+        ConstructInfo::new(
+            format!("PersistenceId: {persistence_id}"),
+            persistence,
+            format!("{span}; List/update(..)"),
+        ),
+        ctx.construct_context,
+        ctx.actor_context,
+        list_actor,
+        config,
+    );
+    Ok(Some(result))
+}
+
 /// Build List/append with call recording for persistence.
 /// This enables capturing function calls that produce list items (e.g., new_todo())
 /// so they can be replayed on restoration.
@@ -6104,6 +6233,11 @@ fn build_list_append_with_recording(
                 ctx.construct_context.clone(),
             ) else {
                 zoon::eprintln!("[DEBUG] Failed to restore input for item {}", index);
+                emit_warning(
+                    LogLevel::Warn,
+                    "restore",
+                    format!("Dropped restored list item {index}: its input could not be restored"),
+                );
                 continue;
             };
             let input_actor: ActorHandle = create_constant_actor(
@@ -6195,6 +6329,11 @@ fn build_list_append_with_recording(
                 }
                 Err(e) => {
                     zoon::eprintln!("[DEBUG] Failed to restore item {}: {}", index, e);
+                    emit_warning(
+                        LogLevel::Warn,
+                        "restore",
+                        format!("Dropped restored list item {index}: {e}"),
+                    );
                 }
             }
         }
@@ -7002,8 +7141,8 @@ fn call_function(
         }
     }
 
-    // Try builtin functions
-    let path_strs: Vec<&str> = path.iter().map(|s| s.as_str()).collect();
+    // Try builtin functions (old names of renamed builtins resolve to the current ones)
+    let path_strs: Vec<&str> = canonical_builtin_path(path.iter().map(|s| s.as_str()).collect());
     match static_function_call_path_to_definition(&path_strs, span) {
         Ok(definition) => {
             // Call the builtin function using FunctionCall
@@ -7016,10 +7155,19 @@ fn call_function(
             // For builtin functions, only prepend piped value if use_piped_for_builtin is true.
             // This flag is only true when the function is the direct target of a pipe (`|>`).
             let mut builtin_args = Vec::new();
+            let mut persisted_bool_toggle = false;
             if use_piped_for_builtin {
                 if let Some(piped) = &ctx.actor_context.piped {
                     builtin_args.push(piped.clone());
                 }
+            } else if path_strs.as_slice() == ["Bool", "toggle"] {
+                // `Bool/toggle(on: trigger, initial: False)` replaces a HOLD, so unlike the
+                // piped `Bool/toggle(when:)` it is persisted. Pass `initial` before `on`.
+                if let Some(on) = arg_map.remove("on") {
+                    positional_args = arg_map.remove("initial").into_iter().collect();
+                    positional_args.push(on);
+                    persisted_bool_toggle = true;
+                }
             }
             builtin_args.extend(positional_args);
 
@@ -7049,6 +7197,26 @@ fn call_function(
                 registry_scope_id: ctx.actor_context.registry_scope_id,
             };
 
+            if persisted_bool_toggle {
+                // Scope the storage key like a HOLD's, so a toggle inside a user-defined
+                // function (e.g. one per list item) keeps its own state per call site
+                let persistence_id = persistence_id.in_scope(&ctx.actor_context.scope);
+                return Ok(Some(FunctionCall::new_arc_value_actor(
+                    construct_info,
+                    ctx.construct_context,
+                    call_actor_context,
+                    move |arguments, id, _, construct_context, actor_context| {
+                        api::function_bool_toggle_on(
+                            arguments,
+                            id,
+                            persistence_id,
+                            construct_context,
+                            actor_context,
+                        )
+                    },
+                    builtin_args,
+                )));
+            }
             Ok(Some(FunctionCall::new_arc_value_actor(
                 construct_info,
                 ctx.construct_context,
@@ -7546,7 +7714,20 @@ impl ModuleLoader {
                     module_name,
                     path
                 );
-                if let Some(module_data) = parse_module(&path, &source_code) {
+                // Generated modules (BUILD.bn `File/write_with_map`) carry a line map
+                let source_map = match virtual_fs.read_text(&source_map_path(&path)).await {
+                    Some(text) => SourceMap::parse(&text)
+                        .inspect_err(|error| {
+                            zoon::eprintln!(
+                                "[ModuleLoader] Ignoring invalid source map of '{}': {}",
+                                path,
+                                error
+                            );
+                        })
+                        .ok(),
+                    None => None,
+                };
+                if let Some(module_data) = parse_module(&path, &source_code, source_map) {
                     // Cache the module
                     self.cache(module_name.to_string(), module_data.clone());
                     return Some(module_data);
@@ -7592,7 +7773,31 @@ impl ModuleLoader {
 }
 
 /// Parse module source code into ModuleData (free function, no state needed).
-pub fn parse_module(filename: &str, source_code: &str) -> Option<ModuleData> {
+///
+/// Errors are reported to the console. With a `source_map` (generated modules), each
+/// report also names the template line in the generating file.
+pub fn parse_module(
+    filename: &str,
+    source_code: &str,
+    source_map: Option<SourceMap>,
+) -> Option<ModuleData> {
+    match try_parse_module(filename, source_code, source_map.as_ref()) {
+        Ok(module_data) => Some(module_data),
+        Err(reports) => {
+            for report in reports {
+                zoon::eprintln!("[ModuleLoader] {}", report);
+            }
+            None
+        }
+    }
+}
+
+/// [`parse_module`] returning the rendered error reports instead of printing them.
+fn try_parse_module(
+    filename: &str,
+    source_code: &str,
+    source_map: Option<&SourceMap>,
+) -> Result<ModuleData, Vec<String>> {
     // Create SourceCode FIRST so all parsing borrows from this Arc'd String.
     // This is critical: the AST will contain &str slices that point into this allocation.
     // If we create SourceCode after parsing, the pointers won't match.
@@ -7602,14 +7807,14 @@ pub fn parse_module(filename: &str, source_code: &str) -> Option<ModuleData> {
     // Lexer
     let (tokens, errors) = lexer().parse(source_code).into_output_errors();
     if !errors.is_empty() {
-        zoon::eprintln!(
-            "[ModuleLoader] Lex errors in '{}': {:?}",
+        return Err(render_error_reports(
+            errors,
             filename,
-            errors.len()
-        );
-        return None;
+            source_code,
+            source_map,
+        ));
     }
-    let mut tokens = tokens?;
+    let mut tokens = tokens.ok_or_else(Vec::new)?;
     tokens.retain(|spanned_token| !matches!(spanned_token.node, Token::Comment(_)));
 
     // Parser
@@ -7624,23 +7829,25 @@ pub fn parse_module(filename: &str, source_code: &str) -> Option<ModuleData> {
         ))
         .into_output_errors();
     if !errors.is_empty() {
-        for err in &errors {
-            zoon::eprintln!("[ModuleLoader] Parse error in '{}': {:?}", filename, err);
-        }
-        return None;
+        return Err(render_error_reports(
+            errors,
+            filename,
+            source_code,
+            source_map,
+        ));
     }
-    let ast = ast?;
+    let ast = ast.ok_or_else(Vec::new)?;
 
     // Reference resolution
     let ast = match resolve_references(ast) {
         Ok(ast) => ast,
         Err(errors) => {
-            zoon::eprintln!(
-                "[ModuleLoader] Reference errors in '{}': {:?}",
+            return Err(render_error_reports(
+                errors,
                 filename,
-                errors.len()
-            );
-            return None;
+                source_code,
+                source_map,
+            ));
         }
     };
 
@@ -7649,12 +7856,12 @@ pub fn parse_module(filename: &str, source_code: &str) -> Option<ModuleData> {
         match resolve_persistence(ast, None::<Vec<Spanned<boon::parser::Expression>>>, "") {
             Ok(result) => result,
             Err(errors) => {
-                zoon::eprintln!(
-                    "[ModuleLoader] Persistence errors in '{}': {:?}",
+                return Err(render_error_reports(
+                    errors,
                     filename,
-                    errors.len()
-                );
-                return None;
+                    source_code,
+                    source_map,
+                ));
             }
         };
 
@@ -7690,7 +7897,7 @@ pub fn parse_module(filename: &str, source_code: &str) -> Option<ModuleData> {
         }
     }
 
-    Some(ModuleData {
+    Ok(ModuleData {
         functions,
         variables,
     })
@@ -8073,8 +8280,8 @@ fn static_function_call_path_to_definition(
             )
             .boxed_local()
         },
-        ["Math", "round"] => |arguments, id, persistence_id, construct_context, actor_context| {
-            api::function_math_round(
+        ["Math", "average"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_math_average(
                 arguments,
                 id,
                 persistence_id,
@@ -8083,8 +8290,8 @@ fn static_function_call_path_to_definition(
             )
             .boxed_local()
         },
-        ["Math", "min"] => |arguments, id, persistence_id, construct_context, actor_context| {
-            api::function_math_min(
+        ["Math", "count"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_math_count(
                 arguments,
                 id,
                 persistence_id,
@@ -8093,8 +8300,8 @@ fn static_function_call_path_to_definition(
             )
             .boxed_local()
         },
-        ["Math", "max"] => |arguments, id, persistence_id, construct_context, actor_context| {
-            api::function_math_max(
+        ["Math", "abs"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_math_abs(
                 arguments,
                 id,
                 persistence_id,
@@ -8103,8 +8310,20 @@ fn static_function_call_path_to_definition(
             )
             .boxed_local()
         },
-        ["Text", "empty"] => |arguments, id, persistence_id, construct_context, actor_context| {
-            api::function_text_empty(
+        ["Math", "negate_if"] => {
+            |arguments, id, persistence_id, construct_context, actor_context| {
+                api::function_math_negate_if(
+                    arguments,
+                    id,
+                    persistence_id,
+                    construct_context,
+                    actor_context,
+                )
+                .boxed_local()
+            }
+        }
+        ["Math", "pow"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_math_pow(
                 arguments,
                 id,
                 persistence_id,
@@ -8113,8 +8332,8 @@ fn static_function_call_path_to_definition(
             )
             .boxed_local()
         },
-        ["Text", "space"] => |arguments, id, persistence_id, construct_context, actor_context| {
-            api::function_text_space(
+        ["Math", "sqrt"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_math_sqrt(
                 arguments,
                 id,
                 persistence_id,
@@ -8123,8 +8342,8 @@ fn static_function_call_path_to_definition(
             )
             .boxed_local()
         },
-        ["Text", "trim"] => |arguments, id, persistence_id, construct_context, actor_context| {
-            api::function_text_trim(
+        ["Math", "round"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_math_round(
                 arguments,
                 id,
                 persistence_id,
@@ -8133,56 +8352,8 @@ fn static_function_call_path_to_definition(
             )
             .boxed_local()
         },
-        ["Text", "is_empty"] => {
-            |arguments, id, persistence_id, construct_context, actor_context| {
-                api::function_text_is_empty(
-                    arguments,
-                    id,
-                    persistence_id,
-                    construct_context,
-                    actor_context,
-                )
-                .boxed_local()
-            }
-        }
-        ["Text", "is_not_empty"] => {
-            |arguments, id, persistence_id, construct_context, actor_context| {
-                api::function_text_is_not_empty(
-                    arguments,
-                    id,
-                    persistence_id,
-                    construct_context,
-                    actor_context,
-                )
-                .boxed_local()
-            }
-        }
-        ["Text", "to_number"] => {
-            |arguments, id, persistence_id, construct_context, actor_context| {
-                api::function_text_to_number(
-                    arguments,
-                    id,
-                    persistence_id,
-                    construct_context,
-                    actor_context,
-                )
-                .boxed_local()
-            }
-        }
-        ["Text", "starts_with"] => {
-            |arguments, id, persistence_id, construct_context, actor_context| {
-                api::function_text_starts_with(
-                    arguments,
-                    id,
-                    persistence_id,
-                    construct_context,
-                    actor_context,
-                )
-                .boxed_local()
-            }
-        }
-        ["Bool", "not"] => |arguments, id, persistence_id, construct_context, actor_context| {
-            api::function_bool_not(
+        ["Math", "floor"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_math_floor(
                 arguments,
                 id,
                 persistence_id,
@@ -8191,8 +8362,8 @@ fn static_function_call_path_to_definition(
             )
             .boxed_local()
         },
-        ["Bool", "toggle"] => |arguments, id, persistence_id, construct_context, actor_context| {
-            api::function_bool_toggle(
+        ["Math", "ceil"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_math_ceil(
                 arguments,
                 id,
                 persistence_id,
@@ -8201,8 +8372,8 @@ fn static_function_call_path_to_definition(
             )
             .boxed_local()
         },
-        ["Bool", "or"] => |arguments, id, persistence_id, construct_context, actor_context| {
-            api::function_bool_or(
+        ["Math", "min"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_math_min(
                 arguments,
                 id,
                 persistence_id,
@@ -8211,8 +8382,8 @@ fn static_function_call_path_to_definition(
             )
             .boxed_local()
         },
-        ["List", "count"] => |arguments, id, persistence_id, construct_context, actor_context| {
-            api::function_list_count(
+        ["Math", "max"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_math_max(
                 arguments,
                 id,
                 persistence_id,
@@ -8221,8 +8392,8 @@ fn static_function_call_path_to_definition(
             )
             .boxed_local()
         },
-        ["List", "append"] => |arguments, id, persistence_id, construct_context, actor_context| {
-            api::function_list_append(
+        ["Math", "clamp"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_math_clamp(
                 arguments,
                 id,
                 persistence_id,
@@ -8231,8 +8402,8 @@ fn static_function_call_path_to_definition(
             )
             .boxed_local()
         },
-        ["List", "clear"] => |arguments, id, persistence_id, construct_context, actor_context| {
-            api::function_list_clear(
+        ["Text", "empty"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_text_empty(
                 arguments,
                 id,
                 persistence_id,
@@ -8241,8 +8412,8 @@ fn static_function_call_path_to_definition(
             )
             .boxed_local()
         },
-        ["List", "latest"] => |arguments, id, persistence_id, construct_context, actor_context| {
-            api::function_list_latest(
+        ["Text", "space"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_text_space(
                 arguments,
                 id,
                 persistence_id,
@@ -8251,8 +8422,8 @@ fn static_function_call_path_to_definition(
             )
             .boxed_local()
         },
-        ["List", "last"] => |arguments, id, persistence_id, construct_context, actor_context| {
-            api::function_list_last(
+        ["Text", "trim"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_text_trim(
                 arguments,
                 id,
                 persistence_id,
@@ -8261,9 +8432,9 @@ fn static_function_call_path_to_definition(
             )
             .boxed_local()
         },
-        ["List", "remove_last"] => {
+        ["Text", "is_empty"] => {
             |arguments, id, persistence_id, construct_context, actor_context| {
-                api::function_list_remove_last(
+                api::function_text_is_empty(
                     arguments,
                     id,
                     persistence_id,
@@ -8273,9 +8444,9 @@ fn static_function_call_path_to_definition(
                 .boxed_local()
             }
         }
-        ["List", "is_empty"] => {
+        ["Text", "is_not_empty"] => {
             |arguments, id, persistence_id, construct_context, actor_context| {
-                api::function_list_is_empty(
+                api::function_text_is_not_empty(
                     arguments,
                     id,
                     persistence_id,
@@ -8285,9 +8456,9 @@ fn static_function_call_path_to_definition(
                 .boxed_local()
             }
         }
-        ["List", "is_not_empty"] => {
+        ["Text", "to_number"] => {
             |arguments, id, persistence_id, construct_context, actor_context| {
-                api::function_list_is_not_empty(
+                api::function_text_to_number(
                     arguments,
                     id,
                     persistence_id,
@@ -8297,8 +8468,44 @@ fn static_function_call_path_to_definition(
                 .boxed_local()
             }
         }
-        ["List", "get"] => |arguments, id, persistence_id, construct_context, actor_context| {
-            api::function_list_get(
+        ["Text", "starts_with"] => {
+            |arguments, id, persistence_id, construct_context, actor_context| {
+                api::function_text_starts_with(
+                    arguments,
+                    id,
+                    persistence_id,
+                    construct_context,
+                    actor_context,
+                )
+                .boxed_local()
+            }
+        }
+        ["Text", "ends_with"] => {
+            |arguments, id, persistence_id, construct_context, actor_context| {
+                api::function_text_ends_with(
+                    arguments,
+                    id,
+                    persistence_id,
+                    construct_context,
+                    actor_context,
+                )
+                .boxed_local()
+            }
+        }
+        ["Text", "contains"] => {
+            |arguments, id, persistence_id, construct_context, actor_context| {
+                api::function_text_contains(
+                    arguments,
+                    id,
+                    persistence_id,
+                    construct_context,
+                    actor_context,
+                )
+                .boxed_local()
+            }
+        }
+        ["Text", "replace"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_text_replace(
                 arguments,
                 id,
                 persistence_id,
@@ -8307,8 +8514,8 @@ fn static_function_call_path_to_definition(
             )
             .boxed_local()
         },
-        ["List", "range"] => |arguments, id, persistence_id, construct_context, actor_context| {
-            api::function_list_range(
+        ["Text", "join"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_text_join(
                 arguments,
                 id,
                 persistence_id,
@@ -8317,8 +8524,8 @@ fn static_function_call_path_to_definition(
             )
             .boxed_local()
         },
-        ["List", "sum"] => |arguments, id, persistence_id, construct_context, actor_context| {
-            api::function_list_sum(
+        ["Text", "split"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_text_split(
                 arguments,
                 id,
                 persistence_id,
@@ -8327,8 +8534,8 @@ fn static_function_call_path_to_definition(
             )
             .boxed_local()
         },
-        ["List", "product"] => |arguments, id, persistence_id, construct_context, actor_context| {
-            api::function_list_product(
+        ["Bool", "not"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_bool_not(
                 arguments,
                 id,
                 persistence_id,
@@ -8337,8 +8544,8 @@ fn static_function_call_path_to_definition(
             )
             .boxed_local()
         },
-        ["Text", "length"] => |arguments, id, persistence_id, construct_context, actor_context| {
-            api::function_text_length(
+        ["Bool", "toggle"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_bool_toggle(
                 arguments,
                 id,
                 persistence_id,
@@ -8347,8 +8554,8 @@ fn static_function_call_path_to_definition(
             )
             .boxed_local()
         },
-        ["Text", "char_at"] => |arguments, id, persistence_id, construct_context, actor_context| {
-            api::function_text_char_at(
+        ["Bool", "and"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_bool_and(
                 arguments,
                 id,
                 persistence_id,
@@ -8357,8 +8564,8 @@ fn static_function_call_path_to_definition(
             )
             .boxed_local()
         },
-        ["Text", "find"] => |arguments, id, persistence_id, construct_context, actor_context| {
-            api::function_text_find(
+        ["Bool", "or"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_bool_or(
                 arguments,
                 id,
                 persistence_id,
@@ -8367,9 +8574,149 @@ fn static_function_call_path_to_definition(
             )
             .boxed_local()
         },
-        ["Text", "find_closing"] => {
+        ["List", "count"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_list_count(
+                arguments,
+                id,
+                persistence_id,
+                construct_context,
+                actor_context,
+            )
+            .boxed_local()
+        },
+        ["List", "append"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_list_append(
+                arguments,
+                id,
+                persistence_id,
+                construct_context,
+                actor_context,
+            )
+            .boxed_local()
+        },
+        ["List", "clear"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_list_clear(
+                arguments,
+                id,
+                persistence_id,
+                construct_context,
+                actor_context,
+            )
+            .boxed_local()
+        },
+        ["List", "latest"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_list_latest(
+                arguments,
+                id,
+                persistence_id,
+                construct_context,
+                actor_context,
+            )
+            .boxed_local()
+        },
+        ["List", "first"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_list_first(
+                arguments,
+                id,
+                persistence_id,
+                construct_context,
+                actor_context,
+            )
+            .boxed_local()
+        },
+        ["List", "last"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_list_last(
+                arguments,
+                id,
+                persistence_id,
+                construct_context,
+                actor_context,
+            )
+            .boxed_local()
+        },
+        ["List", "concat"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_list_concat(
+                arguments,
+                id,
+                persistence_id,
+                construct_context,
+                actor_context,
+            )
+            .boxed_local()
+        },
+        ["List", "take"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_list_take(
+                arguments,
+                id,
+                persistence_id,
+                construct_context,
+                actor_context,
+            )
+            .boxed_local()
+        },
+        ["List", "drop"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_list_drop(
+                arguments,
+                id,
+                persistence_id,
+                construct_context,
+                actor_context,
+            )
+            .boxed_local()
+        },
+        ["List", "flatten"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_list_flatten(
+                arguments,
+                id,
+                persistence_id,
+                construct_context,
+                actor_context,
+            )
+            .boxed_local()
+        },
+        ["List", "insert"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_list_insert(
+                arguments,
+                id,
+                persistence_id,
+                construct_context,
+                actor_context,
+            )
+            .boxed_local()
+        },
+        ["List", "move"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_list_move(
+                arguments,
+                id,
+                persistence_id,
+                construct_context,
+                actor_context,
+            )
+            .boxed_local()
+        },
+        ["List", "chunk"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_list_chunk(
+                arguments,
+                id,
+                persistence_id,
+                construct_context,
+                actor_context,
+            )
+            .boxed_local()
+        },
+        ["List", "zip"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_list_zip(
+                arguments,
+                id,
+                persistence_id,
+                construct_context,
+                actor_context,
+            )
+            .boxed_local()
+        },
+        ["List", "contains"] => {
             |arguments, id, persistence_id, construct_context, actor_context| {
-                api::function_text_find_closing(
+                api::function_list_contains(
                     arguments,
                     id,
                     persistence_id,
@@ -8379,9 +8726,9 @@ fn static_function_call_path_to_definition(
                 .boxed_local()
             }
         }
-        ["Text", "substring"] => {
+        ["List", "index_of"] => {
             |arguments, id, persistence_id, construct_context, actor_context| {
-                api::function_text_substring(
+                api::function_list_index_of(
                     arguments,
                     id,
                     persistence_id,
@@ -8391,9 +8738,29 @@ fn static_function_call_path_to_definition(
                 .boxed_local()
             }
         }
-        ["Text", "to_uppercase"] => {
+        ["List", "reverse"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_list_reverse(
+                arguments,
+                id,
+                persistence_id,
+                construct_context,
+                actor_context,
+            )
+            .boxed_local()
+        },
+        ["List", "unique"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_list_unique(
+                arguments,
+                id,
+                persistence_id,
+                construct_context,
+                actor_context,
+            )
+            .boxed_local()
+        },
+        ["List", "remove_last"] => {
             |arguments, id, persistence_id, construct_context, actor_context| {
-                api::function_text_to_uppercase(
+                api::function_list_remove_last(
                     arguments,
                     id,
                     persistence_id,
@@ -8403,9 +8770,9 @@ fn static_function_call_path_to_definition(
                 .boxed_local()
             }
         }
-        ["Text", "char_code"] => {
+        ["List", "is_empty"] => {
             |arguments, id, persistence_id, construct_context, actor_context| {
-                api::function_text_char_code(
+                api::function_list_is_empty(
                     arguments,
                     id,
                     persistence_id,
@@ -8415,9 +8782,9 @@ fn static_function_call_path_to_definition(
                 .boxed_local()
             }
         }
-        ["Text", "from_char_code"] => {
+        ["List", "is_not_empty"] => {
             |arguments, id, persistence_id, construct_context, actor_context| {
-                api::function_text_from_char_code(
+                api::function_list_is_not_empty(
                     arguments,
                     id,
                     persistence_id,
@@ -8427,8 +8794,8 @@ fn static_function_call_path_to_definition(
                 .boxed_local()
             }
         }
-        ["Math", "modulo"] => |arguments, id, persistence_id, construct_context, actor_context| {
-            api::function_math_modulo(
+        ["List", "get"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_list_get(
                 arguments,
                 id,
                 persistence_id,
@@ -8437,8 +8804,8 @@ fn static_function_call_path_to_definition(
             )
             .boxed_local()
         },
-        ["Router", "route"] => |arguments, id, persistence_id, construct_context, actor_context| {
-            api::function_router_route(
+        ["List", "range"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_list_range(
                 arguments,
                 id,
                 persistence_id,
@@ -8447,8 +8814,8 @@ fn static_function_call_path_to_definition(
             )
             .boxed_local()
         },
-        ["Router", "go_to"] => |arguments, id, persistence_id, construct_context, actor_context| {
-            api::function_router_go_to(
+        ["List", "sum"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_list_sum(
                 arguments,
                 id,
                 persistence_id,
@@ -8457,20 +8824,8 @@ fn static_function_call_path_to_definition(
             )
             .boxed_local()
         },
-        ["Ulid", "generate"] => {
-            |arguments, id, persistence_id, construct_context, actor_context| {
-                api::function_ulid_generate(
-                    arguments,
-                    id,
-                    persistence_id,
-                    construct_context,
-                    actor_context,
-                )
-                .boxed_local()
-            }
-        }
-        ["Document", "new"] => |arguments, id, persistence_id, construct_context, actor_context| {
-            api::function_document_new(
+        ["List", "min"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_list_min(
                 arguments,
                 id,
                 persistence_id,
@@ -8479,21 +8834,59 @@ fn static_function_call_path_to_definition(
             )
             .boxed_local()
         },
-        ["Element", "stripe"] => {
-            |arguments, id, persistence_id, construct_context, actor_context| {
-                api::function_element_stripe(
-                    arguments,
-                    id,
-                    persistence_id,
-                    construct_context,
-                    actor_context,
-                )
-                .boxed_local()
-            }
-        }
-        ["Element", "container"] => {
+        ["List", "max"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_list_max(
+                arguments,
+                id,
+                persistence_id,
+                construct_context,
+                actor_context,
+            )
+            .boxed_local()
+        },
+        ["List", "product"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_list_product(
+                arguments,
+                id,
+                persistence_id,
+                construct_context,
+                actor_context,
+            )
+            .boxed_local()
+        },
+        ["Text", "length"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_text_length(
+                arguments,
+                id,
+                persistence_id,
+                construct_context,
+                actor_context,
+            )
+            .boxed_local()
+        },
+        ["Text", "char_at"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_text_char_at(
+                arguments,
+                id,
+                persistence_id,
+                construct_context,
+                actor_context,
+            )
+            .boxed_local()
+        },
+        ["Text", "find"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_text_find(
+                arguments,
+                id,
+                persistence_id,
+                construct_context,
+                actor_context,
+            )
+            .boxed_local()
+        },
+        ["Text", "find_closing"] => {
             |arguments, id, persistence_id, construct_context, actor_context| {
-                api::function_element_container(
+                api::function_text_find_closing(
                     arguments,
                     id,
                     persistence_id,
@@ -8503,9 +8896,9 @@ fn static_function_call_path_to_definition(
                 .boxed_local()
             }
         }
-        ["Element", "stack"] => {
+        ["Text", "substring"] => {
             |arguments, id, persistence_id, construct_context, actor_context| {
-                api::function_element_stack(
+                api::function_text_substring(
                     arguments,
                     id,
                     persistence_id,
@@ -8515,9 +8908,9 @@ fn static_function_call_path_to_definition(
                 .boxed_local()
             }
         }
-        ["Element", "button"] => {
+        ["Text", "pad_start"] => {
             |arguments, id, persistence_id, construct_context, actor_context| {
-                api::function_element_button(
+                api::function_text_pad_start(
                     arguments,
                     id,
                     persistence_id,
@@ -8527,8 +8920,270 @@ fn static_function_call_path_to_definition(
                 .boxed_local()
             }
         }
-        ["Element", "text_input"] => {
-            |arguments, id, persistence_id, construct_context, actor_context| {
+        ["Text", "pad_end"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_text_pad_end(
+                arguments,
+                id,
+                persistence_id,
+                construct_context,
+                actor_context,
+            )
+            .boxed_local()
+        },
+        ["Text", "slice"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_text_slice(
+                arguments,
+                id,
+                persistence_id,
+                construct_context,
+                actor_context,
+            )
+            .boxed_local()
+        },
+        ["Text", "to_uppercase"] => {
+            |arguments, id, persistence_id, construct_context, actor_context| {
+                api::function_text_to_uppercase(
+                    arguments,
+                    id,
+                    persistence_id,
+                    construct_context,
+                    actor_context,
+                )
+                .boxed_local()
+            }
+        }
+        ["Text", "to_lowercase"] => {
+            |arguments, id, persistence_id, construct_context, actor_context| {
+                api::function_text_to_lowercase(
+                    arguments,
+                    id,
+                    persistence_id,
+                    construct_context,
+                    actor_context,
+                )
+                .boxed_local()
+            }
+        }
+        ["Text", "char_code"] => {
+            |arguments, id, persistence_id, construct_context, actor_context| {
+                api::function_text_char_code(
+                    arguments,
+                    id,
+                    persistence_id,
+                    construct_context,
+                    actor_context,
+                )
+                .boxed_local()
+            }
+        }
+        ["Text", "from_char_code"] => {
+            |arguments, id, persistence_id, construct_context, actor_context| {
+                api::function_text_from_char_code(
+                    arguments,
+                    id,
+                    persistence_id,
+                    construct_context,
+                    actor_context,
+                )
+                .boxed_local()
+            }
+        }
+        ["Math", "divide_int"] => {
+            |arguments, id, persistence_id, construct_context, actor_context| {
+                api::function_math_divide_int(
+                    arguments,
+                    id,
+                    persistence_id,
+                    construct_context,
+                    actor_context,
+                )
+                .boxed_local()
+            }
+        }
+        ["Math", "modulo"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_math_modulo(
+                arguments,
+                id,
+                persistence_id,
+                construct_context,
+                actor_context,
+            )
+            .boxed_local()
+        },
+        ["Number", "parse"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_number_parse(
+                arguments,
+                id,
+                persistence_id,
+                construct_context,
+                actor_context,
+            )
+            .boxed_local()
+        },
+        ["Number", "to_text"] => {
+            |arguments, id, persistence_id, construct_context, actor_context| {
+                api::function_number_to_text(
+                    arguments,
+                    id,
+                    persistence_id,
+                    construct_context,
+                    actor_context,
+                )
+                .boxed_local()
+            }
+        }
+        ["Router", "route"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_router_route(
+                arguments,
+                id,
+                persistence_id,
+                construct_context,
+                actor_context,
+            )
+            .boxed_local()
+        },
+        ["Router", "go_to"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_router_go_to(
+                arguments,
+                id,
+                persistence_id,
+                construct_context,
+                actor_context,
+            )
+            .boxed_local()
+        },
+        ["Document", "query_params"] => {
+            |arguments, id, persistence_id, construct_context, actor_context| {
+                api::function_document_query_params(
+                    arguments,
+                    id,
+                    persistence_id,
+                    construct_context,
+                    actor_context,
+                )
+                .boxed_local()
+            }
+        }
+        ["Document", "visible"] => {
+            |arguments, id, persistence_id, construct_context, actor_context| {
+                api::function_document_visible(
+                    arguments,
+                    id,
+                    persistence_id,
+                    construct_context,
+                    actor_context,
+                )
+                .boxed_local()
+            }
+        }
+        ["Document", "online"] => {
+            |arguments, id, persistence_id, construct_context, actor_context| {
+                api::function_document_online(
+                    arguments,
+                    id,
+                    persistence_id,
+                    construct_context,
+                    actor_context,
+                )
+                .boxed_local()
+            }
+        }
+        ["Document", "confirm_unload"] => {
+            |arguments, id, persistence_id, construct_context, actor_context| {
+                api::function_document_confirm_unload(
+                    arguments,
+                    id,
+                    persistence_id,
+                    construct_context,
+                    actor_context,
+                )
+                .boxed_local()
+            }
+        }
+        ["Document", "set_query_param"] => {
+            |arguments, id, persistence_id, construct_context, actor_context| {
+                api::function_document_set_query_param(
+                    arguments,
+                    id,
+                    persistence_id,
+                    construct_context,
+                    actor_context,
+                )
+                .boxed_local()
+            }
+        }
+        ["Ulid", "generate"] => {
+            |arguments, id, persistence_id, construct_context, actor_context| {
+                api::function_ulid_generate(
+                    arguments,
+                    id,
+                    persistence_id,
+                    construct_context,
+                    actor_context,
+                )
+                .boxed_local()
+            }
+        }
+        ["Document", "new"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_document_new(
+                arguments,
+                id,
+                persistence_id,
+                construct_context,
+                actor_context,
+            )
+            .boxed_local()
+        },
+        ["Element", "stripe"] => {
+            |arguments, id, persistence_id, construct_context, actor_context| {
+                api::function_element_stripe(
+                    arguments,
+                    id,
+                    persistence_id,
+                    construct_context,
+                    actor_context,
+                )
+                .boxed_local()
+            }
+        }
+        ["Element", "container"] => {
+            |arguments, id, persistence_id, construct_context, actor_context| {
+                api::function_element_container(
+                    arguments,
+                    id,
+                    persistence_id,
+                    construct_context,
+                    actor_context,
+                )
+                .boxed_local()
+            }
+        }
+        ["Element", "stack"] => {
+            |arguments, id, persistence_id, construct_context, actor_context| {
+                api::function_element_stack(
+                    arguments,
+                    id,
+                    persistence_id,
+                    construct_context,
+                    actor_context,
+                )
+                .boxed_local()
+            }
+        }
+        ["Element", "button"] => {
+            |arguments, id, persistence_id, construct_context, actor_context| {
+                api::function_element_button(
+                    arguments,
+                    id,
+                    persistence_id,
+                    construct_context,
+                    actor_context,
+                )
+                .boxed_local()
+            }
+        }
+        ["Element", "text_input"] => {
+            |arguments, id, persistence_id, construct_context, actor_context| {
                 api::function_element_text_input(
                     arguments,
                     id,
@@ -8621,6 +9276,16 @@ fn static_function_call_path_to_definition(
                 .boxed_local()
             }
         }
+        ["Element", "code"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_element_code(
+                arguments,
+                id,
+                persistence_id,
+                construct_context,
+                actor_context,
+            )
+            .boxed_local()
+        },
         ["Element", "link"] => |arguments, id, persistence_id, construct_context, actor_context| {
             api::function_element_link(
                 arguments,
@@ -8936,9 +9601,9 @@ fn static_function_call_path_to_definition(
                 .boxed_local()
             }
         }
-        ["Directory", "entries"] => {
+        ["File", "write_with_map"] => {
             |arguments, id, persistence_id, construct_context, actor_context| {
-                api::function_directory_entries(
+                api::function_file_write_with_map(
                     arguments,
                     id,
                     persistence_id,
@@ -8948,10 +9613,22 @@ fn static_function_call_path_to_definition(
                 .boxed_local()
             }
         }
-        ["Stream", "skip"] => |arguments, id, persistence_id, construct_context, actor_context| {
-            api::function_stream_skip(
-                arguments,
-                id,
+        ["Directory", "entries"] => {
+            |arguments, id, persistence_id, construct_context, actor_context| {
+                api::function_directory_entries(
+                    arguments,
+                    id,
+                    persistence_id,
+                    construct_context,
+                    actor_context,
+                )
+                .boxed_local()
+            }
+        }
+        ["Stream", "skip"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_stream_skip(
+                arguments,
+                id,
                 persistence_id,
                 construct_context,
                 actor_context,
@@ -8980,9 +9657,29 @@ fn static_function_call_path_to_definition(
                 .boxed_local()
             }
         }
-        ["Stream", "pulses"] => {
+        ["Stream", "min"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_stream_min(
+                arguments,
+                id,
+                persistence_id,
+                construct_context,
+                actor_context,
+            )
+            .boxed_local()
+        },
+        ["Stream", "max"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_stream_max(
+                arguments,
+                id,
+                persistence_id,
+                construct_context,
+                actor_context,
+            )
+            .boxed_local()
+        },
+        ["Stream", "sequence"] => {
             |arguments, id, persistence_id, construct_context, actor_context| {
-                api::function_stream_pulses(
+                api::function_stream_sequence(
                     arguments,
                     id,
                     persistence_id,
@@ -9004,6 +9701,28 @@ fn static_function_call_path_to_definition(
                 .boxed_local()
             }
         }
+        ["Stream", "sample"] => {
+            |arguments, id, persistence_id, construct_context, actor_context| {
+                api::function_stream_sample(
+                    arguments,
+                    id,
+                    persistence_id,
+                    construct_context,
+                    actor_context,
+                )
+                .boxed_local()
+            }
+        }
+        ["Stream", "gate"] => |arguments, id, persistence_id, construct_context, actor_context| {
+            api::function_stream_gate(
+                arguments,
+                id,
+                persistence_id,
+                construct_context,
+                actor_context,
+            )
+            .boxed_local()
+        },
         _ => {
             return Err(format!(
                 "Unknown function '{}(..)' in static context",
@@ -9081,11 +9800,15 @@ fn spawn_recorded_calls_storage_actor(
 
 #[cfg(test)]
 mod tests {
-    use super::{FunctionRegistry, ModuleLoader, evaluate_with_registry, flatten_pipe_chain};
+    use super::{
+        FunctionRegistry, ModuleLoader, evaluate_with_registry, flatten_pipe_chain,
+        try_parse_module,
+    };
     use crate::engine::{
         ActorContext, ConstructContext, ConstructId, Object, ScopeDestroyGuard, Value, Variable,
         VirtualFilesystem,
     };
+    use boon::parser::source_map::{SourceMap, source_map_path};
     use boon::parser::{
         SourceCode, Spanned, Token, lexer, parser, resolve_references, span_at, static_expression,
     };
@@ -9204,6 +9927,13 @@ pressed: increment_button.event.press |> THEN { TEXT { pressed } }
     }
 
     fn evaluate_program(source: &str) -> (Arc<Object>, ConstructContext, ScopeDestroyGuard) {
+        evaluate_program_with_fs(source, VirtualFilesystem::new())
+    }
+
+    fn evaluate_program_with_fs(
+        source: &str,
+        virtual_fs: VirtualFilesystem,
+    ) -> (Arc<Object>, ConstructContext, ScopeDestroyGuard) {
         let (source_code, expressions) = parse_static_program(source);
         let (
             root_object,
@@ -9218,7 +9948,7 @@ pressed: increment_button.event.press |> THEN { TEXT { pressed } }
             source_code,
             expressions,
             "",
-            VirtualFilesystem::new(),
+            virtual_fs,
             FunctionRegistry::new(),
             ModuleLoader::default(),
         )
@@ -9296,6 +10026,17 @@ pressed: increment_button.event.press |> THEN { TEXT { pressed } }
             .expect("link signal send should succeed");
     }
 
+    /// Sends `{name: true}` through the LINK variable `name`, like a button press.
+    fn press(root_object: &Object, construct_context: &ConstructContext, name: &'static str) {
+        let sender = root_object.expect_variable(name).expect_link_value_sender();
+        block_on(send_link_signal(
+            construct_context.clone(),
+            sender,
+            json!({ name: true }),
+            name,
+        ));
+    }
+
     fn append_from_link_source() -> &'static str {
         r#"
 edit_committed: LINK
@@ -9356,1059 +10097,2211 @@ count: 0 |> HOLD state {
 "#
     }
 
-    fn nested_cells_map_source(with_link: bool) -> String {
-        let mapped_element = if with_link {
-            "Element/label(element: [], style: [width: 80], label: TEXT { x }) |> LINK { cell.cell_elements.display }"
-        } else {
-            "Element/label(element: [], style: [width: 80], label: TEXT { x })"
-        };
+    fn stream_sample_source() -> &'static str {
+        r#"
+text: LINK
+submit: LINK
 
-        format!(
-            r#"
-FUNCTION make_cell(column, row) {{
-    [column: column row: row cell_elements: [display: LINK, editing: LINK]]
-}}
+submitted: text |> Stream/sample(on: submit)
+"#
+    }
 
-FUNCTION make_row_cells(row) {{ LIST {{
-    make_cell(column: 1, row: row)
-    make_cell(column: 2, row: row)
-}} }}
+    fn stream_gate_source() -> &'static str {
+        r#"
+event: LINK
+open: LINK
 
-FUNCTION make_cell_element(cell) {{
-    {mapped_element}
-}}
+passed: event |> Stream/gate(open: open)
+"#
+    }
 
-cells: make_row_cells(row: 1)
-elements: cells |> List/map(cell, new: make_cell_element(cell: cell))
+    /// BUILD.bn fixture: the generated module's second line (`broken`) does not parse.
+    fn generated_module_build_source() -> &'static str {
+        r#"
+-- Generates Broken.bn with a line map back to this file
+output_file: TEXT { Generated/Broken.bn }
+
+written: File/write_with_map(
+    path: output_file
+    content: TEXT {
+        answer: 42
+        broken: [answer
+    }
+    map: LIST {
+        [line: 1, file: TEXT { BUILD.bn }, source_line: 8]
+        [line: 2, file: TEXT { BUILD.bn }, source_line: 9]
+    }
+)
 "#
-        )
     }
 
-    fn nested_cells_map_helper_when_while_source() -> String {
+    fn count_where_source() -> &'static str {
         r#"
-FUNCTION make_cell(column, row) {
-    [column: column row: row]
+FUNCTION make_todo(completed) {
+    [completed: completed]
 }
 
-FUNCTION make_row_cells(row) { LIST {
-    make_cell(column: 1, row: row)
-    make_cell(column: 2, row: row)
-} }
+todos: LIST {
+    make_todo(completed: True)
+    make_todo(completed: False)
+    make_todo(completed: True)
+}
 
-FUNCTION is_editing_cell(cell) {
-    editing_cell.row == cell.row |> WHEN {
-        True => editing_cell.column == cell.column
-        __ => False
+counted: todos |> List/count_where(item, if: item.completed)
+retained: todos |> List/retain(item, if: item.completed) |> List/count()
+summary: TEXT { {counted} {retained} }
+"#
     }
+
+    fn list_sort_by_source() -> &'static str {
+        r#"
+items: LIST {
+    [title: TEXT { b }, priority: 2]
+    [title: TEXT { a }, priority: 1]
+    [title: TEXT { c }, priority: 2]
 }
 
-FUNCTION make_cell_element(cell) {
-    is_editing_cell(cell: cell) |> WHILE {
-        True => Element/label(element: [], style: [width: 80], label: TEXT { y })
-        False => Element/label(element: [], style: [width: 80], label: TEXT { x })
-    }
+numbers: LIST { 3 1 2 } |> List/sort_by(item, key: item)
+titles: items |> List/sort_by(item, key: item.priority) |> List/map(item, new: item.title)
+
+dated: LIST {
+    [title: TEXT { undated }]
+    [title: TEXT { later }, created_at: 2]
+    [title: TEXT { earlier }, created_at: 1]
 }
+dated_titles: dated |> List/sort_by(item, key: item.created_at) |> List/map(item, new: item.title)
+
+add_thirty: LINK
+add_ten: LINK
+add_twenty: LINK
+appended: LIST {}
+    |> List/append(item: LATEST {
+        add_thirty |> THEN { [created_at: 30] }
+        add_ten |> THEN { [created_at: 10] }
+        add_twenty |> THEN { [created_at: 20] }
+    })
+    |> List/sort_by(item, key: item.created_at)
+    |> List/map(item, new: item.created_at)
 
-cells: make_row_cells(row: 1)
-editing_cell: [row: 0, column: 0]
-elements: cells |> List/map(cell, new: make_cell_element(cell: cell))
+promote: LINK
+second_rank: 2 |> HOLD state {
+    promote |> THEN { 0 }
+}
+ranked: LIST {
+    [title: TEXT { first }, rank: 1]
+    [title: TEXT { second }, rank: second_rank]
+}
+ranked_titles: ranked |> List/sort_by(item, key: item.rank) |> List/map(item, new: item.title)
 "#
-        .to_string()
     }
 
-    fn nested_cells_map_helper_when_while_text_input_source() -> String {
+    fn list_filter_source() -> &'static str {
         r#"
-FUNCTION make_cell(column, row) {
-    [column: column row: row cell_elements: [display: LINK, editing: LINK]]
+finish_b: LINK
+remove_a: LINK
+
+b_completed: False |> HOLD state {
+    finish_b |> THEN { True }
 }
 
-FUNCTION make_row_cells(row) { LIST {
-    make_cell(column: 1, row: row)
-    make_cell(column: 2, row: row)
-} }
+todos: LIST {
+    [title: TEXT { a }, completed: True]
+    [title: TEXT { b }, completed: b_completed]
+    [title: TEXT { c }, completed: False]
+}
+    |> List/remove(item, on: remove_a |> THEN {
+        item.title == TEXT { a } |> WHEN {
+            True => []
+            False => SKIP
+        }
+    })
 
-FUNCTION is_editing_cell(cell) {
-    editing_cell.row == cell.row |> WHEN {
-        True => editing_cell.column == cell.column
-        __ => False
+numbers: LIST { 123, 345, 1 }
+large: numbers |> List/filter(item, if: item > 200)
+
+active: todos |> List/filter(item, if: item.completed |> Bool/not()) |> List/map(item, new: item.title)
+completed: todos |> List/filter(item, if: item.completed) |> List/map(item, new: item.title)
+all: todos |> List/map(item, new: item.title)
+"#
     }
-}
 
-FUNCTION make_cell_element(cell) {
-    is_editing_cell(cell: cell) |> WHILE {
-        True => Element/text_input(element: [event: [input: LINK, key_down: LINK, blur: LINK]] style: [width: 80] label: Hidden[text: TEXT { cell }] text: TEXT {  } placeholder: [text: Text/empty()] focus: True) |> LINK { cell.cell_elements.editing }
-        False => Element/label(element: [], style: [width: 80], label: TEXT { x }) |> LINK { cell.cell_elements.display }
+    fn list_first_last_source() -> &'static str {
+        r#"
+add: LINK
+
+numbers: LIST { 1 2 3 }
+first: numbers |> List/first()
+last: numbers |> List/last()
+empty_first: LIST {} |> List/first()
+empty_last: LIST {} |> List/last()
+
+growing: LIST {} |> List/append(item: add |> THEN { 7 })
+growing_last: growing |> List/last()
+
+remove_first: LINK
+clear: LINK
+items: LIST { 1, 2 }
+    |> List/append(item: add |> THEN { 3 })
+    |> List/remove(item, on: remove_first |> THEN {
+        item == 1 |> WHEN {
+            True => []
+            False => SKIP
+        }
+    })
+    |> List/clear(on: clear)
+ends: [first: items |> List/first(), last: items |> List/last()]
+"#
     }
-}
 
-cells: make_row_cells(row: 1)
-editing_cell: [row: 0, column: 0]
-elements: cells |> List/map(cell, new: make_cell_element(cell: cell))
+    fn list_reverse_source() -> &'static str {
+        r#"
+add: LINK
+remove_oldest: LINK
+
+numbers: LIST { 1 2 3 }
+    |> List/append(item: add |> THEN { 4 })
+    |> List/remove(item, on: remove_oldest |> THEN {
+        item == 1 |> WHEN {
+            True => []
+            False => SKIP
+        }
+    })
+reversed: numbers |> List/reverse()
+empty: LIST {} |> List/reverse()
 "#
-        .to_string()
     }
 
-    fn nested_cells_map_helper_when_while_nested_link_source() -> String {
+    fn list_contains_index_of_source() -> &'static str {
         r#"
-FUNCTION make_cell(column, row) {
-    [column: column row: row cell_elements: [display: LINK, editing: LINK]]
+add: LINK
+clear: LINK
+next: LINK
+rename: LINK
+
+tags: LIST { Red, Green, Blue, Green }
+    |> List/append(item: add |> THEN { Home })
+    |> List/clear(on: clear)
+has_green: tags |> List/contains(value: Green)
+green_position: tags |> List/index_of(value: Green)
+has_home: tags |> List/contains(value: Home)
+home_position: tags |> List/index_of(value: Home)
+has_point: LIST { [x: 1, y: 2], [x: 3, y: 4] } |> List/contains(value: [y: 4, x: 3])
+
+searched: 1 |> HOLD state {
+    next |> THEN { state + 1 }
 }
+searched_position: LIST { 2, 3 } |> List/index_of(value: searched)
 
-FUNCTION make_row_cells(row) { LIST {
-    make_cell(column: 1, row: row)
-    make_cell(column: 2, row: row)
-} }
-
-FUNCTION is_editing_cell(cell) {
-    editing_cell.row == cell.row |> WHEN {
-        True => editing_cell.column == cell.column
-        __ => False
+second: TEXT { b } |> HOLD state {
+    rename |> THEN { TEXT { c } }
+}
+letters: LIST { TEXT { a }, second }
+found: [
+    contains: letters |> List/contains(value: TEXT { c })
+    index: letters |> List/index_of(value: TEXT { c })
+]
+"#
     }
+
+    fn list_zip_source() -> &'static str {
+        r#"
+add_name: LINK
+clear_scores: LINK
+
+names: LIST { TEXT { Ann } } |> List/append(item: add_name |> THEN { TEXT { Bob } })
+scores: LIST { 3, 5 } |> List/clear(on: clear_scores)
+pairs: names |> List/zip(with: scores)
+sums: LIST { 1, 2, 3 }
+    |> List/zip(with: LIST { 10, 20 })
+    |> List/map(item, new: item.first + item.second)
+
+score: LINK
+first_score: 0 |> HOLD state {
+    score |> THEN { state + 4 }
 }
+scored: LIST { TEXT { Ann } } |> List/zip(with: LIST { first_score })
+"#
+    }
 
-FUNCTION make_cell_element(cell) {
-    is_editing_cell(cell: cell) |> WHILE {
-        True => Element/label(element: [], style: [width: 80], label: TEXT { y })
-        False => Element/label(element: [], style: [width: 80], label: TEXT { x }) |> LINK { cell.cell_elements.display }
+    fn list_sum_min_max_source() -> &'static str {
+        r#"
+add: LINK
+add_to_bread: LINK
+remove_largest: LINK
+clear: LINK
+
+bread: 2 |> HOLD state {
+    add_to_bread |> THEN { state + 3 }
+}
+prices: LIST { 1, TEXT { free }, bread }
+    |> List/append(item: add |> THEN { 0.5 })
+    |> List/remove(item, on: remove_largest |> THEN {
+        item == 5 |> WHEN {
+            True => []
+            False => SKIP
+        }
+    })
+    |> List/clear(on: clear)
+total: prices |> List/sum()
+cheapest: prices |> List/min()
+priciest: prices |> List/max()
+empty_total: LIST {} |> List/sum()
+empty_cheapest: LIST {} |> List/min()
+"#
     }
+
+    fn list_any_all_source() -> &'static str {
+        r#"
+toggle_first: LINK
+toggle_all: LINK
+add: LINK
+
+FUNCTION new_todo(toggle) {
+    [
+        completed: False |> HOLD state {
+            LATEST {
+                toggle |> THEN { state |> Bool/not() }
+                toggle_all |> THEN { all_completed |> Bool/not() }
+            }
+        }
+    ]
 }
 
-cells: make_row_cells(row: 1)
-editing_cell: [row: 0, column: 0]
-elements: cells |> List/map(cell, new: make_cell_element(cell: cell))
+todos: LIST { new_todo(toggle: toggle_first), new_todo(toggle: SKIP) }
+    |> List/append(item: add |> THEN { new_todo(toggle: SKIP) })
+any_completed: todos |> List/any(item, if: item.completed)
+all_completed: todos |> List/all(item, if: item.completed)
+empty_all: LIST {} |> List/all(item, if: False)
 "#
-        .to_string()
     }
 
-    fn nested_cells_map_helper_when_while_nested_link_toggle_source() -> String {
+    fn list_get_source() -> &'static str {
         r#"
-FUNCTION make_cell(column, row) {
-    [column: column row: row cell_elements: [display: LINK, editing: LINK]]
+next: LINK
+previous: LINK
+add: LINK
+clear: LINK
+press: LINK
+select_second: LINK
+
+FUNCTION counter(name) {
+    [
+        name: name
+        count: 0 |> HOLD state { press |> THEN { state + 1 } }
+    ]
 }
 
-FUNCTION make_row_cells(row) { LIST {
-    make_cell(column: 1, row: row)
-    make_cell(column: 2, row: row)
-} }
-
-FUNCTION is_editing_cell(cell) {
-    editing_cell.row == cell.row |> WHEN {
-        True => editing_cell.column == cell.column
-        __ => False
+items: LIST { TEXT { a }, TEXT { b } } |> List/append(item: add |> THEN { TEXT { c } })
+first: items |> List/get(index: 1)
+zero: items |> List/get(index: 0)
+fraction: items |> List/get(index: 1.5)
+current: 1 |> HOLD state {
+    LATEST {
+        next |> THEN { state + 1 }
+        previous |> THEN { state - 1 }
     }
 }
-
-FUNCTION make_cell_element(cell) {
-    is_editing_cell(cell: cell) |> WHILE {
-        True => Element/label(element: [], style: [width: 80], label: TEXT { y }) |> LINK { cell.cell_elements.editing }
-        False => Element/label(element: [], style: [width: 80], label: TEXT { x }) |> LINK { cell.cell_elements.display }
+selected: items |> List/get(index: current)
+clearable: LIST { A }
+    |> List/append(item: add |> THEN { B })
+    |> List/clear(on: clear)
+second: clearable |> List/get(index: 2)
+counters: LIST { counter(name: A), counter(name: B) }
+shown_index: 1 |> HOLD state { select_second |> THEN { 2 } }
+shown: counters |> List/get(index: shown_index)
+shown_fields: [name: shown.name, count: shown.count]
+"#
     }
+
+    fn list_unique_source() -> &'static str {
+        r#"
+add: LINK
+add_b: LINK
+remove_a: LINK
+recategorize: LINK
+
+FUNCTION new_todo(title, category) {
+    [
+        title: title
+        category: category |> HOLD state { recategorize |> THEN { Home } }
+    ]
 }
 
-toggle: LINK
-cells: make_row_cells(row: 1)
-editing_cell: [row: 1, column: 1] |> HOLD state {
-    toggle |> THEN { [row: 0, column: 0] }
+letters: LIST { B, A, B, C, A }
+    |> List/append(item: LATEST {
+        add |> THEN { D }
+        add_b |> THEN { B }
+    })
+    |> List/remove(item, on: remove_a |> THEN {
+        item == A |> WHEN {
+            True => []
+            False => SKIP
+        }
+    })
+unique_letters: letters |> List/unique()
+points: LIST { [x: 1, y: 2], [x: 1, y: 3], [x: 1, y: 2] } |> List/unique()
+todos: LIST {
+    new_todo(title: TEXT { Milk }, category: Shopping)
+    new_todo(title: TEXT { Report }, category: Work)
+    new_todo(title: TEXT { Bread }, category: Shopping)
 }
-elements: cells |> List/map(cell, new: make_cell_element(cell: cell))
+first_per_category: todos
+    |> List/unique_by(item, key: item.category)
+    |> List/map(item, new: item.title)
+categories: todos |> List/map(item, new: item.category) |> List/unique()
 "#
-        .to_string()
     }
 
-    fn nested_cells_reopen_editing_source() -> String {
+    fn list_concat_source() -> &'static str {
         r#"
-FUNCTION make_cell(column, row) {
-    [column: column row: row cell_elements: [display: LINK, editing: LINK]]
+pin: LINK
+unpin: LINK
+add: LINK
+clear_recent: LINK
+press: LINK
+add_counter: LINK
+
+FUNCTION counter(name) {
+    [
+        name: name
+        count: 0 |> HOLD state { press |> THEN { state + 1 } }
+    ]
 }
 
-FUNCTION make_row_cells(row) { LIST {
-    make_cell(column: 1, row: row)
-} }
+pinned: LIST { TEXT { pinned } }
+    |> List/append(item: pin |> THEN { TEXT { starred } })
+    |> List/remove(item, on: unpin |> THEN {
+        item == TEXT { pinned } |> WHEN {
+            True => []
+            False => SKIP
+        }
+    })
+recent: LIST { TEXT { recent } }
+    |> List/append(item: add |> THEN { TEXT { newest } })
+    |> List/clear(on: clear_recent)
+notes: pinned |> List/concat(with: recent)
+empty_first: LIST {} |> List/concat(with: LIST { TEXT { a } })
+pinned_counters: LIST { counter(name: A) }
+recent_counters: LIST { counter(name: B) } |> List/append(item: add_counter |> THEN { counter(name: C) })
+counters: pinned_counters
+    |> List/concat(with: recent_counters)
+    |> List/map(item, new: [name: item.name, count: item.count])
+"#
+    }
 
-FUNCTION is_editing_cell(cell) {
-    editing_active |> WHEN {
-        True => editing_row == cell.row |> WHEN {
-            True => editing_column == cell.column
-            __ => False
+    fn text_join_source() -> &'static str {
+        r#"
+add: LINK
+rename: LINK
+remove_shared: LINK
+
+first_tag: TEXT { draft } |> HOLD state { rename |> THEN { TEXT { final } } }
+tags: LIST { first_tag, TEXT { shared } }
+    |> List/append(item: add |> THEN { TEXT { new } })
+    |> List/remove(item, on: remove_shared |> THEN {
+        item == TEXT { shared } |> WHEN {
+            True => []
+            False => SKIP
         }
-        __ => False
+    })
+joined: tags |> Text/join(separator: TEXT { ,  })
+mixed: LIST { 3, Urgent, TEXT { ok } } |> Text/join(separator: TEXT { / })
+empty: LIST {} |> Text/join(separator: TEXT { ,  })
+"#
     }
-}
 
-FUNCTION make_cell_element(cell) {
-    BLOCK {
-        display_element: [event: [double_click: LINK]]
-        editing_element: [event: [key_down: LINK, blur: LINK]]
-
-        edit_started_row_link:
-            display_element.event.double_click |> THEN { cell.row }
-            |> LINK { event_ports.edit_started_row }
-        edit_started_column_link:
-            display_element.event.double_click |> THEN { cell.column }
-            |> LINK { event_ports.edit_started_column }
-        edit_started_active_link:
-            display_element.event.double_click |> THEN { True }
-            |> LINK { event_ports.edit_active_event }
-
-        edit_cancelled_link:
-            editing_element.event.key_down.key
-            |> WHEN { Escape => False __ => SKIP }
-            |> LINK { event_ports.edit_active_event }
-        edit_blurred_link:
-            editing_element.event.blur
-            |> THEN { False }
-            |> LINK { event_ports.edit_active_event }
-
-        is_editing_cell(cell: cell) |> WHILE {
-            True => Element/text_input(
-                element: editing_element
-                style: [width: 80]
-                label: Hidden[text: TEXT { cell }]
-                text: Text/empty()
-                placeholder: [text: Text/empty()]
-                focus: True
-            )
-            |> LINK { cell.cell_elements.editing }
+    fn text_split_source() -> &'static str {
+        r#"
+change: LINK
+clear: LINK
 
-            False => Element/label(
-                element: display_element
-                style: [width: 80]
-                label: TEXT { x }
-            )
-            |> LINK { cell.cell_elements.display }
-        }
+tags: TEXT { red, green } |> HOLD state {
+    LATEST {
+        change |> THEN { TEXT { red, blue, } }
+        clear |> THEN { Text/empty() }
     }
 }
+parts: tags |> Text/split(separator: TEXT { , }) |> List/map(tag, new: tag |> Text/trim())
+empty: Text/empty() |> Text/split(separator: TEXT { , })
+lengths: TEXT { one and two and three }
+    |> Text/split(separator: TEXT {  and  })
+    |> List/map(part, new: part |> Text/length())
+"#
+    }
 
-event_ports: [
-    edit_started_row: LINK
-    edit_started_column: LINK
-    edit_active_event: LINK
-]
+    fn text_case_source() -> &'static str {
+        r#"
+change: LINK
+search_ann: LINK
+search_jo: LINK
 
-editing_row: 0 |> HOLD state { event_ports.edit_started_row }
-editing_column: 0 |> HOLD state { event_ports.edit_started_column }
-editing_active: False |> HOLD state { event_ports.edit_active_event }
+name: TEXT { Ann } |> HOLD state { change |> THEN { TEXT { Bob } } }
+shouted: name |> Text/to_uppercase()
+query: Text/empty() |> HOLD state {
+    LATEST {
+        search_ann |> THEN { TEXT { ANN } }
+        search_jo |> THEN { TEXT { jo } }
+    }
+}
+names: LIST { TEXT { Anna Smith }, TEXT { Bob Jones }, TEXT { JOANNA Lee } }
+matches: names |> List/retain(item, if: BLOCK {
+    lowercase_query: query |> Text/to_lowercase()
+    position: item |> Text/to_lowercase() |> Text/find(search: lowercase_query)
 
-cells: make_row_cells(row: 1)
-elements: cells |> List/map(cell, new: make_cell_element(cell: cell))
+    position >= 0
+})
 "#
-        .to_string()
     }
 
-    fn mapped_todo_item_event_link_source() -> &'static str {
+    fn text_contains_source() -> &'static str {
         r#"
-FUNCTION new_todo(title) {
-    [
-        title: title
-        todo_elements: [
-            todo_title_element: LINK
-            todo_checkbox: LINK
-        ]
-    ]
+arrive: LINK
+rename: LINK
+search: LINK
+search_buy: LINK
+search_room: LINK
+
+found: TEXT { Buy groceries } |> Text/contains(needle: TEXT { groc })
+missing: TEXT { Buy groceries } |> Text/contains(needle: TEXT { milk })
+empty_needle: TEXT { Buy groceries } |> Text/contains(needle: Text/empty())
+case_sensitive: TEXT { Buy groceries } |> Text/contains(needle: TEXT { BUY })
+late_needle: TEXT { Buy groceries } |> Text/contains(needle: arrive |> THEN { TEXT { Buy } })
+title: TEXT { Buy milk } |> HOLD state { rename |> THEN { TEXT { Buy bread } } }
+needle: TEXT { milk } |> HOLD state { search |> THEN { TEXT { bread } } }
+title_matches: title |> Text/contains(needle: needle)
+query: Text/empty() |> HOLD state {
+    LATEST {
+        search_buy |> THEN { TEXT { Buy } }
+        search_room |> THEN { TEXT { room } }
+    }
 }
+todos: LIST { TEXT { Buy milk }, TEXT { Clean room }, TEXT { Buy bread } }
+    |> List/filter(item, if: item |> Text/contains(needle: query))
+"#
+    }
 
-FUNCTION todo_checkbox(todo) {
-    Element/checkbox(
-        element: [event: [click: LINK]]
-        style: []
-        label: Hidden[text: TEXT { Toggle }]
-        checked: False
-        icon: Element/container(element: [], style: [], child: NoElement)
-    )
-    |> LINK { todo.todo_elements.todo_checkbox }
+    fn text_replace_source() -> &'static str {
+        r#"
+dash: LINK
+clear_replacement: LINK
+change_text: LINK
+change_from: LINK
+
+every: TEXT { one-two-three } |> Text/replace(from: TEXT { - }, to: TEXT { + })
+repeated: TEXT { aaa } |> Text/replace(from: TEXT { aa }, to: TEXT { b })
+overlapping: TEXT { ababa } |> Text/replace(from: TEXT { aba }, to: TEXT { x })
+grows: TEXT { aa } |> Text/replace(from: TEXT { a }, to: TEXT { aa })
+empty_from: TEXT { abc } |> Text/replace(from: Text/empty(), to: TEXT { x })
+empty_to: TEXT { a, b, c } |> Text/replace(from: TEXT { ,  }, to: Text/empty())
+replacement: TEXT { _ } |> HOLD state {
+    LATEST {
+        dash |> THEN { TEXT { - } }
+        clear_replacement |> THEN { Text/empty() }
+    }
 }
+name: TEXT { snake case name } |> Text/replace(from: Text/space(), to: replacement)
+text: TEXT { red apple } |> HOLD state { change_text |> THEN { TEXT { red pear } } }
+from: TEXT { red } |> HOLD state { change_from |> THEN { TEXT { pear } } }
+recolored: text |> Text/replace(from: from, to: TEXT { green })
+"#
+    }
 
-FUNCTION todo_item(todo) {
-    Element/stripe(
-        element: []
-        direction: Row
-        gap: 0
-        style: []
-        items: LIST {
-            todo_checkbox(todo: todo)
-            Element/label(
-                element: [event: [double_click: LINK]]
-                style: []
-                label: todo.title
-            )
-            |> LINK { todo.todo_elements.todo_title_element }
+    fn text_starts_ends_with_source() -> &'static str {
+        r#"
+go_to: LINK
+section: LINK
+
+path: TEXT { /users/42/edit }
+is_users: path |> Text/starts_with(prefix: TEXT { /users })
+is_admin: path |> Text/starts_with(prefix: TEXT { /admin })
+is_edit: path |> Text/ends_with(suffix: TEXT { /edit })
+is_view: path |> Text/ends_with(suffix: TEXT { /view })
+empty_prefix: TEXT { abc } |> Text/starts_with(prefix: Text/empty())
+empty_suffix: TEXT { abc } |> Text/ends_with(suffix: Text/empty())
+number_text: 42 |> Text/starts_with(prefix: Text/empty())
+tag_suffix: TEXT { Done } |> Text/ends_with(suffix: Done)
+route: TEXT { / } |> HOLD state { go_to |> THEN { go_to } }
+in_settings: route |> Text/starts_with(prefix: TEXT { /settings })
+is_detail: route |> Text/ends_with(suffix: TEXT { /detail })
+prefix: TEXT { /settings } |> HOLD state { section |> THEN { TEXT { /home } } }
+in_section: TEXT { /settings/profile } |> Text/starts_with(prefix: prefix)
+"#
+    }
+
+    fn text_slice_source() -> &'static str {
+        r#"
+grow: LINK
+rename: LINK
+
+FUNCTION short_title(title) {
+    BLOCK {
+        length: title |> Text/length()
+
+        length > 10 |> WHILE {
+            True => BLOCK {
+                start: title |> Text/slice(start: 1, end: 9)
+
+                TEXT { {start}… }
+            }
+            False => title
         }
-    )
+    }
 }
 
-todos: LIST {
-    new_todo(title: TEXT { Buy groceries })
+short: short_title(title: TEXT { Buy milk })
+long: short_title(title: TEXT { Clean the whole garage })
+end: 3 |> HOLD state { grow |> THEN { state + 2 } }
+title: TEXT { Groceries } |> HOLD state { rename |> THEN { TEXT { Laundry } } }
+sliced: title |> Text/slice(start: 1, end: end)
+"#
+    }
+
+    fn text_pad_source() -> &'static str {
+        r#"
+widen: LINK
+change_fill: LINK
+
+zero_padded: TEXT { 7 } |> Text/pad_start(length: 3, with: TEXT { 0 })
+dotted: TEXT { Name } |> Text/pad_end(length: 8, with: TEXT { . })
+repeated_start: TEXT { 7 } |> Text/pad_start(length: 6, with: TEXT { ab })
+repeated_end: TEXT { 7 } |> Text/pad_end(length: 6, with: TEXT { ab })
+already_long: TEXT { 123 } |> Text/pad_start(length: 2, with: TEXT { 0 })
+exact: TEXT { 12 } |> Text/pad_start(length: 2, with: TEXT { 0 })
+empty_with: TEXT { 7 } |> Text/pad_end(length: 5, with: Text/empty())
+characters: TEXT { 👋 } |> Text/pad_start(length: 3, with: TEXT { č })
+number: 5 |> Text/pad_start(length: 2, with: TEXT { 0 })
+length: 2 |> HOLD state { widen |> THEN { state + 2 } }
+fill: TEXT { 0 } |> HOLD state { change_fill |> THEN { TEXT { * } } }
+padded: TEXT { 7 } |> Text/pad_start(length: length, with: fill)
+elapsed: 425
+clock: BLOCK {
+    seconds: elapsed |> Math/modulo(by: 60)
+    minutes: (elapsed - seconds) / 60
+    minutes_text: minutes |> Text/pad_start(length: 2, with: TEXT { 0 })
+    seconds_text: seconds |> Text/pad_start(length: 2, with: TEXT { 0 })
+
+    TEXT { {minutes_text}:{seconds_text} }
 }
+"#
+    }
 
-elements: todos |> List/map(todo, new: todo_item(todo: todo))
+    fn list_take_drop_source() -> &'static str {
+        r#"
+add: LINK
+grow: LINK
+
+count: 1 |> HOLD state { grow |> THEN { state + 1 } }
+items: LIST { 1, 2, 3 } |> List/append(item: add |> THEN { 4 })
+first: items |> List/take(count: count)
+rest: items |> List/drop(count: count)
+take_negative: items |> List/take(count: -1)
+drop_text: items |> List/drop(count: TEXT { two })
+
+fixed: LIST { 1, 2, 3 }
+clamped: [
+    take_more: fixed |> List/take(count: 5)
+    take_text: fixed |> List/take(count: TEXT { two })
+    drop_more: fixed |> List/drop(count: 5)
+    drop_negative: fixed |> List/drop(count: -1)
+]
 "#
     }
 
-    fn linked_checkbox_updates_sibling_hold_source() -> &'static str {
+    fn list_find_source() -> &'static str {
         r#"
-FUNCTION todo_checkbox(todo) {
-    Element/checkbox(
-        element: [event: [click: LINK]]
-        style: []
-        label: Hidden[text: TEXT { Toggle }]
-        checked: False
-        icon: Element/container(element: [], style: [], child: NoElement)
-    )
-    |> LINK { todo.todo_elements.todo_checkbox }
+next: LINK
+rename: LINK
+
+selected_id: 1 |> HOLD state { next |> THEN { state + 1 } }
+second_name: TEXT { Banana } |> HOLD state { rename |> THEN { TEXT { Blueberry } } }
+items: LIST {
+    [id: 1, name: TEXT { Apple }]
+    [id: 2, name: second_name]
+}
+selected_name: items |> List/find(item, if: item.id == selected_id) |> WHILE {
+    None => TEXT { none }
+    item => item.name
 }
 
-FUNCTION new_todo() {
-    [
-        todo_elements: [
-            todo_checkbox: LINK
-        ]
+first_large: LIST { 1, 5, 10, 15 } |> List/find(item, if: item > 4)
+no_match: LIST { 1, 2 } |> List/find(item, if: item > 4)
 
-        completed: False |> HOLD state {
-            todo_elements.todo_checkbox.event.click |> THEN { state |> Bool/not() }
-        }
-    ]
+finish: LINK
+first_done: False |> HOLD state { finish |> THEN { True } }
+tasks: LIST {
+    [title: TEXT { Write }, done: first_done]
+    [title: TEXT { Review }, done: False]
+}
+done_title: tasks |> List/find(item, if: item.done) |> WHILE {
+    None => TEXT { none }
+    item => item.title
 }
 
-todo: new_todo()
-checkbox: todo_checkbox(todo: todo)
+add: LINK
+remove_large: LINK
+growing: LIST { 1 }
+    |> List/append(item: add |> THEN { 7 })
+    |> List/remove(item, on: remove_large |> THEN {
+        item > 4 |> WHEN {
+            True => []
+            False => SKIP
+        }
+    })
+large: growing |> List/find(item, if: item > 4)
 "#
     }
 
-    fn read_example_source(path: &str) -> String {
-        std::fs::read_to_string(path).unwrap_or_else(|error| {
-            panic!("failed to read example source {path}: {error}");
-        })
-    }
-
-    fn list_map_external_dependency_source() -> &'static str {
+    fn list_flatten_source() -> &'static str {
         r#"
+add: LINK
 toggle: LINK
 
-show_shifted: False |> HOLD state {
-    toggle |> THEN { state |> Bool/not() }
+show_first: True |> HOLD state { toggle |> THEN { state |> Bool/not() } }
+first: LIST { 1, 2 }
+second: LIST { 10 } |> List/append(item: add |> THEN { 11 })
+sections: LIST {
+    show_first |> WHILE {
+        True => first
+        False => LIST {}
+    }
+    second
+    99
 }
+flat: sections |> List/flatten()
+one_level: LIST { LIST { LIST { 1 } }, LIST { 2 } } |> List/flatten()
 
-items: LIST { 1 2 3 }
-
-mapped: items |> List/map(item, new:
-    show_shifted |> WHEN {
-        True => item + 10
-        __ => item
+add_section: LINK
+grown: LIST { LIST { 1, 2 } }
+    |> List/append(item: add_section |> THEN { LIST { 3, 4 } })
+    |> List/flatten()
+"#
     }
-)
+
+    fn list_insert_move_source() -> &'static str {
+        r#"
+add: LINK
+append: LINK
+down: LINK
+up: LINK
+
+items: LIST { 1, 2, 3 }
+    |> List/append(item: append |> THEN { 4 })
+    |> List/insert(index: 1, item: 0, on: add)
+    |> List/move(from: 1, to: 100, on: down)
+    |> List/move(from: 100, to: 1, on: up)
+large: items |> List/retain(item, if: item > 1)
+
+insert_middle: LINK
+middle: LIST { 1, 2, 3 } |> List/insert(index: 2, item: 9, on: insert_middle)
+
+add_first: LINK
+add_last: LINK
+edges: LIST { 1, 2 }
+    |> List/insert(index: 0, item: 0, on: add_first)
+    |> List/insert(index: 99, item: 9, on: add_last)
+
+forward: LINK
+back: LINK
+shuffled: LIST { 1, 2, 3, 4 }
+    |> List/move(from: 1, to: 3, on: forward)
+    |> List/move(from: 4, to: 1, on: back)
+
+to_end: LINK
+clamped: LIST { 1, 2, 3 } |> List/move(from: -5, to: 99, on: to_end)
+
+bump: LINK
+move_first_to_end: LINK
+FUNCTION counter(name) {
+    [
+        name: name
+        count: 0 |> HOLD state { bump |> THEN { state + 1 } }
+    ]
+}
+counters: LIST { counter(name: TEXT { a }), counter(name: TEXT { b }) }
+    |> List/move(from: 1, to: 2, on: move_first_to_end)
 "#
     }
 
-    fn cells_mapped_formula_dependency_source() -> &'static str {
+    fn list_update_source() -> &'static str {
         r#"
-FUNCTION matching_overrides(column, row) {
-    overrides |> List/retain(item, if:
-        item.row == row |> WHEN {
-            True => item.column == column
-            __ => False
-        }
-    )
+mark: LINK
+mark_all: LINK
+press: LINK
+reset_first: LINK
+
+FUNCTION counter() {
+    [count: 0 |> HOLD state { press |> THEN { state + 1 } }]
 }
 
-FUNCTION cell_formula(column, row) {
-    BLOCK {
-        matches: matching_overrides(column: column, row: row)
-        match_count: matches |> List/count()
-        match_count == 0 |> WHEN {
-            True => default_formula(column: column, row: row)
-            __ => BLOCK {
-                latest_match: matches |> List/get(index: match_count)
-                latest_match.text
-            }
-        }
-    }
+items: LIST { 1, 2, 3 } |> List/update(index: 2, new: 20, on: mark)
+large: items |> List/retain(item, if: item > 2)
+doubled: items |> List/map(item, new: item * 2)
+total: items |> List/sum()
+outside: LIST { A, B } |> List/update(index: 5, new: X, on: mark)
+messages: LIST {
+    [title: TEXT { Hi }, read: False]
+    [title: TEXT { News }, read: True]
+    [title: TEXT { Bye }, read: False]
 }
+|> List/update(
+    item
+    if: item.read |> Bool/not()
+    new: [title: item.title, read: True]
+    on: mark_all
+)
+unread: messages |> List/count_where(item, if: item.read |> Bool/not())
+counters: LIST { counter(), counter() } |> List/update(index: 1, new: counter(), on: reset_first)
+"#
+    }
 
-FUNCTION default_formula(column, row) {
-    column == 1 |> WHEN {
-        True => row == 1 |> WHEN {
-            True => TEXT { 5 }
-            __ => TEXT { 10 }
+    fn list_chunk_source() -> &'static str {
+        r#"
+add: LINK
+add_five: LINK
+remove_first: LINK
+
+items: LIST { 1, 2, 3 }
+    |> List/append(item: LATEST {
+        add |> THEN { 4 }
+        add_five |> THEN { 5 }
+    })
+    |> List/remove(item, on: remove_first |> THEN {
+        item == 1 |> WHEN {
+            True => []
+            False => SKIP
         }
-        __ => TEXT { =add(A1, A2) }
+    })
+rows: items |> List/chunk(size: 2)
+scaled: rows |> List/map(row, new: row |> List/map(item, new: item * 10))
+range_rows: List/range(from: 1, to: 7) |> List/chunk(size: 3)
+empty: LIST {} |> List/chunk(size: 3)
+exact: LIST { 1, 2 } |> List/chunk(size: 2)
+larger_than_list: LIST { 1, 2 } |> List/chunk(size: 5)
+"#
     }
-}
 
-FUNCTION compute_value(formula_text) {
-    formula_text |> Text/starts_with(prefix: TEXT { = }) |> WHILE {
-        False => formula_text |> Text/to_number() |> WHILE { NaN => 0 number => number }
-        __ => compute_value(formula_text: cell_formula(column: 1, row: 1))
-            + compute_value(formula_text: cell_formula(column: 1, row: 2))
-    }
+    fn shopping_total_source() -> &'static str {
+        r#"
+add: LINK
+
+items: LIST {
+    [name: TEXT { Milk }, price: 1]
+    [name: TEXT { Bread }, price: 3.1]
+    [name: TEXT { Apples }, price: 5.8]
 }
+|> List/append(item: add |> THEN { [name: TEXT { Coffee }, price: 2.6] })
 
-FUNCTION make_cell(column, row) { [column: column row: row] }
+total: items |> List/map(item, new: item.price) |> List/sum()
+total_text: BLOCK {
+    total: total |> Number/to_text(decimals: 2, thousands_separator: TEXT { , })
+    TEXT { Total: {total} }
+}
+"#
+    }
 
-FUNCTION make_cell_text(cell) {
-    BLOCK {
-        formula_text: cell_formula(column: cell.column, row: cell.row)
-        display_value: compute_value(formula_text: formula_text)
-        TEXT { {display_value} }
+    fn number_to_text_source() -> &'static str {
+        r#"
+add: LINK
+more_decimals: LINK
+group: LINK
+
+noisy_total: 0.1 + 0.2
+noisy_formatted: noisy_total |> Number/to_text(decimals: 2)
+interpolated: TEXT { {noisy_total} {noisy_formatted} }
+total: 1000 |> HOLD state { add |> THEN { state + 234.5 } }
+decimals: 0 |> HOLD state { more_decimals |> THEN { state + 2 } }
+separator: Text/empty() |> HOLD state { group |> THEN { TEXT { , } } }
+formatted: total |> Number/to_text(decimals: decimals, thousands_separator: separator)
+"#
     }
-}
 
-edit_committed: LINK
+    fn number_parse_source() -> &'static str {
+        r#"
+typed: LINK
 
-overrides: LIST { } |> HOLD state {
-    edit_committed |> THEN {
-        state
-        |> List/retain(item, if:
-            item.row == edit_committed.row |> WHEN {
-                True => item.column == edit_committed.column |> WHEN {
-                    True => False
-                    __ => True
-                }
-                __ => True
-            }
-        )
-        |> List/append(item: [
-            row: edit_committed.row
-            column: edit_committed.column
-            text: edit_committed.text
-        ])
+FUNCTION describe(text) {
+    text |> Number/parse() |> WHEN {
+        ParseError[text] => TEXT { not a number: {text} }
+        number => TEXT { number {number} }
     }
 }
 
-formula_a1: cell_formula(column: 1, row: 1)
-cells: LIST { make_cell(column: 1, row: 1) make_cell(column: 2, row: 1) }
-mapped: cells |> List/map(cell, new: make_cell_text(cell: cell))
+letters: describe(text: TEXT { twelve })
+exponent: describe(text: TEXT { 1e3 })
+empty: describe(text: Text/empty())
+number: describe(text: TEXT { 7 })
+unit_price: 4
+quantity_text: TEXT { 1 } |> HOLD state { typed |> THEN { typed } }
+price: quantity_text |> Number/parse() |> WHEN {
+    ParseError[text] => 0
+    quantity => quantity * unit_price
+}
 "#
     }
 
-    fn shared_snapshot_root_toggle_source() -> &'static str {
+    fn character_counter_source() -> &'static str {
         r#"
-toggle_all: LINK
-
-FUNCTION make_todo(initial_completed) {
-    [
-        completed: initial_completed |> HOLD state {
-            toggle_all |> THEN { all_completed |> Bool/not() }
-        }
-    ]
+typed: LINK
+post: LINK
+
+posted: post |> THEN { trimmed_draft }
+draft: LATEST {
+    Text/empty()
+    typed
+    post |> THEN { Text/empty() }
 }
+trimmed_draft: draft |> Text/trim()
+length: draft |> Text/length()
+counter: TEXT { {length}/280 }
+post_disabled: trimmed_draft |> Text/is_empty()
+posts: LIST {} |> List/append(item: posted)
+"#
+    }
 
-todos: LIST {
-    make_todo(initial_completed: False)
-    make_todo(initial_completed: False)
-    make_todo(initial_completed: False)
-    make_todo(initial_completed: True)
-}
+    fn math_min_max_source() -> &'static str {
+        r#"
+scroll: LINK
+resize: LINK
+tick: LINK
+change: LINK
+
+offset: 0 |> HOLD state { scroll |> THEN { state + scroll } }
+max_offset: 100 |> HOLD state { resize |> THEN { resize } }
+clamped: offset |> Math/min(with: max_offset) |> Math/max(with: 0)
+elapsed: 0 |> HOLD state { tick |> THEN { state + 40 } }
+progress: elapsed |> Math/min(with: 100)
+limit: 10 |> HOLD state { change |> THEN { change } }
+at_least_five: 5 |> Math/max(with: limit)
+"#
+    }
 
-completed_todos_count: todos |> List/retain(item, if: item.completed) |> List/count()
-all_completed: completed_todos_count == 4
+    fn math_rounding_source() -> &'static str {
+        r#"
+add: LINK
+
+value: 0 |> HOLD state { add |> THEN { state + add } }
+rounded: [
+    round: value |> Math/round()
+    floor: value |> Math/floor()
+    ceil: value |> Math/ceil()
+]
 "#
     }
 
-    #[test]
-    fn latest_press_sequence_preserves_two_then_arms_for_evaluator() {
-        let (_source_code, variables) = parse_static_variables(latest_conformance_source());
-        let selected = variables
-            .get("selected")
-            .cloned()
-            .expect("selected variable should exist");
+    fn math_abs_negate_if_source() -> &'static str {
+        r#"
+flip: LINK
+add: LINK
+change: LINK
+
+negative: False |> HOLD state { flip |> THEN { state |> Bool/not() } }
+flipped: 3 |> Math/negate_if(condition: negative)
+amount: 1 |> HOLD state { add |> THEN { state + add } }
+negated_amount: amount |> Math/negate_if(condition: True)
+previous: 10
+current: 10 |> HOLD state { change |> THEN { change } }
+delta: BLOCK {
+    difference: current - previous
+    fell: difference < 0
+    size: difference |> Math/abs()
+    fell |> WHEN {
+        True => TEXT { -{size} }
+        False => TEXT { +{size} }
+    }
+}
+signed_back: current - previous |> Math/abs() |> Math/negate_if(condition: current < previous)
+"#
+    }
 
-        let static_expression::Expression::Latest { inputs } = selected.node else {
-            panic!("expected selected to remain a LATEST expression");
-        };
+    fn math_pow_sqrt_source() -> &'static str {
+        r#"
+grow: LINK
+years: LINK
+change: LINK
 
-        assert_eq!(inputs.len(), 2, "expected two LATEST inputs");
+FUNCTION describe_root(number) {
+    number |> Math/sqrt() |> WHEN {
+        MathError => TEXT { no real root }
+        root => TEXT { root {root} }
+    }
+}
 
-        let arm_texts: Vec<String> = inputs
-            .iter()
-            .map(|input| {
-                let chain = flatten_pipe_chain(input.clone());
-                assert_eq!(
-                    chain.len(),
-                    2,
-                    "expected each LATEST arm to stay a two-step pipe for Actors evaluation"
-                );
+negative: describe_root(number: -9)
+positive: describe_root(number: 9)
+rate: 1 |> HOLD state { grow |> THEN { state + 1 } }
+exponent: 1 |> HOLD state { years |> THEN { years } }
+power: rate |> Math/pow(exponent: exponent)
+number: 4 |> HOLD state { change |> THEN { change } }
+root: number |> Math/sqrt()
+principal: 1000
+yearly_rate: 0.05
+growth: 1 + yearly_rate |> Math/pow(exponent: 2)
+balance: principal * growth |> Math/round()
+"#
+    }
 
-                let then_expr = chain.last().expect("pipe should have THEN step");
-                let static_expression::Expression::Then { body } = &then_expr.node else {
-                    panic!("expected LATEST arm to end in THEN");
-                };
-                match &body.node {
-                    static_expression::Expression::TextLiteral { parts, .. }
-                        if parts.len() == 1 =>
-                    {
-                        match &parts[0] {
-                            static_expression::TextPart::Text(text) => text.to_string(),
-                            other => panic!("expected text literal part, got {other:?}"),
-                        }
-                    }
-                    other => {
-                        panic!("expected THEN body to be single-part TEXT literal, got {other:?}")
-                    }
-                }
-            })
-            .collect();
+    fn math_modulo_source() -> &'static str {
+        r#"
+tick: LINK
+change: LINK
 
-        assert_eq!(arm_texts, vec!["left".to_string(), "right".to_string()]);
+FUNCTION stripe(index) {
+    index |> Math/modulo(by: 2) |> WHEN {
+        0 => Even
+        __ => Odd
+    }
+}
 
-        let expected = select_latest(&[
-            LatestCandidate::new(KernelValue::from("left"), TickSeq::new(TickId(1), 1)),
-            LatestCandidate::new(KernelValue::from("right"), TickSeq::new(TickId(1), 2)),
-        ]);
-        assert_eq!(expected, KernelValue::from("right"));
+first: stripe(index: 1)
+second: stripe(index: 2)
+third: stripe(index: 3)
+number: 10 |> HOLD state { tick |> THEN { state + 1 } }
+by: 4 |> HOLD state { change |> THEN { change } }
+remainder: number |> Math/modulo(by: by)
+"#
     }
 
-    #[test]
-    fn hold_press_sequence_preserves_initial_state_and_then_body_shape() {
-        let (_source_code, variables) = parse_static_variables(hold_conformance_source());
-        let counter = variables
-            .get("counter")
-            .cloned()
-            .expect("counter variable should exist");
+    fn progress_percentage_source() -> &'static str {
+        r#"
+complete: LINK
+reset: LINK
 
-        let chain = flatten_pipe_chain(counter);
-        assert_eq!(
-            chain.len(),
-            2,
-            "expected HOLD expression to remain a two-step pipe in Actors evaluator input"
-        );
-        assert!(
-            matches!(chain[0].node, static_expression::Expression::Literal(static_expression::Literal::Number(n)) if (n - 0.0).abs() < f64::EPSILON),
-            "expected HOLD initial value to stay as literal 0"
-        );
+total: 3
+completed: 0 |> HOLD state {
+    LATEST {
+        complete |> THEN { state + 1 |> Math/min(with: total) }
+        reset |> THEN { 0 }
+    }
+}
+percentage: completed / total * 100 |> Math/round()
+"#
+    }
 
-        let static_expression::Expression::Hold { state_param, body } = &chain[1].node else {
-            panic!("expected second pipe step to be HOLD");
-        };
-        assert_eq!(state_param.to_string(), "state");
+    fn math_clamp_source() -> &'static str {
+        r#"
+change_value: LINK
+change_min: LINK
+change_max: LINK
+
+value: 5 |> HOLD state { change_value |> THEN { change_value } }
+min: 0 |> HOLD state { change_min |> THEN { change_min } }
+max: 10 |> HOLD state { change_max |> THEN { change_max } }
+clamped: value |> Math/clamp(min: min, max: max)
+"#
+    }
 
-        let body_chain = flatten_pipe_chain((**body).clone());
-        assert_eq!(
-            body_chain.len(),
-            2,
-            "expected HOLD body to remain an event |> THEN pipeline"
-        );
-        let static_expression::Expression::Then { body: then_body } = &body_chain[1].node else {
-            panic!("expected HOLD body to end in THEN");
-        };
-        match &then_body.node {
-            static_expression::Expression::ArithmeticOperator(
-                static_expression::ArithmeticOperator::Add {
-                    operand_a,
-                    operand_b,
-                },
-            ) => {
-                assert!(
-                    matches!(
-                        operand_a.node,
-                        static_expression::Expression::Alias(
-                            static_expression::Alias::WithoutPassed { ref parts, .. }
-                        ) if parts.len() == 1 && parts[0].to_string() == "state"
-                    ),
-                    "expected HOLD body lhs to reference state"
-                );
-                assert!(
-                    matches!(
-                        operand_b.node,
-                        static_expression::Expression::Literal(
-                            static_expression::Literal::Number(n)
-                        ) if (n - 1.0).abs() < f64::EPSILON
-                    ),
-                    "expected HOLD body rhs to stay literal 1"
-                );
-            }
-            other => panic!("expected HOLD THEN body to be `state + 1`, got {other:?}"),
-        }
+    fn math_average_count_source() -> &'static str {
+        r#"
+reading: LINK
+event: LINK
+reset: LINK
+
+average: reading |> Math/average()
+count: event |> Math/count()
+resettable_average: reading |> Math/average(reset: reset)
+resettable_count: event |> Math/count(reset: reset)
+"#
     }
 
-    #[test]
-    fn link_press_sequence_preserves_alias_path_into_then() {
-        let (_source_code, variables) = parse_static_variables(link_conformance_source());
+    fn stream_min_max_source() -> &'static str {
+        r#"
+score: LINK
+temperature: LINK
+step: LINK
+
+high: score |> Stream/max()
+high_changes: high |> Math/count()
+low: temperature |> Stream/min()
+level: 0 |> HOLD state {
+    step |> THEN { state + 1 |> Math/modulo(by: 4) }
+}
+high_level: level |> Stream/max()
+"#
+    }
 
-        let increment_button = variables
-            .get("increment_button")
-            .cloned()
-            .expect("increment_button variable should exist");
-        assert!(
-            matches!(increment_button.node, static_expression::Expression::Link),
-            "expected increment_button to stay a LINK placeholder"
-        );
+    fn bool_and_source() -> &'static str {
+        r#"
+flip_this: LINK
+flip_that: LINK
+ready: LINK
 
-        let pressed = variables
-            .get("pressed")
-            .cloned()
-            .expect("pressed variable should exist");
-        let chain = flatten_pipe_chain(pressed);
-        assert_eq!(
-            chain.len(),
-            2,
-            "expected LINK event consumer to remain a two-step pipe"
-        );
-        assert!(
-            matches!(
-                &chain[0].node,
-                static_expression::Expression::Alias(
-                    static_expression::Alias::WithoutPassed { parts, .. }
-                ) if parts.iter().map(|part| part.to_string()).collect::<Vec<_>>()
-                    == vec!["increment_button".to_string(), "event".to_string(), "press".to_string()]
-            ),
-            "expected first step to keep increment_button.event.press alias path"
-        );
-        let static_expression::Expression::Then { body } = &chain[1].node else {
-            panic!("expected LINK event consumer to end in THEN");
-        };
-        assert!(
-            matches!(
-                body.node,
-                static_expression::Expression::TextLiteral { ref parts, .. }
-                    if parts.len() == 1
-                        && matches!(&parts[0], static_expression::TextPart::Text(text) if text.to_string() == "pressed")
-            ),
-            "expected THEN body to stay TEXT {{ pressed }}"
-        );
+FUNCTION is_editing_cell(active, same_row, same_column) {
+    active |> Bool/and(that: same_row) |> Bool/and(that: same_column)
+}
+
+true_true: True |> Bool/and(that: True)
+true_false: True |> Bool/and(that: False)
+false_true: False |> Bool/and(that: True)
+false_false: False |> Bool/and(that: False)
+this: False |> HOLD state { flip_this |> THEN { state |> Bool/not() } }
+that: False |> HOLD state { flip_that |> THEN { state |> Bool/not() } }
+both: this |> Bool/and(that: that)
+ready_count: ready |> Bool/and(that: True) |> Math/count()
+editing: is_editing_cell(active: True, same_row: True, same_column: True)
+other_column: is_editing_cell(active: True, same_row: True, same_column: False)
+inactive: is_editing_cell(active: False, same_row: True, same_column: True)
+"#
     }
 
-    #[test]
-    fn list_append_from_link_event_snapshots_object_fields() {
-        let (root_object, construct_context, _scope_guard) =
-            evaluate_program(append_from_link_source());
-        let edit_committed = root_object.expect_variable("edit_committed");
-        let overrides = root_object.expect_variable("overrides");
-        let sender = edit_committed.expect_link_value_sender();
+    fn bounded_counter_source() -> &'static str {
+        r#"
+decrement: LINK
+increment: LINK
 
-        let updated_json = block_on(async move {
-            let mut updates = overrides.value_actor().stream_from_now();
-            send_edit_committed(construct_context, sender, 1.0, 1.0, "7").await;
-            updates
-                .next()
-                .await
-                .expect("overrides should emit updated list")
-                .to_json()
-                .await
-        });
+maximum: 5
+count: 0 |> HOLD state {
+    LATEST {
+        decrement |> THEN { state - 1 |> Math/clamp(min: 0, max: maximum) }
+        increment |> THEN { state + 1 |> Math/clamp(min: 0, max: maximum) }
+    }
+}
+at_floor: count == 0
+at_ceiling: count == maximum
+"#
+    }
 
-        assert_eq!(
-            updated_json,
-            json!([{
-                "row": 1.0,
-                "column": 1.0,
-                "text": "7"
-            }])
-        );
+    fn bool_toggle_source() -> &'static str {
+        r#"
+toggle_a: LINK
+toggle_b: LINK
+toggle_all: LINK
+
+FUNCTION make_item(toggle, checked) {
+    [
+        checked: Bool/toggle(
+            on: toggle
+            initial: LATEST {
+                checked
+                toggle_all |> THEN { True }
+            }
+        )
+    ]
+}
+
+item_a: make_item(toggle: toggle_a, checked: False)
+item_b: make_item(toggle: toggle_b, checked: True)
+a_checked: item_a.checked
+b_checked: item_b.checked
+unstarted: Bool/toggle(on: toggle_a)
+piped: False |> Bool/toggle(when: toggle_b)
+reordered: Bool/toggle(initial: True, on: toggle_a)
+held: False |> HOLD state { toggle_a |> THEN { state |> Bool/not() } }
+toggled: Bool/toggle(on: toggle_a, initial: False)
+"#
     }
 
-    #[test]
-    fn cells_override_hold_upsert_tracks_commit_and_default_removal() {
-        let (root_object, construct_context, _scope_guard) =
-            evaluate_program(cells_override_upsert_source());
-        let edit_committed = root_object.expect_variable("edit_committed");
-        let overrides = root_object.expect_variable("overrides");
-        let sender = edit_committed.expect_link_value_sender();
+    fn math_division_negative_source() -> &'static str {
+        r#"
+negative_remainder: -7 |> Math/modulo(by: 3)
+negative_quotient: -7 |> Math/divide_int(by: 3)
+remainder_by_negative: 7 |> Math/modulo(by: -3)
+quotient_by_negative: 7 |> Math/divide_int(by: -3)
+both_negative: -7 |> Math/modulo(by: -3)
+exact_negative: -6 |> Math/modulo(by: 3)
+"#
+    }
 
-        let (committed_json, reverted_json) = block_on(async move {
-            let mut updates = overrides.value_actor().stream_from_now();
+    fn text_replace_amplification_source() -> &'static str {
+        r#"
+ten: TEXT { aaaaaaaaaa }
+grown: TEXT { a }
+    |> Text/replace(from: TEXT { a }, to: ten)
+    |> Text/replace(from: TEXT { a }, to: ten)
+    |> Text/replace(from: TEXT { a }, to: ten)
+    |> Text/replace(from: TEXT { a }, to: ten)
+"#
+    }
 
-            send_edit_committed(construct_context.clone(), sender.clone(), 1.0, 1.0, "7").await;
-            let committed_json = updates
+    /// The first value the top-level variable `name` emits, as JSON.
+    fn first_json(root_object: &Object, name: &str) -> serde_json::Value {
+        let variable = root_object.expect_variable(name);
+        block_on(async move {
+            let value = variable
+                .value_actor()
+                .stream()
                 .next()
                 .await
-                .expect("overrides should emit committed override")
-                .to_json()
-                .await;
-
-            send_edit_committed(construct_context, sender, 1.0, 1.0, "5").await;
-            let reverted_json = updates
-                .next()
-                .await
-                .expect("overrides should emit removal when reverting to default")
-                .to_json()
-                .await;
-
-            (committed_json, reverted_json)
-        });
+                .unwrap_or_else(|| panic!("{name} should emit"));
+            value.to_json().await
+        })
+    }
 
-        assert_eq!(
-            committed_json,
-            json!([{
-                "row": 1.0,
-                "column": 1.0,
-                "text": "7"
-            }])
-        );
-        assert_eq!(reverted_json, serde_json::Value::Array(Vec::new()));
+    /// Polls `name` until its current value is `expected` as JSON, or gives up and
+    /// returns the last value seen.
+    fn settled_json(
+        root_object: &Object,
+        name: &str,
+        expected: serde_json::Value,
+    ) -> serde_json::Value {
+        let variable = root_object.expect_variable(name);
+        block_on(async move {
+            let mut current = serde_json::Value::Null;
+            for _ in 0..50 {
+                if let Ok(value) = variable.value_actor().current_value().await {
+                    current = value.to_json().await;
+                    if current == expected {
+                        break;
+                    }
+                }
+                std::thread::sleep(Duration::from_millis(10));
+            }
+            current
+        })
     }
 
-    #[test]
-    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
-    fn repeated_equal_scalar_conditions_from_distinct_emissions_are_not_deduped() {
-        let (root_object, construct_context, _scope_guard) =
-            evaluate_program(repeated_scalar_condition_source());
-        let trigger = root_object.expect_variable("trigger");
-        let count = root_object.expect_variable("count");
-        let sender = trigger.expect_link_value_sender();
+    fn nested_cells_map_source(with_link: bool) -> String {
+        let mapped_element = if with_link {
+            "Element/label(element: [], style: [width: 80], label: TEXT { x }) |> LINK { cell.cell_elements.display }"
+        } else {
+            "Element/label(element: [], style: [width: 80], label: TEXT { x })"
+        };
 
-        let (first_json, second_json) = block_on(async move {
-            let mut updates = count.value_actor().stream_from_now();
+        format!(
+            r#"
+FUNCTION make_cell(column, row) {{
+    [column: column row: row cell_elements: [display: LINK, editing: LINK]]
+}}
 
-            send_link_signal(
-                construct_context.clone(),
-                sender.clone(),
-                json!({
-                    "key": "Enter",
-                    "text": ""
-                }),
-                "test.trigger.first",
-            )
-            .await;
+FUNCTION make_row_cells(row) {{ LIST {{
+    make_cell(column: 1, row: row)
+    make_cell(column: 2, row: row)
+}} }}
 
-            let first_json = updates
-                .next()
-                .await
-                .expect("count should emit after first Enter")
-                .to_json()
-                .await;
+FUNCTION make_cell_element(cell) {{
+    {mapped_element}
+}}
 
-            send_link_signal(
-                construct_context,
-                sender,
-                json!({
-                    "key": "Enter",
-                    "text": ""
-                }),
-                "test.trigger.second",
-            )
-            .await;
+cells: make_row_cells(row: 1)
+elements: cells |> List/map(cell, new: make_cell_element(cell: cell))
+"#
+        )
+    }
 
-            let second_json = updates
-                .next()
-                .await
-                .expect("count should emit after second Enter")
-                .to_json()
-                .await;
+    fn nested_cells_map_helper_when_while_source() -> String {
+        r#"
+FUNCTION make_cell(column, row) {
+    [column: column row: row]
+}
 
-            (first_json, second_json)
-        });
+FUNCTION make_row_cells(row) { LIST {
+    make_cell(column: 1, row: row)
+    make_cell(column: 2, row: row)
+} }
 
-        assert_eq!(first_json, json!(1.0));
-        assert_eq!(second_json, json!(2.0));
+FUNCTION is_editing_cell(cell) {
+    editing_cell.row == cell.row |> WHEN {
+        True => editing_cell.column == cell.column
+        __ => False
     }
+}
 
-    #[test]
-    fn nested_cells_map_without_link_produces_label_elements() {
-        let source = nested_cells_map_source(false);
-        let (root_object, _construct_context, _scope_guard) = evaluate_program(&source);
-        let elements = root_object.expect_variable("elements");
-
-        block_on(async move {
-            let value = elements
-                .value_actor()
-                .current_value()
-                .await
-                .expect("elements list should have a current value");
-            let Value::List(list, _) = value else {
-                panic!("elements should evaluate to a list");
-            };
-            let snapshot = list.snapshot().await;
-            assert_eq!(snapshot.len(), 2, "expected two mapped cell elements");
+FUNCTION make_cell_element(cell) {
+    is_editing_cell(cell: cell) |> WHILE {
+        True => Element/label(element: [], style: [width: 80], label: TEXT { y })
+        False => Element/label(element: [], style: [width: 80], label: TEXT { x })
+    }
+}
 
-            for (_pid, item_actor) in snapshot {
-                let item_value = item_actor
-                    .current_value()
-                    .await
-                    .expect("mapped cell item should have value");
-                let Value::TaggedObject(tagged, _) = item_value else {
-                    panic!("mapped cell item should be a tagged element object");
-                };
-                assert_eq!(tagged.tag(), "ElementLabel");
-            }
-        });
+cells: make_row_cells(row: 1)
+editing_cell: [row: 0, column: 0]
+elements: cells |> List/map(cell, new: make_cell_element(cell: cell))
+"#
+        .to_string()
     }
 
-    #[test]
-    fn nested_cells_map_with_link_produces_label_elements() {
-        let source = nested_cells_map_source(true);
-        let (root_object, _construct_context, _scope_guard) = evaluate_program(&source);
-        let elements = root_object.expect_variable("elements");
+    fn nested_cells_map_helper_when_while_text_input_source() -> String {
+        r#"
+FUNCTION make_cell(column, row) {
+    [column: column row: row cell_elements: [display: LINK, editing: LINK]]
+}
 
-        block_on(async move {
-            let value = elements
-                .value_actor()
-                .current_value()
-                .await
-                .expect("elements list should have a current value");
-            let Value::List(list, _) = value else {
-                panic!("elements should evaluate to a list");
-            };
-            let snapshot = list.snapshot().await;
-            assert_eq!(
-                snapshot.len(),
-                2,
-                "expected two mapped linked cell elements"
-            );
+FUNCTION make_row_cells(row) { LIST {
+    make_cell(column: 1, row: row)
+    make_cell(column: 2, row: row)
+} }
 
-            for (_pid, item_actor) in snapshot {
-                let item_value = item_actor
-                    .current_value()
-                    .await
-                    .expect("mapped linked cell item should have value");
-                let Value::TaggedObject(tagged, _) = item_value else {
-                    panic!("mapped linked cell item should be a tagged element object");
-                };
-                assert_eq!(tagged.tag(), "ElementLabel");
-            }
-        });
+FUNCTION is_editing_cell(cell) {
+    editing_cell.row == cell.row |> WHEN {
+        True => editing_cell.column == cell.column
+        __ => False
     }
+}
 
-    #[test]
-    fn nested_cells_map_helper_when_while_produces_label_elements() {
-        let source = nested_cells_map_helper_when_while_source();
-        let (root_object, _construct_context, _scope_guard) = evaluate_program(&source);
-        let elements = root_object.expect_variable("elements");
-
-        block_on(async move {
-            let value = elements
-                .value_actor()
-                .current_value()
-                .await
-                .expect("elements list should have a current value");
-            let Value::List(list, _) = value else {
-                panic!("elements should evaluate to a list");
-            };
-            let snapshot = list.snapshot().await;
-            assert_eq!(
-                snapshot.len(),
-                2,
-                "expected two mapped helper-fed cell elements"
-            );
+FUNCTION make_cell_element(cell) {
+    is_editing_cell(cell: cell) |> WHILE {
+        True => Element/text_input(element: [event: [input: LINK, key_down: LINK, blur: LINK]] style: [width: 80] label: Hidden[text: TEXT { cell }] text: TEXT {  } placeholder: [text: Text/empty()] focus: True) |> LINK { cell.cell_elements.editing }
+        False => Element/label(element: [], style: [width: 80], label: TEXT { x }) |> LINK { cell.cell_elements.display }
+    }
+}
 
-            for (_pid, item_actor) in snapshot {
-                let item_value = item_actor
-                    .current_value()
-                    .await
-                    .expect("mapped helper-fed cell item should have value");
-                let Value::TaggedObject(tagged, _) = item_value else {
-                    panic!("mapped helper-fed cell item should be a tagged element object");
-                };
-                assert_eq!(tagged.tag(), "ElementLabel");
-            }
-        });
+cells: make_row_cells(row: 1)
+editing_cell: [row: 0, column: 0]
+elements: cells |> List/map(cell, new: make_cell_element(cell: cell))
+"#
+        .to_string()
     }
 
-    #[test]
-    fn nested_cells_map_helper_when_while_with_text_input_inactive_branch_produces_label_elements()
-    {
-        let source = nested_cells_map_helper_when_while_text_input_source();
-        let (root_object, _construct_context, _scope_guard) = evaluate_program(&source);
-        let elements = root_object.expect_variable("elements");
+    fn nested_cells_map_helper_when_while_nested_link_source() -> String {
+        r#"
+FUNCTION make_cell(column, row) {
+    [column: column row: row cell_elements: [display: LINK, editing: LINK]]
+}
 
-        block_on(async move {
-            let value = elements
-                .value_actor()
-                .current_value()
-                .await
-                .expect("elements list should have a current value");
-            let Value::List(list, _) = value else {
-                panic!("elements should evaluate to a list");
-            };
-            let snapshot = list.snapshot().await;
-            assert_eq!(
-                snapshot.len(),
-                2,
-                "expected two mapped helper-fed cell elements with inactive text input branch"
-            );
+FUNCTION make_row_cells(row) { LIST {
+    make_cell(column: 1, row: row)
+    make_cell(column: 2, row: row)
+} }
 
-            for (_pid, item_actor) in snapshot {
-                let item_value = item_actor
-                    .current_value()
-                    .await
-                    .expect("mapped helper-fed item should have value");
-                let Value::TaggedObject(tagged, _) = item_value else {
-                    panic!("mapped helper-fed item should be a tagged element object");
-                };
-                assert_eq!(tagged.tag(), "ElementLabel");
-            }
-        });
+FUNCTION is_editing_cell(cell) {
+    editing_cell.row == cell.row |> WHEN {
+        True => editing_cell.column == cell.column
+        __ => False
     }
+}
 
-    #[test]
-    fn nested_cells_map_helper_when_while_with_nested_link_produces_label_elements() {
-        let source = nested_cells_map_helper_when_while_nested_link_source();
-        let (root_object, _construct_context, _scope_guard) = evaluate_program(&source);
-        let elements = root_object.expect_variable("elements");
-
-        block_on(async move {
-            let value = elements
-                .value_actor()
-                .current_value()
-                .await
-                .expect("elements list should have a current value");
-            let Value::List(list, _) = value else {
-                panic!("elements should evaluate to a list");
-            };
-            let snapshot = list.snapshot().await;
-            assert_eq!(
-                snapshot.len(),
-                2,
-                "expected two mapped helper-fed cell elements with nested link target"
-            );
-
-            for (_pid, item_actor) in snapshot {
-                let item_value = item_actor
-                    .current_value()
-                    .await
-                    .expect("mapped helper-fed nested-link item should have value");
-                let Value::TaggedObject(tagged, _) = item_value else {
-                    panic!("mapped helper-fed nested-link item should be a tagged element object");
-                };
-                assert_eq!(tagged.tag(), "ElementLabel");
-            }
-        });
+FUNCTION make_cell_element(cell) {
+    is_editing_cell(cell: cell) |> WHILE {
+        True => Element/label(element: [], style: [width: 80], label: TEXT { y })
+        False => Element/label(element: [], style: [width: 80], label: TEXT { x }) |> LINK { cell.cell_elements.display }
     }
+}
 
-    #[test]
-    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
-    fn nested_cells_map_helper_when_while_nested_link_switches_branch_on_toggle() {
-        let source = nested_cells_map_helper_when_while_nested_link_toggle_source();
-        let (root_object, construct_context, _scope_guard) = evaluate_program(&source);
-        let elements = root_object.expect_variable("elements");
-        let toggle = root_object.expect_variable("toggle");
-        let sender = toggle.expect_link_value_sender();
+cells: make_row_cells(row: 1)
+editing_cell: [row: 0, column: 0]
+elements: cells |> List/map(cell, new: make_cell_element(cell: cell))
+"#
+        .to_string()
+    }
 
-        block_on(async move {
-            let snapshot_to_json = |value: Value| async move {
-                let Value::List(list, _) = value else {
-                    panic!("elements should evaluate to a list");
-                };
-                let snapshot = list.snapshot().await;
-                let (_pid, first_item_actor) = snapshot
-                    .into_iter()
-                    .next()
-                    .expect("elements should contain first item");
-                first_item_actor
-                    .current_value()
-                    .await
-                    .expect("first mapped item should have current value")
-                    .to_json()
-                    .await
-            };
+    fn nested_cells_map_helper_when_while_nested_link_toggle_source() -> String {
+        r#"
+FUNCTION make_cell(column, row) {
+    [column: column row: row cell_elements: [display: LINK, editing: LINK]]
+}
 
-            let initial_value = elements
-                .value_actor()
-                .current_value()
-                .await
-                .expect("elements should have initial value");
-            let initial_json = snapshot_to_json(initial_value).await;
+FUNCTION make_row_cells(row) { LIST {
+    make_cell(column: 1, row: row)
+    make_cell(column: 2, row: row)
+} }
 
-            send_link_signal(
-                construct_context,
-                sender,
-                serde_json::json!(true),
-                "test.toggle.nested_link_switch",
-            )
-            .await;
+FUNCTION is_editing_cell(cell) {
+    editing_cell.row == cell.row |> WHEN {
+        True => editing_cell.column == cell.column
+        __ => False
+    }
+}
 
-            let updated_value = elements
-                .value_actor()
-                .stream_from_now()
-                .next()
-                .await
-                .expect("elements should emit updated value after toggle");
-            let updated_json = snapshot_to_json(updated_value).await;
+FUNCTION make_cell_element(cell) {
+    is_editing_cell(cell: cell) |> WHILE {
+        True => Element/label(element: [], style: [width: 80], label: TEXT { y }) |> LINK { cell.cell_elements.editing }
+        False => Element/label(element: [], style: [width: 80], label: TEXT { x }) |> LINK { cell.cell_elements.display }
+    }
+}
 
-            let initial_text = initial_json.to_string();
-            let updated_text = updated_json.to_string();
-            assert!(
-                initial_text.contains("\"y\""),
-                "expected initial first item to be editing branch label, got {}",
-                initial_text
-            );
-            assert!(
-                updated_text.contains("\"x\""),
-                "expected updated first item to switch to display branch label, got {}",
-                updated_text
-            );
-        });
+toggle: LINK
+cells: make_row_cells(row: 1)
+editing_cell: [row: 1, column: 1] |> HOLD state {
+    toggle |> THEN { [row: 0, column: 0] }
+}
+elements: cells |> List/map(cell, new: make_cell_element(cell: cell))
+"#
+        .to_string()
     }
 
-    #[test]
-    #[ignore = "requires browser-side actors runtime; host lib tests still touch js-sys statics"]
-    fn mapped_cell_can_reopen_after_escape_via_nested_link_events() {
-        let source = nested_cells_reopen_editing_source();
-        let (root_object, construct_context, _scope_guard) = evaluate_program(&source);
-        let elements = root_object.expect_variable("elements");
-        let cells = root_object.expect_variable("cells");
+    fn nested_cells_reopen_editing_source() -> String {
+        r#"
+FUNCTION make_cell(column, row) {
+    [column: column row: row cell_elements: [display: LINK, editing: LINK]]
+}
 
-        block_on(async move {
-            async fn first_mapped_item(elements: &Variable) -> Value {
-                let elements_value = elements
-                    .value_actor()
-                    .current_value()
-                    .await
-                    .expect("elements should have a current value");
-                let Value::List(list, _) = elements_value else {
-                    panic!("elements should evaluate to a list");
-                };
-                let snapshot = list.snapshot().await;
-                let (_pid, first_item_actor) = snapshot
-                    .into_iter()
-                    .next()
-                    .expect("elements should contain first mapped item");
-                first_item_actor
-                    .current_value()
-                    .await
-                    .expect("first mapped item should have current value")
-            }
+FUNCTION make_row_cells(row) { LIST {
+    make_cell(column: 1, row: row)
+} }
 
-            async fn first_cell(cells: &Variable) -> Arc<Object> {
-                let cells_value = cells
-                    .value_actor()
-                    .current_value()
-                    .await
-                    .expect("cells should have a current value");
-                let Value::List(list, _) = cells_value else {
-                    panic!("cells should evaluate to a list");
-                };
-                let snapshot = list.snapshot().await;
-                let (_pid, first_cell_actor) = snapshot
-                    .into_iter()
-                    .next()
-                    .expect("cells should contain first cell");
-                let first_cell_value = first_cell_actor
-                    .current_value()
-                    .await
-                    .expect("first cell should have current value");
-                let Value::Object(cell_obj, _) = first_cell_value else {
-                    panic!("first cell should be an object");
-                };
-                cell_obj
-            }
+FUNCTION is_editing_cell(cell) {
+    editing_active |> WHEN {
+        True => editing_row == cell.row |> WHEN {
+            True => editing_column == cell.column
+            __ => False
+        }
+        __ => False
+    }
+}
 
-            async fn nested_link_sender(
-                object: Arc<Object>,
-                value_field: &str,
-                event_field: &str,
-            ) -> crate::engine::NamedChannel<Value> {
-                let cell_elements = object
-                    .variable("cell_elements")
-                    .expect("cell should have cell_elements");
-                let cell_elements_value = cell_elements
-                    .value_actor()
-                    .current_value()
-                    .await
-                    .expect("cell_elements should have current value");
-                let Value::Object(cell_elements_obj, _) = cell_elements_value else {
-                    panic!("cell_elements should be an object");
-                };
+FUNCTION make_cell_element(cell) {
+    BLOCK {
+        display_element: [event: [double_click: LINK]]
+        editing_element: [event: [key_down: LINK, blur: LINK]]
 
-                let linked_element = cell_elements_obj
-                    .variable(value_field)
-                    .unwrap_or_else(|| panic!("cell_elements should contain {}", value_field));
-                let linked_element_value = linked_element
-                    .value_actor()
-                    .current_value()
-                    .await
-                    .unwrap_or_else(|_| panic!("{value_field} should have current value"));
-                let Value::TaggedObject(linked_tagged, _) = linked_element_value else {
+        edit_started_row_link:
+            display_element.event.double_click |> THEN { cell.row }
+            |> LINK { event_ports.edit_started_row }
+        edit_started_column_link:
+            display_element.event.double_click |> THEN { cell.column }
+            |> LINK { event_ports.edit_started_column }
+        edit_started_active_link:
+            display_element.event.double_click |> THEN { True }
+            |> LINK { event_ports.edit_active_event }
+
+        edit_cancelled_link:
+            editing_element.event.key_down.key
+            |> WHEN { Escape => False __ => SKIP }
+            |> LINK { event_ports.edit_active_event }
+        edit_blurred_link:
+            editing_element.event.blur
+            |> THEN { False }
+            |> LINK { event_ports.edit_active_event }
+
+        is_editing_cell(cell: cell) |> WHILE {
+            True => Element/text_input(
+                element: editing_element
+                style: [width: 80]
+                label: Hidden[text: TEXT { cell }]
+                text: Text/empty()
+                placeholder: [text: Text/empty()]
+                focus: True
+            )
+            |> LINK { cell.cell_elements.editing }
+
+            False => Element/label(
+                element: display_element
+                style: [width: 80]
+                label: TEXT { x }
+            )
+            |> LINK { cell.cell_elements.display }
+        }
+    }
+}
+
+event_ports: [
+    edit_started_row: LINK
+    edit_started_column: LINK
+    edit_active_event: LINK
+]
+
+editing_row: 0 |> HOLD state { event_ports.edit_started_row }
+editing_column: 0 |> HOLD state { event_ports.edit_started_column }
+editing_active: False |> HOLD state { event_ports.edit_active_event }
+
+cells: make_row_cells(row: 1)
+elements: cells |> List/map(cell, new: make_cell_element(cell: cell))
+"#
+        .to_string()
+    }
+
+    fn mapped_todo_item_event_link_source() -> &'static str {
+        r#"
+FUNCTION new_todo(title) {
+    [
+        title: title
+        todo_elements: [
+            todo_title_element: LINK
+            todo_checkbox: LINK
+        ]
+    ]
+}
+
+FUNCTION todo_checkbox(todo) {
+    Element/checkbox(
+        element: [event: [click: LINK]]
+        style: []
+        label: Hidden[text: TEXT { Toggle }]
+        checked: False
+        icon: Element/container(element: [], style: [], child: NoElement)
+    )
+    |> LINK { todo.todo_elements.todo_checkbox }
+}
+
+FUNCTION todo_item(todo) {
+    Element/stripe(
+        element: []
+        direction: Row
+        gap: 0
+        style: []
+        items: LIST {
+            todo_checkbox(todo: todo)
+            Element/label(
+                element: [event: [double_click: LINK]]
+                style: []
+                label: todo.title
+            )
+            |> LINK { todo.todo_elements.todo_title_element }
+        }
+    )
+}
+
+todos: LIST {
+    new_todo(title: TEXT { Buy groceries })
+}
+
+elements: todos |> List/map(todo, new: todo_item(todo: todo))
+"#
+    }
+
+    fn linked_checkbox_updates_sibling_hold_source() -> &'static str {
+        r#"
+FUNCTION todo_checkbox(todo) {
+    Element/checkbox(
+        element: [event: [click: LINK]]
+        style: []
+        label: Hidden[text: TEXT { Toggle }]
+        checked: False
+        icon: Element/container(element: [], style: [], child: NoElement)
+    )
+    |> LINK { todo.todo_elements.todo_checkbox }
+}
+
+FUNCTION new_todo() {
+    [
+        todo_elements: [
+            todo_checkbox: LINK
+        ]
+
+        completed: False |> HOLD state {
+            todo_elements.todo_checkbox.event.click |> THEN { state |> Bool/not() }
+        }
+    ]
+}
+
+todo: new_todo()
+checkbox: todo_checkbox(todo: todo)
+"#
+    }
+
+    fn read_example_source(path: &str) -> String {
+        std::fs::read_to_string(path).unwrap_or_else(|error| {
+            panic!("failed to read example source {path}: {error}");
+        })
+    }
+
+    fn list_map_external_dependency_source() -> &'static str {
+        r#"
+toggle: LINK
+
+show_shifted: False |> HOLD state {
+    toggle |> THEN { state |> Bool/not() }
+}
+
+items: LIST { 1 2 3 }
+
+mapped: items |> List/map(item, new:
+    show_shifted |> WHEN {
+        True => item + 10
+        __ => item
+    }
+)
+"#
+    }
+
+    fn cells_mapped_formula_dependency_source() -> &'static str {
+        r#"
+FUNCTION matching_overrides(column, row) {
+    overrides |> List/retain(item, if:
+        item.row == row |> WHEN {
+            True => item.column == column
+            __ => False
+        }
+    )
+}
+
+FUNCTION cell_formula(column, row) {
+    BLOCK {
+        matches: matching_overrides(column: column, row: row)
+        match_count: matches |> List/count()
+        match_count == 0 |> WHEN {
+            True => default_formula(column: column, row: row)
+            __ => BLOCK {
+                latest_match: matches |> List/get(index: match_count)
+                latest_match.text
+            }
+        }
+    }
+}
+
+FUNCTION default_formula(column, row) {
+    column == 1 |> WHEN {
+        True => row == 1 |> WHEN {
+            True => TEXT { 5 }
+            __ => TEXT { 10 }
+        }
+        __ => TEXT { =add(A1, A2) }
+    }
+}
+
+FUNCTION compute_value(formula_text) {
+    formula_text |> Text/starts_with(prefix: TEXT { = }) |> WHILE {
+        False => formula_text |> Text/to_number() |> WHILE { NaN => 0 number => number }
+        __ => compute_value(formula_text: cell_formula(column: 1, row: 1))
+            + compute_value(formula_text: cell_formula(column: 1, row: 2))
+    }
+}
+
+FUNCTION make_cell(column, row) { [column: column row: row] }
+
+FUNCTION make_cell_text(cell) {
+    BLOCK {
+        formula_text: cell_formula(column: cell.column, row: cell.row)
+        display_value: compute_value(formula_text: formula_text)
+        TEXT { {display_value} }
+    }
+}
+
+edit_committed: LINK
+
+overrides: LIST { } |> HOLD state {
+    edit_committed |> THEN {
+        state
+        |> List/retain(item, if:
+            item.row == edit_committed.row |> WHEN {
+                True => item.column == edit_committed.column |> WHEN {
+                    True => False
+                    __ => True
+                }
+                __ => True
+            }
+        )
+        |> List/append(item: [
+            row: edit_committed.row
+            column: edit_committed.column
+            text: edit_committed.text
+        ])
+    }
+}
+
+formula_a1: cell_formula(column: 1, row: 1)
+cells: LIST { make_cell(column: 1, row: 1) make_cell(column: 2, row: 1) }
+mapped: cells |> List/map(cell, new: make_cell_text(cell: cell))
+"#
+    }
+
+    fn shared_snapshot_root_toggle_source() -> &'static str {
+        r#"
+toggle_all: LINK
+
+FUNCTION make_todo(initial_completed) {
+    [
+        completed: initial_completed |> HOLD state {
+            toggle_all |> THEN { all_completed |> Bool/not() }
+        }
+    ]
+}
+
+todos: LIST {
+    make_todo(initial_completed: False)
+    make_todo(initial_completed: False)
+    make_todo(initial_completed: False)
+    make_todo(initial_completed: True)
+}
+
+completed_todos_count: todos |> List/retain(item, if: item.completed) |> List/count()
+all_completed: completed_todos_count == 4
+"#
+    }
+
+    #[test]
+    fn latest_press_sequence_preserves_two_then_arms_for_evaluator() {
+        let (_source_code, variables) = parse_static_variables(latest_conformance_source());
+        let selected = variables
+            .get("selected")
+            .cloned()
+            .expect("selected variable should exist");
+
+        let static_expression::Expression::Latest { inputs } = selected.node else {
+            panic!("expected selected to remain a LATEST expression");
+        };
+
+        assert_eq!(inputs.len(), 2, "expected two LATEST inputs");
+
+        let arm_texts: Vec<String> = inputs
+            .iter()
+            .map(|input| {
+                let chain = flatten_pipe_chain(input.clone());
+                assert_eq!(
+                    chain.len(),
+                    2,
+                    "expected each LATEST arm to stay a two-step pipe for Actors evaluation"
+                );
+
+                let then_expr = chain.last().expect("pipe should have THEN step");
+                let static_expression::Expression::Then { body } = &then_expr.node else {
+                    panic!("expected LATEST arm to end in THEN");
+                };
+                match &body.node {
+                    static_expression::Expression::TextLiteral { parts, .. }
+                        if parts.len() == 1 =>
+                    {
+                        match &parts[0] {
+                            static_expression::TextPart::Text(text) => text.to_string(),
+                            other => panic!("expected text literal part, got {other:?}"),
+                        }
+                    }
+                    other => {
+                        panic!("expected THEN body to be single-part TEXT literal, got {other:?}")
+                    }
+                }
+            })
+            .collect();
+
+        assert_eq!(arm_texts, vec!["left".to_string(), "right".to_string()]);
+
+        let expected = select_latest(&[
+            LatestCandidate::new(KernelValue::from("left"), TickSeq::new(TickId(1), 1)),
+            LatestCandidate::new(KernelValue::from("right"), TickSeq::new(TickId(1), 2)),
+        ]);
+        assert_eq!(expected, KernelValue::from("right"));
+    }
+
+    #[test]
+    fn hold_press_sequence_preserves_initial_state_and_then_body_shape() {
+        let (_source_code, variables) = parse_static_variables(hold_conformance_source());
+        let counter = variables
+            .get("counter")
+            .cloned()
+            .expect("counter variable should exist");
+
+        let chain = flatten_pipe_chain(counter);
+        assert_eq!(
+            chain.len(),
+            2,
+            "expected HOLD expression to remain a two-step pipe in Actors evaluator input"
+        );
+        assert!(
+            matches!(chain[0].node, static_expression::Expression::Literal(static_expression::Literal::Number(n)) if (n - 0.0).abs() < f64::EPSILON),
+            "expected HOLD initial value to stay as literal 0"
+        );
+
+        let static_expression::Expression::Hold { state_param, body } = &chain[1].node else {
+            panic!("expected second pipe step to be HOLD");
+        };
+        assert_eq!(state_param.to_string(), "state");
+
+        let body_chain = flatten_pipe_chain((**body).clone());
+        assert_eq!(
+            body_chain.len(),
+            2,
+            "expected HOLD body to remain an event |> THEN pipeline"
+        );
+        let static_expression::Expression::Then { body: then_body } = &body_chain[1].node else {
+            panic!("expected HOLD body to end in THEN");
+        };
+        match &then_body.node {
+            static_expression::Expression::ArithmeticOperator(
+                static_expression::ArithmeticOperator::Add {
+                    operand_a,
+                    operand_b,
+                },
+            ) => {
+                assert!(
+                    matches!(
+                        operand_a.node,
+                        static_expression::Expression::Alias(
+                            static_expression::Alias::WithoutPassed { ref parts, .. }
+                        ) if parts.len() == 1 && parts[0].to_string() == "state"
+                    ),
+                    "expected HOLD body lhs to reference state"
+                );
+                assert!(
+                    matches!(
+                        operand_b.node,
+                        static_expression::Expression::Literal(
+                            static_expression::Literal::Number(n)
+                        ) if (n - 1.0).abs() < f64::EPSILON
+                    ),
+                    "expected HOLD body rhs to stay literal 1"
+                );
+            }
+            other => panic!("expected HOLD THEN body to be `state + 1`, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn link_press_sequence_preserves_alias_path_into_then() {
+        let (_source_code, variables) = parse_static_variables(link_conformance_source());
+
+        let increment_button = variables
+            .get("increment_button")
+            .cloned()
+            .expect("increment_button variable should exist");
+        assert!(
+            matches!(increment_button.node, static_expression::Expression::Link),
+            "expected increment_button to stay a LINK placeholder"
+        );
+
+        let pressed = variables
+            .get("pressed")
+            .cloned()
+            .expect("pressed variable should exist");
+        let chain = flatten_pipe_chain(pressed);
+        assert_eq!(
+            chain.len(),
+            2,
+            "expected LINK event consumer to remain a two-step pipe"
+        );
+        assert!(
+            matches!(
+                &chain[0].node,
+                static_expression::Expression::Alias(
+                    static_expression::Alias::WithoutPassed { parts, .. }
+                ) if parts.iter().map(|part| part.to_string()).collect::<Vec<_>>()
+                    == vec!["increment_button".to_string(), "event".to_string(), "press".to_string()]
+            ),
+            "expected first step to keep increment_button.event.press alias path"
+        );
+        let static_expression::Expression::Then { body } = &chain[1].node else {
+            panic!("expected LINK event consumer to end in THEN");
+        };
+        assert!(
+            matches!(
+                body.node,
+                static_expression::Expression::TextLiteral { ref parts, .. }
+                    if parts.len() == 1
+                        && matches!(&parts[0], static_expression::TextPart::Text(text) if text.to_string() == "pressed")
+            ),
+            "expected THEN body to stay TEXT {{ pressed }}"
+        );
+    }
+
+    #[test]
+    fn list_append_from_link_event_snapshots_object_fields() {
+        let (root_object, construct_context, _scope_guard) =
+            evaluate_program(append_from_link_source());
+        let edit_committed = root_object.expect_variable("edit_committed");
+        let overrides = root_object.expect_variable("overrides");
+        let sender = edit_committed.expect_link_value_sender();
+
+        let updated_json = block_on(async move {
+            let mut updates = overrides.value_actor().stream_from_now();
+            send_edit_committed(construct_context, sender, 1.0, 1.0, "7").await;
+            updates
+                .next()
+                .await
+                .expect("overrides should emit updated list")
+                .to_json()
+                .await
+        });
+
+        assert_eq!(
+            updated_json,
+            json!([{
+                "row": 1.0,
+                "column": 1.0,
+                "text": "7"
+            }])
+        );
+    }
+
+    #[test]
+    fn cells_override_hold_upsert_tracks_commit_and_default_removal() {
+        let (root_object, construct_context, _scope_guard) =
+            evaluate_program(cells_override_upsert_source());
+        let edit_committed = root_object.expect_variable("edit_committed");
+        let overrides = root_object.expect_variable("overrides");
+        let sender = edit_committed.expect_link_value_sender();
+
+        let (committed_json, reverted_json) = block_on(async move {
+            let mut updates = overrides.value_actor().stream_from_now();
+
+            send_edit_committed(construct_context.clone(), sender.clone(), 1.0, 1.0, "7").await;
+            let committed_json = updates
+                .next()
+                .await
+                .expect("overrides should emit committed override")
+                .to_json()
+                .await;
+
+            send_edit_committed(construct_context, sender, 1.0, 1.0, "5").await;
+            let reverted_json = updates
+                .next()
+                .await
+                .expect("overrides should emit removal when reverting to default")
+                .to_json()
+                .await;
+
+            (committed_json, reverted_json)
+        });
+
+        assert_eq!(
+            committed_json,
+            json!([{
+                "row": 1.0,
+                "column": 1.0,
+                "text": "7"
+            }])
+        );
+        assert_eq!(reverted_json, serde_json::Value::Array(Vec::new()));
+    }
+
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn repeated_equal_scalar_conditions_from_distinct_emissions_are_not_deduped() {
+        let (root_object, construct_context, _scope_guard) =
+            evaluate_program(repeated_scalar_condition_source());
+        let trigger = root_object.expect_variable("trigger");
+        let count = root_object.expect_variable("count");
+        let sender = trigger.expect_link_value_sender();
+
+        let (first_json, second_json) = block_on(async move {
+            let mut updates = count.value_actor().stream_from_now();
+
+            send_link_signal(
+                construct_context.clone(),
+                sender.clone(),
+                json!({
+                    "key": "Enter",
+                    "text": ""
+                }),
+                "test.trigger.first",
+            )
+            .await;
+
+            let first_json = updates
+                .next()
+                .await
+                .expect("count should emit after first Enter")
+                .to_json()
+                .await;
+
+            send_link_signal(
+                construct_context,
+                sender,
+                json!({
+                    "key": "Enter",
+                    "text": ""
+                }),
+                "test.trigger.second",
+            )
+            .await;
+
+            let second_json = updates
+                .next()
+                .await
+                .expect("count should emit after second Enter")
+                .to_json()
+                .await;
+
+            (first_json, second_json)
+        });
+
+        assert_eq!(first_json, json!(1.0));
+        assert_eq!(second_json, json!(2.0));
+    }
+
+    #[test]
+    fn nested_cells_map_without_link_produces_label_elements() {
+        let source = nested_cells_map_source(false);
+        let (root_object, _construct_context, _scope_guard) = evaluate_program(&source);
+        let elements = root_object.expect_variable("elements");
+
+        block_on(async move {
+            let value = elements
+                .value_actor()
+                .current_value()
+                .await
+                .expect("elements list should have a current value");
+            let Value::List(list, _) = value else {
+                panic!("elements should evaluate to a list");
+            };
+            let snapshot = list.snapshot().await;
+            assert_eq!(snapshot.len(), 2, "expected two mapped cell elements");
+
+            for (_pid, item_actor) in snapshot {
+                let item_value = item_actor
+                    .current_value()
+                    .await
+                    .expect("mapped cell item should have value");
+                let Value::TaggedObject(tagged, _) = item_value else {
+                    panic!("mapped cell item should be a tagged element object");
+                };
+                assert_eq!(tagged.tag(), "ElementLabel");
+            }
+        });
+    }
+
+    #[test]
+    fn nested_cells_map_with_link_produces_label_elements() {
+        let source = nested_cells_map_source(true);
+        let (root_object, _construct_context, _scope_guard) = evaluate_program(&source);
+        let elements = root_object.expect_variable("elements");
+
+        block_on(async move {
+            let value = elements
+                .value_actor()
+                .current_value()
+                .await
+                .expect("elements list should have a current value");
+            let Value::List(list, _) = value else {
+                panic!("elements should evaluate to a list");
+            };
+            let snapshot = list.snapshot().await;
+            assert_eq!(
+                snapshot.len(),
+                2,
+                "expected two mapped linked cell elements"
+            );
+
+            for (_pid, item_actor) in snapshot {
+                let item_value = item_actor
+                    .current_value()
+                    .await
+                    .expect("mapped linked cell item should have value");
+                let Value::TaggedObject(tagged, _) = item_value else {
+                    panic!("mapped linked cell item should be a tagged element object");
+                };
+                assert_eq!(tagged.tag(), "ElementLabel");
+            }
+        });
+    }
+
+    #[test]
+    fn nested_cells_map_helper_when_while_produces_label_elements() {
+        let source = nested_cells_map_helper_when_while_source();
+        let (root_object, _construct_context, _scope_guard) = evaluate_program(&source);
+        let elements = root_object.expect_variable("elements");
+
+        block_on(async move {
+            let value = elements
+                .value_actor()
+                .current_value()
+                .await
+                .expect("elements list should have a current value");
+            let Value::List(list, _) = value else {
+                panic!("elements should evaluate to a list");
+            };
+            let snapshot = list.snapshot().await;
+            assert_eq!(
+                snapshot.len(),
+                2,
+                "expected two mapped helper-fed cell elements"
+            );
+
+            for (_pid, item_actor) in snapshot {
+                let item_value = item_actor
+                    .current_value()
+                    .await
+                    .expect("mapped helper-fed cell item should have value");
+                let Value::TaggedObject(tagged, _) = item_value else {
+                    panic!("mapped helper-fed cell item should be a tagged element object");
+                };
+                assert_eq!(tagged.tag(), "ElementLabel");
+            }
+        });
+    }
+
+    #[test]
+    fn nested_cells_map_helper_when_while_with_text_input_inactive_branch_produces_label_elements()
+    {
+        let source = nested_cells_map_helper_when_while_text_input_source();
+        let (root_object, _construct_context, _scope_guard) = evaluate_program(&source);
+        let elements = root_object.expect_variable("elements");
+
+        block_on(async move {
+            let value = elements
+                .value_actor()
+                .current_value()
+                .await
+                .expect("elements list should have a current value");
+            let Value::List(list, _) = value else {
+                panic!("elements should evaluate to a list");
+            };
+            let snapshot = list.snapshot().await;
+            assert_eq!(
+                snapshot.len(),
+                2,
+                "expected two mapped helper-fed cell elements with inactive text input branch"
+            );
+
+            for (_pid, item_actor) in snapshot {
+                let item_value = item_actor
+                    .current_value()
+                    .await
+                    .expect("mapped helper-fed item should have value");
+                let Value::TaggedObject(tagged, _) = item_value else {
+                    panic!("mapped helper-fed item should be a tagged element object");
+                };
+                assert_eq!(tagged.tag(), "ElementLabel");
+            }
+        });
+    }
+
+    #[test]
+    fn nested_cells_map_helper_when_while_with_nested_link_produces_label_elements() {
+        let source = nested_cells_map_helper_when_while_nested_link_source();
+        let (root_object, _construct_context, _scope_guard) = evaluate_program(&source);
+        let elements = root_object.expect_variable("elements");
+
+        block_on(async move {
+            let value = elements
+                .value_actor()
+                .current_value()
+                .await
+                .expect("elements list should have a current value");
+            let Value::List(list, _) = value else {
+                panic!("elements should evaluate to a list");
+            };
+            let snapshot = list.snapshot().await;
+            assert_eq!(
+                snapshot.len(),
+                2,
+                "expected two mapped helper-fed cell elements with nested link target"
+            );
+
+            for (_pid, item_actor) in snapshot {
+                let item_value = item_actor
+                    .current_value()
+                    .await
+                    .expect("mapped helper-fed nested-link item should have value");
+                let Value::TaggedObject(tagged, _) = item_value else {
+                    panic!("mapped helper-fed nested-link item should be a tagged element object");
+                };
+                assert_eq!(tagged.tag(), "ElementLabel");
+            }
+        });
+    }
+
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn nested_cells_map_helper_when_while_nested_link_switches_branch_on_toggle() {
+        let source = nested_cells_map_helper_when_while_nested_link_toggle_source();
+        let (root_object, construct_context, _scope_guard) = evaluate_program(&source);
+        let elements = root_object.expect_variable("elements");
+        let toggle = root_object.expect_variable("toggle");
+        let sender = toggle.expect_link_value_sender();
+
+        block_on(async move {
+            let snapshot_to_json = |value: Value| async move {
+                let Value::List(list, _) = value else {
+                    panic!("elements should evaluate to a list");
+                };
+                let snapshot = list.snapshot().await;
+                let (_pid, first_item_actor) = snapshot
+                    .into_iter()
+                    .next()
+                    .expect("elements should contain first item");
+                first_item_actor
+                    .current_value()
+                    .await
+                    .expect("first mapped item should have current value")
+                    .to_json()
+                    .await
+            };
+
+            let initial_value = elements
+                .value_actor()
+                .current_value()
+                .await
+                .expect("elements should have initial value");
+            let initial_json = snapshot_to_json(initial_value).await;
+
+            send_link_signal(
+                construct_context,
+                sender,
+                serde_json::json!(true),
+                "test.toggle.nested_link_switch",
+            )
+            .await;
+
+            let updated_value = elements
+                .value_actor()
+                .stream_from_now()
+                .next()
+                .await
+                .expect("elements should emit updated value after toggle");
+            let updated_json = snapshot_to_json(updated_value).await;
+
+            let initial_text = initial_json.to_string();
+            let updated_text = updated_json.to_string();
+            assert!(
+                initial_text.contains("\"y\""),
+                "expected initial first item to be editing branch label, got {}",
+                initial_text
+            );
+            assert!(
+                updated_text.contains("\"x\""),
+                "expected updated first item to switch to display branch label, got {}",
+                updated_text
+            );
+        });
+    }
+
+    #[test]
+    #[ignore = "requires browser-side actors runtime; host lib tests still touch js-sys statics"]
+    fn mapped_cell_can_reopen_after_escape_via_nested_link_events() {
+        let source = nested_cells_reopen_editing_source();
+        let (root_object, construct_context, _scope_guard) = evaluate_program(&source);
+        let elements = root_object.expect_variable("elements");
+        let cells = root_object.expect_variable("cells");
+
+        block_on(async move {
+            async fn first_mapped_item(elements: &Variable) -> Value {
+                let elements_value = elements
+                    .value_actor()
+                    .current_value()
+                    .await
+                    .expect("elements should have a current value");
+                let Value::List(list, _) = elements_value else {
+                    panic!("elements should evaluate to a list");
+                };
+                let snapshot = list.snapshot().await;
+                let (_pid, first_item_actor) = snapshot
+                    .into_iter()
+                    .next()
+                    .expect("elements should contain first mapped item");
+                first_item_actor
+                    .current_value()
+                    .await
+                    .expect("first mapped item should have current value")
+            }
+
+            async fn first_cell(cells: &Variable) -> Arc<Object> {
+                let cells_value = cells
+                    .value_actor()
+                    .current_value()
+                    .await
+                    .expect("cells should have a current value");
+                let Value::List(list, _) = cells_value else {
+                    panic!("cells should evaluate to a list");
+                };
+                let snapshot = list.snapshot().await;
+                let (_pid, first_cell_actor) = snapshot
+                    .into_iter()
+                    .next()
+                    .expect("cells should contain first cell");
+                let first_cell_value = first_cell_actor
+                    .current_value()
+                    .await
+                    .expect("first cell should have current value");
+                let Value::Object(cell_obj, _) = first_cell_value else {
+                    panic!("first cell should be an object");
+                };
+                cell_obj
+            }
+
+            async fn nested_link_sender(
+                object: Arc<Object>,
+                value_field: &str,
+                event_field: &str,
+            ) -> crate::engine::NamedChannel<Value> {
+                let cell_elements = object
+                    .variable("cell_elements")
+                    .expect("cell should have cell_elements");
+                let cell_elements_value = cell_elements
+                    .value_actor()
+                    .current_value()
+                    .await
+                    .expect("cell_elements should have current value");
+                let Value::Object(cell_elements_obj, _) = cell_elements_value else {
+                    panic!("cell_elements should be an object");
+                };
+
+                let linked_element = cell_elements_obj
+                    .variable(value_field)
+                    .unwrap_or_else(|| panic!("cell_elements should contain {}", value_field));
+                let linked_element_value = linked_element
+                    .value_actor()
+                    .current_value()
+                    .await
+                    .unwrap_or_else(|_| panic!("{value_field} should have current value"));
+                let Value::TaggedObject(linked_tagged, _) = linked_element_value else {
                     panic!("{value_field} should be a tagged element object");
                 };
                 let element = linked_tagged
@@ -10418,968 +12311,3246 @@ all_completed: completed_todos_count == 4
                     .value_actor()
                     .current_value()
                     .await
-                    .expect("linked element object should have current value");
-                let Value::Object(element_obj, _) = element_value else {
-                    panic!("linked element field should be an object");
+                    .expect("linked element object should have current value");
+                let Value::Object(element_obj, _) = element_value else {
+                    panic!("linked element field should be an object");
+                };
+                let event = element_obj
+                    .variable("event")
+                    .expect("element should have event");
+                let event_value = event
+                    .value_actor()
+                    .current_value()
+                    .await
+                    .expect("event should have current value");
+                let Value::Object(event_obj, _) = event_value else {
+                    panic!("event should be an object");
+                };
+                event_obj
+                    .variable(event_field)
+                    .and_then(|var| var.link_value_sender())
+                    .unwrap_or_else(|| {
+                        panic!("{value_field}.element.event.{event_field} should have LINK sender")
+                    })
+            }
+
+            let _initial = first_mapped_item(&elements).await;
+            let cell_obj = first_cell(&cells).await;
+
+            let display_sender =
+                nested_link_sender(cell_obj.clone(), "display", "double_click").await;
+            send_link_signal(
+                construct_context.clone(),
+                display_sender,
+                serde_json::json!(true),
+                "test.cells.reopen.open_first",
+            )
+            .await;
+
+            let opened_value = first_mapped_item(&elements).await;
+            let Value::TaggedObject(opened_tagged, _) = opened_value else {
+                panic!("opened mapped item should be a tagged element object");
+            };
+            assert_eq!(
+                opened_tagged.tag(),
+                "ElementTextInput",
+                "double click should switch mapped cell into editing branch"
+            );
+
+            let editing_sender = nested_link_sender(cell_obj.clone(), "editing", "key_down").await;
+            send_link_signal(
+                construct_context.clone(),
+                editing_sender,
+                serde_json::json!({
+                    "key": "Escape",
+                    "text": ""
+                }),
+                "test.cells.reopen.escape",
+            )
+            .await;
+
+            let closed_value = first_mapped_item(&elements).await;
+            let Value::TaggedObject(closed_tagged, _) = closed_value else {
+                panic!("closed mapped item should be a tagged element object");
+            };
+            assert_eq!(
+                closed_tagged.tag(),
+                "ElementLabel",
+                "Escape should switch mapped cell back to display branch"
+            );
+
+            let display_sender = nested_link_sender(cell_obj, "display", "double_click").await;
+            send_link_signal(
+                construct_context,
+                display_sender,
+                serde_json::json!(true),
+                "test.cells.reopen.open_second",
+            )
+            .await;
+
+            let reopened_value = first_mapped_item(&elements).await;
+            let Value::TaggedObject(reopened_tagged, _) = reopened_value else {
+                panic!("reopened mapped item should be a tagged element object");
+            };
+            assert_eq!(
+                reopened_tagged.tag(),
+                "ElementTextInput",
+                "cell should reopen after Escape via the nested display LINK path"
+            );
+        });
+    }
+
+    #[test]
+    fn mapped_todo_item_preserves_nested_event_link_senders() {
+        let source = mapped_todo_item_event_link_source();
+        let (root_object, _construct_context, _scope_guard) = evaluate_program(source);
+        let elements = root_object.expect_variable("elements");
+
+        block_on(async move {
+            eprintln!("test: reading elements current_value");
+            let elements_value = elements
+                .value_actor()
+                .current_value()
+                .await
+                .expect("elements should have a current value");
+            let Value::List(list, _) = elements_value else {
+                panic!("elements should evaluate to a list");
+            };
+            let snapshot = list.snapshot().await;
+            let (_pid, first_item_actor) = snapshot
+                .into_iter()
+                .next()
+                .expect("mapped todo element should exist");
+
+            eprintln!("test: reading first mapped item");
+            let first_item_value = first_item_actor
+                .current_value()
+                .await
+                .expect("mapped todo item should have a current value");
+            let Value::TaggedObject(tagged, _) = first_item_value else {
+                panic!("mapped todo item should be a tagged element object");
+            };
+
+            let settings = tagged
+                .variable("settings")
+                .expect("mapped todo item should have settings");
+            eprintln!("test: reading settings");
+            let settings_value = settings
+                .value_actor()
+                .current_value()
+                .await
+                .expect("settings should have current value");
+            let Value::Object(settings_obj, _) = settings_value else {
+                panic!("settings should be an object");
+            };
+
+            let items = settings_obj
+                .variable("items")
+                .expect("stripe settings should contain items");
+            eprintln!("test: reading items");
+            let items_value = items
+                .value_actor()
+                .current_value()
+                .await
+                .expect("items should have current value");
+            let Value::List(items_list, _) = items_value else {
+                panic!("items should be a list");
+            };
+            let item_snapshot = items_list.snapshot().await;
+            assert_eq!(item_snapshot.len(), 2, "expected checkbox + label items");
+
+            let (_checkbox_pid, checkbox_actor) = &item_snapshot[0];
+            eprintln!("test: reading checkbox item");
+            let checkbox_value = checkbox_actor
+                .current_value()
+                .await
+                .expect("checkbox item should have current value");
+            let Value::TaggedObject(checkbox_tagged, _) = checkbox_value else {
+                panic!("first item should be checkbox tagged object");
+            };
+            let checkbox_element = checkbox_tagged
+                .variable("element")
+                .expect("checkbox should have element object");
+            eprintln!("test: reading checkbox element");
+            let checkbox_element_value = checkbox_element
+                .value_actor()
+                .current_value()
+                .await
+                .expect("checkbox element should have current value");
+            let Value::Object(checkbox_element_obj, _) = checkbox_element_value else {
+                panic!("checkbox element should be object");
+            };
+            let checkbox_event = checkbox_element_obj
+                .variable("event")
+                .expect("checkbox element should have event");
+            eprintln!("test: reading checkbox event");
+            let checkbox_event_value = checkbox_event
+                .value_actor()
+                .current_value()
+                .await
+                .expect("checkbox event should have current value");
+            let Value::Object(checkbox_event_obj, _) = checkbox_event_value else {
+                panic!("checkbox event should be object");
+            };
+            assert!(
+                checkbox_event_obj
+                    .variable("click")
+                    .and_then(|var| var.link_value_sender())
+                    .is_some(),
+                "checkbox click LINK sender should survive mapped nested link target"
+            );
+
+            let (_label_pid, label_actor) = &item_snapshot[1];
+            eprintln!("test: reading label item");
+            let label_value = label_actor
+                .current_value()
+                .await
+                .expect("label item should have current value");
+            let Value::TaggedObject(label_tagged, _) = label_value else {
+                panic!("second item should be label tagged object");
+            };
+            let label_element = label_tagged
+                .variable("element")
+                .expect("label should have element object");
+            eprintln!("test: reading label element");
+            let label_element_value = label_element
+                .value_actor()
+                .current_value()
+                .await
+                .expect("label element should have current value");
+            let Value::Object(label_element_obj, _) = label_element_value else {
+                panic!("label element should be object");
+            };
+            let label_event = label_element_obj
+                .variable("event")
+                .expect("label element should have event");
+            eprintln!("test: reading label event");
+            let label_event_value = label_event
+                .value_actor()
+                .current_value()
+                .await
+                .expect("label event should have current value");
+            let Value::Object(label_event_obj, _) = label_event_value else {
+                panic!("label event should be object");
+            };
+            assert!(
+                label_event_obj
+                    .variable("double_click")
+                    .and_then(|var| var.link_value_sender())
+                    .is_some(),
+                "label double_click LINK sender should survive mapped nested link target"
+            );
+        });
+    }
+
+    #[test]
+    fn mapped_todo_item_link_target_receives_nested_event_link_senders() {
+        let source = mapped_todo_item_event_link_source();
+        let (root_object, _construct_context, _scope_guard) = evaluate_program(source);
+        let elements = root_object.expect_variable("elements");
+        let todos = root_object.expect_variable("todos");
+
+        block_on(async move {
+            eprintln!("target-test: forcing elements evaluation");
+            let _elements_value = elements
+                .value_actor()
+                .current_value()
+                .await
+                .expect("elements should have a current value");
+
+            eprintln!("target-test: reading todos");
+            let todos_value = todos
+                .value_actor()
+                .current_value()
+                .await
+                .expect("todos should have a current value");
+            let Value::List(todos_list, _) = todos_value else {
+                panic!("todos should evaluate to a list");
+            };
+
+            let todo_snapshot = todos_list.snapshot().await;
+            let (_todo_pid, todo_actor) = todo_snapshot
+                .into_iter()
+                .next()
+                .expect("first todo should exist");
+
+            eprintln!("target-test: reading first todo object");
+            let todo_value = todo_actor
+                .current_value()
+                .await
+                .expect("todo item should have a current value");
+            let Value::Object(todo_obj, _) = todo_value else {
+                panic!("todo item should be an object");
+            };
+
+            let todo_elements = todo_obj
+                .variable("todo_elements")
+                .expect("todo should have todo_elements");
+            eprintln!("target-test: reading todo_elements");
+            let todo_elements_value = todo_elements
+                .value_actor()
+                .current_value()
+                .await
+                .expect("todo_elements should have current value");
+            let Value::Object(todo_elements_obj, _) = todo_elements_value else {
+                panic!("todo_elements should be an object");
+            };
+
+            let todo_checkbox = todo_elements_obj
+                .variable("todo_checkbox")
+                .expect("todo_elements should contain todo_checkbox");
+            eprintln!("target-test: reading linked todo_checkbox");
+            let todo_checkbox_value = todo_checkbox
+                .value_actor()
+                .current_value()
+                .await
+                .expect("linked todo_checkbox should have current value");
+            let Value::TaggedObject(todo_checkbox_tagged, _) = todo_checkbox_value else {
+                panic!("linked todo_checkbox should be a tagged element object");
+            };
+            let todo_checkbox_element = todo_checkbox_tagged
+                .variable("element")
+                .expect("linked todo_checkbox should have element");
+            let todo_checkbox_element_value = todo_checkbox_element
+                .value_actor()
+                .current_value()
+                .await
+                .expect("linked todo_checkbox element should have current value");
+            let Value::Object(todo_checkbox_element_obj, _) = todo_checkbox_element_value else {
+                panic!("linked todo_checkbox element should be object");
+            };
+            let todo_checkbox_event = todo_checkbox_element_obj
+                .variable("event")
+                .expect("linked todo_checkbox should have event");
+            let todo_checkbox_event_value = todo_checkbox_event
+                .value_actor()
+                .current_value()
+                .await
+                .expect("linked todo_checkbox event should have current value");
+            let Value::Object(todo_checkbox_event_obj, _) = todo_checkbox_event_value else {
+                panic!("linked todo_checkbox event should be object");
+            };
+            assert!(
+                todo_checkbox_event_obj
+                    .variable("click")
+                    .and_then(|var| var.link_value_sender())
+                    .is_some(),
+                "linked todo_checkbox click LINK sender should survive link target forwarding"
+            );
+
+            let todo_title = todo_elements_obj
+                .variable("todo_title_element")
+                .expect("todo_elements should contain todo_title_element");
+            eprintln!("target-test: reading linked todo_title_element");
+            let todo_title_value = todo_title
+                .value_actor()
+                .current_value()
+                .await
+                .expect("linked todo_title_element should have current value");
+            let Value::TaggedObject(todo_title_tagged, _) = todo_title_value else {
+                panic!("linked todo_title_element should be a tagged element object");
+            };
+            let todo_title_element = todo_title_tagged
+                .variable("element")
+                .expect("linked todo_title_element should have element");
+            let todo_title_element_value = todo_title_element
+                .value_actor()
+                .current_value()
+                .await
+                .expect("linked todo_title_element element should have current value");
+            let Value::Object(todo_title_element_obj, _) = todo_title_element_value else {
+                panic!("linked todo_title_element element should be object");
+            };
+            let todo_title_event = todo_title_element_obj
+                .variable("event")
+                .expect("linked todo_title_element should have event");
+            let todo_title_event_value = todo_title_event
+                .value_actor()
+                .current_value()
+                .await
+                .expect("linked todo_title_element event should have current value");
+            let Value::Object(todo_title_event_obj, _) = todo_title_event_value else {
+                panic!("linked todo_title_element event should be object");
+            };
+            assert!(
+                todo_title_event_obj
+                    .variable("double_click")
+                    .and_then(|var| var.link_value_sender())
+                    .is_some(),
+                "linked todo_title_element double_click LINK sender should survive link target forwarding"
+            );
+        });
+    }
+
+    #[test]
+    #[ignore = "requires browser-side actors runtime; host lib tests still touch js-sys statics"]
+    fn crud_linked_row_preserves_press_sender() {
+        let source = read_example_source("../../playground/frontend/src/examples/crud/crud.bn");
+        let (root_object, _construct_context, _scope_guard) = evaluate_program(&source);
+        let document = root_object.expect_variable("document");
+        let store = root_object.expect_variable("store");
+
+        block_on(async move {
+            let _document_value = document
+                .value_actor()
+                .current_value()
+                .await
+                .expect("document should have a current value");
+
+            let store_value = store
+                .value_actor()
+                .current_value()
+                .await
+                .expect("store should have a current value");
+            let Value::Object(store_obj, _) = store_value else {
+                panic!("store should be an object");
+            };
+
+            let people = store_obj
+                .variable("people")
+                .expect("store should contain people");
+            let people_value = people
+                .value_actor()
+                .current_value()
+                .await
+                .expect("people should have a current value");
+            let Value::List(people_list, _) = people_value else {
+                panic!("people should evaluate to a list");
+            };
+            let people_snapshot = people_list.snapshot().await;
+            let (_tansen_pid, tansen_actor) = people_snapshot
+                .get(2)
+                .cloned()
+                .expect("third CRUD person should exist");
+
+            let tansen_value = tansen_actor
+                .current_value()
+                .await
+                .expect("Tansen row object should have a current value");
+            let Value::Object(tansen_obj, _) = tansen_value else {
+                panic!("CRUD person should be an object");
+            };
+
+            let person_elements = tansen_obj
+                .variable("person_elements")
+                .expect("CRUD person should have person_elements");
+            let person_elements_value = person_elements
+                .value_actor()
+                .current_value()
+                .await
+                .expect("person_elements should have current value");
+            let Value::Object(person_elements_obj, _) = person_elements_value else {
+                panic!("person_elements should be object");
+            };
+
+            let row = person_elements_obj
+                .variable("row")
+                .expect("person_elements should contain row");
+            let row_value = row
+                .value_actor()
+                .current_value()
+                .await
+                .expect("linked row should have current value after document evaluation");
+            let Value::TaggedObject(row_tagged, _) = row_value else {
+                panic!("linked row should be tagged element object");
+            };
+            let row_element = row_tagged
+                .variable("element")
+                .expect("linked row should have element");
+            let row_element_value = row_element
+                .value_actor()
+                .current_value()
+                .await
+                .expect("linked row element should have current value");
+            let Value::Object(row_element_obj, _) = row_element_value else {
+                panic!("linked row element should be object");
+            };
+            let row_event = row_element_obj
+                .variable("event")
+                .expect("linked row should have event");
+            let row_event_value = row_event
+                .value_actor()
+                .current_value()
+                .await
+                .expect("linked row event should have current value");
+            let Value::Object(row_event_obj, _) = row_event_value else {
+                panic!("linked row event should be object");
+            };
+
+            assert!(
+                row_event_obj
+                    .variable("press")
+                    .and_then(|var| var.link_value_sender())
+                    .is_some(),
+                "linked CRUD row press LINK sender should survive nested link target forwarding"
+            );
+        });
+    }
+
+    #[test]
+    #[ignore = "requires browser-side actors runtime; host lib tests still touch js-sys statics"]
+    fn crud_mapped_row_press_updates_selected_id() {
+        let source = read_example_source("../../playground/frontend/src/examples/crud/crud.bn");
+        let (root_object, construct_context, _scope_guard) = evaluate_program(&source);
+        let document = root_object.expect_variable("document");
+        let store = root_object.expect_variable("store");
+
+        block_on(async move {
+            let _document_value = document
+                .value_actor()
+                .current_value()
+                .await
+                .expect("document should have a current value");
+
+            let store_value = store
+                .value_actor()
+                .current_value()
+                .await
+                .expect("store should have a current value");
+            let Value::Object(store_obj, _) = store_value else {
+                panic!("store should be an object");
+            };
+
+            let people = store_obj
+                .variable("people")
+                .expect("store should contain people");
+            let selected_id = store_obj
+                .variable("selected_id")
+                .expect("store should contain selected_id");
+
+            let people_value = people
+                .value_actor()
+                .current_value()
+                .await
+                .expect("people should have a current value");
+            let Value::List(people_list, _) = people_value else {
+                panic!("people should evaluate to a list");
+            };
+            let people_snapshot = people_list.snapshot().await;
+            let (_tansen_pid, tansen_actor) = people_snapshot
+                .get(2)
+                .cloned()
+                .expect("third CRUD person should exist");
+
+            let tansen_value = tansen_actor
+                .current_value()
+                .await
+                .expect("Tansen row object should have a current value");
+            let Value::Object(tansen_obj, _) = tansen_value else {
+                panic!("CRUD person should be an object");
+            };
+
+            let tansen_id = tansen_obj
+                .variable("id")
+                .expect("CRUD person should have id")
+                .value_actor()
+                .current_value()
+                .await
+                .expect("CRUD person id should have current value")
+                .to_json()
+                .await;
+
+            let person_elements_value = tansen_obj
+                .variable("person_elements")
+                .expect("CRUD person should have person_elements")
+                .value_actor()
+                .current_value()
+                .await
+                .expect("person_elements should have current value");
+            let Value::Object(person_elements_obj, _) = person_elements_value else {
+                panic!("person_elements should be object");
+            };
+            let row_var = person_elements_obj
+                .variable("row")
+                .expect("person_elements should contain row");
+            let row_value = row_var
+                .value_actor()
+                .current_value()
+                .await
+                .expect("linked row should have current value after document evaluation");
+            let Value::TaggedObject(row_tagged, _) = row_value else {
+                panic!("linked row should be tagged element object");
+            };
+            let row_element = row_tagged
+                .variable("element")
+                .expect("linked row should have element");
+            let row_element_value = row_element
+                .value_actor()
+                .current_value()
+                .await
+                .expect("linked row element should have current value");
+            let Value::Object(row_element_obj, _) = row_element_value else {
+                panic!("linked row element should be object");
+            };
+            let row_event = row_element_obj
+                .variable("event")
+                .expect("linked row should have event");
+            let row_event_value = row_event
+                .value_actor()
+                .current_value()
+                .await
+                .expect("linked row event should have current value");
+            let Value::Object(row_event_obj, _) = row_event_value else {
+                panic!("linked row event should be object");
+            };
+            let press_sender = row_event_obj
+                .variable("press")
+                .and_then(|var| var.link_value_sender())
+                .expect("linked row press sender should exist");
+
+            let initial_selected_id = selected_id
+                .value_actor()
+                .current_value()
+                .await
+                .expect("selected_id should have initial value")
+                .to_json()
+                .await;
+
+            send_link_signal(
+                construct_context,
+                press_sender,
+                serde_json::json!({}),
+                "test.crud.row_press",
+            )
+            .await;
+
+            let mut updated_selected_id = selected_id
+                .value_actor()
+                .current_value()
+                .await
+                .expect("selected_id should still have current value")
+                .to_json()
+                .await;
+            for _ in 0..50 {
+                if updated_selected_id == tansen_id {
+                    break;
+                }
+                std::thread::sleep(Duration::from_millis(10));
+                updated_selected_id = selected_id
+                    .value_actor()
+                    .current_value()
+                    .await
+                    .expect("selected_id should still have current value")
+                    .to_json()
+                    .await;
+            }
+
+            assert_eq!(initial_selected_id, serde_json::json!("None"));
+            assert_eq!(updated_selected_id, tansen_id);
+        });
+    }
+
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn linked_checkbox_updates_sibling_hold_state() {
+        let (root_object, construct_context, _scope_guard) =
+            evaluate_program(linked_checkbox_updates_sibling_hold_source());
+        let checkbox = root_object.expect_variable("checkbox");
+        let todo = root_object.expect_variable("todo");
+
+        let (initial_completed_json, updated_completed_json) = block_on(async move {
+            let checkbox_value = checkbox
+                .value_actor()
+                .current_value()
+                .await
+                .expect("checkbox should have a current value");
+            let Value::TaggedObject(checkbox_tagged, _) = checkbox_value else {
+                panic!("checkbox should be a tagged element object");
+            };
+            let checkbox_element = checkbox_tagged
+                .variable("element")
+                .expect("checkbox should have element object");
+            let checkbox_element_value = checkbox_element
+                .value_actor()
+                .current_value()
+                .await
+                .expect("checkbox element should have current value");
+            let Value::Object(checkbox_element_obj, _) = checkbox_element_value else {
+                panic!("checkbox element should be object");
+            };
+            let checkbox_event = checkbox_element_obj
+                .variable("event")
+                .expect("checkbox element should have event");
+            let checkbox_event_value = checkbox_event
+                .value_actor()
+                .current_value()
+                .await
+                .expect("checkbox event should have current value");
+            let Value::Object(checkbox_event_obj, _) = checkbox_event_value else {
+                panic!("checkbox event should be object");
+            };
+            let click_sender = checkbox_event_obj
+                .variable("click")
+                .and_then(|var| var.link_value_sender())
+                .expect("checkbox click sender should exist");
+
+            let todo_value = todo
+                .value_actor()
+                .current_value()
+                .await
+                .expect("todo should have current value");
+            let Value::Object(todo_obj, _) = todo_value else {
+                panic!("todo should be an object");
+            };
+            let completed = todo_obj
+                .variable("completed")
+                .expect("todo should contain completed");
+
+            let initial_completed_json = completed
+                .value_actor()
+                .current_value()
+                .await
+                .expect("completed should have initial value")
+                .to_json()
+                .await;
+
+            send_link_signal(
+                construct_context,
+                click_sender,
+                serde_json::json!({}),
+                "test.checkbox.click",
+            )
+            .await;
+
+            let mut updated_completed_json = completed
+                .value_actor()
+                .current_value()
+                .await
+                .expect("completed should still have current value")
+                .to_json()
+                .await;
+            for _ in 0..50 {
+                if updated_completed_json == serde_json::json!(true) {
+                    break;
+                }
+                std::thread::sleep(Duration::from_millis(10));
+                updated_completed_json = completed
+                    .value_actor()
+                    .current_value()
+                    .await
+                    .expect("completed should still have current value")
+                    .to_json()
+                    .await;
+            }
+
+            (initial_completed_json, updated_completed_json)
+        });
+
+        assert_eq!(initial_completed_json, serde_json::json!(false));
+        assert_eq!(updated_completed_json, serde_json::json!(true));
+    }
+
+    #[test]
+    #[ignore = "requires browser-side actors runtime; host lib tests still touch js-sys statics"]
+    fn list_map_reacts_to_external_dependency_changes() {
+        let (root_object, construct_context, _scope_guard) =
+            evaluate_program(list_map_external_dependency_source());
+        let toggle = root_object.expect_variable("toggle");
+        let mapped = root_object.expect_variable("mapped");
+        let sender = toggle.expect_link_value_sender();
+
+        let (initial_json, updated_json) = block_on(async move {
+            let Value::List(list, _) = mapped
+                .value_actor()
+                .current_value()
+                .await
+                .expect("mapped should have current list value")
+            else {
+                panic!("mapped should evaluate to a list");
+            };
+
+            let items_to_json = |items: Vec<crate::engine::ActorHandle>| async move {
+                let mut values = Vec::new();
+                for item_actor in items {
+                    let value = item_actor
+                        .current_value()
+                        .await
+                        .expect("mapped item should have current value")
+                        .to_json()
+                        .await;
+                    values.push(value);
+                }
+                serde_json::Value::Array(values)
+            };
+
+            let initial_snapshot = list.snapshot().await;
+            let initial_json = items_to_json(
+                initial_snapshot
+                    .iter()
+                    .map(|(_id, item_actor)| item_actor.clone())
+                    .collect(),
+            )
+            .await;
+            let current_items: Vec<crate::engine::ActorHandle> = initial_snapshot
+                .into_iter()
+                .map(|(_id, item_actor)| item_actor)
+                .collect();
+            send_link_signal(
+                construct_context,
+                sender,
+                json!({"toggle": true}),
+                "test.toggle",
+            )
+            .await;
+
+            let mut updated_json = items_to_json(current_items.clone()).await;
+            for _ in 0..50 {
+                if updated_json == json!([11.0, 12.0, 13.0]) {
+                    break;
+                }
+                std::thread::sleep(Duration::from_millis(10));
+                updated_json = items_to_json(current_items.clone()).await;
+            }
+
+            (initial_json, updated_json)
+        });
+
+        assert_eq!(initial_json, json!([1.0, 2.0, 3.0]));
+        assert_eq!(updated_json, json!([11.0, 12.0, 13.0]));
+    }
+
+    #[test]
+    fn mapped_formula_dependency_updates_after_override_commit() {
+        let (root_object, construct_context, _scope_guard) =
+            evaluate_program(cells_mapped_formula_dependency_source());
+        let edit_committed = root_object.expect_variable("edit_committed");
+        let formula_a1 = root_object.expect_variable("formula_a1");
+        let mapped = root_object.expect_variable("mapped");
+        let sender = edit_committed.expect_link_value_sender();
+
+        let (initial_formula_json, updated_formula_json, initial_mapped_json, updated_mapped_json) =
+            block_on(async move {
+                let Value::List(list, _) = mapped
+                    .value_actor()
+                    .current_value()
+                    .await
+                    .expect("mapped should have current list value")
+                else {
+                    panic!("mapped should evaluate to a list");
                 };
-                let event = element_obj
-                    .variable("event")
-                    .expect("element should have event");
-                let event_value = event
+
+                let items_to_json = |items: Vec<crate::engine::ActorHandle>| async move {
+                    let mut values = Vec::new();
+                    for item_actor in items {
+                        let value = item_actor
+                            .current_value()
+                            .await
+                            .expect("mapped item should have current value")
+                            .to_json()
+                            .await;
+                        values.push(value);
+                    }
+                    serde_json::Value::Array(values)
+                };
+
+                let initial_formula_json = formula_a1
+                    .value_actor()
+                    .current_value()
+                    .await
+                    .expect("formula_a1 should have current value")
+                    .to_json()
+                    .await;
+
+                let initial_snapshot = list.snapshot().await;
+                let current_items: Vec<crate::engine::ActorHandle> = initial_snapshot
+                    .iter()
+                    .map(|(_id, item_actor)| item_actor.clone())
+                    .collect();
+                let initial_mapped_json = items_to_json(current_items.clone()).await;
+
+                send_edit_committed(construct_context, sender, 1.0, 1.0, "7").await;
+
+                let mut updated_formula_json = formula_a1
                     .value_actor()
                     .current_value()
                     .await
-                    .expect("event should have current value");
-                let Value::Object(event_obj, _) = event_value else {
-                    panic!("event should be an object");
-                };
-                event_obj
-                    .variable(event_field)
-                    .and_then(|var| var.link_value_sender())
-                    .unwrap_or_else(|| {
-                        panic!("{value_field}.element.event.{event_field} should have LINK sender")
-                    })
+                    .expect("formula_a1 should still have current value")
+                    .to_json()
+                    .await;
+                let mut updated_mapped_json = items_to_json(current_items.clone()).await;
+
+                for _ in 0..50 {
+                    if updated_formula_json == json!("7")
+                        && updated_mapped_json == json!(["7", "17"])
+                    {
+                        break;
+                    }
+                    std::thread::sleep(Duration::from_millis(10));
+                    updated_formula_json = formula_a1
+                        .value_actor()
+                        .current_value()
+                        .await
+                        .expect("formula_a1 should still have current value")
+                        .to_json()
+                        .await;
+                    updated_mapped_json = items_to_json(current_items.clone()).await;
+                }
+
+                (
+                    initial_formula_json,
+                    updated_formula_json,
+                    initial_mapped_json,
+                    updated_mapped_json,
+                )
+            });
+
+        assert_eq!(initial_formula_json, json!("5"));
+        assert_eq!(updated_formula_json, json!("7"));
+        assert_eq!(initial_mapped_json, json!(["5", "15"]));
+        assert_eq!(updated_mapped_json, json!(["7", "17"]));
+    }
+
+    #[test]
+    #[ignore = "requires browser-side actors runtime; host lib tests still touch js-sys statics"]
+    fn snapshot_then_body_freezes_referenced_roots_across_sibling_hold_updates() {
+        let (root_object, construct_context, _scope_guard) =
+            evaluate_program(shared_snapshot_root_toggle_source());
+        let toggle_all = root_object.expect_variable("toggle_all");
+        let todos = root_object.expect_variable("todos");
+        let sender = toggle_all.expect_link_value_sender();
+
+        let (initial_json, updated_json) = block_on(async move {
+            let Value::List(list, _) = todos
+                .value_actor()
+                .current_value()
+                .await
+                .expect("todos should have current list value")
+            else {
+                panic!("todos should evaluate to a list");
+            };
+
+            let items_to_json = |items: Vec<crate::engine::ActorHandle>| async move {
+                let mut values = Vec::new();
+                for item_actor in items {
+                    let value = item_actor
+                        .current_value()
+                        .await
+                        .expect("todo item should have current value");
+                    let Value::Object(todo_obj, _) = value else {
+                        panic!("todo item should be an object");
+                    };
+                    let completed = todo_obj
+                        .variable("completed")
+                        .expect("todo item should have completed");
+                    values.push(
+                        completed
+                            .value_actor()
+                            .current_value()
+                            .await
+                            .expect("completed should have current value")
+                            .to_json()
+                            .await,
+                    );
+                }
+                serde_json::Value::Array(values)
+            };
+
+            let snapshot = list.snapshot().await;
+            let current_items: Vec<crate::engine::ActorHandle> = snapshot
+                .into_iter()
+                .map(|(_id, item_actor)| item_actor)
+                .collect();
+            let initial_json = items_to_json(current_items.clone()).await;
+
+            send_link_signal(
+                construct_context,
+                sender,
+                json!({}),
+                "test.toggle_all.snapshot_root",
+            )
+            .await;
+
+            let mut updated_json = items_to_json(current_items.clone()).await;
+            for _ in 0..50 {
+                if updated_json == json!([true, true, true, true]) {
+                    break;
+                }
+                std::thread::sleep(Duration::from_millis(10));
+                updated_json = items_to_json(current_items.clone()).await;
+            }
+
+            (initial_json, updated_json)
+        });
+
+        assert_eq!(initial_json, json!([false, false, false, true]));
+        assert_eq!(updated_json, json!([true, true, true, true]));
+    }
+
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn stream_sample_emits_latest_value_per_trigger_and_nothing_before_a_value() {
+        let (root_object, construct_context, _scope_guard) =
+            evaluate_program(stream_sample_source());
+        let text = root_object
+            .expect_variable("text")
+            .expect_link_value_sender();
+        let submit = root_object
+            .expect_variable("submit")
+            .expect_link_value_sender();
+        let submitted = root_object.expect_variable("submitted");
+
+        let emitted = block_on(async move {
+            let mut updates = submitted.value_actor().stream_from_now();
+            let press = || json!({ "_tag": "Press" });
+
+            // No text yet: this press must not emit
+            send_link_signal(
+                construct_context.clone(),
+                submit.clone(),
+                press(),
+                "test.press.early",
+            )
+            .await;
+            send_link_signal(
+                construct_context.clone(),
+                text.clone(),
+                json!("Milk"),
+                "test.text.milk",
+            )
+            .await;
+            send_link_signal(
+                construct_context.clone(),
+                submit.clone(),
+                press(),
+                "test.press.first",
+            )
+            .await;
+            // Same text sampled again is a second emission
+            send_link_signal(
+                construct_context.clone(),
+                submit.clone(),
+                press(),
+                "test.press.second",
+            )
+            .await;
+            send_link_signal(
+                construct_context.clone(),
+                text,
+                json!("Bread"),
+                "test.text.bread",
+            )
+            .await;
+            send_link_signal(construct_context, submit, press(), "test.press.third").await;
+
+            let mut emitted = Vec::new();
+            for _ in 0..3 {
+                let value = updates
+                    .next()
+                    .await
+                    .expect("submitted should emit per press");
+                emitted.push(value.to_json().await);
             }
+            emitted
+        });
+
+        assert_eq!(emitted, vec![json!("Milk"), json!("Milk"), json!("Bread")]);
+    }
+
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn errors_in_generated_module_point_back_at_build_template() {
+        let virtual_fs = VirtualFilesystem::new();
+        let (root_object, _construct_context, _scope_guard) =
+            evaluate_program_with_fs(generated_module_build_source(), virtual_fs.clone());
+        let written = root_object.expect_variable("written");
+
+        let reports = block_on(async move {
+            let result = written
+                .value_actor()
+                .stream()
+                .next()
+                .await
+                .expect("File/write_with_map should emit");
+            assert!(matches!(&result, Value::Tag(tag, _) if tag.tag() == "Success"));
+
+            let path = "Generated/Broken.bn";
+            let source = virtual_fs
+                .read_text(path)
+                .await
+                .expect("generated module should be written");
+            let map = virtual_fs
+                .read_text(&source_map_path(path))
+                .await
+                .expect("line map should be written next to it");
+            let source_map = SourceMap::parse(&map).expect("line map should parse");
+            try_parse_module(path, &source, Some(&source_map))
+                .err()
+                .expect("generated module should not parse")
+        });
+
+        assert!(
+            reports
+                .iter()
+                .all(|report| report.contains("generated from BUILD.bn:9")),
+            "{reports:#?}"
+        );
+    }
+
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn stream_gate_delivers_events_enqueued_before_it_closes() {
+        let (root_object, construct_context, _scope_guard) = evaluate_program(stream_gate_source());
+        let event = root_object
+            .expect_variable("event")
+            .expect_link_value_sender();
+        let open = root_object
+            .expect_variable("open")
+            .expect_link_value_sender();
+        let passed = root_object.expect_variable("passed");
+
+        let emitted = block_on(async move {
+            let mut updates = passed.value_actor().stream_from_now();
+            let send_event = |text: &'static str, construct_id: &'static str| {
+                send_link_signal(
+                    construct_context.clone(),
+                    event.clone(),
+                    json!(text),
+                    construct_id,
+                )
+            };
+            let set_open = |tag: &'static str, construct_id: &'static str| {
+                send_link_signal(
+                    construct_context.clone(),
+                    open.clone(),
+                    json!({ "_tag": tag }),
+                    construct_id,
+                )
+            };
+
+            set_open("True", "test.open.first").await;
+            send_event("a", "test.event.a").await;
+            // `b` is enqueued before the gate closes in the same tick, so it still delivers
+            send_event("b", "test.event.b").await;
+            set_open("False", "test.open.close").await;
+            send_event("c", "test.event.c").await;
+            set_open("True", "test.open.reopen").await;
+            send_event("d", "test.event.d").await;
+
+            let mut emitted = Vec::new();
+            for _ in 0..3 {
+                let value = updates.next().await.expect("passed should emit while open");
+                emitted.push(value.to_json().await);
+            }
+            emitted
+        });
+
+        assert_eq!(emitted, vec![json!("a"), json!("b"), json!("d")]);
+    }
+
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn count_where_matches_retain_then_count() {
+        let (root_object, _construct_context, _scope_guard) =
+            evaluate_program(count_where_source());
+        let summary = root_object.expect_variable("summary");
+
+        let summary = block_on(async move {
+            let value = summary
+                .value_actor()
+                .stream()
+                .next()
+                .await
+                .expect("summary should emit");
+            value.to_json().await
+        });
+
+        assert_eq!(summary, json!("2 2"));
+    }
+
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn list_sort_by_orders_by_key_and_keeps_ties_in_order() {
+        let (root_object, construct_context, _scope_guard) =
+            evaluate_program(list_sort_by_source());
+
+        assert_eq!(first_json(&root_object, "numbers"), json!([1.0, 2.0, 3.0]));
+        assert_eq!(first_json(&root_object, "titles"), json!(["a", "b", "c"]));
+        assert_eq!(
+            settled_json(
+                &root_object,
+                "dated_titles",
+                json!(["earlier", "later", "undated"])
+            ),
+            json!(["earlier", "later", "undated"])
+        );
+
+        for name in ["add_thirty", "add_ten", "add_twenty"] {
+            press(&root_object, &construct_context, name);
+        }
+        assert_eq!(
+            settled_json(&root_object, "appended", json!([10.0, 20.0, 30.0])),
+            json!([10.0, 20.0, 30.0])
+        );
+
+        assert_eq!(
+            settled_json(&root_object, "ranked_titles", json!(["first", "second"])),
+            json!(["first", "second"])
+        );
+        press(&root_object, &construct_context, "promote");
+        assert_eq!(
+            settled_json(&root_object, "ranked_titles", json!(["second", "first"])),
+            json!(["second", "first"])
+        );
+    }
+
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn list_filter_views_follow_the_predicate_without_touching_the_source() {
+        let (root_object, construct_context, _scope_guard) = evaluate_program(list_filter_source());
+
+        assert_eq!(first_json(&root_object, "large"), json!([345.0]));
+        assert_eq!(
+            first_json(&root_object, "numbers"),
+            json!([123.0, 345.0, 1.0])
+        );
+        assert_eq!(first_json(&root_object, "active"), json!(["b", "c"]));
+        assert_eq!(first_json(&root_object, "completed"), json!(["a"]));
+
+        press(&root_object, &construct_context, "finish_b");
+
+        assert_eq!(
+            settled_json(&root_object, "active", json!(["c"])),
+            json!(["c"])
+        );
+        assert_eq!(
+            settled_json(&root_object, "completed", json!(["a", "b"])),
+            json!(["a", "b"])
+        );
+        assert_eq!(first_json(&root_object, "all"), json!(["a", "b", "c"]));
+
+        // A removed item leaves every tab
+        press(&root_object, &construct_context, "remove_a");
+        assert_eq!(
+            settled_json(&root_object, "completed", json!(["b"])),
+            json!(["b"])
+        );
+        assert_eq!(
+            settled_json(&root_object, "all", json!(["b", "c"])),
+            json!(["b", "c"])
+        );
+        assert_eq!(first_json(&root_object, "active"), json!(["c"]));
+    }
+
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn list_first_and_last_are_none_on_an_empty_list() {
+        let (root_object, construct_context, _scope_guard) =
+            evaluate_program(list_first_last_source());
+
+        assert_eq!(first_json(&root_object, "first"), json!(1.0));
+        assert_eq!(first_json(&root_object, "last"), json!(3.0));
+        assert_eq!(
+            first_json(&root_object, "empty_first"),
+            json!({"_tag": "None"})
+        );
+        assert_eq!(
+            first_json(&root_object, "empty_last"),
+            json!({"_tag": "None"})
+        );
+        assert_eq!(
+            first_json(&root_object, "growing_last"),
+            json!({"_tag": "None"})
+        );
+
+        assert_eq!(
+            settled_json(&root_object, "ends", json!({"first": 1.0, "last": 2.0})),
+            json!({"first": 1.0, "last": 2.0})
+        );
+
+        press(&root_object, &construct_context, "add");
+        assert_eq!(
+            settled_json(&root_object, "growing_last", json!(7.0)),
+            json!(7.0)
+        );
+        assert_eq!(
+            settled_json(&root_object, "ends", json!({"first": 1.0, "last": 3.0})),
+            json!({"first": 1.0, "last": 3.0})
+        );
+
+        press(&root_object, &construct_context, "remove_first");
+        assert_eq!(
+            settled_json(&root_object, "ends", json!({"first": 2.0, "last": 3.0})),
+            json!({"first": 2.0, "last": 3.0})
+        );
+
+        press(&root_object, &construct_context, "clear");
+        let none = json!({"first": {"_tag": "None"}, "last": {"_tag": "None"}});
+        assert_eq!(settled_json(&root_object, "ends", none.clone()), none);
+    }
+
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn list_reverse_mirrors_appends_and_removals_in_the_source() {
+        let (root_object, construct_context, _scope_guard) =
+            evaluate_program(list_reverse_source());
+
+        assert_eq!(
+            settled_json(&root_object, "reversed", json!([3.0, 2.0, 1.0])),
+            json!([3.0, 2.0, 1.0])
+        );
+        assert_eq!(settled_json(&root_object, "empty", json!([])), json!([]));
+
+        press(&root_object, &construct_context, "add");
+        assert_eq!(
+            settled_json(&root_object, "reversed", json!([4.0, 3.0, 2.0, 1.0])),
+            json!([4.0, 3.0, 2.0, 1.0])
+        );
+
+        press(&root_object, &construct_context, "remove_oldest");
+        assert_eq!(
+            settled_json(&root_object, "reversed", json!([4.0, 3.0, 2.0])),
+            json!([4.0, 3.0, 2.0])
+        );
+        assert_eq!(first_json(&root_object, "numbers"), json!([2.0, 3.0, 4.0]));
+    }
+
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn list_contains_and_index_of_follow_membership_and_the_searched_value() {
+        let (root_object, construct_context, _scope_guard) =
+            evaluate_program(list_contains_index_of_source());
+
+        assert_eq!(
+            first_json(&root_object, "has_green"),
+            json!({"_tag": "True"})
+        );
+        assert_eq!(first_json(&root_object, "green_position"), json!(2.0));
+        assert_eq!(
+            first_json(&root_object, "has_home"),
+            json!({"_tag": "False"})
+        );
+        assert_eq!(
+            first_json(&root_object, "home_position"),
+            json!({"_tag": "None"})
+        );
+        assert_eq!(
+            first_json(&root_object, "has_point"),
+            json!({"_tag": "True"})
+        );
+        assert_eq!(
+            first_json(&root_object, "searched_position"),
+            json!({"_tag": "None"})
+        );
+
+        let add = root_object
+            .expect_variable("add")
+            .expect_link_value_sender();
+        block_on(send_link_signal(
+            construct_context.clone(),
+            add,
+            json!({"add": true}),
+            "test.add",
+        ));
+        assert_eq!(
+            settled_json(&root_object, "home_position", json!(5.0)),
+            json!(5.0)
+        );
+        assert_eq!(
+            settled_json(&root_object, "has_home", json!({"_tag": "True"})),
+            json!({"_tag": "True"})
+        );
+        press(&root_object, &construct_context, "clear");
+        assert_eq!(
+            settled_json(&root_object, "home_position", json!({"_tag": "None"})),
+            json!({"_tag": "None"})
+        );
+        assert_eq!(
+            settled_json(&root_object, "has_home", json!({"_tag": "False"})),
+            json!({"_tag": "False"})
+        );
 
-            let _initial = first_mapped_item(&elements).await;
-            let cell_obj = first_cell(&cells).await;
+        press(&root_object, &construct_context, "next");
+        assert_eq!(
+            settled_json(&root_object, "searched_position", json!(1.0)),
+            json!(1.0)
+        );
 
-            let display_sender =
-                nested_link_sender(cell_obj.clone(), "display", "double_click").await;
-            send_link_signal(
+        let missing = json!({"contains": {"_tag": "False"}, "index": {"_tag": "None"}});
+        assert_eq!(
+            settled_json(&root_object, "found", missing.clone()),
+            missing
+        );
+        press(&root_object, &construct_context, "rename");
+        let found = json!({"contains": {"_tag": "True"}, "index": 2.0});
+        assert_eq!(settled_json(&root_object, "found", found.clone()), found);
+    }
+
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn list_zip_pairs_up_to_the_shorter_list_and_follows_both() {
+        let (root_object, construct_context, _scope_guard) = evaluate_program(list_zip_source());
+
+        assert_eq!(
+            settled_json(&root_object, "sums", json!([11.0, 22.0])),
+            json!([11.0, 22.0])
+        );
+        assert_eq!(
+            settled_json(
+                &root_object,
+                "pairs",
+                json!([{"first": "Ann", "second": 3.0}])
+            ),
+            json!([{"first": "Ann", "second": 3.0}])
+        );
+
+        let add_name = root_object
+            .expect_variable("add_name")
+            .expect_link_value_sender();
+        block_on(send_link_signal(
+            construct_context.clone(),
+            add_name,
+            json!({"add_name": true}),
+            "test.add_name",
+        ));
+        let both = json!([
+            {"first": "Ann", "second": 3.0},
+            {"first": "Bob", "second": 5.0}
+        ]);
+        assert_eq!(settled_json(&root_object, "pairs", both.clone()), both);
+
+        let clear_scores = root_object
+            .expect_variable("clear_scores")
+            .expect_link_value_sender();
+        block_on(send_link_signal(
+            construct_context.clone(),
+            clear_scores,
+            json!({"clear_scores": true}),
+            "test.clear_scores",
+        ));
+        assert_eq!(settled_json(&root_object, "pairs", json!([])), json!([]));
+
+        press(&root_object, &construct_context, "score");
+        let scored = json!([{"first": "Ann", "second": 4.0}]);
+        assert_eq!(settled_json(&root_object, "scored", scored.clone()), scored);
+    }
+
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn list_sum_min_max_skip_text_and_follow_items() {
+        let (root_object, construct_context, _scope_guard) =
+            evaluate_program(list_sum_min_max_source());
+
+        assert_eq!(first_json(&root_object, "total"), json!(3.0));
+        assert_eq!(first_json(&root_object, "cheapest"), json!(1.0));
+        assert_eq!(first_json(&root_object, "priciest"), json!(2.0));
+        assert_eq!(first_json(&root_object, "empty_total"), json!(0.0));
+        assert_eq!(
+            first_json(&root_object, "empty_cheapest"),
+            json!({"_tag": "None"})
+        );
+
+        let add = root_object
+            .expect_variable("add")
+            .expect_link_value_sender();
+        block_on(send_link_signal(
+            construct_context.clone(),
+            add,
+            json!({"add": true}),
+            "test.add",
+        ));
+        assert_eq!(
+            settled_json(&root_object, "cheapest", json!(0.5)),
+            json!(0.5)
+        );
+        assert_eq!(settled_json(&root_object, "total", json!(3.5)), json!(3.5));
+
+        let add_to_bread = root_object
+            .expect_variable("add_to_bread")
+            .expect_link_value_sender();
+        block_on(send_link_signal(
+            construct_context.clone(),
+            add_to_bread,
+            json!({"add_to_bread": true}),
+            "test.add_to_bread",
+        ));
+        assert_eq!(
+            settled_json(&root_object, "priciest", json!(5.0)),
+            json!(5.0)
+        );
+        assert_eq!(settled_json(&root_object, "total", json!(6.5)), json!(6.5));
+
+        press(&root_object, &construct_context, "remove_largest");
+        assert_eq!(
+            settled_json(&root_object, "priciest", json!(1.0)),
+            json!(1.0)
+        );
+        assert_eq!(settled_json(&root_object, "total", json!(1.5)), json!(1.5));
+
+        press(&root_object, &construct_context, "clear");
+        assert_eq!(settled_json(&root_object, "total", json!(0.0)), json!(0.0));
+        assert_eq!(
+            settled_json(&root_object, "cheapest", json!({"_tag": "None"})),
+            json!({"_tag": "None"})
+        );
+        assert_eq!(
+            settled_json(&root_object, "priciest", json!({"_tag": "None"})),
+            json!({"_tag": "None"})
+        );
+    }
+
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn list_any_and_all_follow_todo_toggles() {
+        let (root_object, construct_context, _scope_guard) =
+            evaluate_program(list_any_all_source());
+        let signal = |name: &str| {
+            let sender = root_object.expect_variable(name).expect_link_value_sender();
+            block_on(send_link_signal(
                 construct_context.clone(),
-                display_sender,
-                serde_json::json!(true),
-                "test.cells.reopen.open_first",
-            )
-            .await;
+                sender,
+                json!({"signal": true}),
+                "test.signal",
+            ));
+        };
+        let expect = |any: &str, all: &str| {
+            assert_eq!(
+                settled_json(&root_object, "any_completed", json!({"_tag": any})),
+                json!({"_tag": any})
+            );
+            assert_eq!(
+                settled_json(&root_object, "all_completed", json!({"_tag": all})),
+                json!({"_tag": all})
+            );
+        };
+
+        assert_eq!(
+            first_json(&root_object, "empty_all"),
+            json!({"_tag": "True"})
+        );
+        expect("False", "False");
+        signal("toggle_first");
+        expect("True", "False");
+        signal("toggle_all");
+        expect("True", "True");
+        signal("add");
+        expect("True", "False");
+        signal("toggle_all");
+        expect("True", "True");
+        signal("toggle_all");
+        expect("False", "False");
+    }
+
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn list_get_is_one_based_and_none_outside_the_list() {
+        let (root_object, construct_context, _scope_guard) = evaluate_program(list_get_source());
+        let signal = |name: &str| {
+            let sender = root_object.expect_variable(name).expect_link_value_sender();
+            block_on(send_link_signal(
+                construct_context.clone(),
+                sender,
+                json!({"signal": true}),
+                "test.signal",
+            ));
+        };
+
+        assert_eq!(first_json(&root_object, "first"), json!("a"));
+        assert_eq!(first_json(&root_object, "zero"), json!({"_tag": "None"}));
+        assert_eq!(
+            first_json(&root_object, "fraction"),
+            json!({"_tag": "None"})
+        );
+        assert_eq!(first_json(&root_object, "selected"), json!("a"));
+        assert_eq!(first_json(&root_object, "second"), json!({"_tag": "None"}));
+
+        signal("next");
+        signal("next");
+        assert_eq!(
+            settled_json(&root_object, "selected", json!({"_tag": "None"})),
+            json!({"_tag": "None"})
+        );
+        signal("add");
+        assert_eq!(
+            settled_json(&root_object, "selected", json!("c")),
+            json!("c")
+        );
+        assert_eq!(
+            settled_json(&root_object, "second", json!({"_tag": "B"})),
+            json!({"_tag": "B"})
+        );
+        signal("previous");
+        assert_eq!(
+            settled_json(&root_object, "selected", json!("b")),
+            json!("b")
+        );
+        signal("clear");
+        assert_eq!(
+            settled_json(&root_object, "second", json!({"_tag": "None"})),
+            json!({"_tag": "None"})
+        );
+
+        let shown = |count: f64, name: &str| json!({"count": count, "name": {"_tag": name}});
+        assert_eq!(
+            settled_json(&root_object, "shown_fields", shown(0.0, "A")),
+            shown(0.0, "A")
+        );
+        signal("press");
+        assert_eq!(
+            settled_json(&root_object, "shown_fields", shown(1.0, "A")),
+            shown(1.0, "A")
+        );
+        signal("select_second");
+        assert_eq!(
+            settled_json(&root_object, "shown_fields", shown(1.0, "B")),
+            shown(1.0, "B")
+        );
+        signal("press");
+        assert_eq!(
+            settled_json(&root_object, "shown_fields", shown(2.0, "B")),
+            shown(2.0, "B")
+        );
+    }
+
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn list_unique_and_unique_by_keep_first_occurrences() {
+        let (root_object, construct_context, _scope_guard) = evaluate_program(list_unique_source());
+        let signal = |name: &str| {
+            let sender = root_object.expect_variable(name).expect_link_value_sender();
+            block_on(send_link_signal(
+                construct_context.clone(),
+                sender,
+                json!({"signal": true}),
+                "test.signal",
+            ));
+        };
+
+        assert_eq!(
+            settled_json(
+                &root_object,
+                "unique_letters",
+                json!([{"_tag": "B"}, {"_tag": "A"}, {"_tag": "C"}])
+            ),
+            json!([{"_tag": "B"}, {"_tag": "A"}, {"_tag": "C"}])
+        );
+        assert_eq!(
+            settled_json(
+                &root_object,
+                "points",
+                json!([{"x": 1.0, "y": 2.0}, {"x": 1.0, "y": 3.0}])
+            ),
+            json!([{"x": 1.0, "y": 2.0}, {"x": 1.0, "y": 3.0}])
+        );
+        assert_eq!(
+            settled_json(
+                &root_object,
+                "first_per_category",
+                json!(["Milk", "Report"])
+            ),
+            json!(["Milk", "Report"])
+        );
+        assert_eq!(
+            settled_json(
+                &root_object,
+                "categories",
+                json!([{"_tag": "Shopping"}, {"_tag": "Work"}])
+            ),
+            json!([{"_tag": "Shopping"}, {"_tag": "Work"}])
+        );
+
+        signal("add");
+        assert_eq!(
+            settled_json(
+                &root_object,
+                "unique_letters",
+                json!([{"_tag": "B"}, {"_tag": "A"}, {"_tag": "C"}, {"_tag": "D"}])
+            ),
+            json!([{"_tag": "B"}, {"_tag": "A"}, {"_tag": "C"}, {"_tag": "D"}])
+        );
+        signal("add_b");
+        assert_eq!(
+            settled_json(
+                &root_object,
+                "unique_letters",
+                json!([{"_tag": "B"}, {"_tag": "A"}, {"_tag": "C"}, {"_tag": "D"}])
+            ),
+            json!([{"_tag": "B"}, {"_tag": "A"}, {"_tag": "C"}, {"_tag": "D"}])
+        );
+        signal("remove_a");
+        assert_eq!(
+            settled_json(
+                &root_object,
+                "unique_letters",
+                json!([{"_tag": "B"}, {"_tag": "C"}, {"_tag": "D"}])
+            ),
+            json!([{"_tag": "B"}, {"_tag": "C"}, {"_tag": "D"}])
+        );
+        signal("recategorize");
+        assert_eq!(
+            settled_json(&root_object, "first_per_category", json!(["Milk"])),
+            json!(["Milk"])
+        );
+        assert_eq!(
+            settled_json(&root_object, "categories", json!([{"_tag": "Home"}])),
+            json!([{"_tag": "Home"}])
+        );
+    }
+
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn list_concat_follows_both_lists() {
+        let (root_object, construct_context, _scope_guard) = evaluate_program(list_concat_source());
+        let signal = |name: &str| {
+            let sender = root_object.expect_variable(name).expect_link_value_sender();
+            block_on(send_link_signal(
+                construct_context.clone(),
+                sender,
+                json!({"signal": true}),
+                "test.signal",
+            ));
+        };
+
+        assert_eq!(
+            settled_json(&root_object, "notes", json!(["pinned", "recent"])),
+            json!(["pinned", "recent"])
+        );
+        assert_eq!(
+            settled_json(&root_object, "empty_first", json!(["a"])),
+            json!(["a"])
+        );
+
+        signal("add");
+        assert_eq!(
+            settled_json(&root_object, "notes", json!(["pinned", "recent", "newest"])),
+            json!(["pinned", "recent", "newest"])
+        );
+        signal("pin");
+        assert_eq!(
+            settled_json(
+                &root_object,
+                "notes",
+                json!(["pinned", "starred", "recent", "newest"])
+            ),
+            json!(["pinned", "starred", "recent", "newest"])
+        );
+        signal("unpin");
+        assert_eq!(
+            settled_json(
+                &root_object,
+                "notes",
+                json!(["starred", "recent", "newest"])
+            ),
+            json!(["starred", "recent", "newest"])
+        );
+        signal("clear_recent");
+        assert_eq!(
+            settled_json(&root_object, "notes", json!(["starred"])),
+            json!(["starred"])
+        );
+
+        signal("press");
+        signal("add_counter");
+        let counters = json!([
+            {"count": 1.0, "name": {"_tag": "A"}},
+            {"count": 1.0, "name": {"_tag": "B"}},
+            {"count": 0.0, "name": {"_tag": "C"}},
+        ]);
+        assert_eq!(
+            settled_json(&root_object, "counters", counters.clone()),
+            counters
+        );
+    }
+
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn text_join_follows_membership_and_item_edits() {
+        let (root_object, construct_context, _scope_guard) = evaluate_program(text_join_source());
+        let signal = |name: &str| {
+            let sender = root_object.expect_variable(name).expect_link_value_sender();
+            block_on(send_link_signal(
+                construct_context.clone(),
+                sender,
+                json!({"signal": true}),
+                "test.signal",
+            ));
+        };
+
+        assert_eq!(first_json(&root_object, "mixed"), json!("3/Urgent/ok"));
+        assert_eq!(first_json(&root_object, "empty"), json!(""));
+        assert_eq!(
+            settled_json(&root_object, "joined", json!("draft, shared")),
+            json!("draft, shared")
+        );
+
+        signal("add");
+        assert_eq!(
+            settled_json(&root_object, "joined", json!("draft, shared, new")),
+            json!("draft, shared, new")
+        );
+        signal("rename");
+        assert_eq!(
+            settled_json(&root_object, "joined", json!("final, shared, new")),
+            json!("final, shared, new")
+        );
+        signal("remove_shared");
+        assert_eq!(
+            settled_json(&root_object, "joined", json!("final, new")),
+            json!("final, new")
+        );
+    }
+
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn text_split_keeps_empty_parts_and_follows_the_text() {
+        let (root_object, construct_context, _scope_guard) = evaluate_program(text_split_source());
+
+        assert_eq!(settled_json(&root_object, "empty", json!([])), json!([]));
+        assert_eq!(
+            settled_json(&root_object, "lengths", json!([3.0, 3.0, 5.0])),
+            json!([3.0, 3.0, 5.0])
+        );
+        assert_eq!(
+            settled_json(&root_object, "parts", json!(["red", "green"])),
+            json!(["red", "green"])
+        );
+
+        press(&root_object, &construct_context, "change");
+        assert_eq!(
+            settled_json(&root_object, "parts", json!(["red", "blue", ""])),
+            json!(["red", "blue", ""])
+        );
+        press(&root_object, &construct_context, "clear");
+        assert_eq!(settled_json(&root_object, "parts", json!([])), json!([]));
+    }
+
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn text_case_follows_the_text_and_filters_names_case_insensitively() {
+        let (root_object, construct_context, _scope_guard) = evaluate_program(text_case_source());
+
+        assert_eq!(
+            settled_json(&root_object, "shouted", json!("ANN")),
+            json!("ANN")
+        );
+        press(&root_object, &construct_context, "change");
+        assert_eq!(
+            settled_json(&root_object, "shouted", json!("BOB")),
+            json!("BOB")
+        );
+
+        assert_eq!(
+            settled_json(
+                &root_object,
+                "matches",
+                json!(["Anna Smith", "Bob Jones", "JOANNA Lee"])
+            ),
+            json!(["Anna Smith", "Bob Jones", "JOANNA Lee"])
+        );
+        press(&root_object, &construct_context, "search_ann");
+        assert_eq!(
+            settled_json(&root_object, "matches", json!(["Anna Smith", "JOANNA Lee"])),
+            json!(["Anna Smith", "JOANNA Lee"])
+        );
+        press(&root_object, &construct_context, "search_jo");
+        assert_eq!(
+            settled_json(&root_object, "matches", json!(["Bob Jones", "JOANNA Lee"])),
+            json!(["Bob Jones", "JOANNA Lee"])
+        );
+    }
+
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn text_contains_is_case_sensitive_and_follows_both_sides() {
+        let (root_object, construct_context, _scope_guard) =
+            evaluate_program(text_contains_source());
+        let true_tag = json!({"_tag": "True"});
+        let false_tag = json!({"_tag": "False"});
+
+        assert_eq!(first_json(&root_object, "found"), true_tag);
+        assert_eq!(first_json(&root_object, "missing"), false_tag);
+        assert_eq!(first_json(&root_object, "empty_needle"), true_tag);
+        assert_eq!(first_json(&root_object, "case_sensitive"), false_tag);
+
+        assert_eq!(first_json(&root_object, "late_needle"), false_tag);
+        press(&root_object, &construct_context, "arrive");
+        assert_eq!(
+            settled_json(&root_object, "late_needle", true_tag.clone()),
+            true_tag
+        );
+
+        assert_eq!(first_json(&root_object, "title_matches"), true_tag);
+        press(&root_object, &construct_context, "rename");
+        assert_eq!(
+            settled_json(&root_object, "title_matches", false_tag.clone()),
+            false_tag
+        );
+        press(&root_object, &construct_context, "search");
+        assert_eq!(
+            settled_json(&root_object, "title_matches", true_tag.clone()),
+            true_tag
+        );
+
+        assert_eq!(
+            settled_json(
+                &root_object,
+                "todos",
+                json!(["Buy milk", "Clean room", "Buy bread"])
+            ),
+            json!(["Buy milk", "Clean room", "Buy bread"])
+        );
+        press(&root_object, &construct_context, "search_buy");
+        assert_eq!(
+            settled_json(&root_object, "todos", json!(["Buy milk", "Buy bread"])),
+            json!(["Buy milk", "Buy bread"])
+        );
+        press(&root_object, &construct_context, "search_room");
+        assert_eq!(
+            settled_json(&root_object, "todos", json!(["Clean room"])),
+            json!(["Clean room"])
+        );
+    }
+
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn text_replace_replaces_every_occurrence_and_follows_every_input() {
+        let (root_object, construct_context, _scope_guard) =
+            evaluate_program(text_replace_source());
+
+        assert_eq!(first_json(&root_object, "every"), json!("one+two+three"));
+        assert_eq!(first_json(&root_object, "repeated"), json!("ba"));
+        assert_eq!(first_json(&root_object, "overlapping"), json!("xba"));
+        assert_eq!(first_json(&root_object, "grows"), json!("aaaa"));
+        assert_eq!(first_json(&root_object, "empty_from"), json!("abc"));
+        assert_eq!(first_json(&root_object, "empty_to"), json!("abc"));
+
+        assert_eq!(
+            settled_json(&root_object, "name", json!("snake_case_name")),
+            json!("snake_case_name")
+        );
+        press(&root_object, &construct_context, "dash");
+        assert_eq!(
+            settled_json(&root_object, "name", json!("snake-case-name")),
+            json!("snake-case-name")
+        );
+        press(&root_object, &construct_context, "clear_replacement");
+        assert_eq!(
+            settled_json(&root_object, "name", json!("snakecasename")),
+            json!("snakecasename")
+        );
+
+        assert_eq!(
+            settled_json(&root_object, "recolored", json!("green apple")),
+            json!("green apple")
+        );
+        press(&root_object, &construct_context, "change_text");
+        assert_eq!(
+            settled_json(&root_object, "recolored", json!("green pear")),
+            json!("green pear")
+        );
+        press(&root_object, &construct_context, "change_from");
+        assert_eq!(
+            settled_json(&root_object, "recolored", json!("red green")),
+            json!("red green")
+        );
+    }
 
-            let opened_value = first_mapped_item(&elements).await;
-            let Value::TaggedObject(opened_tagged, _) = opened_value else {
-                panic!("opened mapped item should be a tagged element object");
-            };
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn text_starts_and_ends_with_follow_the_route_and_the_prefix() {
+        let (root_object, construct_context, _scope_guard) =
+            evaluate_program(text_starts_ends_with_source());
+        let tag = |name: &str| json!({ "_tag": name });
+        let expect = |name: &str, expected: &str| {
             assert_eq!(
-                opened_tagged.tag(),
-                "ElementTextInput",
-                "double click should switch mapped cell into editing branch"
+                settled_json(&root_object, name, tag(expected)),
+                tag(expected),
+                "{name}"
             );
+        };
 
-            let editing_sender = nested_link_sender(cell_obj.clone(), "editing", "key_down").await;
-            send_link_signal(
+        expect("is_users", "True");
+        expect("is_admin", "False");
+        expect("is_edit", "True");
+        expect("is_view", "False");
+        expect("empty_prefix", "True");
+        expect("empty_suffix", "True");
+        expect("number_text", "False");
+        expect("tag_suffix", "False");
+
+        let go_to = |route: &str| {
+            let sender = root_object
+                .expect_variable("go_to")
+                .expect_link_value_sender();
+            block_on(send_link_signal(
                 construct_context.clone(),
-                editing_sender,
-                serde_json::json!({
-                    "key": "Escape",
-                    "text": ""
-                }),
-                "test.cells.reopen.escape",
-            )
-            .await;
+                sender,
+                json!(route),
+                "test.go_to",
+            ));
+        };
+        expect("in_settings", "False");
+        expect("is_detail", "False");
+        go_to("/settings/profile");
+        expect("in_settings", "True");
+        expect("is_detail", "False");
+        go_to("/settings/profile/detail");
+        expect("in_settings", "True");
+        expect("is_detail", "True");
+        go_to("/home");
+        expect("in_settings", "False");
+        expect("is_detail", "False");
+
+        expect("in_section", "True");
+        press(&root_object, &construct_context, "section");
+        expect("in_section", "False");
+    }
 
-            let closed_value = first_mapped_item(&elements).await;
-            let Value::TaggedObject(closed_tagged, _) = closed_value else {
-                panic!("closed mapped item should be a tagged element object");
-            };
-            assert_eq!(
-                closed_tagged.tag(),
-                "ElementLabel",
-                "Escape should switch mapped cell back to display branch"
-            );
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn text_slice_truncates_titles_and_follows_its_inputs() {
+        let (root_object, construct_context, _scope_guard) = evaluate_program(text_slice_source());
 
-            let display_sender = nested_link_sender(cell_obj, "display", "double_click").await;
-            send_link_signal(
-                construct_context,
-                display_sender,
-                serde_json::json!(true),
-                "test.cells.reopen.open_second",
-            )
-            .await;
+        assert_eq!(
+            settled_json(&root_object, "short", json!("Buy milk")),
+            json!("Buy milk")
+        );
+        assert_eq!(
+            settled_json(&root_object, "long", json!("Clean the…")),
+            json!("Clean the…")
+        );
 
-            let reopened_value = first_mapped_item(&elements).await;
-            let Value::TaggedObject(reopened_tagged, _) = reopened_value else {
-                panic!("reopened mapped item should be a tagged element object");
-            };
+        assert_eq!(
+            settled_json(&root_object, "sliced", json!("Gro")),
+            json!("Gro")
+        );
+        press(&root_object, &construct_context, "grow");
+        assert_eq!(
+            settled_json(&root_object, "sliced", json!("Groce")),
+            json!("Groce")
+        );
+        press(&root_object, &construct_context, "rename");
+        assert_eq!(
+            settled_json(&root_object, "sliced", json!("Laund")),
+            json!("Laund")
+        );
+    }
+
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn text_pad_fills_either_side_and_follows_its_inputs() {
+        let (root_object, construct_context, _scope_guard) = evaluate_program(text_pad_source());
+        let expect = |name: &str, expected: &str| {
             assert_eq!(
-                reopened_tagged.tag(),
-                "ElementTextInput",
-                "cell should reopen after Escape via the nested display LINK path"
+                settled_json(&root_object, name, json!(expected)),
+                json!(expected),
+                "{name}"
             );
-        });
+        };
+
+        expect("zero_padded", "007");
+        expect("dotted", "Name....");
+        expect("repeated_start", "ababa7");
+        expect("repeated_end", "7ababa");
+        expect("already_long", "123");
+        expect("exact", "12");
+        expect("empty_with", "7");
+        expect("characters", "čč👋");
+        expect("number", "05");
+        expect("clock", "07:05");
+
+        expect("padded", "07");
+        press(&root_object, &construct_context, "widen");
+        expect("padded", "0007");
+        press(&root_object, &construct_context, "change_fill");
+        expect("padded", "***7");
     }
 
     #[test]
-    fn mapped_todo_item_preserves_nested_event_link_senders() {
-        let source = mapped_todo_item_event_link_source();
-        let (root_object, _construct_context, _scope_guard) = evaluate_program(source);
-        let elements = root_object.expect_variable("elements");
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn list_take_and_drop_follow_membership_and_their_count() {
+        let (root_object, construct_context, _scope_guard) =
+            evaluate_program(list_take_drop_source());
+        let signal = |name: &str| {
+            let sender = root_object.expect_variable(name).expect_link_value_sender();
+            block_on(send_link_signal(
+                construct_context.clone(),
+                sender,
+                json!({"signal": true}),
+                "test.signal",
+            ));
+        };
 
-        block_on(async move {
-            eprintln!("test: reading elements current_value");
-            let elements_value = elements
-                .value_actor()
-                .current_value()
-                .await
-                .expect("elements should have a current value");
-            let Value::List(list, _) = elements_value else {
-                panic!("elements should evaluate to a list");
-            };
-            let snapshot = list.snapshot().await;
-            let (_pid, first_item_actor) = snapshot
-                .into_iter()
-                .next()
-                .expect("mapped todo element should exist");
+        assert_eq!(
+            settled_json(&root_object, "first", json!([1.0])),
+            json!([1.0])
+        );
+        assert_eq!(
+            settled_json(&root_object, "rest", json!([2.0, 3.0])),
+            json!([2.0, 3.0])
+        );
+        assert_eq!(
+            settled_json(&root_object, "take_negative", json!([])),
+            json!([])
+        );
+        assert_eq!(
+            settled_json(&root_object, "drop_text", json!([1.0, 2.0, 3.0])),
+            json!([1.0, 2.0, 3.0])
+        );
+        let clamped = json!({
+            "take_more": [1.0, 2.0, 3.0],
+            "take_text": [],
+            "drop_more": [],
+            "drop_negative": [1.0, 2.0, 3.0]
+        });
+        assert_eq!(
+            settled_json(&root_object, "clamped", clamped.clone()),
+            clamped
+        );
 
-            eprintln!("test: reading first mapped item");
-            let first_item_value = first_item_actor
-                .current_value()
-                .await
-                .expect("mapped todo item should have a current value");
-            let Value::TaggedObject(tagged, _) = first_item_value else {
-                panic!("mapped todo item should be a tagged element object");
-            };
+        signal("add");
+        assert_eq!(
+            settled_json(&root_object, "rest", json!([2.0, 3.0, 4.0])),
+            json!([2.0, 3.0, 4.0])
+        );
+        signal("grow");
+        assert_eq!(
+            settled_json(&root_object, "first", json!([1.0, 2.0])),
+            json!([1.0, 2.0])
+        );
+        assert_eq!(
+            settled_json(&root_object, "rest", json!([3.0, 4.0])),
+            json!([3.0, 4.0])
+        );
+    }
 
-            let settings = tagged
-                .variable("settings")
-                .expect("mapped todo item should have settings");
-            eprintln!("test: reading settings");
-            let settings_value = settings
-                .value_actor()
-                .current_value()
-                .await
-                .expect("settings should have current value");
-            let Value::Object(settings_obj, _) = settings_value else {
-                panic!("settings should be an object");
-            };
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn list_find_follows_its_external_dependency_and_the_found_item() {
+        let (root_object, construct_context, _scope_guard) = evaluate_program(list_find_source());
+        let signal = |name: &str| {
+            let sender = root_object.expect_variable(name).expect_link_value_sender();
+            block_on(send_link_signal(
+                construct_context.clone(),
+                sender,
+                json!({"signal": true}),
+                "test.signal",
+            ));
+        };
 
-            let items = settings_obj
-                .variable("items")
-                .expect("stripe settings should contain items");
-            eprintln!("test: reading items");
-            let items_value = items
-                .value_actor()
-                .current_value()
-                .await
-                .expect("items should have current value");
-            let Value::List(items_list, _) = items_value else {
-                panic!("items should be a list");
-            };
-            let item_snapshot = items_list.snapshot().await;
-            assert_eq!(item_snapshot.len(), 2, "expected checkbox + label items");
+        assert_eq!(first_json(&root_object, "first_large"), json!(5.0));
+        assert_eq!(
+            first_json(&root_object, "no_match"),
+            json!({"_tag": "None"})
+        );
+        assert_eq!(
+            settled_json(&root_object, "selected_name", json!("Apple")),
+            json!("Apple")
+        );
 
-            let (_checkbox_pid, checkbox_actor) = &item_snapshot[0];
-            eprintln!("test: reading checkbox item");
-            let checkbox_value = checkbox_actor
-                .current_value()
-                .await
-                .expect("checkbox item should have current value");
-            let Value::TaggedObject(checkbox_tagged, _) = checkbox_value else {
-                panic!("first item should be checkbox tagged object");
-            };
-            let checkbox_element = checkbox_tagged
-                .variable("element")
-                .expect("checkbox should have element object");
-            eprintln!("test: reading checkbox element");
-            let checkbox_element_value = checkbox_element
-                .value_actor()
-                .current_value()
-                .await
-                .expect("checkbox element should have current value");
-            let Value::Object(checkbox_element_obj, _) = checkbox_element_value else {
-                panic!("checkbox element should be object");
-            };
-            let checkbox_event = checkbox_element_obj
-                .variable("event")
-                .expect("checkbox element should have event");
-            eprintln!("test: reading checkbox event");
-            let checkbox_event_value = checkbox_event
-                .value_actor()
-                .current_value()
-                .await
-                .expect("checkbox event should have current value");
-            let Value::Object(checkbox_event_obj, _) = checkbox_event_value else {
-                panic!("checkbox event should be object");
-            };
-            assert!(
-                checkbox_event_obj
-                    .variable("click")
-                    .and_then(|var| var.link_value_sender())
-                    .is_some(),
-                "checkbox click LINK sender should survive mapped nested link target"
-            );
+        signal("next");
+        assert_eq!(
+            settled_json(&root_object, "selected_name", json!("Banana")),
+            json!("Banana")
+        );
+        signal("rename");
+        assert_eq!(
+            settled_json(&root_object, "selected_name", json!("Blueberry")),
+            json!("Blueberry")
+        );
+        signal("next");
+        assert_eq!(
+            settled_json(&root_object, "selected_name", json!("none")),
+            json!("none")
+        );
 
-            let (_label_pid, label_actor) = &item_snapshot[1];
-            eprintln!("test: reading label item");
-            let label_value = label_actor
-                .current_value()
-                .await
-                .expect("label item should have current value");
-            let Value::TaggedObject(label_tagged, _) = label_value else {
-                panic!("second item should be label tagged object");
-            };
-            let label_element = label_tagged
-                .variable("element")
-                .expect("label should have element object");
-            eprintln!("test: reading label element");
-            let label_element_value = label_element
-                .value_actor()
-                .current_value()
-                .await
-                .expect("label element should have current value");
-            let Value::Object(label_element_obj, _) = label_element_value else {
-                panic!("label element should be object");
-            };
-            let label_event = label_element_obj
-                .variable("event")
-                .expect("label element should have event");
-            eprintln!("test: reading label event");
-            let label_event_value = label_event
-                .value_actor()
-                .current_value()
-                .await
-                .expect("label event should have current value");
-            let Value::Object(label_event_obj, _) = label_event_value else {
-                panic!("label event should be object");
-            };
-            assert!(
-                label_event_obj
-                    .variable("double_click")
-                    .and_then(|var| var.link_value_sender())
-                    .is_some(),
-                "label double_click LINK sender should survive mapped nested link target"
-            );
-        });
+        assert_eq!(
+            settled_json(&root_object, "done_title", json!("none")),
+            json!("none")
+        );
+        signal("finish");
+        assert_eq!(
+            settled_json(&root_object, "done_title", json!("Write")),
+            json!("Write")
+        );
+
+        let none = json!({"_tag": "None"});
+        assert_eq!(settled_json(&root_object, "large", none.clone()), none);
+        signal("add");
+        assert_eq!(settled_json(&root_object, "large", json!(7.0)), json!(7.0));
+        signal("remove_large");
+        assert_eq!(settled_json(&root_object, "large", none.clone()), none);
+    }
+
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn list_flatten_follows_the_outer_and_inner_lists() {
+        let (root_object, construct_context, _scope_guard) =
+            evaluate_program(list_flatten_source());
+        let signal = |name: &str| {
+            let sender = root_object.expect_variable(name).expect_link_value_sender();
+            block_on(send_link_signal(
+                construct_context.clone(),
+                sender,
+                json!({"signal": true}),
+                "test.signal",
+            ));
+        };
+
+        assert_eq!(
+            settled_json(&root_object, "flat", json!([1.0, 2.0, 10.0, 99.0])),
+            json!([1.0, 2.0, 10.0, 99.0])
+        );
+        assert_eq!(
+            settled_json(&root_object, "one_level", json!([[1.0], 2.0])),
+            json!([[1.0], 2.0])
+        );
+
+        signal("add");
+        assert_eq!(
+            settled_json(&root_object, "flat", json!([1.0, 2.0, 10.0, 11.0, 99.0])),
+            json!([1.0, 2.0, 10.0, 11.0, 99.0])
+        );
+        signal("toggle");
+        assert_eq!(
+            settled_json(&root_object, "flat", json!([10.0, 11.0, 99.0])),
+            json!([10.0, 11.0, 99.0])
+        );
+        signal("toggle");
+        assert_eq!(
+            settled_json(&root_object, "flat", json!([1.0, 2.0, 10.0, 11.0, 99.0])),
+            json!([1.0, 2.0, 10.0, 11.0, 99.0])
+        );
+
+        assert_eq!(
+            settled_json(&root_object, "grown", json!([1.0, 2.0])),
+            json!([1.0, 2.0])
+        );
+        signal("add_section");
+        assert_eq!(
+            settled_json(&root_object, "grown", json!([1.0, 2.0, 3.0, 4.0])),
+            json!([1.0, 2.0, 3.0, 4.0])
+        );
     }
 
     #[test]
-    fn mapped_todo_item_link_target_receives_nested_event_link_senders() {
-        let source = mapped_todo_item_event_link_source();
-        let (root_object, _construct_context, _scope_guard) = evaluate_program(source);
-        let elements = root_object.expect_variable("elements");
-        let todos = root_object.expect_variable("todos");
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn list_insert_and_move_reorder_and_follow_the_source() {
+        let (root_object, construct_context, _scope_guard) =
+            evaluate_program(list_insert_move_source());
+        let signal = |name: &str| {
+            let sender = root_object.expect_variable(name).expect_link_value_sender();
+            block_on(send_link_signal(
+                construct_context.clone(),
+                sender,
+                json!({"signal": true}),
+                "test.signal",
+            ));
+        };
 
-        block_on(async move {
-            eprintln!("target-test: forcing elements evaluation");
-            let _elements_value = elements
-                .value_actor()
-                .current_value()
-                .await
-                .expect("elements should have a current value");
+        assert_eq!(
+            settled_json(&root_object, "items", json!([1.0, 2.0, 3.0])),
+            json!([1.0, 2.0, 3.0])
+        );
 
-            eprintln!("target-test: reading todos");
-            let todos_value = todos
-                .value_actor()
-                .current_value()
-                .await
-                .expect("todos should have a current value");
-            let Value::List(todos_list, _) = todos_value else {
-                panic!("todos should evaluate to a list");
-            };
+        signal("add");
+        assert_eq!(
+            settled_json(&root_object, "items", json!([0.0, 1.0, 2.0, 3.0])),
+            json!([0.0, 1.0, 2.0, 3.0])
+        );
+        signal("down");
+        assert_eq!(
+            settled_json(&root_object, "items", json!([1.0, 2.0, 3.0, 0.0])),
+            json!([1.0, 2.0, 3.0, 0.0])
+        );
+        assert_eq!(
+            settled_json(&root_object, "large", json!([2.0, 3.0])),
+            json!([2.0, 3.0])
+        );
+        signal("up");
+        assert_eq!(
+            settled_json(&root_object, "items", json!([0.0, 1.0, 2.0, 3.0])),
+            json!([0.0, 1.0, 2.0, 3.0])
+        );
+        signal("append");
+        assert_eq!(
+            settled_json(&root_object, "items", json!([0.0, 1.0, 2.0, 3.0, 4.0])),
+            json!([0.0, 1.0, 2.0, 3.0, 4.0])
+        );
+        signal("down");
+        assert_eq!(
+            settled_json(&root_object, "items", json!([1.0, 2.0, 3.0, 4.0, 0.0])),
+            json!([1.0, 2.0, 3.0, 4.0, 0.0])
+        );
 
-            let todo_snapshot = todos_list.snapshot().await;
-            let (_todo_pid, todo_actor) = todo_snapshot
-                .into_iter()
-                .next()
-                .expect("first todo should exist");
+        signal("insert_middle");
+        assert_eq!(
+            settled_json(&root_object, "middle", json!([1.0, 9.0, 2.0, 3.0])),
+            json!([1.0, 9.0, 2.0, 3.0])
+        );
 
-            eprintln!("target-test: reading first todo object");
-            let todo_value = todo_actor
-                .current_value()
-                .await
-                .expect("todo item should have a current value");
-            let Value::Object(todo_obj, _) = todo_value else {
-                panic!("todo item should be an object");
-            };
+        // Out-of-range indices and positions are clamped to the list
+        signal("add_first");
+        signal("add_last");
+        assert_eq!(
+            settled_json(&root_object, "edges", json!([0.0, 1.0, 2.0, 9.0])),
+            json!([0.0, 1.0, 2.0, 9.0])
+        );
+        signal("to_end");
+        assert_eq!(
+            settled_json(&root_object, "clamped", json!([2.0, 3.0, 1.0])),
+            json!([2.0, 3.0, 1.0])
+        );
 
-            let todo_elements = todo_obj
-                .variable("todo_elements")
-                .expect("todo should have todo_elements");
-            eprintln!("target-test: reading todo_elements");
-            let todo_elements_value = todo_elements
-                .value_actor()
-                .current_value()
-                .await
-                .expect("todo_elements should have current value");
-            let Value::Object(todo_elements_obj, _) = todo_elements_value else {
-                panic!("todo_elements should be an object");
-            };
+        signal("forward");
+        assert_eq!(
+            settled_json(&root_object, "shuffled", json!([2.0, 3.0, 1.0, 4.0])),
+            json!([2.0, 3.0, 1.0, 4.0])
+        );
+        signal("back");
+        assert_eq!(
+            settled_json(&root_object, "shuffled", json!([4.0, 2.0, 3.0, 1.0])),
+            json!([4.0, 2.0, 3.0, 1.0])
+        );
 
-            let todo_checkbox = todo_elements_obj
-                .variable("todo_checkbox")
-                .expect("todo_elements should contain todo_checkbox");
-            eprintln!("target-test: reading linked todo_checkbox");
-            let todo_checkbox_value = todo_checkbox
-                .value_actor()
-                .current_value()
-                .await
-                .expect("linked todo_checkbox should have current value");
-            let Value::TaggedObject(todo_checkbox_tagged, _) = todo_checkbox_value else {
-                panic!("linked todo_checkbox should be a tagged element object");
-            };
-            let todo_checkbox_element = todo_checkbox_tagged
-                .variable("element")
-                .expect("linked todo_checkbox should have element");
-            let todo_checkbox_element_value = todo_checkbox_element
-                .value_actor()
-                .current_value()
-                .await
-                .expect("linked todo_checkbox element should have current value");
-            let Value::Object(todo_checkbox_element_obj, _) = todo_checkbox_element_value else {
-                panic!("linked todo_checkbox element should be object");
-            };
-            let todo_checkbox_event = todo_checkbox_element_obj
-                .variable("event")
-                .expect("linked todo_checkbox should have event");
-            let todo_checkbox_event_value = todo_checkbox_event
-                .value_actor()
-                .current_value()
-                .await
-                .expect("linked todo_checkbox event should have current value");
-            let Value::Object(todo_checkbox_event_obj, _) = todo_checkbox_event_value else {
-                panic!("linked todo_checkbox event should be object");
-            };
-            assert!(
-                todo_checkbox_event_obj
-                    .variable("click")
-                    .and_then(|var| var.link_value_sender())
-                    .is_some(),
-                "linked todo_checkbox click LINK sender should survive link target forwarding"
-            );
+        // A moved item keeps its HOLD state
+        signal("bump");
+        signal("move_first_to_end");
+        signal("bump");
+        let counters = json!([{"name": "b", "count": 2.0}, {"name": "a", "count": 2.0}]);
+        assert_eq!(
+            settled_json(&root_object, "counters", counters.clone()),
+            counters
+        );
+    }
 
-            let todo_title = todo_elements_obj
-                .variable("todo_title_element")
-                .expect("todo_elements should contain todo_title_element");
-            eprintln!("target-test: reading linked todo_title_element");
-            let todo_title_value = todo_title
-                .value_actor()
-                .current_value()
-                .await
-                .expect("linked todo_title_element should have current value");
-            let Value::TaggedObject(todo_title_tagged, _) = todo_title_value else {
-                panic!("linked todo_title_element should be a tagged element object");
-            };
-            let todo_title_element = todo_title_tagged
-                .variable("element")
-                .expect("linked todo_title_element should have element");
-            let todo_title_element_value = todo_title_element
-                .value_actor()
-                .current_value()
-                .await
-                .expect("linked todo_title_element element should have current value");
-            let Value::Object(todo_title_element_obj, _) = todo_title_element_value else {
-                panic!("linked todo_title_element element should be object");
-            };
-            let todo_title_event = todo_title_element_obj
-                .variable("event")
-                .expect("linked todo_title_element should have event");
-            let todo_title_event_value = todo_title_event
-                .value_actor()
-                .current_value()
-                .await
-                .expect("linked todo_title_element event should have current value");
-            let Value::Object(todo_title_event_obj, _) = todo_title_event_value else {
-                panic!("linked todo_title_element event should be object");
-            };
-            assert!(
-                todo_title_event_obj
-                    .variable("double_click")
-                    .and_then(|var| var.link_value_sender())
-                    .is_some(),
-                "linked todo_title_element double_click LINK sender should survive link target forwarding"
-            );
-        });
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn list_update_replaces_items_by_index_and_by_predicate() {
+        let (root_object, construct_context, _scope_guard) = evaluate_program(list_update_source());
+        let signal = |name: &str| {
+            let sender = root_object.expect_variable(name).expect_link_value_sender();
+            block_on(send_link_signal(
+                construct_context.clone(),
+                sender,
+                json!({"signal": true}),
+                "test.signal",
+            ));
+        };
+
+        assert_eq!(
+            settled_json(&root_object, "items", json!([1.0, 2.0, 3.0])),
+            json!([1.0, 2.0, 3.0])
+        );
+        signal("mark");
+        assert_eq!(
+            settled_json(&root_object, "items", json!([1.0, 20.0, 3.0])),
+            json!([1.0, 20.0, 3.0])
+        );
+        assert_eq!(
+            settled_json(&root_object, "large", json!([20.0, 3.0])),
+            json!([20.0, 3.0])
+        );
+        assert_eq!(
+            settled_json(&root_object, "doubled", json!([2.0, 40.0, 6.0])),
+            json!([2.0, 40.0, 6.0])
+        );
+        assert_eq!(
+            settled_json(&root_object, "total", json!(24.0)),
+            json!(24.0)
+        );
+        assert_eq!(first_json(&root_object, "outside"), json!(["A", "B"]));
+
+        assert_eq!(settled_json(&root_object, "unread", json!(2.0)), json!(2.0));
+        signal("mark_all");
+        let all_read = json!([
+            {"read": true, "title": "Hi"},
+            {"read": true, "title": "News"},
+            {"read": true, "title": "Bye"},
+        ]);
+        assert_eq!(
+            settled_json(&root_object, "messages", all_read.clone()),
+            all_read
+        );
+        assert_eq!(settled_json(&root_object, "unread", json!(0.0)), json!(0.0));
+
+        signal("press");
+        signal("press");
+        signal("reset_first");
+        assert_eq!(
+            settled_json(
+                &root_object,
+                "counters",
+                json!([{"count": 0.0}, {"count": 2.0}])
+            ),
+            json!([{"count": 0.0}, {"count": 2.0}])
+        );
+        signal("press");
+        assert_eq!(
+            settled_json(
+                &root_object,
+                "counters",
+                json!([{"count": 1.0}, {"count": 3.0}])
+            ),
+            json!([{"count": 1.0}, {"count": 3.0}])
+        );
     }
 
     #[test]
-    #[ignore = "requires browser-side actors runtime; host lib tests still touch js-sys statics"]
-    fn crud_linked_row_preserves_press_sender() {
-        let source = read_example_source("../../playground/frontend/src/examples/crud/crud.bn");
-        let (root_object, _construct_context, _scope_guard) = evaluate_program(&source);
-        let document = root_object.expect_variable("document");
-        let store = root_object.expect_variable("store");
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn list_chunk_groups_items_and_follows_membership() {
+        let (root_object, construct_context, _scope_guard) = evaluate_program(list_chunk_source());
+        let signal = |name: &str| {
+            let sender = root_object.expect_variable(name).expect_link_value_sender();
+            block_on(send_link_signal(
+                construct_context.clone(),
+                sender,
+                json!({"signal": true}),
+                "test.signal",
+            ));
+        };
 
-        block_on(async move {
-            let _document_value = document
-                .value_actor()
-                .current_value()
-                .await
-                .expect("document should have a current value");
+        assert_eq!(
+            settled_json(&root_object, "rows", json!([[1.0, 2.0], [3.0]])),
+            json!([[1.0, 2.0], [3.0]])
+        );
+        assert_eq!(
+            settled_json(&root_object, "scaled", json!([[10.0, 20.0], [30.0]])),
+            json!([[10.0, 20.0], [30.0]])
+        );
+        assert_eq!(
+            settled_json(
+                &root_object,
+                "range_rows",
+                json!([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0]])
+            ),
+            json!([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0]])
+        );
+        assert_eq!(settled_json(&root_object, "empty", json!([])), json!([]));
+        assert_eq!(
+            settled_json(&root_object, "exact", json!([[1.0, 2.0]])),
+            json!([[1.0, 2.0]])
+        );
+        assert_eq!(
+            settled_json(&root_object, "larger_than_list", json!([[1.0, 2.0]])),
+            json!([[1.0, 2.0]])
+        );
 
-            let store_value = store
-                .value_actor()
-                .current_value()
-                .await
-                .expect("store should have a current value");
-            let Value::Object(store_obj, _) = store_value else {
-                panic!("store should be an object");
-            };
+        signal("add");
+        assert_eq!(
+            settled_json(&root_object, "rows", json!([[1.0, 2.0], [3.0, 4.0]])),
+            json!([[1.0, 2.0], [3.0, 4.0]])
+        );
+        assert_eq!(
+            settled_json(&root_object, "scaled", json!([[10.0, 20.0], [30.0, 40.0]])),
+            json!([[10.0, 20.0], [30.0, 40.0]])
+        );
+        signal("add_five");
+        assert_eq!(
+            settled_json(&root_object, "rows", json!([[1.0, 2.0], [3.0, 4.0], [5.0]])),
+            json!([[1.0, 2.0], [3.0, 4.0], [5.0]])
+        );
+        signal("remove_first");
+        assert_eq!(
+            settled_json(&root_object, "rows", json!([[2.0, 3.0], [4.0, 5.0]])),
+            json!([[2.0, 3.0], [4.0, 5.0]])
+        );
+    }
 
-            let people = store_obj
-                .variable("people")
-                .expect("store should contain people");
-            let people_value = people
-                .value_actor()
-                .current_value()
-                .await
-                .expect("people should have a current value");
-            let Value::List(people_list, _) = people_value else {
-                panic!("people should evaluate to a list");
-            };
-            let people_snapshot = people_list.snapshot().await;
-            let (_tansen_pid, tansen_actor) = people_snapshot
-                .get(2)
-                .cloned()
-                .expect("third CRUD person should exist");
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn number_to_text_leaves_interpolation_alone_and_follows_its_inputs() {
+        let (root_object, construct_context, _scope_guard) =
+            evaluate_program(number_to_text_source());
 
-            let tansen_value = tansen_actor
-                .current_value()
-                .await
-                .expect("Tansen row object should have a current value");
-            let Value::Object(tansen_obj, _) = tansen_value else {
-                panic!("CRUD person should be an object");
-            };
+        assert_eq!(
+            settled_json(
+                &root_object,
+                "interpolated",
+                json!("0.30000000000000004 0.30")
+            ),
+            json!("0.30000000000000004 0.30")
+        );
 
-            let person_elements = tansen_obj
-                .variable("person_elements")
-                .expect("CRUD person should have person_elements");
-            let person_elements_value = person_elements
-                .value_actor()
-                .current_value()
-                .await
-                .expect("person_elements should have current value");
-            let Value::Object(person_elements_obj, _) = person_elements_value else {
-                panic!("person_elements should be object");
-            };
+        assert_eq!(
+            settled_json(&root_object, "formatted", json!("1000")),
+            json!("1000")
+        );
+        press(&root_object, &construct_context, "add");
+        assert_eq!(
+            settled_json(&root_object, "formatted", json!("1234")),
+            json!("1234")
+        );
+        press(&root_object, &construct_context, "more_decimals");
+        assert_eq!(
+            settled_json(&root_object, "formatted", json!("1234.50")),
+            json!("1234.50")
+        );
+        press(&root_object, &construct_context, "group");
+        assert_eq!(
+            settled_json(&root_object, "formatted", json!("1,234.50")),
+            json!("1,234.50")
+        );
+    }
 
-            let row = person_elements_obj
-                .variable("row")
-                .expect("person_elements should contain row");
-            let row_value = row
-                .value_actor()
-                .current_value()
-                .await
-                .expect("linked row should have current value after document evaluation");
-            let Value::TaggedObject(row_tagged, _) = row_value else {
-                panic!("linked row should be tagged element object");
-            };
-            let row_element = row_tagged
-                .variable("element")
-                .expect("linked row should have element");
-            let row_element_value = row_element
-                .value_actor()
-                .current_value()
-                .await
-                .expect("linked row element should have current value");
-            let Value::Object(row_element_obj, _) = row_element_value else {
-                panic!("linked row element should be object");
-            };
-            let row_event = row_element_obj
-                .variable("event")
-                .expect("linked row should have event");
-            let row_event_value = row_event
-                .value_actor()
-                .current_value()
-                .await
-                .expect("linked row event should have current value");
-            let Value::Object(row_event_obj, _) = row_event_value else {
-                panic!("linked row event should be object");
-            };
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn number_parse_reports_the_unread_text_and_feeds_arithmetic() {
+        let (root_object, construct_context, _scope_guard) =
+            evaluate_program(number_parse_source());
 
-            assert!(
-                row_event_obj
-                    .variable("press")
-                    .and_then(|var| var.link_value_sender())
-                    .is_some(),
-                "linked CRUD row press LINK sender should survive nested link target forwarding"
-            );
-        });
+        assert_eq!(
+            settled_json(&root_object, "letters", json!("not a number: twelve")),
+            json!("not a number: twelve")
+        );
+        assert_eq!(
+            settled_json(&root_object, "exponent", json!("not a number: 1e3")),
+            json!("not a number: 1e3")
+        );
+        assert_eq!(
+            settled_json(&root_object, "empty", json!("not a number: ")),
+            json!("not a number: ")
+        );
+        assert_eq!(
+            settled_json(&root_object, "number", json!("number 7")),
+            json!("number 7")
+        );
+
+        let typed = root_object
+            .expect_variable("typed")
+            .expect_link_value_sender();
+        let type_quantity = |text: &str| {
+            block_on(send_link_signal(
+                construct_context.clone(),
+                typed.clone(),
+                json!(text),
+                "test.typed",
+            ));
+        };
+        assert_eq!(settled_json(&root_object, "price", json!(4.0)), json!(4.0));
+        type_quantity("3");
+        assert_eq!(
+            settled_json(&root_object, "price", json!(12.0)),
+            json!(12.0)
+        );
+        type_quantity("3x");
+        assert_eq!(settled_json(&root_object, "price", json!(0.0)), json!(0.0));
+        type_quantity("2.5");
+        assert_eq!(
+            settled_json(&root_object, "price", json!(10.0)),
+            json!(10.0)
+        );
     }
 
     #[test]
-    #[ignore = "requires browser-side actors runtime; host lib tests still touch js-sys statics"]
-    fn crud_mapped_row_press_updates_selected_id() {
-        let source = read_example_source("../../playground/frontend/src/examples/crud/crud.bn");
-        let (root_object, construct_context, _scope_guard) = evaluate_program(&source);
-        let document = root_object.expect_variable("document");
-        let store = root_object.expect_variable("store");
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn shopping_total_formats_the_floating_point_sum() {
+        let (root_object, construct_context, _scope_guard) =
+            evaluate_program(shopping_total_source());
 
-        block_on(async move {
-            let _document_value = document
-                .value_actor()
-                .current_value()
-                .await
-                .expect("document should have a current value");
+        assert_eq!(
+            settled_json(&root_object, "total_text", json!("Total: 9.90")),
+            json!("Total: 9.90")
+        );
 
-            let store_value = store
-                .value_actor()
-                .current_value()
-                .await
-                .expect("store should have a current value");
-            let Value::Object(store_obj, _) = store_value else {
-                panic!("store should be an object");
-            };
+        let sender = root_object
+            .expect_variable("add")
+            .expect_link_value_sender();
+        block_on(send_link_signal(
+            construct_context,
+            sender,
+            json!({"add": true}),
+            "test.add",
+        ));
 
-            let people = store_obj
-                .variable("people")
-                .expect("store should contain people");
-            let selected_id = store_obj
-                .variable("selected_id")
-                .expect("store should contain selected_id");
+        assert_eq!(
+            settled_json(&root_object, "total_text", json!("Total: 12.50")),
+            json!("Total: 12.50")
+        );
+    }
 
-            let people_value = people
-                .value_actor()
-                .current_value()
-                .await
-                .expect("people should have a current value");
-            let Value::List(people_list, _) = people_value else {
-                panic!("people should evaluate to a list");
-            };
-            let people_snapshot = people_list.snapshot().await;
-            let (_tansen_pid, tansen_actor) = people_snapshot
-                .get(2)
-                .cloned()
-                .expect("third CRUD person should exist");
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn character_counter_posts_the_trimmed_draft_and_clears_it() {
+        let (root_object, construct_context, _scope_guard) =
+            evaluate_program(character_counter_source());
 
-            let tansen_value = tansen_actor
-                .current_value()
-                .await
-                .expect("Tansen row object should have a current value");
-            let Value::Object(tansen_obj, _) = tansen_value else {
-                panic!("CRUD person should be an object");
-            };
+        assert_eq!(settled_json(&root_object, "length", json!(0.0)), json!(0.0));
+        assert_eq!(
+            settled_json(&root_object, "counter", json!("0/280")),
+            json!("0/280")
+        );
+        assert_eq!(
+            settled_json(&root_object, "post_disabled", json!({"_tag": "True"})),
+            json!({"_tag": "True"})
+        );
 
-            let tansen_id = tansen_obj
-                .variable("id")
-                .expect("CRUD person should have id")
-                .value_actor()
-                .current_value()
-                .await
-                .expect("CRUD person id should have current value")
-                .to_json()
-                .await;
+        let typed = root_object
+            .expect_variable("typed")
+            .expect_link_value_sender();
+        block_on(send_link_signal(
+            construct_context.clone(),
+            typed.clone(),
+            json!(" Hi 👋 "),
+            "test.typed",
+        ));
+        assert_eq!(settled_json(&root_object, "length", json!(6.0)), json!(6.0));
+        assert_eq!(
+            settled_json(&root_object, "counter", json!("6/280")),
+            json!("6/280")
+        );
+        assert_eq!(
+            settled_json(&root_object, "post_disabled", json!({"_tag": "False"})),
+            json!({"_tag": "False"})
+        );
 
-            let person_elements_value = tansen_obj
-                .variable("person_elements")
-                .expect("CRUD person should have person_elements")
-                .value_actor()
-                .current_value()
-                .await
-                .expect("person_elements should have current value");
-            let Value::Object(person_elements_obj, _) = person_elements_value else {
-                panic!("person_elements should be object");
-            };
-            let row_var = person_elements_obj
-                .variable("row")
-                .expect("person_elements should contain row");
-            let row_value = row_var
-                .value_actor()
-                .current_value()
-                .await
-                .expect("linked row should have current value after document evaluation");
-            let Value::TaggedObject(row_tagged, _) = row_value else {
-                panic!("linked row should be tagged element object");
-            };
-            let row_element = row_tagged
-                .variable("element")
-                .expect("linked row should have element");
-            let row_element_value = row_element
-                .value_actor()
-                .current_value()
-                .await
-                .expect("linked row element should have current value");
-            let Value::Object(row_element_obj, _) = row_element_value else {
-                panic!("linked row element should be object");
-            };
-            let row_event = row_element_obj
-                .variable("event")
-                .expect("linked row should have event");
-            let row_event_value = row_event
-                .value_actor()
-                .current_value()
-                .await
-                .expect("linked row event should have current value");
-            let Value::Object(row_event_obj, _) = row_event_value else {
-                panic!("linked row event should be object");
-            };
-            let press_sender = row_event_obj
-                .variable("press")
-                .and_then(|var| var.link_value_sender())
-                .expect("linked row press sender should exist");
+        let post = root_object
+            .expect_variable("post")
+            .expect_link_value_sender();
+        block_on(send_link_signal(
+            construct_context.clone(),
+            post,
+            json!({"post": true}),
+            "test.post",
+        ));
+        assert_eq!(
+            settled_json(&root_object, "posts", json!(["Hi 👋"])),
+            json!(["Hi 👋"])
+        );
+        assert_eq!(settled_json(&root_object, "length", json!(0.0)), json!(0.0));
 
-            let initial_selected_id = selected_id
-                .value_actor()
-                .current_value()
-                .await
-                .expect("selected_id should have initial value")
-                .to_json()
-                .await;
+        block_on(send_link_signal(
+            construct_context,
+            typed,
+            json!("   "),
+            "test.typed",
+        ));
+        assert_eq!(
+            settled_json(&root_object, "counter", json!("3/280")),
+            json!("3/280")
+        );
+        assert_eq!(
+            settled_json(&root_object, "post_disabled", json!({"_tag": "True"})),
+            json!({"_tag": "True"})
+        );
+    }
+
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn math_min_max_follow_both_operands_and_skip_values_that_are_not_numbers() {
+        let (root_object, construct_context, _scope_guard) =
+            evaluate_program(math_min_max_source());
+        let send = |name: &'static str, payload: serde_json::Value| {
+            let sender = root_object.expect_variable(name).expect_link_value_sender();
+            block_on(send_link_signal(
+                construct_context.clone(),
+                sender,
+                payload,
+                name,
+            ));
+        };
 
-            send_link_signal(
-                construct_context,
-                press_sender,
-                serde_json::json!({}),
-                "test.crud.row_press",
-            )
-            .await;
+        assert_eq!(
+            settled_json(&root_object, "clamped", json!(0.0)),
+            json!(0.0)
+        );
+        send("scroll", json!(60));
+        assert_eq!(
+            settled_json(&root_object, "clamped", json!(60.0)),
+            json!(60.0)
+        );
+        send("scroll", json!(60));
+        assert_eq!(
+            settled_json(&root_object, "clamped", json!(100.0)),
+            json!(100.0)
+        );
+        send("resize", json!(150));
+        assert_eq!(
+            settled_json(&root_object, "clamped", json!(120.0)),
+            json!(120.0)
+        );
+        send("scroll", json!(-200));
+        assert_eq!(
+            settled_json(&root_object, "clamped", json!(0.0)),
+            json!(0.0)
+        );
 
-            let mut updated_selected_id = selected_id
-                .value_actor()
-                .current_value()
-                .await
-                .expect("selected_id should still have current value")
-                .to_json()
-                .await;
-            for _ in 0..50 {
-                if updated_selected_id == tansen_id {
-                    break;
-                }
-                std::thread::sleep(Duration::from_millis(10));
-                updated_selected_id = selected_id
-                    .value_actor()
-                    .current_value()
-                    .await
-                    .expect("selected_id should still have current value")
-                    .to_json()
-                    .await;
-            }
+        assert_eq!(
+            settled_json(&root_object, "progress", json!(0.0)),
+            json!(0.0)
+        );
+        press(&root_object, &construct_context, "tick");
+        assert_eq!(
+            settled_json(&root_object, "progress", json!(40.0)),
+            json!(40.0)
+        );
+        press(&root_object, &construct_context, "tick");
+        press(&root_object, &construct_context, "tick");
+        assert_eq!(
+            settled_json(&root_object, "progress", json!(100.0)),
+            json!(100.0)
+        );
 
-            assert_eq!(initial_selected_id, serde_json::json!("None"));
-            assert_eq!(updated_selected_id, tansen_id);
-        });
+        assert_eq!(
+            settled_json(&root_object, "at_least_five", json!(10.0)),
+            json!(10.0)
+        );
+        send("change", json!("lots"));
+        assert_eq!(
+            settled_json(&root_object, "at_least_five", json!(10.0)),
+            json!(10.0)
+        );
+        send("change", json!(2));
+        assert_eq!(
+            settled_json(&root_object, "at_least_five", json!(5.0)),
+            json!(5.0)
+        );
     }
 
     #[test]
     #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
-    fn linked_checkbox_updates_sibling_hold_state() {
+    fn math_rounding_follows_its_input() {
         let (root_object, construct_context, _scope_guard) =
-            evaluate_program(linked_checkbox_updates_sibling_hold_source());
-        let checkbox = root_object.expect_variable("checkbox");
-        let todo = root_object.expect_variable("todo");
+            evaluate_program(math_rounding_source());
+        let add = |amount: f64| {
+            let sender = root_object
+                .expect_variable("add")
+                .expect_link_value_sender();
+            block_on(send_link_signal(
+                construct_context.clone(),
+                sender,
+                json!(amount),
+                "test.add",
+            ));
+        };
+        let rounded = |round: f64, floor: f64, ceil: f64| json!({"ceil": ceil, "floor": floor, "round": round});
 
-        let (initial_completed_json, updated_completed_json) = block_on(async move {
-            let checkbox_value = checkbox
-                .value_actor()
-                .current_value()
-                .await
-                .expect("checkbox should have a current value");
-            let Value::TaggedObject(checkbox_tagged, _) = checkbox_value else {
-                panic!("checkbox should be a tagged element object");
-            };
-            let checkbox_element = checkbox_tagged
-                .variable("element")
-                .expect("checkbox should have element object");
-            let checkbox_element_value = checkbox_element
-                .value_actor()
-                .current_value()
-                .await
-                .expect("checkbox element should have current value");
-            let Value::Object(checkbox_element_obj, _) = checkbox_element_value else {
-                panic!("checkbox element should be object");
-            };
-            let checkbox_event = checkbox_element_obj
-                .variable("event")
-                .expect("checkbox element should have event");
-            let checkbox_event_value = checkbox_event
-                .value_actor()
-                .current_value()
-                .await
-                .expect("checkbox event should have current value");
-            let Value::Object(checkbox_event_obj, _) = checkbox_event_value else {
-                panic!("checkbox event should be object");
-            };
-            let click_sender = checkbox_event_obj
-                .variable("click")
-                .and_then(|var| var.link_value_sender())
-                .expect("checkbox click sender should exist");
+        assert_eq!(
+            settled_json(&root_object, "rounded", rounded(0.0, 0.0, 0.0)),
+            rounded(0.0, 0.0, 0.0)
+        );
+        add(1.4);
+        assert_eq!(
+            settled_json(&root_object, "rounded", rounded(1.0, 1.0, 2.0)),
+            rounded(1.0, 1.0, 2.0)
+        );
+        add(0.2);
+        assert_eq!(
+            settled_json(&root_object, "rounded", rounded(2.0, 1.0, 2.0)),
+            rounded(2.0, 1.0, 2.0)
+        );
+    }
 
-            let todo_value = todo
-                .value_actor()
-                .current_value()
-                .await
-                .expect("todo should have current value");
-            let Value::Object(todo_obj, _) = todo_value else {
-                panic!("todo should be an object");
-            };
-            let completed = todo_obj
-                .variable("completed")
-                .expect("todo should contain completed");
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn math_abs_and_negate_if_follow_the_number_and_the_condition() {
+        let (root_object, construct_context, _scope_guard) =
+            evaluate_program(math_abs_negate_if_source());
+        let send = |name: &'static str, payload: serde_json::Value| {
+            let sender = root_object.expect_variable(name).expect_link_value_sender();
+            block_on(send_link_signal(
+                construct_context.clone(),
+                sender,
+                payload,
+                name,
+            ));
+        };
 
-            let initial_completed_json = completed
-                .value_actor()
-                .current_value()
-                .await
-                .expect("completed should have initial value")
-                .to_json()
-                .await;
+        assert_eq!(
+            settled_json(&root_object, "flipped", json!(3.0)),
+            json!(3.0)
+        );
+        press(&root_object, &construct_context, "flip");
+        assert_eq!(
+            settled_json(&root_object, "flipped", json!(-3.0)),
+            json!(-3.0)
+        );
+        press(&root_object, &construct_context, "flip");
+        assert_eq!(
+            settled_json(&root_object, "flipped", json!(3.0)),
+            json!(3.0)
+        );
 
-            send_link_signal(
-                construct_context,
-                click_sender,
-                serde_json::json!({}),
-                "test.checkbox.click",
-            )
-            .await;
+        assert_eq!(
+            settled_json(&root_object, "negated_amount", json!(-1.0)),
+            json!(-1.0)
+        );
+        send("add", json!(4));
+        assert_eq!(
+            settled_json(&root_object, "negated_amount", json!(-5.0)),
+            json!(-5.0)
+        );
 
-            let mut updated_completed_json = completed
-                .value_actor()
-                .current_value()
-                .await
-                .expect("completed should still have current value")
-                .to_json()
-                .await;
-            for _ in 0..50 {
-                if updated_completed_json == serde_json::json!(true) {
-                    break;
-                }
-                std::thread::sleep(Duration::from_millis(10));
-                updated_completed_json = completed
-                    .value_actor()
-                    .current_value()
-                    .await
-                    .expect("completed should still have current value")
-                    .to_json()
-                    .await;
+        for (current, delta, signed_back) in [
+            (None, "+0", 0.0),
+            (Some(13), "+3", 3.0),
+            (Some(7), "-3", -3.0),
+            (Some(10), "+0", 0.0),
+        ] {
+            if let Some(current) = current {
+                send("change", json!(current));
             }
-
-            (initial_completed_json, updated_completed_json)
-        });
-
-        assert_eq!(initial_completed_json, serde_json::json!(false));
-        assert_eq!(updated_completed_json, serde_json::json!(true));
+            assert_eq!(
+                settled_json(&root_object, "delta", json!(delta)),
+                json!(delta)
+            );
+            assert_eq!(
+                settled_json(&root_object, "signed_back", json!(signed_back)),
+                json!(signed_back)
+            );
+        }
     }
 
     #[test]
-    #[ignore = "requires browser-side actors runtime; host lib tests still touch js-sys statics"]
-    fn list_map_reacts_to_external_dependency_changes() {
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn math_pow_and_sqrt_follow_their_inputs_and_recover_after_a_math_error() {
         let (root_object, construct_context, _scope_guard) =
-            evaluate_program(list_map_external_dependency_source());
-        let toggle = root_object.expect_variable("toggle");
-        let mapped = root_object.expect_variable("mapped");
-        let sender = toggle.expect_link_value_sender();
+            evaluate_program(math_pow_sqrt_source());
+        let send = |name: &'static str, payload: serde_json::Value| {
+            let sender = root_object.expect_variable(name).expect_link_value_sender();
+            block_on(send_link_signal(
+                construct_context.clone(),
+                sender,
+                payload,
+                name,
+            ));
+        };
 
-        let (initial_json, updated_json) = block_on(async move {
-            let Value::List(list, _) = mapped
-                .value_actor()
-                .current_value()
-                .await
-                .expect("mapped should have current list value")
-            else {
-                panic!("mapped should evaluate to a list");
-            };
+        assert_eq!(
+            settled_json(&root_object, "negative", json!("no real root")),
+            json!("no real root")
+        );
+        assert_eq!(
+            settled_json(&root_object, "positive", json!("root 3")),
+            json!("root 3")
+        );
+        assert_eq!(
+            settled_json(&root_object, "balance", json!(1103.0)),
+            json!(1103.0)
+        );
 
-            let items_to_json = |items: Vec<crate::engine::ActorHandle>| async move {
-                let mut values = Vec::new();
-                for item_actor in items {
-                    let value = item_actor
-                        .current_value()
-                        .await
-                        .expect("mapped item should have current value")
-                        .to_json()
-                        .await;
-                    values.push(value);
-                }
-                serde_json::Value::Array(values)
-            };
+        assert_eq!(settled_json(&root_object, "power", json!(1.0)), json!(1.0));
+        press(&root_object, &construct_context, "grow");
+        assert_eq!(settled_json(&root_object, "power", json!(2.0)), json!(2.0));
+        send("years", json!(10));
+        assert_eq!(
+            settled_json(&root_object, "power", json!(1024.0)),
+            json!(1024.0)
+        );
+        send("years", json!(-2000));
+        assert_eq!(settled_json(&root_object, "power", json!(0.0)), json!(0.0));
 
-            let initial_snapshot = list.snapshot().await;
-            let initial_json = items_to_json(
-                initial_snapshot
-                    .iter()
-                    .map(|(_id, item_actor)| item_actor.clone())
-                    .collect(),
-            )
-            .await;
-            let current_items: Vec<crate::engine::ActorHandle> = initial_snapshot
-                .into_iter()
-                .map(|(_id, item_actor)| item_actor)
-                .collect();
-            send_link_signal(
-                construct_context,
+        assert_eq!(settled_json(&root_object, "root", json!(2.0)), json!(2.0));
+        send("change", json!(-1));
+        assert_eq!(
+            settled_json(&root_object, "root", json!({"_tag": "MathError"})),
+            json!({"_tag": "MathError"})
+        );
+        send("change", json!(81));
+        assert_eq!(settled_json(&root_object, "root", json!(9.0)), json!(9.0));
+    }
+
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn math_modulo_stripes_rows_and_recovers_from_division_by_zero() {
+        let (root_object, construct_context, _scope_guard) = evaluate_program(math_modulo_source());
+
+        assert_eq!(first_json(&root_object, "first"), json!({"_tag": "Odd"}));
+        assert_eq!(first_json(&root_object, "second"), json!({"_tag": "Even"}));
+        assert_eq!(first_json(&root_object, "third"), json!({"_tag": "Odd"}));
+
+        let change = |by: f64| {
+            let sender = root_object
+                .expect_variable("change")
+                .expect_link_value_sender();
+            block_on(send_link_signal(
+                construct_context.clone(),
+                sender,
+                json!(by),
+                "test.change",
+            ));
+        };
+        assert_eq!(
+            settled_json(&root_object, "remainder", json!(2.0)),
+            json!(2.0)
+        );
+        press(&root_object, &construct_context, "tick");
+        assert_eq!(
+            settled_json(&root_object, "remainder", json!(3.0)),
+            json!(3.0)
+        );
+        change(0.0);
+        assert_eq!(
+            settled_json(&root_object, "remainder", json!({"_tag": "MathError"})),
+            json!({"_tag": "MathError"})
+        );
+        change(5.0);
+        assert_eq!(
+            settled_json(&root_object, "remainder", json!(1.0)),
+            json!(1.0)
+        );
+    }
+
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn progress_percentage_rounds_and_stops_at_one_hundred() {
+        let (root_object, construct_context, _scope_guard) =
+            evaluate_program(progress_percentage_source());
+        let press = |name: &str| {
+            let sender = root_object.expect_variable(name).expect_link_value_sender();
+            block_on(send_link_signal(
+                construct_context.clone(),
                 sender,
-                json!({"toggle": true}),
-                "test.toggle",
-            )
-            .await;
+                json!({"press": true}),
+                "test.press",
+            ));
+        };
 
-            let mut updated_json = items_to_json(current_items.clone()).await;
-            for _ in 0..50 {
-                if updated_json == json!([11.0, 12.0, 13.0]) {
-                    break;
-                }
-                std::thread::sleep(Duration::from_millis(10));
-                updated_json = items_to_json(current_items.clone()).await;
-            }
+        assert_eq!(
+            settled_json(&root_object, "percentage", json!(0.0)),
+            json!(0.0)
+        );
+        press("complete");
+        assert_eq!(
+            settled_json(&root_object, "percentage", json!(33.0)),
+            json!(33.0)
+        );
+        press("complete");
+        assert_eq!(
+            settled_json(&root_object, "percentage", json!(67.0)),
+            json!(67.0)
+        );
+        press("complete");
+        press("complete");
+        assert_eq!(
+            settled_json(&root_object, "percentage", json!(100.0)),
+            json!(100.0)
+        );
+        assert_eq!(
+            settled_json(&root_object, "completed", json!(3.0)),
+            json!(3.0)
+        );
+        press("reset");
+        assert_eq!(
+            settled_json(&root_object, "percentage", json!(0.0)),
+            json!(0.0)
+        );
+    }
 
-            (initial_json, updated_json)
-        });
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn math_clamp_follows_all_three_inputs() {
+        let (root_object, construct_context, _scope_guard) = evaluate_program(math_clamp_source());
+        let send = |name: &'static str, number: f64| {
+            let sender = root_object.expect_variable(name).expect_link_value_sender();
+            block_on(send_link_signal(
+                construct_context.clone(),
+                sender,
+                json!(number),
+                name,
+            ));
+        };
 
-        assert_eq!(initial_json, json!([1.0, 2.0, 3.0]));
-        assert_eq!(updated_json, json!([11.0, 12.0, 13.0]));
+        assert_eq!(
+            settled_json(&root_object, "clamped", json!(5.0)),
+            json!(5.0)
+        );
+        for (name, number, expected) in [
+            ("change_value", 20.0, 10.0),
+            ("change_max", 15.0, 15.0),
+            ("change_min", 18.0, 18.0),
+            ("change_max", 16.0, 18.0),
+            ("change_max", 30.0, 20.0),
+        ] {
+            send(name, number);
+            assert_eq!(
+                settled_json(&root_object, "clamped", json!(expected)),
+                json!(expected),
+                "{name}: {number}"
+            );
+        }
     }
 
     #[test]
-    fn mapped_formula_dependency_updates_after_override_commit() {
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn math_average_and_count_follow_each_value_and_start_over_on_reset() {
         let (root_object, construct_context, _scope_guard) =
-            evaluate_program(cells_mapped_formula_dependency_source());
-        let edit_committed = root_object.expect_variable("edit_committed");
-        let formula_a1 = root_object.expect_variable("formula_a1");
-        let mapped = root_object.expect_variable("mapped");
-        let sender = edit_committed.expect_link_value_sender();
-
-        let (initial_formula_json, updated_formula_json, initial_mapped_json, updated_mapped_json) =
-            block_on(async move {
-                let Value::List(list, _) = mapped
-                    .value_actor()
-                    .current_value()
-                    .await
-                    .expect("mapped should have current list value")
-                else {
-                    panic!("mapped should evaluate to a list");
-                };
-
-                let items_to_json = |items: Vec<crate::engine::ActorHandle>| async move {
-                    let mut values = Vec::new();
-                    for item_actor in items {
-                        let value = item_actor
-                            .current_value()
-                            .await
-                            .expect("mapped item should have current value")
-                            .to_json()
-                            .await;
-                        values.push(value);
-                    }
-                    serde_json::Value::Array(values)
-                };
+            evaluate_program(math_average_count_source());
+        let send = |name: &'static str, payload: serde_json::Value| {
+            let sender = root_object.expect_variable(name).expect_link_value_sender();
+            block_on(send_link_signal(
+                construct_context.clone(),
+                sender,
+                payload,
+                name,
+            ));
+        };
+        let expect = |name: &str, expected: f64| {
+            assert_eq!(
+                settled_json(&root_object, name, json!(expected)),
+                json!(expected),
+                "{name}"
+            );
+        };
 
-                let initial_formula_json = formula_a1
-                    .value_actor()
-                    .current_value()
-                    .await
-                    .expect("formula_a1 should have current value")
-                    .to_json()
-                    .await;
+        send("event", json!({"event": true}));
+        expect("count", 1.0);
+        send("event", json!("text"));
+        expect("count", 2.0);
+        send("event", json!(7));
+        expect("count", 3.0);
+        expect("resettable_count", 3.0);
+
+        for (reading, average) in [
+            (json!(18), 18.0),
+            (json!(22), 20.0),
+            (json!("broken sensor"), 20.0),
+            (json!(20), 20.0),
+            (json!(24), 21.0),
+        ] {
+            send("reading", reading);
+            expect("average", average);
+            expect("resettable_average", average);
+        }
+
+        press(&root_object, &construct_context, "reset");
+        expect("resettable_average", 0.0);
+        expect("resettable_count", 0.0);
+        press(&root_object, &construct_context, "reset");
+        expect("resettable_count", 0.0);
+        send("event", json!({"event": true}));
+        expect("resettable_count", 1.0);
+        expect("count", 4.0);
+        send("reading", json!(1));
+        expect("resettable_average", 1.0);
+        send("reading", json!(2));
+        expect("resettable_average", 1.5);
+    }
 
-                let initial_snapshot = list.snapshot().await;
-                let current_items: Vec<crate::engine::ActorHandle> = initial_snapshot
-                    .iter()
-                    .map(|(_id, item_actor)| item_actor.clone())
-                    .collect();
-                let initial_mapped_json = items_to_json(current_items.clone()).await;
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn stream_min_and_max_emit_only_when_the_extreme_changes() {
+        let (root_object, construct_context, _scope_guard) =
+            evaluate_program(stream_min_max_source());
+        let send = |name: &'static str, payload: serde_json::Value| {
+            let sender = root_object.expect_variable(name).expect_link_value_sender();
+            block_on(send_link_signal(
+                construct_context.clone(),
+                sender,
+                payload,
+                name,
+            ));
+        };
+        let expect = |name: &str, expected: f64| {
+            assert_eq!(
+                settled_json(&root_object, name, json!(expected)),
+                json!(expected),
+                "{name}"
+            );
+        };
 
-                send_edit_committed(construct_context, sender, 1.0, 1.0, "7").await;
+        for (score, high, high_changes) in [
+            (json!(3), 3.0, 1.0),
+            (json!(2), 3.0, 1.0),
+            (json!("game over"), 3.0, 1.0),
+            (json!(3), 3.0, 1.0),
+            (json!(7), 7.0, 2.0),
+            (json!(-2), 7.0, 2.0),
+        ] {
+            send("score", score);
+            expect("high", high);
+            expect("high_changes", high_changes);
+        }
 
-                let mut updated_formula_json = formula_a1
-                    .value_actor()
-                    .current_value()
-                    .await
-                    .expect("formula_a1 should still have current value")
-                    .to_json()
-                    .await;
-                let mut updated_mapped_json = items_to_json(current_items.clone()).await;
+        for (temperature, low) in [(12.0, 12.0), (15.0, 12.0), (-4.5, -4.5)] {
+            send("temperature", json!(temperature));
+            expect("low", low);
+        }
 
-                for _ in 0..50 {
-                    if updated_formula_json == json!("7")
-                        && updated_mapped_json == json!(["7", "17"])
-                    {
-                        break;
-                    }
-                    std::thread::sleep(Duration::from_millis(10));
-                    updated_formula_json = formula_a1
-                        .value_actor()
-                        .current_value()
-                        .await
-                        .expect("formula_a1 should still have current value")
-                        .to_json()
-                        .await;
-                    updated_mapped_json = items_to_json(current_items.clone()).await;
-                }
+        for _ in 0..6 {
+            press(&root_object, &construct_context, "step");
+        }
+        expect("level", 2.0);
+        expect("high_level", 3.0);
+    }
 
-                (
-                    initial_formula_json,
-                    updated_formula_json,
-                    initial_mapped_json,
-                    updated_mapped_json,
-                )
-            });
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn bool_and_follows_both_inputs_and_waits_for_both_bools() {
+        let (root_object, construct_context, _scope_guard) = evaluate_program(bool_and_source());
+        let expect = |name: &str, expected: &str| {
+            let tag = json!({ "_tag": expected });
+            assert_eq!(settled_json(&root_object, name, tag.clone()), tag, "{name}");
+        };
 
-        assert_eq!(initial_formula_json, json!("5"));
-        assert_eq!(updated_formula_json, json!("7"));
-        assert_eq!(initial_mapped_json, json!(["5", "15"]));
-        assert_eq!(updated_mapped_json, json!(["7", "17"]));
+        expect("true_true", "True");
+        expect("true_false", "False");
+        expect("false_true", "False");
+        expect("false_false", "False");
+        expect("editing", "True");
+        expect("other_column", "False");
+        expect("inactive", "False");
+
+        expect("both", "False");
+        for (link, expected) in [
+            ("flip_this", "False"),
+            ("flip_that", "True"),
+            ("flip_this", "False"),
+            ("flip_that", "False"),
+            ("flip_this", "False"),
+            ("flip_that", "True"),
+        ] {
+            press(&root_object, &construct_context, link);
+            expect("both", expected);
+        }
+
+        let ready = root_object
+            .expect_variable("ready")
+            .expect_link_value_sender();
+        block_on(send_link_signal(
+            construct_context.clone(),
+            ready.clone(),
+            json!({"ready": true}),
+            "test.ready",
+        ));
+        block_on(send_link_signal(
+            construct_context,
+            ready,
+            json!({"_tag": "True"}),
+            "test.ready",
+        ));
+        assert_eq!(
+            settled_json(&root_object, "ready_count", json!(1.0)),
+            json!(1.0)
+        );
     }
 
     #[test]
-    #[ignore = "requires browser-side actors runtime; host lib tests still touch js-sys statics"]
-    fn snapshot_then_body_freezes_referenced_roots_across_sibling_hold_updates() {
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn bounded_counter_stays_between_zero_and_the_maximum() {
         let (root_object, construct_context, _scope_guard) =
-            evaluate_program(shared_snapshot_root_toggle_source());
-        let toggle_all = root_object.expect_variable("toggle_all");
-        let todos = root_object.expect_variable("todos");
-        let sender = toggle_all.expect_link_value_sender();
+            evaluate_program(bounded_counter_source());
+        let press = |name: &str, times: usize| {
+            for _ in 0..times {
+                let sender = root_object.expect_variable(name).expect_link_value_sender();
+                block_on(send_link_signal(
+                    construct_context.clone(),
+                    sender,
+                    json!({"press": true}),
+                    "test.press",
+                ));
+            }
+        };
 
-        let (initial_json, updated_json) = block_on(async move {
-            let Value::List(list, _) = todos
-                .value_actor()
-                .current_value()
-                .await
-                .expect("todos should have current list value")
-            else {
-                panic!("todos should evaluate to a list");
-            };
+        press("decrement", 1);
+        assert_eq!(settled_json(&root_object, "count", json!(0.0)), json!(0.0));
+        assert_eq!(
+            settled_json(&root_object, "at_floor", json!({"_tag": "True"})),
+            json!({"_tag": "True"})
+        );
+        press("increment", 6);
+        assert_eq!(settled_json(&root_object, "count", json!(5.0)), json!(5.0));
+        assert_eq!(
+            settled_json(&root_object, "at_ceiling", json!({"_tag": "True"})),
+            json!({"_tag": "True"})
+        );
+        assert_eq!(
+            settled_json(&root_object, "at_floor", json!({"_tag": "False"})),
+            json!({"_tag": "False"})
+        );
+    }
 
-            let items_to_json = |items: Vec<crate::engine::ActorHandle>| async move {
-                let mut values = Vec::new();
-                for item_actor in items {
-                    let value = item_actor
-                        .current_value()
-                        .await
-                        .expect("todo item should have current value");
-                    let Value::Object(todo_obj, _) = value else {
-                        panic!("todo item should be an object");
-                    };
-                    let completed = todo_obj
-                        .variable("completed")
-                        .expect("todo item should have completed");
-                    values.push(
-                        completed
-                            .value_actor()
-                            .current_value()
-                            .await
-                            .expect("completed should have current value")
-                            .to_json()
-                            .await,
-                    );
-                }
-                serde_json::Value::Array(values)
-            };
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn bool_toggle_flips_each_call_site_on_its_own_trigger() {
+        let (root_object, construct_context, _scope_guard) = evaluate_program(bool_toggle_source());
+        let signal = |name: &str| {
+            let sender = root_object.expect_variable(name).expect_link_value_sender();
+            block_on(send_link_signal(
+                construct_context.clone(),
+                sender,
+                json!({"signal": true}),
+                "test.signal",
+            ));
+        };
+        let expect = |name: &str, tag: &str| {
+            assert_eq!(
+                settled_json(&root_object, name, json!({"_tag": tag})),
+                json!({"_tag": tag}),
+                "{name}"
+            );
+        };
 
-            let snapshot = list.snapshot().await;
-            let current_items: Vec<crate::engine::ActorHandle> = snapshot
-                .into_iter()
-                .map(|(_id, item_actor)| item_actor)
-                .collect();
-            let initial_json = items_to_json(current_items.clone()).await;
+        expect("a_checked", "False");
+        expect("b_checked", "True");
+        expect("unstarted", "False");
+        expect("piped", "False");
+        expect("reordered", "True");
+        expect("held", "False");
+        expect("toggled", "False");
+        signal("toggle_a");
+        expect("a_checked", "True");
+        expect("b_checked", "True");
+        expect("unstarted", "True");
+        expect("reordered", "False");
+        expect("held", "True");
+        expect("toggled", "True");
+        signal("toggle_b");
+        expect("a_checked", "True");
+        expect("b_checked", "False");
+        expect("piped", "True");
+        // Only the first `initial` Bool counts; a later one changes nothing
+        signal("toggle_all");
+        expect("a_checked", "True");
+        expect("b_checked", "False");
+        signal("toggle_a");
+        expect("a_checked", "False");
+        expect("b_checked", "False");
+        expect("reordered", "True");
+        expect("held", "False");
+        expect("toggled", "False");
+        signal("toggle_a");
+        expect("a_checked", "True");
+        expect("held", "True");
+        expect("toggled", "True");
+    }
 
-            send_link_signal(
-                construct_context,
-                sender,
-                json!({}),
-                "test.toggle_all.snapshot_root",
-            )
-            .await;
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn math_division_rounds_down_for_negative_operands() {
+        let (root_object, _construct_context, _scope_guard) =
+            evaluate_program(math_division_negative_source());
 
-            let mut updated_json = items_to_json(current_items.clone()).await;
-            for _ in 0..50 {
-                if updated_json == json!([true, true, true, true]) {
-                    break;
-                }
-                std::thread::sleep(Duration::from_millis(10));
-                updated_json = items_to_json(current_items.clone()).await;
-            }
+        assert_eq!(first_json(&root_object, "negative_remainder"), json!(2.0));
+        assert_eq!(first_json(&root_object, "negative_quotient"), json!(-3.0));
+        assert_eq!(
+            first_json(&root_object, "remainder_by_negative"),
+            json!(-2.0)
+        );
+        assert_eq!(
+            first_json(&root_object, "quotient_by_negative"),
+            json!(-3.0)
+        );
+        assert_eq!(first_json(&root_object, "both_negative"), json!(-1.0));
+        assert_eq!(first_json(&root_object, "exact_negative"), json!(0.0));
+    }
 
-            (initial_json, updated_json)
-        });
+    #[test]
+    #[ignore = "requires wasm/js runtime; host lib tests still touch js-sys statics"]
+    fn text_replace_amplification_is_truncated_and_reported() {
+        use boon::platform::browser::common::{
+            DEFAULT_MAX_TEXT_BYTES, TEXT_TRUNCATION_MARKER, set_max_text_bytes, set_warning_hook,
+        };
 
-        assert_eq!(initial_json, json!([false, false, false, true]));
-        assert_eq!(updated_json, json!([true, true, true, true]));
+        let warnings = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let sink = warnings.clone();
+        set_warning_hook(move |warning| sink.borrow_mut().push(warning));
+        // Four replaces grow 1 byte to 10 000; every step is held to 500
+        set_max_text_bytes(500);
+        let (root_object, _construct_context, _scope_guard) =
+            evaluate_program(text_replace_amplification_source());
+        let grown = first_json(&root_object, "grown");
+        set_max_text_bytes(DEFAULT_MAX_TEXT_BYTES);
+
+        let grown = grown.as_str().expect("grown should be Text");
+        assert_eq!(grown.len(), 500 + TEXT_TRUNCATION_MARKER.len());
+        assert!(grown.ends_with(TEXT_TRUNCATION_MARKER));
+        let warnings = warnings.borrow();
+        assert!(!warnings.is_empty());
+        assert!(
+            warnings
+                .iter()
+                .all(|warning| warning.category == "text-size"
+                    && warning.message.contains("Text/replace"))
+        );
     }
 }
@@ -19,9 +19,13 @@ use crate::{
     evaluator::{FunctionRegistry, ModuleLoader, evaluate_with_registry},
 };
 use boon::parser::{
-    Expression, Input, ParseError, Parser, SourceCode, Spanned, Token, lexer, parser,
-    reset_expression_depth, resolve_persistence, resolve_references, span_at, static_expression,
+    Expression, Input, ParseError, Parser, SourceCode, Spanned, Token,
+    builtin_aliases::deprecated_builtins_used, exhaustiveness::check_exhaustiveness, lexer, parser,
+    reset_expression_depth, resolve_persistence, resolve_references, source_map::SourceMap,
+    span_at, static_expression,
 };
+use boon::platform::browser::common::{LogLevel, emit_warning};
+use boon::platform::browser::saved_state::arm_ui_state_restore;
 
 /// Run a Boon program and return the result.
 ///
@@ -67,6 +71,8 @@ pub fn run(
         println!("{source_code}");
     }
 
+    report_deprecated_builtins(source_code);
+
     let (tokens, errors) = lexer().parse(source_code).into_output_errors();
     if let Some(_tokens) = tokens.as_ref() {
         // println!("[Tokens]");
@@ -148,9 +154,15 @@ pub fn run(
         }
     }
 
+    // Focus and scroll come back together with program state
+    if !states_local_storage_key.is_empty() {
+        arm_ui_state_restore();
+    }
+
     // Convert to static expressions (owned, 'static, no lifetimes)
     // Note: source_code_arc was created at the start of this function
     let static_ast = static_expression::convert_expressions(source_code_arc.clone(), ast);
+    report_exhaustiveness_hints(&static_ast, source_code);
 
     let function_registry = FunctionRegistry::new();
     let module_loader = ModuleLoader::default();
@@ -259,6 +271,8 @@ pub fn run_with_registry(
         println!("{source_code}");
     }
 
+    report_deprecated_builtins(source_code);
+
     let (tokens, errors) = lexer().parse(source_code).into_output_errors();
     if !errors.is_empty() {
         println!("[Lex Errors]");
@@ -330,9 +344,15 @@ pub fn run_with_registry(
         }
     }
 
+    // Focus and scroll come back together with program state
+    if !states_local_storage_key.is_empty() {
+        arm_ui_state_restore();
+    }
+
     // Convert to static expressions (owned, 'static, no lifetimes)
     // Note: source_code_arc was created at the start of this function
     let static_ast = static_expression::convert_expressions(source_code_arc.clone(), ast);
+    report_exhaustiveness_hints(&static_ast, source_code);
 
     let registry = function_registry.unwrap_or_default();
     let module_loader = ModuleLoader::default();
@@ -439,31 +459,72 @@ fn parse_old<'filename, 'old_code>(
     Some(ast_with_reference_data)
 }
 
+/// One warning per renamed builtin the program still calls by its old name.
+fn report_deprecated_builtins(source_code: &str) {
+    for alias in deprecated_builtins_used(source_code) {
+        emit_warning(LogLevel::Warn, "deprecated", alias.message());
+    }
+}
+
+/// One warning per WHEN/WHILE that misses arms for statically known tags.
+fn report_exhaustiveness_hints(
+    static_ast: &[static_expression::Spanned<static_expression::Expression>],
+    source_code: &str,
+) {
+    for warning in check_exhaustiveness(static_ast) {
+        emit_warning(
+            LogLevel::Warn,
+            "exhaustiveness",
+            warning.message(source_code),
+        );
+    }
+}
+
 fn report_errors<'code, T: fmt::Display + 'code>(
     errors: impl IntoIterator<Item = ParseError<'code, T>>,
     filename: &str,
     source_code: &str,
 ) {
+    for report in render_error_reports(errors, filename, source_code, None) {
+        eprintln!("{report}");
+    }
+}
+
+/// One rendered report per error. With a `source_map`, `filename` is a generated
+/// file and each report notes the template line the error came from.
+pub(crate) fn render_error_reports<'code, T: fmt::Display + 'code>(
+    errors: impl IntoIterator<Item = ParseError<'code, T>>,
+    filename: &str,
+    source_code: &str,
+    source_map: Option<&SourceMap>,
+) -> Vec<String> {
     let mut report_bytes = Cursor::new(Vec::new());
-    let mut report_string = String::new();
+    let mut reports = Vec::new();
     for error in errors {
         report_bytes.set_position(0);
         report_bytes.get_mut().clear();
-        Report::build(ReportKind::Error, (filename, error.span().into_range()))
+        let mut report = Report::build(ReportKind::Error, (filename, error.span().into_range()))
             .with_config(Config::default().with_color(false))
             .with_message(error.to_string())
             .with_label(
                 Label::new((filename, error.span().into_range()))
                     .with_message(error.reason().to_string()),
-            )
+            );
+        if let Some(note) = source_map
+            .and_then(|source_map| source_map.generated_from_note(source_code, error.span().start))
+        {
+            report = report.with_note(note);
+        }
+        report
             .finish()
             .write((filename, Source::from(source_code)), &mut report_bytes)
             .unwrap_throw();
         report_bytes.set_position(0);
-        report_string.clear();
+        let mut report_string = String::new();
         report_bytes
             .read_to_string(&mut report_string)
             .unwrap_throw();
-        eprintln!("{report_string}");
+        reports.push(report_string);
     }
+    reports
 }
@@ -16,18 +16,23 @@ use chumsky::input::Input as _;
 use indexmap::IndexMap;
 
 use boon::parser::{
-    Parser as _, SourceCode, lexer, parser, reset_expression_depth, resolve_references, span_at,
+    Parser as _, SourceCode,
+    builtin_aliases::canonical_builtin_path,
+    lexer, parser, reset_expression_depth, resolve_references, span_at,
     static_expression::{
         self, Alias, Argument, ArithmeticOperator, Comparator, Expression, Literal, Spanned,
         TextPart,
     },
 };
+use boon::platform::browser::stdlib::{
+    clamp_between, floored_division, floored_modulo, format_number,
+};
 
 use super::types::{
     BroadcastHandlerFn, CollectionSpec, DEP_FIELD_PREFIX, DataflowGraph, HOVER_PATH_FIELD,
     HOVERED_FIELD, HoldTransformFn, InputId, InputKind, InputSpec, KEYED_LIST_NAME_FIELD,
-    KeyedListOutput, LINK_PATH_FIELD, LIST_TAG, ListKey, PASSED_VAR, ROUTER_INPUT, SideEffectKind,
-    VarId,
+    KeyedListOutput, LINK_PATH_FIELD, LIST_TAG, ListKey, PASSED_VAR, QUERY_PARAMS_INPUT,
+    ROUTER_INPUT, SideEffectKind, VarId,
 };
 use super::value::Value;
 
@@ -68,20 +73,34 @@ pub fn compile(
     storage_key: Option<&str>,
     persisted_holds: &std::collections::HashMap<String, Value>,
     external_functions: Option<&[ExternalFunction]>,
+) -> Result<CompiledProgram, String> {
+    let ast = parse_source(source_code)?;
+    compile_parsed(
+        source_code,
+        &ast,
+        storage_key,
+        persisted_holds,
+        external_functions,
+    )
+}
+
+/// [`compile`] for an AST from [`parse_source`], for callers that also inspect the AST.
+pub fn compile_parsed(
+    source_code: &str,
+    ast: &[Spanned<Expression>],
+    storage_key: Option<&str>,
+    persisted_holds: &std::collections::HashMap<String, Value>,
+    external_functions: Option<&[ExternalFunction]>,
 ) -> Result<CompiledProgram, String> {
     let trace_cells = std::env::var_os("BOON_DD_TRACE_CELLS").is_some()
         && source_code.contains("-- 7GUIs Task 7: Cells (Spreadsheet)");
-    if trace_cells {
-        std::println!("[cells-dd] compile: start");
-    }
-    let ast = parse_source(source_code)?;
     if trace_cells {
         std::println!("[cells-dd] compile: parsed source");
     }
 
     // Build top-level scope
     let mut compiler = Compiler::new();
-    compiler.register_top_level(&ast);
+    compiler.register_top_level(ast);
     if trace_cells {
         std::println!("[cells-dd] compile: registered top level");
     }
@@ -135,7 +154,8 @@ pub fn compile(
 // Parser integration
 // ---------------------------------------------------------------------------
 
-fn parse_source(source_code: &str) -> Result<Vec<Spanned<Expression>>, String> {
+/// Lexes, parses and reference-resolves `source_code` into the static AST.
+pub fn parse_source(source_code: &str) -> Result<Vec<Spanned<Expression>>, String> {
     let trace_cells = std::env::var_os("BOON_DD_TRACE_CELLS").is_some()
         && source_code.contains("-- 7GUIs Task 7: Cells (Spreadsheet)");
     if trace_cells {
@@ -286,27 +306,36 @@ impl Compiler {
                 | ["Text", "find_closing"]
                 | ["Text", "substring"]
                 | ["Text", "to_uppercase"]
+                | ["Text", "to_lowercase"]
                 | ["Math", "round"]
+                | ["Math", "floor"]
+                | ["Math", "ceil"]
+                | ["Math", "abs"]
+                | ["Math", "negate_if"]
+                | ["Math", "sqrt"]
+                | ["Math", "pow"]
                 | ["Math", "min"]
                 | ["Math", "max"]
+                | ["Math", "clamp"]
                 | ["Math", "modulo"]
+                | ["Math", "divide_int"]
+                | ["Number", "to_text"]
                 | ["List", "count"]
                 | ["List", "is_empty"]
-                | ["List", "sum"]
                 | ["List", "product"]
-                | ["List", "last"]
-                | ["List", "get"]
                 | ["Log", "info"]
-        )
+        ) || Self::is_list_builtin(&strs)
     }
 
     /// Evaluate a built-in piped function with a runtime Value input.
     /// Mirrors the static evaluator's piped dispatch but takes a Value directly.
+    /// Arguments are evaluated in `local_scope`, the scope the piped input comes from.
     fn eval_builtin_piped(
         &self,
         input: &Value,
         path: &[String],
         arguments: &[(String, Option<Spanned<Expression>>)],
+        local_scope: &IndexMap<String, Value>,
     ) -> Result<Value, String> {
         let strs: Vec<&str> = path.iter().map(|s| s.as_str()).collect();
         match strs.as_slice() {
@@ -345,7 +374,7 @@ impl Compiler {
                     .iter()
                     .find(|(name, _)| name == "that")
                     .and_then(|(_, val_expr)| val_expr.as_ref())
-                    .and_then(|v| self.eval_static(v).ok())
+                    .and_then(|v| self.eval_static_with_scope(v, local_scope).ok())
                     .and_then(|v| v.as_bool())
                     .unwrap_or(false);
                 Ok(if a || b {
@@ -360,7 +389,7 @@ impl Compiler {
                     .iter()
                     .find(|(name, _)| name == "that")
                     .and_then(|(_, val_expr)| val_expr.as_ref())
-                    .and_then(|v| self.eval_static(v).ok())
+                    .and_then(|v| self.eval_static_with_scope(v, local_scope).ok())
                     .and_then(|v| v.as_bool())
                     .unwrap_or(false);
                 Ok(if a && b {
@@ -388,7 +417,7 @@ impl Compiler {
                     .iter()
                     .find(|(name, _)| name == "prefix")
                     .and_then(|(_, val_expr)| val_expr.as_ref())
-                    .and_then(|v| self.eval_static(v).ok())
+                    .and_then(|v| self.eval_static_with_scope(v, local_scope).ok())
                     .and_then(|v| v.as_text().map(|s| s.to_string()))
                     .unwrap_or_default();
                 Ok(if s.starts_with(&prefix) {
@@ -399,7 +428,9 @@ impl Compiler {
             }
             ["Text", "length"] => {
                 let s = input.as_text().unwrap_or("");
-                Ok(Value::number(s.len() as f64))
+                // Characters (Unicode scalar values), not UTF-8 bytes
+                let length = u32::try_from(s.chars().count()).unwrap_or(u32::MAX);
+                Ok(Value::number(f64::from(length)))
             }
             ["Text", "char_at"] => {
                 let s = input.as_text().unwrap_or("");
@@ -407,7 +438,7 @@ impl Compiler {
                     .iter()
                     .find(|(name, _)| name == "index")
                     .and_then(|(_, val_expr)| val_expr.as_ref())
-                    .and_then(|v| self.eval_static(v).ok())
+                    .and_then(|v| self.eval_static_with_scope(v, local_scope).ok())
                     .and_then(|v| v.as_number())
                     .unwrap_or(0.0) as usize;
                 match s.chars().nth(index) {
@@ -435,7 +466,7 @@ impl Compiler {
                     .iter()
                     .find(|(name, _)| name == "search")
                     .and_then(|(_, val_expr)| val_expr.as_ref())
-                    .and_then(|v| self.eval_static(v).ok())
+                    .and_then(|v| self.eval_static_with_scope(v, local_scope).ok())
                     .and_then(|v| v.as_text().map(|s| s.to_string()))
                     .unwrap_or_default();
                 match s.find(&search) {
@@ -449,14 +480,14 @@ impl Compiler {
                     .iter()
                     .find(|(name, _)| name == "open")
                     .and_then(|(_, val_expr)| val_expr.as_ref())
-                    .and_then(|v| self.eval_static(v).ok())
+                    .and_then(|v| self.eval_static_with_scope(v, local_scope).ok())
                     .and_then(|v| v.as_text().map(|s| s.to_string()))
                     .unwrap_or_else(|| "(".to_string());
                 let close = arguments
                     .iter()
                     .find(|(name, _)| name == "close")
                     .and_then(|(_, val_expr)| val_expr.as_ref())
-                    .and_then(|v| self.eval_static(v).ok())
+                    .and_then(|v| self.eval_static_with_scope(v, local_scope).ok())
                     .and_then(|v| v.as_text().map(|s| s.to_string()))
                     .unwrap_or_else(|| ")".to_string());
                 let open_char = open.chars().next().unwrap_or('(');
@@ -482,14 +513,14 @@ impl Compiler {
                     .iter()
                     .find(|(name, _)| name == "start")
                     .and_then(|(_, val_expr)| val_expr.as_ref())
-                    .and_then(|v| self.eval_static(v).ok())
+                    .and_then(|v| self.eval_static_with_scope(v, local_scope).ok())
                     .and_then(|v| v.as_number())
                     .unwrap_or(0.0) as usize;
                 let length = arguments
                     .iter()
                     .find(|(name, _)| name == "length")
                     .and_then(|(_, val_expr)| val_expr.as_ref())
-                    .and_then(|v| self.eval_static(v).ok())
+                    .and_then(|v| self.eval_static_with_scope(v, local_scope).ok())
                     .and_then(|v| v.as_number())
                     .unwrap_or(0.0) as usize;
                 let chars: Vec<char> = s.chars().collect();
@@ -498,78 +529,346 @@ impl Compiler {
                 let result: String = chars[start..end].iter().collect();
                 Ok(Value::text(result))
             }
-            ["Text", "to_uppercase"] => {
-                let s = input.as_text().unwrap_or("");
-                Ok(Value::text(s.to_uppercase()))
-            }
+            // Case conversions pass values that are not Text through unchanged
+            ["Text", "to_uppercase"] => Ok(match input.as_text() {
+                Some(s) => Value::text(s.to_uppercase()),
+                None => input.clone(),
+            }),
+            ["Text", "to_lowercase"] => Ok(match input.as_text() {
+                Some(s) => Value::text(s.to_lowercase()),
+                None => input.clone(),
+            }),
             ["Math", "round"] => {
                 let n = input.as_number().unwrap_or(0.0);
                 Ok(Value::number(n.round()))
             }
+            ["Math", "floor"] => {
+                let n = input.as_number().unwrap_or(0.0);
+                Ok(Value::number(n.floor()))
+            }
+            ["Math", "ceil"] => {
+                let n = input.as_number().unwrap_or(0.0);
+                Ok(Value::number(n.ceil()))
+            }
+            ["Math", "abs"] => {
+                let n = input.as_number().unwrap_or(0.0);
+                Ok(Value::number(n.abs()))
+            }
+            ["Math", "negate_if"] => {
+                let n = input.as_number().unwrap_or(0.0);
+                let condition = arguments
+                    .iter()
+                    .find(|(name, _)| name == "condition")
+                    .and_then(|(_, val_expr)| val_expr.as_ref())
+                    .and_then(|v| self.eval_static_with_scope(v, local_scope).ok())
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
+                Ok(Value::number(if condition { 0.0 - n } else { n }))
+            }
+            ["Math", "sqrt"] => {
+                let n = input.as_number().unwrap_or(0.0);
+                Ok(finite_number_or_math_error(n.sqrt()))
+            }
+            ["Math", "pow"] => {
+                let base = input.as_number().unwrap_or(0.0);
+                let exponent = arguments
+                    .iter()
+                    .find(|(name, _)| name == "exponent")
+                    .and_then(|(_, val_expr)| val_expr.as_ref())
+                    .and_then(|v| self.eval_static_with_scope(v, local_scope).ok())
+                    .and_then(|v| v.as_number())
+                    .unwrap_or(1.0);
+                Ok(finite_number_or_math_error(base.powf(exponent)))
+            }
             ["Math", "min"] => {
                 let a = input.as_number().unwrap_or(0.0);
                 let b = arguments
                     .iter()
-                    .find(|(name, _)| name == "b")
+                    .find(|(name, _)| name == "with" || name == "b")
                     .and_then(|(_, val_expr)| val_expr.as_ref())
-                    .and_then(|v| self.eval_static(v).ok())
+                    .and_then(|v| self.eval_static_with_scope(v, local_scope).ok())
                     .and_then(|v| v.as_number())
-                    .unwrap_or(0.0);
+                    .unwrap_or(f64::INFINITY);
                 Ok(Value::number(a.min(b)))
             }
             ["Math", "max"] => {
                 let a = input.as_number().unwrap_or(0.0);
                 let b = arguments
                     .iter()
-                    .find(|(name, _)| name == "b")
+                    .find(|(name, _)| name == "with" || name == "b")
                     .and_then(|(_, val_expr)| val_expr.as_ref())
-                    .and_then(|v| self.eval_static(v).ok())
+                    .and_then(|v| self.eval_static_with_scope(v, local_scope).ok())
                     .and_then(|v| v.as_number())
-                    .unwrap_or(0.0);
+                    .unwrap_or(f64::NEG_INFINITY);
                 Ok(Value::number(a.max(b)))
             }
-            ["Math", "modulo"] => {
+            ["Math", "clamp"] => {
+                let bound = |bound_name: &str, unbounded: f64| {
+                    arguments
+                        .iter()
+                        .find(|(name, _)| name == bound_name)
+                        .and_then(|(_, val_expr)| val_expr.as_ref())
+                        .and_then(|v| self.eval_static_with_scope(v, local_scope).ok())
+                        .and_then(|v| v.as_number())
+                        .unwrap_or(unbounded)
+                };
+                let value = input.as_number().unwrap_or(0.0);
+                let min = bound("min", f64::NEG_INFINITY);
+                let max = bound("max", f64::INFINITY);
+                Ok(Value::number(clamp_between(value, min, max)))
+            }
+            ["Math", "modulo"] | ["Math", "divide_int"] => {
                 let a = input.as_number().unwrap_or(0.0);
-                let b = arguments
+                let by = arguments
                     .iter()
-                    .find(|(name, _)| name == "divisor")
+                    .find(|(name, _)| name == "by" || name == "divisor")
                     .and_then(|(_, val_expr)| val_expr.as_ref())
-                    .and_then(|v| self.eval_static(v).ok())
+                    .and_then(|v| self.eval_static_with_scope(v, local_scope).ok())
                     .and_then(|v| v.as_number())
                     .unwrap_or(1.0);
-                Ok(Value::number(a % b))
+                Ok(math_division(&strs, a, by))
             }
-            ["List", "sum"] => {
-                let items = input.list_items();
-                let sum: f64 = items.iter().filter_map(|v| v.as_number()).sum();
-                Ok(Value::number(sum))
+            ["Number", "to_text"] => {
+                let argument = |argument_name: &str| {
+                    arguments
+                        .iter()
+                        .find(|(name, _)| name == argument_name)
+                        .and_then(|(_, val_expr)| val_expr.as_ref())
+                        .and_then(|v| self.eval_static_with_scope(v, local_scope).ok())
+                };
+                let decimals = argument("decimals")
+                    .and_then(|v| v.as_number())
+                    .unwrap_or(0.0);
+                let separator = argument("thousands_separator")
+                    .and_then(|v| v.as_text().map(str::to_string))
+                    .unwrap_or_default();
+                Ok(match input.as_number() {
+                    Some(n) => Value::text(format_number(n, decimals, &separator)),
+                    None => input.clone(),
+                })
             }
             ["List", "product"] => {
                 let items = input.list_items();
                 let prod: f64 = items.iter().filter_map(|v| v.as_number()).product();
                 Ok(Value::number(prod))
             }
-            ["List", "last"] => {
-                let items = input.list_items();
-                Ok(items.last().cloned().cloned().unwrap_or(Value::Unit))
+            path if Self::is_list_builtin(path) => {
+                Ok(self.eval_list_builtin(input, path, arguments, local_scope, None))
+            }
+            _ => Err(format!("Not a built-in piped function: {}", path.join("/"))),
+        }
+    }
+
+    /// List builtins computed from the whole list value, along with `Text/join` and
+    /// `Text/split`, which convert between a list and a Text the same way. Static
+    /// pipes call them directly; reactive pipes run them as a Map over the input, so
+    /// the result follows membership and item changes.
+    fn is_list_builtin(path: &[&str]) -> bool {
+        matches!(
+            path,
+            ["List", "sort_by"]
+                | ["List", "filter"]
+                | ["List", "first"]
+                | ["List", "last"]
+                | ["List", "reverse"]
+                | ["List", "contains"]
+                | ["List", "index_of"]
+                | ["List", "zip"]
+                | ["List", "sum"]
+                | ["List", "min"]
+                | ["List", "max"]
+                | ["List", "all"]
+                | ["List", "get"]
+                | ["List", "unique"]
+                | ["List", "unique_by"]
+                | ["List", "concat"]
+                | ["Text", "join"]
+                | ["Text", "split"]
+                | ["List", "count_where"]
+        )
+    }
+
+    /// Evaluates an [`Self::is_list_builtin`] function on `input`. Per-item arguments
+    /// such as `key:` see the item under the name of the first argument. A reactive
+    /// pipe passes the current value of its reactive argument as `reactive_argument`.
+    fn eval_list_builtin(
+        &self,
+        input: &Value,
+        path: &[&str],
+        arguments: &[(String, Option<Spanned<Expression>>)],
+        local_scope: &IndexMap<String, Value>,
+        reactive_argument: Option<(&str, &Value)>,
+    ) -> Value {
+        let argument_expression = |argument_name: &str| {
+            arguments
+                .iter()
+                .find(|(name, _)| name == argument_name)
+                .and_then(|(_, value)| value.as_ref())
+        };
+        let argument = |argument_name: &str| match reactive_argument {
+            Some((name, value)) if name == argument_name => value.clone(),
+            _ => argument_expression(argument_name).map_or(Value::Unit, |expression| {
+                self.eval_static_tolerant(expression, local_scope)
+            }),
+        };
+        let item_param = arguments
+            .first()
+            .map(|(name, _)| name.as_str())
+            .unwrap_or("item");
+        let per_item = |argument_name: &str, item: &Value| {
+            argument_expression(argument_name).map_or(Value::Unit, |expression| {
+                let mut item_scope = local_scope.clone();
+                item_scope.insert(item_param.to_string(), item.clone());
+                self.eval_static_tolerant(expression, &item_scope)
+            })
+        };
+        match path {
+            ["List", "sort_by"] => input.list_sorted_by(|item| per_item("key", item)),
+            // Keeps items whose predicate fails to evaluate, like List/retain
+            ["List", "filter"] => {
+                input.list_retain(|item| per_item("if", item).as_bool().unwrap_or(true))
+            }
+            // `None` on an empty list, so the empty case can be matched
+            ["List", "first"] => input
+                .list_items()
+                .first()
+                .map_or_else(|| Value::tag("None"), |item| (*item).clone()),
+            ["List", "last"] => input
+                .list_items()
+                .last()
+                .map_or_else(|| Value::tag("None"), |item| (*item).clone()),
+            ["List", "reverse"] => {
+                Value::list_from_items(input.list_items().into_iter().rev().cloned())
+            }
+            ["List", "concat"] => Value::list_from_items(
+                input
+                    .list_items()
+                    .into_iter()
+                    .chain(argument("with").list_items())
+                    .cloned(),
+            ),
+            // Items read as they do in `TEXT { {item} }`
+            ["Text", "join"] => Value::text(
+                input
+                    .list_items()
+                    .into_iter()
+                    .map(Value::to_display_string)
+                    .collect::<Vec<_>>()
+                    .join(argument("separator").as_text().unwrap_or("")),
+            ),
+            // Each separator ends a part; empty text has no parts, and an empty
+            // separator keeps the whole text as one part
+            ["Text", "split"] => {
+                let text = input.as_text().unwrap_or("");
+                let separator = argument("separator");
+                let separator = separator.as_text().unwrap_or("");
+                Value::list_from_items(
+                    match (text, separator) {
+                        ("", _) => Vec::new(),
+                        (_, "") => vec![text],
+                        _ => text.split(separator).collect(),
+                    }
+                    .into_iter()
+                    .map(Value::text),
+                )
             }
+            // Pairs up to the shorter list
+            ["List", "zip"] => Value::list_from_items(
+                input
+                    .list_items()
+                    .into_iter()
+                    .zip(argument("with").list_items())
+                    .map(|(first, second)| {
+                        Value::object([("first", first.clone()), ("second", second.clone())])
+                    }),
+            ),
+            // 1-based; zero, negative, fractional and past-end indices give `None`
             ["List", "get"] => {
-                let index = arguments
-                    .iter()
-                    .find(|(name, _)| name == "index")
-                    .and_then(|(_, val_expr)| val_expr.as_ref())
-                    .and_then(|v| self.eval_static(v).ok())
-                    .and_then(|v| v.as_number())
-                    .unwrap_or(1.0) as usize;
-                // 1-based indexing
-                let items = input.list_items();
-                Ok(if index >= 1 && index <= items.len() {
-                    items[index - 1].clone()
+                let index = argument("index").as_number();
+                input
+                    .list_items()
+                    .into_iter()
+                    .zip(1u32..)
+                    .find(|(_, position)| Some(f64::from(*position)) == index)
+                    .map_or_else(|| Value::tag("None"), |(item, _)| item.clone())
+            }
+            // Keeps the first item of each group of equal items, or of equal `key:` values
+            path @ (["List", "unique"] | ["List", "unique_by"]) => {
+                let mut seen_keys: Vec<Value> = Vec::new();
+                Value::list_from_items(
+                    input
+                        .list_items()
+                        .into_iter()
+                        .filter(|item| {
+                            let key = if path[1] == "unique" {
+                                (*item).clone()
+                            } else {
+                                per_item("key", item)
+                            };
+                            let first = !seen_keys.contains(&key);
+                            if first {
+                                seen_keys.push(key);
+                            }
+                            first
+                        })
+                        .cloned(),
+                )
+            }
+            // Items whose predicate fails to evaluate are not counted
+            ["List", "count_where"] => Value::number(f64::from(
+                u32::try_from(
+                    input
+                        .list_items()
+                        .into_iter()
+                        .filter(|item| per_item("if", item).as_bool().unwrap_or(false))
+                        .count(),
+                )
+                .unwrap_or(u32::MAX),
+            )),
+            // True on an empty list, unlike List/every
+            ["List", "all"] => Value::tag(
+                if input
+                    .list_items()
+                    .into_iter()
+                    .all(|item| per_item("if", item).as_bool().unwrap_or(false))
+                {
+                    "True"
                 } else {
-                    Value::Unit
-                })
+                    "False"
+                },
+            ),
+            // Items that are not Numbers are left out; min and max of none are `None`
+            ["List", "sum"] => Value::number(
+                input
+                    .list_items()
+                    .into_iter()
+                    .filter_map(Value::as_number)
+                    .sum(),
+            ),
+            path @ (["List", "min"] | ["List", "max"]) => input
+                .list_items()
+                .into_iter()
+                .filter_map(Value::as_number)
+                .reduce(if path[1] == "min" { f64::min } else { f64::max })
+                .map_or_else(|| Value::tag("None"), Value::number),
+            // Unit never equals anything, so searching for it finds nothing
+            path @ (["List", "contains"] | ["List", "index_of"]) => {
+                let value = argument("value");
+                let position = input
+                    .list_items()
+                    .into_iter()
+                    .position(|item| value != Value::Unit && *item == value);
+                match (path, position) {
+                    (["List", "contains"], position) => {
+                        Value::tag(if position.is_some() { "True" } else { "False" })
+                    }
+                    (_, Some(index)) => {
+                        Value::number(f64::from(u32::try_from(index + 1).unwrap_or(u32::MAX)))
+                    }
+                    (_, None) => Value::tag("None"),
+                }
             }
-            _ => Err(format!("Not a built-in piped function: {}", path.join("/"))),
+            _ => Value::Unit,
         }
     }
 
@@ -723,7 +1022,11 @@ impl Compiler {
                 let path_strs: Vec<&str> = path.iter().map(|s| s.as_str()).collect();
                 if matches!(
                     path_strs.as_slice(),
-                    ["Timer", "interval"] | ["Router", "route"] | ["Router", "go_to"]
+                    ["Timer", "interval"]
+                        | ["Router", "route"]
+                        | ["Router", "go_to"]
+                        | ["Document", "query_params"]
+                        | ["Document", "set_query_param"]
                 ) {
                     return true;
                 }
@@ -845,6 +1148,8 @@ impl Compiler {
                         | ["Stream", "skip"]
                         | ["Router", "route"]
                         | ["Router", "go_to"]
+                        | ["Document", "query_params"]
+                        | ["Document", "set_query_param"]
                         | ["List", "count"]
                         | ["List", "latest"]
                         | ["List", "every"]
@@ -1648,6 +1953,7 @@ impl Compiler {
                             "link" => "ElementLink",
                             "checkbox" => "ElementCheckbox",
                             "paragraph" => "ElementParagraph",
+                            "code" => "ElementCode",
                             "container" => "ElementContainer",
                             _ => return Value::Unit,
                         };
@@ -1948,7 +2254,8 @@ impl Compiler {
         arguments: &[Spanned<Argument>],
         local_scope: &IndexMap<String, Value>,
     ) -> Result<Value, String> {
-        let path_strs: Vec<&str> = path.iter().map(|s| s.as_str()).collect();
+        let path_strs: Vec<&str> =
+            canonical_builtin_path(path.iter().map(|s| s.as_str()).collect());
 
         match path_strs.as_slice() {
             ["Document", "new"] => {
@@ -2054,6 +2361,7 @@ impl Compiler {
                     "container" => "ElementContainer",
                     "stack" => "ElementStack",
                     "paragraph" => "ElementParagraph",
+                    "code" => "ElementCode",
                     "text_input" => "ElementTextInput",
                     "label" => "ElementLabel",
                     "link" => "ElementLink",
@@ -2085,6 +2393,8 @@ impl Compiler {
                 self.eval_element_static("ElementParagraph", arguments, local_scope)
             }
 
+            ["Element", "code"] => self.eval_element_static("ElementCode", arguments, local_scope),
+
             ["Element", "text_input"] => {
                 self.eval_element_static("ElementTextInput", arguments, local_scope)
             }
@@ -2134,6 +2444,23 @@ impl Compiler {
                 // Non-piped: shouldn't happen but handle it
                 Ok(Value::tag("True"))
             }
+            // `Bool/toggle(on:, initial:)` in static context — the starting value of
+            // `initial` (the first input of a LATEST), like a HOLD's initial state
+            ["Bool", "toggle"] => {
+                let initial = arguments
+                    .iter()
+                    .find(|a| a.node.name.as_str() == "initial")
+                    .and_then(|a| a.node.value.as_ref());
+                let initial = match initial.map(|initial| (&initial.node, initial)) {
+                    Some((Expression::Latest { inputs }, _)) => inputs.first(),
+                    Some((_, initial)) => Some(initial),
+                    None => None,
+                };
+                match initial {
+                    Some(initial) => self.eval_static_with_scope(initial, local_scope),
+                    None => Ok(Value::tag("False")),
+                }
+            }
 
             // List utilities (non-piped)
             ["List", "count"] => Ok(Value::number(0.0)),
@@ -2191,10 +2518,19 @@ impl Compiler {
             ["Text", "find_closing"] => Ok(Value::number(-1.0)),
             ["Text", "substring"] => Ok(Value::text("")),
             ["Text", "to_uppercase"] => Ok(Value::text("")),
+            ["Text", "to_lowercase"] => Ok(Value::text("")),
             ["Math", "round"] => Ok(Value::number(0.0)),
+            ["Math", "floor"] => Ok(Value::number(0.0)),
+            ["Math", "ceil"] => Ok(Value::number(0.0)),
+            ["Math", "abs"] => Ok(Value::number(0.0)),
+            ["Math", "negate_if"] => Ok(Value::number(0.0)),
+            ["Math", "sqrt"] => Ok(Value::number(0.0)),
+            ["Math", "pow"] => Ok(Value::number(0.0)),
             ["Math", "min"] => Ok(Value::number(0.0)),
             ["Math", "max"] => Ok(Value::number(0.0)),
+            ["Math", "clamp"] => Ok(Value::number(0.0)),
             ["Math", "modulo"] => Ok(Value::number(0.0)),
+            ["Math", "divide_int"] => Ok(Value::number(0.0)),
 
             [fn_name] => {
                 // User-defined function call
@@ -2395,7 +2731,8 @@ impl Compiler {
 
         match &to.node {
             Expression::FunctionCall { path, arguments } => {
-                let path_strs: Vec<&str> = path.iter().map(|s| s.as_str()).collect();
+                let path_strs: Vec<&str> =
+                    canonical_builtin_path(path.iter().map(|s| s.as_str()).collect());
                 match path_strs.as_slice() {
                     ["Document", "new"] => {
                         if arguments.is_empty() {
@@ -2434,7 +2771,7 @@ impl Compiler {
                             Err("Stream/skip: not enough values".to_string())
                         }
                     }
-                    ["Stream", "pulses"] => {
+                    ["Stream", "sequence"] => {
                         let n = from_val.as_number().unwrap_or(0.0) as usize;
                         let mut fields = BTreeMap::new();
                         for i in 0..n {
@@ -2717,6 +3054,13 @@ impl Compiler {
                             Ok(from_val)
                         }
                     }
+                    path if Self::is_list_builtin(path) => {
+                        let arguments: Vec<(String, Option<Spanned<Expression>>)> = arguments
+                            .iter()
+                            .map(|a| (a.node.name.as_str().to_string(), a.node.value.clone()))
+                            .collect();
+                        Ok(self.eval_list_builtin(&from_val, path, &arguments, local_scope, None))
+                    }
 
                     [fn_name] => {
                         // Piped user function call: `value |> fn()` → fn(value)
@@ -2774,11 +3118,21 @@ impl Compiler {
                                 }
                                 "length" => {
                                     let s = from_val.as_text().unwrap_or("");
-                                    return Ok(Value::number(s.len() as f64));
+                                    let length =
+                                        u32::try_from(s.chars().count()).unwrap_or(u32::MAX);
+                                    return Ok(Value::number(f64::from(length)));
                                 }
                                 "to_uppercase" => {
-                                    let s = from_val.as_text().unwrap_or("");
-                                    return Ok(Value::text(s.to_uppercase()));
+                                    return Ok(match from_val.as_text() {
+                                        Some(s) => Value::text(s.to_uppercase()),
+                                        None => from_val,
+                                    });
+                                }
+                                "to_lowercase" => {
+                                    return Ok(match from_val.as_text() {
+                                        Some(s) => Value::text(s.to_lowercase()),
+                                        None => from_val,
+                                    });
                                 }
                                 "char_at" => {
                                     let s = from_val.as_text().unwrap_or("");
@@ -2908,10 +3262,38 @@ impl Compiler {
                             let n = from_val.as_number().unwrap_or(0.0);
                             match *fn_name {
                                 "round" => return Ok(Value::number(n.round())),
+                                "floor" => return Ok(Value::number(n.floor())),
+                                "ceil" => return Ok(Value::number(n.ceil())),
+                                "abs" => return Ok(Value::number(n.abs())),
+                                "negate_if" => {
+                                    let condition = arguments
+                                        .iter()
+                                        .find(|a| a.node.name.as_str() == "condition")
+                                        .and_then(|a| a.node.value.as_ref())
+                                        .and_then(|v| {
+                                            self.eval_static_with_scope(v, local_scope).ok()
+                                        })
+                                        .and_then(|v| v.as_bool())
+                                        .unwrap_or(false);
+                                    return Ok(Value::number(if condition { 0.0 - n } else { n }));
+                                }
+                                "sqrt" => return Ok(finite_number_or_math_error(n.sqrt())),
+                                "pow" => {
+                                    let exponent = arguments
+                                        .iter()
+                                        .find(|a| a.node.name.as_str() == "exponent")
+                                        .and_then(|a| a.node.value.as_ref())
+                                        .and_then(|v| {
+                                            self.eval_static_with_scope(v, local_scope).ok()
+                                        })
+                                        .and_then(|v| v.as_number())
+                                        .unwrap_or(1.0);
+                                    return Ok(finite_number_or_math_error(n.powf(exponent)));
+                                }
                                 "min" => {
                                     let b = arguments
                                         .iter()
-                                        .find(|a| a.node.name.as_str() == "b")
+                                        .find(|a| matches!(a.node.name.as_str(), "with" | "b"))
                                         .and_then(|a| a.node.value.as_ref())
                                         .and_then(|v| {
                                             self.eval_static_with_scope(v, local_scope).ok()
@@ -2923,7 +3305,7 @@ impl Compiler {
                                 "max" => {
                                     let b = arguments
                                         .iter()
-                                        .find(|a| a.node.name.as_str() == "b")
+                                        .find(|a| matches!(a.node.name.as_str(), "with" | "b"))
                                         .and_then(|a| a.node.value.as_ref())
                                         .and_then(|v| {
                                             self.eval_static_with_scope(v, local_scope).ok()
@@ -2932,35 +3314,63 @@ impl Compiler {
                                         .unwrap_or(f64::NEG_INFINITY);
                                     return Ok(Value::number(n.max(b)));
                                 }
-                                "modulo" => {
-                                    let divisor = arguments
+                                "clamp" => {
+                                    let bound = |bound_name: &str, unbounded: f64| {
+                                        arguments
+                                            .iter()
+                                            .find(|a| a.node.name.as_str() == bound_name)
+                                            .and_then(|a| a.node.value.as_ref())
+                                            .and_then(|v| {
+                                                self.eval_static_with_scope(v, local_scope).ok()
+                                            })
+                                            .and_then(|v| v.as_number())
+                                            .unwrap_or(unbounded)
+                                    };
+                                    let min = bound("min", f64::NEG_INFINITY);
+                                    let max = bound("max", f64::INFINITY);
+                                    return Ok(Value::number(clamp_between(n, min, max)));
+                                }
+                                "modulo" | "divide_int" => {
+                                    let by = arguments
                                         .iter()
-                                        .find(|a| a.node.name.as_str() == "divisor")
+                                        .find(|a| matches!(a.node.name.as_str(), "by" | "divisor"))
                                         .and_then(|a| a.node.value.as_ref())
                                         .and_then(|v| {
                                             self.eval_static_with_scope(v, local_scope).ok()
                                         })
                                         .and_then(|v| v.as_number())
                                         .unwrap_or(1.0);
-                                    return Ok(Value::number(n % divisor));
+                                    return Ok(math_division(&["Math", *fn_name], n, by));
                                 }
                                 _ => {}
                             }
                         }
+                        if *module == "Number" && *fn_name == "to_text" {
+                            let arguments: Vec<(String, Option<Spanned<Expression>>)> = arguments
+                                .iter()
+                                .map(|a| (a.node.name.as_str().to_string(), a.node.value.clone()))
+                                .collect();
+                            let argument = |argument_name: &str| {
+                                arguments
+                                    .iter()
+                                    .find(|(name, _)| name == argument_name)
+                                    .and_then(|(_, val_expr)| val_expr.as_ref())
+                                    .and_then(|v| self.eval_static_with_scope(v, local_scope).ok())
+                            };
+                            let decimals = argument("decimals")
+                                .and_then(|v| v.as_number())
+                                .unwrap_or(0.0);
+                            let separator = argument("thousands_separator")
+                                .and_then(|v| v.as_text().map(str::to_string))
+                                .unwrap_or_default();
+                            return Ok(match from_val.as_number() {
+                                Some(n) => Value::text(format_number(n, decimals, &separator)),
+                                None => from_val,
+                            });
+                        }
                         // Built-in List/ functions
                         if *module == "List" {
                             match *fn_name {
-                                "sum" => {
-                                    let mut sum = 0.0;
-                                    if let Value::Tagged { tag, fields } = &from_val {
-                                        if tag.as_ref() == LIST_TAG {
-                                            for v in fields.values() {
-                                                sum += v.as_number().unwrap_or(0.0);
-                                            }
-                                        }
-                                    }
-                                    return Ok(Value::number(sum));
-                                }
                                 "product" => {
                                     let mut prod = 1.0;
                                     if let Value::Tagged { tag, fields } = &from_val {
@@ -2972,39 +3382,6 @@ impl Compiler {
                                     }
                                     return Ok(Value::number(prod));
                                 }
-                                "last" => {
-                                    if let Value::Tagged { tag, fields } = &from_val {
-                                        if tag.as_ref() == LIST_TAG {
-                                            if let Some(last) = fields.values().last() {
-                                                return Ok(last.clone());
-                                            }
-                                        }
-                                    }
-                                    return Ok(Value::Unit);
-                                }
-                                "get" => {
-                                    let index = arguments
-                                        .iter()
-                                        .find(|a| a.node.name.as_str() == "index")
-                                        .and_then(|a| a.node.value.as_ref())
-                                        .and_then(|v| {
-                                            self.eval_static_with_scope(v, local_scope).ok()
-                                        })
-                                        .and_then(|v| v.as_number())
-                                        .unwrap_or(1.0)
-                                        as usize;
-                                    // 1-based indexing
-                                    if let Value::Tagged { tag, fields } = &from_val {
-                                        if tag.as_ref() == LIST_TAG {
-                                            if let Some(val) =
-                                                fields.values().nth(index.saturating_sub(1))
-                                            {
-                                                return Ok(val.clone());
-                                            }
-                                        }
-                                    }
-                                    return Ok(Value::Unit);
-                                }
                                 _ => {}
                             }
                         }
@@ -3131,7 +3508,7 @@ impl Compiler {
     }
 
     /// Evaluate HOLD statically as a fold operation.
-    /// `initial |> HOLD state { body }` where body contains `N |> Stream/pulses() |> THEN { transform }`
+    /// `initial |> HOLD state { body }` where body contains `N |> Stream/sequence() |> THEN { transform }`
     fn eval_hold_static(
         &self,
         state_name: &str,
@@ -3140,12 +3517,12 @@ impl Compiler {
         local_scope: &IndexMap<String, Value>,
     ) -> Result<Value, String> {
         // Extract the pattern: event_source |> THEN { transform }
-        // where event_source is N |> Stream/pulses()
+        // where event_source is N |> Stream/sequence()
         match &body.node {
             Expression::Pipe { from, to } => {
                 match &to.node {
                     Expression::Then { body: then_body } => {
-                        // Try to evaluate as a fold (Stream/pulses pattern)
+                        // Try to evaluate as a fold (Stream/sequence pattern)
                         match self.eval_pulse_count(from, local_scope) {
                             Ok(pulse_count) => {
                                 // Run the fold: apply then_body pulse_count times
@@ -3170,7 +3547,7 @@ impl Compiler {
                                 })
                             }
                             Err(_) => {
-                                // Not a Stream/pulses source (e.g., LATEST |> THEN) —
+                                // Not a Stream/sequence source (e.g., LATEST |> THEN) —
                                 // reactive pattern, return initial value
                                 Ok(initial_val.clone())
                             }
@@ -3190,7 +3567,7 @@ impl Compiler {
         }
     }
 
-    /// Extract the pulse count from an expression like `N |> Stream/pulses()` or `expr |> Stream/pulses()`.
+    /// Extract the pulse count from an expression like `N |> Stream/sequence()` or `expr |> Stream/sequence()`.
     fn eval_pulse_count(
         &self,
         expr: &Spanned<Expression>,
@@ -3199,13 +3576,14 @@ impl Compiler {
         match &expr.node {
             Expression::Pipe { from, to } => {
                 if let Expression::FunctionCall { path, .. } = &to.node {
-                    let path_strs: Vec<&str> = path.iter().map(|s| s.as_str()).collect();
-                    if path_strs.as_slice() == ["Stream", "pulses"] {
+                    let path_strs: Vec<&str> =
+                        canonical_builtin_path(path.iter().map(|s| s.as_str()).collect());
+                    if path_strs.as_slice() == ["Stream", "sequence"] {
                         let val = self.eval_static_with_scope(from, local_scope)?;
                         return val
                             .as_number()
                             .map(|n| n as usize)
-                            .ok_or_else(|| "Stream/pulses count must be a number".to_string());
+                            .ok_or_else(|| "Stream/sequence count must be a number".to_string());
                     }
                 }
                 // Recurse for nested pipes
@@ -3940,6 +4318,16 @@ impl<'a> GraphBuilder<'a> {
             // Pattern: `LATEST { ... }`
             Expression::Latest { inputs } => self.compile_latest(name, inputs),
 
+            // Pattern: `Bool/toggle(on: event_source, initial: False)`
+            Expression::FunctionCall { path, arguments }
+                if {
+                    let p: Vec<&str> = path.iter().map(|s| s.as_str()).collect();
+                    p.as_slice() == ["Bool", "toggle"]
+                } =>
+            {
+                self.compile_bool_toggle(name, None, arguments)
+            }
+
             // Pattern: `Router/route()`
             Expression::FunctionCall { path, .. }
                 if {
@@ -3955,38 +4343,133 @@ impl<'a> GraphBuilder<'a> {
                 Ok(var)
             }
 
-            // Pattern: `reactive_a - reactive_b` (or +, *, /)
-            Expression::ArithmeticOperator(op) => self.compile_reactive_arithmetic(name, op),
-
-            // Pattern: `reactive_a == reactive_b` (or !=, <, >, <=, >=)
-            Expression::Comparator(cmp) => self.compile_reactive_comparison(name, cmp),
-
-            // Objects with reactive fields: individual fields are compiled via
-            // flattened dotted-name variables (e.g., "theme_options.name").
-            // The parent Object itself doesn't need a DD collection.
-            Expression::Object(_) => Ok(VarId::new(name)),
+            // Pattern: `Document/query_params()`
+            Expression::FunctionCall { path, .. }
+                if {
+                    let p: Vec<&str> = path.iter().map(|s| s.as_str()).collect();
+                    p.as_slice() == ["Document", "query_params"]
+                } =>
+            {
+                let input_id =
+                    self.add_input(InputKind::QueryParams, Some(QUERY_PARAMS_INPUT.to_string()));
+                let var = VarId::new(name);
+                self.collections
+                    .insert(var.clone(), CollectionSpec::Input(input_id));
+                self.reactive_vars.insert(name.to_string(), var.clone());
+                Ok(var)
+            }
 
-            _ => {
-                // Check if this is an element with LINK bindings
-                if self.compiler.expr_contains_link(expr) {
-                    // Element definitions are static but contain LINK markers
-                    // Don't add to reactive collections
-                    Ok(VarId::new(name))
-                } else {
-                    Err(format!(
-                        "Unsupported reactive pattern for '{}': {:?}",
-                        name,
-                        std::mem::discriminant(&expr.node)
-                    ))
-                }
+            // Pattern: `Document/query_params().filter`
+            // Emits nothing while the key is missing, so a LATEST default applies.
+            Expression::PostfixFieldAccess {
+                expr: params,
+                field,
+            } if matches!(&params.node, Expression::FunctionCall { path, .. }
+                    if path.iter().map(|s| s.as_str()).eq(["Document", "query_params"])) =>
+            {
+                let input_id =
+                    self.add_input(InputKind::QueryParams, Some(QUERY_PARAMS_INPUT.to_string()));
+                let params_var = self.fresh_var(QUERY_PARAMS_INPUT);
+                self.collections
+                    .insert(params_var.clone(), CollectionSpec::Input(input_id));
+                let field = field.as_str().to_string();
+                let var = VarId::new(name);
+                self.collections.insert(
+                    var.clone(),
+                    CollectionSpec::FlatMap {
+                        source: params_var,
+                        f: Arc::new(move |params: Value| params.get_field(&field).cloned()),
+                    },
+                );
+                self.reactive_vars.insert(name.to_string(), var.clone());
+                Ok(var)
             }
-        }
-    }
 
-    /// Compile a reactive arithmetic operation (e.g., `todos_count - completed_todos_count`).
-    fn compile_reactive_arithmetic(
-        &mut self,
-        name: &str,
+            // Pattern: `Document/set_query_param(key: TEXT { filter }, value: source)`
+            Expression::FunctionCall { path, arguments }
+                if {
+                    let p: Vec<&str> = path.iter().map(|s| s.as_str()).collect();
+                    p.as_slice() == ["Document", "set_query_param"]
+                } =>
+            {
+                let argument = |argument_name: &str| {
+                    arguments
+                        .iter()
+                        .find(|a| a.node.name.as_str() == argument_name)
+                        .and_then(|a| a.node.value.as_ref())
+                };
+                let key_expr = argument("key").ok_or_else(|| {
+                    format!(
+                        "Document/set_query_param missing 'key' argument for '{}'",
+                        name
+                    )
+                })?;
+                let value_expr = argument("value").ok_or_else(|| {
+                    format!(
+                        "Document/set_query_param missing 'value' argument for '{}'",
+                        name
+                    )
+                })?;
+                let key = self
+                    .compiler
+                    .eval_static(key_expr)
+                    .ok()
+                    .and_then(|key| key.as_text().map(str::to_string))
+                    .ok_or_else(|| {
+                        format!(
+                            "Document/set_query_param needs a constant Text key for '{}'",
+                            name
+                        )
+                    })?;
+
+                let value_name = format!("{}_query_value", name);
+                let source_var = self.compile_reactive_var(&value_name, value_expr)?;
+                self.reactive_vars.shift_remove(&value_name);
+
+                let var = VarId::new(name);
+                self.collections.insert(
+                    var.clone(),
+                    CollectionSpec::SideEffect {
+                        source: source_var,
+                        effect: SideEffectKind::SetQueryParam { key },
+                    },
+                );
+                self.reactive_vars.insert(name.to_string(), var.clone());
+                Ok(var)
+            }
+
+            // Pattern: `reactive_a - reactive_b` (or +, *, /)
+            Expression::ArithmeticOperator(op) => self.compile_reactive_arithmetic(name, op),
+
+            // Pattern: `reactive_a == reactive_b` (or !=, <, >, <=, >=)
+            Expression::Comparator(cmp) => self.compile_reactive_comparison(name, cmp),
+
+            // Objects with reactive fields: individual fields are compiled via
+            // flattened dotted-name variables (e.g., "theme_options.name").
+            // The parent Object itself doesn't need a DD collection.
+            Expression::Object(_) => Ok(VarId::new(name)),
+
+            _ => {
+                // Check if this is an element with LINK bindings
+                if self.compiler.expr_contains_link(expr) {
+                    // Element definitions are static but contain LINK markers
+                    // Don't add to reactive collections
+                    Ok(VarId::new(name))
+                } else {
+                    Err(format!(
+                        "Unsupported reactive pattern for '{}': {:?}",
+                        name,
+                        std::mem::discriminant(&expr.node)
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Compile a reactive arithmetic operation (e.g., `todos_count - completed_todos_count`).
+    fn compile_reactive_arithmetic(
+        &mut self,
+        name: &str,
         op: &ArithmeticOperator,
     ) -> Result<VarId, String> {
         let (operand_a, operand_b, op_fn): (_, _, Arc<dyn Fn(&Value, &Value) -> Value + 'static>) =
@@ -4245,6 +4728,16 @@ impl<'a> GraphBuilder<'a> {
                         }
                     }
 
+                    // Pattern: `... |> Math/average(reset: event)` / `... |> Math/count()`
+                    ["Math", fn_name @ ("average" | "count")] => {
+                        self.compile_running_total(name, fn_name, from, arguments)
+                    }
+
+                    // Pattern: `... |> Stream/min()` / `... |> Stream/max()`
+                    ["Stream", fn_name @ ("min" | "max")] => {
+                        self.compile_stream_extreme(name, fn_name, from)
+                    }
+
                     // Pattern: `... |> Timer/interval()`
                     ["Timer", "interval"] => self.compile_timer(name, from),
 
@@ -4316,12 +4809,18 @@ impl<'a> GraphBuilder<'a> {
                     // Pattern: `source |> List/count()`
                     ["List", "count"] => self.compile_list_count(name, from),
 
+                    // Pattern: `source |> List/count_where(item, if: predicate)`
+                    ["List", "count_where"] => self.compile_list_count_where(name, from, arguments),
+
                     // Pattern: `source |> List/latest()`
                     ["List", "latest"] => self.compile_list_latest(name, from),
 
                     // Pattern: `source |> List/every(item, if: predicate)`
                     ["List", "every"] => self.compile_list_every(name, from, arguments),
 
+                    // Pattern: `source |> List/all(item, if: predicate)`
+                    ["List", "all"] => self.compile_list_all(name, from, arguments),
+
                     // Pattern: `source |> List/any(item, if: predicate)`
                     ["List", "any"] => self.compile_list_any(name, from, arguments),
 
@@ -4341,7 +4840,7 @@ impl<'a> GraphBuilder<'a> {
                     ["List", "remove"] => self.compile_list_remove(name, from, arguments),
 
                     // Pattern: `initial |> Bool/toggle(when: event_source)`
-                    ["Bool", "toggle"] => self.compile_bool_toggle(name, from, arguments),
+                    ["Bool", "toggle"] => self.compile_bool_toggle(name, Some(from), arguments),
 
                     _ => {
                         // Try user-defined function: `source |> my_function()`
@@ -4441,8 +4940,27 @@ impl<'a> GraphBuilder<'a> {
                             // If so, use Join to combine source and arg, then Map.
                             let reactive_arg = self.find_first_reactive_argument(&args_for_builtin);
 
-                            if let Some((_arg_name, arg_var)) = reactive_arg {
+                            if let Some((arg_name, arg_var)) = reactive_arg {
                                 // Reactive argument: Join source + arg, then Map
+                                // Math/clamp joins only its reactive bound; the other
+                                // bound is read once here.
+                                let argument_scope = self.argument_scope(&args_for_builtin);
+                                let static_bounds = if fn_path == ["Math", "clamp"] {
+                                    ["min", "max"].map(|bound_name| {
+                                        args_for_builtin
+                                            .iter()
+                                            .find(|(name, _)| name == bound_name)
+                                            .and_then(|(_, val_expr)| val_expr.as_ref())
+                                            .and_then(|v| {
+                                                self.compiler
+                                                    .eval_static_with_scope(v, &argument_scope)
+                                                    .ok()
+                                            })
+                                            .and_then(|v| v.as_number())
+                                    })
+                                } else {
+                                    [None; 2]
+                                };
                                 let join_var = self.fresh_var(&format!("{}_builtin_join", name));
                                 self.collections.insert(
                                     join_var.clone(),
@@ -4458,6 +4976,7 @@ impl<'a> GraphBuilder<'a> {
                                     },
                                 );
                                 let fn_path_for_closure = fn_path.clone();
+                                let compiler = self.compiler.clone();
                                 let map_var = VarId::new(name);
                                 self.collections.insert(
                                     map_var.clone(),
@@ -4478,19 +4997,58 @@ impl<'a> GraphBuilder<'a> {
                                                 .collect();
                                             match strs.as_slice() {
                                                 ["Math", "min"] => Value::number(
-                                                    src.as_number()
-                                                        .unwrap_or(0.0)
-                                                        .min(arg.as_number().unwrap_or(0.0)),
+                                                    src.as_number().unwrap_or(0.0).min(
+                                                        arg.as_number().unwrap_or(f64::INFINITY),
+                                                    ),
                                                 ),
                                                 ["Math", "max"] => Value::number(
+                                                    src.as_number().unwrap_or(0.0).max(
+                                                        arg.as_number()
+                                                            .unwrap_or(f64::NEG_INFINITY),
+                                                    ),
+                                                ),
+                                                path @ (["Math", "modulo"]
+                                                | ["Math", "divide_int"]) => math_division(
+                                                    path,
+                                                    src.as_number().unwrap_or(0.0),
+                                                    arg.as_number().unwrap_or(1.0),
+                                                ),
+                                                ["Math", "pow"] => finite_number_or_math_error(
                                                     src.as_number()
                                                         .unwrap_or(0.0)
-                                                        .max(arg.as_number().unwrap_or(0.0)),
-                                                ),
-                                                ["Math", "modulo"] => Value::number(
-                                                    src.as_number().unwrap_or(0.0)
-                                                        % arg.as_number().unwrap_or(1.0),
+                                                        .powf(arg.as_number().unwrap_or(1.0)),
                                                 ),
+                                                ["Math", "clamp"] => {
+                                                    let [min, max] = static_bounds;
+                                                    let bound = arg.as_number();
+                                                    let (min, max) = if arg_name == "min" {
+                                                        (bound, max)
+                                                    } else {
+                                                        (min, bound)
+                                                    };
+                                                    Value::number(clamp_between(
+                                                        src.as_number().unwrap_or(0.0),
+                                                        min.unwrap_or(f64::NEG_INFINITY),
+                                                        max.unwrap_or(f64::INFINITY),
+                                                    ))
+                                                }
+                                                ["Math", "negate_if"] => {
+                                                    let n = src.as_number().unwrap_or(0.0);
+                                                    if arg.as_bool().unwrap_or(false) {
+                                                        Value::number(0.0 - n)
+                                                    } else {
+                                                        Value::number(n)
+                                                    }
+                                                }
+                                                ["Bool", "and"] => {
+                                                    let a = src.as_bool().unwrap_or(false);
+                                                    let b = arg.as_bool().unwrap_or(false);
+                                                    if a && b {
+                                                        Value::tag("True")
+                                                    } else {
+                                                        Value::tag("False")
+                                                    }
+                                                }
                                                 ["Text", "starts_with"] => {
                                                     let s = src.as_text().unwrap_or("");
                                                     let prefix = arg.as_text().unwrap_or("");
@@ -4500,6 +5058,14 @@ impl<'a> GraphBuilder<'a> {
                                                         Value::tag("False")
                                                     }
                                                 }
+                                                path if Compiler::is_list_builtin(path) => compiler
+                                                    .eval_list_builtin(
+                                                        &src,
+                                                        path,
+                                                        &args_for_builtin,
+                                                        &argument_scope,
+                                                        Some((arg_name.as_str(), &arg)),
+                                                    ),
                                                 _ => src.clone(),
                                             }
                                         }),
@@ -4511,6 +5077,7 @@ impl<'a> GraphBuilder<'a> {
 
                             // All args are static: use simple Map
                             let compiler = self.compiler.clone();
+                            let argument_scope = self.argument_scope(&args_for_builtin);
                             let map_var = VarId::new(name);
                             self.collections.insert(
                                 map_var.clone(),
@@ -4518,7 +5085,12 @@ impl<'a> GraphBuilder<'a> {
                                     source: source_var,
                                     f: Arc::new(move |input: &Value| {
                                         compiler
-                                            .eval_builtin_piped(input, &fn_path, &args_for_builtin)
+                                            .eval_builtin_piped(
+                                                input,
+                                                &fn_path,
+                                                &args_for_builtin,
+                                                &argument_scope,
+                                            )
                                             .unwrap_or(Value::Unit)
                                     }),
                                 },
@@ -5310,6 +5882,204 @@ impl<'a> GraphBuilder<'a> {
         Ok(sum_var)
     }
 
+    /// `Math/average` / `Math/count`: a HoldState of the running `sum` and `count` of
+    /// `from`, zeroed by the optional `reset:` event. Like Math/sum, nothing is
+    /// emitted before the first event.
+    fn compile_running_total(
+        &mut self,
+        name: &str,
+        fn_name: &str,
+        from: &Spanned<Expression>,
+        arguments: &[Spanned<Argument>],
+    ) -> Result<VarId, String> {
+        let pre_total_name = format!("{}_pre_total", name);
+        let source_var = self.compile_reactive_var(&pre_total_name, from)?;
+        self.reactive_vars.shift_remove(&pre_total_name);
+
+        let value_events_var = self.fresh_var(&format!("{}_total_values", name));
+        self.collections.insert(
+            value_events_var.clone(),
+            CollectionSpec::Map {
+                source: source_var,
+                f: Arc::new(|value: &Value| Value::object([("__value", value.clone())])),
+            },
+        );
+        let mut event_vars = vec![value_events_var];
+        if let Some(reset_expr) = arguments
+            .iter()
+            .find(|a| a.node.name.as_str() == "reset")
+            .and_then(|a| a.node.value.as_ref())
+        {
+            let pre_reset_name = format!("{}_pre_reset", name);
+            let reset_var = self.compile_reactive_var(&pre_reset_name, reset_expr)?;
+            self.reactive_vars.shift_remove(&pre_reset_name);
+            let reset_events_var = self.fresh_var(&format!("{}_total_resets", name));
+            self.collections.insert(
+                reset_events_var.clone(),
+                CollectionSpec::Map {
+                    source: reset_var,
+                    f: Arc::new(|_: &Value| Value::object([("__reset", Value::Unit)])),
+                },
+            );
+            event_vars.push(reset_events_var);
+        }
+        let events_var = if event_vars.len() == 1 {
+            event_vars[0].clone()
+        } else {
+            let concat_var = self.fresh_var(&format!("{}_total_events", name));
+            self.collections
+                .insert(concat_var.clone(), CollectionSpec::Concat(event_vars));
+            concat_var
+        };
+
+        let zero = Value::object([("sum", Value::number(0.0)), ("count", Value::number(0.0))]);
+        let initial_var = self.fresh_var(&format!("{}_total_init", name));
+        self.collections
+            .insert(initial_var.clone(), CollectionSpec::Literal(zero.clone()));
+        let hold_var = self.fresh_var(&format!("{}_total_hold", name));
+        let counts_every_value = fn_name == "count";
+        self.collections.insert(
+            hold_var.clone(),
+            CollectionSpec::HoldState {
+                initial: initial_var,
+                events: events_var,
+                initial_value: zero.clone(),
+                transform: Arc::new(move |state: &Value, event: &Value| {
+                    if event.get_field("__reset").is_some() {
+                        return zero.clone();
+                    }
+                    let field = |field_name: &str| {
+                        state
+                            .get_field(field_name)
+                            .and_then(|v| v.as_number())
+                            .unwrap_or(0.0)
+                    };
+                    let number = event.get_field("__value").and_then(|v| v.as_number());
+                    let number = match number {
+                        Some(number) => number,
+                        None if counts_every_value => 0.0,
+                        None => return state.clone(),
+                    };
+                    Value::object([
+                        ("sum", Value::number(field("sum") + number)),
+                        ("count", Value::number(field("count") + 1.0)),
+                    ])
+                }),
+            },
+        );
+        // Skip the initial state — only emit after the first event
+        let skipped_var = self.fresh_var(&format!("{}_total_skip", name));
+        self.collections.insert(
+            skipped_var.clone(),
+            CollectionSpec::Skip {
+                source: hold_var,
+                count: 1,
+            },
+        );
+        let total_var = VarId::new(name);
+        self.collections.insert(
+            total_var.clone(),
+            CollectionSpec::Map {
+                source: skipped_var,
+                f: Arc::new(move |state: &Value| {
+                    let field = |field_name: &str| {
+                        state
+                            .get_field(field_name)
+                            .and_then(|v| v.as_number())
+                            .unwrap_or(0.0)
+                    };
+                    let count = field("count");
+                    if counts_every_value {
+                        Value::number(count)
+                    } else if count == 0.0 {
+                        Value::number(0.0)
+                    } else {
+                        Value::number(field("sum") / count)
+                    }
+                }),
+            },
+        );
+        self.reactive_vars
+            .insert(name.to_string(), total_var.clone());
+        Ok(total_var)
+    }
+
+    /// `Stream/min` / `Stream/max`: a HoldState of the extreme number seen on `from`,
+    /// which changes only when a new extreme arrives. It is persisted like a LATEST
+    /// sum, so a restored extreme is emitted before any new input.
+    fn compile_stream_extreme(
+        &mut self,
+        name: &str,
+        fn_name: &str,
+        from: &Spanned<Expression>,
+    ) -> Result<VarId, String> {
+        let pre_extreme_name = format!("{}_pre_extreme", name);
+        let source_var = self.compile_reactive_var(&pre_extreme_name, from)?;
+        self.reactive_vars.shift_remove(&pre_extreme_name);
+
+        // Unit until the first number, unless an extreme was persisted
+        let initial_value = self
+            .persisted_holds
+            .get(name)
+            .cloned()
+            .unwrap_or(Value::Unit);
+        let initial_var = self.fresh_var(&format!("{}_extreme_init", name));
+        self.collections.insert(
+            initial_var.clone(),
+            CollectionSpec::Literal(initial_value.clone()),
+        );
+        let keeps_lowest = fn_name == "min";
+        let hold_var = self.fresh_var(&format!("{}_extreme_hold", name));
+        self.collections.insert(
+            hold_var.clone(),
+            CollectionSpec::HoldState {
+                initial: initial_var,
+                events: source_var,
+                initial_value,
+                transform: Arc::new(move |state: &Value, event: &Value| {
+                    let Some(number) = event.as_number() else {
+                        return state.clone();
+                    };
+                    match state.as_number() {
+                        Some(extreme) if keeps_lowest && extreme <= number => state.clone(),
+                        Some(extreme) if !keeps_lowest && extreme >= number => state.clone(),
+                        _ => Value::number(number),
+                    }
+                }),
+            },
+        );
+        if let Some(key) = self.storage_key.clone() {
+            let persist_var = self.fresh_var(&format!("{}_persist", name));
+            self.collections.insert(
+                persist_var,
+                CollectionSpec::SideEffect {
+                    source: hold_var.clone(),
+                    effect: SideEffectKind::PersistHold {
+                        key,
+                        hold_name: name.to_string(),
+                    },
+                },
+            );
+        }
+        let extreme_var = VarId::new(name);
+        self.collections.insert(
+            extreme_var.clone(),
+            CollectionSpec::FlatMap {
+                source: hold_var,
+                f: Arc::new(|v: Value| {
+                    if matches!(v, Value::Unit) {
+                        None
+                    } else {
+                        Some(v)
+                    }
+                }),
+            },
+        );
+        self.reactive_vars
+            .insert(name.to_string(), extreme_var.clone());
+        Ok(extreme_var)
+    }
+
     fn compile_document_expr(&mut self, expr: &Spanned<Expression>) -> Result<VarId, String> {
         let trace_compile = std::env::var_os("BOON_DD_TRACE_COMPILE").is_some();
         #[cfg(test)]
@@ -5585,7 +6355,11 @@ impl<'a> GraphBuilder<'a> {
                     path_strs.as_slice(),
                     ["Timer", "interval"]
                         | ["Math", "sum"]
+                        | ["Math", "average"]
+                        | ["Math", "count"]
                         | ["Stream", "skip"]
+                        | ["Stream", "min"]
+                        | ["Stream", "max"]
                         | ["Document", "new"]
                 )
             }
@@ -6677,6 +7451,39 @@ impl<'a> GraphBuilder<'a> {
     /// Resolve an alias expression to its reactive VarId.
     /// Check if any argument in a built-in piped function references a reactive variable.
     /// Returns the first reactive argument's name and VarId, if any.
+    /// Static values of the scope-prefixed siblings the arguments reference, keyed by
+    /// their short name, so `count |> Math/min(with: limit)` inside `store` reads
+    /// `store.limit` just like the piped `count` resolves to `store.count`.
+    fn argument_scope(
+        &self,
+        args: &[(String, Option<Spanned<Expression>>)],
+    ) -> IndexMap<String, Value> {
+        let mut scope = IndexMap::new();
+        let Some(prefix) = &self.scope_prefix else {
+            return scope;
+        };
+        for (name, expr) in self.compiler.variables.iter() {
+            let Some(short_name) = name
+                .strip_prefix(prefix.as_str())
+                .and_then(|rest| rest.strip_prefix('.'))
+                .filter(|short_name| !short_name.contains('.'))
+            else {
+                continue;
+            };
+            let referenced = args.iter().any(|(_, arg_expr)| {
+                arg_expr
+                    .as_ref()
+                    .is_some_and(|arg_expr| Self::expr_references_name(arg_expr, short_name))
+            });
+            if referenced && !self.compiler.is_reactive(expr) {
+                if let Ok(value) = self.compiler.eval_static(expr) {
+                    scope.insert(short_name.to_string(), value);
+                }
+            }
+        }
+        scope
+    }
+
     fn find_first_reactive_argument(
         &self,
         args: &[(String, Option<Spanned<Expression>>)],
@@ -6766,6 +7573,17 @@ impl<'a> GraphBuilder<'a> {
                         .insert(ROUTER_INPUT.to_string(), router_var.clone());
                     return Ok(router_var);
                 }
+                ["Document", "query_params"] => {
+                    // Like Router/route(), with the query parameters as an object
+                    let input_id = self
+                        .add_input(InputKind::QueryParams, Some(QUERY_PARAMS_INPUT.to_string()));
+                    let params_var = self.fresh_var(QUERY_PARAMS_INPUT);
+                    self.collections
+                        .insert(params_var.clone(), CollectionSpec::Input(input_id));
+                    self.reactive_vars
+                        .insert(QUERY_PARAMS_INPUT.to_string(), params_var.clone());
+                    return Ok(params_var);
+                }
                 _ => {}
             }
         }
@@ -7356,6 +8174,50 @@ impl<'a> GraphBuilder<'a> {
         Ok(count_var)
     }
 
+    /// Compile `source |> List/count_where(item, if: predicate)`.
+    ///
+    /// A keyed source counts through the same inline retain as
+    /// `List/retain(..) |> List/count()`, so the count follows predicate flips,
+    /// membership and bulk toggles without a materialized filtered list.
+    fn compile_list_count_where(
+        &mut self,
+        name: &str,
+        from: &Spanned<Expression>,
+        arguments: &[Spanned<Argument>],
+    ) -> Result<VarId, String> {
+        if let Some(keyed_var) = self.resolve_keyed_source(from) {
+            let retain_var = self.compile_inline_keyed_retain(keyed_var, arguments, &[])?;
+            return self.build_empty_safe_keyed_count(name, retain_var);
+        }
+
+        // Scalar fallback: compile source and count with the list builtin
+        let source_var = self.resolve_list_source(from)?;
+        let arguments: Vec<(String, Option<Spanned<Expression>>)> = arguments
+            .iter()
+            .map(|a| (a.node.name.as_str().to_string(), a.node.value.clone()))
+            .collect();
+        let compiler = self.compiler.clone();
+        let count_var = VarId::new(name);
+        self.collections.insert(
+            count_var.clone(),
+            CollectionSpec::Map {
+                source: source_var,
+                f: Arc::new(move |v: &Value| {
+                    compiler.eval_list_builtin(
+                        v,
+                        &["List", "count_where"],
+                        &arguments,
+                        &IndexMap::new(),
+                        None,
+                    )
+                }),
+            },
+        );
+        self.reactive_vars
+            .insert(name.to_string(), count_var.clone());
+        Ok(count_var)
+    }
+
     /// Compile `source |> List/latest()`.
     ///
     /// Reduces a keyed `(ListKey, Value)` collection to a scalar `Value`
@@ -8450,7 +9312,19 @@ impl<'a> GraphBuilder<'a> {
         from: &Spanned<Expression>,
         arguments: &[Spanned<Argument>],
     ) -> Result<VarId, String> {
-        self.compile_list_aggregate_bool(name, from, arguments, true)
+        self.compile_list_aggregate_bool(name, from, arguments, "every")
+    }
+
+    /// Compile `source |> List/all(item, if: predicate)`.
+    ///
+    /// Like List/every, but True on an empty list.
+    fn compile_list_all(
+        &mut self,
+        name: &str,
+        from: &Spanned<Expression>,
+        arguments: &[Spanned<Argument>],
+    ) -> Result<VarId, String> {
+        self.compile_list_aggregate_bool(name, from, arguments, "all")
     }
 
     /// Compile `source |> List/any(item, if: predicate)`.
@@ -8462,17 +9336,17 @@ impl<'a> GraphBuilder<'a> {
         from: &Spanned<Expression>,
         arguments: &[Spanned<Argument>],
     ) -> Result<VarId, String> {
-        self.compile_list_aggregate_bool(name, from, arguments, false)
+        self.compile_list_aggregate_bool(name, from, arguments, "any")
     }
 
-    /// Shared implementation for List/every and List/any.
-    /// `is_every=true` → ListEvery, `is_every=false` → ListAny.
+    /// Shared implementation for List/every, List/all and List/any.
+    /// `every` and `all` → ListEvery, `any` → ListAny.
     fn compile_list_aggregate_bool(
         &mut self,
         name: &str,
         from: &Spanned<Expression>,
         arguments: &[Spanned<Argument>],
-        is_every: bool,
+        function_name: &str,
     ) -> Result<VarId, String> {
         let item_param: String = arguments
             .iter()
@@ -8484,21 +9358,11 @@ impl<'a> GraphBuilder<'a> {
             .iter()
             .find(|a| a.node.name.as_str() == "if")
             .and_then(|a| a.node.value.as_ref())
-            .ok_or_else(|| {
-                format!(
-                    "List/{} missing 'if' argument for '{}'",
-                    if is_every { "every" } else { "any" },
-                    name
-                )
-            })?;
+            .ok_or_else(|| format!("List/{function_name} missing 'if' argument for '{name}'"))?;
 
         // Resolve keyed source
         let keyed_var = self.resolve_keyed_source(from).ok_or_else(|| {
-            format!(
-                "List/{} requires a keyed list source for '{}'",
-                if is_every { "every" } else { "any" },
-                name
-            )
+            format!("List/{function_name} requires a keyed list source for '{name}'")
         })?;
 
         // Build predicate closure
@@ -8515,15 +9379,16 @@ impl<'a> GraphBuilder<'a> {
         });
 
         let result_var = VarId::new(name);
-        let spec = if is_every {
-            CollectionSpec::ListEvery {
+        let spec = if function_name == "any" {
+            CollectionSpec::ListAny {
                 source: keyed_var,
                 predicate,
             }
         } else {
-            CollectionSpec::ListAny {
+            CollectionSpec::ListEvery {
                 source: keyed_var,
                 predicate,
+                true_when_empty: function_name == "all",
             }
         };
         self.collections.insert(result_var.clone(), spec);
@@ -8592,30 +9457,54 @@ impl<'a> GraphBuilder<'a> {
         Ok(result_var)
     }
 
-    /// Compile `initial |> Bool/toggle(when: event_source)`.
+    /// Compile `initial |> Bool/toggle(when: event_source)` and its unpiped spelling
+    /// `Bool/toggle(on: event_source, initial: False)`, where `initial` defaults to False.
     ///
     /// Desugars to: `HoldState { initial, events, transform: |state, _| !state }`.
+    /// The unpiped spelling replaces a HOLD, so like a HOLD it is persisted: a persisted
+    /// Bool replaces the initial value and every flip is persisted. The piped one is not.
     fn compile_bool_toggle(
         &mut self,
         name: &str,
-        from: &Spanned<Expression>,
+        from: Option<&Spanned<Expression>>,
         arguments: &[Spanned<Argument>],
     ) -> Result<VarId, String> {
-        // Extract `when:` argument
-        let when_expr = arguments
-            .iter()
-            .find(|a| a.node.name.as_str() == "when")
-            .and_then(|a| a.node.value.as_ref())
-            .ok_or_else(|| format!("Bool/toggle missing 'when' argument for '{}'", name))?;
-
-        // Compile initial value
-        let initial_var = self.compile_reactive_var(&format!("{}_initial", name), from)?;
+        let argument = |argument_name: &str| {
+            arguments
+                .iter()
+                .find(|a| a.node.name.as_str() == argument_name)
+                .and_then(|a| a.node.value.as_ref())
+        };
+        // Extract `when:` (piped) or `on:` (unpiped) argument
+        let when_expr = argument("when")
+            .or_else(|| argument("on"))
+            .ok_or_else(|| format!("Bool/toggle missing 'on' argument for '{}'", name))?;
+        let persisted = from.is_none();
+        let from = from.or_else(|| argument("initial"));
+
+        // Compile initial value, unless a persisted one takes its place
+        let persisted_value = if persisted {
+            self.persisted_holds.get(name).cloned()
+        } else {
+            None
+        };
+        let initial_var = match (&persisted_value, from) {
+            (None, Some(from)) => self.compile_reactive_var(&format!("{}_initial", name), from)?,
+            _ => {
+                let initial_var = self.fresh_var(&format!("{}_initial", name));
+                self.collections.insert(
+                    initial_var.clone(),
+                    CollectionSpec::Literal(persisted_value.clone().unwrap_or(Value::tag("False"))),
+                );
+                initial_var
+            }
+        };
 
         // Evaluate initial value statically for HoldState
-        let initial_value = self
-            .compiler
-            .eval_static(from)
-            .unwrap_or(Value::tag("False"));
+        let initial_value = persisted_value.unwrap_or_else(|| {
+            from.and_then(|from| self.compiler.eval_static(from).ok())
+                .unwrap_or(Value::tag("False"))
+        });
 
         // Compile the `when:` event source
         let (events_var, _) = self.compile_event_source(when_expr)?;
@@ -8638,6 +9527,19 @@ impl<'a> GraphBuilder<'a> {
                 }),
             },
         );
+        if persisted && let Some(key) = self.storage_key.clone() {
+            let persist_var = self.fresh_var(&format!("{}_persist", name));
+            self.collections.insert(
+                persist_var,
+                CollectionSpec::SideEffect {
+                    source: toggle_var.clone(),
+                    effect: SideEffectKind::PersistHold {
+                        key,
+                        hold_name: name.to_string(),
+                    },
+                },
+            );
+        }
         self.reactive_vars
             .insert(name.to_string(), toggle_var.clone());
         Ok(toggle_var)
@@ -8687,7 +9589,8 @@ impl<'a> GraphBuilder<'a> {
     /// Compile a keyed ListRetain inline (not as a named variable).
     ///
     /// Used by compile_list_count for `keyed |> List/retain(...) |> List/count()`
-    /// chains. Returns the keyed VarId of the retain result.
+    /// chains and by compile_list_count_where. Returns the keyed VarId of the
+    /// retain result.
     fn compile_inline_keyed_retain(
         &mut self,
         keyed_var: VarId,
@@ -11794,10 +12697,12 @@ impl<'a> GraphBuilder<'a> {
             Some(CollectionSpec::HoldState { .. }) => true,
             Some(CollectionSpec::KeyedHoldState { .. }) => true,
             Some(CollectionSpec::Input(input_id)) => {
-                // Router inputs have initial values (current route), LINK inputs don't
-                self.inputs
-                    .iter()
-                    .any(|spec| spec.id == *input_id && spec.kind == InputKind::Router)
+                // Router and query parameter inputs have initial values (current
+                // route and query), LINK inputs don't
+                self.inputs.iter().any(|spec| {
+                    spec.id == *input_id
+                        && matches!(spec.kind, InputKind::Router | InputKind::QueryParams)
+                })
             }
             Some(CollectionSpec::Map { source, .. }) => self.has_initial_value(source),
             Some(CollectionSpec::Then { source, .. }) => self.has_initial_value(source),
@@ -12903,6 +13808,26 @@ fn capitalize(s: &str) -> String {
     }
 }
 
+/// `Math/divide_int` (floored) or `Math/modulo` (with the sign of `by`) of `a`;
+/// dividing by zero gives the `MathError` tag.
+fn math_division(path: &[&str], a: f64, by: f64) -> Value {
+    let result = match path {
+        ["Math", "divide_int"] => floored_division(a, by),
+        _ => floored_modulo(a, by),
+    };
+    finite_number_or_math_error(result)
+}
+
+/// `result` as a Number, or the `MathError` tag when it is NaN or infinite, so
+/// `Math/sqrt` and `Math/pow` never leak "NaN" into text.
+fn finite_number_or_math_error(result: f64) -> Value {
+    if result.is_finite() {
+        Value::number(result)
+    } else {
+        Value::tag("MathError")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
@@ -12910,7 +13835,8 @@ mod tests {
         decorate_reactive_scope_value, inject_item_link_paths_with_key, parse_source,
     };
     use crate::core::types::{
-        DEP_FIELD_PREFIX, HOVER_PATH_FIELD, InputKind, LINK_PATH_FIELD, ListKey, VarId,
+        DEP_FIELD_PREFIX, HOVER_PATH_FIELD, InputKind, LINK_PATH_FIELD, ListKey, SideEffectKind,
+        VarId,
     };
     use crate::core::value::Value;
     use boon::parser::static_expression::Expression;
@@ -14374,6 +15300,98 @@ document: Document/new(root:
         );
     }
 
+    /// Indented dump of an element tree for golden comparisons; internal `__` fields are skipped.
+    fn element_tree(value: &Value, indent: usize, out: &mut String) {
+        let fields = match value {
+            Value::Tagged { tag, fields } => {
+                out.push_str(tag);
+                fields
+            }
+            Value::Object(fields) => {
+                out.push_str("[]");
+                fields
+            }
+            Value::Text(text) => {
+                out.push_str(&format!("{text:?}"));
+                return;
+            }
+            other => {
+                out.push_str(&other.to_display_string());
+                return;
+            }
+        };
+        for (name, field) in fields.iter() {
+            if name.starts_with("__") {
+                continue;
+            }
+            out.push('\n');
+            out.push_str(&"  ".repeat(indent + 1));
+            out.push_str(&format!("{name}: "));
+            element_tree(field, indent + 1, out);
+        }
+    }
+
+    #[test]
+    fn aliased_builtin_compiles_like_its_new_name() {
+        let fibonacci =
+            read_example("../../playground/frontend/src/examples/fibonacci/fibonacci.bn");
+        assert!(fibonacci.contains("Stream/sequence()"));
+        let aliased = fibonacci.replace("Stream/sequence()", "Stream/pulses()");
+
+        let document_tree = |source: &str| {
+            let program = compile(source, None, &std::collections::HashMap::new(), None)
+                .expect("fibonacci should compile");
+            let CompiledProgram::Static { document_value, .. } = program else {
+                panic!("expected static document");
+            };
+            let mut tree = String::new();
+            element_tree(&document_value, 0, &mut tree);
+            tree
+        };
+
+        let tree = document_tree(&fibonacci);
+        assert!(tree.contains("10. Fibonacci number is 55"), "{tree}");
+        assert_eq!(document_tree(&aliased), tree);
+    }
+
+    #[test]
+    fn element_code_golden_tree() {
+        let source = r#"
+document: Document/new(root:
+    Element/code(
+        element: []
+        style: [font: [size: 14]]
+        language: Boon
+        content: TEXT #{ counter: LATEST { 0 } }
+    )
+)
+"#;
+
+        let program = compile(source, None, &std::collections::HashMap::new(), None)
+            .expect("Element/code document should compile");
+
+        let CompiledProgram::Static { document_value, .. } = program else {
+            panic!("expected static document");
+        };
+
+        let root = document_value.get_field("root").expect("document root");
+        let mut tree = String::new();
+        element_tree(root, 0, &mut tree);
+        assert_eq!(
+            tree,
+            [
+                "ElementCode",
+                "  content: \"counter: LATEST { 0 }\"",
+                "  element: []",
+                "  language: Boon",
+                "  style: []",
+                "    font: []",
+                "      size: 14",
+            ]
+            .join("\n")
+        );
+    }
+
     #[test]
     fn inject_item_link_paths_covers_generic_element_groups() {
         let item = Value::object([
@@ -14615,4 +15633,885 @@ document: Document/new(root:
             Some("cells.0001.0001.cell_elements.display")
         );
     }
+
+    /// The `root` of a static program's document.
+    fn static_document_root(source: &str) -> Value {
+        let program = compile(source, None, &std::collections::HashMap::new(), None)
+            .expect("program should compile");
+        let CompiledProgram::Static { document_value, .. } = program else {
+            panic!("expected static document");
+        };
+        document_value
+            .get_field("root")
+            .cloned()
+            .expect("document root")
+    }
+
+    fn display_items(list: Option<&Value>) -> Vec<String> {
+        list.expect("list field")
+            .list_items()
+            .into_iter()
+            .map(Value::to_display_string)
+            .collect()
+    }
+
+    #[test]
+    fn list_sort_by_is_stable_and_puts_missing_keys_last() {
+        let root = static_document_root(
+            r#"
+items: LIST {
+    [title: TEXT { b }, priority: 2]
+    [title: TEXT { undated }]
+    [title: TEXT { a }, priority: 1]
+    [title: TEXT { c }, priority: 2]
+}
+document: Document/new(root: [
+    numbers: LIST { 3, 1, 2 } |> List/sort_by(item, key: item)
+    titles: items |> List/sort_by(item, key: item.priority) |> List/map(item, new: item.title)
+])
+"#,
+        );
+
+        assert_eq!(display_items(root.get_field("numbers")), ["1", "2", "3"]);
+        assert_eq!(
+            display_items(root.get_field("titles")),
+            ["a", "b", "c", "undated"]
+        );
+    }
+
+    #[test]
+    fn list_filter_views_leave_the_source_whole() {
+        let root = static_document_root(
+            r#"
+todos: LIST {
+    [title: TEXT { a }, completed: True]
+    [title: TEXT { b }, completed: False]
+    [title: TEXT { c }, completed: True]
+}
+document: Document/new(root: [
+    active: todos |> List/filter(item, if: item.completed |> Bool/not()) |> List/map(item, new: item.title)
+    completed: todos |> List/filter(item, if: item.completed) |> List/map(item, new: item.title)
+    all: todos |> List/map(item, new: item.title)
+])
+"#,
+        );
+
+        assert_eq!(display_items(root.get_field("active")), ["b"]);
+        assert_eq!(display_items(root.get_field("completed")), ["a", "c"]);
+        assert_eq!(display_items(root.get_field("all")), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn list_first_and_last_are_none_on_an_empty_list() {
+        let root = static_document_root(
+            r#"
+numbers: LIST { 1 2 3 }
+document: Document/new(root: [
+    first: numbers |> List/first()
+    last: numbers |> List/last()
+    empty_first: LIST {} |> List/first()
+    empty_last: LIST {} |> List/last() |> WHEN {
+        None => TEXT { Nothing yet }
+        item => item
+    }
+])
+"#,
+        );
+
+        assert_eq!(root.get_field("first"), Some(&Value::number(1.0)));
+        assert_eq!(root.get_field("last"), Some(&Value::number(3.0)));
+        assert_eq!(root.get_field("empty_first"), Some(&Value::tag("None")));
+        assert_eq!(
+            root.get_field("empty_last"),
+            Some(&Value::text("Nothing yet"))
+        );
+    }
+
+    #[test]
+    fn list_reverse_reverses_items_and_keeps_an_empty_list_empty() {
+        let root = static_document_root(
+            r#"
+document: Document/new(root: [
+    reversed: LIST { TEXT { a } TEXT { b } TEXT { c } } |> List/reverse()
+    empty: LIST {} |> List/reverse()
+])
+"#,
+        );
+
+        assert_eq!(display_items(root.get_field("reversed")), ["c", "b", "a"]);
+        assert_eq!(display_items(root.get_field("empty")), Vec::<String>::new());
+    }
+
+    #[test]
+    fn list_contains_and_index_of_compare_structurally() {
+        let root = static_document_root(
+            r#"
+items: LIST { 1, TEXT { two }, Green, Green, True }
+document: Document/new(root: [
+    number: items |> List/contains(value: 1)
+    text: items |> List/contains(value: TEXT { two })
+    tag: items |> List/contains(value: Green)
+    bool: items |> List/contains(value: True)
+    missing: items |> List/contains(value: 4)
+    point: LIST { [x: 1, y: 2], [x: 3, y: 4] } |> List/contains(value: [y: 4, x: 3])
+    green_position: items |> List/index_of(value: Green)
+    missing_position: items |> List/index_of(value: Blue)
+])
+"#,
+        );
+
+        assert_eq!(root.get_field("number"), Some(&Value::tag("True")));
+        assert_eq!(root.get_field("text"), Some(&Value::tag("True")));
+        assert_eq!(root.get_field("tag"), Some(&Value::tag("True")));
+        assert_eq!(root.get_field("bool"), Some(&Value::tag("True")));
+        assert_eq!(root.get_field("missing"), Some(&Value::tag("False")));
+        assert_eq!(root.get_field("point"), Some(&Value::tag("True")));
+        assert_eq!(root.get_field("green_position"), Some(&Value::number(3.0)));
+        assert_eq!(
+            root.get_field("missing_position"),
+            Some(&Value::tag("None"))
+        );
+    }
+
+    #[test]
+    fn list_index_of_follows_a_reactive_searched_value() {
+        let source = r#"
+reset: LINK
+next: LINK
+items: LIST { 2, 3 } |> HOLD state {
+    reset |> THEN { LIST { 2, 3 } }
+}
+searched: 1 |> HOLD state {
+    next |> THEN { state + 1 }
+}
+position: items |> List/index_of(value: searched)
+document: Document/new(root: position)
+"#;
+        let program = compile(source, None, &std::collections::HashMap::new(), None)
+            .expect("program should compile");
+        let CompiledProgram::Dataflow { graph } = program else {
+            panic!("expected a reactive searched value to compile as dataflow");
+        };
+        let Some(CollectionSpec::Map { source, f }) =
+            graph.collections.get(&VarId::new("position"))
+        else {
+            panic!("position should compile to a Map");
+        };
+        assert!(
+            matches!(
+                graph.collections.get(source),
+                Some(CollectionSpec::Join { .. })
+            ),
+            "position should join the list with the searched value"
+        );
+
+        let items = Value::list_from_items([Value::number(2.0), Value::number(3.0)]);
+        let joined = |searched: f64| {
+            Value::object([("__src", items.clone()), ("__arg", Value::number(searched))])
+        };
+        assert_eq!(f(&joined(1.0)), Value::tag("None"));
+        assert_eq!(f(&joined(3.0)), Value::number(2.0));
+    }
+
+    #[test]
+    fn list_zip_pairs_items_up_to_the_shorter_list() {
+        let root = static_document_root(
+            r#"
+document: Document/new(root: [
+    pairs: LIST { 1, 2, 3 } |> List/zip(with: LIST { TEXT { a }, TEXT { b } })
+    shorter_source: LIST { 1 } |> List/zip(with: LIST { TEXT { a }, TEXT { b } })
+    sums: LIST { 1, 2 }
+        |> List/zip(with: LIST { 10, 20 })
+        |> List/map(item, new: item.first + item.second)
+    empty: LIST {} |> List/zip(with: LIST { 1 })
+])
+"#,
+        );
+
+        assert_eq!(
+            display_items(root.get_field("pairs")),
+            ["[first: 1, second: a]", "[first: 2, second: b]"]
+        );
+        assert_eq!(
+            display_items(root.get_field("shorter_source")),
+            ["[first: 1, second: a]"]
+        );
+        assert_eq!(display_items(root.get_field("sums")), ["11", "22"]);
+        assert_eq!(display_items(root.get_field("empty")), Vec::<String>::new());
+    }
+
+    #[test]
+    fn list_sum_min_max_skip_items_that_are_not_numbers() {
+        let root = static_document_root(
+            r#"
+prices: LIST { 3, TEXT { free }, 1.5, 4 }
+document: Document/new(root: [
+    total: prices |> List/sum()
+    cheapest: prices |> List/min()
+    priciest: prices |> List/max()
+    empty_total: LIST {} |> List/sum()
+    empty_cheapest: LIST {} |> List/min()
+    empty_priciest: LIST {} |> List/max()
+])
+"#,
+        );
+
+        assert_eq!(root.get_field("total"), Some(&Value::number(8.5)));
+        assert_eq!(root.get_field("cheapest"), Some(&Value::number(1.5)));
+        assert_eq!(root.get_field("priciest"), Some(&Value::number(4.0)));
+        assert_eq!(root.get_field("empty_total"), Some(&Value::number(0.0)));
+        assert_eq!(root.get_field("empty_cheapest"), Some(&Value::tag("None")));
+        assert_eq!(root.get_field("empty_priciest"), Some(&Value::tag("None")));
+    }
+
+    #[test]
+    fn list_all_is_true_on_an_empty_list() {
+        let root = static_document_root(
+            r#"
+document: Document/new(root: [
+    any_even: LIST { 1, 2, 3 } |> List/any(item, if: item == 2)
+    all_positive: LIST { 1, 2, 3 } |> List/all(item, if: item > 0)
+    all_even: LIST { 1, 2, 3 } |> List/all(item, if: item == 2)
+    every: LIST { 2, 2 } |> List/every(item, if: item == 2)
+    empty: LIST {} |> List/all(item, if: False)
+    empty_any: LIST {} |> List/any(item, if: True)
+])
+"#,
+        );
+
+        assert_eq!(root.get_field("any_even"), Some(&Value::tag("True")));
+        assert_eq!(root.get_field("all_positive"), Some(&Value::tag("True")));
+        assert_eq!(root.get_field("all_even"), Some(&Value::tag("False")));
+        assert_eq!(root.get_field("every"), Some(&Value::tag("True")));
+        assert_eq!(root.get_field("empty"), Some(&Value::tag("True")));
+        assert_eq!(root.get_field("empty_any"), Some(&Value::tag("False")));
+    }
+
+    #[test]
+    fn todo_mvc_all_completed_is_a_list_all_over_the_todos() {
+        let source = read_example("../../playground/frontend/src/examples/todo_mvc/todo_mvc.bn");
+        let program = compile(&source, None, &std::collections::HashMap::new(), None)
+            .expect("todo_mvc should compile");
+        let CompiledProgram::Dataflow { graph } = program else {
+            panic!("expected todo_mvc to compile as dataflow");
+        };
+
+        assert!(
+            matches!(
+                graph.collections.get(&VarId::new("store.all_completed")),
+                Some(CollectionSpec::ListEvery {
+                    true_when_empty: true,
+                    ..
+                })
+            ),
+            "store.all_completed should compile to ListEvery that is True when empty"
+        );
+    }
+
+    #[test]
+    fn todo_mvc_active_count_is_a_list_count_where_over_the_todos() {
+        let source = read_example("../../playground/frontend/src/examples/todo_mvc/todo_mvc.bn");
+        let program = compile(&source, None, &std::collections::HashMap::new(), None)
+            .expect("todo_mvc should compile");
+        let CompiledProgram::Dataflow { graph } = program else {
+            panic!("expected todo_mvc to compile as dataflow");
+        };
+
+        assert!(
+            matches!(
+                graph
+                    .collections
+                    .get(&VarId::new("store.active_todos_count")),
+                Some(CollectionSpec::HoldState { .. })
+            ),
+            "store.active_todos_count should compile to a keyed count of the retained todos"
+        );
+    }
+
+    #[test]
+    fn list_count_where_counts_matching_items() {
+        let root = static_document_root(
+            r#"
+numbers: LIST { 1, 2, 3, 4, 5 }
+document: Document/new(root: [
+    count_where: numbers |> List/count_where(item, if: item > 2)
+    retain_count: numbers |> List/retain(item, if: item > 2) |> List/count()
+    empty: LIST {} |> List/count_where(item, if: True)
+])
+"#,
+        );
+        assert_eq!(root.get_field("count_where"), Some(&Value::number(3.0)));
+        assert_eq!(root.get_field("retain_count"), Some(&Value::number(3.0)));
+        assert_eq!(root.get_field("empty"), Some(&Value::number(0.0)));
+    }
+
+    #[test]
+    fn list_get_is_one_based_and_none_outside_the_list() {
+        let root = static_document_root(
+            r#"
+items: LIST { TEXT { a }, TEXT { b }, TEXT { c } }
+document: Document/new(root: [
+    first: items |> List/get(index: 1)
+    third: items |> List/get(index: 3)
+    zero: items |> List/get(index: 0)
+    negative: items |> List/get(index: -1)
+    fraction: items |> List/get(index: 1.5)
+    past_end: items |> List/get(index: 4)
+    empty: LIST {} |> List/get(index: 1)
+])
+"#,
+        );
+
+        assert_eq!(root.get_field("first"), Some(&Value::text("a")));
+        assert_eq!(root.get_field("third"), Some(&Value::text("c")));
+        for name in ["zero", "negative", "fraction", "past_end", "empty"] {
+            assert_eq!(root.get_field(name), Some(&Value::tag("None")), "{name}");
+        }
+    }
+
+    #[test]
+    fn list_unique_and_unique_by_keep_first_occurrences() {
+        let root = static_document_root(
+            r#"
+todos: LIST {
+    [title: TEXT { Milk }, category: Shopping]
+    [title: TEXT { Report }, category: Work]
+    [title: TEXT { Bread }, category: Shopping]
+}
+document: Document/new(root: [
+    letters: LIST { B, A, B, C, A } |> List/unique()
+    points: LIST { [x: 1, y: 2], [x: 1, y: 3], [x: 1, y: 2] } |> List/unique()
+    first_per_category: todos
+        |> List/unique_by(item, key: item.category)
+        |> List/map(item, new: item.title)
+    empty: LIST {} |> List/unique()
+])
+"#,
+        );
+
+        assert_eq!(display_items(root.get_field("letters")), ["B", "A", "C"]);
+        assert_eq!(
+            display_items(root.get_field("points")),
+            ["[x: 1, y: 2]", "[x: 1, y: 3]"]
+        );
+        assert_eq!(
+            display_items(root.get_field("first_per_category")),
+            ["Milk", "Report"]
+        );
+        assert_eq!(display_items(root.get_field("empty")), Vec::<String>::new());
+    }
+
+    #[test]
+    fn list_concat_appends_the_second_list() {
+        let root = static_document_root(
+            r#"
+document: Document/new(root: [
+    notes: LIST { TEXT { a }, TEXT { b } } |> List/concat(with: LIST { TEXT { c } })
+    empty_first: LIST {} |> List/concat(with: LIST { TEXT { a } })
+    empty_second: LIST { TEXT { a } } |> List/concat(with: LIST {})
+])
+"#,
+        );
+
+        assert_eq!(display_items(root.get_field("notes")), ["a", "b", "c"]);
+        assert_eq!(display_items(root.get_field("empty_first")), ["a"]);
+        assert_eq!(display_items(root.get_field("empty_second")), ["a"]);
+    }
+
+    #[test]
+    fn text_join_stringifies_items_like_interpolation() {
+        let root = static_document_root(
+            r#"
+document: Document/new(root: [
+    colors: LIST { TEXT { red }, TEXT { green } } |> Text/join(separator: TEXT { ,  })
+    mixed: LIST { 3, Urgent, TEXT { ok } } |> Text/join(separator: TEXT { / })
+    single: LIST { TEXT { red } } |> Text/join(separator: TEXT { ,  })
+    empty: LIST {} |> Text/join(separator: TEXT { ,  })
+])
+"#,
+        );
+
+        assert_eq!(root.get_field("colors"), Some(&Value::text("red, green")));
+        assert_eq!(root.get_field("mixed"), Some(&Value::text("3/Urgent/ok")));
+        assert_eq!(root.get_field("single"), Some(&Value::text("red")));
+        assert_eq!(root.get_field("empty"), Some(&Value::text("")));
+    }
+
+    #[test]
+    fn text_split_keeps_empty_parts_and_gives_no_parts_for_empty_text() {
+        let root = static_document_root(
+            r#"
+document: Document/new(root: [
+    colors: TEXT { red,green,blue } |> Text/split(separator: TEXT { , })
+    single: TEXT { red } |> Text/split(separator: TEXT { , })
+    consecutive: TEXT { a,,b } |> Text/split(separator: TEXT { , })
+    leading: TEXT { ,a } |> Text/split(separator: TEXT { , })
+    trailing: TEXT { a,b, } |> Text/split(separator: TEXT { , })
+    empty: Text/empty() |> Text/split(separator: TEXT { , })
+    count: TEXT { one and two } |> Text/split(separator: TEXT {  and  }) |> List/count()
+])
+"#,
+        );
+
+        assert_eq!(
+            display_items(root.get_field("colors")),
+            ["red", "green", "blue"]
+        );
+        assert_eq!(display_items(root.get_field("single")), ["red"]);
+        assert_eq!(display_items(root.get_field("consecutive")), ["a", "", "b"]);
+        assert_eq!(display_items(root.get_field("leading")), ["", "a"]);
+        assert_eq!(display_items(root.get_field("trailing")), ["a", "b", ""]);
+        assert_eq!(display_items(root.get_field("empty")), Vec::<String>::new());
+        assert_eq!(root.get_field("count"), Some(&Value::number(2.0)));
+    }
+
+    #[test]
+    fn number_to_text_pads_decimals_and_groups_thousands() {
+        let root = static_document_root(
+            r#"
+prices: LIST { 1, 3.1, 5.8 }
+document: Document/new(root: [
+    total: prices |> List/sum() |> Number/to_text(decimals: 2)
+    grouped: 1234567.891 |> Number/to_text(decimals: 1, thousands_separator: TEXT { , })
+    ungrouped: 1234567 |> Number/to_text(decimals: 0)
+    negative_zero: -0.001 |> Number/to_text(decimals: 2)
+    noisy: 0.1 + 0.2 |> Number/to_text(decimals: 2)
+    padded: 12.5 |> Number/to_text(decimals: 2)
+    whole: 2.675 |> Number/to_text(decimals: 0)
+    carried: 999.999 |> Number/to_text(decimals: 2, thousands_separator: TEXT { , })
+    negative: -1234.5 |> Number/to_text(decimals: 1, thousands_separator: TEXT { . })
+    label: BLOCK {
+        price: 2.6 |> Number/to_text(decimals: 2)
+        TEXT { Coffee {price} }
+    }
+])
+"#,
+        );
+
+        assert_eq!(root.get_field("total"), Some(&Value::text("9.90")));
+        assert_eq!(root.get_field("grouped"), Some(&Value::text("1,234,567.9")));
+        assert_eq!(root.get_field("ungrouped"), Some(&Value::text("1234567")));
+        assert_eq!(root.get_field("negative_zero"), Some(&Value::text("0.00")));
+        assert_eq!(root.get_field("noisy"), Some(&Value::text("0.30")));
+        assert_eq!(root.get_field("padded"), Some(&Value::text("12.50")));
+        assert_eq!(root.get_field("whole"), Some(&Value::text("3")));
+        assert_eq!(root.get_field("carried"), Some(&Value::text("1,000.00")));
+        assert_eq!(root.get_field("negative"), Some(&Value::text("-1.234.5")));
+        assert_eq!(root.get_field("label"), Some(&Value::text("Coffee 2.60")));
+    }
+
+    #[test]
+    fn text_case_conversions_pass_values_that_are_not_text_through() {
+        let root = static_document_root(
+            r#"
+document: Document/new(root: [
+    upper: TEXT { Straße } |> Text/to_uppercase()
+    lower: TEXT { ÀB } |> Text/to_lowercase()
+    ascii_upper: TEXT { Hello World } |> Text/to_uppercase()
+    ascii_lower: TEXT { Hello World } |> Text/to_lowercase()
+    greek_lower: TEXT { ÁRVÍZTŰRŐ ΣΟΦΙΑ } |> Text/to_lowercase()
+    number: 42 |> Text/to_uppercase()
+    tag: True |> Text/to_lowercase()
+])
+"#,
+        );
+
+        assert_eq!(root.get_field("upper"), Some(&Value::text("STRASSE")));
+        assert_eq!(root.get_field("lower"), Some(&Value::text("àb")));
+        assert_eq!(
+            root.get_field("ascii_upper"),
+            Some(&Value::text("HELLO WORLD"))
+        );
+        assert_eq!(
+            root.get_field("ascii_lower"),
+            Some(&Value::text("hello world"))
+        );
+        assert_eq!(
+            root.get_field("greek_lower"),
+            Some(&Value::text("árvíztűrő σοφια"))
+        );
+        assert_eq!(root.get_field("number"), Some(&Value::number(42.0)));
+        assert_eq!(root.get_field("tag"), Some(&Value::tag("True")));
+
+        // The reactive path maps each emitted value the same way
+        let compiler = Compiler::new();
+        let path = ["Text".to_string(), "to_uppercase".to_string()];
+        assert_eq!(
+            compiler.eval_builtin_piped(&Value::text("abc"), &path, &[], &IndexMap::new()),
+            Ok(Value::text("ABC"))
+        );
+        assert_eq!(
+            compiler.eval_builtin_piped(&Value::number(42.0), &path, &[], &IndexMap::new()),
+            Ok(Value::number(42.0))
+        );
+    }
+
+    #[test]
+    fn text_length_counts_characters_and_is_empty_counts_spaces() {
+        let root = static_document_root(
+            r#"
+document: Document/new(root: [
+    ascii: TEXT { Hello } |> Text/length()
+    accented: TEXT { árvíz } |> Text/length()
+    emoji: TEXT { Hi 👋 } |> Text/length()
+    empty_length: Text/empty() |> Text/length()
+    empty: Text/empty() |> Text/is_empty()
+    spaces: TEXT {   } |> Text/is_empty()
+    trimmed_spaces: TEXT {   } |> Text/trim() |> Text/is_empty()
+    word: TEXT { a } |> Text/is_empty()
+])
+"#,
+        );
+
+        assert_eq!(root.get_field("ascii"), Some(&Value::number(5.0)));
+        assert_eq!(root.get_field("accented"), Some(&Value::number(5.0)));
+        assert_eq!(root.get_field("emoji"), Some(&Value::number(4.0)));
+        assert_eq!(root.get_field("empty_length"), Some(&Value::number(0.0)));
+        assert_eq!(root.get_field("empty"), Some(&Value::tag("True")));
+        assert_eq!(root.get_field("spaces"), Some(&Value::tag("False")));
+        assert_eq!(root.get_field("trimmed_spaces"), Some(&Value::tag("True")));
+        assert_eq!(root.get_field("word"), Some(&Value::tag("False")));
+    }
+
+    #[test]
+    fn math_min_and_max_read_with_and_the_old_b_name() {
+        let root = static_document_root(
+            r#"
+document: Document/new(root: [
+    smaller: 3 |> Math/min(with: 7)
+    larger: 3 |> Math/max(with: 7)
+    negative: -2 |> Math/max(with: -5)
+    old_smaller: 3 |> Math/min(b: 7)
+    old_larger: 3 |> Math/max(b: 7)
+])
+"#,
+        );
+
+        assert_eq!(root.get_field("smaller"), Some(&Value::number(3.0)));
+        assert_eq!(root.get_field("larger"), Some(&Value::number(7.0)));
+        assert_eq!(root.get_field("negative"), Some(&Value::number(-2.0)));
+        assert_eq!(root.get_field("old_smaller"), Some(&Value::number(3.0)));
+        assert_eq!(root.get_field("old_larger"), Some(&Value::number(7.0)));
+    }
+
+    #[test]
+    fn math_rounding_rounds_halves_away_from_zero_and_keeps_whole_numbers() {
+        let root = static_document_root(
+            r#"
+document: Document/new(root: [
+    round: 2.5 |> Math/round()
+    floor: 2.7 |> Math/floor()
+    ceil: 2.2 |> Math/ceil()
+    negative_round: -2.5 |> Math/round()
+    negative_floor: -2.5 |> Math/floor()
+    negative_ceil: -2.5 |> Math/ceil()
+    whole_round: 4 |> Math/round()
+    whole_floor: 4 |> Math/floor()
+    whole_ceil: 4 |> Math/ceil()
+    exact_pages: 20 / 10 |> Math/ceil()
+    partial_pages: 21 / 10 |> Math/ceil()
+])
+"#,
+        );
+
+        for (name, expected) in [
+            ("round", 3.0),
+            ("floor", 2.0),
+            ("ceil", 3.0),
+            ("negative_round", -3.0),
+            ("negative_floor", -3.0),
+            ("negative_ceil", -2.0),
+            ("whole_round", 4.0),
+            ("whole_floor", 4.0),
+            ("whole_ceil", 4.0),
+            ("exact_pages", 2.0),
+            ("partial_pages", 3.0),
+        ] {
+            assert_eq!(
+                root.get_field(name),
+                Some(&Value::number(expected)),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn math_abs_drops_the_sign_and_negate_if_follows_the_condition() {
+        let root = static_document_root(
+            r#"
+document: Document/new(root: [
+    negative: -3.5 |> Math/abs()
+    positive: 2 |> Math/abs()
+    zero: 0 |> Math/abs()
+    negated: 3 |> Math/negate_if(condition: True)
+    kept: 3 |> Math/negate_if(condition: False)
+    double_negative: -3 |> Math/negate_if(condition: True)
+])
+"#,
+        );
+
+        for (name, expected) in [
+            ("negative", 3.5),
+            ("positive", 2.0),
+            ("zero", 0.0),
+            ("negated", -3.0),
+            ("kept", 3.0),
+            ("double_negative", 3.0),
+        ] {
+            assert_eq!(
+                root.get_field(name),
+                Some(&Value::number(expected)),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn math_pow_and_sqrt_give_math_error_for_results_that_are_not_finite() {
+        let root = static_document_root(
+            r#"
+document: Document/new(root: [
+    square: 3 |> Math/pow(exponent: 2)
+    inverse: 4 |> Math/pow(exponent: -1)
+    root: 16 |> Math/sqrt()
+    fractional_root: 2.25 |> Math/sqrt()
+    negative_root: -4 |> Math/sqrt()
+    division_by_zero: 0 |> Math/pow(exponent: -1)
+    fractional_power_of_negative: -8 |> Math/pow(exponent: 0.5)
+    overflow: 10 |> Math/pow(exponent: 400)
+])
+"#,
+        );
+
+        assert_eq!(root.get_field("square"), Some(&Value::number(9.0)));
+        assert_eq!(root.get_field("inverse"), Some(&Value::number(0.25)));
+        assert_eq!(root.get_field("root"), Some(&Value::number(4.0)));
+        assert_eq!(root.get_field("fractional_root"), Some(&Value::number(1.5)));
+        for name in [
+            "negative_root",
+            "division_by_zero",
+            "fractional_power_of_negative",
+            "overflow",
+        ] {
+            assert_eq!(
+                root.get_field(name),
+                Some(&Value::tag("MathError")),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn math_modulo_and_divide_int_give_math_error_for_division_by_zero() {
+        let root = static_document_root(
+            r#"
+elapsed: 3 * 60 + 58
+document: Document/new(root: [
+    remainder: 7 |> Math/modulo(by: 3)
+    quotient: 7 |> Math/divide_int(by: 3)
+    fractional_remainder: 7.5 |> Math/modulo(by: 2)
+    exact: 9 |> Math/divide_int(by: 3)
+    old_divisor: 17 |> Math/modulo(divisor: 5)
+    minutes: elapsed |> Math/divide_int(by: 60)
+    seconds: elapsed |> Math/modulo(by: 60)
+    remainder_by_zero: 5 |> Math/modulo(by: 0)
+    quotient_by_zero: 5 |> Math/divide_int(by: 0)
+])
+"#,
+        );
+
+        for (name, expected) in [
+            ("remainder", 1.0),
+            ("quotient", 2.0),
+            ("fractional_remainder", 1.5),
+            ("exact", 3.0),
+            ("old_divisor", 2.0),
+            ("minutes", 3.0),
+            ("seconds", 58.0),
+        ] {
+            assert_eq!(
+                root.get_field(name),
+                Some(&Value::number(expected)),
+                "{name}"
+            );
+        }
+        assert_eq!(
+            root.get_field("remainder_by_zero"),
+            Some(&Value::tag("MathError"))
+        );
+        assert_eq!(
+            root.get_field("quotient_by_zero"),
+            Some(&Value::tag("MathError"))
+        );
+    }
+
+    #[test]
+    fn math_clamp_keeps_the_value_within_the_bounds_and_gives_min_when_they_cross() {
+        let root = static_document_root(
+            r#"
+document: Document/new(root: [
+    inside: 5 |> Math/clamp(min: 0, max: 10)
+    below: -3 |> Math/clamp(min: 0, max: 10)
+    above: 12.5 |> Math/clamp(min: 0, max: 10)
+    on_bound: 10 |> Math/clamp(min: 0, max: 10)
+    crossed: 5 |> Math/clamp(min: 10, max: 0)
+])
+"#,
+        );
+
+        for (name, expected) in [
+            ("inside", 5.0),
+            ("below", 0.0),
+            ("above", 10.0),
+            ("on_bound", 10.0),
+            ("crossed", 10.0),
+        ] {
+            assert_eq!(
+                root.get_field(name),
+                Some(&Value::number(expected)),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn bool_and_is_true_only_when_both_are_true() {
+        let root = static_document_root(
+            r#"
+document: Document/new(root: [
+    true_true: True |> Bool/and(that: True)
+    true_false: True |> Bool/and(that: False)
+    false_true: False |> Bool/and(that: True)
+    false_false: False |> Bool/and(that: False)
+])
+"#,
+        );
+
+        assert_eq!(root.get_field("true_true"), Some(&Value::tag("True")));
+        for name in ["true_false", "false_true", "false_false"] {
+            assert_eq!(root.get_field(name), Some(&Value::tag("False")), "{name}");
+        }
+    }
+
+    #[test]
+    fn math_division_rounds_down_for_negative_operands() {
+        let root = static_document_root(
+            r#"
+document: Document/new(root: [
+    negative_remainder: -7 |> Math/modulo(by: 3)
+    negative_quotient: -7 |> Math/divide_int(by: 3)
+    remainder_by_negative: 7 |> Math/modulo(by: -3)
+    quotient_by_negative: 7 |> Math/divide_int(by: -3)
+    both_negative: -7 |> Math/modulo(by: -3)
+    exact_negative: -6 |> Math/modulo(by: 3)
+])
+"#,
+        );
+
+        assert_eq!(
+            root.get_field("negative_remainder"),
+            Some(&Value::number(2.0))
+        );
+        assert_eq!(
+            root.get_field("negative_quotient"),
+            Some(&Value::number(-3.0))
+        );
+        assert_eq!(
+            root.get_field("remainder_by_negative"),
+            Some(&Value::number(-2.0))
+        );
+        assert_eq!(
+            root.get_field("quotient_by_negative"),
+            Some(&Value::number(-3.0))
+        );
+        assert_eq!(root.get_field("both_negative"), Some(&Value::number(-1.0)));
+        assert_eq!(root.get_field("exact_negative"), Some(&Value::number(0.0)));
+    }
+
+    #[test]
+    fn reactive_math_min_and_max_read_arguments_in_the_local_scope() {
+        let ast = parse_source("limit_argument: limit\ntext_argument: TEXT { none }")
+            .expect("arguments should parse");
+        let argument = |index: usize| {
+            let Expression::Variable(variable) = &ast[index].node else {
+                panic!("expected a variable");
+            };
+            vec![("with".to_string(), Some(variable.value.clone()))]
+        };
+        let compiler = Compiler::new();
+        let min = ["Math".to_string(), "min".to_string()];
+        let max = ["Math".to_string(), "max".to_string()];
+        let local_scope = IndexMap::from([("limit".to_string(), Value::number(3.0))]);
+
+        assert_eq!(
+            compiler.eval_builtin_piped(&Value::number(5.0), &min, &argument(0), &local_scope),
+            Ok(Value::number(3.0))
+        );
+        // A `with:` that is not a Number is skipped instead of read as 0
+        assert_eq!(
+            compiler.eval_builtin_piped(&Value::number(5.0), &min, &argument(1), &local_scope),
+            Ok(Value::number(5.0))
+        );
+        assert_eq!(
+            compiler.eval_builtin_piped(&Value::number(-5.0), &max, &argument(1), &local_scope),
+            Ok(Value::number(-5.0))
+        );
+    }
+
+    #[test]
+    fn only_the_unpiped_bool_toggle_is_persisted() {
+        let source = r#"
+document: Document/new(root: Element/stripe(
+    element: []
+    direction: Column
+    gap: 0
+    style: []
+
+    items: LIST {
+        piped
+        unpiped
+        toggle_button
+    }
+))
+
+piped: False |> Bool/toggle(when: toggle_button.event.press)
+
+unpiped: Bool/toggle(on: toggle_button.event.press, initial: False)
+
+toggle_button: Element/button(
+    element: [event: [press: LINK]]
+    style: []
+    label: TEXT { Toggle }
+)
+"#;
+        let persisted_holds = std::collections::HashMap::from([
+            ("piped".to_string(), Value::tag("True")),
+            ("unpiped".to_string(), Value::tag("True")),
+        ]);
+        let program = compile(source, Some("toggles"), &persisted_holds, None)
+            .expect("compile should succeed");
+        let CompiledProgram::Dataflow { graph } = program else {
+            panic!("expected dataflow program");
+        };
+
+        let persisted_hold_names: Vec<&str> = graph
+            .collections
+            .values()
+            .filter_map(|spec| match spec {
+                CollectionSpec::SideEffect {
+                    effect: SideEffectKind::PersistHold { hold_name, .. },
+                    ..
+                } => Some(hold_name.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(persisted_hold_names, ["unpiped"]);
+
+        let initial_value = |name: &str| match graph.collections.get(&VarId::new(name)) {
+            Some(CollectionSpec::HoldState { initial_value, .. }) => initial_value.clone(),
+            _ => panic!("{name} should compile to HoldState"),
+        };
+        assert_eq!(initial_value("piped"), Value::tag("False"));
+        assert_eq!(initial_value("unpiped"), Value::tag("True"));
+    }
 }
@@ -441,12 +441,14 @@ where
         .as_collection()
 }
 
-/// List/every: keyed list → scalar bool (True if all items match predicate).
+/// List/every and List/all: keyed list → scalar bool (True if all items match predicate).
 ///
 /// Maintains a `HashMap<ListKey, bool>` tracking per-item predicate results.
-/// Emits retract/insert only when the aggregate result changes.
+/// Emits retract/insert only when the aggregate result changes. An emptied list
+/// gives `true_when_empty`.
 pub fn list_every<G>(
     list: &VecCollection<G, (ListKey, Value), isize>,
+    true_when_empty: bool,
     predicate: impl Fn(&Value) -> bool + 'static,
 ) -> VecCollection<G, Value, isize>
 where
@@ -466,7 +468,11 @@ where
                             items.remove(&key);
                         }
                     }
-                    let new_result = !items.is_empty() && items.values().all(|&b| b);
+                    let new_result = if items.is_empty() {
+                        true_when_empty
+                    } else {
+                        items.values().all(|&b| b)
+                    };
                     if current_result != Some(new_result) {
                         let mut session = output.session(&time);
                         if let Some(old) = current_result {
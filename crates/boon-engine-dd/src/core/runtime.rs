@@ -11,7 +11,7 @@ use std::sync::Arc;
 use differential_dataflow::VecCollection;
 use differential_dataflow::input::Input;
 use differential_dataflow::input::InputSession;
-use timely::dataflow::Scope;
+use timely::dataflow::{ProbeHandle, Scope};
 
 use super::operators;
 use super::types::{
@@ -29,6 +29,9 @@ pub struct MaterializedGraph {
     pub literal_sessions_keyed: Vec<InputSession<u64, (ListKey, Value), isize>>,
     /// The VarId of the document output collection.
     pub document_var: VarId,
+    /// Frontier of every output (document, keyed display and persistence, side effects).
+    /// Once it passes an epoch, everything injected before that epoch has been delivered.
+    pub probe: ProbeHandle<u64>,
 }
 
 /// A DD collection that can hold either scalar Values or keyed list items.
@@ -230,6 +233,8 @@ where
     let mut literal_sessions: Vec<InputSession<u64, Value, isize>> = Vec::new();
     let mut literal_sessions_keyed: Vec<InputSession<u64, (ListKey, Value), isize>> = Vec::new();
 
+    let mut probe = ProbeHandle::new();
+
     // Build collections once their dependencies are available.
     let mut collections: HashMap<VarId, AnyCollection<G>> = HashMap::new();
     let mut pending: Vec<(&VarId, &CollectionSpec)> = graph.collections.iter().collect();
@@ -413,11 +418,13 @@ where
                         .as_scalar();
                     let effect_clone = effect.clone();
                     let callback = on_side_effect.clone();
-                    source_coll.inspect(move |(value, _time, diff)| {
-                        if *diff > 0 {
-                            callback(&effect_clone, value);
-                        }
-                    });
+                    source_coll
+                        .inspect(move |(value, _time, diff)| {
+                            if *diff > 0 {
+                                callback(&effect_clone, value);
+                            }
+                        })
+                        .probe_with(&mut probe);
                     // SideEffect is transparent — passes through the source collection
                     AnyCollection::Scalar(source_coll.clone())
                 }
@@ -449,13 +456,19 @@ where
                     AnyCollection::Scalar(operators::list_latest(list))
                 }
 
-                CollectionSpec::ListEvery { source, predicate } => {
+                CollectionSpec::ListEvery {
+                    source,
+                    predicate,
+                    true_when_empty,
+                } => {
                     let list = collections
                         .get(source)
                         .expect("ListEvery source not found")
                         .as_keyed();
                     let predicate = Arc::clone(predicate);
-                    AnyCollection::Scalar(operators::list_every(list, move |v| predicate(v)))
+                    AnyCollection::Scalar(operators::list_every(list, *true_when_empty, move |v| {
+                        predicate(v)
+                    }))
                 }
 
                 CollectionSpec::ListAny { source, predicate } => {
@@ -706,9 +719,11 @@ where
         .expect("Document collection not found")
         .as_scalar();
 
-    doc_collection.inspect(move |(value, time, diff)| {
-        on_output(value, time, diff);
-    });
+    doc_collection
+        .inspect(move |(value, time, diff)| {
+            on_output(value, time, diff);
+        })
+        .probe_with(&mut probe);
 
     // Wire keyed inspect callback on the display collection (post-retain, post-map element Values).
     // These diffs go to the bridge for O(1) per-item rendering.
@@ -718,16 +733,18 @@ where
             .expect("Keyed display collection not found")
             .as_keyed();
         let callback = on_keyed;
-        display_coll.inspect(move |((key, value), _time, diff)| {
-            if *diff > 0 {
-                callback(KeyedDiff::Upsert {
-                    key: key.clone(),
-                    value: value.clone(),
-                });
-            } else if *diff < 0 {
-                callback(KeyedDiff::Remove { key: key.clone() });
-            }
-        });
+        display_coll
+            .inspect(move |((key, value), _time, diff)| {
+                if *diff > 0 {
+                    callback(KeyedDiff::Upsert {
+                        key: key.clone(),
+                        value: value.clone(),
+                    });
+                } else if *diff < 0 {
+                    callback(KeyedDiff::Remove { key: key.clone() });
+                }
+            })
+            .probe_with(&mut probe);
     }
 
     // Wire keyed inspect callback on the persistence collection (raw data, pre-map).
@@ -738,16 +755,18 @@ where
             .expect("Keyed persistence collection not found")
             .as_keyed();
         let callback = on_persist;
-        persist_coll.inspect(move |((key, value), _time, diff)| {
-            if *diff > 0 {
-                callback(KeyedDiff::Upsert {
-                    key: key.clone(),
-                    value: value.clone(),
-                });
-            } else if *diff < 0 {
-                callback(KeyedDiff::Remove { key: key.clone() });
-            }
-        });
+        persist_coll
+            .inspect(move |((key, value), _time, diff)| {
+                if *diff > 0 {
+                    callback(KeyedDiff::Upsert {
+                        key: key.clone(),
+                        value: value.clone(),
+                    });
+                } else if *diff < 0 {
+                    callback(KeyedDiff::Remove { key: key.clone() });
+                }
+            })
+            .probe_with(&mut probe);
     }
 
     MaterializedGraph {
@@ -755,5 +774,6 @@ where
         literal_sessions,
         literal_sessions_keyed,
         document_var: graph.document.clone(),
+        probe,
     }
 }
@@ -87,6 +87,8 @@ pub const HOVER_PATH_FIELD: &str = "__hover_path__";
 pub const HOVERED_FIELD: &str = "__hovered";
 /// Variable/input name for the browser router.
 pub const ROUTER_INPUT: &str = "__router";
+/// Variable/input name for the URL query parameters.
+pub const QUERY_PARAMS_INPUT: &str = "__query_params";
 /// Scope variable name for PASS/PASSED context propagation.
 pub const PASSED_VAR: &str = "__passed";
 /// Field name prefix for multi-dependency document closures (e.g., "__dep_0", "__dep_1").
@@ -139,7 +141,7 @@ pub struct KeyedListOutput {
 /// Contains all input specifications, collection definitions in topological
 /// order, and the root document output variable.
 pub struct DataflowGraph {
-    /// External input sources (LINK events, timers, router).
+    /// External input sources (LINK events, timers, router, query parameters).
     pub inputs: Vec<InputSpec>,
     /// Collection definitions in topological order.
     /// Each entry maps a VarId to its CollectionSpec.
@@ -182,6 +184,7 @@ pub enum InputKind {
     HoverChange,
     Timer,
     Router,
+    QueryParams,
 }
 
 /// Closure types for DD operators.
@@ -262,10 +265,12 @@ pub enum CollectionSpec {
     /// List/latest: keyed list → scalar (most recently changed value).
     ListLatest(VarId),
 
-    /// List/every: keyed list → scalar bool (all items match predicate).
+    /// List/every and List/all: keyed list → scalar bool (all items match predicate).
+    /// `true_when_empty` is set for List/all, which is True on an empty list.
     ListEvery {
         source: VarId,
         predicate: Arc<dyn Fn(&Value) -> bool + 'static>,
+        true_when_empty: bool,
     },
 
     /// List/any: keyed list → scalar bool (any item matches predicate).
@@ -362,4 +367,5 @@ pub enum CollectionSpec {
 pub enum SideEffectKind {
     PersistHold { key: String, hold_name: String },
     RouterGoTo,
+    SetQueryParam { key: String },
 }
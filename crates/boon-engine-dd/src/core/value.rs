@@ -221,6 +221,32 @@ impl Value {
         self.clone()
     }
 
+    /// A list of `items` in the given order, keyed by position.
+    pub fn list_from_items(items: impl IntoIterator<Item = Value>) -> Self {
+        Value::Tagged {
+            tag: Arc::from(LIST_TAG),
+            fields: Arc::new(
+                items
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, item)| (Arc::from(format!("{:04}", i)), item))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Stable sort of list items by `key`: Numbers, then Texts, then Tags; any other
+    /// key (Unit, a missing field) sorts last.
+    pub fn list_sorted_by(&self, key: impl Fn(&Value) -> Value) -> Self {
+        let mut keyed: Vec<(Value, Value)> = self
+            .list_items()
+            .into_iter()
+            .map(|item| (key(item), item.clone()))
+            .collect();
+        keyed.sort_by(|(a, _), (b, _)| sort_key_order(a, b));
+        Value::list_from_items(keyed.into_iter().map(|(_, item)| item))
+    }
+
     pub fn list_every(&self, f: impl Fn(&Value) -> bool) -> bool {
         if let Value::Tagged { tag, fields } = self {
             if tag.as_ref() == LIST_TAG {
@@ -312,3 +338,20 @@ impl std::fmt::Display for Value {
         write!(f, "{}", self.to_display_string())
     }
 }
+
+/// `List/sort_by` key order; keys of different kinds compare by kind.
+fn sort_key_order(a: &Value, b: &Value) -> std::cmp::Ordering {
+    fn rank(key: &Value) -> u8 {
+        match key {
+            Value::Number(_) => 0,
+            Value::Text(_) => 1,
+            Value::Tag(_) => 2,
+            _ => 3,
+        }
+    }
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a.cmp(b),
+        (Value::Text(a), Value::Text(b)) | (Value::Tag(a), Value::Tag(b)) => a.cmp(b),
+        _ => rank(a).cmp(&rank(b)),
+    }
+}
@@ -11,15 +11,20 @@ use std::collections::HashMap;
 use std::rc::Rc;
 use std::sync::Arc;
 
+use boon::platform::browser::common::{EngineType, Quiescence};
+use boon::platform::browser::repro::{ReproScript, record_injection, start_injection_recording};
+use boon::platform::browser::stdlib::{format_query_params, parse_query_params, set_query_param};
 use differential_dataflow::input::InputSession;
+use serde::{Deserialize, Serialize};
 use timely::communication::allocator::thread::Thread;
+use timely::dataflow::ProbeHandle;
 use timely::worker::Worker;
 use zoon::*;
 
 use super::super::core::runtime;
 use super::super::core::types::{
-    DataflowGraph, InputId, InputKind, KeyedDiff, LIST_TAG, LinkId, ListKey, ROUTER_INPUT,
-    SideEffectKind,
+    DataflowGraph, InputId, InputKind, KeyedDiff, LIST_TAG, LinkId, ListKey, QUERY_PARAMS_INPUT,
+    ROUTER_INPUT, SideEffectKind,
 };
 use super::super::core::value::Value;
 
@@ -31,8 +36,8 @@ use super::super::core::value::Value;
 ///
 /// Events are injected into the DD engine via DdWorkerHandle::inject_dd_event().
 /// Each event maps to one or more DD InputSessions based on the compiled
-/// DataflowGraph's InputSpec entries.
-#[derive(Clone, Debug)]
+/// DataflowGraph's InputSpec entries. Events are the payloads of DD repro scripts.
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum Event {
     LinkPress {
         link_path: String,
@@ -77,6 +82,31 @@ pub enum Event {
     RouterChange {
         path: String,
     },
+    /// The whole query string changed (`?filter=active`), like after a navigation.
+    QueryChange {
+        query: String,
+    },
+}
+
+impl Event {
+    /// Link path, timer variable, route or query string the event is injected into.
+    pub fn path(&self) -> &str {
+        match self {
+            Self::LinkPress { link_path }
+            | Self::LinkClick { link_path }
+            | Self::KeyDown { link_path, .. }
+            | Self::TextChange { link_path, .. }
+            | Self::NumberChange { link_path, .. }
+            | Self::Blur { link_path }
+            | Self::Focus { link_path }
+            | Self::DoubleClick { link_path }
+            | Self::SvgClick { link_path, .. }
+            | Self::HoverChange { link_path, .. } => link_path,
+            Self::TimerTick { var_name } => var_name,
+            Self::RouterChange { path } => path,
+            Self::QueryChange { query } => query,
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -127,16 +157,48 @@ struct DdWorkerInner {
     /// Callback to notify when output changes.
     on_output_change: Option<Box<dyn Fn(&Value)>>,
     /// Literal sessions must be kept alive for the dataflow to work.
-    _literal_sessions: Vec<InputSession<u64, Value, isize>>,
-    _literal_sessions_keyed: Vec<InputSession<u64, (ListKey, Value), isize>>,
+    /// They advance with the event inputs so the output frontier can move.
+    literal_sessions: Vec<InputSession<u64, Value, isize>>,
+    literal_sessions_keyed: Vec<InputSession<u64, (ListKey, Value), isize>>,
+    /// Frontier of all outputs, see [`runtime::MaterializedGraph::probe`].
+    probe: ProbeHandle<u64>,
+    /// Current query string, the base for `Document/set_query_param`.
+    query: String,
 }
 
+/// Worker steps one event may take before the rest of its propagation is left
+/// for the next event. Shallow graphs settle in a handful of steps.
+const STEP_BUDGET_PER_EVENT: usize = 10_000;
+
 impl DdWorkerInner {
-    fn drive_until_settled(&mut self) {
-        // DD pipelines in this repo often require several worker steps for an
-        // event to reach keyed list diffs and the assembled document output.
-        for _ in 0..1000 {
+    /// Moves every session, literals included, to `epoch`.
+    fn advance_to(&mut self, epoch: u64) {
+        self.epoch = epoch;
+        for session in self.inputs.values_mut() {
+            session.advance_to(epoch);
+            session.flush();
+        }
+        for session in self.literal_sessions.iter_mut() {
+            session.advance_to(epoch);
+            session.flush();
+        }
+        for session in self.literal_sessions_keyed.iter_mut() {
+            session.advance_to(epoch);
+            session.flush();
+        }
+    }
+
+    /// Steps the worker until everything injected before the current epoch has
+    /// reached the outputs, or the per-event budget runs out.
+    fn drive_until_settled(&mut self) -> Quiescence {
+        for step in 1..=STEP_BUDGET_PER_EVENT {
             self.worker.step();
+            if !self.probe.less_than(&self.epoch) {
+                return Quiescence::Settled { steps: step };
+            }
+        }
+        Quiescence::BudgetExhausted {
+            budget: STEP_BUDGET_PER_EVENT,
         }
     }
 
@@ -164,6 +226,9 @@ impl DdWorkerHandle {
         graph: DataflowGraph,
         on_output_change: impl Fn(&Value) + 'static,
     ) -> Self {
+        // Every new graph is a new run; its repro script starts empty.
+        start_injection_recording(EngineType::DifferentialDataflow);
+
         let alloc = Thread::default();
         let mut worker = Worker::new(Default::default(), alloc, None);
         let output_cell: Rc<RefCell<Value>> = Rc::new(RefCell::new(Value::Unit));
@@ -244,12 +309,15 @@ impl DdWorkerHandle {
         // Build link_path → InputId mapping from graph inputs
         let mut link_path_to_input: HashMap<String, InputId> = HashMap::new();
         let mut has_router_input = false;
+        let mut has_query_params_input = false;
         for input_spec in &graph.inputs {
             if let Some(ref path) = input_spec.link_path {
                 link_path_to_input.insert(path.clone(), input_spec.id);
             }
-            if input_spec.kind == InputKind::Router {
-                has_router_input = true;
+            match input_spec.kind {
+                InputKind::Router => has_router_input = true,
+                InputKind::QueryParams => has_query_params_input = true,
+                _ => {}
             }
         }
 
@@ -273,6 +341,7 @@ impl DdWorkerHandle {
         let input_sessions = materialized.input_sessions;
         let mut literal_sessions = materialized.literal_sessions;
         let mut literal_sessions_keyed = materialized.literal_sessions_keyed;
+        let probe = materialized.probe;
 
         // Advance ALL sessions (literals + event inputs) past initial epoch.
         // Timely requires all input frontiers to advance before data can propagate.
@@ -294,8 +363,10 @@ impl DdWorkerHandle {
             output_cell: output_cell.clone(),
             last_notified: Value::Unit,
             on_output_change: Some(Box::new(on_output_change)),
-            _literal_sessions: literal_sessions,
-            _literal_sessions_keyed: literal_sessions_keyed,
+            literal_sessions,
+            literal_sessions_keyed,
+            probe,
+            query: String::new(),
         };
 
         // Inject initial route for Router inputs at epoch 0 (BEFORE advancing).
@@ -313,16 +384,32 @@ impl DdWorkerHandle {
             }
         }
 
-        // Advance event input sessions past initial epoch
-        for session in inner.inputs.values_mut() {
-            session.advance_to(1);
-            session.flush();
+        // Inject the initial query parameters the same way. Headless runs start
+        // without a query; tests inject `Event::QueryChange` instead.
+        if has_query_params_input {
+            if let Some(input_id) = inner.link_path_to_input.get(QUERY_PARAMS_INPUT).copied() {
+                #[cfg(target_arch = "wasm32")]
+                {
+                    inner.query = web_sys::window()
+                        .and_then(|w| w.location().search().ok())
+                        .unwrap_or_default();
+                }
+                let params = query_params_value(&inner.query);
+                if let Some(session) = inner.inputs.get_mut(&input_id) {
+                    session.update(params, 1);
+                    session.flush();
+                }
+            }
         }
-        inner.epoch = 1;
+
+        // Advance event input sessions past initial epoch
+        inner.advance_to(1);
 
         // Step to propagate initial data through the dataflow chain.
         // Multiple steps may be needed for deeply chained operators.
-        inner.drive_until_settled();
+        inner
+            .drive_until_settled()
+            .warn_if_exhausted("DD worker (initial run)");
 
         // Read initial output and notify
         let initial_output = inner.output_cell.borrow().clone();
@@ -367,9 +454,45 @@ impl DdWorkerHandle {
 
     /// Inject an event and step the DD worker.
     ///
-    /// Maps the event to the appropriate InputSession based on the
-    /// compiled DataflowGraph's InputSpec entries, then steps the worker.
+    /// The event is recorded for repro scripts, then mapped to the appropriate
+    /// InputSession based on the compiled DataflowGraph's InputSpec entries.
     pub fn inject_dd_event(&self, event: Event) {
+        let tick = self.inner.borrow().epoch + 1;
+        if let Ok(payload) = serde_json::to_value(&event) {
+            record_injection(event.path(), payload, tick);
+        }
+        self.apply_dd_event(event);
+    }
+
+    /// Injects the events of a repro script recorded by this engine, in order.
+    ///
+    /// Timer ticks were recorded as `TimerTick` injections, so the time between
+    /// injections needs no clock here. Returns the number of injected events.
+    pub fn replay(&self, script: &ReproScript) -> Result<usize, String> {
+        if script.engine != EngineType::DifferentialDataflow {
+            return Err(format!(
+                "repro script was recorded by the {} engine",
+                script.engine.short_name()
+            ));
+        }
+        let events = script
+            .injections
+            .iter()
+            .map(|injection| {
+                serde_json::from_value::<Event>(injection.payload.clone()).map_err(|error| {
+                    format!("invalid injection into `{}`: {error}", injection.path)
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let count = events.len();
+        for event in events {
+            self.inject_dd_event(event);
+        }
+        Ok(count)
+    }
+
+    /// Maps the event to its InputSession and steps the worker, without recording it.
+    fn apply_dd_event(&self, event: Event) {
         let (link_path, event_value) = match event {
             Event::LinkPress { link_path } => normalize_dd_link_press(link_path),
             Event::LinkClick { link_path } => (link_path, Value::tag("Click")),
@@ -407,6 +530,11 @@ impl DdWorkerHandle {
                 // Router events map to the __router input with the route text as value
                 (ROUTER_INPUT.to_string(), Value::text(path))
             }
+            Event::QueryChange { query } => {
+                let params = query_params_value(&query);
+                self.inner.borrow_mut().query = query;
+                (QUERY_PARAMS_INPUT.to_string(), params)
+            }
         };
 
         {
@@ -420,8 +548,7 @@ impl DdWorkerHandle {
                     match inner.link_path_to_input.get("__wildcard") {
                         Some(id) => {
                             let wildcard_id = *id;
-                            inner.epoch += 1;
-                            let epoch = inner.epoch;
+                            let epoch = inner.epoch + 1;
                             // Send tagged event with full path
                             let tagged = Value::object([
                                 ("path", Value::text(link_path.as_str())),
@@ -430,11 +557,8 @@ impl DdWorkerHandle {
                             if let Some(session) = inner.inputs.get_mut(&wildcard_id) {
                                 session.update(tagged, 1);
                             }
-                            for session in inner.inputs.values_mut() {
-                                session.advance_to(epoch);
-                                session.flush();
-                            }
-                            inner.drive_until_settled();
+                            inner.advance_to(epoch);
+                            inner.drive_until_settled().warn_if_exhausted("DD worker");
                             let has_keyed = !self.keyed_diff_buffer.borrow().is_empty();
                             inner.notify_if_changed(has_keyed);
                             drop(inner);
@@ -449,23 +573,19 @@ impl DdWorkerHandle {
                 }
             };
 
-            inner.epoch += 1;
-            let epoch = inner.epoch;
+            let epoch = inner.epoch + 1;
 
             // Insert event into the target input
             if let Some(session) = inner.inputs.get_mut(&input_id) {
                 session.update(event_value, 1);
             }
 
-            // Advance ALL input sessions to the new epoch.
+            // Advance ALL sessions to the new epoch.
             // Timely requires all input frontiers to advance past a timestamp
             // before data at that timestamp can propagate through operators.
-            for session in inner.inputs.values_mut() {
-                session.advance_to(epoch);
-                session.flush();
-            }
+            inner.advance_to(epoch);
 
-            inner.drive_until_settled();
+            inner.drive_until_settled().warn_if_exhausted("DD worker");
             let has_keyed = !self.keyed_diff_buffer.borrow().is_empty();
             inner.notify_if_changed(has_keyed);
         }
@@ -497,12 +617,43 @@ impl DdWorkerHandle {
                                 );
                             }
                         }
-                        // Inject route change into the Router input
-                        self.inject_dd_event(Event::RouterChange {
+                        // Inject route change into the Router input. Not recorded:
+                        // replaying the navigation that caused it produces it again.
+                        self.apply_dd_event(Event::RouterChange {
                             path: route.to_string(),
                         });
                     }
                 }
+                SideEffectKind::SetQueryParam { ref key } => {
+                    let value = match &value {
+                        Value::Text(text) => text.to_string(),
+                        Value::Number(number) => number.0.to_string(),
+                        Value::Tag(tag) => tag.to_string(),
+                        _ => String::new(),
+                    };
+                    let current_query = self.inner.borrow().query.clone();
+                    let mut params = parse_query_params(&current_query);
+                    set_query_param(&mut params, key, value);
+                    let query = format_query_params(&params);
+                    // The new query flows back into `Document/query_params()`, which
+                    // usually feeds this value again; stop once nothing changes.
+                    if query == current_query {
+                        continue;
+                    }
+                    #[cfg(target_arch = "wasm32")]
+                    if let Some(window) = web_sys::window() {
+                        let pathname = window.location().pathname().unwrap_or_default();
+                        if let Ok(history) = window.history() {
+                            let _ = history.replace_state_with_url(
+                                &wasm_bindgen::JsValue::NULL,
+                                "",
+                                Some(&format!("{pathname}{query}")),
+                            );
+                        }
+                    }
+                    // Not recorded, like the route change above.
+                    self.apply_dd_event(Event::QueryChange { query });
+                }
             }
         }
     }
@@ -553,16 +704,14 @@ impl DdWorkerHandle {
                 }
             };
 
-            inner.epoch += 1;
-            let epoch = inner.epoch;
+            let epoch = inner.epoch + 1;
 
             if let Some(session) = inner.inputs.get_mut(&input_id) {
                 session.update(event_value, 1);
-                session.advance_to(epoch);
-                session.flush();
             }
+            inner.advance_to(epoch);
 
-            inner.drive_until_settled();
+            inner.drive_until_settled().warn_if_exhausted("DD worker");
             let has_keyed = !self.keyed_diff_buffer.borrow().is_empty();
             inner.notify_if_changed(has_keyed);
         }
@@ -593,9 +742,18 @@ fn normalize_dd_link_press(link_path: String) -> (String, Value) {
     }
 }
 
+/// `?filter=active` as the object `Document/query_params()` emits, with Text fields.
+fn query_params_value(query: &str) -> Value {
+    Value::object(
+        parse_query_params(query)
+            .into_iter()
+            .map(|(key, value)| (key, Value::text(value))),
+    )
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{DdWorkerHandle, Event};
+    use super::{DdWorkerHandle, Event, Quiescence};
     use crate::core::compile::{CompiledProgram, compile};
     use crate::core::types::KeyedDiff;
     use crate::core::value::Value;
@@ -604,6 +762,7 @@ mod tests {
         ExprId, KernelValue, LatestCandidate, Runtime as KernelRuntime, RuntimeUpdate, ScopeId,
         SlotKey, TickId, TickSeq, Trigger, select_latest,
     };
+    use boon::platform::browser::repro::{ReproScript, recorded_repro_script};
     use boon_scene::RenderSurface;
     use std::path::PathBuf;
 
@@ -1067,6 +1226,26 @@ document: Document/new(root:
         );
     }
 
+    #[test]
+    fn event_settles_before_the_step_budget_runs_out() {
+        let source =
+            read_example("../../playground/frontend/src/examples/flight_booker/flight_booker.bn");
+        let program = compile(&source, None, &std::collections::HashMap::new(), None)
+            .expect("flight_booker should compile");
+        let CompiledProgram::Dataflow { graph } = program else {
+            panic!("expected Flight Booker dataflow");
+        };
+
+        let handle = DdWorkerHandle::new_from_graph(graph, |_value| {});
+        handle.inject_dd_event(Event::LinkPress {
+            link_path: "store.elements.book_button.event.press".to_string(),
+        });
+
+        // The event settled inside inject_dd_event, so the outputs are already past its epoch.
+        let quiescence = handle.inner.borrow_mut().drive_until_settled();
+        assert_eq!(quiescence, Quiescence::Settled { steps: 1 });
+    }
+
     #[test]
     fn shopping_list_enter_adds_item() {
         let source =
@@ -1097,6 +1276,211 @@ document: Document/new(root:
         );
     }
 
+    #[test]
+    fn shopping_total_shows_two_decimals_after_adding_coffee() {
+        let source =
+            read_example("../../playground/frontend/src/examples/shopping_total/shopping_total.bn");
+        let program = compile(&source, None, &std::collections::HashMap::new(), None)
+            .expect("shopping_total should compile");
+        let CompiledProgram::Dataflow { graph } = program else {
+            panic!("expected shopping_total dataflow");
+        };
+
+        let handle = DdWorkerHandle::new_from_graph(graph, |_value| {});
+        let output_text = handle.current_output().to_display_string();
+        assert!(
+            output_text.contains("Bread 3.10") && output_text.contains("Total: 9.90"),
+            "expected padded prices and total, got output: {output_text}"
+        );
+
+        handle.inject_dd_event(Event::LinkPress {
+            link_path: "store.elements.coffee_button.event.press".to_string(),
+        });
+        let output_text = handle.current_output().to_display_string();
+        assert!(
+            output_text.contains("Coffee 2.60") && output_text.contains("Total: 12.50"),
+            "expected the coffee and 12.50 after Add coffee, got output: {output_text}"
+        );
+    }
+
+    #[test]
+    fn character_counter_counts_characters_and_posts_the_trimmed_draft() {
+        let source = read_example(
+            "../../playground/frontend/src/examples/character_counter/character_counter.bn",
+        );
+        let program = compile(&source, None, &std::collections::HashMap::new(), None)
+            .expect("character_counter should compile");
+        let CompiledProgram::Dataflow { graph } = program else {
+            panic!("expected character_counter dataflow");
+        };
+
+        let handle = DdWorkerHandle::new_from_graph(graph, |_value| {});
+        let type_draft = |text: &str| {
+            handle.inject_dd_event(Event::TextChange {
+                link_path: "store.elements.post_input.event.change".to_string(),
+                text: text.to_string(),
+            });
+        };
+
+        type_draft("Hello");
+        let output_text = handle.current_output().to_display_string();
+        assert!(
+            output_text.contains("5/280"),
+            "expected 5/280 after typing Hello, got output: {output_text}"
+        );
+
+        type_draft(" Hi 👋 ");
+        let output_text = handle.current_output().to_display_string();
+        assert!(
+            output_text.contains("6/280"),
+            "expected the emoji to count as one character, got output: {output_text}"
+        );
+
+        handle.inject_dd_event(Event::LinkPress {
+            link_path: "store.elements.post_button.event.press".to_string(),
+        });
+        let output_text = handle.current_output().to_display_string();
+        assert!(
+            output_text.contains("0/280") && output_text.contains("label: Hi 👋"),
+            "expected the trimmed post and a cleared draft, got output: {output_text}"
+        );
+    }
+
+    #[test]
+    fn progress_percentage_rounds_and_stops_at_one_hundred() {
+        let source = read_example(
+            "../../playground/frontend/src/examples/progress_percentage/progress_percentage.bn",
+        );
+        let program = compile(&source, None, &std::collections::HashMap::new(), None)
+            .expect("progress_percentage should compile");
+        let CompiledProgram::Dataflow { graph } = program else {
+            panic!("expected progress_percentage dataflow");
+        };
+
+        let handle = DdWorkerHandle::new_from_graph(graph, |_value| {});
+        let press = |button: &str| {
+            handle.inject_dd_event(Event::LinkPress {
+                link_path: format!("store.elements.{button}.event.press"),
+            });
+        };
+        let assert_progress = |expected: &str| {
+            let output_text = handle.current_output().to_display_string();
+            assert!(
+                output_text.contains(expected),
+                "expected {expected:?}, got output: {output_text}"
+            );
+        };
+
+        assert_progress("Progress: 0%");
+        press("complete_button");
+        assert_progress("Progress: 33%");
+        press("complete_button");
+        assert_progress("Progress: 67%");
+        press("complete_button");
+        press("complete_button");
+        assert_progress("Progress: 100%");
+        press("reset_button");
+        assert_progress("Progress: 0%");
+    }
+
+    #[test]
+    fn bounded_counter_stays_between_zero_and_the_maximum() {
+        let source = read_example(
+            "../../playground/frontend/src/examples/bounded_counter/bounded_counter.bn",
+        );
+        let program = compile(&source, None, &std::collections::HashMap::new(), None)
+            .expect("bounded_counter should compile");
+        let CompiledProgram::Dataflow { graph } = program else {
+            panic!("expected bounded_counter dataflow");
+        };
+
+        let handle = DdWorkerHandle::new_from_graph(graph, |_value| {});
+        let press = |button: &str, times: usize| {
+            for _ in 0..times {
+                handle.inject_dd_event(Event::LinkPress {
+                    link_path: format!("store.elements.{button}.event.press"),
+                });
+            }
+        };
+        let assert_count = |expected: &str| {
+            let output_text = handle.current_output().to_display_string();
+            assert!(
+                output_text.contains(expected),
+                "expected {expected:?}, got output: {output_text}"
+            );
+        };
+
+        press("decrement_button", 1);
+        assert_count("Count: 0 of 5");
+        press("increment_button", 1);
+        assert_count("Count: 1 of 5");
+        press("increment_button", 6);
+        assert_count("Count: 5 of 5");
+        press("decrement_button", 6);
+        assert_count("Count: 0 of 5");
+    }
+
+    #[test]
+    fn math_min_reads_sibling_arguments_and_skips_non_numbers() {
+        let source = r#"
+store: [
+    step_button: LINK
+    limit: 3
+
+    count: 0 |> HOLD state {
+        step_button.event.press |> THEN { state + 1 }
+    }
+
+    capped: count |> Math/min(with: limit)
+    uncapped: count |> Math/min(with: TEXT { none })
+    raised: count |> Math/max(with: TEXT { none })
+]
+
+document: Document/new(root:
+    Element/stripe(
+        element: []
+        direction: Column
+        gap: 0
+        style: []
+        items: LIST {
+            Element/button(
+                element: [event: [press: LINK]]
+                label: TEXT { Step }
+                style: []
+            ) |> LINK { store.step_button }
+            Element/label(
+                element: []
+                style: []
+                label: BLOCK {
+                    capped: store.capped
+                    uncapped: store.uncapped
+                    raised: store.raised
+
+                    TEXT { {capped} {uncapped} {raised} }
+                }
+            )
+        }
+    )
+)
+"#;
+        let program = compile(source, None, &std::collections::HashMap::new(), None)
+            .expect("Math/min program should compile");
+        let CompiledProgram::Dataflow { graph } = program else {
+            panic!("expected Math/min program to compile as dataflow");
+        };
+        let handle = DdWorkerHandle::new_from_graph(graph, |_value| {});
+        for _ in 0..5 {
+            handle.inject_dd_event(Event::LinkPress {
+                link_path: "store.step_button.event.press".to_string(),
+            });
+        }
+        let output_text = handle.current_output().to_display_string();
+        assert!(
+            output_text.contains("label: 3 5 5"),
+            "expected `limit` from the store and a Text `with:` to be skipped; got {output_text}"
+        );
+    }
+
     #[test]
     fn todo_mvc_initial_output_shows_seed_items_and_input() {
         let source = read_example("../../playground/frontend/src/examples/todo_mvc/todo_mvc.bn");
@@ -1343,6 +1727,42 @@ document: Document/new(root:
         );
     }
 
+    #[test]
+    fn replayed_todo_session_reaches_the_recorded_state() {
+        let source = read_example("../../playground/frontend/src/examples/todo_mvc/todo_mvc.bn");
+        let new_handle = || match compile(&source, None, &std::collections::HashMap::new(), None)
+            .expect("todo_mvc should compile")
+        {
+            CompiledProgram::Dataflow { graph } => {
+                DdWorkerHandle::new_from_graph(graph, |_value| {})
+            }
+            CompiledProgram::Static { .. } => panic!("todo_mvc should compile to dataflow"),
+        };
+
+        let recorded = new_handle();
+        for title in ["Walk the dog", "Feed the cat"] {
+            recorded.inject_dd_event(Event::TextChange {
+                link_path: "store.elements.new_todo_title_text_input.event.change".to_string(),
+                text: title.to_string(),
+            });
+            recorded.inject_dd_event(Event::KeyDown {
+                link_path: "store.elements.new_todo_title_text_input.event.key_down".to_string(),
+                key: "Enter".to_string(),
+                text: title.to_string(),
+            });
+        }
+        recorded.inject_dd_event(Event::LinkPress {
+            link_path: "store.todos.0000.todo_elements.todo_checkbox.event.press".to_string(),
+        });
+        let script = recorded_repro_script().expect("the run should be recorded");
+        assert_eq!(script.injections.len(), 5);
+        let script = ReproScript::from_json(&script.to_json()).expect("script should round-trip");
+
+        let replayed = new_handle();
+        assert_eq!(replayed.replay(&script), Ok(5));
+        assert_eq!(replayed.current_output(), recorded.current_output());
+    }
+
     #[test]
     fn timer_reaches_100_after_slider_change_and_ticks() {
         let source = read_example("../../playground/frontend/src/examples/timer/timer.bn");
@@ -1757,6 +2177,68 @@ document: Document/new(root:
         );
     }
 
+    #[test]
+    fn query_params_feed_latest_and_follow_set_query_param() {
+        let source = r#"
+active_button: LINK
+
+filter: LATEST {
+    Document/query_params().filter
+    active_button.event.press |> THEN { TEXT { active } }
+    TEXT { all }
+}
+
+filter_sync: Document/set_query_param(key: TEXT { filter }, value: filter)
+
+document: Document/new(root:
+    Element/stripe(
+        element: []
+        direction: Column
+        gap: 0
+        style: []
+        items: LIST {
+            Element/button(
+                element: [event: [press: LINK]]
+                label: TEXT { Active }
+                style: []
+            ) |> LINK { active_button }
+            Element/label(
+                element: []
+                style: []
+                label: filter
+            )
+        }
+    )
+)
+"#;
+        let program = compile(source, None, &std::collections::HashMap::new(), None)
+            .expect("query params program should compile");
+        let CompiledProgram::Dataflow { graph } = program else {
+            panic!("expected query params program to compile as dataflow");
+        };
+        let handle = DdWorkerHandle::new_from_graph(graph, |_value| {});
+        let label = |handle: &DdWorkerHandle| handle.current_output().to_display_string();
+
+        handle.inject_dd_event(Event::QueryChange {
+            query: "?filter=active&filter=completed".to_string(),
+        });
+        assert!(
+            label(&handle).contains("label: completed"),
+            "expected the last duplicate key to win; got {}",
+            label(&handle)
+        );
+
+        handle.inject_dd_event(Event::LinkPress {
+            link_path: "active_button.event.press".to_string(),
+        });
+        assert!(
+            label(&handle).contains("label: active"),
+            "expected the button to override the query; got {}",
+            label(&handle)
+        );
+        assert_eq!(handle.inner.borrow().query, "?filter=active");
+    }
+
     #[test]
     fn cells_worker_boot_reaches_output_clone() {
         let source = read_example("../../playground/frontend/src/examples/cells/cells.bn");
@@ -19,6 +19,9 @@ pub mod render;
 pub use core::types::{InputId, LinkId, ListKey, VarId};
 pub use core::value::Value;
 
+use boon::parser::builtin_aliases::deprecated_builtins_used;
+use boon::parser::exhaustiveness::check_exhaustiveness;
+use boon::platform::browser::common::{LogLevel, emit_warning};
 use boon_scene::{RenderRootHandle, RenderSurface, SceneHandles};
 use std::cell::Cell;
 use std::cell::RefCell;
@@ -211,12 +214,26 @@ pub fn run_dd_reactive_with_persistence(
         std::collections::HashMap::new()
     };
 
-    let compiled = match compile::compile(
-        source_code,
-        states_storage_key,
-        &persisted_holds,
-        external_functions,
-    ) {
+    for alias in deprecated_builtins_used(source_code) {
+        emit_warning(LogLevel::Warn, "deprecated", alias.message());
+    }
+    let compiled = compile::parse_source(source_code).and_then(|ast| {
+        for warning in check_exhaustiveness(&ast) {
+            emit_warning(
+                LogLevel::Warn,
+                "exhaustiveness",
+                warning.message(source_code),
+            );
+        }
+        compile::compile_parsed(
+            source_code,
+            &ast,
+            states_storage_key,
+            &persisted_holds,
+            external_functions,
+        )
+    });
+    let compiled = match compiled {
         Ok(program) => program,
         Err(e) => {
             zoon::eprintln!("DD compilation error: {}", e);
@@ -250,6 +267,11 @@ pub fn run_dd_reactive_with_persistence(
                 .iter()
                 .any(|i| i.kind == core::types::InputKind::Router);
 
+            let has_query_params = graph
+                .inputs
+                .iter()
+                .any(|i| i.kind == core::types::InputKind::QueryParams);
+
             // Collect timer specifications before moving graph into worker
             let timer_specs: Vec<(String, f64)> = graph
                 .inputs
@@ -314,6 +336,26 @@ pub fn run_dd_reactive_with_persistence(
                 popstate_closure.forget(); // Listener lives until page unload
             }
 
+            // Back/forward can change the query string as well
+            if has_query_params {
+                let handle_for_popstate = worker_handle.clone();
+                let popstate_closure =
+                    wasm_bindgen::closure::Closure::<dyn Fn(web_sys::Event)>::new(
+                        move |_event: web_sys::Event| {
+                            let query = web_sys::window()
+                                .and_then(|w| w.location().search().ok())
+                                .unwrap_or_default();
+                            handle_for_popstate
+                                .inject_dd_event(io::worker::Event::QueryChange { query });
+                        },
+                    );
+                let _ = web_sys::window().unwrap().add_event_listener_with_callback(
+                    "popstate",
+                    popstate_closure.as_ref().unchecked_ref(),
+                );
+                popstate_closure.forget(); // Listener lives until page unload
+            }
+
             Some(DdResult {
                 document: Some(build_dd_document(output, revision, render_surface)),
                 context: DdContext { has_timers },
@@ -28,6 +28,8 @@ use futures_channel::mpsc;
 use pin_project::pin_project;
 use zoon::*;
 
+use boon::platform::browser::code_block::code_block;
+
 use super::super::core::types::{HOVER_PATH_FIELD, KeyedDiff, LINK_PATH_FIELD, LIST_TAG};
 use super::super::core::value::Value;
 use super::super::io::worker::{DdWorkerHandle, Event};
@@ -173,6 +175,13 @@ fn get_style_obj(fields: &Fields) -> Option<&Fields> {
     }
 }
 
+/// `Some("")` while `style.disabled` is `True`, for the HTML `disabled` attribute;
+/// `None` removes the attribute.
+fn extract_disabled(value: &Value) -> Option<&'static str> {
+    let style = get_style_obj(get_fields(value)?)?;
+    (style.get("disabled")?.as_tag()? == "True").then_some("")
+}
+
 /// Get fields from a Value (works for Object and Tagged).
 fn get_fields(value: &Value) -> Option<&Fields> {
     match value {
@@ -1565,6 +1574,9 @@ enum RetainedNode {
     Text {
         value: Mutable<Arc<Value>>,
     },
+    Code {
+        value: Mutable<Arc<Value>>,
+    },
     Block {
         value: Mutable<Arc<Value>>,
         child: Option<Box<RetainedNode>>,
@@ -1619,6 +1631,7 @@ impl RetainedNode {
             }
             (RetainedNode::Link { .. }, Value::Tagged { tag, .. }) => tag.as_ref() == "ElementLink",
             (RetainedNode::Text { .. }, Value::Tagged { tag, .. }) => tag.as_ref() == "ElementText",
+            (RetainedNode::Code { .. }, Value::Tagged { tag, .. }) => tag.as_ref() == "ElementCode",
             (RetainedNode::Block { .. }, Value::Tagged { tag, .. }) => {
                 tag.as_ref() == "ElementBlock"
             }
@@ -1735,6 +1748,7 @@ fn build_retained_tagged(
         }
         "ElementLink" => build_retained_link(fields, full_value, handle, link_path, scene_params),
         "ElementText" => build_retained_text(fields, full_value, handle, link_path, scene_params),
+        "ElementCode" => build_retained_code(full_value, scene_params),
         "ElementBlock" => build_retained_block(fields, full_value, handle, link_path, scene_params),
         "ElementSelect" => build_retained_select_placeholder(fields, full_value, handle, link_path),
         "ElementSlider" => build_retained_slider_placeholder(fields, full_value, handle, link_path),
@@ -2208,6 +2222,8 @@ fn build_retained_button(
             move |raw_el| {
                 let raw_el = apply_raw_css_signals(raw_el, &vm);
                 let raw_el = apply_physical_css_signals(raw_el, &vm, scene_params.clone());
+                let raw_el = raw_el
+                    .attr_signal("disabled", vm.signal_cloned().map(|v| extract_disabled(&v)));
                 if effective_link.is_empty() {
                     raw_el
                 } else {
@@ -3325,6 +3341,52 @@ fn build_retained_text(
     (el.unify(), RetainedNode::Text { value: vm })
 }
 
+/// Text of an ElementCode field; `language` may also be a tag like `Boon`.
+fn code_field_text(fields: &Fields, name: &str) -> String {
+    fields
+        .get(name)
+        .and_then(|field| field.as_text().or_else(|| field.as_tag()))
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// ElementCode — preformatted source, re-highlighted as the content changes.
+/// Structure: ElementCode[element, style, language, content]
+fn build_retained_code(
+    full_value: &Value,
+    scene_params: Option<Mutable<PhysicalSceneParams>>,
+) -> (RawElOrText, RetainedNode) {
+    let vm = Mutable::new(Arc::new(full_value.clone()));
+
+    let el = El::new()
+        .s(Width::fill())
+        .child(code_block(
+            vm.signal_cloned().map(|v| {
+                get_fields(&v)
+                    .map(|fields| code_field_text(fields, "language"))
+                    .unwrap_or_default()
+            }),
+            vm.signal_cloned().map(|v| {
+                get_fields(&v)
+                    .map(|fields| code_field_text(fields, "content"))
+                    .unwrap_or_default()
+            }),
+        ))
+        .s(Font::with_signal_self(
+            vm.signal_cloned().map(|v| extract_font(&v)),
+        ))
+        .s(extract_padding_style(&vm))
+        .update_raw_el({
+            let vm = vm.clone();
+            move |raw_el| {
+                let raw_el = apply_raw_css_signals(raw_el, &vm);
+                apply_physical_css_signals(raw_el, &vm, scene_params.clone())
+            }
+        });
+
+    (el.unify(), RetainedNode::Code { value: vm })
+}
+
 /// ElementBlock — styled wrapper with a single child.
 /// Structure: ElementBlock[settings: [child: <element>, style: [...]]]
 fn build_retained_block(
@@ -3772,6 +3834,10 @@ impl RetainedNode {
                 value.set_neq(Arc::new(new_value.clone()));
             }
 
+            RetainedNode::Code { value } => {
+                value.set_neq(Arc::new(new_value.clone()));
+            }
+
             RetainedNode::Block {
                 value,
                 child,
@@ -4080,6 +4146,19 @@ fn render_tagged_static(tag: &str, fields: &Arc<Fields>) -> RawElOrText {
             };
             el.unify()
         }
+        "ElementCode" => {
+            let mut el = El::new().s(Width::fill()).child(code_block(
+                always(code_field_text(fields, "language")),
+                always(code_field_text(fields, "content")),
+            ));
+            if let Some(font) = extract_font_from_fields(fields) {
+                el = el.s(font);
+            }
+            if let Some(padding) = extract_padding_from_fields(fields) {
+                el = el.s(padding);
+            }
+            el.unify()
+        }
         "ElementBlock" => {
             let child = fields
                 .get("settings")
@@ -1575,7 +1575,7 @@ fn list_quantifier_scalar_compare_operands(
     };
     let quantifier = if path_matches(path, &["List", "any"]) {
         "any"
-    } else if path_matches(path, &["List", "every"]) {
+    } else if path_matches(path, &["List", "every"]) || path_matches(path, &["List", "all"]) {
         "every"
     } else {
         return Ok(None);
@@ -1899,8 +1899,8 @@ fn derived_scalar_operand_in_context(
                 else {
                     return Ok(None);
                 };
-                let right_expr = find_named_argument(arguments, "b")
-                    .ok_or_else(|| "Math/min requires `b`".to_string())?;
+                let right_expr = math_operand_argument(arguments)
+                    .ok_or_else(|| "Math/min requires `with`".to_string())?;
                 let Some(right) =
                     derived_scalar_operand_in_context(right_expr, context, locals, passed, stack)?
                 else {
@@ -5292,11 +5292,13 @@ fn initial_scalar_value_in_context_inner<'a>(
             }
             if path_matches(path, &["Math", "min"]) {
                 let left = initial_scalar_value_in_context(from, context, stack, locals, passed)?;
-                let right = find_named_argument(arguments, "b")
-                    .ok_or_else(|| "Math/min requires `b`".to_string())
+                let right = math_operand_argument(arguments)
+                    .ok_or_else(|| "Math/min requires `with`".to_string())
                     .and_then(|argument| {
                         initial_scalar_value_in_context(argument, context, stack, locals, passed)?
-                            .ok_or_else(|| "Math/min requires an initial numeric `b`".to_string())
+                            .ok_or_else(|| {
+                                "Math/min requires an initial numeric `with`".to_string()
+                            })
                     })?;
                 return Ok(left.map(|left| left.min(right)));
             }
@@ -8162,6 +8164,7 @@ fn object_list_pipe_preserves_source_binding(expression: &StaticExpression) -> b
         StaticExpression::FunctionCall { path, .. } => {
             path_matches(path, &["List", "any"])
                 || path_matches(path, &["List", "every"])
+                || path_matches(path, &["List", "all"])
                 || path_matches(path, &["List", "retain"])
                 || path_matches(path, &["List", "remove_last"])
                 || path_matches(path, &["List", "remove"])
@@ -8344,6 +8347,14 @@ fn find_named_argument<'a>(
         .and_then(|argument| argument.node.value.as_ref())
 }
 
+/// Second operand of `Math/min`, named `with`, or `b` in programs written before the
+/// rename.
+fn math_operand_argument(
+    arguments: &[static_expression::Spanned<StaticArgument>],
+) -> Option<&StaticSpannedExpression> {
+    find_named_argument(arguments, "with").or_else(|| find_named_argument(arguments, "b"))
+}
+
 fn resolve_element_object<'a>(
     element: Option<&'a StaticSpannedExpression>,
     context: &'a LowerContext<'a>,
@@ -9551,6 +9562,10 @@ fn detect_top_level_bool_spec(
     path_bindings: &BTreeMap<String, &StaticSpannedExpression>,
     binding_path: &str,
 ) -> Result<Option<BoolSpec>, String> {
+    if let Some(spec) = detect_top_level_bool_toggle_spec(expression, path_bindings, binding_path)?
+    {
+        return Ok(Some(spec));
+    }
     let StaticExpression::Pipe { from, to } = &expression.node else {
         return Ok(None);
     };
@@ -9606,6 +9621,44 @@ fn detect_top_level_bool_spec(
     Ok(Some(BoolSpec { initial, events }))
 }
 
+/// `Bool/toggle(on: trigger, initial: False)` with a literal (or omitted) `initial`.
+fn detect_top_level_bool_toggle_spec(
+    expression: &StaticSpannedExpression,
+    path_bindings: &BTreeMap<String, &StaticSpannedExpression>,
+    binding_path: &str,
+) -> Result<Option<BoolSpec>, String> {
+    let StaticExpression::FunctionCall { path, arguments } = &expression.node else {
+        return Ok(None);
+    };
+    if !path_matches(path, &["Bool", "toggle"]) {
+        return Ok(None);
+    }
+    let Some(on) = find_named_argument(arguments, "on") else {
+        return Ok(None);
+    };
+    let initial = match find_named_argument(arguments, "initial") {
+        Some(initial) => match extract_bool_literal_opt(initial)? {
+            Some(initial) => initial,
+            None => return Ok(None),
+        },
+        None => false,
+    };
+    let Some((trigger_binding, event_name)) =
+        canonical_event_source_path(on, path_bindings, binding_path)?
+    else {
+        return Ok(None);
+    };
+    Ok(Some(BoolSpec {
+        initial,
+        events: vec![BoolEventSpec {
+            trigger_binding,
+            event_name,
+            update: BoolEventUpdate::Toggle,
+            payload_filter: None,
+        }],
+    }))
+}
+
 fn top_level_bool_event_spec(
     trigger_source: &StaticSpannedExpression,
     trigger_then: &StaticSpannedExpression,
@@ -9686,6 +9739,16 @@ fn list_count_scalar_value(
     let StaticExpression::FunctionCall { path, arguments } = &to.node else {
         return Ok(None);
     };
+    if path_matches(path, &["List", "count_where"]) {
+        return object_list_count_where_scalar_value(
+            from,
+            arguments,
+            "List/count_where",
+            path_bindings,
+            functions,
+            binding_path,
+        );
+    }
     if !path_matches(path, &["List", "count"]) || !arguments.is_empty() {
         return Ok(None);
     }
@@ -9744,6 +9807,20 @@ fn derived_list_count_scalar_spec(
     let StaticExpression::FunctionCall { path, arguments } = &to.node else {
         return Ok(None);
     };
+    if path_matches(path, &["List", "count_where"]) {
+        return Ok(filtered_object_list_ref(
+            from,
+            arguments,
+            "List/count_where",
+            path_bindings,
+            binding_path,
+        )?
+        .map(|(binding, filter)| DerivedScalarSpec::ObjectListCount {
+            binding,
+            filter,
+            target: binding_path.to_string(),
+        }));
+    }
     if !path_matches(path, &["List", "count"]) || !arguments.is_empty() {
         return Ok(None);
     }
@@ -9774,28 +9851,13 @@ fn derived_object_list_ref(
             return Ok(None);
         };
         if path_matches(path, &["List", "retain"]) {
-            let Some(binding) =
-                runtime_object_list_binding_path_for_scalar(from, path_bindings, binding_path)?
-            else {
-                return Ok(None);
-            };
-            let item_name = find_positional_parameter_name(arguments)
-                .ok_or_else(|| "List/retain requires an item parameter name".to_string())?;
-            let condition = find_named_argument(arguments, "if")
-                .ok_or_else(|| "List/retain requires `if`".to_string())?;
-            if matches!(
-                &condition.node,
-                StaticExpression::Literal(static_expression::Literal::Tag(tag))
-                    if tag.as_str() == "True"
-            ) {
-                return Ok(Some((binding, None)));
-            }
-            let Some(filter) =
-                derived_object_list_filter(condition, item_name, path_bindings, binding_path)?
-            else {
-                return Ok(None);
-            };
-            return Ok(Some((binding, Some(filter))));
+            return filtered_object_list_ref(
+                from,
+                arguments,
+                "List/retain",
+                path_bindings,
+                binding_path,
+            );
         }
     }
     if let Some(binding) =
@@ -9806,6 +9868,39 @@ fn derived_object_list_ref(
     Ok(None)
 }
 
+/// The object list `source` and the filter of `List/retain` or `List/count_where`
+/// called on it with `arguments`.
+fn filtered_object_list_ref(
+    source: &StaticSpannedExpression,
+    arguments: &[static_expression::Spanned<StaticArgument>],
+    function_name: &str,
+    path_bindings: &BTreeMap<String, &StaticSpannedExpression>,
+    binding_path: &str,
+) -> Result<Option<(String, Option<ObjectListFilter>)>, String> {
+    let Some(binding) =
+        runtime_object_list_binding_path_for_scalar(source, path_bindings, binding_path)?
+    else {
+        return Ok(None);
+    };
+    let item_name = find_positional_parameter_name(arguments)
+        .ok_or_else(|| format!("{function_name} requires an item parameter name"))?;
+    let condition = find_named_argument(arguments, "if")
+        .ok_or_else(|| format!("{function_name} requires `if`"))?;
+    if matches!(
+        &condition.node,
+        StaticExpression::Literal(static_expression::Literal::Tag(tag))
+            if tag.as_str() == "True"
+    ) {
+        return Ok(Some((binding, None)));
+    }
+    let Some(filter) =
+        derived_object_list_filter(condition, item_name, path_bindings, binding_path)?
+    else {
+        return Ok(None);
+    };
+    Ok(Some((binding, Some(filter))))
+}
+
 fn derived_text_list_ref(
     expression: &StaticSpannedExpression,
     path_bindings: &BTreeMap<String, &StaticSpannedExpression>,
@@ -9972,12 +10067,32 @@ fn filtered_object_list_count_scalar_value(
     if !path_matches(path, &["List", "retain"]) {
         return Ok(None);
     }
+    object_list_count_where_scalar_value(
+        from,
+        arguments,
+        "List/retain",
+        path_bindings,
+        functions,
+        binding_path,
+    )
+}
+
+/// The initial number of items of the object list `source` that `List/retain` or
+/// `List/count_where` with `arguments` keeps.
+fn object_list_count_where_scalar_value(
+    source: &StaticSpannedExpression,
+    arguments: &[static_expression::Spanned<StaticArgument>],
+    function_name: &str,
+    path_bindings: &BTreeMap<String, &StaticSpannedExpression>,
+    functions: &BTreeMap<String, FunctionSpec<'_>>,
+    binding_path: &str,
+) -> Result<Option<i64>, String> {
     let item_name = find_positional_parameter_name(arguments)
-        .ok_or_else(|| "List/retain requires an item parameter name".to_string())?;
+        .ok_or_else(|| format!("{function_name} requires an item parameter name"))?;
     let condition = find_named_argument(arguments, "if")
-        .ok_or_else(|| "List/retain requires `if`".to_string())?;
+        .ok_or_else(|| format!("{function_name} requires `if`"))?;
     let Some(binding) =
-        runtime_object_list_binding_path_for_scalar(from, path_bindings, binding_path)?
+        runtime_object_list_binding_path_for_scalar(source, path_bindings, binding_path)?
     else {
         return Ok(None);
     };
@@ -10127,11 +10242,11 @@ fn arithmetic_scalar_value(
                 else {
                     return Ok(None);
                 };
-                let right = find_named_argument(arguments, "b")
-                    .ok_or_else(|| "Math/min requires `b`".to_string())
+                let right = math_operand_argument(arguments)
+                    .ok_or_else(|| "Math/min requires `with`".to_string())
                     .and_then(|argument| {
                         scalar_operand_value(argument, path_bindings, functions, binding_path)?
-                            .ok_or_else(|| "Math/min requires a numeric `b`".to_string())
+                            .ok_or_else(|| "Math/min requires a numeric `with`".to_string())
                     })?;
                 return Ok(Some(left.min(right)));
             }
@@ -10592,8 +10707,8 @@ fn derived_scalar_operand(
                 let Some(left) = derived_scalar_operand(from, path_bindings, binding_path)? else {
                     return Ok(None);
                 };
-                let right_expr = find_named_argument(arguments, "b")
-                    .ok_or_else(|| "Math/min requires `b`".to_string())?;
+                let right_expr = math_operand_argument(arguments)
+                    .ok_or_else(|| "Math/min requires `with`".to_string())?;
                 let Some(right) = derived_scalar_operand(right_expr, path_bindings, binding_path)?
                 else {
                     return Ok(None);
@@ -11026,24 +11141,45 @@ fn detect_local_bool_spec(
     if let Some(spec) = detect_local_hold_bool_spec(expression)? {
         return Ok(Some(spec));
     }
-    let StaticExpression::Pipe { from, to } = &expression.node else {
-        return Ok(None);
-    };
-    let StaticExpression::FunctionCall { path, arguments } = &to.node else {
-        return Ok(None);
+    // `from |> Bool/toggle(when: trigger)` or `Bool/toggle(on: trigger, initial: from)`
+    let (from, path, arguments, trigger_argument) = match &expression.node {
+        StaticExpression::Pipe { from, to } => {
+            let StaticExpression::FunctionCall { path, arguments } = &to.node else {
+                return Ok(None);
+            };
+            (Some(from.as_ref()), path, arguments, "when")
+        }
+        StaticExpression::FunctionCall { path, arguments } => (
+            find_named_argument(arguments, "initial"),
+            path,
+            arguments,
+            "on",
+        ),
+        _ => return Ok(None),
     };
     if !path_matches(path, &["Bool", "toggle"]) {
         return Ok(None);
     }
-    let Some(when) = find_named_argument(arguments, "when") else {
+    let Some(when) = find_named_argument(arguments, trigger_argument) else {
         return Ok(None);
     };
     let Some((trigger_binding, event_name)) = object_event_source_from_expression(when)? else {
         return Ok(None);
     };
-    let Some(mut spec) = detect_local_bool_source_spec(from)? else {
+    let spec = match from {
+        Some(from) => detect_local_bool_source_spec(from)?,
+        None => Some(BoolSpec {
+            initial: false,
+            events: Vec::new(),
+        }),
+    };
+    let Some(mut spec) = spec else {
         return Ok(None);
     };
+    if trigger_argument == "on" {
+        // Only the first Bool of `initial` counts, like `initial |> HOLD state {..}`
+        spec.events.clear();
+    }
     spec.events.push(BoolEventSpec {
         trigger_binding,
         event_name,
@@ -21708,6 +21844,74 @@ document: Document/new(root: Element/button(
         );
     }
 
+    #[test]
+    fn todo_mvc_completed_bool_spec_detects_checkbox_and_toggle_all() {
+        let context = todo_mvc_test_context();
+        let new_todo = context
+            .functions
+            .get("new_todo")
+            .expect("new_todo function should exist");
+        let completed = super::resolve_static_object_field_expression(
+            new_todo.body,
+            &context.functions,
+            "completed",
+        )
+        .expect("new_todo.completed should exist");
+        let spec = super::detect_local_bool_spec(completed)
+            .expect("completed bool spec should inspect")
+            .expect("completed should lower as a bool spec");
+
+        assert!(!spec.initial);
+        assert!(
+            spec.events.iter().any(|event| {
+                event.trigger_binding == "todo_elements.todo_checkbox"
+                    && event.event_name == "click"
+                    && matches!(event.update, super::BoolEventUpdate::Toggle)
+            }),
+            "completed events were {:?}",
+            spec.events,
+        );
+        assert!(
+            spec.events.iter().any(|event| {
+                event.trigger_binding == "store.elements.toggle_all_checkbox"
+                    && event.event_name == "click"
+            }),
+            "completed events were {:?}",
+            spec.events,
+        );
+    }
+
+    #[test]
+    fn switch_hold_test_show_item_a_bool_toggle_spec_starts_true() {
+        let source = include_str!(
+            "../../../playground/frontend/src/examples/switch_hold_test/switch_hold_test.bn"
+        );
+        let expressions =
+            parse_static_expressions(source).expect("switch_hold_test source should parse");
+        let bindings = top_level_bindings(&expressions);
+        let path_bindings = flatten_binding_paths(&bindings);
+        let show_item_a = path_bindings
+            .get("store.show_item_a")
+            .copied()
+            .expect("store.show_item_a binding should exist");
+        let spec =
+            super::detect_top_level_bool_spec(show_item_a, &path_bindings, "store.show_item_a")
+                .expect("show_item_a should inspect")
+                .expect("show_item_a should lower as a bool spec");
+
+        assert!(spec.initial);
+        assert!(
+            matches!(
+                spec.events.as_slice(),
+                [event] if event.trigger_binding.ends_with("view_toggle")
+                    && event.event_name == "press"
+                    && matches!(event.update, super::BoolEventUpdate::Toggle)
+            ),
+            "show_item_a events were {:?}",
+            spec.events,
+        );
+    }
+
     #[test]
     fn todo_mvc_physical_selected_filter_spec_detects_filter_button_events() {
         let context = todo_mvc_physical_test_context();
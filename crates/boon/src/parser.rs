@@ -1,4 +1,5 @@
 use chumsky::{input::ValueInput, pratt::*, prelude::*};
+use serde::Serialize;
 use std::cell::Cell;
 use std::fmt;
 
@@ -30,13 +31,21 @@ pub use scope_resolver::{Referenceables, resolve_references};
 mod persistence_resolver;
 pub use persistence_resolver::{
     Persistence, PersistenceId, PersistenceStatus, Scope, resolve_persistence,
+    resolve_persistence_with_pairs,
 };
 
 mod source;
 pub use source::{SourceCode, StrSlice};
 
+pub mod builtin_aliases;
+pub mod builtins;
+pub mod exhaustiveness;
 pub mod formatter;
+pub mod highlight;
+pub mod rename;
+pub mod source_map;
 pub mod static_expression;
+pub mod unused_variables;
 
 pub use chumsky::prelude::{Input, Parser};
 
@@ -48,9 +57,40 @@ pub fn span_at(pos: usize) -> Span {
     SimpleSpan::new((), pos..pos)
 }
 
-#[derive(Debug, Clone)]
+/// Byte range of a node in the AST JSON (`boon check --emit-ast`).
+#[derive(Serialize)]
+struct SpanRange {
+    start: usize,
+    end: usize,
+}
+
+pub(crate) fn serialize_span<S: serde::Serializer>(
+    span: &Span,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    SpanRange {
+        start: span.start,
+        end: span.end,
+    }
+    .serialize(serializer)
+}
+
+fn serialize_optional_span<S: serde::Serializer>(
+    span: &Option<Span>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    span.map(|span| SpanRange {
+        start: span.start,
+        end: span.end,
+    })
+    .serialize(serializer)
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct Spanned<T> {
+    #[serde(serialize_with = "serialize_span")]
     pub span: Span,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub persistence: Option<Persistence>,
     pub node: T,
 }
@@ -858,7 +898,7 @@ where
 }
 
 // @TODO not everything is expression, FUNCTIONs can be defined only in the root, etc.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub enum Expression<'code> {
     Variable(Box<Variable<'code>>),
     Literal(Literal<'code>),
@@ -952,7 +992,7 @@ pub enum Expression<'code> {
     },
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub enum Comparator<'code> {
     Equal {
         operand_a: Box<Spanned<Expression<'code>>>,
@@ -980,7 +1020,7 @@ pub enum Comparator<'code> {
     },
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub enum ArithmeticOperator<'code> {
     Negate {
         operand: Box<Spanned<Expression<'code>>>,
@@ -1003,23 +1043,24 @@ pub enum ArithmeticOperator<'code> {
     },
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub enum TextPart<'code> {
     // Plain text content
     Text(&'code str),
     // Interpolated variable: {var_name}
     Interpolation {
         var: &'code str,
+        #[serde(serialize_with = "serialize_optional_span")]
         referenced_span: Option<SimpleSpan>,
     },
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Object<'code> {
     pub variables: Vec<Spanned<Variable<'code>>>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Variable<'code> {
     pub name: &'code str,
     pub is_referenced: bool,
@@ -1029,36 +1070,37 @@ pub struct Variable<'code> {
     pub value_changed: bool,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub enum Literal<'code> {
     Number(f64),
     Tag(&'code str),
     Text(&'code str),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct MapEntry<'code> {
     pub key: Spanned<MapEntryKey<'code>>,
     pub value: Spanned<Expression<'code>>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub enum MapEntryKey<'code> {
     Literal(Literal<'code>),
     Alias(Alias<'code>),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Argument<'code> {
     pub name: &'code str,
     pub is_referenced: bool,
     pub value: Option<Spanned<Expression<'code>>>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub enum Alias<'code> {
     WithoutPassed {
         parts: Vec<&'code str>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         referenceables: Option<Referenceables<'code>>,
     },
     WithPassed {
@@ -1087,13 +1129,13 @@ impl<'code> fmt::Display for Alias<'code> {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Arm<'code> {
     pub pattern: Pattern<'code>,
     pub body: Spanned<Expression<'code>>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub enum Pattern<'code> {
     Literal(Literal<'code>),
     List {
@@ -1116,18 +1158,19 @@ pub enum Pattern<'code> {
     /// Produced by dotted paths (`parent.field`) or braced variables (`{var}`).
     ValueComparison {
         path: Vec<&'code str>,
+        #[serde(serialize_with = "serialize_optional_span")]
         referenced_span: Option<SimpleSpan>,
     },
     WildCard,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct PatternVariable<'code> {
     pub name: &'code str,
     pub value: Option<Pattern<'code>>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct PatternMapEntry<'code> {
     pub key: Pattern<'code>,
     pub value: Option<Pattern<'code>>,
@@ -1358,4 +1401,33 @@ mod tests {
         assert!(errors.is_empty(), "Parser errors: {:?}", errors);
         assert!(ast.is_some(), "AST should be produced");
     }
+
+    #[test]
+    fn ast_json_has_a_span_on_every_node() {
+        let code = "counter: 1 + 2";
+        let tokens = lexer().parse(code).unwrap();
+        let input = tokens.map(
+            span_at(code.len()),
+            |Spanned {
+                 node,
+                 span,
+                 persistence: _,
+             }| (node, span),
+        );
+        let expressions = parser().parse(input).unwrap();
+        let json = serde_json::to_value(&expressions).unwrap();
+
+        let variable = &json[0];
+        assert_eq!(
+            variable["span"],
+            serde_json::json!({ "start": 0, "end": 14 })
+        );
+        assert_eq!(variable["node"]["Variable"]["name"], "counter");
+        let value = &variable["node"]["Variable"]["value"];
+        assert_eq!(value["span"], serde_json::json!({ "start": 9, "end": 14 }));
+        let add = &value["node"]["ArithmeticOperator"]["Add"];
+        assert_eq!(add["operand_a"]["node"]["Literal"]["Number"], 1.0);
+        assert_eq!(add["operand_b"]["span"]["start"], 13);
+        assert!(variable.get("persistence").is_none());
+    }
 }
@@ -0,0 +1,159 @@
+//! Renamed builtins that keep working under their old path.
+//!
+//! Engines look builtin calls up through [`canonical_builtin_path`], so saved
+//! projects and shared playground links keep running after a rename. Tooling
+//! reports every use of an old path with [`deprecated_builtin_calls`] so authors
+//! can migrate before the alias is removed.
+
+use std::ops::Range;
+
+use chumsky::prelude::Parser;
+
+use super::{Token, lexer};
+
+/// Old builtin path still accepted in place of `new_path`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct BuiltinAlias {
+    pub old_path: [&'static str; 2],
+    pub new_path: [&'static str; 2],
+    /// When the alias goes away, shown to the author in the diagnostic.
+    pub removal: &'static str,
+}
+
+impl BuiltinAlias {
+    pub fn old_name(&self) -> String {
+        self.old_path.join("/")
+    }
+
+    pub fn new_name(&self) -> String {
+        self.new_path.join("/")
+    }
+
+    /// Diagnostic text shared by the playground, engines and `boon-cli check`.
+    pub fn message(&self) -> String {
+        format!(
+            "`{}` is deprecated, use `{}` instead; {}",
+            self.old_name(),
+            self.new_name(),
+            self.removal
+        )
+    }
+}
+
+/// Every renamed builtin, oldest first.
+pub const BUILTIN_ALIASES: &[BuiltinAlias] = &[BuiltinAlias {
+    old_path: ["Stream", "pulses"],
+    new_path: ["Stream", "sequence"],
+    removal: "the alias will be removed once no bundled example uses it",
+}];
+
+/// Alias entry for `path`, if `path` is an old builtin name.
+pub fn builtin_alias(path: &[&str]) -> Option<&'static BuiltinAlias> {
+    BUILTIN_ALIASES
+        .iter()
+        .find(|alias| alias.old_path.as_slice() == path)
+}
+
+/// `path` with an old builtin name replaced by its current one.
+pub fn canonical_builtin_path<'a>(path: Vec<&'a str>) -> Vec<&'a str> {
+    match builtin_alias(&path) {
+        Some(alias) => alias.new_path.to_vec(),
+        None => path,
+    }
+}
+
+/// Call of an aliased builtin found in source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeprecatedBuiltinCall {
+    /// Byte range of the `Module/function` path.
+    pub range: Range<usize>,
+    pub alias: &'static BuiltinAlias,
+}
+
+/// Every `Module/function(` call in `source` that uses an old builtin name, in source order.
+///
+/// Works on tokens, so it also reports calls in code that does not parse yet.
+pub fn deprecated_builtin_calls(source: &str) -> Vec<DeprecatedBuiltinCall> {
+    let Some(tokens) = lexer().parse(source).into_output() else {
+        return Vec::new();
+    };
+    tokens
+        .windows(4)
+        .filter_map(|window| {
+            let [module, slash, function, bracket] = window else {
+                return None;
+            };
+            let (
+                Token::PascalCaseIdentifier(module_name),
+                Token::Slash,
+                Token::SnakeCaseIdentifier(function_name),
+                Token::BracketRoundOpen,
+            ) = (&module.node, &slash.node, &function.node, &bracket.node)
+            else {
+                return None;
+            };
+            let alias = builtin_alias(&[*module_name, *function_name])?;
+            Some(DeprecatedBuiltinCall {
+                range: module.span.start..function.span.end,
+                alias,
+            })
+        })
+        .collect()
+}
+
+/// Aliases used anywhere in `source`, each listed once.
+///
+/// Engines emit one warning per entry on every compile.
+pub fn deprecated_builtins_used(source: &str) -> Vec<&'static BuiltinAlias> {
+    let mut aliases: Vec<&'static BuiltinAlias> = Vec::new();
+    for call in deprecated_builtin_calls(source) {
+        if !aliases.contains(&call.alias) {
+            aliases.push(call.alias);
+        }
+    }
+    aliases
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn old_path_resolves_to_new_path() {
+        assert_eq!(
+            canonical_builtin_path(vec!["Stream", "pulses"]),
+            vec!["Stream", "sequence"]
+        );
+        assert_eq!(
+            canonical_builtin_path(vec!["Stream", "skip"]),
+            vec!["Stream", "skip"]
+        );
+    }
+
+    #[test]
+    fn aliases_never_chain_or_shadow_each_other() {
+        for alias in BUILTIN_ALIASES {
+            assert!(
+                builtin_alias(&alias.new_path).is_none(),
+                "`{}` is itself aliased",
+                alias.new_name()
+            );
+        }
+    }
+
+    #[test]
+    fn reports_each_call_with_its_path_range() {
+        let source = "a: 3 |> Stream/pulses()\nb: 2 |> Stream/pulses() |> Stream/skip(count: 1)";
+        let calls = deprecated_builtin_calls(source);
+        assert_eq!(calls.len(), 2);
+        assert_eq!(&source[calls[0].range.clone()], "Stream/pulses");
+        assert_eq!(calls[1].alias.new_name(), "Stream/sequence");
+        assert_eq!(deprecated_builtins_used(source).len(), 1);
+    }
+
+    #[test]
+    fn current_names_and_non_calls_are_not_reported() {
+        let source = "a: 3 |> Stream/sequence()\n-- Stream/pulses()";
+        assert!(deprecated_builtin_calls(source).is_empty());
+    }
+}
@@ -0,0 +1,335 @@
+//! Registry of the builtin `Module/function` paths every engine provides.
+//!
+//! Engines dispatch builtin calls by path; this list is what tooling checks calls
+//! against, so a typo like `List/appendd(..)` is an error with a suggestion
+//! instead of a call that silently produces nothing. Calls into modules that are
+//! not builtin (`Theme/material(..)`) belong to user files and are not checked
+//! here. Old names from [`super::builtin_aliases`] count as known.
+
+use std::ops::Range;
+
+use chumsky::prelude::Parser;
+
+use super::builtin_aliases::builtin_alias;
+use super::{Token, lexer};
+
+/// Every builtin function, sorted by module and name.
+pub const BUILTIN_FUNCTIONS: &[[&str; 2]] = &[
+    ["Args", "get"],
+    ["Bits", "add"],
+    ["Bits", "and"],
+    ["Bits", "equal"],
+    ["Bits", "get"],
+    ["Bits", "greater_equal"],
+    ["Bits", "less_equal"],
+    ["Bits", "less_than"],
+    ["Bits", "multiply"],
+    ["Bits", "not_equal"],
+    ["Bits", "or"],
+    ["Bits", "set"],
+    ["Bits", "shift_left"],
+    ["Bits", "shift_right"],
+    ["Bits", "slice"],
+    ["Bits", "subtract"],
+    ["Bits", "sum"],
+    ["Bits", "to_bool_list"],
+    ["Bits", "to_number"],
+    ["Bits", "xor"],
+    ["Bits", "zero_extend"],
+    ["Bool", "and"],
+    ["Bool", "not"],
+    ["Bool", "or"],
+    ["Bool", "to_u_bit"],
+    ["Bool", "toggle"],
+    ["Bool", "xor"],
+    ["Build", "fail"],
+    ["Build", "succeed"],
+    ["Directory", "entries"],
+    ["Document", "confirm_unload"],
+    ["Document", "new"],
+    ["Document", "online"],
+    ["Document", "query_params"],
+    ["Document", "set_query_param"],
+    ["Document", "visible"],
+    ["Element", "block"],
+    ["Element", "button"],
+    ["Element", "checkbox"],
+    ["Element", "code"],
+    ["Element", "container"],
+    ["Element", "label"],
+    ["Element", "link"],
+    ["Element", "paragraph"],
+    ["Element", "select"],
+    ["Element", "slider"],
+    ["Element", "stack"],
+    ["Element", "stripe"],
+    ["Element", "svg"],
+    ["Element", "svg_circle"],
+    ["Element", "text"],
+    ["Element", "text_input"],
+    ["File", "read_text"],
+    ["File", "write_text"],
+    ["File", "write_with_map"],
+    ["Light", "ambient"],
+    ["Light", "directional"],
+    ["Light", "spot"],
+    ["Lights", "ambient"],
+    ["Lights", "basic"],
+    ["Lights", "directional"],
+    ["List", "all"],
+    ["List", "any"],
+    ["List", "append"],
+    ["List", "chain"],
+    ["List", "chunk"],
+    ["List", "clear"],
+    ["List", "concat"],
+    ["List", "contains"],
+    ["List", "count"],
+    ["List", "count_where"],
+    ["List", "drop"],
+    ["List", "every"],
+    ["List", "filter"],
+    ["List", "find"],
+    ["List", "first"],
+    ["List", "flatten"],
+    ["List", "get"],
+    ["List", "index_of"],
+    ["List", "insert"],
+    ["List", "is_empty"],
+    ["List", "is_not_empty"],
+    ["List", "last"],
+    ["List", "latest"],
+    ["List", "map"],
+    ["List", "max"],
+    ["List", "min"],
+    ["List", "move"],
+    ["List", "product"],
+    ["List", "range"],
+    ["List", "remove"],
+    ["List", "remove_last"],
+    ["List", "retain"],
+    ["List", "reverse"],
+    ["List", "sort_by"],
+    ["List", "sum"],
+    ["List", "take"],
+    ["List", "to_u_bits"],
+    ["List", "unique"],
+    ["List", "unique_by"],
+    ["List", "update"],
+    ["List", "zip"],
+    ["Log", "error"],
+    ["Log", "info"],
+    ["Math", "abs"],
+    ["Math", "average"],
+    ["Math", "ceil"],
+    ["Math", "clamp"],
+    ["Math", "count"],
+    ["Math", "divide_int"],
+    ["Math", "floor"],
+    ["Math", "max"],
+    ["Math", "min"],
+    ["Math", "modulo"],
+    ["Math", "negate_if"],
+    ["Math", "pow"],
+    ["Math", "random"],
+    ["Math", "round"],
+    ["Math", "sqrt"],
+    ["Math", "sum"],
+    ["Memory", "initialize"],
+    ["Memory", "read"],
+    ["Memory", "write"],
+    ["Number", "parse"],
+    ["Number", "to_text"],
+    ["Router", "go_to"],
+    ["Router", "route"],
+    ["Scene", "new"],
+    ["Stream", "debounce"],
+    ["Stream", "distinct"],
+    ["Stream", "gate"],
+    ["Stream", "max"],
+    ["Stream", "min"],
+    ["Stream", "sample"],
+    ["Stream", "sequence"],
+    ["Stream", "skip"],
+    ["Stream", "take"],
+    ["Text", "char_at"],
+    ["Text", "char_code"],
+    ["Text", "contains"],
+    ["Text", "empty"],
+    ["Text", "ends_with"],
+    ["Text", "find"],
+    ["Text", "find_closing"],
+    ["Text", "from_char_code"],
+    ["Text", "is_empty"],
+    ["Text", "is_not_empty"],
+    ["Text", "join"],
+    ["Text", "join_lines"],
+    ["Text", "length"],
+    ["Text", "pad_end"],
+    ["Text", "pad_start"],
+    ["Text", "replace"],
+    ["Text", "slice"],
+    ["Text", "space"],
+    ["Text", "split"],
+    ["Text", "starts_with"],
+    ["Text", "substring"],
+    ["Text", "to_lowercase"],
+    ["Text", "to_number"],
+    ["Text", "to_uppercase"],
+    ["Text", "trim"],
+    ["Timer", "interval"],
+    ["Ulid", "generate"],
+    ["Url", "encode"],
+];
+
+/// Most suggestions listed for one unknown call.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Whether `module` names a builtin module such as `List`.
+pub fn is_builtin_module(module: &str) -> bool {
+    BUILTIN_FUNCTIONS.iter().any(|[name, _]| *name == module)
+}
+
+/// Whether `path` is a builtin function, by its current or an old name.
+pub fn is_builtin_function(path: &[&str]) -> bool {
+    BUILTIN_FUNCTIONS
+        .iter()
+        .any(|function| function.as_slice() == path)
+        || builtin_alias(path).is_some()
+}
+
+/// Levenshtein distance over chars.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Known builtins closest to `path`, nearest first, e.g. `List/append` for
+/// `List/appendd`. Empty when nothing is close.
+pub fn suggestions(path: &[&str]) -> Vec<String> {
+    let name = path.join("/");
+    // Allow about one typo per four characters of the function name
+    let max_distance = path.last().map_or(0, |function| function.len() / 4).max(2);
+    let mut candidates = BUILTIN_FUNCTIONS
+        .iter()
+        .map(|function| {
+            let candidate = function.join("/");
+            (edit_distance(&name, &candidate), candidate)
+        })
+        .filter(|(distance, _)| *distance <= max_distance)
+        .collect::<Vec<_>>();
+    candidates.sort();
+    candidates
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
+
+/// Call of a function that its builtin module does not have.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownBuiltinCall {
+    /// Byte range of the `Module/function` path.
+    pub range: Range<usize>,
+    pub path: String,
+    pub suggestions: Vec<String>,
+}
+
+impl UnknownBuiltinCall {
+    /// Diagnostic text shared by `boon-cli check` and `--strict`.
+    pub fn message(&self) -> String {
+        match self.suggestions.as_slice() {
+            [] => format!("unknown builtin function `{}`", self.path),
+            [suggestion] => format!(
+                "unknown builtin function `{}`, did you mean `{}`?",
+                self.path, suggestion
+            ),
+            suggestions => format!(
+                "unknown builtin function `{}`, did you mean one of `{}`?",
+                self.path,
+                suggestions.join("`, `")
+            ),
+        }
+    }
+}
+
+/// Every `Module/function(` call in `source` whose module is builtin but has no
+/// such function, in source order. Modules for which `is_user_module` is true are
+/// skipped, since a user file may shadow a builtin module.
+///
+/// Works on tokens, like [`super::builtin_aliases::deprecated_builtin_calls`].
+pub fn unknown_builtin_calls(
+    source: &str,
+    is_user_module: impl Fn(&str) -> bool,
+) -> Vec<UnknownBuiltinCall> {
+    let Some(tokens) = lexer().parse(source).into_output() else {
+        return Vec::new();
+    };
+    tokens
+        .windows(4)
+        .filter_map(|window| {
+            let [module, slash, function, bracket] = window else {
+                return None;
+            };
+            let (
+                Token::PascalCaseIdentifier(module_name),
+                Token::Slash,
+                Token::SnakeCaseIdentifier(function_name),
+                Token::BracketRoundOpen,
+            ) = (&module.node, &slash.node, &function.node, &bracket.node)
+            else {
+                return None;
+            };
+            let path = [*module_name, *function_name];
+            if !is_builtin_module(module_name)
+                || is_user_module(module_name)
+                || is_builtin_function(&path)
+            {
+                return None;
+            }
+            Some(UnknownBuiltinCall {
+                range: module.span.start..function.span.end,
+                path: path.join("/"),
+                suggestions: suggestions(&path),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_is_sorted_without_duplicates() {
+        assert!(BUILTIN_FUNCTIONS.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn misspelled_call_suggests_the_nearest_builtin() {
+        let source = "items: LIST { 1 } |> List/appendd(item: 2)\ncount: items |> List/count()";
+        let calls = unknown_builtin_calls(source, |_| false);
+        assert_eq!(calls.len(), 1);
+        assert_eq!(&source[calls[0].range.clone()], "List/appendd");
+        assert_eq!(
+            calls[0].message(),
+            "unknown builtin function `List/appendd`, did you mean `List/append`?"
+        );
+    }
+
+    #[test]
+    fn user_modules_and_old_names_are_not_reported() {
+        let source = "a: Theme/material(of: Danger)\nb: 3 |> Stream/pulses()\nc: List/shuffle()";
+        assert!(unknown_builtin_calls(source, |module| module == "List").is_empty());
+        assert_eq!(suggestions(&["List", "shuffle"]), Vec::<String>::new());
+    }
+}
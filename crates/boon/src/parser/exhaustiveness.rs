@@ -0,0 +1,487 @@
+//! Best-effort exhaustiveness hints for WHEN/WHILE over statically known tag sets.
+//!
+//! A WHEN without an arm for a value it can receive silently stops producing.
+//! When every value its input can take is a tag literal we can see in the source
+//! (tag producers, THEN bodies, upstream WHEN arms, Bool-returning builtins and
+//! comparisons), missing arms are reported.
+//!
+//! The analysis gives up on anything it cannot see through (function parameters,
+//! field paths, `PASSED`, non-tag patterns, ...), so it may miss dead ends but
+//! never reports a WHEN that is actually exhaustive.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use chumsky::prelude::{Input, Parser};
+
+use super::static_expression::{
+    Alias, ArithmeticOperator, Arm, Comparator, Expression, Literal, Pattern, Spanned,
+    convert_expressions,
+};
+use super::{
+    SourceCode, Span, Token, lexer, parser, reset_expression_depth, resolve_references, span_at,
+};
+
+/// Builtins whose result is always `True` or `False`.
+const BOOL_FUNCTIONS: &[[&str; 2]] = &[
+    ["Bool", "and"],
+    ["Bool", "not"],
+    ["Bool", "or"],
+    ["Bool", "toggle"],
+    ["List", "all"],
+    ["List", "any"],
+    ["List", "contains"],
+    ["List", "is_empty"],
+    ["List", "is_not_empty"],
+    ["Text", "contains"],
+    ["Text", "ends_with"],
+    ["Text", "is_empty"],
+    ["Text", "is_not_empty"],
+    ["Text", "starts_with"],
+];
+
+/// WHEN/WHILE that has no arm for some tags its input can produce.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExhaustivenessWarning {
+    /// Span of the WHEN/WHILE block
+    pub span: Span,
+    /// `WHEN` or `WHILE`
+    pub keyword: &'static str,
+    /// Unhandled tags, sorted
+    pub missing: Vec<String>,
+}
+
+impl ExhaustivenessWarning {
+    /// Diagnostic text with the 1-based line of the block in `source`.
+    pub fn message(&self, source: &str) -> String {
+        let line = source
+            .get(..self.span.start)
+            .map_or(1, |prefix| prefix.matches('\n').count() + 1);
+        format!(
+            "line {line}: {} has no arm for {}; add the missing arms or a `__` arm",
+            self.keyword,
+            self.missing
+                .iter()
+                .map(|tag| format!("`{tag}`"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+/// Checks every WHEN/WHILE in a reference-resolved AST.
+pub fn check_exhaustiveness(ast: &[Spanned<Expression>]) -> Vec<ExhaustivenessWarning> {
+    let mut analysis = Analysis {
+        variables: HashMap::new(),
+        visiting: HashSet::new(),
+    };
+    for expression in ast {
+        analysis.collect_variables(expression);
+    }
+    let mut warnings = Vec::new();
+    for expression in ast {
+        analysis.check(expression, &mut warnings);
+    }
+    warnings.sort_by_key(|warning| warning.span.start);
+    warnings
+}
+
+/// Parses `source` and checks it; errors are the first lex, parse or reference error.
+pub fn check_source_exhaustiveness(source: &str) -> Result<Vec<ExhaustivenessWarning>, String> {
+    let (tokens, lex_errors) = lexer().parse(source).into_output_errors();
+    if let Some(error) = lex_errors.into_iter().next() {
+        return Err(format!("lex error: {error}"));
+    }
+    let mut tokens = tokens.ok_or_else(|| "lex error: no tokens produced".to_owned())?;
+    tokens.retain(|token| !matches!(token.node, Token::Comment(_)));
+
+    reset_expression_depth();
+    let (ast, parse_errors) = parser()
+        .parse(tokens.map(
+            span_at(source.len()),
+            |super::Spanned {
+                 node,
+                 span,
+                 persistence: _,
+             }| (node, span),
+        ))
+        .into_output_errors();
+    if let Some(error) = parse_errors.into_iter().next() {
+        return Err(format!("parse error: {error}"));
+    }
+    let ast = ast.ok_or_else(|| "parse error: no AST produced".to_owned())?;
+    let ast = resolve_references(ast).map_err(|errors| {
+        errors.into_iter().next().map_or_else(
+            || "reference error".to_owned(),
+            |error| format!("reference error: {error}"),
+        )
+    })?;
+    let ast = convert_expressions(SourceCode::new(source.to_owned()), ast);
+    Ok(check_exhaustiveness(&ast))
+}
+
+struct Analysis<'ast> {
+    /// Variable values by the span aliases resolve to
+    variables: HashMap<Span, &'ast Spanned<Expression>>,
+    /// Variables being inferred, to stop on reference cycles
+    visiting: HashSet<Span>,
+}
+
+impl<'ast> Analysis<'ast> {
+    fn collect_variables(&mut self, expression: &'ast Spanned<Expression>) {
+        match &expression.node {
+            Expression::Variable(variable) => {
+                self.variables.insert(expression.span, &variable.value);
+            }
+            Expression::Object(object) | Expression::TaggedObject { object, .. } => {
+                for variable in &object.variables {
+                    self.variables.insert(variable.span, &variable.node.value);
+                }
+            }
+            Expression::Block { variables, .. } => {
+                for variable in variables {
+                    self.variables.insert(variable.span, &variable.node.value);
+                }
+            }
+            _ => {}
+        }
+        for child in children(&expression.node) {
+            self.collect_variables(child);
+        }
+    }
+
+    fn check(
+        &mut self,
+        expression: &'ast Spanned<Expression>,
+        warnings: &mut Vec<ExhaustivenessWarning>,
+    ) {
+        if let Expression::Pipe { from, to } = &expression.node {
+            let (keyword, arms) = match &to.node {
+                Expression::When { arms } => ("WHEN", Some(arms)),
+                Expression::While { arms } => ("WHILE", Some(arms)),
+                _ => ("", None),
+            };
+            if let Some(covered) = arms.and_then(|arms| covered_tags(arms)) {
+                if let Some(candidates) = self.candidate_tags(from) {
+                    let missing: Vec<String> = candidates.difference(&covered).cloned().collect();
+                    if !missing.is_empty() {
+                        warnings.push(ExhaustivenessWarning {
+                            span: to.span,
+                            keyword,
+                            missing,
+                        });
+                    }
+                }
+            }
+        }
+        for child in children(&expression.node) {
+            self.check(child, warnings);
+        }
+    }
+
+    /// Every tag `expression` can produce, or `None` when it may produce anything else.
+    fn candidate_tags(
+        &mut self,
+        expression: &'ast Spanned<Expression>,
+    ) -> Option<BTreeSet<String>> {
+        match &expression.node {
+            Expression::Literal(Literal::Tag(tag)) => Some(BTreeSet::from([tag.to_string()])),
+            Expression::Skip => Some(BTreeSet::new()),
+            Expression::Comparator(_) => Some(bool_tags()),
+            Expression::FunctionCall { path, .. } if is_bool_function(path) => Some(bool_tags()),
+            Expression::Alias(Alias::WithoutPassed {
+                parts,
+                referenced_span: Some(referenced_span),
+            }) if parts.len() == 1 => {
+                let value = *self.variables.get(referenced_span)?;
+                if !self.visiting.insert(*referenced_span) {
+                    return None;
+                }
+                let tags = self.candidate_tags(value);
+                self.visiting.remove(referenced_span);
+                tags
+            }
+            Expression::Latest { inputs } => self.union(inputs.iter()),
+            Expression::Block { output, .. } => self.candidate_tags(output),
+            Expression::Pipe { from, to } => match &to.node {
+                Expression::When { arms } | Expression::While { arms } => {
+                    self.union(arms.iter().map(|arm| &arm.body))
+                }
+                Expression::Then { body } => self.candidate_tags(body),
+                Expression::Hold { body, .. } => self.union([from.as_ref(), body.as_ref()]),
+                Expression::FunctionCall { path, .. } if is_bool_function(path) => {
+                    Some(bool_tags())
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn union(
+        &mut self,
+        expressions: impl IntoIterator<Item = &'ast Spanned<Expression>>,
+    ) -> Option<BTreeSet<String>> {
+        let mut tags = BTreeSet::new();
+        for expression in expressions {
+            tags.extend(self.candidate_tags(expression)?);
+        }
+        Some(tags)
+    }
+}
+
+/// Tags matched by `arms`, or `None` when an arm can match more than a single tag.
+fn covered_tags(arms: &[Arm]) -> Option<BTreeSet<String>> {
+    arms.iter()
+        .map(|arm| match &arm.pattern {
+            Pattern::Literal(Literal::Tag(tag)) => Some(tag.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn bool_tags() -> BTreeSet<String> {
+    BTreeSet::from(["False".to_owned(), "True".to_owned()])
+}
+
+fn is_bool_function(path: &[super::StrSlice]) -> bool {
+    BOOL_FUNCTIONS.iter().any(|function| {
+        path.len() == function.len()
+            && path
+                .iter()
+                .zip(function)
+                .all(|(part, name)| part.as_str() == *name)
+    })
+}
+
+fn children(expression: &Expression) -> Vec<&Spanned<Expression>> {
+    match expression {
+        Expression::Variable(variable) => vec![&variable.value],
+        Expression::List { items } => items.iter().collect(),
+        Expression::Object(object) | Expression::TaggedObject { object, .. } => object
+            .variables
+            .iter()
+            .map(|variable| &variable.node.value)
+            .collect(),
+        Expression::Map { entries } => entries.iter().map(|entry| &entry.value).collect(),
+        Expression::Function { body, .. }
+        | Expression::Hold { body, .. }
+        | Expression::Then { body } => vec![body.as_ref()],
+        Expression::FunctionCall { arguments, .. } => arguments
+            .iter()
+            .filter_map(|argument| argument.node.value.as_ref())
+            .collect(),
+        Expression::Latest { inputs } => inputs.iter().collect(),
+        Expression::Flush { value } | Expression::Spread { value } => vec![value.as_ref()],
+        Expression::When { arms } | Expression::While { arms } => {
+            arms.iter().map(|arm| &arm.body).collect()
+        }
+        Expression::Pipe { from, to } => vec![from.as_ref(), to.as_ref()],
+        Expression::Block { variables, output } => variables
+            .iter()
+            .map(|variable| &variable.node.value)
+            .chain([output.as_ref()])
+            .collect(),
+        Expression::Comparator(
+            Comparator::Equal {
+                operand_a,
+                operand_b,
+            }
+            | Comparator::NotEqual {
+                operand_a,
+                operand_b,
+            }
+            | Comparator::Greater {
+                operand_a,
+                operand_b,
+            }
+            | Comparator::GreaterOrEqual {
+                operand_a,
+                operand_b,
+            }
+            | Comparator::Less {
+                operand_a,
+                operand_b,
+            }
+            | Comparator::LessOrEqual {
+                operand_a,
+                operand_b,
+            },
+        ) => vec![operand_a.as_ref(), operand_b.as_ref()],
+        Expression::ArithmeticOperator(ArithmeticOperator::Negate { operand }) => {
+            vec![operand.as_ref()]
+        }
+        Expression::ArithmeticOperator(
+            ArithmeticOperator::Add {
+                operand_a,
+                operand_b,
+            }
+            | ArithmeticOperator::Subtract {
+                operand_a,
+                operand_b,
+            }
+            | ArithmeticOperator::Multiply {
+                operand_a,
+                operand_b,
+            }
+            | ArithmeticOperator::Divide {
+                operand_a,
+                operand_b,
+            },
+        ) => vec![operand_a.as_ref(), operand_b.as_ref()],
+        Expression::Bits { size } => vec![size.as_ref()],
+        Expression::Memory { address } => vec![address.as_ref()],
+        Expression::Bytes { data } => data.iter().collect(),
+        Expression::PostfixFieldAccess { expr, .. } => vec![expr.as_ref()],
+        Expression::Literal(_)
+        | Expression::Alias(_)
+        | Expression::LinkSetter { .. }
+        | Expression::Link
+        | Expression::Skip
+        | Expression::TextLiteral { .. }
+        | Expression::FieldAccess { .. } => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn missing_arms(source: &str) -> Vec<(&'static str, Vec<String>)> {
+        check_source_exhaustiveness(source)
+            .expect("source should parse")
+            .into_iter()
+            .map(|warning| (warning.keyword, warning.missing))
+            .collect()
+    }
+
+    #[test]
+    fn bool_when_missing_false_arm() {
+        let source = r#"
+checked: False |> HOLD state {
+    toggle.event.press |> THEN { state |> Bool/not() }
+}
+toggle: LINK
+label: checked |> WHEN {
+    True => TEXT { on }
+}
+"#;
+        assert_eq!(
+            missing_arms(source),
+            vec![("WHEN", vec!["False".to_owned()])]
+        );
+    }
+
+    #[test]
+    fn three_tag_router_missing_one_arm() {
+        let source = r#"
+buttons: [active: LINK, completed: LINK]
+filter: LATEST {
+    All
+    buttons.active.event.press |> THEN { Active }
+    buttons.completed.event.press |> THEN { Completed }
+}
+title: filter |> WHILE {
+    All => TEXT { All }
+    Active => TEXT { Active }
+}
+"#;
+        assert_eq!(
+            missing_arms(source),
+            vec![("WHILE", vec!["Completed".to_owned()])]
+        );
+    }
+
+    #[test]
+    fn tags_flow_through_upstream_arms_and_bool_builtins() {
+        let source = r#"
+items: LIST {}
+mode: items |> List/is_empty() |> WHEN {
+    True => Empty
+    False => Filled
+}
+label: mode |> WHEN {
+    Empty => TEXT { empty }
+}
+"#;
+        assert_eq!(
+            missing_arms(source),
+            vec![("WHEN", vec!["Filled".to_owned()])]
+        );
+    }
+
+    #[test]
+    fn predicate_builtins_produce_bool() {
+        let source = r#"
+name: TEXT { report.txt }
+items: LIST { 1, 2 }
+a: name |> Text/ends_with(suffix: TEXT { .txt }) |> WHEN {
+    True => 1
+}
+b: name |> Text/contains(needle: TEXT { port }) |> WHEN {
+    True => 1
+}
+c: items |> List/any(item, if: item > 1) |> WHEN {
+    False => 1
+}
+d: items |> List/all(item, if: item > 0) |> WHEN {
+    True => 1
+}
+e: items |> List/contains(value: 2) |> WHEN {
+    True => 1
+}
+f: True |> Bool/and(that: False) |> WHEN {
+    True => 1
+    False => 2
+}
+"#;
+        assert_eq!(
+            missing_arms(source),
+            vec![
+                ("WHEN", vec!["False".to_owned()]),
+                ("WHEN", vec!["False".to_owned()]),
+                ("WHEN", vec!["True".to_owned()]),
+                ("WHEN", vec!["False".to_owned()]),
+                ("WHEN", vec!["False".to_owned()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn wildcard_binding_and_complete_arms_are_not_reported() {
+        let source = r#"
+filter: LATEST {
+    All
+    Active
+}
+a: filter |> WHEN {
+    All => 1
+    __ => 2
+}
+b: filter |> WHEN {
+    value => 1
+}
+c: filter |> WHEN {
+    All => 1
+    Active => 2
+}
+"#;
+        assert!(missing_arms(source).is_empty());
+    }
+
+    #[test]
+    fn unknown_sources_are_not_reported() {
+        let source = r#"
+FUNCTION describe(filter) {
+    filter |> WHEN {
+        All => 1
+    }
+}
+store: [filter: All]
+a: store.filter |> WHEN {
+    Active => 1
+}
+b: describe(filter: Active)
+"#;
+        assert!(missing_arms(source).is_empty());
+    }
+}
@@ -1709,6 +1709,19 @@ mod tests {
 
     // -- Idempotency tests --------------------------------------------------
 
+    fn comment_texts(source_code: &str) -> Vec<&str> {
+        lexer::lexer()
+            .parse(source_code)
+            .into_output()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|token| match token.node {
+                Token::Comment(text) => Some(text.trim_end()),
+                _ => None,
+            })
+            .collect()
+    }
+
     macro_rules! idempotency_test {
         ($name:ident, $file:expr) => {
             #[test]
@@ -1722,6 +1735,12 @@ mod tests {
                     "Formatting is not idempotent for {}",
                     stringify!($name)
                 );
+                assert_eq!(
+                    comment_texts(&first),
+                    comment_texts(input),
+                    "Formatting lost or reordered comments in {}",
+                    stringify!($name)
+                );
             }
         };
     }
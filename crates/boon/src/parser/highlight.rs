@@ -0,0 +1,171 @@
+//! Token classification for syntax highlighting outside the code editor.
+//!
+//! Engines render Boon snippets (e.g. `Element/code` with `language: TEXT { boon }`)
+//! with this instead of shipping their own highlighter, so every renderer colors
+//! the same source the same way.
+
+use std::ops::Range;
+
+use chumsky::prelude::Parser;
+
+use super::{Token, lexer};
+
+/// Highlighting class of a source range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HighlightKind {
+    /// `LIST`, `HOLD`, `WHEN`, ...
+    Keyword,
+    /// PascalCase tags and module names
+    Tag,
+    /// snake_case identifier directly followed by `(`
+    Function,
+    Identifier,
+    Number,
+    /// Whole `TEXT { ... }` literal
+    Text,
+    Comment,
+    /// `|>`, `=>`, comparators and arithmetic
+    Operator,
+    /// Brackets, `:`, `,`, `.`, `/`
+    Punctuation,
+}
+
+/// Classified byte range of the highlighted source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HighlightToken {
+    pub range: Range<usize>,
+    pub kind: HighlightKind,
+}
+
+/// Splits `source` into classified byte ranges, in source order.
+///
+/// Whitespace and anything the lexer could not recognize are left out; renderers
+/// show those gaps as plain text. Never fails, so it is safe on half-typed code.
+pub fn tokenize_for_highlighting(source: &str) -> Vec<HighlightToken> {
+    let Some(tokens) = lexer().parse(source).into_output() else {
+        return Vec::new();
+    };
+    let mut highlight_tokens = Vec::with_capacity(tokens.len());
+    for (index, token) in tokens.iter().enumerate() {
+        let kind = match &token.node {
+            Token::Newline => continue,
+            Token::Comment(_) => HighlightKind::Comment,
+            Token::Number(_) => HighlightKind::Number,
+            Token::TextContent(..) => HighlightKind::Text,
+            Token::PascalCaseIdentifier(_) => HighlightKind::Tag,
+            Token::SnakeCaseIdentifier(_) => {
+                let is_call = tokens
+                    .get(index + 1)
+                    .is_some_and(|next| next.node == Token::BracketRoundOpen);
+                if is_call {
+                    HighlightKind::Function
+                } else {
+                    HighlightKind::Identifier
+                }
+            }
+            Token::List
+            | Token::Map
+            | Token::Function
+            | Token::Link
+            | Token::Latest
+            | Token::Hold
+            | Token::Then
+            | Token::When
+            | Token::While
+            | Token::Skip
+            | Token::Block
+            | Token::Pass
+            | Token::Passed
+            | Token::Flush
+            | Token::Bits
+            | Token::Memory
+            | Token::Bytes => HighlightKind::Keyword,
+            Token::Pipe
+            | Token::Implies
+            | Token::Wildcard
+            | Token::Spread
+            | Token::NotEqual
+            | Token::GreaterOrEqual
+            | Token::Greater
+            | Token::LessOrEqual
+            | Token::Less
+            | Token::Equal
+            | Token::Minus
+            | Token::Plus
+            | Token::Asterisk => HighlightKind::Operator,
+            Token::BracketRoundOpen
+            | Token::BracketRoundClose
+            | Token::BracketCurlyOpen
+            | Token::BracketCurlyClose
+            | Token::BracketSquareOpen
+            | Token::BracketSquareClose
+            | Token::Colon
+            | Token::Comma
+            | Token::Dot
+            | Token::Slash => HighlightKind::Punctuation,
+        };
+        highlight_tokens.push(HighlightToken {
+            range: token.span.start..token.span.end,
+            kind,
+        });
+    }
+    highlight_tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn classified(source: &str) -> Vec<(&str, HighlightKind)> {
+        tokenize_for_highlighting(source)
+            .into_iter()
+            .map(|token| (&source[token.range], token.kind))
+            .collect()
+    }
+
+    #[test]
+    fn classifies_calls_keywords_and_literals() {
+        assert_eq!(
+            classified("count: 0 |> HOLD state { Math/sum() } -- total"),
+            vec![
+                ("count", HighlightKind::Identifier),
+                (":", HighlightKind::Punctuation),
+                ("0", HighlightKind::Number),
+                ("|>", HighlightKind::Operator),
+                ("HOLD", HighlightKind::Keyword),
+                ("state", HighlightKind::Identifier),
+                ("{", HighlightKind::Punctuation),
+                ("Math", HighlightKind::Tag),
+                ("/", HighlightKind::Punctuation),
+                ("sum", HighlightKind::Function),
+                ("(", HighlightKind::Punctuation),
+                (")", HighlightKind::Punctuation),
+                ("}", HighlightKind::Punctuation),
+                ("-- total", HighlightKind::Comment),
+            ]
+        );
+    }
+
+    #[test]
+    fn text_literal_is_one_token_across_lines() {
+        assert_eq!(
+            classified("label: TEXT { Hello {name} }\nnext: 1"),
+            vec![
+                ("label", HighlightKind::Identifier),
+                (":", HighlightKind::Punctuation),
+                ("TEXT { Hello {name} }", HighlightKind::Text),
+                ("next", HighlightKind::Identifier),
+                (":", HighlightKind::Punctuation),
+                ("1", HighlightKind::Number),
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_input_leaves_gaps_instead_of_failing() {
+        let source = "a: 1 ; b: 2";
+        let tokens = classified(source);
+        assert_eq!(tokens.first(), Some(&("a", HighlightKind::Identifier)));
+        assert_eq!(tokens.last(), Some(&("2", HighlightKind::Number)));
+    }
+}
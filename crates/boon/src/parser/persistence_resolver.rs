@@ -158,13 +158,13 @@ impl std::fmt::Display for PersistenceId {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize)]
 pub struct Persistence {
     pub id: PersistenceId,
     pub status: PersistenceStatus,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub enum PersistenceStatus {
     NewOrChanged,
     Unchanged,
@@ -202,8 +202,30 @@ pub fn resolve_persistence<'new_code, 'old_code>(
         None
     };
 
+    resolve_persistence_with_pairs(
+        new_expressions,
+        old_expressions,
+        &old_span_id_pairs.unwrap_or_default(),
+    )
+}
+
+/// [`resolve_persistence`] with the span-id pairs of the previous resolution passed in
+/// instead of loaded from local storage, for hosts that keep them in memory between
+/// builds (e.g. `boon run --watch`). `old_span_id_pairs` must come from resolving
+/// `old_expressions`.
+pub fn resolve_persistence_with_pairs<'new_code, 'old_code>(
+    mut new_expressions: Vec<Spanned<Expression<'new_code>>>,
+    old_expressions: Option<Vec<Spanned<Expression<'old_code>>>>,
+    old_span_id_pairs: &HashMap<Span, PersistenceId>,
+) -> Result<
+    (
+        Vec<Spanned<Expression<'new_code>>>,
+        HashMap<Span, PersistenceId>,
+        Vec<PersistenceId>,
+    ),
+    Vec<ResolveError<'new_code>>,
+> {
     let old_expressions = old_expressions.unwrap_or_default();
-    let old_span_id_pairs = old_span_id_pairs.unwrap_or_default();
 
     let mut new_span_id_pairs = HashMap::new();
     let mut errors = Vec::new();
@@ -212,7 +234,7 @@ pub fn resolve_persistence<'new_code, 'old_code>(
         set_persistence(
             new_expression,
             &old_expressions.iter().collect::<Vec<_>>(),
-            &old_span_id_pairs,
+            old_span_id_pairs,
             &mut new_span_id_pairs,
             &mut errors,
             &mut changed_variable_ids,
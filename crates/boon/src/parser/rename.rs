@@ -0,0 +1,1017 @@
+//! Rename refactoring behind the playground editor's "Rename symbol" (F2).
+//!
+//! Every occurrence of one binding is found across the project files through the
+//! scope resolver, so a shadowing local with the same name stays untouched.
+//! Occurrences resolved only at runtime (`PASSED.*`, `.field`, `expr.field`)
+//! are reported as possible matches the user has to confirm.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Range;
+
+use super::*;
+use lexer::Token;
+
+// ---------------------------------------------------------------------------
+// Public API
+// ---------------------------------------------------------------------------
+
+/// Whether an edit follows from static resolution or needs confirmation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenameCertainty {
+    Definite,
+    /// Same name reached through a value whose shape is only known at runtime.
+    Possible,
+}
+
+/// One identifier occurrence to rewrite.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenameEdit {
+    pub file: String,
+    /// Byte range of the identifier in the file.
+    pub range: Range<usize>,
+    pub certainty: RenameCertainty,
+}
+
+/// All edits of one rename, sorted by file and position, not yet applied.
+#[derive(Debug, Clone)]
+pub struct RenamePlan {
+    pub old_name: String,
+    pub new_name: String,
+    pub edits: Vec<RenameEdit>,
+}
+
+/// One line of the rename preview.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenamePreviewLine {
+    pub file: String,
+    /// 1-based line number.
+    pub line: usize,
+    pub before: String,
+    pub after: String,
+    pub certainty: RenameCertainty,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenameError {
+    InvalidName(String),
+    UnknownFile(String),
+    NoSymbolAtCursor,
+    Unsupported(String),
+    Conflict(String),
+    Parse { file: String, message: String },
+}
+
+impl fmt::Display for RenameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidName(name) => write!(f, "'{name}' is not a valid snake_case name"),
+            Self::UnknownFile(file) => write!(f, "File '{file}' is not part of the project"),
+            Self::NoSymbolAtCursor => write!(f, "No renameable symbol at the cursor"),
+            Self::Unsupported(reason) => write!(f, "{reason}"),
+            Self::Conflict(reason) => write!(f, "{reason}"),
+            Self::Parse { file, message } => {
+                write!(f, "Cannot rename, {file} has errors: {message}")
+            }
+        }
+    }
+}
+
+/// Plan renaming the symbol at byte `offset` in `file` to `new_name`.
+///
+/// Only `.bn` files take part. Every file has to parse and resolve, otherwise
+/// references in the broken file could be missed.
+pub fn plan_rename(
+    files: &BTreeMap<String, String>,
+    file: &str,
+    offset: usize,
+    new_name: &str,
+) -> Result<RenamePlan, RenameError> {
+    if !is_snake_case_identifier(new_name) {
+        return Err(RenameError::InvalidName(new_name.to_owned()));
+    }
+    let file_names: Vec<&str> = files
+        .keys()
+        .map(String::as_str)
+        .filter(|name| name.ends_with(".bn"))
+        .collect();
+    let cursor_file = file_names
+        .iter()
+        .position(|name| *name == file)
+        .ok_or_else(|| RenameError::UnknownFile(file.to_owned()))?;
+    let indexes = file_names
+        .iter()
+        .enumerate()
+        .map(|(file_index, name)| {
+            FileIndex::new(file_index, &files[*name]).map_err(|message| RenameError::Parse {
+                file: (*name).to_owned(),
+                message,
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let project = Project {
+        file_names,
+        indexes,
+    };
+
+    let target = project.symbol_at(cursor_file, offset)?;
+    let Some(definition) = project.definition(target) else {
+        return Err(RenameError::Unsupported(
+            "Only variables, fields, functions and their parameters can be renamed".to_owned(),
+        ));
+    };
+    // Planning with the current name lists the occurrences without changing anything
+    if definition.name != new_name
+        && project.indexes[target.file].scopes[definition.scope].contains(&new_name)
+    {
+        return Err(RenameError::Conflict(format!(
+            "'{new_name}' is already defined next to '{}'",
+            definition.name
+        )));
+    }
+
+    Ok(RenamePlan {
+        old_name: definition.name.to_owned(),
+        new_name: new_name.to_owned(),
+        edits: project.edits(target),
+    })
+}
+
+impl RenamePlan {
+    pub fn possible_count(&self) -> usize {
+        self.edits
+            .iter()
+            .filter(|edit| edit.certainty == RenameCertainty::Possible)
+            .count()
+    }
+
+    /// Apply all definite edits and the accepted possible ones.
+    ///
+    /// Returns the complete new file map so the caller can swap it in at once.
+    pub fn apply(
+        &self,
+        files: &BTreeMap<String, String>,
+        accept_possible: impl Fn(&RenameEdit) -> bool,
+    ) -> BTreeMap<String, String> {
+        let mut files = files.clone();
+        // Edits are sorted, going backwards keeps earlier ranges valid
+        for edit in self.edits.iter().rev() {
+            if edit.certainty == RenameCertainty::Possible && !accept_possible(edit) {
+                continue;
+            }
+            if let Some(source) = files.get_mut(&edit.file) {
+                source.replace_range(edit.range.clone(), &self.new_name);
+            }
+        }
+        files
+    }
+
+    /// The line around every edit, before and after applying only that edit.
+    pub fn preview(&self, files: &BTreeMap<String, String>) -> Vec<RenamePreviewLine> {
+        self.edits
+            .iter()
+            .filter_map(|edit| {
+                let source = files.get(&edit.file)?;
+                let line_start = source[..edit.range.start]
+                    .rfind('\n')
+                    .map_or(0, |index| index + 1);
+                let line_end = source[edit.range.end..]
+                    .find('\n')
+                    .map_or(source.len(), |index| edit.range.end + index);
+                Some(RenamePreviewLine {
+                    file: edit.file.clone(),
+                    line: source[..edit.range.start].matches('\n').count() + 1,
+                    before: source[line_start..line_end].to_owned(),
+                    after: format!(
+                        "{}{}{}",
+                        &source[line_start..edit.range.start],
+                        self.new_name,
+                        &source[edit.range.end..line_end]
+                    ),
+                    certainty: edit.certainty,
+                })
+            })
+            .collect()
+    }
+}
+
+pub fn is_snake_case_identifier(name: &str) -> bool {
+    let mut characters = name.chars();
+    let Some(first) = characters.next() else {
+        return false;
+    };
+    (first == '_' || first.is_ascii_lowercase())
+        && characters.all(|character| {
+            character == '_' || character.is_ascii_lowercase() || character.is_ascii_digit()
+        })
+        && name != "_"
+        && name != "__"
+}
+
+// ---------------------------------------------------------------------------
+// Project — symbol lookup across files
+// ---------------------------------------------------------------------------
+
+/// Identity of a binding: its file plus the span the scope resolver records for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(super) struct SymbolKey {
+    file: usize,
+    start: usize,
+    end: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum SymbolKind {
+    Variable,
+    Field,
+    Function,
+    Parameter,
+}
+
+enum PathResolution {
+    Symbol(SymbolKey),
+    /// Some part goes through a value whose shape is only known at runtime.
+    Unknown,
+    /// Resolves statically, to something else.
+    Unrelated,
+}
+
+struct Project<'files> {
+    file_names: Vec<&'files str>,
+    indexes: Vec<FileIndex<'files>>,
+}
+
+impl<'files> Project<'files> {
+    fn definition(&self, key: SymbolKey) -> Option<&Definition<'files>> {
+        self.indexes[key.file].definitions.get(&key)
+    }
+
+    fn symbol_at(&self, file: usize, offset: usize) -> Result<SymbolKey, RenameError> {
+        let index = &self.indexes[file];
+        let contains = |range: &Range<usize>| range.start <= offset && offset <= range.end;
+
+        if let Some(key) = index
+            .definitions
+            .iter()
+            .find_map(|(key, definition)| contains(&definition.range).then_some(*key))
+        {
+            return Ok(key);
+        }
+        for site in &index.paths {
+            if let Some(part_index) = site.parts.iter().position(|part| contains(&part.range)) {
+                return match self.resolve_path(site, part_index) {
+                    PathResolution::Symbol(key) => Ok(key),
+                    PathResolution::Unknown | PathResolution::Unrelated => {
+                        Err(RenameError::Unsupported(format!(
+                            "'{}' is only resolved at runtime, rename its definition instead",
+                            site.parts[part_index].name
+                        )))
+                    }
+                };
+            }
+        }
+        for site in &index.calls {
+            if let Some(function) = site.path.last().filter(|part| contains(&part.range)) {
+                return self.resolve_call(file, site).ok_or_else(|| {
+                    RenameError::Unsupported(format!(
+                        "'{}' is not a function defined in this project",
+                        function.name
+                    ))
+                });
+            }
+            if let Some(argument) = site.arguments.iter().find(|part| contains(&part.range)) {
+                return self
+                    .resolve_call(file, site)
+                    .and_then(|function| self.parameter(function, argument.name))
+                    .ok_or_else(|| {
+                        RenameError::Unsupported(format!(
+                            "'{}' is an argument of a built-in function",
+                            argument.name
+                        ))
+                    });
+            }
+        }
+        if let Some(field) = index
+            .dynamic_fields
+            .iter()
+            .find(|occurrence| contains(&occurrence.range))
+        {
+            return Err(RenameError::Unsupported(format!(
+                "'{}' is only resolved at runtime, rename its definition instead",
+                field.name
+            )));
+        }
+        Err(RenameError::NoSymbolAtCursor)
+    }
+
+    /// Follow `a.b.c` from the resolved base through object literal fields.
+    fn resolve_path(&self, site: &PathSite<'files>, part_index: usize) -> PathResolution {
+        let Some(mut current) = site.base else {
+            return PathResolution::Unknown;
+        };
+        for part in &site.parts[1..=part_index] {
+            let Some(definition) = self.definition(current) else {
+                return PathResolution::Unknown;
+            };
+            match definition.fields.get(part.name) {
+                Some(field) => current = *field,
+                None if definition.has_known_shape => return PathResolution::Unrelated,
+                None => return PathResolution::Unknown,
+            }
+        }
+        PathResolution::Symbol(current)
+    }
+
+    /// `function()` resolves in the same file, `Module/function()` in `Module.bn`.
+    fn resolve_call(&self, file: usize, site: &CallSite<'files>) -> Option<SymbolKey> {
+        match site.path.as_slice() {
+            [function] => self.indexes[file].functions.get(function.name).copied(),
+            [module, function] => self
+                .file_names
+                .iter()
+                .position(|name| module_name(name) == module.name)
+                .and_then(|module_file| {
+                    self.indexes[module_file]
+                        .functions
+                        .get(function.name)
+                        .copied()
+                }),
+            _ => None,
+        }
+    }
+
+    fn parameter(&self, function: SymbolKey, name: &str) -> Option<SymbolKey> {
+        self.definition(function)?
+            .parameters
+            .iter()
+            .copied()
+            .find(|parameter| {
+                self.definition(*parameter)
+                    .is_some_and(|definition| definition.name == name)
+            })
+    }
+
+    fn edits(&self, target: SymbolKey) -> Vec<RenameEdit> {
+        let Some(definition) = self.definition(target) else {
+            return Vec::new();
+        };
+        let name = definition.name;
+        let edit = |file: usize, range: &Range<usize>, certainty| RenameEdit {
+            file: self.file_names[file].to_owned(),
+            range: range.clone(),
+            certainty,
+        };
+
+        let mut edits = vec![edit(
+            target.file,
+            &definition.range,
+            RenameCertainty::Definite,
+        )];
+        for (file, index) in self.indexes.iter().enumerate() {
+            for site in &index.paths {
+                for (part_index, part) in site.parts.iter().enumerate() {
+                    if part.name != name {
+                        continue;
+                    }
+                    let certainty = match self.resolve_path(site, part_index) {
+                        PathResolution::Symbol(key) if key == target => RenameCertainty::Definite,
+                        PathResolution::Unknown
+                            if part_index > 0 && definition.kind == SymbolKind::Field =>
+                        {
+                            RenameCertainty::Possible
+                        }
+                        _ => continue,
+                    };
+                    edits.push(edit(file, &part.range, certainty));
+                }
+            }
+            for site in &index.calls {
+                match definition.kind {
+                    SymbolKind::Function => {
+                        if let Some(function) = site.path.last().filter(|function| {
+                            function.name == name && self.resolve_call(file, site) == Some(target)
+                        }) {
+                            edits.push(edit(file, &function.range, RenameCertainty::Definite));
+                        }
+                    }
+                    SymbolKind::Parameter => {
+                        for argument in &site.arguments {
+                            if argument.name == name
+                                && self
+                                    .resolve_call(file, site)
+                                    .and_then(|function| self.parameter(function, name))
+                                    == Some(target)
+                            {
+                                edits.push(edit(file, &argument.range, RenameCertainty::Definite));
+                            }
+                        }
+                    }
+                    SymbolKind::Variable | SymbolKind::Field => {}
+                }
+            }
+            if definition.kind == SymbolKind::Field {
+                for occurrence in &index.dynamic_fields {
+                    if occurrence.name == name {
+                        edits.push(edit(file, &occurrence.range, RenameCertainty::Possible));
+                    }
+                }
+            }
+        }
+        edits.sort_by(|a, b| a.file.cmp(&b.file).then(a.range.start.cmp(&b.range.start)));
+        edits.dedup_by(|a, b| a.file == b.file && a.range == b.range);
+        edits
+    }
+}
+
+/// `Theme/material()` lives in `Theme.bn` or `Theme/Theme.bn`.
+fn module_name(file: &str) -> &str {
+    let file_name = file.rsplit('/').next().unwrap_or(file);
+    file_name.strip_suffix(".bn").unwrap_or(file_name)
+}
+
+// ---------------------------------------------------------------------------
+// FileIndex — definitions and occurrences of one file
+// ---------------------------------------------------------------------------
+
+pub(super) struct Definition<'source> {
+    pub(super) name: &'source str,
+    pub(super) range: Range<usize>,
+    pub(super) kind: SymbolKind,
+    /// Index into `FileIndex::scopes`: the names bound next to this one
+    pub(super) scope: usize,
+    /// Fields of the object literal bound to this name
+    fields: BTreeMap<&'source str, SymbolKey>,
+    /// False unless the value is an object literal without spreads
+    has_known_shape: bool,
+    parameters: Vec<SymbolKey>,
+}
+
+struct Occurrence<'source> {
+    name: &'source str,
+    range: Range<usize>,
+}
+
+/// A dotted path whose first part the scope resolver resolved: aliases,
+/// `{a.b}` text interpolations and value comparison patterns.
+pub(super) struct PathSite<'source> {
+    pub(super) base: Option<SymbolKey>,
+    parts: Vec<Occurrence<'source>>,
+}
+
+struct CallSite<'source> {
+    path: Vec<Occurrence<'source>>,
+    arguments: Vec<Occurrence<'source>>,
+}
+
+pub(super) struct FileIndex<'source> {
+    file: usize,
+    source: &'source str,
+    pub(super) definitions: BTreeMap<SymbolKey, Definition<'source>>,
+    scopes: Vec<Vec<&'source str>>,
+    /// Index into `scopes` of the top-level definitions
+    pub(super) root_scope: usize,
+    /// Last top-level expression when it is a variable; its value is the program result
+    pub(super) result: Option<SymbolKey>,
+    functions: BTreeMap<&'source str, SymbolKey>,
+    pub(super) paths: Vec<PathSite<'source>>,
+    calls: Vec<CallSite<'source>>,
+    /// Field names resolved only at runtime: `PASSED.*`, `.field`, `expr.field`
+    dynamic_fields: Vec<Occurrence<'source>>,
+}
+
+impl<'source> FileIndex<'source> {
+    pub(super) fn new(file: usize, source: &'source str) -> Result<Self, String> {
+        let (tokens, lex_errors) = lexer().parse(source).into_output_errors();
+        if let Some(error) = lex_errors.into_iter().next() {
+            return Err(format!("lex error: {error}"));
+        }
+        let mut tokens = tokens.ok_or_else(|| "lex error: no tokens produced".to_owned())?;
+        tokens.retain(|token| !matches!(token.node, Token::Comment(_)));
+
+        reset_expression_depth();
+        let (ast, parse_errors) = parser()
+            .parse(tokens.map(
+                span_at(source.len()),
+                |Spanned {
+                     node,
+                     span,
+                     persistence: _,
+                 }| (node, span),
+            ))
+            .into_output_errors();
+        if let Some(error) = parse_errors.into_iter().next() {
+            return Err(format!("parse error: {error}"));
+        }
+        let ast = ast.ok_or_else(|| "parse error: no AST produced".to_owned())?;
+        let ast = resolve_references(ast).map_err(|errors| {
+            errors.into_iter().next().map_or_else(
+                || "reference error".to_owned(),
+                |error| format!("reference error: {error}"),
+            )
+        })?;
+
+        let mut index = Self {
+            file,
+            source,
+            definitions: BTreeMap::new(),
+            scopes: Vec::new(),
+            root_scope: 0,
+            result: None,
+            functions: BTreeMap::new(),
+            paths: Vec::new(),
+            calls: Vec::new(),
+            dynamic_fields: Vec::new(),
+        };
+        index.root_scope = index.new_scope();
+        let root_scope = index.root_scope;
+        if let Some(last) = ast.last()
+            && matches!(last.node, Expression::Variable(_))
+        {
+            index.result = Some(index.key(last.span));
+        }
+        for expression in &ast {
+            match &expression.node {
+                Expression::Variable(variable) => {
+                    index.visit_variable(
+                        expression.span,
+                        variable,
+                        SymbolKind::Variable,
+                        root_scope,
+                    );
+                }
+                Expression::Function {
+                    name,
+                    parameters,
+                    body,
+                } => index.visit_function(expression.span, name, parameters, body, root_scope),
+                _ => index.visit(expression),
+            }
+        }
+        Ok(index)
+    }
+
+    /// Map a slice of the parsed source back to its byte range.
+    fn occurrence(&self, slice: &str) -> Option<Occurrence<'source>> {
+        let start = slice
+            .as_ptr()
+            .addr()
+            .checked_sub(self.source.as_ptr().addr())?;
+        let end = start + slice.len();
+        let source = self.source;
+        let name = source.get(start..end)?;
+        Some(Occurrence {
+            name,
+            range: start..end,
+        })
+    }
+
+    fn key(&self, span: Span) -> SymbolKey {
+        SymbolKey {
+            file: self.file,
+            start: span.start,
+            end: span.end,
+        }
+    }
+
+    fn new_scope(&mut self) -> usize {
+        self.scopes.push(Vec::new());
+        self.scopes.len() - 1
+    }
+
+    fn define(
+        &mut self,
+        span: Span,
+        name: &str,
+        kind: SymbolKind,
+        scope: usize,
+    ) -> Option<SymbolKey> {
+        let Occurrence { name, range } = self.occurrence(name)?;
+        let key = self.key(span);
+        self.scopes[scope].push(name);
+        self.definitions.insert(
+            key,
+            Definition {
+                name,
+                range,
+                kind,
+                scope,
+                fields: BTreeMap::new(),
+                has_known_shape: false,
+                parameters: Vec::new(),
+            },
+        );
+        Some(key)
+    }
+
+    fn visit_variable(
+        &mut self,
+        span: Span,
+        variable: &Variable<'_>,
+        kind: SymbolKind,
+        scope: usize,
+    ) -> Option<SymbolKey> {
+        let shape = match &variable.value.node {
+            Expression::Object(object) | Expression::TaggedObject { object, .. } => {
+                Some(self.visit_object(object))
+            }
+            _ => {
+                self.visit(&variable.value);
+                None
+            }
+        };
+        let key = self.define(span, variable.name, kind, scope)?;
+        if let (Some((fields, has_known_shape)), Some(definition)) =
+            (shape, self.definitions.get_mut(&key))
+        {
+            definition.fields = fields;
+            definition.has_known_shape = has_known_shape;
+        }
+        Some(key)
+    }
+
+    /// Returns the object's fields and whether that is all of them (no spreads).
+    fn visit_object(&mut self, object: &Object<'_>) -> (BTreeMap<&'source str, SymbolKey>, bool) {
+        let scope = self.new_scope();
+        let mut fields = BTreeMap::new();
+        let mut has_known_shape = true;
+        for variable in &object.variables {
+            // Spread entries have an empty name
+            if variable.node.name.is_empty() {
+                has_known_shape = false;
+                self.visit(&variable.node.value);
+                continue;
+            }
+            let key = self.visit_variable(variable.span, &variable.node, SymbolKind::Field, scope);
+            if let Some(definition) = key.and_then(|key| self.definitions.get(&key)) {
+                fields.insert(definition.name, self.key(variable.span));
+            }
+        }
+        (fields, has_known_shape)
+    }
+
+    fn visit_function(
+        &mut self,
+        span: Span,
+        name: &str,
+        parameters: &[Spanned<&str>],
+        body: &Spanned<Expression<'_>>,
+        scope: usize,
+    ) {
+        let parameter_scope = self.new_scope();
+        let parameters: Vec<SymbolKey> = parameters
+            .iter()
+            .filter_map(|parameter| {
+                self.define(
+                    parameter.span,
+                    parameter.node,
+                    SymbolKind::Parameter,
+                    parameter_scope,
+                )
+            })
+            .collect();
+        self.visit(body);
+        let Some(key) = self.define(span, name, SymbolKind::Function, scope) else {
+            return;
+        };
+        if let Some(definition) = self.definitions.get_mut(&key) {
+            definition.parameters = parameters;
+            self.functions.insert(definition.name, key);
+        }
+    }
+
+    fn visit(&mut self, expression: &Spanned<Expression<'_>>) {
+        match &expression.node {
+            Expression::Variable(variable) => {
+                let scope = self.new_scope();
+                self.visit_variable(expression.span, variable, SymbolKind::Variable, scope);
+            }
+            Expression::Object(object) | Expression::TaggedObject { object, .. } => {
+                self.visit_object(object);
+            }
+            Expression::Function {
+                name,
+                parameters,
+                body,
+            } => {
+                let scope = self.new_scope();
+                self.visit_function(expression.span, name, parameters, body, scope);
+            }
+            Expression::FunctionCall { path, arguments } => {
+                let path: Option<Vec<_>> = path.iter().map(|part| self.occurrence(part)).collect();
+                let argument_names: Option<Vec<_>> = arguments
+                    .iter()
+                    .filter(|argument| !argument.node.name.is_empty())
+                    .map(|argument| self.occurrence(argument.node.name))
+                    .collect();
+                if let (Some(path), Some(arguments)) = (path, argument_names) {
+                    self.calls.push(CallSite { path, arguments });
+                }
+                for argument in arguments {
+                    if let Some(value) = &argument.node.value {
+                        self.visit(value);
+                    }
+                }
+            }
+            Expression::Alias(alias) => self.visit_alias(alias),
+            Expression::LinkSetter { alias } => self.visit_alias(&alias.node),
+            Expression::TextLiteral { parts, .. } => {
+                for part in parts {
+                    if let TextPart::Interpolation {
+                        var,
+                        referenced_span,
+                    } = part
+                    {
+                        let path: Vec<&str> = var.split('.').collect();
+                        if path[0] == "PASSED" {
+                            self.push_dynamic_fields(&path[1..]);
+                        } else {
+                            let base = referenced_span.map(|span| self.key(span));
+                            self.push_path(base, &path);
+                        }
+                    }
+                }
+            }
+            Expression::When { arms } | Expression::While { arms } => {
+                for arm in arms {
+                    self.visit_pattern(&arm.pattern);
+                    self.visit(&arm.body);
+                }
+            }
+            Expression::Hold { state_param, body } => {
+                let scope = self.new_scope();
+                self.define(expression.span, state_param, SymbolKind::Variable, scope);
+                self.visit(body);
+            }
+            Expression::Block { variables, output } => {
+                let scope = self.new_scope();
+                for variable in variables {
+                    self.visit_variable(variable.span, &variable.node, SymbolKind::Variable, scope);
+                }
+                self.visit(output);
+            }
+            Expression::FieldAccess { path } => self.push_dynamic_fields(path),
+            Expression::PostfixFieldAccess { expr, field } => {
+                self.visit(expr);
+                self.push_dynamic_fields(&[*field]);
+            }
+            Expression::Map { entries } => {
+                for entry in entries {
+                    if let MapEntryKey::Alias(alias) = &entry.key.node {
+                        self.visit_alias(alias);
+                    }
+                    self.visit(&entry.value);
+                }
+            }
+            Expression::List { items }
+            | Expression::Latest { inputs: items }
+            | Expression::Bytes { data: items } => {
+                for item in items {
+                    self.visit(item);
+                }
+            }
+            Expression::Then { body }
+            | Expression::Flush { value: body }
+            | Expression::Spread { value: body }
+            | Expression::Bits { size: body }
+            | Expression::Memory { address: body } => self.visit(body),
+            Expression::Pipe { from, to } => {
+                self.visit(from);
+                self.visit(to);
+            }
+            Expression::Comparator(
+                Comparator::Equal {
+                    operand_a,
+                    operand_b,
+                }
+                | Comparator::NotEqual {
+                    operand_a,
+                    operand_b,
+                }
+                | Comparator::Greater {
+                    operand_a,
+                    operand_b,
+                }
+                | Comparator::GreaterOrEqual {
+                    operand_a,
+                    operand_b,
+                }
+                | Comparator::Less {
+                    operand_a,
+                    operand_b,
+                }
+                | Comparator::LessOrEqual {
+                    operand_a,
+                    operand_b,
+                },
+            )
+            | Expression::ArithmeticOperator(
+                ArithmeticOperator::Add {
+                    operand_a,
+                    operand_b,
+                }
+                | ArithmeticOperator::Subtract {
+                    operand_a,
+                    operand_b,
+                }
+                | ArithmeticOperator::Multiply {
+                    operand_a,
+                    operand_b,
+                }
+                | ArithmeticOperator::Divide {
+                    operand_a,
+                    operand_b,
+                },
+            ) => {
+                self.visit(operand_a);
+                self.visit(operand_b);
+            }
+            Expression::ArithmeticOperator(ArithmeticOperator::Negate { operand }) => {
+                self.visit(operand)
+            }
+            Expression::Literal(_) | Expression::Link | Expression::Skip => {}
+        }
+    }
+
+    fn visit_alias(&mut self, alias: &Alias<'_>) {
+        match alias {
+            Alias::WithoutPassed {
+                parts,
+                referenceables,
+            } => {
+                let base = referenceables
+                    .as_ref()
+                    .and_then(|referenceables| referenceables.referenced)
+                    .map(|referenced| self.key(referenced.span));
+                self.push_path(base, parts);
+            }
+            Alias::WithPassed { extra_parts } => self.push_dynamic_fields(extra_parts),
+        }
+    }
+
+    fn visit_pattern(&mut self, pattern: &Pattern<'_>) {
+        match pattern {
+            Pattern::ValueComparison {
+                path,
+                referenced_span,
+            } => {
+                let base = referenced_span.map(|span| self.key(span));
+                self.push_path(base, path);
+            }
+            Pattern::List { items } => {
+                for item in items {
+                    self.visit_pattern(item);
+                }
+            }
+            Pattern::Object { variables } | Pattern::TaggedObject { variables, .. } => {
+                for variable in variables {
+                    if let Some(value) = &variable.value {
+                        self.visit_pattern(value);
+                    }
+                }
+            }
+            Pattern::Map { entries } => {
+                for entry in entries {
+                    self.visit_pattern(&entry.key);
+                    if let Some(value) = &entry.value {
+                        self.visit_pattern(value);
+                    }
+                }
+            }
+            // Pattern aliases bind new names; they never refer to the renamed binding
+            Pattern::Alias { .. } | Pattern::Literal(_) | Pattern::WildCard => {}
+        }
+    }
+
+    fn push_path(&mut self, base: Option<SymbolKey>, parts: &[&str]) {
+        let parts: Option<Vec<_>> = parts.iter().map(|part| self.occurrence(part)).collect();
+        if let Some(parts) = parts.filter(|parts| !parts.is_empty()) {
+            self.paths.push(PathSite { base, parts });
+        }
+    }
+
+    fn push_dynamic_fields(&mut self, fields: &[&str]) {
+        for field in fields {
+            if let Some(occurrence) = self.occurrence(field) {
+                self.dynamic_fields.push(occurrence);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(files: &[(&str, &str)]) -> BTreeMap<String, String> {
+        files
+            .iter()
+            .map(|(name, source)| ((*name).to_owned(), (*source).to_owned()))
+            .collect()
+    }
+
+    fn rename_at(
+        files: &BTreeMap<String, String>,
+        file: &str,
+        marker: &str,
+        new_name: &str,
+    ) -> Result<RenamePlan, RenameError> {
+        let offset = files[file]
+            .find(marker)
+            .expect("marker should be in the file");
+        plan_rename(files, file, offset, new_name)
+    }
+
+    #[test]
+    fn rename_keeps_shadowed_locals() {
+        let files = project(&[(
+            "main.bn",
+            "todos: LIST { 1, 2 }\n\
+             count: todos |> List/count()\n\
+             FUNCTION first_of(todos) {\n    todos |> List/first()\n}\n\
+             local: BLOCK {\n    todos: 5\n    todos + 1\n}\n",
+        )]);
+        let plan = rename_at(&files, "main.bn", "todos: LIST", "items").unwrap();
+        let renamed = plan.apply(&files, |_| false);
+        assert_eq!(
+            renamed["main.bn"],
+            "items: LIST { 1, 2 }\n\
+             count: items |> List/count()\n\
+             FUNCTION first_of(todos) {\n    todos |> List/first()\n}\n\
+             local: BLOCK {\n    todos: 5\n    todos + 1\n}\n"
+        );
+    }
+
+    #[test]
+    fn rename_store_field_through_paths_and_interpolation() {
+        let files = project(&[(
+            "main.bn",
+            "store: [\n    todos: LIST { 1 }\n    count: todos |> List/count()\n]\n\
+             size: store.todos |> List/count()\n\
+             label: TEXT { {store.todos} }\n",
+        )]);
+        let plan = rename_at(&files, "main.bn", "todos: LIST", "items").unwrap();
+        assert_eq!(plan.old_name, "todos");
+        assert_eq!(plan.possible_count(), 0);
+        assert_eq!(
+            plan.apply(&files, |_| false)["main.bn"],
+            "store: [\n    items: LIST { 1 }\n    count: items |> List/count()\n]\n\
+             size: store.items |> List/count()\n\
+             label: TEXT { {store.items} }\n"
+        );
+    }
+
+    #[test]
+    fn rename_from_reference_and_across_modules() {
+        let files = project(&[
+            ("RUN.bn", "value: Theme/accent(level: 1)\n"),
+            (
+                "Theme.bn",
+                "FUNCTION accent(level) {\n    level + 1\n}\nother: accent(level: 2)\n",
+            ),
+        ]);
+        let plan = rename_at(&files, "RUN.bn", "accent", "highlight").unwrap();
+        let renamed = plan.apply(&files, |_| false);
+        assert_eq!(renamed["RUN.bn"], "value: Theme/highlight(level: 1)\n");
+        assert_eq!(
+            renamed["Theme.bn"],
+            "FUNCTION highlight(level) {\n    level + 1\n}\nother: highlight(level: 2)\n"
+        );
+
+        let plan = rename_at(&files, "Theme.bn", "level) {", "depth").unwrap();
+        let renamed = plan.apply(&files, |_| false);
+        assert_eq!(renamed["RUN.bn"], "value: Theme/accent(depth: 1)\n");
+        assert_eq!(
+            renamed["Theme.bn"],
+            "FUNCTION accent(depth) {\n    depth + 1\n}\nother: accent(depth: 2)\n"
+        );
+    }
+
+    #[test]
+    fn passed_fields_are_possible_matches() {
+        let files = project(&[(
+            "main.bn",
+            "store: [\n    todos: LIST { 1 }\n]\n\
+             FUNCTION view() {\n    PASSED.store.todos |> List/count()\n}\n",
+        )]);
+        let plan = rename_at(&files, "main.bn", "todos: LIST", "items").unwrap();
+        assert_eq!(plan.possible_count(), 1);
+        let preview = plan.preview(&files);
+        assert_eq!(preview[1].line, 5);
+        assert_eq!(preview[1].after, "    PASSED.store.items |> List/count()");
+        assert!(!plan.apply(&files, |_| false)["main.bn"].contains("PASSED.store.items"));
+        assert!(plan.apply(&files, |_| true)["main.bn"].contains("PASSED.store.items"));
+    }
+
+    #[test]
+    fn rename_rejects_conflicts_and_invalid_names() {
+        let files = project(&[("main.bn", "todos: 1\nitems: todos + 1\n")]);
+        assert!(matches!(
+            rename_at(&files, "main.bn", "todos: 1", "items"),
+            Err(RenameError::Conflict(_))
+        ));
+        assert_eq!(
+            rename_at(&files, "main.bn", "todos: 1", "Items").unwrap_err(),
+            RenameError::InvalidName("Items".to_owned())
+        );
+    }
+}
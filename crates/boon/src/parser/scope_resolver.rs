@@ -2,19 +2,21 @@ use super::{
     Alias, ArithmeticOperator, Comparator, Expression, ParseError, Pattern, Span, Spanned,
     TextPart, Token,
 };
+use serde::Serialize;
 use std::collections::{BTreeMap, HashSet};
 
 // @TODO Immutables or different tree traversal algorithm?
 pub type ReachableReferenceables<'code> = BTreeMap<&'code str, Vec<Referenceable<'code>>>;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Referenceables<'code> {
     pub referenced: Option<Referenceable<'code>>,
 }
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize)]
 pub struct Referenceable<'code> {
     pub name: &'code str,
+    #[serde(serialize_with = "super::serialize_span")]
     pub span: Span,
     pub level: usize,
 }
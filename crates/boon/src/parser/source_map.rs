@@ -0,0 +1,154 @@
+//! Line maps from generated Boon files back to the templates that produced them.
+//!
+//! A BUILD.bn script that writes a module with `File/write_with_map` stores a
+//! [`SourceMap`] next to it, under [`source_map_path`]. Module loaders keep the map
+//! with the module, so an error in generated text the author never wrote can point at
+//! the template line in BUILD.bn instead.
+//!
+//! The stored form is plain text, one `generated_line file:line` entry per line, so
+//! maps stay readable next to the generated file.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Path of the map stored for the generated file at `generated_path`.
+pub fn source_map_path(generated_path: &str) -> String {
+    format!("{generated_path}.map")
+}
+
+/// Template location a generated line came from. Lines are 1-based.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLocation {
+    pub file: String,
+    pub line: usize,
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.file, self.line)
+    }
+}
+
+/// Generated line (1-based) -> template location.
+///
+/// Maps may be sparse: a line without its own entry belongs to the closest mapped
+/// line above it, so one entry covers a whole interpolated block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceMap {
+    lines: BTreeMap<usize, SourceLocation>,
+}
+
+impl SourceMap {
+    pub fn insert(&mut self, generated_line: usize, location: SourceLocation) {
+        self.lines.insert(generated_line, location);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Template location of `generated_line`, if any mapped line covers it.
+    pub fn original_location(&self, generated_line: usize) -> Option<&SourceLocation> {
+        self.lines
+            .range(..=generated_line)
+            .next_back()
+            .map(|(_, location)| location)
+    }
+
+    /// Template location of the byte `offset` in `generated_source`.
+    pub fn original_location_at(
+        &self,
+        generated_source: &str,
+        offset: usize,
+    ) -> Option<&SourceLocation> {
+        let offset = offset.min(generated_source.len());
+        let line = generated_source.as_bytes()[..offset]
+            .iter()
+            .filter(|byte| **byte == b'\n')
+            .count()
+            + 1;
+        self.original_location(line)
+    }
+
+    /// Note appended to diagnostics at `offset` in the generated file.
+    pub fn generated_from_note(&self, generated_source: &str, offset: usize) -> Option<String> {
+        self.original_location_at(generated_source, offset)
+            .map(|location| format!("generated from {location}"))
+    }
+
+    /// Reads the stored form written by [`SourceMap::to_text`].
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut source_map = Self::default();
+        for (index, entry) in text.lines().enumerate() {
+            let entry = entry.trim();
+            if entry.is_empty() {
+                continue;
+            }
+            let invalid = || format!("line {}: expected `generated_line file:line`", index + 1);
+            let (generated_line, location) = entry.split_once(' ').ok_or_else(invalid)?;
+            let (file, line) = location.trim().rsplit_once(':').ok_or_else(invalid)?;
+            let generated_line = generated_line.parse().map_err(|_| invalid())?;
+            let line = line.parse().map_err(|_| invalid())?;
+            source_map.insert(
+                generated_line,
+                SourceLocation {
+                    file: file.to_string(),
+                    line,
+                },
+            );
+        }
+        Ok(source_map)
+    }
+
+    pub fn to_text(&self) -> String {
+        self.lines
+            .iter()
+            .map(|(generated_line, location)| format!("{generated_line} {location}\n"))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_location(line: usize) -> SourceLocation {
+        SourceLocation {
+            file: "BUILD.bn".to_string(),
+            line,
+        }
+    }
+
+    #[test]
+    fn text_form_round_trips() {
+        let mut source_map = SourceMap::default();
+        source_map.insert(1, build_location(12));
+        source_map.insert(4, build_location(15));
+        let text = source_map.to_text();
+        assert_eq!(text, "1 BUILD.bn:12\n4 BUILD.bn:15\n");
+        assert_eq!(SourceMap::parse(&text), Ok(source_map));
+        assert!(SourceMap::parse("1 BUILD.bn").is_err());
+    }
+
+    #[test]
+    fn unmapped_lines_belong_to_the_closest_mapped_line_above() {
+        let source_map = SourceMap::parse("2 BUILD.bn:12\n4 BUILD.bn:15").unwrap();
+        assert_eq!(source_map.original_location(1), None);
+        assert_eq!(source_map.original_location(3), Some(&build_location(12)));
+        assert_eq!(source_map.original_location(9), Some(&build_location(15)));
+    }
+
+    #[test]
+    fn offsets_translate_to_template_lines() {
+        let generated = "answer: 42\nbroken: [answer";
+        let source_map = SourceMap::parse("1 BUILD.bn:7\n2 BUILD.bn:8").unwrap();
+        assert_eq!(
+            source_map.generated_from_note(generated, generated.len()),
+            Some("generated from BUILD.bn:8".to_string())
+        );
+        assert_eq!(
+            source_map.generated_from_note(generated, 0),
+            Some("generated from BUILD.bn:7".to_string())
+        );
+    }
+}
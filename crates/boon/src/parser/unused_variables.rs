@@ -0,0 +1,117 @@
+//! Variables and function parameters that are defined but never read.
+//!
+//! Builds on the symbol index of [`super::rename`]: a definition is used when some
+//! alias, text interpolation or comparison pattern resolves to it. Object fields
+//! are the object's value, so they are never reported; neither are top-level
+//! functions, which other modules may call. Top-level variables are reported
+//! except for the entry points (`document`, `scene`) and a variable that is the
+//! last expression, whose value is the program result.
+
+use std::collections::BTreeSet;
+use std::ops::Range;
+
+use super::rename::{FileIndex, SymbolKind};
+
+/// Top-level variables the engines read by name.
+const ENTRY_POINTS: &[&str] = &["document", "scene"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnusedVariable {
+    pub name: String,
+    /// Byte range of the name at its definition
+    pub range: Range<usize>,
+    pub is_parameter: bool,
+}
+
+impl UnusedVariable {
+    pub fn message(&self) -> String {
+        if self.is_parameter {
+            format!("Parameter `{}` is never used", self.name)
+        } else {
+            format!("Variable `{}` is defined but never used", self.name)
+        }
+    }
+}
+
+/// Parses `source` and lists its unused variables and parameters in source order;
+/// errors are the first lex, parse or reference error.
+pub fn unused_variables(source: &str) -> Result<Vec<UnusedVariable>, String> {
+    let index = FileIndex::new(0, source)?;
+    let used = index
+        .paths
+        .iter()
+        .filter_map(|site| site.base)
+        .collect::<BTreeSet<_>>();
+
+    let mut unused = index
+        .definitions
+        .iter()
+        .filter(|(key, definition)| {
+            let is_exempt_top_level = definition.scope == index.root_scope
+                && (index.result == Some(**key) || ENTRY_POINTS.contains(&definition.name));
+            matches!(
+                definition.kind,
+                SymbolKind::Variable | SymbolKind::Parameter
+            ) && !is_exempt_top_level
+                && !used.contains(key)
+        })
+        .map(|(_, definition)| UnusedVariable {
+            name: definition.name.to_owned(),
+            range: definition.range.clone(),
+            is_parameter: definition.kind == SymbolKind::Parameter,
+        })
+        .collect::<Vec<_>>();
+    unused.sort_by_key(|variable| variable.range.start);
+    Ok(unused)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unused_names(source: &str) -> Vec<(String, bool)> {
+        unused_variables(source)
+            .expect("source should resolve")
+            .into_iter()
+            .map(|variable| (variable.name, variable.is_parameter))
+            .collect()
+    }
+
+    #[test]
+    fn reports_unused_block_variables_and_parameters() {
+        let source = r#"
+FUNCTION label(value, unit) {
+    BLOCK {
+        doubled: value * 2
+        tripled: value * 3
+        TEXT { {doubled} }
+    }
+}
+document: Document/new(root: label(value: 1, unit: Pixels))
+"#;
+        assert_eq!(
+            unused_names(source),
+            vec![("unit".to_owned(), true), ("tripled".to_owned(), false)]
+        );
+        let tripled = unused_variables(source).unwrap()[1].range.clone();
+        assert_eq!(&source[tripled], "tripled");
+    }
+
+    #[test]
+    fn top_level_result_entry_points_and_fields_are_used() {
+        let source = r#"
+unused: 1
+counter: 0 |> HOLD state { state + 1 }
+store: [count: counter, label: Active]
+document: Document/new(root: store.count)
+result: store
+"#;
+        assert_eq!(unused_names(source), vec![("unused".to_owned(), false)]);
+    }
+
+    #[test]
+    fn unknown_references_are_errors() {
+        let error = unused_variables("counter: 0\nlabel: countr + 1\n").unwrap_err();
+        assert!(error.contains("countr"), "{error}");
+    }
+}
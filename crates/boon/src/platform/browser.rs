@@ -1,2 +1,7 @@
+pub mod clipboard;
+pub mod code_block;
 pub mod common;
 pub mod kernel;
+pub mod repro;
+pub mod saved_state;
+pub mod stdlib;
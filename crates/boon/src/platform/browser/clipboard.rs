@@ -0,0 +1,27 @@
+//! Clipboard capability shared by the engine bridges.
+
+use zoon::{JsCast, js_sys, web_sys};
+
+/// Copies `text` to the system clipboard without waiting for the result.
+///
+/// Returns `false` when the Clipboard API is unavailable (e.g. an insecure
+/// context), so callers can fall back or report it.
+pub fn write_text(text: &str) -> bool {
+    let Some(window) = web_sys::window() else {
+        return false;
+    };
+    let navigator = window.navigator();
+    let Some(clipboard) = js_sys::Reflect::get(&navigator, &"clipboard".into())
+        .ok()
+        .filter(|clipboard| !clipboard.is_undefined())
+    else {
+        return false;
+    };
+    let Some(write_text) = js_sys::Reflect::get(&clipboard, &"writeText".into())
+        .ok()
+        .and_then(|function| function.dyn_into::<js_sys::Function>().ok())
+    else {
+        return false;
+    };
+    write_text.call1(&clipboard, &text.into()).is_ok()
+}
@@ -0,0 +1,168 @@
+//! `Element/code` rendering shared by the engine bridges.
+//!
+//! Engines only extract `language` and `content` from their value trees; the
+//! preformatted block, Boon highlighting and the copy button live here so every
+//! engine renders snippets identically.
+
+use zoon::*;
+
+use super::clipboard;
+use crate::parser::highlight::{HighlightKind, tokenize_for_highlighting};
+
+/// How long the copy button reads "Copied" before it goes back to "Copy".
+const COPIED_LABEL_MS: u32 = 1500;
+
+/// Whether `language` (a `TEXT { boon }` or `Boon` tag value) asks for Boon highlighting.
+pub fn is_boon_language(language: &str) -> bool {
+    language.eq_ignore_ascii_case("boon")
+}
+
+fn highlight_color(kind: HighlightKind) -> &'static str {
+    match kind {
+        HighlightKind::Keyword => "#c792ea",
+        HighlightKind::Tag => "#ffcb6b",
+        HighlightKind::Function => "#82aaff",
+        HighlightKind::Identifier => "#eeffff",
+        HighlightKind::Number => "#f78c6c",
+        HighlightKind::Text => "#c3e88d",
+        HighlightKind::Comment => "#697098",
+        HighlightKind::Operator => "#89ddff",
+        HighlightKind::Punctuation => "#a6accd",
+    }
+}
+
+/// Source split into plain and colored runs, ready to become `<pre>` children.
+fn highlighted_runs(language: &str, content: &str) -> Vec<(String, Option<&'static str>)> {
+    if !is_boon_language(language) {
+        return vec![(content.to_owned(), None)];
+    }
+    let mut runs = Vec::new();
+    let mut position = 0;
+    for token in tokenize_for_highlighting(content) {
+        if token.range.start > position {
+            runs.push((content[position..token.range.start].to_owned(), None));
+        }
+        runs.push((
+            content[token.range.clone()].to_owned(),
+            Some(highlight_color(token.kind)),
+        ));
+        position = token.range.end;
+    }
+    if position < content.len() {
+        runs.push((content[position..].to_owned(), None));
+    }
+    runs
+}
+
+fn code_pre(language: &str, content: &str) -> RawHtmlEl<web_sys::HtmlElement> {
+    RawHtmlEl::new("pre")
+        .style("margin", "0")
+        .style("padding", "12px 16px")
+        .style("overflow-x", "auto")
+        // Global basic.css sets pre-wrap; long lines must scroll instead of wrapping
+        .style("white-space", "pre")
+        .style("font-family", "'JetBrains Mono', monospace")
+        .children(highlighted_runs(language, content).into_iter().map(
+            |(text, color)| match color {
+                Some(color) => RawHtmlEl::new("span").style("color", color).child(text),
+                None => RawHtmlEl::new("span").child(text),
+            },
+        ))
+}
+
+/// Preformatted code block that re-highlights whenever `language` or `content` changes.
+pub fn code_block(
+    language: impl Signal<Item = String> + Unpin + 'static,
+    content: impl Signal<Item = String> + Unpin + 'static,
+) -> impl Element {
+    let latest_content = Mutable::new(String::new());
+    let source = map_ref! {
+        let language = language,
+        let content = content =>
+        (language.clone(), content.clone())
+    };
+    let copied = Mutable::new(false);
+    // Dropping the handle cancels the reset, so a later press restarts the wait
+    let reset_copied = Mutable::new(None::<TaskHandle>);
+    Stack::new()
+        .s(Width::fill())
+        .layer(El::new().s(Width::fill()).child_signal(source.map({
+            let latest_content = latest_content.clone();
+            move |(language, content)| {
+                let pre = code_pre(&language, &content);
+                latest_content.set(content);
+                pre
+            }
+        })))
+        .layer(
+            Button::new()
+                .s(Align::new().top().right())
+                .s(Padding::new().x(8).y(4))
+                .s(Font::new().size(12))
+                .label_signal(copied.signal().map_bool(|| "Copied", || "Copy"))
+                .on_press(move || {
+                    let written = clipboard::write_text(&latest_content.lock_ref());
+                    copied.set(written);
+                    reset_copied.set(written.then(|| {
+                        Task::start_droppable({
+                            let copied = copied.clone();
+                            async move {
+                                Timer::sleep(COPIED_LABEL_MS).await;
+                                copied.set(false);
+                            }
+                        })
+                    }));
+                }),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::highlighted_runs;
+
+    /// The `<pre>` that `code_pre` builds, one line per `<span>` with its color.
+    fn element_tree(language: &str, content: &str) -> String {
+        let mut tree = String::from("pre");
+        for (text, color) in highlighted_runs(language, content) {
+            tree.push_str(&format!("\n  span {} {text:?}", color.unwrap_or("plain")));
+        }
+        tree
+    }
+
+    #[test]
+    fn boon_snippet_golden_tree() {
+        assert_eq!(
+            element_tree("Boon", "counter: LATEST { 0 } |> Math/sum()"),
+            [
+                "pre",
+                "  span #eeffff \"counter\"",
+                "  span #a6accd \":\"",
+                "  span plain \" \"",
+                "  span #c792ea \"LATEST\"",
+                "  span plain \" \"",
+                "  span #a6accd \"{\"",
+                "  span plain \" \"",
+                "  span #f78c6c \"0\"",
+                "  span plain \" \"",
+                "  span #a6accd \"}\"",
+                "  span plain \" \"",
+                "  span #89ddff \"|>\"",
+                "  span plain \" \"",
+                "  span #ffcb6b \"Math\"",
+                "  span #a6accd \"/\"",
+                "  span #82aaff \"sum\"",
+                "  span #a6accd \"(\"",
+                "  span #a6accd \")\"",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn other_languages_render_one_plain_span() {
+        assert_eq!(
+            element_tree("rust", "let x = 1;"),
+            "pre\n  span plain \"let x = 1;\""
+        );
+    }
+}
@@ -64,3 +64,175 @@ impl EngineType {
         }
     }
 }
+
+/// Severity of a message sent through the explicit log channel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    /// Returns the console level name used by the browser tooling.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Debug => "debug",
+            Self::Info => "info",
+            Self::Warn => "warn",
+            Self::Error => "error",
+        }
+    }
+}
+
+type LogHook = Box<dyn Fn(LogLevel, &str)>;
+
+thread_local! {
+    static LOG_HOOK: std::cell::RefCell<Option<LogHook>> = const { std::cell::RefCell::new(None) };
+}
+
+/// Installs the host's log hook (the playground forwards messages to the browser extension).
+///
+/// Console output alone is lost when the debugger attaches late; the hook gives the
+/// host a structured copy of every message with its level.
+pub fn set_log_hook(hook: impl Fn(LogLevel, &str) + 'static) {
+    LOG_HOOK.with(|slot| *slot.borrow_mut() = Some(Box::new(hook)));
+}
+
+/// Sends a message through the log hook, if one is installed.
+pub fn emit_log(level: LogLevel, message: &str) {
+    LOG_HOOK.with(|slot| {
+        if let Some(hook) = slot.borrow().as_ref() {
+            hook(level, message);
+        }
+    });
+}
+
+/// A non-fatal engine event worth noticing after the run that caused it
+/// (state dropped on restore, persistence entries that could not be saved, a run cut
+/// off by its growth guard, timer ticks that never fired, ...).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EngineWarning {
+    pub level: LogLevel,
+    /// Short machine-readable kind, e.g. `restore`, `persistence`, `growth` or `timer`
+    pub category: String,
+    pub message: String,
+}
+
+type WarningHook = Box<dyn Fn(EngineWarning)>;
+
+thread_local! {
+    static WARNING_HOOK: std::cell::RefCell<Option<WarningHook>> = const { std::cell::RefCell::new(None) };
+}
+
+/// Installs the host's warning hook (the playground keeps them for the whole session,
+/// boon-cli can append them to a `--warnings-log` file).
+pub fn set_warning_hook(hook: impl Fn(EngineWarning) + 'static) {
+    WARNING_HOOK.with(|slot| *slot.borrow_mut() = Some(Box::new(hook)));
+}
+
+/// Reports a non-fatal engine event through the warning hook, if one is installed.
+pub fn emit_warning(level: LogLevel, category: &str, message: impl Into<String>) {
+    WARNING_HOOK.with(|slot| {
+        if let Some(hook) = slot.borrow().as_ref() {
+            hook(EngineWarning {
+                level,
+                category: category.to_string(),
+                message: message.into(),
+            });
+        }
+    });
+}
+
+/// How a run of propagation steps after one event ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Quiescence {
+    /// Nothing was left to propagate after `steps` steps.
+    Settled { steps: usize },
+    /// The step budget ran out first; the remaining updates show up on the next event.
+    BudgetExhausted { budget: usize },
+}
+
+impl Quiescence {
+    /// Reports an exhausted budget as a `quiescence` warning; `source` names the runtime.
+    pub fn warn_if_exhausted(self, source: &str) {
+        if let Self::BudgetExhausted { budget } = self {
+            emit_warning(
+                LogLevel::Warn,
+                "quiescence",
+                format!(
+                    "{source} did not settle within {budget} steps; \
+                     some updates will appear with the next event"
+                ),
+            );
+        }
+    }
+}
+
+/// Default soft limit for one Text payload produced by a `TEXT { .. }` template or a
+/// builtin function.
+pub const DEFAULT_MAX_TEXT_BYTES: usize = 4 * 1024 * 1024;
+
+/// Appended to Text cut by [`truncate_oversized_text`].
+pub const TEXT_TRUNCATION_MARKER: &str = "…";
+
+thread_local! {
+    static MAX_TEXT_BYTES: std::cell::Cell<usize> = const { std::cell::Cell::new(DEFAULT_MAX_TEXT_BYTES) };
+}
+
+/// Sets the soft Text size limit (`--max-text-bytes` in boon-cli, a playground setting).
+pub fn set_max_text_bytes(limit: usize) {
+    MAX_TEXT_BYTES.with(|max| max.set(limit));
+}
+
+pub fn max_text_bytes() -> usize {
+    MAX_TEXT_BYTES.with(|max| max.get())
+}
+
+/// Cuts `text` down to the soft limit on a char boundary and appends
+/// [`TEXT_TRUNCATION_MARKER`]. Returns whether anything was cut.
+pub fn truncate_oversized_text(text: &mut String) -> bool {
+    let limit = max_text_bytes();
+    if text.len() <= limit {
+        return false;
+    }
+    let mut end = limit;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    text.truncate(end);
+    text.push_str(TEXT_TRUNCATION_MARKER);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_within_limit_is_kept() {
+        set_max_text_bytes(8);
+        let mut text = "12345678".to_string();
+        assert!(!truncate_oversized_text(&mut text));
+        assert_eq!(text, "12345678");
+    }
+
+    #[test]
+    fn oversized_text_is_cut_on_char_boundary() {
+        set_max_text_bytes(4);
+        let mut text = "abcčd".to_string();
+        assert!(truncate_oversized_text(&mut text));
+        assert_eq!(text, "abc…");
+    }
+
+    #[test]
+    fn only_an_exhausted_budget_is_reported() {
+        let warnings = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let sink = warnings.clone();
+        set_warning_hook(move |warning| sink.borrow_mut().push(warning.category));
+        Quiescence::Settled { steps: 3 }.warn_if_exhausted("test runtime");
+        Quiescence::BudgetExhausted { budget: 10 }.warn_if_exhausted("test runtime");
+        assert_eq!(*warnings.borrow(), vec!["quiescence".to_string()]);
+    }
+}
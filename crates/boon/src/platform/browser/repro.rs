@@ -0,0 +1,279 @@
+//! Recorded event injections, saved as repro scripts for bug reports.
+//!
+//! Engines report every event they inject into a running program with
+//! [`record_injection`]. The last [`DEFAULT_REPRO_CAPACITY`] injections of the current
+//! run are kept, and the playground's "Save repro" button downloads them as a
+//! [`ReproScript`]. Replaying a script injects the same events in the same order,
+//! which for a deterministic engine reproduces the same state.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+use zoon::{JsCast, js_sys, serde_json, web_sys};
+
+use super::common::EngineType;
+
+/// Bumped when [`ReproScript`] changes incompatibly.
+pub const REPRO_SCRIPT_VERSION: u32 = 1;
+
+/// Injections kept per run; older ones are dropped first.
+pub const DEFAULT_REPRO_CAPACITY: usize = 10_000;
+
+/// One injected event.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RecordedInjection {
+    /// Link path (or input name) the event was injected into
+    pub path: String,
+    /// Engine-specific event, enough to inject it again
+    pub payload: serde_json::Value,
+    /// Engine tick (epoch) the injection started
+    pub tick: u64,
+    /// Milliseconds since the run started
+    pub time_ms: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReproScript {
+    pub version: u32,
+    /// Payloads are engine-specific, so a script replays only on the engine that recorded it.
+    pub engine: EngineType,
+    /// Injections lost to the capacity limit before the first recorded one.
+    /// A script with drops starts mid-run and may not reproduce the original state.
+    pub dropped: usize,
+    pub injections: Vec<RecordedInjection>,
+}
+
+/// A replayed injection with the virtual time to advance before it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ReplayStep<'a> {
+    pub advance_ms: u64,
+    pub injection: &'a RecordedInjection,
+}
+
+impl ReproScript {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let script: Self =
+            serde_json::from_str(json).map_err(|error| format!("invalid repro script: {error}"))?;
+        if script.version != REPRO_SCRIPT_VERSION {
+            return Err(format!(
+                "repro script version {} is not supported (expected {REPRO_SCRIPT_VERSION})",
+                script.version
+            ));
+        }
+        Ok(script)
+    }
+
+    /// Injections in order, each with the time elapsed since the previous one
+    /// (the first one since the run started).
+    pub fn replay_steps(&self) -> impl Iterator<Item = ReplayStep<'_>> {
+        let mut previous_ms = 0;
+        self.injections.iter().map(move |injection| {
+            let advance_ms = injection.time_ms.saturating_sub(previous_ms);
+            previous_ms = previous_ms.max(injection.time_ms);
+            ReplayStep {
+                advance_ms,
+                injection,
+            }
+        })
+    }
+}
+
+/// Bounded buffer of the injections of one run.
+#[derive(Clone, Debug)]
+pub struct InjectionRecorder {
+    engine: EngineType,
+    capacity: usize,
+    dropped: usize,
+    injections: VecDeque<RecordedInjection>,
+}
+
+impl InjectionRecorder {
+    pub fn new(engine: EngineType, capacity: usize) -> Self {
+        Self {
+            engine,
+            capacity,
+            dropped: 0,
+            injections: VecDeque::new(),
+        }
+    }
+
+    pub fn record(&mut self, injection: RecordedInjection) {
+        if self.capacity == 0 {
+            self.dropped += 1;
+            return;
+        }
+        if self.injections.len() == self.capacity {
+            self.injections.pop_front();
+            self.dropped += 1;
+        }
+        self.injections.push_back(injection);
+    }
+
+    pub fn script(&self) -> ReproScript {
+        ReproScript {
+            version: REPRO_SCRIPT_VERSION,
+            engine: self.engine,
+            dropped: self.dropped,
+            injections: self.injections.iter().cloned().collect(),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+type RunStart = std::time::Instant;
+
+#[cfg(target_arch = "wasm32")]
+type RunStart = f64;
+
+fn run_start() -> RunStart {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::time::Instant::now()
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        performance_now()
+    }
+}
+
+fn elapsed_ms(start: RunStart) -> u64 {
+    #[cfg(not(target_arch = "wasm32"))]
+    let elapsed = start.elapsed();
+
+    #[cfg(target_arch = "wasm32")]
+    let elapsed = std::time::Duration::from_secs_f64((performance_now() - start).max(0.0) / 1000.0);
+
+    u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn performance_now() -> f64 {
+    web_sys::window()
+        .and_then(|window| window.performance())
+        .map(|performance| performance.now())
+        .unwrap_or_else(js_sys::Date::now)
+}
+
+thread_local! {
+    static RECORDER: RefCell<Option<(InjectionRecorder, RunStart)>> = const { RefCell::new(None) };
+}
+
+/// Starts recording a new run, discarding the previous run's injections.
+pub fn start_injection_recording(engine: EngineType) {
+    RECORDER.with(|recorder| {
+        *recorder.borrow_mut() = Some((
+            InjectionRecorder::new(engine, DEFAULT_REPRO_CAPACITY),
+            run_start(),
+        ));
+    });
+}
+
+/// Forgets the current recording. Hosts call it before every run, so engines that
+/// do not record never leave the previous run's script behind.
+pub fn stop_injection_recording() {
+    RECORDER.with(|recorder| *recorder.borrow_mut() = None);
+}
+
+/// Records an injection of the current run; does nothing before the first
+/// [`start_injection_recording`].
+pub fn record_injection(path: &str, payload: serde_json::Value, tick: u64) {
+    RECORDER.with(|recorder| {
+        if let Some((recorder, started)) = recorder.borrow_mut().as_mut() {
+            recorder.record(RecordedInjection {
+                path: path.to_string(),
+                payload,
+                tick,
+                time_ms: elapsed_ms(*started),
+            });
+        }
+    });
+}
+
+/// Script of the current run so far.
+pub fn recorded_repro_script() -> Option<ReproScript> {
+    RECORDER.with(|recorder| {
+        recorder
+            .borrow()
+            .as_ref()
+            .map(|(recorder, _)| recorder.script())
+    })
+}
+
+/// Offers `script` as a `boon-repro.json` download. Returns `false` when the page
+/// has no document to attach the download link to.
+pub fn download_repro_script(script: &ReproScript) -> bool {
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+        return false;
+    };
+    let Ok(link) = document.create_element("a") else {
+        return false;
+    };
+    let href = format!(
+        "data:application/json;charset=utf-8,{}",
+        js_sys::encode_uri_component(&script.to_json())
+    );
+    if link.set_attribute("href", &href).is_err()
+        || link.set_attribute("download", "boon-repro.json").is_err()
+    {
+        return false;
+    }
+    let Some(click) = js_sys::Reflect::get(&link, &"click".into())
+        .ok()
+        .and_then(|function| function.dyn_into::<js_sys::Function>().ok())
+    else {
+        return false;
+    };
+    click.call0(&link).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn injection(path: &str, tick: u64, time_ms: u64) -> RecordedInjection {
+        RecordedInjection {
+            path: path.to_string(),
+            payload: serde_json::json!({ "kind": "LinkPress" }),
+            tick,
+            time_ms,
+        }
+    }
+
+    #[test]
+    fn full_buffer_drops_the_oldest_injections() {
+        let mut recorder = InjectionRecorder::new(EngineType::DifferentialDataflow, 2);
+        recorder.record(injection("a", 1, 0));
+        recorder.record(injection("b", 2, 10));
+        recorder.record(injection("c", 3, 20));
+
+        let script = recorder.script();
+        assert_eq!(script.dropped, 1);
+        let paths: Vec<_> = script.injections.iter().map(|i| i.path.as_str()).collect();
+        assert_eq!(paths, vec!["b", "c"]);
+    }
+
+    #[test]
+    fn replay_advances_virtual_time_by_recorded_gaps() {
+        let mut recorder = InjectionRecorder::new(EngineType::DifferentialDataflow, 10);
+        recorder.record(injection("a", 1, 250));
+        recorder.record(injection("b", 2, 250));
+        recorder.record(injection("c", 3, 1_000));
+
+        let script = ReproScript::from_json(&recorder.script().to_json()).unwrap();
+        let advances: Vec<_> = script.replay_steps().map(|step| step.advance_ms).collect();
+        assert_eq!(advances, vec![250, 0, 750]);
+    }
+
+    #[test]
+    fn scripts_from_another_version_are_rejected() {
+        let mut script = InjectionRecorder::new(EngineType::Actors, 10).script();
+        script.version = REPRO_SCRIPT_VERSION + 1;
+        assert!(ReproScript::from_json(&script.to_json()).is_err());
+    }
+}
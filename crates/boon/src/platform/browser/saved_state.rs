@@ -0,0 +1,231 @@
+//! Saved state, split into program state and UI state.
+//!
+//! Program state is what persistent HOLDs and LISTs keep between runs. UI state is
+//! what the bridge and the playground record about the page around the program:
+//! the last focused input, scroll offsets and the playground panel toggles. Each
+//! namespace lives under its own localStorage keys, so either one can be cleared
+//! while the other survives a re-run.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use zoon::{WebStorage, local_storage, web_sys};
+
+/// HOLD and LIST values of persistent variables.
+pub const STATES_STORAGE_KEY: &str = "boon-playground-states";
+/// Source of the previous run, used to map persistence ids onto edited code.
+pub const OLD_SOURCE_CODE_STORAGE_KEY: &str = "boon-playground-old-source-code";
+pub const OLD_SPAN_ID_PAIRS_STORAGE_KEY: &str = "boon-playground-span-id-pairs";
+/// Dynamically keyed program state (recorded list calls, removed items, DD and Wasm holds).
+const PROGRAM_STATE_PREFIXES: &[&str] =
+    &["list_calls:", "list_removed:", "dd_", "wasm_", "wasm_pro_"];
+
+/// Focus and scroll positions recorded by the bridge, see [`UiState`].
+pub const UI_STATE_STORAGE_KEY: &str = "boon-playground-ui-state";
+pub const OTHER_COLLAPSED_STORAGE_KEY: &str = "boon-playground-other-collapsed";
+pub const DEBUG_COLLAPSED_STORAGE_KEY: &str = "boon-playground-debug-collapsed";
+
+/// One of the two independently clearable parts of saved state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StateNamespace {
+    Program,
+    Ui,
+}
+
+impl StateNamespace {
+    fn keys(self) -> &'static [&'static str] {
+        match self {
+            Self::Program => &[
+                STATES_STORAGE_KEY,
+                OLD_SOURCE_CODE_STORAGE_KEY,
+                OLD_SPAN_ID_PAIRS_STORAGE_KEY,
+            ],
+            Self::Ui => &[
+                UI_STATE_STORAGE_KEY,
+                OTHER_COLLAPSED_STORAGE_KEY,
+                DEBUG_COLLAPSED_STORAGE_KEY,
+            ],
+        }
+    }
+
+    fn prefixes(self) -> &'static [&'static str] {
+        match self {
+            Self::Program => PROGRAM_STATE_PREFIXES,
+            Self::Ui => &[],
+        }
+    }
+
+    /// Whether the localStorage entry `key` belongs to this namespace.
+    pub fn owns_key(self, key: &str) -> bool {
+        self.keys().contains(&key) || self.prefixes().iter().any(|prefix| key.starts_with(prefix))
+    }
+}
+
+/// Removes every localStorage entry owned by `namespace`.
+///
+/// Engines keep in-memory copies of their holds; hosts clear those separately.
+pub fn clear_state_namespace(namespace: StateNamespace) {
+    let Some(storage) = web_sys::window().and_then(|window| window.local_storage().ok().flatten())
+    else {
+        return;
+    };
+    let length = storage.length().unwrap_or(0);
+    let keys = (0..length)
+        .filter_map(|index| storage.key(index).ok().flatten())
+        .filter(|key| namespace.owns_key(key))
+        .collect::<Vec<_>>();
+    for key in keys {
+        let _ = storage.remove_item(&key);
+    }
+}
+
+/// Page state around the program, keyed by the persistence id of the element's
+/// variable so it follows the element across re-runs like HOLD state does.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UiState {
+    pub focused_input: Option<String>,
+    /// `(left, top)` scroll offsets in pixels.
+    pub scroll_positions: BTreeMap<String, (i32, i32)>,
+}
+
+impl UiState {
+    pub fn load() -> Self {
+        local_storage()
+            .get::<Self>(UI_STATE_STORAGE_KEY)
+            .and_then(Result::ok)
+            .unwrap_or_default()
+    }
+
+    fn store(&self) {
+        if let Err(error) = local_storage().insert(UI_STATE_STORAGE_KEY, self) {
+            zoon::eprintln!("Failed to store UI state: {error:#?}");
+        }
+    }
+
+    /// Whether the input `id` should get focus back; answers `true` at most once.
+    pub fn take_focus(&mut self, id: &str) -> bool {
+        if self.focused_input.as_deref() == Some(id) {
+            self.focused_input = None;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Scroll offsets to restore on element `id`, handed out at most once.
+    pub fn take_scroll_position(&mut self, id: &str) -> Option<(i32, i32)> {
+        self.scroll_positions.remove(id)
+    }
+}
+
+thread_local! {
+    /// UI state of the previous run, waiting for its elements to be mounted again.
+    static PENDING_RESTORE: RefCell<UiState> = RefCell::default();
+}
+
+/// Records `id` as the last focused input.
+pub fn record_focused_input(id: &str) {
+    let mut ui_state = UiState::load();
+    if ui_state.focused_input.as_deref() != Some(id) {
+        ui_state.focused_input = Some(id.to_owned());
+        ui_state.store();
+    }
+}
+
+pub fn record_scroll_position(id: &str, left: i32, top: i32) {
+    let mut ui_state = UiState::load();
+    if ui_state.scroll_positions.get(id) != Some(&(left, top)) {
+        ui_state.scroll_positions.insert(id.to_owned(), (left, top));
+        ui_state.store();
+    }
+}
+
+/// Loads the saved UI state so the elements of the next run can pick it up with
+/// [`take_focus_restore`] and [`take_scroll_restore`].
+///
+/// Engines call it when they restore program state into a run; without persistence
+/// a re-run starts with a fresh page.
+pub fn arm_ui_state_restore() {
+    let ui_state = UiState::load();
+    PENDING_RESTORE.with(|pending| *pending.borrow_mut() = ui_state);
+}
+
+pub fn take_focus_restore(id: &str) -> bool {
+    PENDING_RESTORE.with(|pending| pending.borrow_mut().take_focus(id))
+}
+
+pub fn take_scroll_restore(id: &str) -> Option<(i32, i32)> {
+    PENDING_RESTORE.with(|pending| pending.borrow_mut().take_scroll_position(id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn saved_keys() -> Vec<&'static str> {
+        vec![
+            STATES_STORAGE_KEY,
+            OLD_SPAN_ID_PAIRS_STORAGE_KEY,
+            "list_calls:0196a1b2",
+            "dd_hold_states",
+            UI_STATE_STORAGE_KEY,
+            OTHER_COLLAPSED_STORAGE_KEY,
+            "boon-playground-project-files",
+        ]
+    }
+
+    fn clear(keys: &[&'static str], namespace: StateNamespace) -> Vec<&'static str> {
+        keys.iter()
+            .copied()
+            .filter(|key| !namespace.owns_key(key))
+            .collect()
+    }
+
+    #[test]
+    fn namespaces_never_share_a_key() {
+        for key in saved_keys() {
+            assert!(
+                !(StateNamespace::Program.owns_key(key) && StateNamespace::Ui.owns_key(key)),
+                "`{key}` is in both namespaces"
+            );
+        }
+    }
+
+    #[test]
+    fn clearing_program_state_keeps_ui_state_for_the_next_run() {
+        let remaining = clear(&saved_keys(), StateNamespace::Program);
+        assert_eq!(
+            remaining,
+            vec![
+                UI_STATE_STORAGE_KEY,
+                OTHER_COLLAPSED_STORAGE_KEY,
+                "boon-playground-project-files"
+            ]
+        );
+
+        let mut restored = UiState {
+            focused_input: Some("input".to_owned()),
+            scroll_positions: BTreeMap::from([("list".to_owned(), (0, 120))]),
+        };
+        assert!(restored.take_focus("input"));
+        assert!(!restored.take_focus("input"));
+        assert_eq!(restored.take_scroll_position("list"), Some((0, 120)));
+        assert_eq!(restored.take_scroll_position("list"), None);
+    }
+
+    #[test]
+    fn clearing_ui_state_keeps_program_state() {
+        let remaining = clear(&saved_keys(), StateNamespace::Ui);
+        assert_eq!(
+            remaining,
+            vec![
+                STATES_STORAGE_KEY,
+                OLD_SPAN_ID_PAIRS_STORAGE_KEY,
+                "list_calls:0196a1b2",
+                "dd_hold_states",
+                "boon-playground-project-files"
+            ]
+        );
+    }
+}
@@ -0,0 +1,238 @@
+//! Pure helpers behind stdlib functions that more than one engine implements,
+//! so the engines agree on the edge cases.
+
+/// Parse `?filter=active&page=2` into ordered key/value pairs.
+/// Keys without `=` get an empty value and `+` is decoded as a space.
+/// A repeated key keeps its first position and its last value.
+pub fn parse_query_params(search: &str) -> Vec<(String, String)> {
+    let mut params: Vec<(String, String)> = Vec::new();
+    for pair in search.trim_start_matches('?').split('&') {
+        if pair.is_empty() {
+            continue;
+        }
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let (key, value) = (decode_query_component(key), decode_query_component(value));
+        let existing = params
+            .iter_mut()
+            .find(|(existing_key, _)| *existing_key == key);
+        match existing {
+            Some((_, existing_value)) => *existing_value = value,
+            None => params.push((key, value)),
+        }
+    }
+    params
+}
+
+/// Serialize key/value pairs back into a query string (with the leading `?`, empty if no pairs).
+pub fn format_query_params(params: &[(String, String)]) -> String {
+    if params.is_empty() {
+        return String::new();
+    }
+    let pairs: Vec<String> = params
+        .iter()
+        .map(|(key, value)| {
+            format!(
+                "{}={}",
+                encode_query_component(key),
+                encode_query_component(value)
+            )
+        })
+        .collect();
+    format!("?{}", pairs.join("&"))
+}
+
+/// `params` without `key`, then `key` appended with `value`, like
+/// `Document/set_query_param`. An empty `value` only removes the key.
+pub fn set_query_param(params: &mut Vec<(String, String)>, key: &str, value: String) {
+    params.retain(|(existing_key, _)| existing_key != key);
+    if !value.is_empty() {
+        params.push((key.to_string(), value));
+    }
+}
+
+/// `Math/divide_int`: the quotient rounded toward negative infinity, so -7 by 2 is -4.
+pub fn floored_division(a: f64, by: f64) -> f64 {
+    (a / by).floor()
+}
+
+/// `Math/modulo`: the remainder with the sign of `by`, matching [`floored_division`]:
+/// `a` is always `by * floored_division(a, by) + floored_modulo(a, by)`.
+/// Never -0, NaN when `by` is 0.
+pub fn floored_modulo(a: f64, by: f64) -> f64 {
+    let remainder = a % by;
+    if remainder == 0.0 {
+        0.0
+    } else if (remainder < 0.0) != (by < 0.0) {
+        remainder + by
+    } else {
+        remainder
+    }
+}
+
+/// `Math/clamp`: `value` kept within `min..=max`. Unlike `f64::clamp` it does not
+/// panic when the bounds cross; `min` wins then.
+pub fn clamp_between(value: f64, min: f64, max: f64) -> f64 {
+    if min > max {
+        min
+    } else {
+        value.max(min).min(max)
+    }
+}
+
+/// `Number/to_text`: `number` rounded to `decimals` places (0 to 20, rounded to a
+/// whole count) and padded with zeros, so 12.5 with 2 reads "12.50".
+/// `thousands_separator` goes between groups of three integer digits; empty means no
+/// grouping. A result that rounds to zero drops its minus sign, and NaN and the
+/// infinities print as is.
+pub fn format_number(number: f64, decimals: f64, thousands_separator: &str) -> String {
+    if !number.is_finite() {
+        return number.to_string();
+    }
+    let decimals = (0_u8..20)
+        .take_while(|places| f64::from(*places) < decimals.round())
+        .count();
+    let formatted = format!("{number:.decimals$}");
+    let (sign, unsigned) = match formatted.strip_prefix('-') {
+        Some(unsigned) if unsigned.chars().any(|digit| ('1'..='9').contains(&digit)) => {
+            ("-", unsigned)
+        }
+        Some(unsigned) => ("", unsigned),
+        None => ("", formatted.as_str()),
+    };
+    let (integer, fraction) = match unsigned.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (unsigned, None),
+    };
+    let mut grouped = String::new();
+    for (index, digit) in integer.chars().enumerate() {
+        if index > 0 && (integer.len() - index) % 3 == 0 {
+            grouped.push_str(thousands_separator);
+        }
+        grouped.push(digit);
+    }
+    match fraction {
+        Some(fraction) => format!("{sign}{grouped}.{fraction}"),
+        None => format!("{sign}{grouped}"),
+    }
+}
+
+fn decode_query_component(component: &str) -> String {
+    fn hex_value(byte: u8) -> Option<u8> {
+        char::from(byte)
+            .to_digit(16)
+            .and_then(|digit| u8::try_from(digit).ok())
+    }
+    let bytes = component.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'+' => decoded.push(b' '),
+            b'%' => {
+                let high = bytes.get(index + 1).copied().and_then(hex_value);
+                let low = bytes.get(index + 2).copied().and_then(hex_value);
+                if let (Some(high), Some(low)) = (high, low) {
+                    decoded.push(high * 16 + low);
+                    index += 2;
+                } else {
+                    decoded.push(b'%');
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        index += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn encode_query_component(component: &str) -> String {
+    let mut encoded = String::with_capacity(component.len());
+    for byte in component.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(char::from(byte))
+            }
+            b' ' => encoded.push('+'),
+            byte => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pair(key: &str, value: &str) -> (String, String) {
+        (key.to_string(), value.to_string())
+    }
+
+    #[test]
+    fn repeated_query_key_keeps_its_last_value() {
+        assert_eq!(
+            parse_query_params("?filter=active&page=2&filter=completed"),
+            vec![pair("filter", "completed"), pair("page", "2")]
+        );
+        assert_eq!(
+            parse_query_params("?q=a+b&empty&q=%C4%8D"),
+            vec![pair("q", "č"), pair("empty", "")]
+        );
+        assert_eq!(parse_query_params(""), Vec::new());
+    }
+
+    #[test]
+    fn query_params_round_trip_through_the_query_string() {
+        let mut params = vec![pair("filter", "all"), pair("page", "2")];
+        set_query_param(&mut params, "filter", "in progress".to_string());
+        assert_eq!(format_query_params(&params), "?page=2&filter=in+progress");
+        assert_eq!(parse_query_params(&format_query_params(&params)), params);
+        set_query_param(&mut params, "page", String::new());
+        assert_eq!(format_query_params(&params), "?filter=in+progress");
+        set_query_param(&mut params, "filter", String::new());
+        assert_eq!(format_query_params(&params), "");
+    }
+
+    #[test]
+    fn floored_modulo_takes_the_sign_of_by() {
+        assert_eq!(floored_modulo(7.0, 3.0), 1.0);
+        assert_eq!(floored_modulo(-1.0, 2.0), 1.0);
+        assert_eq!(floored_modulo(1.0, -2.0), -1.0);
+        assert!(floored_modulo(-6.0, 3.0).is_sign_positive());
+        assert_eq!(floored_modulo(5.5, 2.0), 1.5);
+        assert!(floored_modulo(5.0, 0.0).is_nan());
+        assert_eq!(floored_division(-7.0, 2.0), -4.0);
+        for a in -12..=12 {
+            for by in [-5, -3, -1, 1, 2, 7] {
+                let (a, by) = (f64::from(a), f64::from(by));
+                assert_eq!(
+                    by * floored_division(a, by) + floored_modulo(a, by),
+                    a,
+                    "{a} by {by}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn clamp_between_keeps_within_bounds_and_prefers_min_when_they_cross() {
+        assert_eq!(clamp_between(5.0, 0.0, 10.0), 5.0);
+        assert_eq!(clamp_between(-3.0, 0.0, 10.0), 0.0);
+        assert_eq!(clamp_between(12.5, 0.0, 10.0), 10.0);
+        assert_eq!(clamp_between(4.0, 4.0, 4.0), 4.0);
+        assert_eq!(clamp_between(5.0, 10.0, 0.0), 10.0);
+    }
+
+    #[test]
+    fn format_number_fixes_decimals_and_groups_thousands() {
+        assert_eq!(format_number(0.1 + 0.2, 2.0, ""), "0.30");
+        assert_eq!(format_number(12.5, 2.0, ""), "12.50");
+        assert_eq!(format_number(2.675, 0.0, ""), "3");
+        assert_eq!(format_number(1234567.891, 2.0, ","), "1,234,567.89");
+        assert_eq!(format_number(-1234.5, 1.0, " "), "-1 234.5");
+        assert_eq!(format_number(999.999, 2.0, ","), "1,000.00");
+        assert_eq!(format_number(-0.001, 2.0, ""), "0.00");
+        assert_eq!(format_number(5.0, -3.0, ""), "5");
+        assert_eq!(format_number(5.0, f64::NAN, ""), "5");
+        assert_eq!(format_number(f64::INFINITY, 2.0, ","), "inf");
+    }
+}
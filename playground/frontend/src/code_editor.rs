@@ -101,6 +101,20 @@ impl CodeEditor {
             drop(closure);
         })
     }
+
+    /// F2 in the editor; receives the cursor as a UTF-16 offset into the document.
+    pub fn on_rename_request(self, mut on_rename_request: impl FnMut(u32) + 'static) -> Self {
+        let closure = Rc::new(Closure::new(move |offset: u32| {
+            on_rename_request(offset);
+        }));
+        let task = Task::start_droppable(self.controller.wait_for_some_ref(
+            clone!((closure) move |controller| controller.on_rename_request(&closure)),
+        ));
+        self.after_remove(move |_| {
+            drop(task);
+            drop(closure);
+        })
+    }
 }
 
 mod js_bridge {
@@ -132,5 +146,11 @@ mod js_bridge {
             this: &CodeEditorController,
             on_cursor_change: &Closure<dyn FnMut(u32, u32)>,
         );
+
+        #[wasm_bindgen(method)]
+        pub fn on_rename_request(
+            this: &CodeEditorController,
+            on_rename_request: &Closure<dyn FnMut(u32)>,
+        );
     }
 }
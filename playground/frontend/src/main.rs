@@ -5,13 +5,24 @@ use boon::zoon::{Rgba, map_ref};
 use boon::zoon::{eprintln, println, *};
 use std::borrow::Cow;
 use std::cell::RefCell;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::rc::Rc;
 use std::sync::Once;
 use std::sync::Arc;
 use ulid::Ulid;
 
-use boon::platform::browser::common::EngineType;
+use boon::parser::rename::{RenameCertainty, RenameEdit, RenamePlan, plan_rename};
+use boon::platform::browser::common::{
+    DEFAULT_MAX_TEXT_BYTES, EngineType, EngineWarning, LogLevel, emit_log, set_log_hook,
+    set_max_text_bytes, set_warning_hook,
+};
+use boon::platform::browser::repro::{
+    download_repro_script, recorded_repro_script, stop_injection_recording,
+};
+use boon::platform::browser::saved_state::{
+    DEBUG_COLLAPSED_STORAGE_KEY, OLD_SOURCE_CODE_STORAGE_KEY, OLD_SPAN_ID_PAIRS_STORAGE_KEY,
+    OTHER_COLLAPSED_STORAGE_KEY, STATES_STORAGE_KEY, StateNamespace, clear_state_namespace,
+};
 
 #[cfg(feature = "engine-actors")]
 use boon_engine_actors::{
@@ -139,6 +150,19 @@ fn clear_all_compiled_engine_persisted_states() {
     clear_wasm_persisted_states();
 }
 
+/// Drops saved HOLD and LIST values of every engine; focus, scroll and panel toggles stay.
+fn clear_program_state() {
+    clear_all_compiled_engine_persisted_states();
+    clear_state_namespace(StateNamespace::Program);
+}
+
+/// Drops recorded focus and scroll positions and folds the panels back; program state stays.
+fn clear_ui_state(other_collapsed: &Mutable<bool>, debug_collapsed: &Mutable<bool>) {
+    clear_state_namespace(StateNamespace::Ui);
+    other_collapsed.set_neq(true);
+    debug_collapsed.set_neq(true);
+}
+
 fn engine_supports_persistence(engine: EngineType) -> bool {
     !matches!(engine, EngineType::ActorsLite | EngineType::FactoryFabric)
 }
@@ -202,16 +226,12 @@ use code_editor::CodeEditor;
 static PROJECT_FILES_STORAGE_KEY: &str = "boon-playground-project-files";
 static CURRENT_FILE_STORAGE_KEY: &str = "boon-playground-current-file";
 
-static OLD_SOURCE_CODE_STORAGE_KEY: &str = "boon-playground-old-source-code";
-static OLD_SPAN_ID_PAIRS_STORAGE_KEY: &str = "boon-playground-span-id-pairs";
-static STATES_STORAGE_KEY: &str = "boon-playground-states";
 static PANEL_SPLIT_STORAGE_KEY: &str = "boon-playground-panel-split";
-static OTHER_COLLAPSED_STORAGE_KEY: &str = "boon-playground-other-collapsed";
-static DEBUG_COLLAPSED_STORAGE_KEY: &str = "boon-playground-debug-collapsed";
 static CUSTOM_EXAMPLES_STORAGE_KEY: &str = "boon-playground-custom-examples";
 static FORCED_PREVIEW_SIZE_STORAGE_KEY: &str = "boon-playground-forced-preview-size";
 static PANEL_LAYOUT_STORAGE_KEY: &str = "boon-playground-panel-layout";
 static ENGINE_TYPE_STORAGE_KEY: &str = "boon-playground-engine-type";
+static MAX_TEXT_BYTES_STORAGE_KEY: &str = "boon-playground-max-text-bytes";
 
 /// Clear all localStorage keys that match given prefixes.
 /// Used to clean up dynamically-keyed persistence data.
@@ -243,7 +263,7 @@ fn clear_prefixed_storage_keys(prefixes: &[&str]) {
 }
 
 const MAIN_EXAMPLES_COUNT: usize = 12;
-const DEBUG_EXAMPLES_COUNT: usize = 13;
+const DEBUG_EXAMPLES_COUNT: usize = 17;
 
 const DEFAULT_PANEL_SPLIT_RATIO: f64 = 0.5;
 const MIN_PANEL_RATIO: f64 = 0.1;
@@ -539,8 +559,9 @@ fn parse_module_files(
         let (tokens, errors) = lexer().parse(source_code).into_output_errors();
         if !errors.is_empty() {
             eprintln!(
-                "[module-parser] lex errors in {}: {:?}",
+                "[module-parser] lex errors in {}{}: {:?}",
                 file_path,
+                generated_from_suffix(files, file_path, source_code, errors[0].span().start),
                 errors.len()
             );
             continue;
@@ -562,8 +583,9 @@ fn parse_module_files(
             .into_output_errors();
         if !errors.is_empty() {
             eprintln!(
-                "[module-parser] parse errors in {}: {:?}",
+                "[module-parser] parse errors in {}{}: {:?}",
                 file_path,
+                generated_from_suffix(files, file_path, source_code, errors[0].span().start),
                 errors.len()
             );
             continue;
@@ -604,6 +626,24 @@ fn parse_module_files(
     result
 }
 
+/// ` (generated from BUILD.bn:12)` when `file_path` was written by BUILD.bn with a line
+/// map (`File/write_with_map`) that covers `offset`, otherwise empty.
+fn generated_from_suffix(
+    files: &BTreeMap<String, String>,
+    file_path: &str,
+    source_code: &str,
+    offset: usize,
+) -> String {
+    use boon::parser::source_map::{SourceMap, source_map_path};
+
+    files
+        .get(&source_map_path(file_path))
+        .and_then(|text| SourceMap::parse(text).ok())
+        .and_then(|source_map| source_map.generated_from_note(source_code, offset))
+        .map(|note| format!(" ({note})"))
+        .unwrap_or_default()
+}
+
 /// Panel layout mode for screenshot and viewing modes
 #[derive(Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 #[serde(crate = "boon::zoon::serde")]
@@ -649,7 +689,7 @@ static EXAMPLE_DATAS: [ExampleData; MAIN_EXAMPLES_COUNT] = [
 
 // 7GUIs benchmark examples (shown in "Other" expandable section)
 // Added incrementally as each task is implemented.
-static OTHER_EXAMPLE_DATAS: [ExampleData; 12] = [
+static OTHER_EXAMPLE_DATAS: [ExampleData; 30] = [
     make_example_data!("temperature_converter"),
     make_example_data!("crud"),
     make_example_data!("timer"),
@@ -662,11 +702,32 @@ static OTHER_EXAMPLE_DATAS: [ExampleData; 12] = [
     make_example_data!("then"),
     make_example_data!("when"),
     make_example_data!("while"),
+    make_example_data!("pages_query"),
+    make_example_data!("code_block"),
+    make_example_data!("context_menu"),
+    make_example_data!("sample_gate"),
+    make_example_data!("interval_visible"),
+    make_example_data!("paginated_list"),
+    make_example_data!("reorderable_list"),
+    make_example_data!("grid_layout"),
+    make_example_data!("live_search"),
+    make_example_data!("character_counter"),
+    make_example_data!("clock"),
+    make_example_data!("shopping_total"),
+    make_example_data!("calculator"),
+    make_example_data!("progress_percentage"),
+    make_example_data!("zebra_list"),
+    make_example_data!("bounded_counter"),
+    make_example_data!("sensor_average"),
+    make_example_data!("toggle_checkbox"),
 ];
 
 static DEBUG_EXAMPLE_DATAS: [ExampleData; DEBUG_EXAMPLES_COUNT] = [
     make_example_data!("list_retain_reactive"),
     make_example_data!("list_map_external_dep"),
+    make_example_data!("list_find_external_dep"),
+    make_example_data!("list_flatten"),
+    make_example_data!("list_update"),
     make_example_data!("list_map_block"),
     make_example_data!("list_retain_count"),
     make_example_data!("list_object_state"),
@@ -675,6 +736,7 @@ static DEBUG_EXAMPLE_DATAS: [ExampleData; DEBUG_EXAMPLES_COUNT] = [
     make_example_data!("checkbox_test"),
     make_example_data!("chained_list_remove_bug"),
     make_example_data!("while_function_call"),
+    make_example_data!("fibonacci_stepper"),
     make_example_data!("button_hover_test"),
     make_example_data!("button_hover_to_click_test"),
     make_example_data!("switch_hold_test"),
@@ -787,6 +849,7 @@ struct ActorsPreviewKeepalive {
 
 fn main() {
     install_browser_panic_hook();
+    install_log_channel();
     start_app("app", Playground::new);
 }
 
@@ -799,6 +862,7 @@ fn install_browser_panic_hook() {
 
             let message = panic_info.to_string();
             eprintln!("[boon panic] {message}");
+            emit_log(LogLevel::Error, &format!("[boon panic] {message}"));
 
             if let Some(window) = web_sys::window() {
                 let _ = js_sys::Reflect::set(
@@ -811,8 +875,145 @@ fn install_browser_panic_hook() {
     });
 }
 
+/// Messages kept in `window.__boonLogHistory` for a debugger that attaches late.
+const LOG_HISTORY_LIMIT: u32 = 500;
+
+/// Forwards engine log output to the browser tooling.
+///
+/// Every message is appended to the bounded `window.__boonLogHistory` array and
+/// dispatched as a `boon-log` DOM event with `{ level, text, timestamp }`, so the
+/// extension gets levelled output even when it was not attached at the time.
+fn install_log_channel() {
+    set_log_hook(|level, text| {
+        use boon::zoon::{js_sys, wasm_bindgen::JsValue};
+
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+        let detail = js_sys::Object::new();
+        js_sys::Reflect::set(&detail, &"level".into(), &level.as_str().into()).ok();
+        js_sys::Reflect::set(&detail, &"text".into(), &text.into()).ok();
+        js_sys::Reflect::set(
+            &detail,
+            &"timestamp".into(),
+            &JsValue::from_f64(js_sys::Date::now()),
+        )
+        .ok();
+
+        let history = js_sys::Reflect::get(&window, &"__boonLogHistory".into())
+            .ok()
+            .and_then(|value| value.dyn_into::<js_sys::Array>().ok())
+            .unwrap_or_else(|| {
+                let history = js_sys::Array::new();
+                js_sys::Reflect::set(&window, &"__boonLogHistory".into(), &history).ok();
+                history
+            });
+        history.push(&detail);
+        if history.length() > LOG_HISTORY_LIMIT {
+            history.shift();
+        }
+
+        let Some(custom_event) = js_sys::Reflect::get(&window, &"CustomEvent".into())
+            .ok()
+            .and_then(|value| value.dyn_into::<js_sys::Function>().ok())
+        else {
+            return;
+        };
+        let init = js_sys::Object::new();
+        js_sys::Reflect::set(&init, &"detail".into(), &detail).ok();
+        let arguments = js_sys::Array::of2(&"boon-log".into(), &init);
+        if let Ok(event) = js_sys::Reflect::construct(&custom_event, &arguments) {
+            window.dispatch_event(event.unchecked_ref()).ok();
+        }
+    });
+}
+
 const DEFAULT_FILE_NAME: &str = "main.bn";
 
+/// Maximum number of warnings kept for the session (oldest are dropped first)
+const WARNINGS_LIMIT: usize = 1000;
+
+/// Engine warning kept across runs for the warnings panel and `getWarnings()`.
+#[derive(Clone)]
+struct RetainedWarning {
+    warning: EngineWarning,
+    /// Milliseconds since the Unix epoch
+    timestamp: f64,
+    /// 1-based number of the run that emitted the warning (0 = before the first run)
+    run: u32,
+}
+
+/// State of the "Rename symbol" panel (F2 in the editor).
+struct RenameSymbol {
+    file: String,
+    /// Byte offset of the cursor in `file`
+    offset: usize,
+    /// Snapshot the plan is computed against; applying it requires the files to be unchanged
+    files: Rc<BTreeMap<String, String>>,
+    new_name: Mutable<String>,
+    plan: Mutable<Option<Result<Rc<RenamePlan>, String>>>,
+    /// Confirmed possible matches, by file and start offset
+    accepted_possible: Mutable<BTreeSet<(String, usize)>>,
+}
+
+impl RenameSymbol {
+    fn update_plan(&self, new_name: &str) {
+        let plan = plan_rename(&self.files, &self.file, self.offset, new_name.trim())
+            .map(Rc::new)
+            .map_err(|error| error.to_string());
+        self.accepted_possible.set(BTreeSet::new());
+        self.plan.set(Some(plan));
+    }
+
+    fn is_accepted(&self, edit: &RenameEdit) -> bool {
+        self.accepted_possible
+            .lock_ref()
+            .contains(&(edit.file.clone(), edit.range.start))
+    }
+
+    fn toggle_accepted(&self, edit: &RenameEdit) {
+        let key = (edit.file.clone(), edit.range.start);
+        let mut accepted_possible = self.accepted_possible.lock_mut();
+        if !accepted_possible.remove(&key) {
+            accepted_possible.insert(key);
+        }
+    }
+
+    fn renamed_files(&self) -> Option<BTreeMap<String, String>> {
+        let plan = self.plan.get_cloned()?.ok()?;
+        Some(plan.apply(&self.files, |edit| self.is_accepted(edit)))
+    }
+}
+
+/// CodeMirror positions count UTF-16 code units, the parser works with bytes.
+fn utf16_offset_to_byte_offset(text: &str, utf16_offset: u32) -> usize {
+    let target = usize::try_from(utf16_offset).unwrap_or(usize::MAX);
+    let mut utf16_position = 0;
+    for (byte_offset, character) in text.char_indices() {
+        if utf16_position >= target {
+            return byte_offset;
+        }
+        utf16_position += character.len_utf16();
+    }
+    text.len()
+}
+
+/// The snake_case identifier touching `offset`, used to prefill the rename input.
+fn identifier_at(text: &str, offset: usize) -> &str {
+    let is_identifier_character = |character: char| {
+        character == '_' || character.is_ascii_lowercase() || character.is_ascii_digit()
+    };
+    let start = text[..offset]
+        .char_indices()
+        .rev()
+        .find(|(_, character)| !is_identifier_character(*character))
+        .map_or(0, |(index, character)| index + character.len_utf8());
+    let end = text[offset..]
+        .find(|character: char| !is_identifier_character(character))
+        .map_or(text.len(), |index| offset + index);
+    &text[start..end]
+}
+
 #[derive(Clone)]
 struct Playground {
     /// All files in the project (filename -> content)
@@ -848,6 +1049,18 @@ struct Playground {
     engine_type: Mutable<EngineType>,
     /// Cursor position in editor (line, column) — 1-based
     cursor_position: Mutable<(u32, u32)>,
+    /// Open "Rename symbol" panel, if any
+    rename_symbol: Mutable<Option<Rc<RenameSymbol>>>,
+    /// Non-fatal engine warnings from every run of this session
+    warnings: Mutable<Rc<Vec<RetainedWarning>>>,
+    /// Number of runs started in this session
+    run_number: Mutable<u32>,
+    /// Whether the warnings section is collapsed
+    warnings_collapsed: Mutable<bool>,
+    /// Least severe level shown in the warnings section
+    warnings_level_filter: Mutable<LogLevel>,
+    /// Soft limit for one Text payload, applied to the engines on change
+    max_text_bytes: Mutable<usize>,
     _store_files_task: Rc<TaskHandle>,
     _store_current_file_task: Rc<TaskHandle>,
     _store_panel_split_task: Rc<TaskHandle>,
@@ -855,6 +1068,7 @@ struct Playground {
     _store_debug_collapsed_task: Rc<TaskHandle>,
     _store_custom_examples_task: Rc<TaskHandle>,
     _store_forced_preview_size_task: Rc<TaskHandle>,
+    _store_max_text_bytes_task: Rc<TaskHandle>,
     _store_panel_layout_task: Rc<TaskHandle>,
     _store_engine_type_task: Rc<TaskHandle>,
     _sync_source_to_files_task: Rc<TaskHandle>,
@@ -1050,6 +1264,27 @@ impl Playground {
             }),
         ));
 
+        // Warnings survive re-runs; only the Clear button (or a reload) drops them
+        let warnings: Mutable<Rc<Vec<RetainedWarning>>> = Mutable::new(Rc::new(Vec::new()));
+        let run_number = Mutable::new(0);
+        set_warning_hook({
+            let warnings = warnings.clone();
+            let run_number = run_number.clone();
+            move |warning| {
+                let retained = RetainedWarning {
+                    warning,
+                    timestamp: js_sys::Date::now(),
+                    run: run_number.get(),
+                };
+                let mut warnings = warnings.lock_mut();
+                let warnings = Rc::make_mut(&mut warnings);
+                warnings.push(retained);
+                if warnings.len() > WARNINGS_LIMIT {
+                    warnings.remove(0);
+                }
+            }
+        });
+
         // custom_examples already loaded at the start for URL parameter check
 
         let _store_custom_examples_task = Rc::new(Task::start_droppable(
@@ -1062,6 +1297,22 @@ impl Playground {
             }),
         ));
 
+        let max_text_bytes = Mutable::new(
+            local_storage()
+                .get::<usize>(MAX_TEXT_BYTES_STORAGE_KEY)
+                .and_then(Result::ok)
+                .unwrap_or(DEFAULT_MAX_TEXT_BYTES),
+        );
+        let _store_max_text_bytes_task = Rc::new(Task::start_droppable({
+            let max_text_bytes = max_text_bytes.clone();
+            max_text_bytes.signal().for_each_sync(move |limit| {
+                set_max_text_bytes(limit);
+                if let Err(error) = local_storage().insert(MAX_TEXT_BYTES_STORAGE_KEY, &limit) {
+                    eprintln!("Failed to store max text bytes: {error:#?}");
+                }
+            })
+        }));
+
         // Load forced preview size from storage
         let forced_preview_size: Mutable<Option<(u32, u32)>> = Mutable::new(
             local_storage()
@@ -1179,6 +1430,7 @@ impl Playground {
             _store_debug_collapsed_task,
             _store_custom_examples_task,
             _store_forced_preview_size_task,
+            _store_max_text_bytes_task,
             _store_panel_layout_task,
             _store_engine_type_task,
             _sync_source_to_files_task,
@@ -1186,6 +1438,12 @@ impl Playground {
             persistence_enabled: Mutable::new(false),
             engine_type,
             cursor_position: Mutable::new((1, 1)),
+            rename_symbol: Mutable::new(None),
+            warnings,
+            run_number,
+            warnings_collapsed: Mutable::new(true),
+            warnings_level_filter: Mutable::new(LogLevel::Debug),
+            max_text_bytes,
         }
         .root()
     }
@@ -1235,6 +1493,10 @@ impl Playground {
                 let panel_layout = self.panel_layout.clone();
                 let engine_type = self.engine_type.clone();
                 let persistence_enabled = self.persistence_enabled.clone();
+                let warnings = self.warnings.clone();
+                let max_text_bytes = self.max_text_bytes.clone();
+                let other_collapsed = self.other_collapsed.clone();
+                let debug_collapsed = self.debug_collapsed.clone();
                 move |raw_el| {
                     use wasm_bindgen::prelude::*;
 
@@ -1349,6 +1611,64 @@ impl Playground {
                     js_sys::Reflect::set(&api, &"setPreviewSize".into(), set_preview_size.as_ref()).ok();
                     set_preview_size.forget();
 
+                    // getWarnings() - warnings retained this session, oldest first
+                    let warnings_for_get = warnings.clone();
+                    let get_warnings = Closure::wrap(Box::new(move || -> js_sys::Array {
+                        warnings_for_get
+                            .lock_ref()
+                            .iter()
+                            .map(|retained| {
+                                let entry = js_sys::Object::new();
+                                js_sys::Reflect::set(&entry, &"level".into(), &retained.warning.level.as_str().into()).ok();
+                                js_sys::Reflect::set(&entry, &"category".into(), &retained.warning.category.as_str().into()).ok();
+                                js_sys::Reflect::set(&entry, &"message".into(), &retained.warning.message.as_str().into()).ok();
+                                js_sys::Reflect::set(&entry, &"timestamp".into(), &retained.timestamp.into()).ok();
+                                js_sys::Reflect::set(&entry, &"run".into(), &retained.run.into()).ok();
+                                JsValue::from(entry)
+                            })
+                            .collect()
+                    }) as Box<dyn Fn() -> js_sys::Array>);
+                    js_sys::Reflect::set(&api, &"getWarnings".into(), get_warnings.as_ref()).ok();
+                    get_warnings.forget();
+
+                    // clearWarnings() - drop all retained warnings
+                    let warnings_for_clear = warnings.clone();
+                    let clear_warnings = Closure::wrap(Box::new(move || {
+                        warnings_for_clear.set(Rc::new(Vec::new()));
+                    }) as Box<dyn Fn()>);
+                    js_sys::Reflect::set(&api, &"clearWarnings".into(), clear_warnings.as_ref()).ok();
+                    clear_warnings.forget();
+
+                    // setMaxTextBytes(limit) - soft limit for one Text payload, kept across reloads
+                    let max_text_bytes_for_set = max_text_bytes.clone();
+                    let set_text_limit = Closure::wrap(Box::new(move |limit: u32| {
+                        if let Ok(limit) = usize::try_from(limit) {
+                            max_text_bytes_for_set.set_neq(limit);
+                        }
+                    }) as Box<dyn Fn(u32)>);
+                    js_sys::Reflect::set(&api, &"setMaxTextBytes".into(), set_text_limit.as_ref()).ok();
+                    set_text_limit.forget();
+
+                    // getMaxTextBytes() - current soft limit for one Text payload
+                    let max_text_bytes_for_get = max_text_bytes.clone();
+                    let get_text_limit = Closure::wrap(Box::new(move || -> f64 {
+                        u32::try_from(max_text_bytes_for_get.get()).map_or(f64::from(u32::MAX), f64::from)
+                    }) as Box<dyn Fn() -> f64>);
+                    js_sys::Reflect::set(&api, &"getMaxTextBytes".into(), get_text_limit.as_ref()).ok();
+                    get_text_limit.forget();
+
+                    // clearProgramState() - drop saved HOLD/LIST values, keep focus, scroll and panels
+                    let clear_program = Closure::wrap(Box::new(clear_program_state) as Box<dyn Fn()>);
+                    js_sys::Reflect::set(&api, &"clearProgramState".into(), clear_program.as_ref()).ok();
+                    clear_program.forget();
+
+                    // clearUiState() - drop focus, scroll and panel toggles, keep program state
+                    let clear_ui = Closure::wrap(Box::new(move || {
+                        clear_ui_state(&other_collapsed, &debug_collapsed);
+                    }) as Box<dyn Fn()>);
+                    js_sys::Reflect::set(&api, &"clearUiState".into(), clear_ui.as_ref()).ok();
+                    clear_ui.forget();
+
                     // resetPreviewSize() - reset preview pane to auto size
                     let forced_preview_size_for_reset = forced_preview_size.clone();
                     let reset_preview_size = Closure::wrap(Box::new(move || {
@@ -1862,6 +2182,16 @@ impl Playground {
                     }
                 }),
             )
+            .item_signal(self.warnings_collapsed.signal().map({
+                let this = self.clone();
+                move |collapsed| {
+                    if collapsed {
+                        None
+                    } else {
+                        Some(this.warnings_section_content())
+                    }
+                }
+            }))
     }
 
     fn example_tabs_row(&self, engine: EngineType) -> impl Element + use<> {
@@ -1887,6 +2217,7 @@ impl Playground {
             .item(self.add_custom_example_button())
             .item(self.other_section_toggle())
             .item(self.debug_section_toggle())
+            .item(self.warnings_section_toggle())
     }
 
     fn other_section_content(&self, engine: EngineType) -> impl Element + use<> {
@@ -2024,6 +2355,162 @@ impl Playground {
             })
     }
 
+    fn warnings_section_toggle(&self) -> impl Element + use<> {
+        let hovered = Mutable::new(false);
+        let warning_count = self.warnings.signal_ref(|warnings| warnings.len());
+        Button::new()
+            .s(Padding::new().x(10).y(7))
+            .s(RoundedCorners::all(24))
+            .s(Font::new().size(13).weight(FontWeight::Medium).no_wrap())
+            .s(Background::new().color_signal(hovered.signal().map(|h| {
+                if h {
+                    color!("rgba(60, 70, 100, 0.4)")
+                } else {
+                    color!("rgba(40, 50, 80, 0.3)")
+                }
+            })))
+            .s(Borders::all(
+                Border::new()
+                    .color(color!("rgba(88, 126, 194, 0.25)"))
+                    .width(1),
+            ))
+            .s(Font::new().color(muted_text_color()))
+            .label_signal(
+                map_ref! {
+                    let collapsed = self.warnings_collapsed.signal(),
+                    let count = warning_count =>
+                    (*collapsed, *count)
+                }
+                .map(|(collapsed, count)| {
+                    let arrow = if collapsed { "\u{25B8}" } else { "\u{25BE}" };
+                    format!("Warnings ({count}) {arrow}")
+                }),
+            )
+            .on_hovered_change(move |is_hovered| hovered.set(is_hovered))
+            .on_press({
+                let warnings_collapsed = self.warnings_collapsed.clone();
+                move || {
+                    warnings_collapsed.set(!warnings_collapsed.get());
+                }
+            })
+    }
+
+    fn warnings_section_content(&self) -> impl Element + use<> {
+        Column::new()
+            .s(Width::fill())
+            .s(Gap::new().y(6))
+            .s(Padding::new().left(10))
+            .item(
+                Row::new()
+                    .s(Gap::new().x(8))
+                    .s(Align::new().center_y())
+                    .item(self.warnings_filter_button("All", LogLevel::Debug))
+                    .item(self.warnings_filter_button("Warnings", LogLevel::Warn))
+                    .item(self.warnings_filter_button("Errors", LogLevel::Error))
+                    .item(
+                        Button::new()
+                            .s(Padding::new().x(10).y(4))
+                            .s(Font::new().size(12).color(muted_text_color()))
+                            .label("Clear")
+                            .on_press({
+                                let warnings = self.warnings.clone();
+                                move || warnings.set(Rc::new(Vec::new()))
+                            }),
+                    )
+                    .item(self.max_text_bytes_input()),
+            )
+            .item_signal(
+                map_ref! {
+                    let warnings = self.warnings.signal_cloned(),
+                    let level_filter = self.warnings_level_filter.signal() =>
+                    (warnings.clone(), *level_filter)
+                }
+                .map(|(warnings, level_filter)| {
+                    let rows = warnings
+                        .iter()
+                        .rev()
+                        .filter(|retained| retained.warning.level >= level_filter)
+                        .map(warning_row)
+                        .collect::<Vec<_>>();
+                    if rows.is_empty() {
+                        return Some(
+                            El::new()
+                                .s(Font::new().size(12).color(muted_text_color()))
+                                .child("No warnings")
+                                .unify(),
+                        );
+                    }
+                    Some(
+                        Column::new()
+                            .s(Gap::new().y(2))
+                            .update_raw_el(|raw_el| raw_el.style("max-height", "160px"))
+                            .s(Scrollbars::both())
+                            .items(rows)
+                            .unify(),
+                    )
+                }),
+            )
+    }
+
+    fn max_text_bytes_input(&self) -> impl Element + use<> {
+        let label_text = "Text limit (bytes)";
+        Row::new()
+            .s(Gap::new().x(6))
+            .s(Align::new().center_y())
+            .item(
+                El::new()
+                    .s(Font::new().size(12).color(muted_text_color()))
+                    .child(label_text),
+            )
+            .item(
+                TextInput::new()
+                    .s(Width::exact(80))
+                    .s(Height::exact(22))
+                    .s(Padding::new().x(4))
+                    .s(RoundedCorners::all(4))
+                    .s(Font::new().size(12).color(color!("rgba(255,255,255,0.9)")))
+                    .s(Background::new().color(color!("rgba(255,255,255,0.08)")))
+                    .label_hidden(label_text)
+                    .text(self.max_text_bytes.get().to_string())
+                    .on_change({
+                        let max_text_bytes = self.max_text_bytes.clone();
+                        move |text| {
+                            // Half-typed or zero values keep the previous limit
+                            if let Ok(limit) = text.trim().parse::<usize>() {
+                                if limit > 0 {
+                                    max_text_bytes.set_neq(limit);
+                                }
+                            }
+                        }
+                    }),
+            )
+    }
+
+    fn warnings_filter_button(&self, label: &'static str, level: LogLevel) -> impl Element + use<> {
+        let selected = self
+            .warnings_level_filter
+            .signal()
+            .map(move |level_filter| level_filter == level)
+            .broadcast();
+        Button::new()
+            .s(Padding::new().x(10).y(4))
+            .s(RoundedCorners::all(12))
+            .s(Font::new().size(12).color_signal(
+                selected
+                    .signal()
+                    .map_bool(|| color!("#e8ecff"), || muted_text_color()),
+            ))
+            .s(Background::new().color_signal(selected.signal().map_bool(
+                || color!("rgba(88, 126, 194, 0.35)"),
+                || color!("rgba(40, 50, 80, 0.3)"),
+            )))
+            .label(label)
+            .on_press({
+                let warnings_level_filter = self.warnings_level_filter.clone();
+                move || warnings_level_filter.set_neq(level)
+            })
+    }
+
     fn controls_row(&self) -> impl Element + use<> {
         Row::new()
             .s(Width::fill())
@@ -2051,10 +2538,14 @@ impl Playground {
             )
             .item(self.force_size_controls())
             .item(self.persistence_toggle_button())
+            .item(self.save_repro_button())
             .item(
-                El::new()
+                Row::new()
                     .s(Align::new().right())
-                    .child(self.clear_saved_states_button()),
+                    .s(Gap::new().x(6))
+                    .item(self.clear_program_state_button())
+                    .item(self.clear_ui_state_button())
+                    .item(self.clear_saved_states_button()),
             )
     }
 
@@ -2591,6 +3082,49 @@ impl Playground {
             })
     }
 
+    /// Downloads the injections of the current run as a repro script for bug reports.
+    fn save_repro_button(&self) -> impl Element + use<> {
+        let hovered = Mutable::new(false);
+        Button::new()
+            .s(Padding::new().x(12).y(7))
+            .s(RoundedCorners::all(22))
+            .s(Borders::all(
+                Border::new()
+                    .color(color!("rgba(108, 162, 255, 0.35)"))
+                    .width(1),
+            ))
+            .s(Background::new().color_signal(hovered.signal().map_bool(
+                || color!("rgba(108, 162, 255, 0.15)"),
+                || color!("rgba(108, 162, 255, 0.08)"),
+            )))
+            .s(Font::new()
+                .size(13)
+                .weight(FontWeight::Medium)
+                .color_signal(hovered.signal().map_bool(
+                    || color!("rgba(180, 210, 255, 0.95)"),
+                    || color!("rgba(180, 210, 255, 0.75)"),
+                )))
+            .label(
+                El::new()
+                    .s(Font::new().size(12).weight(FontWeight::Medium).no_wrap())
+                    .child("Save repro"),
+            )
+            .on_hovered_change(move |is_hovered| hovered.set(is_hovered))
+            .on_press(|| match recorded_repro_script() {
+                Some(script) if download_repro_script(&script) => {
+                    println!(
+                        "[Repro] Saved {} injections ({} dropped)",
+                        script.injections.len(),
+                        script.dropped
+                    );
+                }
+                Some(_) => eprintln!("[Repro] Failed to start the download"),
+                None => {
+                    eprintln!("[Repro] Nothing recorded; only the DD engine records injections")
+                }
+            })
+    }
+
     fn panel_layout_button(&self) -> impl Element {
         Row::new()
             .s(RoundedCorners::all(22))
@@ -2735,6 +3269,33 @@ impl Playground {
     }
 
     fn clear_saved_states_button(&self) -> impl Element {
+        let other_collapsed = self.other_collapsed.clone();
+        let debug_collapsed = self.debug_collapsed.clone();
+        Self::clear_storage_button("Clear saved states", 14, move || {
+            clear_program_state();
+            clear_ui_state(&other_collapsed, &debug_collapsed);
+            local_storage().remove(PROJECT_FILES_STORAGE_KEY);
+            local_storage().remove(CURRENT_FILE_STORAGE_KEY);
+        })
+    }
+
+    fn clear_program_state_button(&self) -> impl Element {
+        Self::clear_storage_button("Clear program state", 12, clear_program_state)
+    }
+
+    fn clear_ui_state_button(&self) -> impl Element {
+        let other_collapsed = self.other_collapsed.clone();
+        let debug_collapsed = self.debug_collapsed.clone();
+        Self::clear_storage_button("Clear UI state", 12, move || {
+            clear_ui_state(&other_collapsed, &debug_collapsed)
+        })
+    }
+
+    fn clear_storage_button(
+        label: &'static str,
+        font_size: u32,
+        on_press: impl FnMut() + 'static,
+    ) -> impl Element {
         let hovered = Mutable::new(false);
         Button::new()
             .s(Padding::new().x(12).y(7))
@@ -2757,29 +3318,14 @@ impl Playground {
                 )))
             .label(
                 El::new()
-                    .s(Font::new().size(14).weight(FontWeight::Medium).no_wrap())
-                    .child("Clear saved states"),
+                    .s(Font::new()
+                        .size(font_size)
+                        .weight(FontWeight::Medium)
+                        .no_wrap())
+                    .child(label),
             )
             .on_hovered_change(move |is_hovered| hovered.set(is_hovered))
-            .on_press(|| {
-                // Clear DD in-memory state (HOLD_STATES) and localStorage
-                // Note: Previous stop_dd_engine() call was removed as it was a no-op stub
-                clear_all_compiled_engine_persisted_states();
-
-                local_storage().remove(STATES_STORAGE_KEY);
-                local_storage().remove(OLD_SOURCE_CODE_STORAGE_KEY);
-                local_storage().remove(OLD_SPAN_ID_PAIRS_STORAGE_KEY);
-                local_storage().remove(PROJECT_FILES_STORAGE_KEY);
-                local_storage().remove(CURRENT_FILE_STORAGE_KEY);
-                // Clear dynamically-keyed persistence data (list calls, removed sets, DD engine state)
-                clear_prefixed_storage_keys(&[
-                    "list_calls:",
-                    "list_removed:",
-                    "dd_",
-                    "wasm_",
-                    "wasm_pro_",
-                ]);
-            })
+            .on_press(on_press)
     }
 
     fn force_size_controls(&self) -> impl Element + use<> {
@@ -2955,6 +3501,7 @@ impl Playground {
                     .s(Width::fill())
                     .s(Height::fill())
                     .layer(self.standard_code_editor_surface())
+                    .layer(self.rename_symbol_layer())
                     .layer(
                         El::new()
                             .s(Align::new().bottom().right())
@@ -3183,6 +3730,146 @@ impl Playground {
                 let cursor_position = self.cursor_position.clone();
                 move |line, col| cursor_position.set((line, col))
             })
+            .on_rename_request({
+                let this = self.clone();
+                move |offset| this.open_rename_symbol(offset)
+            })
+    }
+
+    fn open_rename_symbol(&self, utf16_offset: u32) {
+        let file = self.current_file.get_cloned();
+        let files = self.files.get_cloned();
+        let Some(source) = files.get(&file) else {
+            return;
+        };
+        let offset = utf16_offset_to_byte_offset(source, utf16_offset);
+        let name = identifier_at(source, offset).to_string();
+        let rename_symbol = RenameSymbol {
+            file,
+            offset,
+            files,
+            new_name: Mutable::new(name.clone()),
+            plan: Mutable::new(None),
+            accepted_possible: Mutable::new(BTreeSet::new()),
+        };
+        rename_symbol.update_plan(&name);
+        self.rename_symbol.set(Some(Rc::new(rename_symbol)));
+    }
+
+    /// Swap in the renamed files at once; refuses if the editor changed meanwhile.
+    fn apply_rename_symbol(&self) {
+        let Some(rename_symbol) = self.rename_symbol.get_cloned() else {
+            return;
+        };
+        if **self.files.lock_ref() != *rename_symbol.files {
+            rename_symbol.plan.set(Some(Err(
+                "Files changed since the rename started, press F2 again".to_string(),
+            )));
+            return;
+        }
+        let Some(renamed_files) = rename_symbol.renamed_files() else {
+            return;
+        };
+        let current_content = renamed_files.get(&*self.current_file.lock_ref()).cloned();
+        self.files.set(Rc::new(renamed_files));
+        if let Some(content) = current_content {
+            self.source_code.set_neq(Rc::new(Cow::from(content)));
+        }
+        self.rename_symbol.set(None);
+    }
+
+    fn rename_symbol_layer(&self) -> impl Element + use<> {
+        El::new()
+            .s(Align::new().top().right())
+            .s(Padding::new().top(20).right(20))
+            .update_raw_el(|raw_el| raw_el.style("z-index", "20"))
+            .child_signal(self.rename_symbol.signal_cloned().map({
+                let this = self.clone();
+                move |rename_symbol| {
+                    rename_symbol.map(|rename_symbol| this.rename_symbol_panel(rename_symbol))
+                }
+            }))
+    }
+
+    fn rename_symbol_panel(&self, rename_symbol: Rc<RenameSymbol>) -> impl Element + use<> {
+        Column::new()
+            .s(Width::exact(480))
+            .s(Padding::all(14))
+            .s(Gap::new().y(10))
+            .s(RoundedCorners::all(12))
+            .s(Background::new().color(color!("rgba(11, 18, 35, 0.96)")))
+            .s(Borders::all(
+                Border::new()
+                    .color(color!("rgba(108, 162, 255, 0.35)"))
+                    .width(1),
+            ))
+            .s(Shadows::new([Shadow::new()
+                .color(color!("rgba(8, 10, 18, 0.6)"))
+                .y(18)
+                .blur(36)
+                .spread(-12)]))
+            .s(Font::new().size(13).color(color!("#e8ecff")))
+            .item(
+                El::new()
+                    .s(Font::new().weight(FontWeight::SemiBold))
+                    .child("Rename symbol"),
+            )
+            .item(
+                TextInput::new()
+                    .s(Padding::new().x(10).y(6))
+                    .s(RoundedCorners::all(6))
+                    .s(Background::new().color(color!("rgba(30, 40, 65, 0.9)")))
+                    .s(Font::new()
+                        .family([FontFamily::new("JetBrains Mono"), FontFamily::Monospace])
+                        .color(color!("#e8ecff")))
+                    .focus(true)
+                    .label_hidden("New name")
+                    .text_signal(rename_symbol.new_name.signal_cloned())
+                    .on_change({
+                        let rename_symbol = rename_symbol.clone();
+                        move |new_name| {
+                            rename_symbol.update_plan(&new_name);
+                            rename_symbol.new_name.set(new_name);
+                        }
+                    })
+                    .update_raw_el({
+                        let this = self.clone();
+                        move |raw_el| {
+                            raw_el.event_handler(move |event: events::KeyDown| {
+                                match event.key().as_str() {
+                                    "Enter" => this.apply_rename_symbol(),
+                                    "Escape" => this.rename_symbol.set(None),
+                                    _ => {}
+                                }
+                            })
+                        }
+                    }),
+            )
+            .item_signal(rename_symbol.plan.signal_cloned().map({
+                let rename_symbol = rename_symbol.clone();
+                move |plan| match plan? {
+                    Ok(plan) => Some(rename_preview(rename_symbol.clone(), plan).unify()),
+                    Err(message) => Some(
+                        El::new()
+                            .s(Font::new().color(color!("#ff8a80")))
+                            .child(message)
+                            .unify(),
+                    ),
+                }
+            }))
+            .item(
+                Row::new()
+                    .s(Align::new().right())
+                    .s(Gap::new().x(8))
+                    .item(rename_symbol_button("Cancel", {
+                        let rename_symbol = self.rename_symbol.clone();
+                        move || rename_symbol.set(None)
+                    }))
+                    .item(rename_symbol_button("Apply", {
+                        let this = self.clone();
+                        move || this.apply_rename_symbol()
+                    })),
+            )
     }
 
     fn example_panel(&self) -> impl Element + use<> {
@@ -3296,6 +3983,8 @@ impl Playground {
         let persistence_enabled = self.persistence_enabled.get();
         set_page_engine_status_snapshot(engine_type, true, 0);
         clear_page_engine_debug_snapshot();
+        stop_injection_recording();
+        self.run_number.replace_with(|run_number| *run_number + 1);
 
         #[cfg(feature = "engine-actors")]
         self.clear_actors_preview_keepalive();
@@ -4217,6 +4906,132 @@ fn force_size_input(value: Mutable<String>, label_text: &'static str) -> impl El
         )
 }
 
+fn warning_row(retained: &RetainedWarning) -> impl Element + use<> {
+    let level_color = match retained.warning.level {
+        LogLevel::Error => color!("#ff8a80"),
+        LogLevel::Warn => color!("#ffd180"),
+        LogLevel::Info | LogLevel::Debug => muted_text_color(),
+    };
+    let time = js_sys::Date::new(&retained.timestamp.into()).to_locale_time_string("en-US");
+    Row::new()
+        .s(Gap::new().x(8))
+        .s(Font::new()
+            .size(12)
+            .family([FontFamily::new("JetBrains Mono"), FontFamily::Monospace]))
+        .item(
+            El::new()
+                .s(Font::new().color(muted_text_color()).no_wrap())
+                .child(format!("{} run #{}", String::from(time), retained.run)),
+        )
+        .item(
+            El::new()
+                .s(Font::new().color(level_color).no_wrap())
+                .child(format!(
+                    "[{}] {}",
+                    retained.warning.level.as_str(),
+                    retained.warning.category
+                )),
+        )
+        .item(El::new().child(retained.warning.message.clone()))
+}
+
+/// Every occurrence with its line before and after; possible matches get a toggle.
+fn rename_preview(rename_symbol: Rc<RenameSymbol>, plan: Rc<RenamePlan>) -> impl Element {
+    let definite_count = plan.edits.len() - plan.possible_count();
+    let summary = match plan.possible_count() {
+        0 => format!("{definite_count} occurrence(s) of '{}'", plan.old_name),
+        possible_count => format!(
+            "{definite_count} occurrence(s) of '{}', {possible_count} possible match(es) to confirm",
+            plan.old_name
+        ),
+    };
+    let lines = plan
+        .edits
+        .iter()
+        .zip(plan.preview(&rename_symbol.files))
+        .map(|(edit, line)| {
+            let possible_toggle = (line.certainty == RenameCertainty::Possible).then(|| {
+                let accepted = rename_symbol
+                    .accepted_possible
+                    .signal_ref({
+                        let key = (edit.file.clone(), edit.range.start);
+                        move |accepted_possible| accepted_possible.contains(&key)
+                    })
+                    .broadcast();
+                Button::new()
+                    .s(Font::new().color_signal(
+                        accepted
+                            .signal()
+                            .map_bool(|| color!("#6df59a"), || color!("rgba(214, 223, 255, 0.6)")),
+                    ))
+                    .label_signal(
+                        accepted
+                            .signal()
+                            .map_bool(|| "[x] possible", || "[ ] possible"),
+                    )
+                    .on_press({
+                        let rename_symbol = rename_symbol.clone();
+                        let edit = edit.clone();
+                        move || rename_symbol.toggle_accepted(&edit)
+                    })
+            });
+            Column::new()
+                .s(Font::new()
+                    .size(12)
+                    .family([FontFamily::new("JetBrains Mono"), FontFamily::Monospace]))
+                .item(
+                    Row::new()
+                        .s(Gap::new().x(8))
+                        .item(
+                            El::new()
+                                .s(Font::new().color(color!("rgba(140, 160, 200, 0.7)")))
+                                .child(format!("{}:{}", line.file, line.line)),
+                        )
+                        .item(possible_toggle),
+                )
+                .item(
+                    El::new()
+                        .s(Font::new().color(color!("#ff8a80")).no_wrap())
+                        .child(format!("- {}", line.before.trim())),
+                )
+                .item(
+                    El::new()
+                        .s(Font::new().color(color!("#6df59a")).no_wrap())
+                        .child(format!("+ {}", line.after.trim())),
+                )
+        })
+        .collect::<Vec<_>>();
+    Column::new()
+        .s(Gap::new().y(8))
+        .item(El::new().child(summary))
+        .item(
+            Column::new()
+                .update_raw_el(|raw_el| raw_el.style("max-height", "280px"))
+                .s(Scrollbars::both())
+                .s(Gap::new().y(8))
+                .items(lines),
+        )
+}
+
+fn rename_symbol_button(label: &'static str, on_press: impl FnMut() + 'static) -> impl Element {
+    let hovered = Mutable::new(false);
+    Button::new()
+        .s(Padding::new().x(12).y(5))
+        .s(RoundedCorners::all(16))
+        .s(Borders::all(
+            Border::new()
+                .color(color!("rgba(108, 162, 255, 0.35)"))
+                .width(1),
+        ))
+        .s(Background::new().color_signal(hovered.signal().map_bool(
+            || color!("rgba(108, 162, 255, 0.15)"),
+            || color!("rgba(108, 162, 255, 0.08)"),
+        )))
+        .label(label)
+        .on_hovered_change(move |is_hovered| hovered.set(is_hovered))
+        .on_press(on_press)
+}
+
 fn force_size_apply_button(
     width_input: Mutable<String>,
     height_input: Mutable<String>,
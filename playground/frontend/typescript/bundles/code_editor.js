@@ -23534,6 +23534,7 @@ var CodeEditorController = class {
 	editor_view = null;
 	on_change_handler = new Compartment();
 	on_cursor_change_handler = new Compartment();
+	on_rename_request_handler = new Compartment();
 	editor_style = new Compartment();
 	init(parent_element) {
 		const state = EditorState.create({ extensions: [
@@ -23549,7 +23550,8 @@ var CodeEditorController = class {
 			keymap.of([indentWithTab]),
 			indentUnit.of("    "),
 			this.on_change_handler.of([]),
-			this.on_cursor_change_handler.of([])
+			this.on_cursor_change_handler.of([]),
+			this.on_rename_request_handler.of([])
 		] });
 		this.editor_view = new EditorView({
 			parent: parent_element,
@@ -23609,6 +23611,16 @@ var CodeEditorController = class {
 		});
 		this.editor_view.dispatch({ effects: this.on_cursor_change_handler.reconfigure(extension) });
 	}
+	on_rename_request(on_rename_request) {
+		const extension = keymap.of([{
+			key: "F2",
+			run: (view) => {
+				on_rename_request(view.state.selection.main.head);
+				return true;
+			}
+		}]);
+		this.editor_view.dispatch({ effects: this.on_rename_request_handler.reconfigure(extension) });
+	}
 };
 
 //#endregion
@@ -12,6 +12,7 @@ export class CodeEditorController {
     editor_view: EditorView | null = null
     on_change_handler = new Compartment
     on_cursor_change_handler = new Compartment
+    on_rename_request_handler = new Compartment
     editor_style = new Compartment
 
     init(parent_element: HTMLElement) {
@@ -31,6 +32,7 @@ export class CodeEditorController {
                 indentUnit.of("    "),
                 this.on_change_handler.of([]),
                 this.on_cursor_change_handler.of([]),
+                this.on_rename_request_handler.of([]),
             ],
         })
         this.editor_view = new EditorView({
@@ -105,4 +107,18 @@ export class CodeEditorController {
             effects: this.on_cursor_change_handler.reconfigure(extension)
         })
     }
+
+    // F2 asks the host to rename the symbol at the cursor (UTF-16 document offset)
+    on_rename_request(on_rename_request: (offset: number) => void) {
+        const extension = keymap.of([{
+            key: "F2",
+            run: view => {
+                on_rename_request(view.state.selection.main.head)
+                return true
+            },
+        }])
+        this.editor_view!.dispatch({
+            effects: this.on_rename_request_handler.reconfigure(extension)
+        })
+    }
 }
@@ -1367,6 +1367,35 @@ function cdpGetConsole(tabId) {
   return cdpConsoleMessages.get(tabId) || [];
 }
 
+// Merge CDP console capture with the playground's explicit log channel
+// (window.__boonLogHistory), which also holds messages logged before the
+// debugger attached. The wasm side prints the same messages as "[LEVEL] text",
+// so CDP copies of channel messages are dropped in favour of the levelled ones.
+async function getConsoleWithLogChannel(tabId) {
+  const cdpMessages = cdpGetConsole(tabId);
+  let channelMessages = [];
+  try {
+    const history = await cdpEvaluate(tabId, 'window.__boonLogHistory || []');
+    if (Array.isArray(history)) channelMessages = history;
+  } catch (e) {
+    // Page not ready or no playground - CDP capture alone is still useful
+  }
+  if (channelMessages.length === 0) return cdpMessages;
+
+  const printedChannelTexts = new Set(
+    channelMessages.map(m => `[${String(m.level).toUpperCase()}] ${m.text}`)
+  );
+  const merged = cdpMessages
+    .filter(m => !printedChannelTexts.has(m.text))
+    .concat(channelMessages.map(m => ({
+      level: m.level,
+      text: m.text,
+      timestamp: typeof m.timestamp === 'number' ? Math.floor(m.timestamp) : null
+    })));
+  merged.sort((a, b) => (a.timestamp || 0) - (b.timestamp || 0));
+  return merged;
+}
+
 // Execute JavaScript via CDP (only when CDP doesn't have equivalent)
 // Includes retry logic for stale sessions
 async function cdpEvaluate(tabId, expression, retryCount = 0) {
@@ -1784,8 +1813,9 @@ async function handleCommand(id, command) {
         }
 
       case 'getConsole':
-        // Use CDP console capture (automatic via Runtime.consoleAPICalled)
-        return { type: 'console', messages: cdpGetConsole(tab.id) };
+        // CDP console capture (automatic via Runtime.consoleAPICalled) plus the
+        // playground log channel; since/level filtering happens in the ws_server
+        return { type: 'console', messages: await getConsoleWithLogChannel(tab.id) };
 
       case 'setupConsole':
         // CDP handles console capture automatically when attached
@@ -2065,6 +2095,28 @@ async function handleCommand(id, command) {
           return { type: 'error', message: e.message };
         }
 
+      case 'setPageVisibility':
+        // Emulate tab visibility: shadow document.hidden/visibilityState and fire visibilitychange.
+        // Overrides live on the document instance, so a page refresh restores real visibility.
+        try {
+          const visible = command.visible;
+          await cdpEvaluate(tab.id, `
+            (function() {
+              const visible = ${JSON.stringify(visible)};
+              Object.defineProperty(document, 'hidden', { configurable: true, get: () => !visible });
+              Object.defineProperty(document, 'visibilityState', {
+                configurable: true,
+                get: () => (visible ? 'visible' : 'hidden')
+              });
+              document.dispatchEvent(new Event('visibilitychange'));
+              return true;
+            })()
+          `);
+          return { type: 'success', data: { visible } };
+        } catch (e) {
+          return { type: 'error', message: `Set page visibility failed: ${e.message}` };
+        }
+
       case 'clearStates':
         // Click the "Clear saved states" button using trusted pointer events.
         try {
@@ -3544,6 +3596,95 @@ async function handleCommand(id, command) {
           return { type: 'error', message: `Double-click by text failed: ${e.message}` };
         }
 
+      case 'rightClickByText':
+        // Right-click an element by its text content.
+        // Dispatches mousedown/mouseup/contextmenu with button 2 directly on the element.
+        // `handled` reports whether Boon routed the event (and so suppressed the native menu).
+        try {
+          const searchText = command.text;
+          const exact = command.exact || false;
+
+          const result = await cdpEvaluate(tab.id, `
+            (function() {
+              const searchText = ${JSON.stringify(searchText)};
+              const exact = ${exact};
+              const preview = document.querySelector('[data-boon-panel="preview"]');
+              if (!preview) return { found: false, error: 'Preview panel not found' };
+
+              let bestMatchElement = null;
+              let bestMatchText = '';
+              let bestMatchSize = Infinity;
+
+              preview.querySelectorAll('*').forEach((el) => {
+                const rect = el.getBoundingClientRect();
+                if (rect.width === 0 || rect.height === 0) return;
+
+                const style = window.getComputedStyle(el);
+                if (style.display === 'none' || style.visibility === 'hidden') return;
+
+                let directText = '';
+                for (const node of el.childNodes) {
+                  if (node.nodeType === Node.TEXT_NODE) {
+                    directText += node.textContent;
+                  }
+                }
+                directText = directText.trim();
+
+                const matches = exact ? directText === searchText : directText.includes(searchText);
+                const size = rect.width * rect.height;
+                if (matches && size < bestMatchSize) {
+                  bestMatchSize = size;
+                  bestMatchElement = el;
+                  bestMatchText = directText;
+                }
+              });
+
+              if (!bestMatchElement) {
+                return { found: false, error: 'No element found with text: ' + searchText };
+              }
+
+              const rect = bestMatchElement.getBoundingClientRect();
+              const centerX = rect.x + rect.width / 2;
+              const centerY = rect.y + rect.height / 2;
+
+              const makeMouseEvent = (type, buttons) => new MouseEvent(type, {
+                bubbles: true,
+                cancelable: true,
+                composed: true,
+                view: window,
+                clientX: centerX,
+                clientY: centerY,
+                screenX: centerX,
+                screenY: centerY,
+                button: 2,
+                buttons
+              });
+
+              bestMatchElement.dispatchEvent(makeMouseEvent('mousedown', 2));
+              bestMatchElement.dispatchEvent(makeMouseEvent('mouseup', 0));
+              const contextMenuEvent = makeMouseEvent('contextmenu', 0);
+              bestMatchElement.dispatchEvent(contextMenuEvent);
+
+              return {
+                found: true,
+                text: bestMatchText,
+                x: Math.round(centerX),
+                y: Math.round(centerY),
+                tagName: bestMatchElement.tagName,
+                handled: contextMenuEvent.defaultPrevented
+              };
+            })()
+          `);
+
+          if (!result.found) {
+            return { type: 'error', message: result.error || 'Element not found' };
+          }
+
+          return { type: 'success', data: { text: result.text, x: result.x, y: result.y, tagName: result.tagName, handled: result.handled, method: 'dom' } };
+        } catch (e) {
+          return { type: 'error', message: `Right-click by text failed: ${e.message}` };
+        }
+
       case 'hoverByText':
         // Hover over an element by its text content
         // Uses scrollIntoView + CDP hover for real pointer events (Zoon needs real mouse events)
@@ -12,10 +12,15 @@ use boon_engine_actors_lite::todo_acceptance::{
     TodoAcceptanceAction, TodoAcceptanceSequence, todo_edit_save_acceptance_sequences,
 };
 use serde::Deserialize;
-use std::path::Path;
+use std::fmt;
+use std::path::{Path, PathBuf};
 
 /// Parsed .expected file specification
+///
+/// Every table rejects unknown keys, so a misspelled key fails the file instead of
+/// being ignored (see [`validate_expected_file`]).
 #[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ExpectedSpec {
     /// Test metadata
     #[serde(default)]
@@ -40,6 +45,7 @@ pub struct ExpectedSpec {
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
 #[allow(dead_code)]
 pub struct TestMeta {
     /// Category: static, interactive, timer
@@ -61,9 +67,15 @@ pub struct TestMeta {
     /// Skip on these engines (e.g., ["Wasm"] for the WebAssembly backend)
     #[serde(default)]
     pub skip_engines: Option<Vec<String>>,
+
+    /// Warning categories this example is allowed to emit (e.g., ["restore"]);
+    /// any other warning reported by `boonPlayground.getWarnings()` fails the test
+    #[serde(default)]
+    pub expected_warnings: Vec<String>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct OutputSpec {
     /// Match mode: contains (default), exact, regex
     #[serde(default)]
@@ -88,6 +100,7 @@ pub enum MatchMode {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct InteractionSequence {
     /// Description of this step
     #[serde(default)]
@@ -106,6 +119,60 @@ pub struct InteractionSequence {
     pub expect_match: MatchMode,
 }
 
+/// Every action name [`Action::parse`] accepts, in the order of its match arms.
+pub const ACTION_NAMES: &[&str] = &[
+    "click",
+    "type",
+    "wait",
+    "clear_states",
+    "record_renders",
+    "assert_renders",
+    "set_visibility",
+    "run",
+    "refresh",
+    "key",
+    "focus_input",
+    "click_text",
+    "click_button",
+    "click_button_near_text",
+    "click_checkbox",
+    "click_at",
+    "dblclick_text",
+    "right_click_text",
+    "dblclick_text_nth",
+    "dblclick_at",
+    "dblclick_cells_cell",
+    "assert_cells_cell_text",
+    "assert_cells_row_visible",
+    "assert_preview_direct_text_visible",
+    "hover_text",
+    "assert_focused",
+    "assert_focused_input_value",
+    "assert_input_placeholder",
+    "assert_url",
+    "assert_input_typeable",
+    "assert_input_not_typeable",
+    "assert_button_disabled",
+    "assert_button_enabled",
+    "assert_button_count",
+    "assert_checkbox_count",
+    "assert_not_contains",
+    "assert_not_focused",
+    "assert_checkbox_unchecked",
+    "assert_checkbox_checked",
+    "assert_button_has_outline",
+    "assert_toggle_all_darker",
+    "assert_input_empty",
+    "assert_contains",
+    "assert_checkbox_clickable",
+    "assert_element_style",
+    "assert_input_value",
+    "set_slider_value",
+    "select_option",
+    "set_input_value",
+    "set_focused_input_value",
+];
+
 /// Action to perform in an interaction sequence
 #[derive(Debug, Clone, Deserialize)]
 #[serde(untagged)]
@@ -168,7 +235,25 @@ impl Action {
                         Ok(ParsedAction::Wait { ms })
                     }
                     "clear_states" => Ok(ParsedAction::ClearStates),
+                    "record_renders" => Ok(ParsedAction::RecordRenders),
+                    "assert_renders" => {
+                        let max_per_event = arr
+                            .get(1)
+                            .and_then(|v| v.as_u64())
+                            .context("assert_renders requires the allowed renders per event")?;
+                        let max_per_event = usize::try_from(max_per_event)
+                            .context("assert_renders limit does not fit in usize")?;
+                        Ok(ParsedAction::AssertRenders { max_per_event })
+                    }
+                    "set_visibility" => {
+                        let visible = arr
+                            .get(1)
+                            .and_then(|v| v.as_bool())
+                            .context("set_visibility requires true (visible) or false (hidden)")?;
+                        Ok(ParsedAction::SetVisibility { visible })
+                    }
                     "run" => Ok(ParsedAction::Run),
+                    "refresh" => Ok(ParsedAction::Refresh),
                     "key" => {
                         let key = arr
                             .get(1)
@@ -247,6 +332,14 @@ impl Action {
                             .to_string();
                         Ok(ParsedAction::DblClickText { text })
                     }
+                    "right_click_text" => {
+                        let text = arr
+                            .get(1)
+                            .and_then(|v| v.as_str())
+                            .context("right_click_text requires text to right-click")?
+                            .to_string();
+                        Ok(ParsedAction::RightClickText { text })
+                    }
                     "dblclick_text_nth" => {
                         let text = arr
                             .get(1)
@@ -585,7 +678,15 @@ impl Action {
                             .to_string();
                         Ok(ParsedAction::SetFocusedInputValue { value })
                     }
-                    _ => anyhow::bail!("Unknown action type: {}", cmd),
+                    _ => anyhow::bail!(
+                        "unknown action `{}`, expected one of {}",
+                        cmd,
+                        ACTION_NAMES
+                            .iter()
+                            .map(|name| format!("`{name}`"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
                 }
             }
         }
@@ -608,7 +709,15 @@ pub enum ParsedAction {
         ms: u64,
     },
     ClearStates,
-    Run, // Trigger code execution
+    SetVisibility {
+        visible: bool,
+    }, // Emulate switching away from / back to the tab
+    RecordRenders, // Start tracing preview DOM events and render flushes
+    AssertRenders {
+        max_per_event: usize,
+    }, // Stop tracing; fail if a DOM event caused more render flushes than allowed
+    Run,           // Trigger code execution
+    Refresh,       // Reload the playground page (keeps URL and localStorage)
     Key {
         key: String,
     },
@@ -639,6 +748,9 @@ pub enum ParsedAction {
         text: String,
         index: usize,
     }, // Double-click nth exact match by text
+    RightClickText {
+        text: String,
+    }, // Right-click (context menu) by text content
     DblClickAt {
         x: i32,
         y: i32,
@@ -956,6 +1068,7 @@ pub fn validate_required_shared_sequences(
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct TimingConfig {
     /// Maximum time to wait for output stabilization (ms)
     #[serde(default = "default_timeout")]
@@ -1012,6 +1125,88 @@ impl ExpectedSpec {
     }
 }
 
+/// Problem found in an .expected file before any test runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpectedFileError {
+    pub path: PathBuf,
+    /// 1-based line of the offending key or action, when known
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for ExpectedFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{}: {}", self.path.display(), line, self.message),
+            None => write!(f, "{}: {}", self.path.display(), self.message),
+        }
+    }
+}
+
+/// Only the actions of each sequence, with their position in the file.
+#[derive(Deserialize)]
+struct SpannedSequences {
+    #[serde(default)]
+    sequence: Vec<SpannedActions>,
+    #[serde(default)]
+    persistence: Vec<SpannedActions>,
+}
+
+#[derive(Deserialize)]
+struct SpannedActions {
+    #[serde(default)]
+    actions: Vec<toml::Spanned<Action>>,
+}
+
+fn line_at(content: &str, offset: usize) -> usize {
+    content[..offset.min(content.len())].matches('\n').count() + 1
+}
+
+/// Checks `content` against the strict schema and every action against
+/// [`ACTION_NAMES`] and its arguments.
+///
+/// Schema errors stop at the first one; action errors are all collected.
+pub fn validate_expected_str(path: &Path, content: &str) -> Vec<ExpectedFileError> {
+    let error = |line: Option<usize>, message: String| ExpectedFileError {
+        path: path.to_path_buf(),
+        line,
+        message,
+    };
+    if let Err(schema_error) = toml::from_str::<ExpectedSpec>(content) {
+        let line = schema_error.span().map(|span| line_at(content, span.start));
+        return vec![error(line, schema_error.message().trim().to_string())];
+    }
+    let sequences = match toml::from_str::<SpannedSequences>(content) {
+        Ok(sequences) => sequences,
+        Err(schema_error) => return vec![error(None, schema_error.message().trim().to_string())],
+    };
+    sequences
+        .sequence
+        .iter()
+        .chain(&sequences.persistence)
+        .flat_map(|sequence| &sequence.actions)
+        .filter_map(|action| {
+            let parse_error = action.get_ref().parse().err()?;
+            Some(error(
+                Some(line_at(content, action.span().start)),
+                format!("{parse_error:#}"),
+            ))
+        })
+        .collect()
+}
+
+/// Reads and checks one .expected file, see [`validate_expected_str`].
+pub fn validate_expected_file(path: &Path) -> Vec<ExpectedFileError> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => validate_expected_str(path, &content),
+        Err(read_error) => vec![ExpectedFileError {
+            path: path.to_path_buf(),
+            line: None,
+            message: format!("Failed to read: {read_error}"),
+        }],
+    }
+}
+
 impl OutputSpec {
     pub fn is_configured(&self) -> bool {
         self.text.is_some() || self.pattern.is_some()
@@ -1068,7 +1263,6 @@ pub fn matches_inline(text: &str, expected: &str, mode: &MatchMode) -> Result<bo
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::path::PathBuf;
 
     fn assert_expected_file_matches_shared_cells_sequences(
         path: &str,
@@ -1161,6 +1355,90 @@ expect = "1"
         assert_eq!(spec.sequence[0].expect, Some("1".to_string()));
     }
 
+    fn fixture_errors(name: &str) -> Vec<(Option<usize>, String)> {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/expected")
+            .join(name);
+        validate_expected_file(&path)
+            .into_iter()
+            .map(|error| (error.line, error.message))
+            .collect()
+    }
+
+    #[test]
+    fn every_listed_action_name_is_parsed() {
+        for name in ACTION_NAMES {
+            let action = Action::Array(vec![serde_json::Value::from(*name)]);
+            if let Err(error) = action.parse() {
+                assert!(
+                    !error.to_string().starts_with("unknown action"),
+                    "`{name}` is listed but not parsed"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn misspelled_actions_are_all_reported_with_their_lines() {
+        let errors = fixture_errors("misspelled_action.expected");
+        assert_eq!(
+            errors.iter().map(|(line, _)| *line).collect::<Vec<_>>(),
+            vec![Some(8), Some(15)]
+        );
+        assert!(
+            errors[0].1.starts_with(
+                "unknown action `clik_button`, expected one of `click`, `type`, `wait`,"
+            )
+        );
+        assert!(errors[1].1.starts_with("unknown action `asert_contains`,"));
+        assert!(errors[1].1.contains("`assert_contains`"));
+    }
+
+    #[test]
+    fn misspelled_keys_are_rejected_with_the_allowed_keys() {
+        assert_eq!(
+            fixture_errors("misspelled_sequence_key.expected"),
+            vec![(
+                Some(9),
+                "unknown field `expct`, expected one of `description`, `actions`, `expect`, `expect_match`"
+                    .to_string()
+            )]
+        );
+        assert_eq!(
+            fixture_errors("misspelled_table.expected"),
+            vec![(
+                Some(6),
+                "unknown field `sequnce`, expected one of `test`, `output`, `sequence`, `persistence`, `timing`"
+                    .to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn missing_action_arguments_are_reported() {
+        assert_eq!(
+            fixture_errors("missing_argument.expected"),
+            vec![(
+                Some(8),
+                "click_button requires index (0-indexed)".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn bundled_expected_files_are_valid() {
+        let examples_dir =
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../playground/frontend/src/examples");
+        let examples = crate::commands::test_examples::discover_examples(&examples_dir)
+            .expect("examples are discovered");
+        let errors = examples
+            .iter()
+            .flat_map(|example| validate_expected_file(&example.expected_path))
+            .map(|error| error.to_string())
+            .collect::<Vec<_>>();
+        assert!(errors.is_empty(), "{}", errors.join("\n"));
+    }
+
     #[test]
     fn counter_expected_matches_shared_acceptance_sequences() {
         let spec = ExpectedSpec::from_file(&PathBuf::from(
@@ -12,8 +12,9 @@ use crate::ws_server::{
 };
 
 use super::expected::{
-    ExpectedSpec, MatchMode, ParsedAction, matches_inline, parse_interaction_sequences,
-    shared_example_parsed_sequences, validate_required_shared_sequences,
+    ExpectedFileError, ExpectedSpec, MatchMode, ParsedAction, matches_inline,
+    parse_interaction_sequences, shared_example_parsed_sequences, validate_expected_file,
+    validate_required_shared_sequences,
 };
 
 /// Options for test-examples command
@@ -958,6 +959,36 @@ pub fn discover_examples(examples_dir: &Path) -> Result<Vec<DiscoveredExample>>
     Ok(examples)
 }
 
+/// Every schema and action problem in the .expected files of `examples`.
+pub fn expected_file_errors(examples: &[DiscoveredExample]) -> Vec<ExpectedFileError> {
+    examples
+        .iter()
+        .flat_map(|example| validate_expected_file(&example.expected_path))
+        .collect()
+}
+
+/// Checks every .expected file without a browser (`exec validate-expected`).
+pub fn run_expected_validation(examples_dir: Option<PathBuf>) -> Result<bool> {
+    let examples_dir = if let Some(dir) = examples_dir {
+        dir
+    } else {
+        find_examples_dir()?
+    };
+    let examples = discover_examples(&examples_dir)?;
+    let errors = expected_file_errors(&examples);
+
+    for error in &errors {
+        println!("  {}", error);
+    }
+    if errors.is_empty() {
+        println!("{} .expected file(s) valid", examples.len());
+    } else {
+        println!("\n{} problem(s) in .expected files", errors.len());
+    }
+
+    Ok(errors.is_empty())
+}
+
 /// Find the extension directory relative to the boon-tools binary
 fn find_extension_dir() -> Option<PathBuf> {
     // Try relative to current exe (for installed binary)
@@ -1248,7 +1279,13 @@ async fn ensure_browser_connection(
             // Start WebSocket server in background
             let watch_path = extension_dir.clone();
             tokio::spawn(async move {
-                if let Err(e) = ws_server::start_server(port, watch_path.as_deref()).await {
+                if let Err(e) = ws_server::start_server(
+                    port,
+                    watch_path.as_deref(),
+                    ws_server::console_history::DEFAULT_CONSOLE_BUFFER_SIZE,
+                )
+                .await
+                {
                     // Only log if it's not "address in use" (another server already running)
                     if !e.to_string().contains("address in use") && !e.to_string().contains("bind")
                     {
@@ -1636,6 +1673,20 @@ async fn run_tests_inner(opts: &TestOptions) -> Result<Vec<TestResult>> {
         return Ok(vec![]);
     }
 
+    // A misspelled key or action would otherwise skip part of a test and still pass,
+    // so every file is checked before the first one runs.
+    let malformed = expected_file_errors(&examples);
+    if !malformed.is_empty() {
+        anyhow::bail!(
+            "Malformed .expected files:\n{}",
+            malformed
+                .iter()
+                .map(|error| format!("  {}", error))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+
     // Apply filter
     if let Some(ref filter) = opts.filter {
         let prefer_exact = examples.iter().any(|example| example.name == *filter);
@@ -2252,18 +2303,78 @@ async fn run_single_test(example: &DiscoveredExample, opts: &TestOptions) -> Res
         }
     }
 
+    let unexpected_warnings =
+        get_unexpected_warnings(opts.port, &spec.test.expected_warnings).await?;
+    let error = if unexpected_warnings.is_empty() {
+        None
+    } else {
+        all_passed = false;
+        Some(format!(
+            "Unexpected engine warnings: {}",
+            unexpected_warnings.join("; ")
+        ))
+    };
+
     Ok(TestResult {
         name: example.name.clone(),
         passed: all_passed,
         skipped: None,
         duration: start.elapsed(),
-        error: None,
+        error,
         actual_output: Some(actual_output),
         expected_output: spec.output.text.clone(),
         steps,
     })
 }
 
+/// Warnings retained by the playground whose category the example does not expect.
+///
+/// Only `warn` and `error` levels count. Pages without `getWarnings()` report none.
+async fn get_unexpected_warnings(port: u16, expected_categories: &[String]) -> Result<Vec<String>> {
+    let response = send_command_with_timeout(
+        port,
+        WsCommand::EvalJs {
+            expression: r#"(function() {
+                if (!window.boonPlayground || typeof window.boonPlayground.getWarnings !== 'function') {
+                    return [];
+                }
+                return window.boonPlayground.getWarnings();
+            })()"#
+                .to_string(),
+        },
+        Duration::from_secs(2),
+    )
+    .await?;
+
+    let warnings = match response {
+        WsResponse::Success {
+            data: Some(serde_json::Value::Array(warnings)),
+        } => warnings,
+        WsResponse::Error { message } => anyhow::bail!("Eval warnings failed: {}", message),
+        _ => return Ok(vec![]),
+    };
+
+    Ok(warnings
+        .iter()
+        .filter(|warning| matches!(warning["level"].as_str(), Some("warn" | "error")))
+        .filter(|warning| {
+            let category = warning["category"].as_str().unwrap_or_default();
+            !expected_categories
+                .iter()
+                .any(|expected| expected == category)
+        })
+        .map(|warning| {
+            format!(
+                "run #{} [{}] {}: {}",
+                warning["run"],
+                warning["level"].as_str().unwrap_or_default(),
+                warning["category"].as_str().unwrap_or_default(),
+                warning["message"].as_str().unwrap_or_default()
+            )
+        })
+        .collect())
+}
+
 enum WaitError {
     Timeout { actual: String },
     Other(anyhow::Error),
@@ -3646,6 +3757,105 @@ async fn set_input_value_by_index(port: u16, index: u32, value: &str) -> Result<
     Ok(())
 }
 
+/// Starts the in-page render trace read back by [`TAKE_RENDER_TRACE_JS`].
+///
+/// Ordering contract: one DOM event reaches the engine as one input, the engine settles
+/// in one step and the preview is flushed once. The trace records user-facing DOM
+/// events (capture phase, so before any handler runs) and every animation frame whose
+/// preview DOM differs from the previous frame. A frame is attributed to the last event
+/// seen before its first mutation, so a hover flush is not counted against the click
+/// that follows it.
+const RECORD_RENDERS_JS: &str = r#"(function() {
+    const root = document.querySelector('[data-boon-panel="preview"]');
+    if (!root) return { error: 'preview root not found' };
+    if (window.__boonRenderTrace) window.__boonRenderTrace.stop();
+    const eventTypes = ['pointerover', 'pointerdown', 'pointerup', 'click', 'dblclick',
+        'contextmenu', 'keydown', 'input', 'focusin', 'focusout'];
+    const trace = { entries: [], lastHtml: root.innerHTML, pending: null };
+    const flush = () => {
+        const entry = trace.pending;
+        trace.pending = null;
+        const html = root.innerHTML;
+        if (html === trace.lastHtml) return;
+        trace.lastHtml = html;
+        entry.text = (root.innerText || '').replace(/\s+/g, ' ').trim().slice(0, 120);
+        trace.entries.splice(entry.position, 0, { kind: 'render', text: entry.text });
+    };
+    const observer = new MutationObserver(() => {
+        if (trace.pending) return;
+        trace.pending = { position: trace.entries.length, text: null };
+        requestAnimationFrame(flush);
+    });
+    observer.observe(root, { subtree: true, childList: true, characterData: true, attributes: true });
+    const onEvent = (event) => trace.entries.push({ kind: 'event', type: event.type });
+    eventTypes.forEach((type) => root.addEventListener(type, onEvent, true));
+    trace.stop = () => {
+        observer.disconnect();
+        eventTypes.forEach((type) => root.removeEventListener(type, onEvent, true));
+    };
+    window.__boonRenderTrace = trace;
+    return { recording: true };
+})()"#;
+
+/// Stops the trace started by [`RECORD_RENDERS_JS`] and returns its entries.
+const TAKE_RENDER_TRACE_JS: &str = r#"(function() {
+    const trace = window.__boonRenderTrace;
+    if (!trace) return { error: 'record_renders was not called before assert_renders' };
+    trace.stop();
+    window.__boonRenderTrace = null;
+    return { entries: trace.entries };
+})()"#;
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum RenderTraceEntry {
+    Event {
+        #[serde(rename = "type")]
+        event_type: String,
+    },
+    /// Preview flush, with the visible text after it.
+    Render { text: String },
+}
+
+/// Events that caused more than `max_per_event` render flushes, one line each.
+///
+/// Renders before the first event come from the previous action and are not counted.
+fn render_budget_violations(trace: &[RenderTraceEntry], max_per_event: usize) -> Vec<String> {
+    let mut violations = Vec::new();
+    let mut current: Option<(&str, Vec<&str>)> = None;
+    let mut report = |current: Option<(&str, Vec<&str>)>| {
+        let Some((event_type, renders)) = current else {
+            return;
+        };
+        if renders.len() > max_per_event {
+            violations.push(format!(
+                "  {} -> {} renders: {}",
+                event_type,
+                renders.len(),
+                renders
+                    .iter()
+                    .map(|text| format!("{text:?}"))
+                    .collect::<Vec<_>>()
+                    .join(" then ")
+            ));
+        }
+    };
+    for entry in trace {
+        match entry {
+            RenderTraceEntry::Event { event_type } => {
+                report(current.replace((event_type.as_str(), Vec::new())));
+            }
+            RenderTraceEntry::Render { text } => {
+                if let Some((_, renders)) = current.as_mut() {
+                    renders.push(text.as_str());
+                }
+            }
+        }
+    }
+    report(current);
+    violations
+}
+
 async fn get_preview_stability_snapshot(port: u16) -> Result<(String, Option<String>)> {
     let preview = get_preview(port).await?;
     let elements_signature = match send_command_to_server(port, WsCommand::GetPreviewElements).await
@@ -3801,6 +4011,60 @@ async fn execute_action(
             let _ = send_command_to_server(port, WsCommand::ClearStates).await?;
             tokio::time::sleep(Duration::from_millis(100)).await;
         }
+        ParsedAction::RecordRenders => {
+            let response = send_command_to_server(
+                port,
+                WsCommand::EvalJs {
+                    expression: RECORD_RENDERS_JS.to_string(),
+                },
+            )
+            .await?;
+            if let WsResponse::Error { message } = response {
+                anyhow::bail!("Record renders failed: {}", message);
+            }
+        }
+        ParsedAction::AssertRenders { max_per_event } => {
+            // Let the last action's frames land before the recorder stops
+            wait_for_preview_to_settle(port).await;
+            let response = send_command_to_server(
+                port,
+                WsCommand::EvalJs {
+                    expression: TAKE_RENDER_TRACE_JS.to_string(),
+                },
+            )
+            .await?;
+            let data = match response {
+                WsResponse::Success { data: Some(data) } => data,
+                WsResponse::Error { message } => {
+                    anyhow::bail!("Assert renders failed: {}", message);
+                }
+                other => anyhow::bail!("Assert renders failed: unexpected response {:?}", other),
+            };
+            if let Some(error) = data.get("error").and_then(|v| v.as_str()) {
+                anyhow::bail!("Assert renders failed: {}", error);
+            }
+            let trace: Vec<RenderTraceEntry> =
+                serde_json::from_value(data.get("entries").cloned().unwrap_or_default())
+                    .context("Assert renders failed: malformed render trace")?;
+            let violations = render_budget_violations(&trace, *max_per_event);
+            if !violations.is_empty() {
+                anyhow::bail!(
+                    "Extra intermediate render (allowed {} per event):\n{}",
+                    max_per_event,
+                    violations.join("\n")
+                );
+            }
+        }
+        ParsedAction::SetVisibility { visible } => {
+            let response =
+                send_command_to_server(port, WsCommand::SetPageVisibility { visible: *visible })
+                    .await?;
+            if let WsResponse::Error { message } = response {
+                anyhow::bail!("Set visibility failed: {}", message);
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            wait_for_preview_to_settle(port).await;
+        }
         ParsedAction::Run => {
             let response = send_command_to_server(port, WsCommand::TriggerRun).await?;
             if let WsResponse::Error { message } = response {
@@ -3808,6 +4072,15 @@ async fn execute_action(
             }
             tokio::time::sleep(Duration::from_millis(200)).await;
         }
+        ParsedAction::Refresh => {
+            let response = send_command_to_server(port, WsCommand::Refresh).await?;
+            if let WsResponse::Error { message } = response {
+                anyhow::bail!("Refresh failed: {}", message);
+            }
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            wait_for_playground_api_ready(port, Duration::from_secs(10)).await?;
+            wait_for_preview_to_settle(port).await;
+        }
         ParsedAction::Key { key } => {
             let before_snapshot = get_preview_stability_snapshot(port).await.ok();
             let response =
@@ -4084,6 +4357,21 @@ async fn execute_action(
             }
             tokio::time::sleep(Duration::from_millis(100)).await;
         }
+        ParsedAction::RightClickText { text } => {
+            let response = send_command_to_server(
+                port,
+                WsCommand::RightClickByText {
+                    text: text.clone(),
+                    exact: false,
+                },
+            )
+            .await?;
+            if let WsResponse::Error { message } = response {
+                anyhow::bail!("Right-click text failed: {}", message);
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            wait_for_preview_to_settle(port).await;
+        }
         ParsedAction::DblClickTextNth { text, index } => {
             let js = format!(
                 r#"(function() {{
@@ -5689,7 +5977,14 @@ async fn get_preview(port: u16) -> Result<String> {
 }
 
 async fn get_console(port: u16) -> Result<Vec<String>> {
-    let response = send_command_to_server(port, WsCommand::GetConsole).await?;
+    let response = send_command_to_server(
+        port,
+        WsCommand::GetConsole {
+            since: None,
+            level: None,
+        },
+    )
+    .await?;
     match response {
         WsResponse::Console { messages } => Ok(messages
             .into_iter()
@@ -5785,6 +6080,11 @@ async fn run_smoke_inner(opts: &SmokeOptions) -> Result<Vec<TestResult>> {
 
     for name in &examples {
         let start = Instant::now();
+        // The server keeps console history across examples; only look at this one's
+        let console_since = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .ok()
+            .and_then(|elapsed| u64::try_from(elapsed.as_millis()).ok());
 
         // Select example
         let select_response = send_command_to_server(
@@ -5818,7 +6118,14 @@ async fn run_smoke_inner(opts: &SmokeOptions) -> Result<Vec<TestResult>> {
         tokio::time::sleep(Duration::from_millis(1500)).await;
 
         // Check console for panics/errors
-        let console_response = send_command_to_server(opts.port, WsCommand::GetConsole).await?;
+        let console_response = send_command_to_server(
+            opts.port,
+            WsCommand::GetConsole {
+                since: console_since,
+                level: Some("error".to_string()),
+            },
+        )
+        .await?;
         let has_panic = match &console_response {
             WsResponse::Console { messages } => messages.iter().any(|m| {
                 m.level == "error"
@@ -5884,3 +6191,55 @@ fn find_examples_dir() -> Result<PathBuf> {
 
     anyhow::bail!("Could not find examples directory. Run from project root or use --examples-dir")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(event_type: &str) -> RenderTraceEntry {
+        RenderTraceEntry::Event {
+            event_type: event_type.to_string(),
+        }
+    }
+
+    fn render(text: &str) -> RenderTraceEntry {
+        RenderTraceEntry::Render {
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn hover_flush_is_not_counted_against_the_click() {
+        let trace = [
+            render("left over from the previous action"),
+            event("pointerover"),
+            render("1 item left ×"),
+            event("click"),
+            render("0 items left ×"),
+        ];
+        assert!(render_budget_violations(&trace, 1).is_empty());
+    }
+
+    #[test]
+    fn intermediate_render_after_one_event_is_reported() {
+        let trace = [
+            event("click"),
+            render("1 item left"),
+            render("0 items left"),
+            event("keydown"),
+        ];
+        assert_eq!(
+            render_budget_violations(&trace, 1),
+            vec![r#"  click -> 2 renders: "1 item left" then "0 items left""#.to_string()]
+        );
+    }
+
+    #[test]
+    fn render_trace_parses_from_page_json() {
+        let trace: Vec<RenderTraceEntry> = serde_json::from_str(
+            r#"[{"kind":"event","type":"click"},{"kind":"render","text":"0 items left"}]"#,
+        )
+        .unwrap();
+        assert_eq!(render_budget_violations(&trace, 0).len(), 1);
+    }
+}
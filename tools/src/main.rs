@@ -163,6 +163,10 @@ enum ServerAction {
         /// Disable file watching for extension hot reload
         #[arg(long)]
         no_watch: bool,
+
+        /// Number of console messages kept in the server's history ring
+        #[arg(long, default_value_t = ws_server::console_history::DEFAULT_CONSOLE_BUFFER_SIZE)]
+        console_buffer_size: usize,
     },
 }
 
@@ -363,7 +367,15 @@ enum ExecAction {
     Status,
 
     /// Get console messages from browser
-    Console,
+    Console {
+        /// Only messages newer than this timestamp (ms since Unix epoch)
+        #[arg(long)]
+        since: Option<u64>,
+
+        /// Minimum level: debug, log, info, warn, error
+        #[arg(short, long)]
+        level: Option<String>,
+    },
 
     /// Scroll the preview panel
     Scroll {
@@ -468,9 +480,25 @@ enum ExecAction {
         exact: bool,
     },
 
+    /// Right-click element containing specific text in the preview panel (fires `contextmenu`)
+    RightClickText {
+        /// Text to find and right-click
+        text: String,
+        /// Match exact text (default: contains match)
+        #[arg(long)]
+        exact: bool,
+    },
+
     /// Clear saved states (reset localStorage for tests)
     ClearStates,
 
+    /// Emulate the page being hidden (tab switched away) or visible again
+    SetVisibility {
+        /// `true` for visible, `false` for hidden
+        #[arg(action = clap::ArgAction::Set)]
+        visible: bool,
+    },
+
     /// Select an example by name (e.g., "todo_mvc.bn")
     Select {
         /// Example name (e.g., "todo_mvc.bn" or "todo_mvc")
@@ -537,6 +565,13 @@ enum ExecAction {
         examples_dir: Option<PathBuf>,
     },
 
+    /// Check every .expected file against the strict schema (no browser needed)
+    ValidateExpected {
+        /// Path to examples directory (default: auto-detect)
+        #[arg(long)]
+        examples_dir: Option<PathBuf>,
+    },
+
     /// Get the currently selected engine and the normal user-facing engine list
     GetEngine,
 
@@ -640,6 +675,7 @@ fn main() -> Result<()> {
                 port,
                 watch,
                 no_watch,
+                console_buffer_size,
             } => {
                 let ws_port = port.unwrap_or(ports.ws_port);
                 let rt = tokio::runtime::Runtime::new()?;
@@ -660,7 +696,11 @@ fn main() -> Result<()> {
                     println!("Hot-reload disabled (use --watch to specify directory)");
                 }
 
-                rt.block_on(ws_server::start_server(ws_port, watch_path.as_deref()))?;
+                rt.block_on(ws_server::start_server(
+                    ws_port,
+                    watch_path.as_deref(),
+                    console_buffer_size,
+                ))?;
             }
         },
 
@@ -1015,13 +1055,20 @@ async fn handle_exec(action: ExecAction, port: u16, playground_port: u16) -> Res
             print_response(response);
         }
 
+        ExecAction::RightClickText { text, exact } => {
+            let response =
+                send_command_to_server(port, WsCommand::RightClickByText { text, exact }).await?;
+            print_response(response);
+        }
+
         ExecAction::Status => {
             let response = send_command_to_server(port, WsCommand::GetStatus).await?;
             print_response(response);
         }
 
-        ExecAction::Console => {
-            let response = send_command_to_server(port, WsCommand::GetConsole).await?;
+        ExecAction::Console { since, level } => {
+            let response =
+                send_command_to_server(port, WsCommand::GetConsole { since, level }).await?;
             match response {
                 WsResponse::Console { messages } => {
                     if messages.is_empty() {
@@ -1267,6 +1314,12 @@ async fn handle_exec(action: ExecAction, port: u16, playground_port: u16) -> Res
             print_response(response);
         }
 
+        ExecAction::SetVisibility { visible } => {
+            let response =
+                send_command_to_server(port, WsCommand::SetPageVisibility { visible }).await?;
+            print_response(response);
+        }
+
         ExecAction::Select { name } => {
             // Add .bn suffix if not present
             let example_name = if name.ends_with(".bn") {
@@ -1387,6 +1440,15 @@ async fn handle_exec(action: ExecAction, port: u16, playground_port: u16) -> Res
             }
         }
 
+        ExecAction::ValidateExpected { examples_dir } => {
+            use commands::test_examples::run_expected_validation;
+
+            let valid = run_expected_validation(examples_dir)?;
+            if !valid {
+                std::process::exit(1);
+            }
+        }
+
         ExecAction::GetEngine => {
             let response = send_command_to_server(port, WsCommand::GetEngine).await?;
             match response {
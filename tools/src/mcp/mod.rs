@@ -106,7 +106,13 @@ pub async fn run_mcp_server(ws_port: u16, playground_port: u16) {
     let watch_path = extension_dir.clone();
     tokio::spawn(async move {
         eprintln!("[MCP] Starting WebSocket server on port {}...", ws_port);
-        if let Err(e) = ws_server::start_server(ws_port, watch_path.as_deref()).await {
+        if let Err(e) = ws_server::start_server(
+            ws_port,
+            watch_path.as_deref(),
+            ws_server::console_history::DEFAULT_CONSOLE_BUFFER_SIZE,
+        )
+        .await
+        {
             eprintln!("[MCP] WebSocket server error: {}", e);
         }
     });
@@ -1078,7 +1084,10 @@ async fn call_tool(
 
 async fn call_ws_tool(name: &str, args: Value, ws_port: u16) -> Result<String, String> {
     let command = match name {
-        "boon_console" => Command::GetConsole,
+        "boon_console" => Command::GetConsole {
+            since: None,
+            level: None,
+        },
         "boon_preview" => Command::GetPreviewText,
         "boon_refresh" => Command::Refresh,
         "boon_status" => Command::GetStatus,
@@ -1644,9 +1653,15 @@ async fn get_filtered_console(
     level_filter: &str,
     pattern: Option<&str>,
 ) -> Result<String, String> {
-    let response = ws_server::send_command_to_server(ws_port, Command::GetConsole)
-        .await
-        .map_err(|e| e.to_string())?;
+    let response = ws_server::send_command_to_server(
+        ws_port,
+        Command::GetConsole {
+            since: None,
+            level: None,
+        },
+    )
+    .await
+    .map_err(|e| e.to_string())?;
 
     match response {
         Response::Console { messages } => {
@@ -0,0 +1,176 @@
+//! Bounded console history kept by the server
+//!
+//! The extension only sees messages captured while its debugger is attached, and
+//! forgets them on navigation. The server merges every console snapshot passing
+//! through it into a fixed-size ring so `exec console` stays reliable regardless
+//! of when the debugger attached, without growing during long sessions.
+
+use std::collections::VecDeque;
+
+use super::protocol::ConsoleMessage;
+
+/// Default number of messages kept when `--console-buffer-size` is not given
+pub const DEFAULT_CONSOLE_BUFFER_SIZE: usize = 2000;
+
+/// Severity rank used by the `level` filter (higher is more severe)
+fn level_rank(level: &str) -> u8 {
+    match level {
+        "debug" | "verbose" => 0,
+        "log" | "info" => 1,
+        "warn" | "warning" => 2,
+        "error" | "assert" => 3,
+        _ => 1,
+    }
+}
+
+/// Ring buffer of console messages with levels and timestamps
+pub struct ConsoleHistory {
+    capacity: usize,
+    messages: VecDeque<ConsoleMessage>,
+}
+
+impl ConsoleHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            messages: VecDeque::new(),
+        }
+    }
+
+    /// Merge a console snapshot into the history.
+    ///
+    /// Snapshots overlap (the extension returns everything it still holds), so a
+    /// message already recorded with the same timestamp, level and text is skipped.
+    /// Messages without a timestamp are stamped with `now_ms`.
+    pub fn merge(&mut self, snapshot: Vec<ConsoleMessage>, now_ms: u64) {
+        for mut message in snapshot {
+            let timestamp = *message.timestamp.get_or_insert(now_ms);
+            let already_recorded = self.messages.iter().rev().any(|recorded| {
+                recorded.timestamp == Some(timestamp)
+                    && recorded.level == message.level
+                    && recorded.text == message.text
+            });
+            if already_recorded {
+                continue;
+            }
+            // Keep the ring ordered by timestamp; late backfilled messages are
+            // inserted behind newer ones instead of appended.
+            let position = self
+                .messages
+                .iter()
+                .rposition(|recorded| recorded.timestamp.unwrap_or(0) <= timestamp)
+                .map_or(0, |index| index + 1);
+            self.messages.insert(position, message);
+            while self.messages.len() > self.capacity {
+                self.messages.pop_front();
+            }
+        }
+    }
+
+    /// Messages newer than `since` and at or above `level`, oldest first
+    pub fn filtered(&self, since: Option<u64>, level: Option<&str>) -> Vec<ConsoleMessage> {
+        let minimum_rank = level.map(level_rank);
+        self.messages
+            .iter()
+            .filter(|message| match since {
+                Some(since) => message.timestamp.unwrap_or(0) > since,
+                None => true,
+            })
+            .filter(|message| match minimum_rank {
+                Some(minimum_rank) => level_rank(&message.level) >= minimum_rank,
+                None => true,
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(level: &str, text: &str, timestamp: u64) -> ConsoleMessage {
+        ConsoleMessage {
+            level: level.to_string(),
+            text: text.to_string(),
+            timestamp: Some(timestamp),
+        }
+    }
+
+    fn texts(messages: &[ConsoleMessage]) -> Vec<&str> {
+        messages.iter().map(|m| m.text.as_str()).collect()
+    }
+
+    #[test]
+    fn overlapping_snapshots_are_deduplicated() {
+        let mut history = ConsoleHistory::new(10);
+        history.merge(vec![message("log", "a", 1), message("log", "b", 2)], 0);
+        history.merge(
+            vec![
+                message("log", "a", 1),
+                message("log", "b", 2),
+                message("error", "c", 3),
+            ],
+            0,
+        );
+        assert_eq!(texts(&history.filtered(None, None)), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn oldest_messages_are_dropped_at_capacity() {
+        let mut history = ConsoleHistory::new(2);
+        history.merge(
+            vec![
+                message("log", "a", 1),
+                message("log", "b", 2),
+                message("log", "c", 3),
+            ],
+            0,
+        );
+        assert_eq!(texts(&history.filtered(None, None)), ["b", "c"]);
+    }
+
+    #[test]
+    fn logs_from_before_attachment_are_backfilled_in_order() {
+        // The debugger attached late: CDP only saw "after", the page-side
+        // log channel later backfills "before".
+        let mut history = ConsoleHistory::new(10);
+        history.merge(vec![message("error", "after", 20)], 0);
+        history.merge(
+            vec![message("info", "before", 10), message("error", "after", 20)],
+            0,
+        );
+        assert_eq!(texts(&history.filtered(None, None)), ["before", "after"]);
+    }
+
+    #[test]
+    fn since_and_level_filters() {
+        let mut history = ConsoleHistory::new(10);
+        history.merge(
+            vec![
+                message("debug", "d", 1),
+                message("log", "l", 2),
+                message("warn", "w", 3),
+                message("error", "e", 4),
+            ],
+            0,
+        );
+        assert_eq!(texts(&history.filtered(None, Some("warn"))), ["w", "e"]);
+        assert_eq!(texts(&history.filtered(Some(2), None)), ["w", "e"]);
+        assert_eq!(texts(&history.filtered(Some(3), Some("error"))), ["e"]);
+    }
+
+    #[test]
+    fn untimestamped_messages_get_receive_time() {
+        let mut history = ConsoleHistory::new(10);
+        history.merge(
+            vec![ConsoleMessage {
+                level: "log".to_string(),
+                text: "x".to_string(),
+                timestamp: None,
+            }],
+            42,
+        );
+        assert_eq!(history.filtered(None, None)[0].timestamp, Some(42));
+    }
+}
@@ -6,6 +6,7 @@
 //! - CLI sends commands to server, server forwards to extension
 //! - Extension executes in browser, returns response
 
+pub mod console_history;
 pub mod protocol;
 
 use anyhow::{Context, Result};
@@ -19,6 +20,7 @@ use tokio::net::{TcpListener, TcpStream};
 use tokio::sync::{broadcast, mpsc, RwLock};
 use tokio_tungstenite::{accept_async, tungstenite::Message};
 
+use console_history::ConsoleHistory;
 pub use protocol::*;
 
 /// Directory for saving screenshots
@@ -58,19 +60,23 @@ pub struct ServerState {
     /// Request ID counter
     next_id: RwLock<u64>,
 
+    /// Bounded console history merged from every GetConsole snapshot
+    console_history: RwLock<ConsoleHistory>,
+
     /// Broadcast channel for server shutdown (kept alive to maintain channel)
     #[allow(dead_code)]
     shutdown_tx: broadcast::Sender<()>,
 }
 
 impl ServerState {
-    pub fn new() -> (Arc<Self>, broadcast::Receiver<()>) {
+    pub fn new(console_buffer_size: usize) -> (Arc<Self>, broadcast::Receiver<()>) {
         let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
         (
             Arc::new(Self {
                 extension_tx: RwLock::new(None),
                 pending_requests: RwLock::new(HashMap::new()),
                 next_id: RwLock::new(1),
+                console_history: RwLock::new(ConsoleHistory::new(console_buffer_size)),
                 shutdown_tx,
             }),
             shutdown_rx,
@@ -79,6 +85,13 @@ impl ServerState {
 
     /// Send command to extension and wait for response
     pub async fn send_command(&self, command: Command) -> Result<Response> {
+        match command {
+            Command::GetConsole { since, level } => self.get_console(since, level).await,
+            command => self.send_command_to_extension(command).await,
+        }
+    }
+
+    async fn send_command_to_extension(&self, command: Command) -> Result<Response> {
         let extension_tx = self.extension_tx.read().await;
         let tx = extension_tx
             .as_ref()
@@ -157,6 +170,38 @@ impl ServerState {
         Ok(response)
     }
 
+    /// Refresh the console history from the extension (when connected) and
+    /// answer from the history, so messages survive late attachment and navigation.
+    async fn get_console(&self, since: Option<u64>, level: Option<String>) -> Result<Response> {
+        let has_extension = self.extension_tx.read().await.is_some();
+        if has_extension {
+            let snapshot = self
+                .send_command_to_extension(Command::GetConsole {
+                    since: None,
+                    level: None,
+                })
+                .await?;
+            match snapshot {
+                Response::Console { messages } => {
+                    let now_ms = std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .map(|d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX))
+                        .unwrap_or(0);
+                    self.console_history.write().await.merge(messages, now_ms);
+                }
+                // Extension errors (e.g. no playground tab) fall back to the history
+                other => log::debug!("GetConsole snapshot failed: {:?}", other),
+            }
+        }
+
+        let messages = self
+            .console_history
+            .read()
+            .await
+            .filtered(since, level.as_deref());
+        Ok(Response::Console { messages })
+    }
+
     /// Handle incoming response from extension
     pub async fn handle_response(&self, msg: ResponseMessage) {
         let mut pending = self.pending_requests.write().await;
@@ -191,7 +236,11 @@ impl ServerState {
 }
 
 /// Start the WebSocket server
-pub async fn start_server(port: u16, watch_path: Option<&Path>) -> Result<()> {
+pub async fn start_server(
+    port: u16,
+    watch_path: Option<&Path>,
+    console_buffer_size: usize,
+) -> Result<()> {
     let addr = format!("127.0.0.1:{}", port);
     let listener = TcpListener::bind(&addr)
         .await
@@ -200,7 +249,7 @@ pub async fn start_server(port: u16, watch_path: Option<&Path>) -> Result<()> {
     println!("WebSocket server listening on ws://{}", addr);
     println!("Waiting for Chrome extension to connect...");
 
-    let (state, mut shutdown_rx) = ServerState::new();
+    let (state, mut shutdown_rx) = ServerState::new(console_buffer_size);
 
     // Set up file watcher if path provided
     let _watcher = if let Some(path) = watch_path {
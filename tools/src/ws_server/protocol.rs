@@ -31,8 +31,15 @@ pub enum Command {
     /// Take a screenshot
     Screenshot,
 
-    /// Get console messages
-    GetConsole,
+    /// Get console messages from the server's bounded history
+    /// `since` keeps messages with a timestamp (ms since epoch) strictly greater than it;
+    /// `level` keeps messages at or above that severity (debug < log/info < warn < error).
+    GetConsole {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        since: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        level: Option<String>,
+    },
 
     /// Get preview panel text content
     GetPreviewText,
@@ -98,6 +105,9 @@ pub enum Command {
     /// Clear saved states (reset localStorage for Boon playground)
     ClearStates,
 
+    /// Emulate the page becoming hidden or visible again (fires `visibilitychange`)
+    SetPageVisibility { visible: bool },
+
     /// Select an example by name (e.g., "todo_mvc.bn", "counter.bn")
     SelectExample { name: String },
 
@@ -157,6 +167,9 @@ pub enum Command {
     /// Double-click on element by text content
     DoubleClickByText { text: String, exact: bool },
 
+    /// Right-click (`contextmenu`) on element by text content
+    RightClickByText { text: String, exact: bool },
+
     /// Hover over element by text content
     HoverByText { text: String, exact: bool },
 